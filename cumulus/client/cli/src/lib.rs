@@ -443,6 +443,10 @@ impl sc_cli::CliConfiguration for NormalizedRunCmd {
 		Ok(self.base.rpc_max_subscriptions_per_connection)
 	}
 
+	fn rpc_archive(&self) -> sc_cli::Result<sc_service::config::RpcArchiveConfig> {
+		self.base.rpc_archive()
+	}
+
 	fn transaction_pool(&self, is_dev: bool) -> sc_cli::Result<TransactionPoolOptions> {
 		self.base.transaction_pool(is_dev)
 	}
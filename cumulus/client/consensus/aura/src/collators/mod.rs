@@ -17,8 +17,134 @@
 //! Stock, pure Aura collators.
 //!
 //! This includes the [`basic`] collator, which only builds on top of the most recently
-//! included parachain block, as well as the [`lookahead`] collator, which prospectively
-//! builds on parachain blocks which have not yet been included in the relay chain.
+//! included parachain block, the [`lookahead`] collator, which prospectively
+//! builds on parachain blocks which have not yet been included in the relay chain,
+//! as well as the [`slot_based`] collator, which builds blocks on its own parachain slot
+//! timer and submits them to every core assigned to the parachain.
+
+use crate::collator::SlotClaim;
+use codec::Codec;
+use cumulus_client_consensus_common::load_abridged_host_configuration;
+use cumulus_primitives_aura::AuraUnincludedSegmentApi;
+use cumulus_primitives_core::relay_chain::Hash as PHash;
+use cumulus_relay_chain_interface::RelayChainInterface;
+use polkadot_node_subsystem::messages::{RuntimeApiMessage, RuntimeApiRequest};
+use polkadot_overseer::Handle as OverseerHandle;
+use polkadot_primitives::{CoreIndex, Id as ParaId};
+
+use futures::channel::oneshot;
+use sc_consensus_aura::standalone as aura_internal;
+use sp_api::ProvideRuntimeApi;
+use sp_consensus_aura::{AuraApi, Slot};
+use sp_core::crypto::Pair;
+use sp_keystore::KeystorePtr;
+use sp_runtime::traits::Block as BlockT;
+use sp_timestamp::Timestamp;
 
 pub mod basic;
 pub mod lookahead;
+pub mod slot_based;
+
+// Checks if we own the slot at the given block and whether there
+// is space in the unincluded segment.
+async fn can_build_upon<Block: BlockT, Client, P>(
+	slot: Slot,
+	timestamp: Timestamp,
+	parent_hash: Block::Hash,
+	included_block: Block::Hash,
+	client: &Client,
+	keystore: &KeystorePtr,
+) -> Option<SlotClaim<P::Public>>
+where
+	Client: ProvideRuntimeApi<Block>,
+	Client::Api: AuraApi<Block, P::Public> + AuraUnincludedSegmentApi<Block>,
+	P: Pair,
+	P::Public: Codec,
+	P::Signature: Codec,
+{
+	let runtime_api = client.runtime_api();
+	let authorities = runtime_api.authorities(parent_hash).ok()?;
+	let author_pub = aura_internal::claim_slot::<P>(slot, &authorities, keystore).await?;
+
+	// Here we lean on the property that building on an empty unincluded segment must always
+	// be legal. Skipping the runtime API query here allows us to seamlessly run this
+	// collator against chains which have not yet upgraded their runtime.
+	if parent_hash != included_block {
+		if !runtime_api.can_build_upon(parent_hash, included_block, slot).ok()? {
+			return None
+		}
+	}
+
+	Some(SlotClaim::unchecked::<P>(author_pub, slot, timestamp))
+}
+
+/// Reads allowed ancestry length parameter from the relay chain storage at the given relay parent.
+///
+/// Falls back to 0 in case of an error.
+async fn max_ancestry_lookback(
+	relay_parent: PHash,
+	relay_client: &impl RelayChainInterface,
+) -> usize {
+	match load_abridged_host_configuration(relay_parent, relay_client).await {
+		Ok(Some(config)) => config.async_backing_params.allowed_ancestry_len as usize,
+		Ok(None) => {
+			tracing::error!(
+				target: crate::LOG_TARGET,
+				"Active config is missing in relay chain storage",
+			);
+			0
+		},
+		Err(err) => {
+			tracing::error!(
+				target: crate::LOG_TARGET,
+				?err,
+				?relay_parent,
+				"Failed to read active config from relay chain client",
+			);
+			0
+		},
+	}
+}
+
+// Returns the indices of the cores assigned to the para at the provided relay parent.
+//
+// Falls back to an empty list in case of an error.
+async fn cores_scheduled_for_para(
+	relay_parent: PHash,
+	para_id: ParaId,
+	overseer_handle: &mut OverseerHandle,
+) -> Vec<CoreIndex> {
+	let (tx, rx) = oneshot::channel();
+	let request = RuntimeApiRequest::AvailabilityCores(tx);
+	overseer_handle
+		.send_msg(RuntimeApiMessage::Request(relay_parent, request), "AuraCollator")
+		.await;
+
+	let cores = match rx.await {
+		Ok(Ok(cores)) => cores,
+		Ok(Err(error)) => {
+			tracing::error!(
+				target: crate::LOG_TARGET,
+				?error,
+				?relay_parent,
+				"Failed to query availability cores runtime API",
+			);
+			return Vec::new()
+		},
+		Err(oneshot::Canceled) => {
+			tracing::error!(
+				target: crate::LOG_TARGET,
+				?relay_parent,
+				"Sender for availability cores runtime request dropped",
+			);
+			return Vec::new()
+		},
+	};
+
+	cores
+		.iter()
+		.enumerate()
+		.filter(|(_, core)| core.para_id() == Some(para_id))
+		.map(|(index, _)| CoreIndex(index as u32))
+		.collect()
+}
@@ -0,0 +1,505 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+use codec::{Codec, Encode};
+use cumulus_client_collator::service::ServiceInterface as CollatorServiceInterface;
+use cumulus_client_consensus_common::{
+	self as consensus_common, ParachainBlockImportMarker, ParentSearchParams,
+};
+use cumulus_client_consensus_proposer::ProposerInterface;
+use cumulus_primitives_aura::AuraUnincludedSegmentApi;
+use cumulus_primitives_core::{
+	relay_chain::{BlockId as RBlockId, Hash as PHash},
+	CollectCollationInfo, PersistedValidationData,
+};
+use cumulus_relay_chain_interface::RelayChainInterface;
+
+use polkadot_overseer::Handle as OverseerHandle;
+use polkadot_primitives::{Id as ParaId, OccupiedCoreAssumption};
+
+use futures::{channel::mpsc::UnboundedSender, prelude::*};
+use sc_client_api::{backend::AuxStore, BlockBackend, BlockOf};
+use sc_consensus::BlockImport;
+use sc_consensus_aura::standalone as aura_internal;
+use sp_api::ProvideRuntimeApi;
+use sp_application_crypto::AppPublic;
+use sp_blockchain::HeaderBackend;
+use sp_consensus_aura::{AuraApi, Slot, SlotDuration};
+use sp_core::crypto::Pair;
+use sp_inherents::CreateInherentDataProviders;
+use sp_keystore::KeystorePtr;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, Member};
+use sp_timestamp::Timestamp;
+use std::{convert::TryFrom, sync::Arc, time::Duration};
+
+use super::CollatorMessage;
+use crate::{
+	collator as collator_util,
+	collators::{can_build_upon, cores_scheduled_for_para, max_ancestry_lookback},
+};
+
+/// Parameters for [`run`].
+pub struct Params<Block: BlockT, BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS> {
+	/// Inherent data providers. Only non-consensus inherent data should be provided, i.e.
+	/// the timestamp, slot, and paras inherents should be omitted, as they are set by this
+	/// collator.
+	pub create_inherent_data_providers: CIDP,
+	/// Used to actually import blocks.
+	pub block_import: BI,
+	/// The underlying para client.
+	pub para_client: Arc<Client>,
+	/// The para client's backend, used to access the database.
+	pub para_backend: Arc<Backend>,
+	/// A handle to the relay-chain client.
+	pub relay_client: RClient,
+	/// A validation code hash provider, used to get the current validation code hash.
+	pub code_hash_provider: CHP,
+	/// The underlying keystore, which should contain Aura consensus keys.
+	pub keystore: KeystorePtr,
+	/// The para's ID.
+	pub para_id: ParaId,
+	/// A handle to the relay-chain client's "Overseer" or task orchestrator.
+	pub overseer_handle: OverseerHandle,
+	/// The underlying block proposer this should call into.
+	pub proposer: Proposer,
+	/// The generic collator service used to plug into this consensus engine.
+	pub collator_service: CS,
+	/// The amount of time to spend authoring each block.
+	pub authoring_duration: Duration,
+	/// Channel to send built blocks to the collation task.
+	pub collator_sender: UnboundedSender<CollatorMessage<Block>>,
+	/// Offset of the parachain slot timer from the slot boundaries.
+	pub slot_drift: Duration,
+}
+
+/// Returns the current duration since the unix epoch.
+fn duration_now() -> Duration {
+	use std::time::SystemTime;
+	let now = SystemTime::now();
+	now.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_else(|e| {
+		panic!("Current time {:?} is before unix epoch. Something is wrong: {:?}", now, e)
+	})
+}
+
+/// Returns the duration until the next slot, given the current (drift-adjusted) time.
+fn time_until_next_slot(now: Duration, slot_duration: Duration) -> Duration {
+	let now = now.as_millis();
+	let slot_duration = slot_duration.as_millis().max(1);
+
+	let next_slot = (now + slot_duration) / slot_duration;
+	let remaining_millis = next_slot * slot_duration - now;
+	Duration::from_millis(remaining_millis as u64)
+}
+
+/// A timer triggering at every parachain slot, offset by a configurable drift.
+struct SlotTimer<Block, Client, P> {
+	client: Arc<Client>,
+	drift: Duration,
+	_marker: std::marker::PhantomData<(Block, Box<dyn Fn(P) + Send + Sync + 'static>)>,
+}
+
+impl<Block, Client, P> SlotTimer<Block, Client, P>
+where
+	Block: BlockT,
+	Client: ProvideRuntimeApi<Block> + AuxStore + HeaderBackend<Block> + Send + Sync + 'static,
+	Client::Api: AuraApi<Block, P::Public>,
+	P: Pair,
+	P::Public: AppPublic + Member + Codec,
+	P::Signature: TryFrom<Vec<u8>> + Member + Codec,
+{
+	fn new(client: Arc<Client>, drift: Duration) -> Self {
+		Self { client, drift, _marker: Default::default() }
+	}
+
+	/// Wait until the next parachain slot begins and return the slot along with its timestamp.
+	///
+	/// The slot duration is read from the best block, so runtime changes to it are picked up
+	/// at the next slot.
+	async fn wait_until_next_slot(&self) -> Option<(Slot, Timestamp)> {
+		let slot_duration: SlotDuration = match aura_internal::slot_duration_at::<P::Public, _, _>(
+			&*self.client,
+			self.client.info().best_hash,
+		) {
+			Ok(d) => d,
+			Err(err) => {
+				tracing::error!(target: crate::LOG_TARGET, ?err, "Failed to fetch slot duration");
+				return None
+			},
+		};
+
+		let time_until_next_slot = time_until_next_slot(
+			duration_now().saturating_sub(self.drift),
+			slot_duration.as_duration(),
+		);
+		futures_timer::Delay::new(time_until_next_slot).await;
+
+		let timestamp =
+			Timestamp::new(duration_now().saturating_sub(self.drift).as_millis() as u64);
+		let slot = Slot::from_timestamp(timestamp, slot_duration);
+
+		Some((slot, timestamp))
+	}
+}
+
+/// Run the block builder task of slot-based Aura.
+///
+/// At every parachain slot, a block is built on top of the best parachain block available at
+/// the best relay-chain block, as long as there is a core assigned to the parachain left for
+/// that relay parent. Built blocks are imported, announced and sent to the collation task.
+pub fn run<Block, P, BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS>(
+	params: Params<Block, BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS>,
+) -> impl Future<Output = ()> + Send + 'static
+where
+	Block: BlockT,
+	Client: ProvideRuntimeApi<Block>
+		+ BlockOf
+		+ AuxStore
+		+ HeaderBackend<Block>
+		+ BlockBackend<Block>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api:
+		AuraApi<Block, P::Public> + CollectCollationInfo<Block> + AuraUnincludedSegmentApi<Block>,
+	Backend: sc_client_api::Backend<Block> + 'static,
+	RClient: RelayChainInterface + Clone + 'static,
+	CIDP: CreateInherentDataProviders<Block, ()> + 'static,
+	CIDP::InherentDataProviders: Send,
+	BI: BlockImport<Block> + ParachainBlockImportMarker + Send + Sync + 'static,
+	Proposer: ProposerInterface<Block> + Send + Sync + 'static,
+	CS: CollatorServiceInterface<Block> + Send + Sync + 'static,
+	CHP: consensus_common::ValidationCodeHashProvider<Block::Hash> + Send + 'static,
+	P: Pair,
+	P::Public: AppPublic + Member + Codec,
+	P::Signature: TryFrom<Vec<u8>> + Member + Codec,
+{
+	// This is an arbitrary value which is likely guaranteed to exceed any reasonable
+	// limit, as it would correspond to 10 non-included blocks.
+	//
+	// Since we only search for parent blocks which have already been imported,
+	// we can guarantee that all imported blocks respect the unincluded segment
+	// rules specified by the parachain's runtime and thus will never be too deep.
+	const PARENT_SEARCH_DEPTH: usize = 10;
+
+	async move {
+		let Params {
+			create_inherent_data_providers,
+			block_import,
+			para_client,
+			para_backend,
+			relay_client,
+			code_hash_provider,
+			keystore,
+			para_id,
+			mut overseer_handle,
+			proposer,
+			collator_service,
+			authoring_duration,
+			collator_sender,
+			slot_drift,
+		} = params;
+
+		let slot_timer = SlotTimer::<_, _, P>::new(para_client.clone(), slot_drift);
+
+		let mut collator = {
+			let params = collator_util::Params {
+				create_inherent_data_providers,
+				block_import,
+				relay_client: relay_client.clone(),
+				keystore: keystore.clone(),
+				para_id,
+				proposer,
+				collator_service,
+			};
+
+			collator_util::Collator::<Block, P, _, _, _, _, _>::new(params)
+		};
+
+		// The relay parent the last blocks were built against, along with the number of
+		// blocks built against it. Every block built against the same relay parent is
+		// assigned to the next core scheduled for the para.
+		let mut last_relay_parent: Option<(PHash, usize)> = None;
+
+		loop {
+			let (para_slot, timestamp) = match slot_timer.wait_until_next_slot().await {
+				Some(x) => x,
+				None => return,
+			};
+
+			let relay_parent = match relay_client.best_block_hash().await {
+				Ok(hash) => hash,
+				Err(err) => {
+					tracing::error!(
+						target: crate::LOG_TARGET,
+						?err,
+						"Unable to fetch best relay chain block hash",
+					);
+					continue
+				},
+			};
+
+			let relay_parent_header = match relay_client.header(RBlockId::Hash(relay_parent)).await
+			{
+				Ok(Some(header)) => header,
+				Ok(None) => continue,
+				Err(err) => {
+					tracing::error!(
+						target: crate::LOG_TARGET,
+						?err,
+						?relay_parent,
+						"Failed to fetch relay chain header",
+					);
+					continue
+				},
+			};
+
+			let blocks_built = match last_relay_parent {
+				Some((hash, count)) if hash == relay_parent => count,
+				_ => 0,
+			};
+
+			let core_indices =
+				cores_scheduled_for_para(relay_parent, para_id, &mut overseer_handle).await;
+			let core_index = match core_indices.get(blocks_built) {
+				Some(core_index) => *core_index,
+				None => {
+					tracing::trace!(
+						target: crate::LOG_TARGET,
+						?relay_parent,
+						?para_id,
+						blocks_built,
+						scheduled_cores = core_indices.len(),
+						"No core left to build on at this relay parent, skipping slot",
+					);
+
+					continue
+				},
+			};
+
+			let max_pov_size = match relay_client
+				.persisted_validation_data(relay_parent, para_id, OccupiedCoreAssumption::Included)
+				.await
+			{
+				Ok(None) => continue,
+				Ok(Some(pvd)) => pvd.max_pov_size,
+				Err(err) => {
+					tracing::error!(
+						target: crate::LOG_TARGET,
+						?err,
+						"Failed to gather information from relay-client",
+					);
+					continue
+				},
+			};
+
+			let parent_search_params = ParentSearchParams {
+				relay_parent,
+				para_id,
+				ancestry_lookback: max_ancestry_lookback(relay_parent, &relay_client).await,
+				max_depth: PARENT_SEARCH_DEPTH,
+				ignore_alternative_branches: true,
+			};
+
+			let potential_parents =
+				cumulus_client_consensus_common::find_potential_parents::<Block>(
+					parent_search_params,
+					&*para_backend,
+					&relay_client,
+				)
+				.await;
+
+			let mut potential_parents = match potential_parents {
+				Err(e) => {
+					tracing::error!(
+						target: crate::LOG_TARGET,
+						?relay_parent,
+						err = ?e,
+						"Could not fetch potential parents to build upon"
+					);
+
+					continue
+				},
+				Ok(x) => x,
+			};
+
+			let included_block = match potential_parents.iter().find(|x| x.depth == 0) {
+				None => continue, // also serves as an `is_empty` check.
+				Some(b) => b.hash,
+			};
+
+			// Sort by depth, ascending, to choose the longest chain.
+			potential_parents.sort_by_key(|a| a.depth);
+			let parent = match potential_parents.pop() {
+				None => continue,
+				Some(p) => p,
+			};
+
+			let parent_hash = parent.hash;
+			let parent_header = parent.header;
+
+			let slot_claim = match can_build_upon::<_, _, P>(
+				para_slot,
+				timestamp,
+				parent_hash,
+				included_block,
+				&*para_client,
+				&keystore,
+			)
+			.await
+			{
+				None => continue,
+				Some(c) => c,
+			};
+
+			tracing::debug!(
+				target: crate::LOG_TARGET,
+				?relay_parent,
+				?core_index,
+				slot = ?para_slot,
+				unincluded_segment_len = parent.depth,
+				"Slot claimed. Building"
+			);
+
+			let validation_data = PersistedValidationData {
+				parent_head: parent_header.encode().into(),
+				relay_parent_number: *relay_parent_header.number(),
+				relay_parent_storage_root: *relay_parent_header.state_root(),
+				max_pov_size,
+			};
+
+			let (parachain_inherent_data, other_inherent_data) = match collator
+				.create_inherent_data(
+					relay_parent,
+					&validation_data,
+					parent_hash,
+					slot_claim.timestamp(),
+				)
+				.await
+			{
+				Err(err) => {
+					tracing::error!(target: crate::LOG_TARGET, ?err);
+					continue
+				},
+				Ok(x) => x,
+			};
+
+			let validation_code_hash = match code_hash_provider.code_hash_at(parent_hash) {
+				None => {
+					tracing::error!(
+						target: crate::LOG_TARGET,
+						?parent_hash,
+						"Could not fetch validation code hash",
+					);
+					continue
+				},
+				Some(v) => v,
+			};
+
+			let parachain_candidate = match collator
+				.build_block_and_import(
+					&parent_header,
+					&slot_claim,
+					None,
+					(parachain_inherent_data, other_inherent_data),
+					authoring_duration,
+					// Set the block limit to 50% of the maximum PoV size.
+					//
+					// TODO: If we got benchmarking that includes the proof size,
+					// we should be able to use the maximum pov size.
+					(validation_data.max_pov_size / 2) as usize,
+				)
+				.await
+			{
+				Ok(Some(candidate)) => candidate,
+				Ok(None) => {
+					tracing::debug!(target: crate::LOG_TARGET, "No block proposal");
+					continue
+				},
+				Err(err) => {
+					tracing::error!(target: crate::LOG_TARGET, ?err);
+					continue
+				},
+			};
+
+			let new_block_hash = parachain_candidate.block.header().hash();
+
+			// Here we are assuming that the import logic protects against equivocations
+			// and provides sybil-resistance, as it should.
+			collator.collator_service().announce_block(new_block_hash, None);
+
+			last_relay_parent = Some((relay_parent, blocks_built + 1));
+
+			if let Err(err) = collator_sender.unbounded_send(CollatorMessage {
+				relay_parent,
+				parent_header,
+				parachain_candidate,
+				validation_code_hash,
+				core_index,
+			}) {
+				tracing::error!(
+					target: crate::LOG_TARGET,
+					?err,
+					"Unable to send block to collation task.",
+				);
+				return
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::time_until_next_slot;
+	use std::time::Duration;
+
+	#[test]
+	fn time_until_next_slot_within_a_slot() {
+		let slot_duration = Duration::from_millis(2000);
+
+		assert_eq!(
+			time_until_next_slot(Duration::from_millis(4500), slot_duration),
+			Duration::from_millis(1500),
+		);
+		assert_eq!(
+			time_until_next_slot(Duration::from_millis(5999), slot_duration),
+			Duration::from_millis(1),
+		);
+	}
+
+	#[test]
+	fn time_until_next_slot_at_a_slot_boundary_waits_a_full_slot() {
+		let slot_duration = Duration::from_millis(2000);
+
+		assert_eq!(time_until_next_slot(Duration::ZERO, slot_duration), slot_duration);
+		assert_eq!(time_until_next_slot(Duration::from_millis(6000), slot_duration), slot_duration);
+	}
+
+	#[test]
+	fn time_until_next_slot_ignores_sub_millisecond_precision() {
+		assert_eq!(
+			time_until_next_slot(Duration::from_micros(4_500_900), Duration::from_millis(2000)),
+			Duration::from_millis(1500),
+		);
+	}
+
+	#[test]
+	fn time_until_next_slot_with_zero_slot_duration() {
+		assert_eq!(
+			time_until_next_slot(Duration::from_millis(4500), Duration::ZERO),
+			Duration::from_millis(1),
+		);
+	}
+}
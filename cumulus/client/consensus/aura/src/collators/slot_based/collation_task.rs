@@ -0,0 +1,151 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+use codec::Encode;
+use cumulus_client_collator::service::ServiceInterface as CollatorServiceInterface;
+
+use polkadot_node_primitives::{MaybeCompressedPoV, SubmitCollationParams};
+use polkadot_node_subsystem::messages::CollationGenerationMessage;
+use polkadot_overseer::Handle as OverseerHandle;
+use polkadot_primitives::{CollatorPair, Id as ParaId};
+
+use futures::{channel::mpsc::UnboundedReceiver, prelude::*};
+use sp_runtime::traits::{Block as BlockT, Header};
+
+use super::CollatorMessage;
+
+/// Parameters for [`run`].
+pub struct Params<Block: BlockT, CS> {
+	/// The collator key used to sign collations before submitting to validators.
+	pub collator_key: CollatorPair,
+	/// The para's ID.
+	pub para_id: ParaId,
+	/// Whether we should reinitialize the collator config (i.e. we are transitioning to aura).
+	pub reinitialize: bool,
+	/// Collator service interface
+	pub collator_service: CS,
+	/// A handle to the relay-chain client's "Overseer" or task orchestrator.
+	pub overseer_handle: OverseerHandle,
+	/// Receiver channel for communication with the block builder task.
+	pub collator_receiver: UnboundedReceiver<CollatorMessage<Block>>,
+}
+
+/// Run the collation task of slot-based Aura.
+///
+/// Packages every block received from the block builder task into a collation and submits it
+/// to the collation generation subsystem, for the core the block was built for.
+pub fn run<Block, CS>(params: Params<Block, CS>) -> impl Future<Output = ()> + Send + 'static
+where
+	Block: BlockT,
+	CS: CollatorServiceInterface<Block> + Send + Sync + 'static,
+{
+	async move {
+		let Params {
+			collator_key,
+			para_id,
+			reinitialize,
+			collator_service,
+			mut overseer_handle,
+			mut collator_receiver,
+		} = params;
+
+		cumulus_client_collator::initialize_collator_subsystems(
+			&mut overseer_handle,
+			collator_key,
+			para_id,
+			reinitialize,
+		)
+		.await;
+
+		while let Some(message) = collator_receiver.next().await {
+			handle_collation_message(message, &collator_service, &mut overseer_handle).await;
+		}
+
+		tracing::debug!(
+			target: crate::LOG_TARGET,
+			"Block builder task stopped, stopping collation task",
+		);
+	}
+}
+
+/// Build a collation from the given block and submit it to the collation generation subsystem.
+async fn handle_collation_message<Block: BlockT>(
+	message: CollatorMessage<Block>,
+	collator_service: &impl CollatorServiceInterface<Block>,
+	overseer_handle: &mut OverseerHandle,
+) {
+	let CollatorMessage {
+		parent_header,
+		parachain_candidate,
+		validation_code_hash,
+		relay_parent,
+		core_index,
+	} = message;
+
+	let hash = parachain_candidate.block.header().hash();
+	let number = *parachain_candidate.block.header().number();
+	let (collation, block_data) =
+		match collator_service.build_collation(&parent_header, hash, parachain_candidate) {
+			Some(collation) => collation,
+			None => {
+				tracing::warn!(
+					target: crate::LOG_TARGET,
+					?hash,
+					?number,
+					?core_index,
+					"Unable to build collation.",
+				);
+				return
+			},
+		};
+
+	tracing::info!(
+		target: crate::LOG_TARGET,
+		"PoV size {{ header: {}kb, extrinsics: {}kb, storage_proof: {}kb }}",
+		block_data.header().encode().len() as f64 / 1024f64,
+		block_data.extrinsics().encode().len() as f64 / 1024f64,
+		block_data.storage_proof().encode().len() as f64 / 1024f64,
+	);
+
+	if let MaybeCompressedPoV::Compressed(ref pov) = collation.proof_of_validity {
+		tracing::info!(
+			target: crate::LOG_TARGET,
+			"Compressed PoV size: {}kb",
+			pov.block_data.0.len() as f64 / 1024f64,
+		);
+	}
+
+	tracing::debug!(
+		target: crate::LOG_TARGET,
+		?core_index,
+		?hash,
+		?number,
+		"Submitting collation for core.",
+	);
+	overseer_handle
+		.send_msg(
+			CollationGenerationMessage::SubmitCollation(SubmitCollationParams {
+				relay_parent,
+				collation,
+				parent_head: parent_header.encode().into(),
+				validation_code_hash,
+				core_index,
+				result_sender: None,
+			}),
+			"SubmitCollation",
+		)
+		.await;
+}
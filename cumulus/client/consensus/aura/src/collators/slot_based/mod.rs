@@ -0,0 +1,185 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A collator for Aura that looks ahead of the most recently included parachain block
+//! when determining what to build upon, and which decouples block building from the
+//! submission of collations to make use of elastic scaling.
+//!
+//! The collator consists of two tasks:
+//!
+//! - The block builder task, which builds a parachain block at every parachain slot. The slot timer
+//!   runs independently of the relay chain, so blocks can be produced faster than one per
+//!   relay-chain block. Each block is built against the best relay-chain block and is mapped to one
+//!   of the cores assigned to the parachain at that relay parent, so that at most one block per
+//!   assigned core is built on each relay parent.
+//! - The collation task, which packages the built blocks into collations and submits them to the
+//!   collation generation subsystem along with the index of the core they are meant for.
+//!
+//! With a single core assigned to the parachain, this behaves like the
+//! [`lookahead`](crate::collators::lookahead) collator with a slot duration matching the
+//! relay chain. With multiple cores, consecutive blocks are built on top of each other and
+//! submitted to consecutive cores, letting the parachain produce several blocks per relay-chain
+//! block.
+
+use codec::Codec;
+use cumulus_client_collator::service::ServiceInterface as CollatorServiceInterface;
+use cumulus_client_consensus_common::{
+	self as consensus_common, ParachainBlockImportMarker, ParachainCandidate,
+};
+use cumulus_client_consensus_proposer::ProposerInterface;
+use cumulus_primitives_aura::AuraUnincludedSegmentApi;
+use cumulus_primitives_core::{relay_chain::Hash as PHash, CollectCollationInfo};
+use cumulus_relay_chain_interface::RelayChainInterface;
+
+use polkadot_overseer::Handle as OverseerHandle;
+use polkadot_primitives::{CollatorPair, CoreIndex, Id as ParaId, ValidationCodeHash};
+
+use futures::{channel::mpsc, prelude::*};
+use sc_client_api::{backend::AuxStore, BlockBackend, BlockOf};
+use sc_consensus::BlockImport;
+use sp_api::ProvideRuntimeApi;
+use sp_application_crypto::AppPublic;
+use sp_blockchain::HeaderBackend;
+use sp_consensus_aura::AuraApi;
+use sp_core::crypto::Pair;
+use sp_inherents::CreateInherentDataProviders;
+use sp_keystore::KeystorePtr;
+use sp_runtime::traits::{Block as BlockT, Member};
+use std::{convert::TryFrom, sync::Arc, time::Duration};
+
+mod block_builder_task;
+mod collation_task;
+
+/// Parameters for [`run`].
+pub struct Params<BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS> {
+	/// Inherent data providers. Only non-consensus inherent data should be provided, i.e.
+	/// the timestamp, slot, and paras inherents should be omitted, as they are set by this
+	/// collator.
+	pub create_inherent_data_providers: CIDP,
+	/// Used to actually import blocks.
+	pub block_import: BI,
+	/// The underlying para client.
+	pub para_client: Arc<Client>,
+	/// The para client's backend, used to access the database.
+	pub para_backend: Arc<Backend>,
+	/// A handle to the relay-chain client.
+	pub relay_client: RClient,
+	/// A validation code hash provider, used to get the current validation code hash.
+	pub code_hash_provider: CHP,
+	/// The underlying keystore, which should contain Aura consensus keys.
+	pub keystore: KeystorePtr,
+	/// The collator key used to sign collations before submitting to validators.
+	pub collator_key: CollatorPair,
+	/// The para's ID.
+	pub para_id: ParaId,
+	/// A handle to the relay-chain client's "Overseer" or task orchestrator.
+	pub overseer_handle: OverseerHandle,
+	/// The underlying block proposer this should call into.
+	pub proposer: Proposer,
+	/// The generic collator service used to plug into this consensus engine.
+	pub collator_service: CS,
+	/// The amount of time to spend authoring each block.
+	///
+	/// This should be lower than the parachain slot duration.
+	pub authoring_duration: Duration,
+	/// Whether we should reinitialize the collator config (i.e. we are transitioning to aura).
+	pub reinitialize: bool,
+	/// Offset of the parachain slot timer from the slot boundaries.
+	///
+	/// Building starts `slot_drift` after the start of each parachain slot, which gives the
+	/// relay chain some time to import the best block we build on.
+	pub slot_drift: Duration,
+}
+
+/// Run the block builder and collation tasks of slot-based Aura.
+///
+/// Returns the futures of the collation task and of the block builder task, in this order.
+/// Both of them should be spawned, preferably as essential tasks.
+pub fn run<Block, P, BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS>(
+	params: Params<BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS>,
+) -> (impl Future<Output = ()> + Send + 'static, impl Future<Output = ()> + Send + 'static)
+where
+	Block: BlockT,
+	Client: ProvideRuntimeApi<Block>
+		+ BlockOf
+		+ AuxStore
+		+ HeaderBackend<Block>
+		+ BlockBackend<Block>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api:
+		AuraApi<Block, P::Public> + CollectCollationInfo<Block> + AuraUnincludedSegmentApi<Block>,
+	Backend: sc_client_api::Backend<Block> + 'static,
+	RClient: RelayChainInterface + Clone + 'static,
+	CIDP: CreateInherentDataProviders<Block, ()> + 'static,
+	CIDP::InherentDataProviders: Send,
+	BI: BlockImport<Block> + ParachainBlockImportMarker + Send + Sync + 'static,
+	Proposer: ProposerInterface<Block> + Send + Sync + 'static,
+	CS: CollatorServiceInterface<Block> + Clone + Send + Sync + 'static,
+	CHP: consensus_common::ValidationCodeHashProvider<Block::Hash> + Send + 'static,
+	P: Pair,
+	P::Public: AppPublic + Member + Codec,
+	P::Signature: TryFrom<Vec<u8>> + Member + Codec,
+{
+	let (collator_sender, collator_receiver) = mpsc::unbounded();
+
+	let collation_task_params = collation_task::Params {
+		collator_key: params.collator_key,
+		para_id: params.para_id,
+		reinitialize: params.reinitialize,
+		collator_service: params.collator_service.clone(),
+		overseer_handle: params.overseer_handle.clone(),
+		collator_receiver,
+	};
+
+	let block_builder_params = block_builder_task::Params {
+		create_inherent_data_providers: params.create_inherent_data_providers,
+		block_import: params.block_import,
+		para_client: params.para_client,
+		para_backend: params.para_backend,
+		relay_client: params.relay_client,
+		code_hash_provider: params.code_hash_provider,
+		keystore: params.keystore,
+		para_id: params.para_id,
+		overseer_handle: params.overseer_handle,
+		proposer: params.proposer,
+		collator_service: params.collator_service,
+		authoring_duration: params.authoring_duration,
+		slot_drift: params.slot_drift,
+		collator_sender,
+	};
+
+	let collation_task = collation_task::run::<Block, _>(collation_task_params);
+	let block_builder_task =
+		block_builder_task::run::<Block, P, _, _, _, _, _, _, _, _>(block_builder_params);
+
+	(collation_task, block_builder_task)
+}
+
+/// A block built by the block builder task, to be submitted by the collation task.
+struct CollatorMessage<Block: BlockT> {
+	/// The hash of the relay chain block the parachain block was built against.
+	relay_parent: PHash,
+	/// The header of the parent of the parachain block.
+	parent_header: Block::Header,
+	/// The parachain block along with its storage proof.
+	parachain_candidate: ParachainCandidate<Block>,
+	/// The hash of the validation code the block was built against.
+	validation_code_hash: ValidationCodeHash,
+	/// The index of the core the collation should be backed on.
+	core_index: CoreIndex,
+}
@@ -294,6 +294,7 @@ fn build_polkadot_full_node(
 			secure_validator_mode: false,
 			workers_path: None,
 			workers_names: None,
+			pvf_execution_dump: None,
 
 			overseer_gen: polkadot_service::CollatorOverseerGen,
 			overseer_message_channel_capacity_override: None,
@@ -28,10 +28,7 @@ use cumulus_relay_chain_interface::{RelayChainInterface, RelayChainResult};
 use cumulus_relay_chain_minimal_node::{
 	build_minimal_relay_chain_node_light_client, build_minimal_relay_chain_node_with_rpc,
 };
-use futures::{
-	channel::{mpsc, oneshot},
-	FutureExt, StreamExt,
-};
+use futures::{channel::mpsc, StreamExt};
 use polkadot_primitives::{CollatorPair, OccupiedCoreAssumption};
 use sc_client_api::{
 	Backend as BackendT, BlockBackend, BlockchainEvents, Finalizer, ProofProvider, UsageProvider,
@@ -41,7 +38,7 @@ use sc_consensus::{
 	BlockImport,
 };
 use sc_network::{config::SyncMode, NetworkService};
-use sc_network_sync::SyncingService;
+use sc_network_sync::{SyncingService, WarpSyncTargetProvider};
 use sc_network_transactions::TransactionsHandlerController;
 use sc_service::{Configuration, NetworkStarter, SpawnTaskHandle, TaskManager, WarpSyncParams};
 use sc_telemetry::{log, TelemetryWorkerHandle};
@@ -50,7 +47,7 @@ use sp_api::ProvideRuntimeApi;
 use sp_blockchain::{HeaderBackend, HeaderMetadata};
 use sp_core::{traits::SpawnNamed, Decode};
 use sp_runtime::traits::{Block as BlockT, BlockIdTo, Header};
-use std::{sync::Arc, time::Duration};
+use std::{marker::PhantomData, sync::Arc, time::Duration};
 
 pub use cumulus_primitives_proof_size_hostfunction::storage_proof_size;
 
@@ -405,7 +402,7 @@ pub struct BuildNetworkParams<
 	pub parachain_config: &'a Configuration,
 	pub net_config: sc_network::config::FullNetworkConfiguration,
 	pub client: Arc<Client>,
-	pub transaction_pool: Arc<sc_transaction_pool::FullPool<Block, Client>>,
+	pub transaction_pool: Arc<sc_transaction_pool::FullTransactionPool<Block, Client>>,
 	pub para_id: ParaId,
 	pub relay_chain_interface: RCInterface,
 	pub spawn_handle: SpawnTaskHandle,
@@ -455,12 +452,9 @@ where
 {
 	let warp_sync_params = match parachain_config.network.sync_mode {
 		SyncMode::Warp => {
-			let target_block = warp_sync_get::<Block, RCInterface>(
-				para_id,
-				relay_chain_interface.clone(),
-				spawn_handle.clone(),
-			);
-			Some(WarpSyncParams::WaitForTarget(target_block))
+			let target_provider =
+				RelayChainTargetProvider::<Block, _>::new(para_id, relay_chain_interface.clone());
+			Some(WarpSyncParams::WithTargetProvider(Arc::new(target_provider)))
 		},
 		_ => None,
 	};
@@ -490,50 +484,42 @@ where
 	})
 }
 
-/// Creates a new background task to wait for the relay chain to sync up and retrieve the parachain
-/// header
-fn warp_sync_get<B, RCInterface>(
+/// Warp sync target provider that retrieves the latest parachain head included in the finalized
+/// relay chain.
+pub struct RelayChainTargetProvider<Block, RCInterface> {
 	para_id: ParaId,
 	relay_chain_interface: RCInterface,
-	spawner: SpawnTaskHandle,
-) -> oneshot::Receiver<<B as BlockT>::Header>
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block, RCInterface> RelayChainTargetProvider<Block, RCInterface> {
+	/// Create a new provider for the parachain with the given id.
+	pub fn new(para_id: ParaId, relay_chain_interface: RCInterface) -> Self {
+		Self { para_id, relay_chain_interface, _phantom: PhantomData }
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block, RCInterface> WarpSyncTargetProvider<Block>
+	for RelayChainTargetProvider<Block, RCInterface>
 where
-	B: BlockT + 'static,
+	Block: BlockT + 'static,
 	RCInterface: RelayChainInterface + 'static,
 {
-	let (sender, receiver) = oneshot::channel::<B::Header>();
-	spawner.spawn(
-		"cumulus-parachain-wait-for-target-block",
-		None,
-		async move {
-			log::debug!(
-				target: LOG_TARGET_SYNC,
-				"waiting for announce block in a background task...",
-			);
-
-			let _ = wait_for_finalized_para_head::<B, _>(sender, para_id, relay_chain_interface)
-				.await
-				.map_err(|e| {
-					log::error!(
-						target: LOG_TARGET_SYNC,
-						"Unable to determine parachain target block {:?}",
-						e
-					)
-				});
-		}
-		.boxed(),
-	);
-
-	receiver
+	async fn target_block(
+		&self,
+	) -> Result<Block::Header, Box<dyn std::error::Error + Send + Sync>> {
+		log::debug!(target: LOG_TARGET_SYNC, "waiting for the relay chain to provide the target block...");
+		wait_for_finalized_para_head::<Block, _>(self.para_id, &self.relay_chain_interface).await
+	}
 }
 
 /// Waits for the relay chain to have finished syncing and then gets the parachain header that
 /// corresponds to the last finalized relay chain block.
 async fn wait_for_finalized_para_head<B, RCInterface>(
-	sender: oneshot::Sender<<B as BlockT>::Header>,
 	para_id: ParaId,
-	relay_chain_interface: RCInterface,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+	relay_chain_interface: &RCInterface,
+) -> Result<<B as BlockT>::Header, Box<dyn std::error::Error + Send + Sync>>
 where
 	B: BlockT + 'static,
 	RCInterface: RelayChainInterface + Send + 'static,
@@ -570,8 +556,7 @@ where
 				finalized_header.number(),
 				finalized_header.hash()
 			);
-			let _ = sender.send(finalized_header);
-			return Ok(())
+			return Ok(finalized_header)
 		}
 	}
 
@@ -60,8 +60,8 @@ use xcm_builder::{
 	ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
 	SignedAccountId32AsNative, SignedToAccountId32, SovereignPaidRemoteExporter,
 	SovereignSignedViaLocation, StartsWith, StartsWithExplicitGlobalConsensus, TakeWeightCredit,
-	TrailingSetTopicAsId, UsingComponents, WeightInfoBounds, WithComputedOrigin, WithUniqueTopic,
-	XcmFeeManagerFromComponents, XcmFeeToAccount,
+	TrailingSetTopicAsId, UsingComponents, WeightInfoBounds, WithComputedOrigin, WithRecording,
+	WithUniqueTopic, XcmFeeManagerFromComponents, XcmFeeToAccount,
 };
 use xcm_executor::{traits::WithOriginFilter, XcmExecutor};
 
@@ -655,15 +655,22 @@ type LocalXcmRouter = (
 
 /// The means for routing XCM messages which are not for local execution into the right message
 /// queues.
-pub type XcmRouter = WithUniqueTopic<(
-	LocalXcmRouter,
-	// Router which wraps and sends xcm to BridgeHub to be delivered to the Westend
-	// GlobalConsensus
-	ToWestendXcmRouter,
-	// Router which wraps and sends xcm to BridgeHub to be delivered to the Ethereum
-	// GlobalConsensus
-	SovereignPaidRemoteExporter<bridging::EthereumNetworkExportTable, XcmpQueue, UniversalLocation>,
-)>;
+pub type XcmRouter = WithUniqueTopic<
+	// Record sent messages so they can be reported when dry-running.
+	WithRecording<(
+		LocalXcmRouter,
+		// Router which wraps and sends xcm to BridgeHub to be delivered to the Westend
+		// GlobalConsensus
+		ToWestendXcmRouter,
+		// Router which wraps and sends xcm to BridgeHub to be delivered to the Ethereum
+		// GlobalConsensus
+		SovereignPaidRemoteExporter<
+			bridging::EthereumNetworkExportTable,
+			XcmpQueue,
+			UniversalLocation,
+		>,
+	)>,
+>;
 
 impl pallet_xcm::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
@@ -20,6 +20,12 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `cob`, CPU: `<UNKNOWN>`
 //! WASM-EXECUTION: `Compiled`, CHAIN: `Some("asset-hub-westend-dev")`, DB CACHE: 1024
+//!
+//! NOTE: the weights of `create_pool_with_kind` and of the `*_stable_swap` and `*_weighted`
+//! swaps were NOT generated by the benchmark CLI. They are hand-written placeholders derived from
+//! the benchmarked `create_pool` and constant product swaps of this file, with an estimated upper
+//! bound of the curve math of each hop added to the swaps. They must be regenerated with
+//! `benchmark pallet` before this file is merged.
 
 // Executed Command:
 // ./target/debug/polkadot-parachain
@@ -153,4 +159,80 @@ impl<T: frame_system::Config> pallet_asset_conversion::WeightInfo for WeightInfo
 			.saturating_add(T::DbWeight::get().writes(4))
 			.saturating_add(Weight::from_parts(0, 393).saturating_mul(n.into()))
 	}
+	/// PLACEHOLDER, not generated by the benchmark CLI: the weight of `create_pool` plus the
+	/// write of the `AssetConversion::PoolKinds` entry. Regenerate before merging.
+	fn create_pool_with_kind() -> Weight {
+		Weight::from_parts(1_102_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 4689))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+	/// PLACEHOLDER, not generated by the benchmark CLI: the weight of
+	/// `swap_exact_tokens_for_tokens` plus one `AssetConversion::PoolKinds` read per hop, and a
+	/// per hop bound of the curve math of 2 * 255 Newton iterations, the most the two StableSwap
+	/// loops may run, at an estimated 1µs each. Regenerate before merging.
+	/// The range of component `n` is the one of `swap_exact_tokens_for_tokens`.
+	fn swap_exact_tokens_for_tokens_stable_swap(n: u32, ) -> Weight {
+		Weight::from_parts(960_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 7404))
+			.saturating_add(Weight::from_parts(41_959_183, 0).saturating_mul(n.into()))
+			// Curve math bound per hop.
+			.saturating_add(Weight::from_parts(510_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(Weight::from_parts(0, 393).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 4299).saturating_mul(n.into()))
+	}
+	/// PLACEHOLDER, not generated by the benchmark CLI: the weight of
+	/// `swap_tokens_for_exact_tokens` plus one `AssetConversion::PoolKinds` read per hop, and a
+	/// per hop bound of the curve math of 2 * 255 Newton iterations, the most the two StableSwap
+	/// loops may run, at an estimated 1µs each. Regenerate before merging.
+	/// The range of component `n` is the one of `swap_tokens_for_exact_tokens`.
+	fn swap_tokens_for_exact_tokens_stable_swap(n: u32, ) -> Weight {
+		Weight::from_parts(956_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 7404))
+			.saturating_add(Weight::from_parts(39_193_877, 0).saturating_mul(n.into()))
+			// Curve math bound per hop.
+			.saturating_add(Weight::from_parts(510_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(Weight::from_parts(0, 393).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 4299).saturating_mul(n.into()))
+	}
+	/// PLACEHOLDER, not generated by the benchmark CLI: the weight of
+	/// `swap_exact_tokens_for_tokens` plus one `AssetConversion::PoolKinds` read per hop, and a
+	/// per hop bound of the curve math of a 64 step binary search, each raising to a power of at
+	/// most 99, at an estimated 4µs per step. Regenerate before merging.
+	/// The range of component `n` is the one of `swap_exact_tokens_for_tokens`.
+	fn swap_exact_tokens_for_tokens_weighted(n: u32, ) -> Weight {
+		Weight::from_parts(960_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 7404))
+			.saturating_add(Weight::from_parts(41_959_183, 0).saturating_mul(n.into()))
+			// Curve math bound per hop.
+			.saturating_add(Weight::from_parts(256_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(Weight::from_parts(0, 393).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 4299).saturating_mul(n.into()))
+	}
+	/// PLACEHOLDER, not generated by the benchmark CLI: the weight of
+	/// `swap_tokens_for_exact_tokens` plus one `AssetConversion::PoolKinds` read per hop, and a
+	/// per hop bound of the curve math of a 64 step binary search, each raising to a power of at
+	/// most 99, at an estimated 4µs per step. Regenerate before merging.
+	/// The range of component `n` is the one of `swap_tokens_for_exact_tokens`.
+	fn swap_tokens_for_exact_tokens_weighted(n: u32, ) -> Weight {
+		Weight::from_parts(956_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 7404))
+			.saturating_add(Weight::from_parts(39_193_877, 0).saturating_mul(n.into()))
+			// Curve math bound per hop.
+			.saturating_add(Weight::from_parts(256_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(Weight::from_parts(0, 393).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 4299).saturating_mul(n.into()))
+	}
 }
@@ -25,9 +25,25 @@ use frame_support::{
 		OnUnbalanced,
 	},
 };
-use pallet_broker::{CoreAssignment, CoreIndex, CoretimeInterface, PartsOf57600, RCBlockNumberOf};
+use pallet_broker::{
+	CoreAssignment, CoreIndex, CoretimeInterface, PartsOf57600, RCBlockNumberOf, TaskId,
+};
 use parachains_common::{AccountId, Balance, BlockNumber};
+use sp_runtime::traits::MaybeConvert;
 use xcm::latest::prelude::*;
+use xcm_executor::traits::ConvertLocation;
+
+/// Converts a task id, i.e. the id of a sibling parachain, into the sovereign account of that
+/// parachain on this chain.
+///
+/// This allows parachains to manage the auto-renewal of their cores through XCM.
+pub struct SovereignAccountOf;
+impl MaybeConvert<TaskId, AccountId> for SovereignAccountOf {
+	fn maybe_convert(id: TaskId) -> Option<AccountId> {
+		let location = Location::new(1, [Parachain(id)]);
+		xcm_config::LocationToAccountId::convert_location(&location)
+	}
+}
 
 // TODO: check AccountId import
 pub struct CreditToCollatorPot;
@@ -234,4 +250,6 @@ impl pallet_broker::Config for Runtime {
 	type PalletId = BrokerPalletId;
 	type AdminOrigin = EnsureRoot<AccountId>;
 	type PriceAdapter = pallet_broker::Linear;
+	type SovereignAccountOf = SovereignAccountOf;
+	type MaxAutoRenewals = ConstU32<100>;
 }
@@ -60,7 +60,37 @@ impl pallet_identity::Config for Runtime {
 	type PendingUsernameExpiration = ConstU32<{ 7 * DAYS }>;
 	type MaxSuffixLength = ConstU32<7>;
 	type MaxUsernameLength = ConstU32<32>;
+	type FieldVerifier = pallet_identity::SignedChallenge<Signature>;
+	type VerificationOracleOrigin = EnsureRoot<Self::AccountId>;
+	type VerificationPeriod = ConstU32<{ 365 * DAYS }>;
+	type MaxVerifiedFields = ConstU32<10>;
 	type WeightInfo = weights::pallet_identity::WeightInfo<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = IdentityBenchmarkHelper;
+}
+
+/// Creates identities with a verifiable `legal` field for the benchmarks of `pallet_identity`.
+///
+/// The field holds an sr25519 account generated in the benchmark keystore, which signs the
+/// challenge.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct IdentityBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_identity::BenchmarkHelper<IdentityInfo, Signature> for IdentityBenchmarkHelper {
+	fn verifiable_identity(
+		challenge: impl FnOnce(&u64, &[u8]) -> Vec<u8>,
+	) -> Option<(IdentityInfo, u64, Signature)> {
+		use sp_runtime::{traits::IdentifyAccount, MultiSigner};
+
+		let public = sp_io::crypto::sr25519_generate(0.into(), None);
+		let value = MultiSigner::Sr25519(public).into_account().encode();
+		let info =
+			IdentityInfo { legal: Data::Raw(value.clone().try_into().ok()?), ..Default::default() };
+		let field = IdentityField::Legal as u64;
+		let signature = sp_io::crypto::sr25519_sign(0.into(), &public, &challenge(&field, &value))?;
+		Some((info, field, Signature::Sr25519(signature)))
+	}
 }
 
 /// The fields that we use to identify the owner of an account with. Each corresponds to a field
@@ -149,6 +179,23 @@ impl IdentityInformationProvider for IdentityInfo {
 		self.fields().bits() & fields == fields
 	}
 
+	fn field_value(&self, field: &Self::FieldsIdentifier) -> Option<Vec<u8>> {
+		let field = BitFlags::<IdentityField>::from_bits(*field).ok()?.exactly_one()?;
+		let value = match field {
+			IdentityField::Display => self.display.as_bytes(),
+			IdentityField::Legal => self.legal.as_bytes(),
+			IdentityField::Web => self.web.as_bytes(),
+			IdentityField::Matrix => self.matrix.as_bytes(),
+			IdentityField::Email => self.email.as_bytes(),
+			IdentityField::PgpFingerprint => self.pgp_fingerprint.as_ref().map(|f| &f[..]),
+			IdentityField::Image => self.image.as_bytes(),
+			IdentityField::Twitter => self.twitter.as_bytes(),
+			IdentityField::GitHub => self.github.as_bytes(),
+			IdentityField::Discord => self.discord.as_bytes(),
+		};
+		value.map(|v| v.to_vec())
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	fn create_identity_info() -> Self {
 		let data = Data::Raw(vec![0; 32].try_into().unwrap());
@@ -95,6 +95,13 @@ pub struct Cli {
 	#[arg(long)]
 	pub no_hardware_benchmarks: bool,
 
+	/// EXPERIMENTAL: Use the slot-based collator, which decouples block building from the
+	/// submission of collations to support elastic scaling.
+	///
+	/// Only supported by the rococo-parachain and penpal runtimes.
+	#[arg(long)]
+	pub experimental_use_slot_based: bool,
+
 	/// Relay chain arguments
 	#[arg(raw = true)]
 	pub relaychain_args: Vec<String>,
@@ -686,7 +686,15 @@ pub fn run() -> Result<()> {
 				info!("Parachain Account: {}", parachain_account);
 				info!("Is collating: {}", if config.role.is_authority() { "yes" } else { "no" });
 
-				match config.chain_spec.runtime()? {
+				let runtime = config.chain_spec.runtime()?;
+				if cli.experimental_use_slot_based && !matches!(runtime, Penpal(_) | Default) {
+					return Err(
+						"The slot-based collator is only supported by the rococo-parachain and penpal runtimes."
+							.into(),
+					)
+				}
+
+				match runtime {
 					AssetHubPolkadot => crate::service::start_asset_hub_node::<
 						AssetHubPolkadotRuntimeApi,
 						AssetHubPolkadotAuraId,
@@ -802,6 +810,7 @@ pub fn run() -> Result<()> {
 							polkadot_config,
 							collator_options,
 							id,
+							cli.experimental_use_slot_based,
 							hwbench,
 						)
 						.await
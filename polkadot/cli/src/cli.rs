@@ -61,6 +61,24 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Replay a PVF execution dumped with `--pvf-execution-dump-path`.
+	ReplayPvfExecution(ReplayPvfExecutionCmd),
+}
+
+/// Replays a PVF execution dump through the execute worker and reports the outcome, timing and
+/// memory usage.
+#[derive(Debug, Parser)]
+pub struct ReplayPvfExecutionCmd {
+	/// Path to the dump file.
+	#[arg(value_name = "DUMP")]
+	pub dump: PathBuf,
+
+	/// Path to the directory where auxiliary worker binaries reside.
+	///
+	/// If not specified, the same directories as for `--workers-path` are searched.
+	#[arg(long, value_name = "PATH")]
+	pub workers_path: Option<PathBuf>,
 }
 
 #[allow(missing_docs)]
@@ -139,6 +157,27 @@ pub struct RunCmd {
 	/// TESTING ONLY: disable the version check between nodes and workers.
 	#[arg(long, hide = true)]
 	pub disable_worker_version_check: bool,
+
+	/// Path to the directory where failed PVF executions are dumped.
+	///
+	/// The dumps can be replayed with the `replay-pvf-execution` subcommand to debug validation
+	/// failures.
+	#[arg(long, value_name = "PATH")]
+	pub pvf_execution_dump_path: Option<PathBuf>,
+
+	/// Also dump one in every `N` successful PVF executions.
+	#[arg(
+		long,
+		value_name = "N",
+		requires = "pvf_execution_dump_path",
+		value_parser = clap::value_parser!(u32).range(1..),
+	)]
+	pub pvf_execution_dump_sample_rate: Option<u32>,
+
+	/// The maximum total size of the PVF execution dumps written since the node started, in MiB.
+	/// Every validation code is dumped at most once on failure and once on success.
+	#[arg(long, value_name = "MiB", default_value_t = 1024, requires = "pvf_execution_dump_path")]
+	pub pvf_execution_dump_max_size: u64,
 }
 
 #[allow(missing_docs)]
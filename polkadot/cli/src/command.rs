@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::cli::{Cli, Subcommand, NODE_VERSION};
+use crate::cli::{Cli, ReplayPvfExecutionCmd, Subcommand, NODE_VERSION};
 use frame_benchmarking_cli::{BenchmarkCmd, ExtrinsicFactory, SUBSTRATE_REFERENCE_HARDWARE};
 use futures::future::TryFutureExt;
 use log::info;
@@ -226,6 +226,16 @@ where
 
 	let secure_validator_mode = cli.run.base.validator && !cli.run.insecure_validator;
 
+	let pvf_execution_dump =
+		cli.run
+			.pvf_execution_dump_path
+			.clone()
+			.map(|path| service::PvfExecutionDumpConfig {
+				path,
+				sample_rate: cli.run.pvf_execution_dump_sample_rate,
+				max_total_size: cli.run.pvf_execution_dump_max_size.saturating_mul(1024 * 1024),
+			});
+
 	runner.run_node_until_exit(move |config| async move {
 		let hwbench = (!cli.run.no_hardware_benchmarks)
 			.then_some(config.database.path().map(|database_path| {
@@ -247,6 +257,7 @@ where
 				secure_validator_mode,
 				workers_path: cli.run.workers_path,
 				workers_names: None,
+				pvf_execution_dump,
 				overseer_gen,
 				overseer_message_channel_capacity_override: cli
 					.run
@@ -269,6 +280,34 @@ where
 	})
 }
 
+fn replay_pvf_execution(cmd: &ReplayPvfExecutionCmd) -> Result<()> {
+	let dump = service::PvfExecutionDump::read(&cmd.dump)
+		.map_err(|err| Error::Other(format!("Failed to read the dump: {}", err)))?;
+	// The dump may have been taken by another node version, skip the version check.
+	let (_, exec_worker_path) =
+		service::workers::determine_workers_paths(cmd.workers_path.clone(), None, None)?;
+
+	let service::PvfExecutionDump::V1(ref dump_v1) = dump;
+	println!("Code hash: {:?}", dump_v1.code_hash);
+	println!("Dumped by node version: {}", dump_v1.node_version.as_deref().unwrap_or("unknown"));
+	println!("Dumped outcome: {}", dump_v1.outcome);
+
+	let runtime = sc_cli::build_runtime()
+		.map_err(|err| Error::Other(format!("Failed to build the runtime: {}", err)))?;
+	let report = runtime.block_on(service::replay_execution_dump(dump, &exec_worker_path))?;
+
+	println!("Replayed outcome: {}", report.outcome);
+	println!("Wall clock time: {:?}", report.wall_clock_time);
+	if let Some(cpu_time) = report.cpu_time {
+		println!("CPU time: {:?}", cpu_time);
+	}
+	if let Some(max_rss_kib) = report.max_rss_kib {
+		println!("Peak memory usage: {} KiB", max_rss_kib);
+	}
+
+	Ok(())
+}
+
 /// Parses polkadot specific CLI arguments and run the service.
 pub fn run() -> Result<()> {
 	let cli: Cli = Cli::from_args();
@@ -485,6 +524,7 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			Ok(runner.sync_run(|config| cmd.run::<service::Block>(&config))?)
 		},
+		Some(Subcommand::ReplayPvfExecution(cmd)) => replay_pvf_execution(cmd),
 	}?;
 
 	#[cfg(feature = "pyroscope")]
@@ -23,6 +23,9 @@
 //! Each of n validators stores their piece of data. We assume `n = 3f + k`, `0 < k ≤ 3`.
 //! f is the maximum number of faulty validators in the system.
 //! The data is coded so any f+1 chunks can be used to reconstruct the full data.
+//!
+//! The encoding is systematic: the first `k` chunks (see [`systematic_recovery_threshold`]) hold
+//! the original data, so having all of them is enough to recover it without decoding.
 
 use parity_scale_codec::{Decode, Encode};
 use polkadot_node_primitives::{AvailableData, Proof};
@@ -110,6 +113,14 @@ pub const fn recovery_threshold(n_validators: usize) -> Result<usize, Error> {
 	Ok(needed + 1)
 }
 
+/// Obtain the threshold of systematic chunks that should be enough to recover the data.
+///
+/// If the regular `recovery_threshold` is a power of two, then it returns the same value.
+/// Otherwise, it returns the next lower power of two.
+pub fn systematic_recovery_threshold(n_validators: usize) -> Result<usize, Error> {
+	code_params(n_validators).map(|params| params.k())
+}
+
 fn code_params(n_validators: usize) -> Result<CodeParams, Error> {
 	// we need to be able to reconstruct from 1/3 - eps
 
@@ -167,6 +178,41 @@ where
 	reconstruct(n_validators, chunks)
 }
 
+/// Reconstruct the v1 available data from the set of systematic chunks.
+///
+/// Provide a vector containing chunk data. If too few chunks are provided, recovery is not
+/// possible.
+pub fn reconstruct_from_systematic_v1(
+	n_validators: usize,
+	chunks: Vec<Vec<u8>>,
+) -> Result<AvailableData, Error> {
+	reconstruct_from_systematic(n_validators, chunks)
+}
+
+/// Reconstruct the available data from the set of systematic chunks.
+///
+/// Provide a vector containing the first k chunks in order. If too few chunks are provided,
+/// recovery is not possible.
+pub fn reconstruct_from_systematic<T: Decode>(
+	n_validators: usize,
+	chunks: Vec<Vec<u8>>,
+) -> Result<T, Error> {
+	let code_params = code_params(n_validators)?;
+	let k = code_params.k();
+
+	for chunk_data in chunks.iter().take(k) {
+		if chunk_data.len() % 2 != 0 {
+			return Err(Error::UnevenLength)
+		}
+	}
+
+	let payload_bytes = code_params
+		.make_encoder()
+		.reconstruct_from_systematic(chunks.into_iter().take(k).map(WrappedShard::new).collect())?;
+
+	Decode::decode(&mut &payload_bytes[..]).map_err(|_| Error::BadPayload)
+}
+
 /// Reconstruct decodable data from a set of chunks.
 ///
 /// Provide an iterator containing chunk data and the corresponding index.
@@ -318,6 +364,55 @@ mod tests {
 		assert_eq!(reconstructed, available_data);
 	}
 
+	#[test]
+	fn round_trip_systematic_works() {
+		let pov = PoV { block_data: BlockData((0..255).collect()) };
+
+		let available_data = AvailableData { pov: pov.into(), validation_data: Default::default() };
+
+		for n_validators in [2, 3, 4, 10, 100, 1000] {
+			let kpow2 = systematic_recovery_threshold(n_validators).unwrap();
+			let chunks = obtain_chunks(n_validators, &available_data).unwrap();
+
+			assert_eq!(
+				reconstruct_from_systematic_v1(
+					n_validators,
+					chunks.into_iter().take(kpow2).collect()
+				)
+				.unwrap(),
+				available_data
+			);
+		}
+	}
+
+	#[test]
+	fn reconstruct_from_systematic_fails_with_too_few_chunks() {
+		let pov = PoV { block_data: BlockData((0..255).collect()) };
+
+		let available_data = AvailableData { pov: pov.into(), validation_data: Default::default() };
+		let n_validators = 100;
+		let kpow2 = systematic_recovery_threshold(n_validators).unwrap();
+		let chunks = obtain_chunks(n_validators, &available_data).unwrap();
+
+		assert!(reconstruct_from_systematic_v1(
+			n_validators,
+			chunks.into_iter().take(kpow2 - 1).collect()
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn systematic_threshold_is_power_of_two_not_above_recovery_threshold() {
+		for n_validators in [2, 3, 4, 10, 100, 300, 1000] {
+			let threshold = recovery_threshold(n_validators).unwrap();
+			let systematic_threshold = systematic_recovery_threshold(n_validators).unwrap();
+
+			assert!(systematic_threshold.is_power_of_two());
+			assert!(systematic_threshold <= threshold);
+			assert!(systematic_threshold * 2 > threshold);
+		}
+	}
+
 	#[test]
 	fn reconstruct_does_not_panic_on_low_validator_count() {
 		let reconstructed = reconstruct_v1(1, [].iter().cloned());
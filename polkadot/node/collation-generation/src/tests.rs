@@ -551,6 +551,7 @@ fn submit_collation_is_no_op_before_initialization() {
 					parent_head: vec![1, 2, 3].into(),
 					validation_code_hash: Hash::repeat_byte(1).into(),
 					result_sender: None,
+					core_index: CoreIndex(0),
 				}),
 			})
 			.await;
@@ -587,6 +588,7 @@ fn submit_collation_leads_to_distribution() {
 					parent_head: vec![1, 2, 3].into(),
 					validation_code_hash,
 					result_sender: None,
+					core_index: CoreIndex(1),
 				}),
 			})
 			.await;
@@ -622,9 +624,12 @@ fn submit_collation_leads_to_distribution() {
 			AllMessages::CollatorProtocol(CollatorProtocolMessage::DistributeCollation(
 				ccr,
 				parent_head_data_hash,
-				..
+				_pov,
+				_result_sender,
+				core_index,
 			)) => {
 				assert_eq!(parent_head_data_hash, parent_head.hash());
+				assert_eq!(core_index, CoreIndex(1));
 				assert_eq!(ccr.descriptor().persisted_validation_data_hash, expected_pvd.hash());
 				assert_eq!(ccr.descriptor().para_head, dummy_head_data().hash());
 				assert_eq!(ccr.descriptor().validation_code_hash, validation_code_hash);
@@ -55,9 +55,9 @@ use polkadot_node_subsystem_util::{
 };
 use polkadot_primitives::{
 	vstaging::{ApprovalVoteMultipleCandidates, ApprovalVotingParams},
-	BlockNumber, CandidateHash, CandidateIndex, CandidateReceipt, DisputeStatement, ExecutorParams,
-	GroupIndex, Hash, PvfExecKind, SessionIndex, SessionInfo, ValidDisputeStatementKind,
-	ValidatorId, ValidatorIndex, ValidatorPair, ValidatorSignature,
+	BlockNumber, CandidateHash, CandidateIndex, CandidateReceipt, CoreIndex, DisputeStatement,
+	ExecutorParams, GroupIndex, Hash, PvfExecKind, SessionIndex, SessionInfo,
+	ValidDisputeStatementKind, ValidatorId, ValidatorIndex, ValidatorPair, ValidatorSignature,
 };
 use sc_keystore::LocalKeystore;
 use sp_application_crypto::Pair;
@@ -92,7 +92,7 @@ use time::{slot_number_to_tick, Clock, ClockExt, DelayedApprovalTimer, SystemClo
 mod approval_checking;
 pub mod approval_db;
 mod backend;
-mod criteria;
+pub mod criteria;
 mod import;
 mod ops;
 mod persisted_entries;
@@ -897,6 +897,7 @@ enum Action {
 		executor_params: ExecutorParams,
 		candidate: CandidateReceipt,
 		backing_group: GroupIndex,
+		core_index: Option<CoreIndex>,
 		distribute_assignment: bool,
 	},
 	NoteApprovedInChainSelection(Hash),
@@ -1156,6 +1157,7 @@ async fn handle_actions<Context>(
 				executor_params,
 				candidate,
 				backing_group,
+				core_index,
 				distribute_assignment,
 			} => {
 				// Don't launch approval work if the node is syncing.
@@ -1212,6 +1214,7 @@ async fn handle_actions<Context>(
 										validator_index,
 										block_hash,
 										backing_group,
+										core_index,
 										executor_params,
 										&launch_approval_span,
 									)
@@ -2960,6 +2963,11 @@ async fn process_wakeup<Context>(
 						true
 					};
 					db.write_block_entry(block_entry.clone());
+					let core_index = block_entry
+						.candidates()
+						.iter()
+						.find(|(_, hash)| *hash == candidate_hash)
+						.map(|(core_index, _)| *core_index);
 
 					actions.push(Action::LaunchApproval {
 						claimed_candidate_indices,
@@ -2971,6 +2979,7 @@ async fn process_wakeup<Context>(
 						executor_params: executor_params.clone(),
 						candidate: candidate_receipt,
 						backing_group,
+						core_index,
 						distribute_assignment,
 					});
 				},
@@ -3029,6 +3038,7 @@ async fn launch_approval<Context>(
 	validator_index: ValidatorIndex,
 	block_hash: Hash,
 	backing_group: GroupIndex,
+	core_index: Option<CoreIndex>,
 	executor_params: ExecutorParams,
 	span: &jaeger::Span,
 ) -> SubsystemResult<RemoteHandle<ApprovalState>> {
@@ -3076,6 +3086,7 @@ async fn launch_approval<Context>(
 		candidate.clone(),
 		session_index,
 		Some(backing_group),
+		core_index,
 		a_tx,
 	))
 	.await;
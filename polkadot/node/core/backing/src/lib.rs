@@ -105,8 +105,10 @@ use polkadot_node_subsystem_util::{
 	Validator,
 };
 use polkadot_primitives::{
-	vstaging::{node_features::FeatureIndex, NodeFeatures},
-	BackedCandidate, CandidateCommitments, CandidateHash, CandidateReceipt,
+	vstaging::{
+		node_features::FeatureIndex, CandidateDescriptorV2, CandidateReceiptError, NodeFeatures,
+	},
+	BackedCandidate, CandidateCommitments, CandidateDescriptor, CandidateHash, CandidateReceipt,
 	CommittedCandidateReceipt, CoreIndex, CoreState, ExecutorParams, GroupIndex, GroupRotationInfo,
 	Hash, Id as ParaId, IndexedVec, PersistedValidationData, PvfExecKind, SessionIndex,
 	SigningContext, ValidationCode, ValidatorId, ValidatorIndex, ValidatorSignature,
@@ -232,6 +234,11 @@ struct PerRelayParentState {
 	/// If true, we're appending extra bits in the BackedCandidate validator indices bitfield,
 	/// which represent the assigned core index. True if ElasticScalingMVP is enabled.
 	inject_core_index: bool,
+	/// If true, v2 candidate descriptors are accepted and the core index and session index
+	/// they commit to are checked. True if `CandidateReceiptV2` is enabled.
+	v2_receipts: bool,
+	/// The session index of the child of the relay parent.
+	session_index: SessionIndex,
 	/// The core states for all cores.
 	cores: Vec<CoreState>,
 	/// The validator index -> group mapping at this relay parent.
@@ -1125,14 +1132,19 @@ async fn construct_per_relay_parent_state<Context>(
 
 	let session_index = try_runtime_api!(session_index);
 
-	let inject_core_index = request_node_features(parent, session_index, ctx.sender())
+	let node_features = request_node_features(parent, session_index, ctx.sender())
 		.await?
-		.unwrap_or(NodeFeatures::EMPTY)
+		.unwrap_or(NodeFeatures::EMPTY);
+	let inject_core_index = node_features
 		.get(FeatureIndex::ElasticScalingMVP as usize)
 		.map(|b| *b)
 		.unwrap_or(false);
+	let v2_receipts = node_features
+		.get(FeatureIndex::CandidateReceiptV2 as usize)
+		.map(|b| *b)
+		.unwrap_or(false);
 
-	gum::debug!(target: LOG_TARGET, inject_core_index, ?parent, "New state");
+	gum::debug!(target: LOG_TARGET, inject_core_index, v2_receipts, ?parent, "New state");
 
 	let validators: Vec<_> = try_runtime_api!(validators);
 	let (validator_groups, group_rotation_info) = try_runtime_api!(groups);
@@ -1240,12 +1252,29 @@ async fn construct_per_relay_parent_state<Context>(
 		fallbacks: HashMap::new(),
 		minimum_backing_votes,
 		inject_core_index,
+		v2_receipts,
+		session_index,
 		cores,
 		validator_to_group: validator_to_group.clone(),
 		group_rotation_info,
 	}))
 }
 
+/// Checks that a v2 candidate descriptor commits to the given core and to the session of the
+/// relay parent. v1 descriptors are always accepted.
+fn check_descriptor_core_index(
+	rp_state: &PerRelayParentState,
+	descriptor: &CandidateDescriptor,
+	core_index: CoreIndex,
+) -> Result<(), CandidateReceiptError> {
+	if !rp_state.v2_receipts {
+		return Ok(())
+	}
+
+	CandidateDescriptorV2::from(descriptor.clone())
+		.check_core_index_and_session(&[core_index], rp_state.session_index)
+}
+
 enum SecondingAllowed {
 	No,
 	Yes(Vec<(Hash, Vec<usize>)>),
@@ -1995,6 +2024,31 @@ async fn maybe_validate_and_import<Context>(
 		return Ok(())
 	}
 
+	if let StatementWithPVD::Seconded(candidate, _) = statement.payload() {
+		let core_index = core_index_from_statement(
+			&rp_state.validator_to_group,
+			&rp_state.group_rotation_info,
+			&rp_state.cores,
+			&statement,
+		);
+
+		if let Some(core_index) = core_index {
+			if let Err(err) =
+				check_descriptor_core_index(rp_state, &candidate.descriptor, core_index)
+			{
+				gum::debug!(
+					target: LOG_TARGET,
+					sender_validator_idx = ?statement.validator_index(),
+					candidate_hash = ?candidate.hash(),
+					?core_index,
+					?err,
+					"Not importing statement because of an invalid candidate descriptor"
+				);
+				return Ok(())
+			}
+		}
+	}
+
 	let res = import_statement(ctx, rp_state, &mut state.per_candidate, &statement).await;
 
 	// if we get an Error::RejectedByProspectiveParachains,
@@ -2185,6 +2239,21 @@ async fn handle_second_message<Context>(
 		"Current assignments vs collation",
 	);
 
+	if let Some(core_index) = rp_state.assigned_core {
+		if let Err(err) = check_descriptor_core_index(rp_state, candidate.descriptor(), core_index)
+		{
+			gum::debug!(
+				target: LOG_TARGET,
+				?candidate_hash,
+				?core_index,
+				?err,
+				"Subsystem asked to second a candidate with an invalid descriptor",
+			);
+
+			return Ok(())
+		}
+	}
+
 	// If the message is a `CandidateBackingMessage::Second`, sign and dispatch a
 	// Seconded statement only if we have not signed a Valid statement for the requested candidate.
 	//
@@ -39,7 +39,9 @@ use polkadot_node_subsystem::{
 	overseer, FromOrchestra, OverseerSignal, SpawnedSubsystem, SubsystemError, SubsystemResult,
 	SubsystemSender,
 };
-use polkadot_node_subsystem_util::executor_params_at_relay_parent;
+use polkadot_node_subsystem_util::{
+	executor_params_at_relay_parent, runtime::request_node_features,
+};
 use polkadot_parachain_primitives::primitives::{
 	ValidationParams, ValidationResult as WasmValidationResult,
 };
@@ -48,9 +50,13 @@ use polkadot_primitives::{
 		DEFAULT_APPROVAL_EXECUTION_TIMEOUT, DEFAULT_BACKING_EXECUTION_TIMEOUT,
 		DEFAULT_LENIENT_PREPARATION_TIMEOUT, DEFAULT_PRECHECK_PREPARATION_TIMEOUT,
 	},
-	CandidateCommitments, CandidateDescriptor, CandidateReceipt, ExecutorParams, Hash,
-	OccupiedCoreAssumption, PersistedValidationData, PvfExecKind, PvfPrepKind, ValidationCode,
-	ValidationCodeHash,
+	vstaging::{
+		node_features::FeatureIndex, CandidateDescriptorV2, CandidateDescriptorVersion,
+		CandidateReceiptError, NodeFeatures,
+	},
+	CandidateCommitments, CandidateDescriptor, CandidateReceipt, CoreIndex, CoreState,
+	ExecutorParams, Hash, Id as ParaId, OccupiedCoreAssumption, PersistedValidationData,
+	PvfExecKind, PvfPrepKind, ValidationCode, ValidationCodeHash,
 };
 
 use parity_scale_codec::Encode;
@@ -100,6 +106,8 @@ pub struct Config {
 	pub prep_worker_path: PathBuf,
 	/// Path to the execution worker binary
 	pub exec_worker_path: PathBuf,
+	/// If set, failed (and optionally sampled) PVF executions are dumped to disk for debugging.
+	pub pvf_execution_dump: Option<polkadot_node_core_pvf::ExecutionDumpConfig>,
 }
 
 /// The candidate validation subsystem.
@@ -181,17 +189,29 @@ where
 			..
 		} => async move {
 			let _timer = metrics.time_validate_from_exhaustive();
-			let res = validate_candidate_exhaustive(
-				validation_host,
-				validation_data,
-				validation_code,
-				candidate_receipt,
-				pov,
-				executor_params,
+			let res = match check_descriptor_version(
+				&mut sender,
+				&candidate_receipt.descriptor,
 				exec_kind,
-				&metrics,
 			)
-			.await;
+			.await
+			{
+				Ok(Ok(v2_receipts)) =>
+					validate_candidate_exhaustive(
+						validation_host,
+						validation_data,
+						validation_code,
+						candidate_receipt,
+						pov,
+						executor_params,
+						exec_kind,
+						v2_receipts,
+						&metrics,
+					)
+					.await,
+				Ok(Err(e)) => Ok(ValidationResult::Invalid(e)),
+				Err(e) => Err(e),
+			};
 
 			metrics.on_validation_event(&res);
 			let _ = response_sender.send(res);
@@ -224,19 +244,19 @@ async fn run<Context>(
 		secure_validator_mode,
 		prep_worker_path,
 		exec_worker_path,
+		pvf_execution_dump,
 	}: Config,
 ) -> SubsystemResult<()> {
-	let (validation_host, task) = polkadot_node_core_pvf::start(
-		polkadot_node_core_pvf::Config::new(
-			artifacts_cache_path,
-			node_version,
-			secure_validator_mode,
-			prep_worker_path,
-			exec_worker_path,
-		),
-		pvf_metrics,
-	)
-	.await?;
+	let mut pvf_config = polkadot_node_core_pvf::Config::new(
+		artifacts_cache_path,
+		node_version,
+		secure_validator_mode,
+		prep_worker_path,
+		exec_worker_path,
+	);
+	pvf_config.execution_dump = pvf_execution_dump;
+
+	let (validation_host, task) = polkadot_node_core_pvf::start(pvf_config, pvf_metrics).await?;
 	ctx.spawn_blocking("pvf-validation-host", task.boxed())?;
 
 	let mut tasks = FuturesUnordered::new();
@@ -542,6 +562,12 @@ where
 			None => return Ok(ValidationResult::Invalid(InvalidCandidate::BadParent)),
 		};
 
+	let v2_receipts =
+		match check_descriptor_version(sender, &candidate_receipt.descriptor, exec_kind).await? {
+			Ok(v2_receipts) => v2_receipts,
+			Err(e) => return Ok(ValidationResult::Invalid(e)),
+		};
+
 	let validation_result = validate_candidate_exhaustive(
 		validation_host,
 		validation_data,
@@ -550,6 +576,7 @@ where
 		pov,
 		executor_params,
 		exec_kind,
+		v2_receipts,
 		metrics,
 	)
 	.await;
@@ -586,6 +613,7 @@ async fn validate_candidate_exhaustive(
 	pov: Arc<PoV>,
 	executor_params: ExecutorParams,
 	exec_kind: PvfExecKind,
+	v2_receipts: bool,
 	metrics: &Metrics,
 ) -> Result<ValidationResult, ValidationFailed> {
 	let _timer = metrics.time_validate_candidate_exhaustive();
@@ -604,6 +632,7 @@ async fn validate_candidate_exhaustive(
 		persisted_validation_data.max_pov_size,
 		&pov,
 		&validation_code_hash,
+		v2_receipts,
 	) {
 		gum::info!(target: LOG_TARGET, ?para_id, "Invalid candidate (basic checks)");
 		return Ok(ValidationResult::Invalid(e))
@@ -887,13 +916,94 @@ impl ValidationBackend for ValidationHost {
 	}
 }
 
+/// Checks the core index and session index committed to by v2 candidate descriptors.
+///
+/// Returns whether v2 candidate receipts are enabled at the relay parent of the candidate, or
+/// the reason why the candidate is invalid. The committed core is only checked against the cores
+/// assigned to the para when backing. Candidates being approved or disputed have been included,
+/// which means their core has already been checked on-chain.
+async fn check_descriptor_version<Sender>(
+	sender: &mut Sender,
+	descriptor: &CandidateDescriptor,
+	exec_kind: PvfExecKind,
+) -> Result<Result<bool, InvalidCandidate>, ValidationFailed>
+where
+	Sender: SubsystemSender<RuntimeApiMessage>,
+{
+	let relay_parent = descriptor.relay_parent;
+
+	let (tx, rx) = oneshot::channel();
+	let session_index =
+		runtime_api_request(sender, relay_parent, RuntimeApiRequest::SessionIndexForChild(tx), rx)
+			.await
+			.map_err(|_| ValidationFailed("Session index: Bad request".into()))?;
+
+	let v2_receipts = request_node_features(relay_parent, session_index, sender)
+		.await
+		.map_err(|e| ValidationFailed(format!("Node features: {:?}", e)))?
+		.unwrap_or(NodeFeatures::EMPTY)
+		.get(FeatureIndex::CandidateReceiptV2 as usize)
+		.map(|b| *b)
+		.unwrap_or(false);
+
+	if !v2_receipts {
+		return Ok(Ok(false))
+	}
+
+	let descriptor = CandidateDescriptorV2::from(descriptor.clone());
+	let assigned_cores = match (descriptor.version(), exec_kind) {
+		(CandidateDescriptorVersion::V2, PvfExecKind::Backing) => {
+			let (tx, rx) = oneshot::channel();
+			let cores = runtime_api_request(
+				sender,
+				relay_parent,
+				RuntimeApiRequest::AvailabilityCores(tx),
+				rx,
+			)
+			.await
+			.map_err(|_| ValidationFailed("Availability cores: Bad request".into()))?;
+
+			cores_assigned_to_para(&cores, descriptor.para_id())
+		},
+		_ => descriptor.core_index().into_iter().collect(),
+	};
+
+	match descriptor.check_core_index_and_session(&assigned_cores, session_index) {
+		Ok(()) => Ok(Ok(true)),
+		Err(CandidateReceiptError::UnknownVersion(_)) =>
+			Ok(Err(InvalidCandidate::UnknownDescriptorVersion)),
+		Err(CandidateReceiptError::NoAssignment | CandidateReceiptError::CoreIndexMismatch) =>
+			Ok(Err(InvalidCandidate::InvalidCoreIndex)),
+		Err(CandidateReceiptError::SessionIndexMismatch) =>
+			Ok(Err(InvalidCandidate::InvalidSessionIndex)),
+	}
+}
+
+/// Returns the indices of the cores a candidate of the given para can be backed on.
+fn cores_assigned_to_para(cores: &[CoreState], para_id: ParaId) -> Vec<CoreIndex> {
+	cores
+		.iter()
+		.enumerate()
+		.filter(|(_, core)| match core {
+			CoreState::Scheduled(scheduled) => scheduled.para_id == para_id,
+			CoreState::Occupied(occupied) =>
+				occupied.next_up_on_available.as_ref().map(|next| next.para_id) == Some(para_id),
+			CoreState::Free => false,
+		})
+		.map(|(index, _)| CoreIndex(index as u32))
+		.collect()
+}
+
 /// Does basic checks of a candidate. Provide the encoded PoV-block. Returns `Ok` if basic checks
 /// are passed, `Err` otherwise.
+///
+/// If `v2_receipts` is set, v2 candidate descriptors are accepted without a collator signature.
 fn perform_basic_checks(
 	candidate: &CandidateDescriptor,
 	max_pov_size: u32,
 	pov: &PoV,
 	validation_code_hash: &ValidationCodeHash,
+	v2_receipts: bool,
 ) -> Result<(), InvalidCandidate> {
 	let pov_hash = pov.hash();
 
@@ -910,8 +1020,14 @@ fn perform_basic_checks(
 		return Err(InvalidCandidate::CodeHashMismatch)
 	}
 
-	if let Err(()) = candidate.check_collator_signature() {
-		return Err(InvalidCandidate::BadSignature)
+	let is_v2 = v2_receipts &&
+		CandidateDescriptorV2::from(candidate.clone()).version() ==
+			CandidateDescriptorVersion::V2;
+
+	if !is_v2 {
+		if let Err(()) = candidate.check_collator_signature() {
+			return Err(InvalidCandidate::BadSignature)
+		}
 	}
 
 	Ok(())
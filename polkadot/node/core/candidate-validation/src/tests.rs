@@ -406,6 +406,7 @@ fn candidate_validation_ok_is_ok() {
 		validation_data.max_pov_size,
 		&pov,
 		&validation_code.hash(),
+		false,
 	);
 	assert!(check.is_ok());
 
@@ -437,6 +438,7 @@ fn candidate_validation_ok_is_ok() {
 		Arc::new(pov),
 		ExecutorParams::default(),
 		PvfExecKind::Backing,
+		false,
 		&Default::default(),
 	))
 	.unwrap();
@@ -474,6 +476,7 @@ fn candidate_validation_bad_return_is_invalid() {
 		validation_data.max_pov_size,
 		&pov,
 		&validation_code.hash(),
+		false,
 	);
 	assert!(check.is_ok());
 
@@ -489,6 +492,7 @@ fn candidate_validation_bad_return_is_invalid() {
 		Arc::new(pov),
 		ExecutorParams::default(),
 		PvfExecKind::Backing,
+		false,
 		&Default::default(),
 	))
 	.unwrap();
@@ -518,6 +522,7 @@ fn perform_basic_checks_on_valid_candidate(
 		validation_data.max_pov_size,
 		&pov,
 		&validation_code.hash(),
+		false,
 	);
 	assert!(check.is_ok());
 	descriptor
@@ -570,6 +575,7 @@ fn candidate_validation_one_ambiguous_error_is_valid() {
 		Arc::new(pov),
 		ExecutorParams::default(),
 		PvfExecKind::Approval,
+		false,
 		&Default::default(),
 	))
 	.unwrap();
@@ -611,6 +617,7 @@ fn candidate_validation_multiple_ambiguous_errors_is_invalid() {
 		Arc::new(pov),
 		ExecutorParams::default(),
 		PvfExecKind::Approval,
+		false,
 		&Default::default(),
 	))
 	.unwrap();
@@ -712,6 +719,7 @@ fn candidate_validation_retry_on_error_helper(
 		validation_data.max_pov_size,
 		&pov,
 		&validation_code.hash(),
+		false,
 	);
 	assert!(check.is_ok());
 
@@ -725,6 +733,7 @@ fn candidate_validation_retry_on_error_helper(
 		Arc::new(pov),
 		ExecutorParams::default(),
 		exec_kind,
+		false,
 		&Default::default(),
 	))
 }
@@ -752,6 +761,7 @@ fn candidate_validation_timeout_is_internal_error() {
 		validation_data.max_pov_size,
 		&pov,
 		&validation_code.hash(),
+		false,
 	);
 	assert!(check.is_ok());
 
@@ -767,6 +777,7 @@ fn candidate_validation_timeout_is_internal_error() {
 		Arc::new(pov),
 		ExecutorParams::default(),
 		PvfExecKind::Backing,
+		false,
 		&Default::default(),
 	));
 
@@ -812,6 +823,7 @@ fn candidate_validation_commitment_hash_mismatch_is_invalid() {
 		Arc::new(pov),
 		ExecutorParams::default(),
 		PvfExecKind::Backing,
+		false,
 		&Default::default(),
 	))
 	.unwrap();
@@ -843,6 +855,7 @@ fn candidate_validation_code_mismatch_is_invalid() {
 		validation_data.max_pov_size,
 		&pov,
 		&validation_code.hash(),
+		false,
 	);
 	assert_matches!(check, Err(InvalidCandidate::CodeHashMismatch));
 
@@ -861,6 +874,7 @@ fn candidate_validation_code_mismatch_is_invalid() {
 		Arc::new(pov),
 		ExecutorParams::default(),
 		PvfExecKind::Backing,
+		false,
 		&Default::default(),
 	))
 	.unwrap();
@@ -918,6 +932,7 @@ fn compressed_code_works() {
 		Arc::new(pov),
 		ExecutorParams::default(),
 		PvfExecKind::Backing,
+		false,
 		&Default::default(),
 	));
 
@@ -969,6 +984,7 @@ fn code_decompression_failure_is_error() {
 		Arc::new(pov),
 		ExecutorParams::default(),
 		PvfExecKind::Backing,
+		false,
 		&Default::default(),
 	));
 
@@ -1021,6 +1037,7 @@ fn pov_decompression_failure_is_invalid() {
 		Arc::new(pov),
 		ExecutorParams::default(),
 		PvfExecKind::Backing,
+		false,
 		&Default::default(),
 	));
 
@@ -0,0 +1,428 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Snapshots of execution jobs, used to reproduce validation failures outside of the node.
+//!
+//! When enabled through [`ExecutionDumpConfig`], the execute queue writes the first failed job of
+//! every validation code (and optionally a sample of the successful ones) to disk, up to a maximum
+//! total size. A dump contains everything needed to run the job again: the compiled artifact, the
+//! validation parameters, the executor parameters and the execution timeout. A dump can then be
+//! replayed through an execute worker with [`replay_execution_dump`].
+
+use super::worker_interface::{self, Outcome};
+use crate::{
+	artifacts::{ArtifactId, ArtifactPathId},
+	SecurityStatus, LOG_TARGET,
+};
+use parity_scale_codec::{Decode, Encode};
+use polkadot_primitives::{ExecutorParams, ValidationCodeHash};
+use rand::Rng;
+use std::{
+	collections::HashSet,
+	future::Future,
+	path::{Path, PathBuf},
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// The time allotted for the execute worker to spawn when replaying a dump.
+const REPLAY_SPAWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Configuration of the execution dumps.
+#[derive(Debug, Clone)]
+pub struct ExecutionDumpConfig {
+	/// The directory the dumps are written to. It is created if it does not exist.
+	pub path: PathBuf,
+	/// If set, one in `sample_rate` successful jobs is dumped as well. Failed jobs are always
+	/// dumped, once per validation code.
+	pub sample_rate: Option<u32>,
+	/// The maximum total size of the dumps written since the node started, in bytes. Jobs are not
+	/// dumped any more once the limit is reached.
+	pub max_total_size: u64,
+}
+
+impl ExecutionDumpConfig {
+	/// Returns whether the job concluded with `outcome` should be dumped.
+	pub(crate) fn should_dump(&self, outcome: &Outcome) -> bool {
+		if !matches!(outcome, Outcome::Ok { .. }) {
+			return true
+		}
+
+		match self.sample_rate {
+			Some(sample_rate) if sample_rate > 0 => rand::thread_rng().gen_ratio(1, sample_rate),
+			_ => false,
+		}
+	}
+}
+
+/// The execution dumps written by the node, bounded by the [`ExecutionDumpConfig`].
+pub(crate) struct ExecutionDumps {
+	config: ExecutionDumpConfig,
+	node_version: Option<String>,
+	/// The code hashes of the dumped jobs, along with whether the job failed. Only one failed and
+	/// one successful job is dumped per validation code.
+	dumped: HashSet<(ValidationCodeHash, bool)>,
+	/// The size of the dumps written since the node started, in bytes.
+	total_size: u64,
+}
+
+impl ExecutionDumps {
+	pub(crate) fn new(config: ExecutionDumpConfig, node_version: Option<String>) -> Self {
+		Self { config, node_version, dumped: HashSet::new(), total_size: 0 }
+	}
+
+	/// Returns a future that writes a dump of the job to the configured directory, if the job
+	/// failed or was sampled, no such job of the same validation code was dumped yet and the total
+	/// size of the dumps stays within the limit.
+	///
+	/// The future is meant to be spawned once the result of the job was sent. Errors are logged
+	/// and otherwise ignored, dumping must never affect the validation.
+	pub(crate) fn prepare(
+		&mut self,
+		artifact: ArtifactPathId,
+		executor_params: ExecutorParams,
+		exec_timeout: Duration,
+		params: Vec<u8>,
+		outcome: &Outcome,
+	) -> Option<impl Future<Output = ()> + Send + 'static> {
+		if !self.config.should_dump(outcome) {
+			return None
+		}
+
+		let code_hash = artifact.id.code_hash;
+		let failed = !matches!(outcome, Outcome::Ok { .. });
+		if self.dumped.contains(&(code_hash, failed)) {
+			return None
+		}
+
+		let artifact_size = match std::fs::metadata(&artifact.path) {
+			Ok(metadata) => metadata.len(),
+			Err(err) => {
+				gum::warn!(
+					target: LOG_TARGET,
+					validation_code_hash = ?code_hash,
+					"failed to read the artifact for an execution dump: {}",
+					err,
+				);
+				return None
+			},
+		};
+		let size = artifact_size.saturating_add(params.len() as u64);
+		if self.total_size.saturating_add(size) > self.config.max_total_size {
+			gum::debug!(
+				target: LOG_TARGET,
+				validation_code_hash = ?code_hash,
+				"execution dump skipped, the dumps reached their maximum size",
+			);
+			return None
+		}
+		self.dumped.insert((code_hash, failed));
+		self.total_size += size;
+
+		Some(write_dump(
+			self.config.path.clone(),
+			self.node_version.clone(),
+			artifact,
+			executor_params,
+			exec_timeout,
+			params,
+			describe_outcome(outcome),
+		))
+	}
+}
+
+/// Reads the artifact and writes the dump of the job to a new file in `dir`.
+async fn write_dump(
+	dir: PathBuf,
+	node_version: Option<String>,
+	artifact: ArtifactPathId,
+	executor_params: ExecutorParams,
+	exec_timeout: Duration,
+	params: Vec<u8>,
+	outcome: String,
+) {
+	let artifact_bytes = match tokio::fs::read(&artifact.path).await {
+		Ok(bytes) => bytes,
+		Err(err) => {
+			gum::warn!(
+				target: LOG_TARGET,
+				validation_code_hash = ?artifact.id.code_hash,
+				"failed to read the artifact for an execution dump: {}",
+				err,
+			);
+			return
+		},
+	};
+
+	let dump = ExecutionDump::V1(ExecutionDumpV1 {
+		node_version,
+		code_hash: artifact.id.code_hash,
+		executor_params,
+		exec_timeout_ms: exec_timeout.as_millis() as u64,
+		params,
+		artifact: artifact_bytes,
+		outcome,
+	});
+
+	match write(&dir, &dump).await {
+		Ok(path) => gum::info!(
+			target: LOG_TARGET,
+			validation_code_hash = ?artifact.id.code_hash,
+			"execution dump written to {}",
+			path.display(),
+		),
+		Err(err) => gum::warn!(
+			target: LOG_TARGET,
+			validation_code_hash = ?artifact.id.code_hash,
+			"failed to write an execution dump to {}: {}",
+			dir.display(),
+			err,
+		),
+	}
+}
+
+/// A snapshot of an execution job.
+///
+/// The format is versioned so that dumps taken by older nodes can still be read.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum ExecutionDump {
+	/// The first version of the dump format.
+	#[codec(index = 1)]
+	V1(ExecutionDumpV1),
+}
+
+/// Version 1 of the execution dump format.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct ExecutionDumpV1 {
+	/// The version of the node that took the dump, if known.
+	pub node_version: Option<String>,
+	/// The hash of the validation code.
+	pub code_hash: ValidationCodeHash,
+	/// The executor parameters the job was executed with.
+	pub executor_params: ExecutorParams,
+	/// The execution timeout, in milliseconds.
+	pub exec_timeout_ms: u64,
+	/// The encoded validation parameters.
+	pub params: Vec<u8>,
+	/// The compiled artifact.
+	pub artifact: Vec<u8>,
+	/// A description of the outcome of the job.
+	pub outcome: String,
+}
+
+impl ExecutionDump {
+	/// Reads a dump from the given file.
+	pub fn read(path: &Path) -> std::io::Result<Self> {
+		let bytes = std::fs::read(path)?;
+		Self::decode(&mut &bytes[..])
+			.map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()))
+	}
+}
+
+/// Writes the dump to a new file in `dir` and returns the path of the file.
+async fn write(dir: &Path, dump: &ExecutionDump) -> std::io::Result<PathBuf> {
+	let ExecutionDump::V1(ExecutionDumpV1 { code_hash, .. }) = dump;
+	let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+	let path =
+		dir.join(format!("{:x}-{}-{:08x}.dump", code_hash, timestamp, rand::random::<u32>()));
+
+	tokio::fs::create_dir_all(dir).await?;
+	tokio::fs::write(&path, dump.encode()).await?;
+	Ok(path)
+}
+
+/// Describes the outcome of a job, in the same way for dumped and replayed jobs.
+fn describe_outcome(outcome: &Outcome) -> String {
+	match outcome {
+		Outcome::Ok { .. } => "ok".to_string(),
+		Outcome::InvalidCandidate { err, .. } => format!("invalid candidate: {}", err),
+		Outcome::HardTimeout => "hard timeout".to_string(),
+		Outcome::WorkerIntfErr => "worker interface error".to_string(),
+		Outcome::JobDied { err } => format!("job died: {}", err),
+		Outcome::JobError { err } => format!("job error: {}", err),
+		Outcome::InternalError { err } => format!("internal error: {}", err),
+	}
+}
+
+/// The result of replaying an execution dump.
+#[derive(Debug, Clone)]
+pub struct ExecutionReplayReport {
+	/// The outcome of the replayed job, described the same way as [`ExecutionDumpV1::outcome`].
+	pub outcome: String,
+	/// The CPU time the job took, as measured by the worker. Only known if the job succeeded.
+	pub cpu_time: Option<Duration>,
+	/// The time between sending the job to the worker and getting its outcome back.
+	pub wall_clock_time: Duration,
+	/// The peak resident set size of the worker and its job, in KiB. Only measured on Linux.
+	pub max_rss_kib: Option<u64>,
+}
+
+/// Replays an execution dump through the execute worker at `execute_worker_program_path`, using
+/// the executor parameters and the execution timeout of the dumped job.
+///
+/// The version check of the worker is skipped, as the dump may have been taken by another version
+/// of the node. The peak memory usage is measured over all the terminated children of the
+/// current process, so this should not be called while other workers are running.
+pub async fn replay_execution_dump(
+	dump: ExecutionDump,
+	execute_worker_program_path: &Path,
+) -> Result<ExecutionReplayReport, String> {
+	let ExecutionDump::V1(dump) = dump;
+
+	let cache_dir = tempfile::tempdir()
+		.map_err(|err| format!("failed to create a temporary directory: {}", err))?;
+	let artifact_path = cache_dir.path().join("artifact");
+	tokio::fs::write(&artifact_path, &dump.artifact)
+		.await
+		.map_err(|err| format!("failed to write the artifact: {}", err))?;
+	let artifact = ArtifactPathId::new(
+		ArtifactId::new(dump.code_hash, dump.executor_params.hash()),
+		&artifact_path,
+	);
+
+	let (idle_worker, worker_handle) = worker_interface::spawn(
+		execute_worker_program_path,
+		cache_dir.path(),
+		dump.executor_params,
+		REPLAY_SPAWN_TIMEOUT,
+		None,
+		SecurityStatus::default(),
+	)
+	.await
+	.map_err(|err| format!("failed to spawn the execute worker: {}", err))?;
+
+	let started_at = Instant::now();
+	let outcome = worker_interface::start_work(
+		idle_worker,
+		artifact,
+		Duration::from_millis(dump.exec_timeout_ms),
+		&dump.params,
+	)
+	.await;
+	let wall_clock_time = started_at.elapsed();
+
+	let cpu_time = match outcome {
+		Outcome::Ok { duration, .. } => Some(duration),
+		_ => None,
+	};
+	let outcome_description = describe_outcome(&outcome);
+
+	// Dropping the outcome closes the connection to the worker, which makes it exit.
+	drop(outcome);
+	let max_rss_kib = match worker_handle.wait().await {
+		Ok(_) => children_max_rss_kib(),
+		Err(err) => {
+			gum::warn!(target: LOG_TARGET, "failed to wait for the execute worker: {}", err);
+			None
+		},
+	};
+
+	Ok(ExecutionReplayReport {
+		outcome: outcome_description,
+		cpu_time,
+		wall_clock_time,
+		max_rss_kib,
+	})
+}
+
+/// Returns the peak resident set size of the largest terminated child of the current process, in
+/// KiB.
+#[cfg(target_os = "linux")]
+fn children_max_rss_kib() -> Option<u64> {
+	let mut usage = std::mem::MaybeUninit::<libc::rusage>::zeroed();
+	// SAFETY: `usage` points to memory large enough to hold a `rusage`.
+	if unsafe { libc::getrusage(libc::RUSAGE_CHILDREN, usage.as_mut_ptr()) } == -1 {
+		return None
+	}
+	// SAFETY: `getrusage` succeeded, so `usage` is initialized.
+	Some(unsafe { usage.assume_init() }.ru_maxrss as u64)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn children_max_rss_kib() -> Option<u64> {
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn dump() -> ExecutionDump {
+		ExecutionDump::V1(ExecutionDumpV1 {
+			node_version: Some("1.0.0".to_string()),
+			code_hash: ValidationCodeHash::from([1; 32]),
+			executor_params: ExecutorParams::default(),
+			exec_timeout_ms: 2000,
+			params: vec![1, 2, 3],
+			artifact: vec![4, 5, 6],
+			outcome: describe_outcome(&Outcome::HardTimeout),
+		})
+	}
+
+	#[tokio::test]
+	async fn dumps_round_trip() {
+		let dir = tempfile::tempdir().unwrap();
+		let dump = dump();
+
+		let path = write(dir.path(), &dump).await.unwrap();
+
+		assert_eq!(path.parent(), Some(dir.path()));
+		assert_eq!(ExecutionDump::read(&path).unwrap(), dump);
+	}
+
+	#[test]
+	fn failed_jobs_are_always_dumped() {
+		let config =
+			ExecutionDumpConfig { path: PathBuf::new(), sample_rate: None, max_total_size: 0 };
+
+		assert!(config.should_dump(&Outcome::HardTimeout));
+		assert!(config.should_dump(&Outcome::WorkerIntfErr));
+		assert!(config.should_dump(&Outcome::JobDied { err: "killed".to_string() }));
+	}
+
+	#[tokio::test]
+	async fn dumps_are_deduplicated_and_bounded() {
+		let dir = tempfile::tempdir().unwrap();
+		let dumps_dir = dir.path().join("dumps");
+		let artifact_path = dir.path().join("artifact");
+		std::fs::write(&artifact_path, [0; 100]).unwrap();
+
+		// Every dump takes 110 bytes, the artifact and the parameters.
+		let config =
+			ExecutionDumpConfig { path: dumps_dir.clone(), sample_rate: None, max_total_size: 250 };
+		let mut dumps = ExecutionDumps::new(config, None);
+		let mut prepare = |code_hash: [u8; 32]| {
+			let artifact = ArtifactPathId::new(
+				ArtifactId::new(code_hash.into(), ExecutorParams::default().hash()),
+				&artifact_path,
+			);
+			dumps.prepare(
+				artifact,
+				ExecutorParams::default(),
+				Duration::from_secs(2),
+				vec![0; 10],
+				&Outcome::HardTimeout,
+			)
+		};
+
+		prepare([1; 32]).expect("first failure of the code is dumped").await;
+		assert!(prepare([1; 32]).is_none());
+
+		assert!(prepare([2; 32]).is_some());
+		assert!(prepare([3; 32]).is_none());
+
+		assert_eq!(std::fs::read_dir(&dumps_dir).unwrap().count(), 1);
+	}
+}
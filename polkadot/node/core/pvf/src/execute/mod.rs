@@ -20,7 +20,12 @@
 //! messages. The queue will spawn workers in new processes. Those processes should jump to
 //! `polkadot_node_core_pvf_worker::execute_worker_entrypoint`.
 
+mod dump;
 mod queue;
 mod worker_interface;
 
+pub use dump::{
+	replay_execution_dump, ExecutionDump, ExecutionDumpConfig, ExecutionDumpV1,
+	ExecutionReplayReport,
+};
 pub use queue::{start, PendingExecutionRequest, ToQueue};
@@ -16,9 +16,12 @@
 
 //! A queue that handles requests for PVF execution.
 
-use super::worker_interface::Outcome;
+use super::{
+	dump::{ExecutionDumpConfig, ExecutionDumps},
+	worker_interface::Outcome,
+};
 use crate::{
-	artifacts::{ArtifactId, ArtifactPathId},
+	artifacts::ArtifactPathId,
 	host::ResultSender,
 	metrics::Metrics,
 	worker_interface::{IdleWorker, WorkerHandle},
@@ -127,7 +130,7 @@ impl Workers {
 
 enum QueueEvent {
 	Spawn(IdleWorker, WorkerHandle, ExecuteJob),
-	StartWork(Worker, Outcome, ArtifactId, ResultSender),
+	StartWork(Worker, Outcome, ExecuteJob),
 }
 
 type Mux = FuturesUnordered<BoxFuture<'static, QueueEvent>>;
@@ -144,6 +147,7 @@ struct Queue {
 	spawn_timeout: Duration,
 	node_version: Option<String>,
 	security_status: SecurityStatus,
+	execution_dumps: Option<ExecutionDumps>,
 
 	/// The queue of jobs that are waiting for a worker to pick up.
 	queue: VecDeque<ExecuteJob>,
@@ -160,6 +164,7 @@ impl Queue {
 		spawn_timeout: Duration,
 		node_version: Option<String>,
 		security_status: SecurityStatus,
+		execution_dump: Option<ExecutionDumpConfig>,
 		to_queue_rx: mpsc::Receiver<ToQueue>,
 	) -> Self {
 		Self {
@@ -167,6 +172,8 @@ impl Queue {
 			program_path,
 			cache_path,
 			spawn_timeout,
+			execution_dumps: execution_dump
+				.map(|config| ExecutionDumps::new(config, node_version.clone())),
 			node_version,
 			security_status,
 			to_queue_rx,
@@ -300,8 +307,8 @@ async fn handle_mux(queue: &mut Queue, event: QueueEvent) {
 		QueueEvent::Spawn(idle, handle, job) => {
 			handle_worker_spawned(queue, idle, handle, job);
 		},
-		QueueEvent::StartWork(worker, outcome, artifact_id, result_tx) => {
-			handle_job_finish(queue, worker, outcome, artifact_id, result_tx);
+		QueueEvent::StartWork(worker, outcome, job) => {
+			handle_job_finish(queue, worker, outcome, job);
 		},
 	}
 }
@@ -327,13 +334,16 @@ fn handle_worker_spawned(
 
 /// If there are pending jobs in the queue, schedules the next of them onto the just freed up
 /// worker. Otherwise, puts back into the available workers list.
-fn handle_job_finish(
-	queue: &mut Queue,
-	worker: Worker,
-	outcome: Outcome,
-	artifact_id: ArtifactId,
-	result_tx: ResultSender,
-) {
+fn handle_job_finish(queue: &mut Queue, worker: Worker, outcome: Outcome, job: ExecuteJob) {
+	let ExecuteJob { artifact, exec_timeout, params, executor_params, result_tx, .. } = job;
+	let artifact_id = artifact.id.clone();
+	// The dump is prepared before the outcome is consumed, but only written once the result was
+	// sent.
+	let dump = queue
+		.execution_dumps
+		.as_mut()
+		.and_then(|dumps| dumps.prepare(artifact, executor_params, exec_timeout, params, &outcome));
+
 	let (idle_worker, result, duration) = match outcome {
 		Outcome::Ok { result_descriptor, duration, idle_worker } => {
 			// TODO: propagate the soft timeout
@@ -390,6 +400,10 @@ fn handle_job_finish(
 	// that's legitimate and we don't treat that as an error.
 	let _ = result_tx.send(result);
 
+	if let Some(dump) = dump {
+		tokio::spawn(dump);
+	}
+
 	// Then, we should deal with the worker:
 	//
 	// - if the `idle_worker` token was returned we should either schedule the next task or just put
@@ -504,10 +518,10 @@ fn assign(queue: &mut Queue, worker: Worker, job: ExecuteJob) {
 				idle,
 				job.artifact.clone(),
 				job.exec_timeout,
-				job.params,
+				&job.params,
 			)
 			.await;
-			QueueEvent::StartWork(worker, outcome, job.artifact.id, job.result_tx)
+			QueueEvent::StartWork(worker, outcome, job)
 		}
 		.boxed(),
 	);
@@ -521,6 +535,7 @@ pub fn start(
 	spawn_timeout: Duration,
 	node_version: Option<String>,
 	security_status: SecurityStatus,
+	execution_dump: Option<ExecutionDumpConfig>,
 ) -> (mpsc::Sender<ToQueue>, impl Future<Output = ()>) {
 	let (to_queue_tx, to_queue_rx) = mpsc::channel(20);
 	let run = Queue::new(
@@ -531,6 +546,7 @@ pub fn start(
 		spawn_timeout,
 		node_version,
 		security_status,
+		execution_dump,
 		to_queue_rx,
 	)
 	.run();
@@ -118,7 +118,7 @@ pub async fn start_work(
 	worker: IdleWorker,
 	artifact: ArtifactPathId,
 	execution_timeout: Duration,
-	validation_params: Vec<u8>,
+	validation_params: &[u8],
 ) -> Outcome {
 	let IdleWorker { mut stream, pid, worker_dir } = worker;
 
@@ -132,7 +132,7 @@ pub async fn start_work(
 	);
 
 	with_worker_dir_setup(worker_dir, pid, &artifact.path, |worker_dir| async move {
-		if let Err(error) = send_request(&mut stream, &validation_params, execution_timeout).await {
+		if let Err(error) = send_request(&mut stream, validation_params, execution_timeout).await {
 			gum::warn!(
 				target: LOG_TARGET,
 				worker_pid = %pid,
@@ -22,7 +22,7 @@
 
 use crate::{
 	artifacts::{ArtifactId, ArtifactPathId, ArtifactState, Artifacts},
-	execute::{self, PendingExecutionRequest},
+	execute::{self, ExecutionDumpConfig, PendingExecutionRequest},
 	metrics::Metrics,
 	prepare, security, Priority, SecurityStatus, ValidationError, LOG_TARGET,
 };
@@ -178,6 +178,9 @@ pub struct Config {
 	pub execute_worker_spawn_timeout: Duration,
 	/// The maximum number of execute workers that can run at the same time.
 	pub execute_workers_max_num: usize,
+	/// If set, failed (and optionally sampled) execution jobs are dumped to disk, to be replayed
+	/// for debugging.
+	pub execution_dump: Option<ExecutionDumpConfig>,
 }
 
 impl Config {
@@ -202,6 +205,7 @@ impl Config {
 			execute_worker_program_path,
 			execute_worker_spawn_timeout: Duration::from_secs(3),
 			execute_workers_max_num: 2,
+			execution_dump: None,
 		}
 	}
 }
@@ -260,6 +264,7 @@ pub async fn start(
 		config.execute_worker_spawn_timeout,
 		config.node_version,
 		security_status,
+		config.execution_dump,
 	);
 
 	let (to_sweeper_tx, to_sweeper_rx) = mpsc::channel(100);
@@ -104,6 +104,10 @@ mod worker_interface;
 pub mod testing;
 
 pub use error::{InvalidCandidate, PossiblyInvalidError, ValidationError};
+pub use execute::{
+	replay_execution_dump, ExecutionDump, ExecutionDumpConfig, ExecutionDumpV1,
+	ExecutionReplayReport,
+};
 pub use host::{
 	start, Config, ValidationHost, EXECUTE_BINARY_NAME, HOST_MESSAGE_QUEUE_SIZE,
 	PREPARE_BINARY_NAME,
@@ -282,6 +282,11 @@ impl WorkerHandle {
 	pub fn id(&self) -> u32 {
 		self.child_id
 	}
+
+	/// Waits for the worker process to exit and reaps it.
+	pub(crate) async fn wait(mut self) -> io::Result<std::process::ExitStatus> {
+		self.child.wait().await
+	}
 }
 
 impl futures::Future for WorkerHandle {
@@ -82,6 +82,9 @@ pub enum Error {
 
 	#[error("Given validator index could not be found in current session")]
 	InvalidValidatorIndex,
+
+	#[error("Failed to compute the availability chunk index: {0}")]
+	ChunkIndex(#[from] polkadot_erasure_coding::Error),
 }
 
 /// General result abbreviation type alias.
@@ -102,6 +105,7 @@ pub fn log_error(
 			match jfyi {
 				JfyiError::UnexpectedPoV |
 				JfyiError::InvalidValidatorIndex |
+				JfyiError::ChunkIndex(_) |
 				JfyiError::NoSuchCachedSession { .. } |
 				JfyiError::QueryAvailableDataResponseChannel(_) |
 				JfyiError::QueryChunkResponseChannel(_) => gum::warn!(target: LOG_TARGET, error = %jfyi, ctx),
@@ -33,9 +33,10 @@ use polkadot_node_subsystem::{
 	messages::{AvailabilityStoreMessage, IfDisconnected, NetworkBridgeTxMessage},
 	overseer,
 };
+use polkadot_node_subsystem_util::availability_chunks::availability_chunk_index;
 use polkadot_primitives::{
-	AuthorityDiscoveryId, BlakeTwo256, CandidateHash, GroupIndex, Hash, HashT, OccupiedCore,
-	SessionIndex,
+	AuthorityDiscoveryId, BlakeTwo256, CandidateHash, CoreIndex, GroupIndex, Hash, HashT,
+	OccupiedCore, SessionIndex,
 };
 
 use crate::{
@@ -136,12 +137,13 @@ impl FetchTaskConfig {
 	/// The result of this function can be passed into [`FetchTask::start`].
 	pub fn new(
 		leaf: Hash,
+		core_index: CoreIndex,
 		core: &OccupiedCore,
 		sender: mpsc::Sender<FromFetchTask>,
 		metrics: Metrics,
 		session_info: &SessionInfo,
 		span: jaeger::Span,
-	) -> Self {
+	) -> Result<Self> {
 		let span = span
 			.child("fetch-task-config")
 			.with_trace_id(core.candidate_hash)
@@ -156,26 +158,31 @@ impl FetchTaskConfig {
 
 		// Don't run tasks for our backing group:
 		if session_info.our_group == Some(core.group_responsible) {
-			return FetchTaskConfig { live_in, prepared_running: None }
+			return Ok(FetchTaskConfig { live_in, prepared_running: None })
 		}
 
+		// The chunk we are responsible for depends on the core the candidate occupies.
+		let chunk_index = availability_chunk_index(
+			Some(&session_info.node_features),
+			session_info.n_validators,
+			core_index,
+			session_info.our_index,
+		)?;
+
 		let prepared_running = RunningTask {
 			session_index: session_info.session_index,
 			group_index: core.group_responsible,
 			group: session_info.validator_groups.get(core.group_responsible.0 as usize)
 				.expect("The responsible group of a candidate should be available in the corresponding session. qed.")
 				.clone(),
-			request: ChunkFetchingRequest {
-				candidate_hash: core.candidate_hash,
-				index: session_info.our_index,
-			},
+			request: ChunkFetchingRequest { candidate_hash: core.candidate_hash, index: chunk_index },
 			erasure_root: core.candidate_descriptor.erasure_root,
 			relay_parent: core.candidate_descriptor.relay_parent,
 			metrics,
 			sender,
 			span,
 		};
-		FetchTaskConfig { live_in, prepared_running: Some(prepared_running) }
+		Ok(FetchTaskConfig { live_in, prepared_running: Some(prepared_running) })
 	}
 }
 
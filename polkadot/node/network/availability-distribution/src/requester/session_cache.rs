@@ -21,7 +21,9 @@ use schnellru::{ByLength, LruMap};
 
 use polkadot_node_subsystem::overseer;
 use polkadot_node_subsystem_util::runtime::RuntimeInfo;
-use polkadot_primitives::{AuthorityDiscoveryId, GroupIndex, Hash, SessionIndex, ValidatorIndex};
+use polkadot_primitives::{
+	vstaging::NodeFeatures, AuthorityDiscoveryId, GroupIndex, Hash, SessionIndex, ValidatorIndex,
+};
 
 use crate::{
 	error::{Error, Result},
@@ -62,6 +64,13 @@ pub struct SessionInfo {
 	///
 	/// `None`, if we are not in fact part of any group.
 	pub our_group: Option<GroupIndex>,
+
+	/// The number of validators in the session.
+	pub n_validators: usize,
+
+	/// The node features enabled in the session, which determine the chunk we are responsible
+	/// for.
+	pub node_features: NodeFeatures,
 }
 
 /// Report of bad validators.
@@ -208,7 +217,14 @@ impl SessionCache {
 				})
 				.collect();
 
-			let info = SessionInfo { validator_groups, our_index, session_index, our_group };
+			let info = SessionInfo {
+				validator_groups,
+				our_index,
+				session_index,
+				our_group,
+				n_validators: info.session_info.validators.len(),
+				node_features: info.node_features.clone(),
+			};
 			return Ok(Some(info))
 		}
 		return Ok(None)
@@ -35,11 +35,15 @@ use futures::{
 	task::{Context, Poll},
 };
 use schnellru::{ByLength, LruMap};
-use task::{FetchChunks, FetchChunksParams, FetchFull, FetchFullParams};
+use task::{
+	FetchChunks, FetchChunksParams, FetchFull, FetchFullParams, FetchSystematicChunks,
+	FetchSystematicChunksParams,
+};
 
 use fatality::Nested;
 use polkadot_erasure_coding::{
-	branch_hash, branches, obtain_chunks_v1, recovery_threshold, Error as ErasureEncodingError,
+	branch_hash, branches, obtain_chunks_v1, recovery_threshold, systematic_recovery_threshold,
+	Error as ErasureEncodingError,
 };
 use task::{RecoveryParams, RecoveryStrategy, RecoveryTask};
 
@@ -55,9 +59,13 @@ use polkadot_node_subsystem::{
 	overseer, ActiveLeavesUpdate, FromOrchestra, OverseerSignal, SpawnedSubsystem,
 	SubsystemContext, SubsystemError, SubsystemResult,
 };
-use polkadot_node_subsystem_util::request_session_info;
+use polkadot_node_subsystem_util::{
+	availability_chunks::availability_chunk_indices, request_session_info,
+	runtime::request_node_features,
+};
 use polkadot_primitives::{
-	BlakeTwo256, BlockNumber, CandidateHash, CandidateReceipt, GroupIndex, Hash, HashT,
+	vstaging::{node_features::FeatureIndex, CandidateDescriptorV2, NodeFeatures},
+	BlakeTwo256, BlockNumber, CandidateHash, CandidateReceipt, CoreIndex, GroupIndex, Hash, HashT,
 	SessionIndex, SessionInfo, ValidatorIndex,
 };
 
@@ -90,6 +98,11 @@ pub enum RecoveryStrategyKind {
 	BackersFirstIfSizeLower(usize),
 	/// We always recover using validator chunks.
 	ChunksAlways,
+	/// We try the backing group first if PoV size is lower than specified, then fallback to
+	/// systematic chunks and finally to regular validator chunks.
+	BackersFirstIfSizeLowerThenSystematicChunks(usize),
+	/// We recover using systematic chunks first, then fallback to regular validator chunks.
+	SystematicChunks,
 }
 
 /// The Availability Recovery Subsystem.
@@ -354,6 +367,7 @@ async fn launch_recovery_task<Context>(
 	response_sender: oneshot::Sender<Result<AvailableData, RecoveryError>>,
 	metrics: &Metrics,
 	recovery_strategies: VecDeque<Box<dyn RecoveryStrategy<<Context as SubsystemContext>::Sender>>>,
+	validator_chunk_indices: Vec<ValidatorIndex>,
 	bypass_availability_store: bool,
 	post_recovery_check: PostRecoveryCheck,
 ) -> error::Result<()> {
@@ -361,6 +375,7 @@ async fn launch_recovery_task<Context>(
 	let params = RecoveryParams {
 		validator_authority_keys: session_info.discovery_keys.clone(),
 		n_validators: session_info.validators.len(),
+		validator_chunk_indices,
 		threshold: recovery_threshold(session_info.validators.len())?,
 		candidate_hash,
 		erasure_root: receipt.descriptor.erasure_root,
@@ -399,6 +414,7 @@ async fn handle_recover<Context>(
 	receipt: CandidateReceipt,
 	session_index: SessionIndex,
 	backing_group: Option<GroupIndex>,
+	maybe_core_index: Option<CoreIndex>,
 	response_sender: oneshot::Sender<Result<AvailableData, RecoveryError>>,
 	metrics: &Metrics,
 	erasure_task_tx: futures::channel::mpsc::Sender<ErasureTask>,
@@ -440,23 +456,59 @@ async fn handle_recover<Context>(
 	let _span = span.child("session-info-ctx-received");
 	match session_info {
 		Some(session_info) => {
+			let n_validators = session_info.validators.len();
+			let node_features =
+				request_node_features(state.live_block.1, session_index, ctx.sender())
+					.await?
+					.unwrap_or(NodeFeatures::EMPTY);
+			let chunk_mapping_enabled = node_features
+				.get(FeatureIndex::AvailabilityChunkMapping as usize)
+				.map(|enabled| *enabled)
+				.unwrap_or(false);
+
+			// The chunk held by each validator depends on the core the candidate occupied. If the
+			// requester doesn't know it, v2 candidate descriptors still tell us.
+			let maybe_core_index = maybe_core_index
+				.or_else(|| CandidateDescriptorV2::from(receipt.descriptor.clone()).core_index());
+			let (validator_chunk_indices, chunk_mapping_known) = match maybe_core_index {
+				Some(core_index) => (
+					availability_chunk_indices(Some(&node_features), n_validators, core_index)?,
+					true,
+				),
+				None => {
+					if chunk_mapping_enabled {
+						gum::debug!(
+							target: LOG_TARGET,
+							?candidate_hash,
+							"Core index unknown, the validator to chunk mapping can't be determined",
+						);
+					}
+
+					(
+						availability_chunk_indices(None, n_validators, CoreIndex(0))?,
+						!chunk_mapping_enabled,
+					)
+				},
+			};
+
 			let mut recovery_strategies: VecDeque<
 				Box<dyn RecoveryStrategy<<Context as SubsystemContext>::Sender>>,
-			> = VecDeque::with_capacity(2);
+			> = VecDeque::with_capacity(3);
 
 			if let Some(backing_group) = backing_group {
 				if let Some(backing_validators) = session_info.validator_groups.get(backing_group) {
 					let mut small_pov_size = true;
 
-					if let RecoveryStrategyKind::BackersFirstIfSizeLower(small_pov_limit) =
-						recovery_strategy_kind
+					if let RecoveryStrategyKind::BackersFirstIfSizeLower(small_pov_limit) |
+					RecoveryStrategyKind::BackersFirstIfSizeLowerThenSystematicChunks(
+						small_pov_limit,
+					) = recovery_strategy_kind
 					{
 						// Get our own chunk size to get an estimate of the PoV size.
 						let chunk_size: Result<Option<usize>, error::Error> =
 							query_chunk_size(ctx, candidate_hash).await;
 						if let Ok(Some(chunk_size)) = chunk_size {
-							let pov_size_estimate =
-								chunk_size.saturating_mul(session_info.validators.len()) / 3;
+							let pov_size_estimate = chunk_size.saturating_mul(n_validators) / 3;
 							small_pov_size = pov_size_estimate < small_pov_limit;
 
 							gum::trace!(
@@ -476,19 +528,46 @@ async fn handle_recover<Context>(
 
 					match (&recovery_strategy_kind, small_pov_size) {
 						(RecoveryStrategyKind::BackersFirstAlways, _) |
-						(RecoveryStrategyKind::BackersFirstIfSizeLower(_), true) => recovery_strategies.push_back(
-							Box::new(FetchFull::new(FetchFullParams {
+						(RecoveryStrategyKind::BackersFirstIfSizeLower(_), true) |
+						(
+							RecoveryStrategyKind::BackersFirstIfSizeLowerThenSystematicChunks(_),
+							true,
+						) => recovery_strategies.push_back(Box::new(FetchFull::new(
+							FetchFullParams {
 								validators: backing_validators.to_vec(),
 								erasure_task_tx: erasure_task_tx.clone(),
-							})),
-						),
+							},
+						))),
 						_ => {},
 					};
 				}
 			}
 
+			let systematic_strategy_enabled = matches!(
+				recovery_strategy_kind,
+				RecoveryStrategyKind::BackersFirstIfSizeLowerThenSystematicChunks(_) |
+					RecoveryStrategyKind::SystematicChunks
+			);
+			// Systematic recovery needs to know which validators hold the systematic chunks.
+			if systematic_strategy_enabled && chunk_mapping_known {
+				let systematic_threshold = systematic_recovery_threshold(n_validators)?;
+				let validators = validator_chunk_indices
+					.iter()
+					.enumerate()
+					.filter(|(_, chunk_index)| (chunk_index.0 as usize) < systematic_threshold)
+					.map(|(validator_index, _)| ValidatorIndex(validator_index as u32))
+					.collect();
+
+				recovery_strategies.push_back(Box::new(FetchSystematicChunks::new(
+					FetchSystematicChunksParams {
+						validators,
+						erasure_task_tx: erasure_task_tx.clone(),
+					},
+				)));
+			}
+
 			recovery_strategies.push_back(Box::new(FetchChunks::new(FetchChunksParams {
-				n_validators: session_info.validators.len(),
+				n_validators,
 				erasure_task_tx,
 			})));
 
@@ -500,6 +579,7 @@ async fn handle_recover<Context>(
 				response_sender,
 				metrics,
 				recovery_strategies,
+				validator_chunk_indices,
 				bypass_availability_store,
 				post_recovery_check,
 			)
@@ -603,6 +683,37 @@ impl AvailabilityRecoverySubsystem {
 		}
 	}
 
+	/// Create a new instance of `AvailabilityRecoverySubsystem` which requests systematic chunks
+	/// first and falls back to regular chunks.
+	pub fn with_systematic_chunks(
+		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
+		metrics: Metrics,
+	) -> Self {
+		Self {
+			recovery_strategy_kind: RecoveryStrategyKind::SystematicChunks,
+			bypass_availability_store: false,
+			post_recovery_check: PostRecoveryCheck::Reencode,
+			req_receiver,
+			metrics,
+		}
+	}
+
+	/// Create a new instance of `AvailabilityRecoverySubsystem` which requests systematic chunks
+	/// if PoV is above a threshold, and falls back to regular chunks.
+	pub fn with_systematic_chunks_if_pov_large(
+		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
+		metrics: Metrics,
+	) -> Self {
+		Self {
+			recovery_strategy_kind:
+				RecoveryStrategyKind::BackersFirstIfSizeLowerThenSystematicChunks(SMALL_POV_LIMIT),
+			bypass_availability_store: false,
+			post_recovery_check: PostRecoveryCheck::Reencode,
+			req_receiver,
+			metrics,
+		}
+	}
+
 	/// Starts the inner subsystem loop.
 	pub async fn run<Context>(self, mut ctx: Context) -> SubsystemResult<()> {
 		let mut state = State::default();
@@ -691,6 +802,7 @@ impl AvailabilityRecoverySubsystem {
 									receipt,
 									session_index,
 									maybe_backing_group,
+									maybe_core_index,
 									response_sender,
 								) => {
 									if let Err(e) = handle_recover(
@@ -699,6 +811,7 @@ impl AvailabilityRecoverySubsystem {
 										receipt,
 										session_index,
 										maybe_backing_group,
+										maybe_core_index,
 										response_sender,
 										&metrics,
 										erasure_task_tx.clone(),
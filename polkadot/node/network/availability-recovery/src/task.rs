@@ -45,6 +45,10 @@ use std::{
 // How many parallel recovery tasks should be running at once.
 const N_PARALLEL: usize = 50;
 
+// How many times a systematic chunk request is retried after a network error, before falling back
+// to regular chunk recovery.
+const SYSTEMATIC_CHUNKS_REQ_RETRY_LIMIT: usize = 2;
+
 /// Time after which we consider a request to have failed
 ///
 /// and we should try more peers. Note in theory the request times out at the network level,
@@ -82,6 +86,9 @@ pub struct RecoveryParams {
 	/// Number of validators.
 	pub n_validators: usize,
 
+	/// The index of the chunk held by each validator, indexed by validator index.
+	pub validator_chunk_indices: Vec<ValidatorIndex>,
+
 	/// The number of chunks needed.
 	pub threshold: usize,
 
@@ -104,38 +111,61 @@ pub struct RecoveryParams {
 	pub pov_hash: Hash,
 }
 
+impl RecoveryParams {
+	/// The index of the chunk held by `validator_index`.
+	fn chunk_index(&self, validator_index: ValidatorIndex) -> ValidatorIndex {
+		self.validator_chunk_indices[validator_index.0 as usize]
+	}
+}
+
 /// Intermediate/common data that must be passed between `RecoveryStrategy`s belonging to the
 /// same `RecoveryTask`.
 pub struct State {
-	/// Chunks received so far.
+	/// Chunks received so far, keyed by chunk index.
 	received_chunks: HashMap<ValidatorIndex, ErasureChunk>,
+	/// Indices of the chunks found in the av-store, if it was already queried.
+	local_chunk_indices: Option<Vec<ValidatorIndex>>,
 }
 
 impl State {
 	fn new() -> Self {
-		Self { received_chunks: HashMap::new() }
+		Self { received_chunks: HashMap::new(), local_chunk_indices: None }
 	}
 
-	fn insert_chunk(&mut self, validator: ValidatorIndex, chunk: ErasureChunk) {
-		self.received_chunks.insert(validator, chunk);
+	fn insert_chunk(&mut self, chunk_index: ValidatorIndex, chunk: ErasureChunk) {
+		self.received_chunks.insert(chunk_index, chunk);
 	}
 
 	fn chunk_count(&self) -> usize {
 		self.received_chunks.len()
 	}
 
-	/// Retrieve the local chunks held in the av-store (either 0 or 1).
+	/// Number of received chunks among the first `systematic_threshold` ones.
+	fn systematic_chunk_count(&self, systematic_threshold: usize) -> usize {
+		self.received_chunks
+			.keys()
+			.filter(|chunk_index| (chunk_index.0 as usize) < systematic_threshold)
+			.count()
+	}
+
+	/// Retrieve the local chunks held in the av-store (either 0 or 1) and return their indices.
+	///
+	/// The av-store is only queried once per recovery task.
 	async fn populate_from_av_store<Sender: overseer::AvailabilityRecoverySenderTrait>(
 		&mut self,
 		params: &RecoveryParams,
 		sender: &mut Sender,
 	) -> Vec<ValidatorIndex> {
+		if let Some(local_chunk_indices) = &self.local_chunk_indices {
+			return local_chunk_indices.clone()
+		}
+
 		let (tx, rx) = oneshot::channel();
 		sender
 			.send_message(AvailabilityStoreMessage::QueryAllChunks(params.candidate_hash, tx))
 			.await;
 
-		match rx.await {
+		let local_chunk_indices = match rx.await {
 			Ok(chunks) => {
 				// This should either be length 1 or 0. If we had the whole data,
 				// we wouldn't have reached this stage.
@@ -146,7 +176,7 @@ impl State {
 						gum::trace!(
 							target: LOG_TARGET,
 							candidate_hash = ?params.candidate_hash,
-							validator_index = ?chunk.index,
+							chunk_index = ?chunk.index,
 							"Found valid chunk on disk"
 						);
 						self.insert_chunk(chunk.index, chunk);
@@ -169,7 +199,10 @@ impl State {
 
 				vec![]
 			},
-		}
+		};
+
+		self.local_chunk_indices = Some(local_chunk_indices.clone());
+		local_chunk_indices
 	}
 
 	/// Launch chunk requests in parallel, according to the parameters.
@@ -193,10 +226,12 @@ impl State {
 		while requesting_chunks.len() < desired_requests_count {
 			if let Some(validator_index) = validators.pop_back() {
 				let validator = params.validator_authority_keys[validator_index.0 as usize].clone();
+				let chunk_index = params.chunk_index(validator_index);
 				gum::trace!(
 					target: LOG_TARGET,
 					?validator,
 					?validator_index,
+					?chunk_index,
 					?candidate_hash,
 					"Requesting chunk",
 				);
@@ -204,7 +239,7 @@ impl State {
 				// Request data.
 				let raw_request = req_res::v1::ChunkFetchingRequest {
 					candidate_hash: params.candidate_hash,
-					index: validator_index,
+					index: chunk_index,
 				};
 
 				let (req, res) = OutgoingRequest::new(Recipient::Authority(validator), raw_request);
@@ -236,6 +271,9 @@ impl State {
 	}
 
 	/// Wait for a sufficient amount of chunks to reconstruct according to the provided `params`.
+	///
+	/// Validators whose requests failed because of network errors are queued again if
+	/// `can_retry` allows it.
 	async fn wait_for_chunks(
 		&mut self,
 		params: &RecoveryParams,
@@ -243,7 +281,8 @@ impl State {
 		requesting_chunks: &mut FuturesUndead<
 			Result<Option<ErasureChunk>, (ValidatorIndex, RequestError)>,
 		>,
-		can_conclude: impl Fn(usize, usize, usize, &RecoveryParams, usize) -> bool,
+		mut can_retry: impl FnMut(ValidatorIndex) -> bool,
+		can_conclude: impl Fn(usize, usize, &State, &RecoveryParams, usize) -> bool,
 	) -> (usize, usize) {
 		let metrics = &params.metrics;
 
@@ -265,7 +304,7 @@ impl State {
 						gum::trace!(
 							target: LOG_TARGET,
 							candidate_hash = ?params.candidate_hash,
-							validator_index = ?chunk.index,
+							chunk_index = ?chunk.index,
 							"Received valid chunk",
 						);
 						self.insert_chunk(chunk.index, chunk);
@@ -309,12 +348,16 @@ impl State {
 								metrics.on_chunk_request_error();
 							}
 
-							validators.push_front(validator_index);
+							if can_retry(validator_index) {
+								validators.push_front(validator_index);
+							}
 						},
 						RequestError::Canceled(_) => {
 							metrics.on_chunk_request_error();
 
-							validators.push_front(validator_index);
+							if can_retry(validator_index) {
+								validators.push_front(validator_index);
+							}
 						},
 					}
 				},
@@ -325,7 +368,7 @@ impl State {
 			if can_conclude(
 				validators.len(),
 				requesting_chunks.total_len(),
-				self.chunk_count(),
+				self,
 				params,
 				error_count,
 			) {
@@ -745,11 +788,13 @@ impl<Sender: overseer::AvailabilityRecoverySenderTrait> RecoveryStrategy<Sender>
 		// First query the store for any chunks we've got.
 		if !common_params.bypass_availability_store {
 			let local_chunk_indices = state.populate_from_av_store(common_params, sender).await;
-			self.validators.retain(|i| !local_chunk_indices.contains(i));
+			self.validators
+				.retain(|i| !local_chunk_indices.contains(&common_params.chunk_index(*i)));
 		}
 
 		// No need to query the validators that have the chunks we already received.
-		self.validators.retain(|i| !state.received_chunks.contains_key(i));
+		self.validators
+			.retain(|i| !state.received_chunks.contains_key(&common_params.chunk_index(*i)));
 
 		loop {
 			// If received_chunks has more than threshold entries, attempt to recover the data.
@@ -809,12 +854,13 @@ impl<Sender: overseer::AvailabilityRecoverySenderTrait> RecoveryStrategy<Sender>
 					common_params,
 					&mut self.validators,
 					&mut self.requesting_chunks,
-					|unrequested_validators, reqs, chunk_count, params, _error_count| {
-						chunk_count >= params.threshold ||
+					|_| true,
+					|unrequested_validators, reqs, state, params, _error_count| {
+						state.chunk_count() >= params.threshold ||
 							Self::is_unavailable(
 								unrequested_validators,
 								reqs,
-								chunk_count,
+								state.chunk_count(),
 								params.threshold,
 							)
 					},
@@ -827,6 +873,244 @@ impl<Sender: overseer::AvailabilityRecoverySenderTrait> RecoveryStrategy<Sender>
 	}
 }
 
+/// `RecoveryStrategy` that requests the systematic chunks from the validators holding them, in
+/// parallel.
+///
+/// The systematic chunks contain the original data, so recovering from them does not need a full
+/// Reed-Solomon decode. If any of them can't be fetched, the strategy concludes as unavailable and
+/// the chunks received so far are reused by the next strategy.
+pub struct FetchSystematicChunks {
+	/// Number of systematic chunks needed for recovery.
+	threshold: usize,
+	/// Validators holding the systematic chunks, in the order in which we request the chunks
+	/// from them.
+	validators: VecDeque<ValidatorIndex>,
+	/// How many times the requests to each validator were retried.
+	retries: HashMap<ValidatorIndex, usize>,
+	/// Collection of in-flight requests.
+	requesting_chunks: FuturesUndead<Result<Option<ErasureChunk>, (ValidatorIndex, RequestError)>>,
+	/// Channel to the erasure task handler.
+	erasure_task_tx: futures::channel::mpsc::Sender<ErasureTask>,
+}
+
+/// Parameters specific to the `FetchSystematicChunks` strategy.
+pub struct FetchSystematicChunksParams {
+	/// Validators holding the systematic chunks.
+	pub validators: Vec<ValidatorIndex>,
+	/// Channel to the erasure task handler.
+	pub erasure_task_tx: futures::channel::mpsc::Sender<ErasureTask>,
+}
+
+impl FetchSystematicChunks {
+	/// Instantiate a new strategy.
+	pub fn new(params: FetchSystematicChunksParams) -> Self {
+		Self {
+			threshold: params.validators.len(),
+			validators: params.validators.into(),
+			retries: HashMap::new(),
+			requesting_chunks: FuturesUndead::new(),
+			erasure_task_tx: params.erasure_task_tx,
+		}
+	}
+
+	fn is_unavailable(
+		unrequested_validators: usize,
+		in_flight_requests: usize,
+		systematic_chunk_count: usize,
+		threshold: usize,
+	) -> bool {
+		is_unavailable(
+			systematic_chunk_count,
+			in_flight_requests,
+			unrequested_validators,
+			threshold,
+		)
+	}
+
+	async fn attempt_systematic_recovery(
+		&mut self,
+		state: &mut State,
+		common_params: &RecoveryParams,
+	) -> Result<AvailableData, RecoveryError> {
+		let recovery_duration = common_params.metrics.time_erasure_recovery();
+
+		let chunks = (0..self.threshold)
+			.map(|chunk_index| {
+				state
+					.received_chunks
+					.get(&ValidatorIndex(chunk_index as u32))
+					.map(|chunk| chunk.chunk.clone())
+					.expect("All systematic chunks were received; qed")
+			})
+			.collect();
+
+		let data = match polkadot_erasure_coding::reconstruct_from_systematic_v1(
+			common_params.n_validators,
+			chunks,
+		) {
+			Ok(data) => data,
+			Err(err) => {
+				recovery_duration.map(|rd| rd.stop_and_discard());
+				gum::trace!(
+					target: LOG_TARGET,
+					candidate_hash = ?common_params.candidate_hash,
+					erasure_root = ?common_params.erasure_root,
+					?err,
+					"Systematic data recovery error",
+				);
+
+				return Err(RecoveryError::Invalid)
+			},
+		};
+
+		let maybe_data = match common_params.post_recovery_check {
+			PostRecoveryCheck::Reencode => {
+				// Send request to re-encode the chunks and check merkle root.
+				let (reencode_tx, reencode_rx) = oneshot::channel();
+				self.erasure_task_tx
+					.send(ErasureTask::Reencode(
+						common_params.n_validators,
+						common_params.erasure_root,
+						data,
+						reencode_tx,
+					))
+					.await
+					.map_err(|_| RecoveryError::ChannelClosed)?;
+
+				reencode_rx.await.map_err(|_| RecoveryError::ChannelClosed)?
+			},
+			PostRecoveryCheck::PovHash =>
+				(data.pov.hash() == common_params.pov_hash).then_some(data),
+		};
+
+		match maybe_data {
+			Some(data) => {
+				gum::trace!(
+					target: LOG_TARGET,
+					candidate_hash = ?common_params.candidate_hash,
+					erasure_root = ?common_params.erasure_root,
+					"Data recovery from systematic chunks complete",
+				);
+
+				Ok(data)
+			},
+			None => {
+				recovery_duration.map(|rd| rd.stop_and_discard());
+				gum::trace!(
+					target: LOG_TARGET,
+					candidate_hash = ?common_params.candidate_hash,
+					erasure_root = ?common_params.erasure_root,
+					"Systematic data recovery error - root or PoV hash mismatch",
+				);
+
+				Err(RecoveryError::Invalid)
+			},
+		}
+	}
+}
+
+#[async_trait::async_trait]
+impl<Sender: overseer::AvailabilityRecoverySenderTrait> RecoveryStrategy<Sender>
+	for FetchSystematicChunks
+{
+	fn display_name(&self) -> &'static str {
+		"Fetch systematic chunks"
+	}
+
+	async fn run(
+		&mut self,
+		state: &mut State,
+		sender: &mut Sender,
+		common_params: &RecoveryParams,
+	) -> Result<AvailableData, RecoveryError> {
+		// First query the store for any chunks we've got.
+		if !common_params.bypass_availability_store {
+			let local_chunk_indices = state.populate_from_av_store(common_params, sender).await;
+			self.validators
+				.retain(|i| !local_chunk_indices.contains(&common_params.chunk_index(*i)));
+		}
+
+		// No need to query the validators that have the chunks we already received.
+		self.validators
+			.retain(|i| !state.received_chunks.contains_key(&common_params.chunk_index(*i)));
+
+		loop {
+			let systematic_chunk_count = state.systematic_chunk_count(self.threshold);
+
+			if systematic_chunk_count >= self.threshold {
+				return self.attempt_systematic_recovery(state, common_params).await
+			}
+
+			if Self::is_unavailable(
+				self.validators.len(),
+				self.requesting_chunks.total_len(),
+				systematic_chunk_count,
+				self.threshold,
+			) {
+				gum::debug!(
+					target: LOG_TARGET,
+					candidate_hash = ?common_params.candidate_hash,
+					erasure_root = ?common_params.erasure_root,
+					received = %systematic_chunk_count,
+					requesting = %self.requesting_chunks.len(),
+					total_requesting = %self.requesting_chunks.total_len(),
+					systematic_threshold = %self.threshold,
+					"Data recovery from systematic chunks is not possible",
+				);
+
+				return Err(RecoveryError::Unavailable)
+			}
+
+			// Each systematic chunk is held by a single validator, so there is no point in
+			// requesting more chunks than are missing.
+			let desired_requests_count =
+				std::cmp::min(N_PARALLEL, self.threshold - systematic_chunk_count);
+			gum::debug!(
+				target: LOG_TARGET,
+				?common_params.candidate_hash,
+				?desired_requests_count,
+				systematic_threshold = ?self.threshold,
+				already_requesting_count = ?self.requesting_chunks.len(),
+				"Requesting systematic availability chunks for a candidate",
+			);
+			state
+				.launch_parallel_chunk_requests(
+					common_params,
+					sender,
+					desired_requests_count,
+					&mut self.validators,
+					&mut self.requesting_chunks,
+				)
+				.await;
+
+			let retries = &mut self.retries;
+			let threshold = self.threshold;
+			state
+				.wait_for_chunks(
+					common_params,
+					&mut self.validators,
+					&mut self.requesting_chunks,
+					|validator_index| {
+						let retry_count = retries.entry(validator_index).or_default();
+						*retry_count += 1;
+						*retry_count <= SYSTEMATIC_CHUNKS_REQ_RETRY_LIMIT
+					},
+					|unrequested_validators, reqs, state, _params, _error_count| {
+						let systematic_chunk_count = state.systematic_chunk_count(threshold);
+						systematic_chunk_count >= threshold ||
+							Self::is_unavailable(
+								unrequested_validators,
+								reqs,
+								systematic_chunk_count,
+								threshold,
+							)
+					},
+				)
+				.await;
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
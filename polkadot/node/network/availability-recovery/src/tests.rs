@@ -163,6 +163,8 @@ struct TestState {
 	current: Hash,
 	candidate: CandidateReceipt,
 	session_index: SessionIndex,
+	core_index: CoreIndex,
+	node_features: NodeFeatures,
 
 	persisted_validation_data: PersistedValidationData,
 
@@ -211,6 +213,30 @@ impl TestState {
 				}))).unwrap();
 			}
 		);
+		assert_matches!(
+			overseer_recv(virtual_overseer).await,
+			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+				relay_parent,
+				RuntimeApiRequest::NodeFeatures(
+					session_index,
+					tx,
+				)
+			)) => {
+				assert_eq!(relay_parent, self.current);
+				assert_eq!(session_index, self.session_index);
+
+				tx.send(Ok(self.node_features.clone())).unwrap();
+			}
+		);
+	}
+
+	fn validator_chunk_indices(&self) -> Vec<ValidatorIndex> {
+		availability_chunk_indices(
+			Some(&self.node_features),
+			self.validators.len(),
+			self.core_index,
+		)
+		.unwrap()
 	}
 
 	async fn respond_to_available_data_query(
@@ -301,10 +327,17 @@ impl TestState {
 							Requests::ChunkFetchingV1(req) => {
 								assert_eq!(req.payload.candidate_hash, candidate_hash);
 
-								let validator_index = req.payload.index.0 as usize;
-								let available_data = match who_has(validator_index) {
+								// The chunk is requested from the validator holding it.
+								let validator_index = self.validator_authority_id
+									.iter()
+									.position(|a| Recipient::Authority(a.clone()) == req.peer)
+									.unwrap();
+								assert_eq!(self.validator_chunk_indices()[validator_index], req.payload.index);
+
+								let chunk_index = req.payload.index.0 as usize;
+								let available_data = match who_has(chunk_index) {
 									Has::No => Ok(None),
-									Has::Yes => Ok(Some(self.chunks[validator_index].clone().into())),
+									Has::Yes => Ok(Some(self.chunks[chunk_index].clone().into())),
 									Has::NetworkError(e) => Err(e),
 									Has::DoesNotReturn => {
 										senders.push(req.pending_response);
@@ -410,6 +443,8 @@ impl Default for TestState {
 		let mut candidate = dummy_candidate_receipt(dummy_hash());
 
 		let session_index = 10;
+		let core_index = CoreIndex(0);
+		let node_features = NodeFeatures::EMPTY;
 
 		let persisted_validation_data = PersistedValidationData {
 			parent_head: HeadData(vec![7, 8, 9]),
@@ -457,6 +492,8 @@ impl Default for TestState {
 			current,
 			candidate,
 			session_index,
+			core_index,
+			node_features,
 			persisted_validation_data,
 			available_data,
 			chunks,
@@ -492,6 +529,7 @@ fn availability_is_recovered_from_chunks_if_no_group_provided() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -530,6 +568,7 @@ fn availability_is_recovered_from_chunks_if_no_group_provided() {
 				new_candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -583,6 +622,7 @@ fn availability_is_recovered_from_chunks_even_if_backing_group_supplied_if_chunk
 				test_state.candidate.clone(),
 				test_state.session_index,
 				Some(GroupIndex(0)),
+				None,
 				tx,
 			),
 		)
@@ -621,6 +661,7 @@ fn availability_is_recovered_from_chunks_even_if_backing_group_supplied_if_chunk
 				new_candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -674,6 +715,7 @@ fn bad_merkle_path_leads_to_recovery_error() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -736,6 +778,7 @@ fn wrong_chunk_index_leads_to_recovery_error() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -814,6 +857,7 @@ fn invalid_erasure_coding_leads_to_invalid_error() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -867,6 +911,7 @@ fn fast_path_backing_group_recovers() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				Some(GroupIndex(0)),
+				None,
 				tx,
 			),
 		)
@@ -925,6 +970,7 @@ fn recovers_from_only_chunks_if_pov_large() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				Some(GroupIndex(0)),
+				None,
 				tx,
 			),
 		)
@@ -972,6 +1018,7 @@ fn recovers_from_only_chunks_if_pov_large() {
 				new_candidate.clone(),
 				test_state.session_index,
 				Some(GroupIndex(0)),
+				None,
 				tx,
 			),
 		)
@@ -1034,6 +1081,7 @@ fn fast_path_backing_group_recovers_if_pov_small() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				Some(GroupIndex(0)),
+				None,
 				tx,
 			),
 		)
@@ -1101,6 +1149,7 @@ fn no_answers_in_fast_path_causes_chunk_requests() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				Some(GroupIndex(0)),
+				None,
 				tx,
 			),
 		)
@@ -1172,6 +1221,7 @@ fn task_canceled_when_receivers_dropped() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -1217,6 +1267,7 @@ fn chunks_retry_until_all_nodes_respond() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				Some(GroupIndex(0)),
+				None,
 				tx,
 			),
 		)
@@ -1283,6 +1334,7 @@ fn not_returning_requests_wont_stall_retrieval() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				Some(GroupIndex(0)),
+				None,
 				tx,
 			),
 		)
@@ -1364,6 +1416,7 @@ fn all_not_returning_requests_still_recovers_on_return() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				Some(GroupIndex(0)),
+				None,
 				tx,
 			),
 		)
@@ -1447,6 +1500,7 @@ fn returns_early_if_we_have_the_data() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -1487,6 +1541,7 @@ fn does_not_query_local_validator() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -1551,6 +1606,7 @@ fn invalid_local_chunk_is_ignored() {
 				test_state.candidate.clone(),
 				test_state.session_index,
 				None,
+				None,
 				tx,
 			),
 		)
@@ -1578,3 +1634,204 @@ fn invalid_local_chunk_is_ignored() {
 		virtual_overseer
 	});
 }
+
+fn node_features_with_chunk_mapping_enabled() -> NodeFeatures {
+	let mut node_features = NodeFeatures::new();
+	node_features.resize(FeatureIndex::AvailabilityChunkMapping as usize + 1, false);
+	node_features.set(FeatureIndex::AvailabilityChunkMapping as usize, true);
+	node_features
+}
+
+#[test]
+fn chunks_are_requested_according_to_the_chunk_mapping() {
+	let mut test_state = TestState::default();
+	test_state.core_index = CoreIndex(1);
+	test_state.node_features = node_features_with_chunk_mapping_enabled();
+	assert_ne!(
+		test_state.validator_chunk_indices(),
+		(0..test_state.validators.len() as u32).map(ValidatorIndex).collect::<Vec<_>>()
+	);
+
+	let req_protocol_names = ReqProtocolNames::new(&GENESIS_HASH, None);
+	let subsystem = AvailabilityRecoverySubsystem::with_chunks_only(
+		request_receiver(&req_protocol_names),
+		Metrics::new_dummy(),
+	);
+
+	test_harness(subsystem, |mut virtual_overseer| async move {
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(new_leaf(
+				test_state.current,
+				1,
+			))),
+		)
+		.await;
+
+		let (tx, rx) = oneshot::channel();
+
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityRecoveryMessage::RecoverAvailableData(
+				test_state.candidate.clone(),
+				test_state.session_index,
+				None,
+				Some(test_state.core_index),
+				tx,
+			),
+		)
+		.await;
+
+		test_state.test_runtime_api(&mut virtual_overseer).await;
+
+		let candidate_hash = test_state.candidate.hash();
+
+		test_state.respond_to_available_data_query(&mut virtual_overseer, false).await;
+		test_state.respond_to_query_all_request(&mut virtual_overseer, |_| false).await;
+
+		test_state
+			.test_chunk_requests(
+				&req_protocol_names,
+				candidate_hash,
+				&mut virtual_overseer,
+				test_state.threshold(),
+				|_| Has::Yes,
+			)
+			.await;
+
+		assert_eq!(rx.await.unwrap().unwrap(), test_state.available_data);
+		virtual_overseer
+	});
+}
+
+#[test]
+fn availability_is_recovered_from_systematic_chunks() {
+	let mut test_state = TestState::default();
+	test_state.core_index = CoreIndex(1);
+	test_state.node_features = node_features_with_chunk_mapping_enabled();
+	let systematic_threshold = systematic_recovery_threshold(test_state.validators.len()).unwrap();
+
+	let req_protocol_names = ReqProtocolNames::new(&GENESIS_HASH, None);
+	let subsystem = AvailabilityRecoverySubsystem::with_systematic_chunks(
+		request_receiver(&req_protocol_names),
+		Metrics::new_dummy(),
+	);
+
+	test_harness(subsystem, |mut virtual_overseer| async move {
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(new_leaf(
+				test_state.current,
+				1,
+			))),
+		)
+		.await;
+
+		let (tx, rx) = oneshot::channel();
+
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityRecoveryMessage::RecoverAvailableData(
+				test_state.candidate.clone(),
+				test_state.session_index,
+				None,
+				Some(test_state.core_index),
+				tx,
+			),
+		)
+		.await;
+
+		test_state.test_runtime_api(&mut virtual_overseer).await;
+
+		let candidate_hash = test_state.candidate.hash();
+
+		test_state.respond_to_available_data_query(&mut virtual_overseer, false).await;
+		test_state.respond_to_query_all_request(&mut virtual_overseer, |_| false).await;
+
+		test_state
+			.test_chunk_requests(
+				&req_protocol_names,
+				candidate_hash,
+				&mut virtual_overseer,
+				systematic_threshold,
+				|i| {
+					assert!(i < systematic_threshold, "requested a non-systematic chunk");
+					Has::Yes
+				},
+			)
+			.await;
+
+		assert_eq!(rx.await.unwrap().unwrap(), test_state.available_data);
+		virtual_overseer
+	});
+}
+
+#[test]
+fn systematic_recovery_falls_back_to_regular_chunks() {
+	let test_state = TestState::default();
+	let systematic_threshold = systematic_recovery_threshold(test_state.validators.len()).unwrap();
+
+	let req_protocol_names = ReqProtocolNames::new(&GENESIS_HASH, None);
+	let subsystem = AvailabilityRecoverySubsystem::with_systematic_chunks(
+		request_receiver(&req_protocol_names),
+		Metrics::new_dummy(),
+	);
+
+	test_harness(subsystem, |mut virtual_overseer| async move {
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(new_leaf(
+				test_state.current,
+				1,
+			))),
+		)
+		.await;
+
+		let (tx, rx) = oneshot::channel();
+
+		overseer_send(
+			&mut virtual_overseer,
+			AvailabilityRecoveryMessage::RecoverAvailableData(
+				test_state.candidate.clone(),
+				test_state.session_index,
+				None,
+				None,
+				tx,
+			),
+		)
+		.await;
+
+		test_state.test_runtime_api(&mut virtual_overseer).await;
+
+		let candidate_hash = test_state.candidate.hash();
+
+		test_state.respond_to_available_data_query(&mut virtual_overseer, false).await;
+		test_state.respond_to_query_all_request(&mut virtual_overseer, |_| false).await;
+
+		// The last systematic chunk is missing.
+		test_state
+			.test_chunk_requests(
+				&req_protocol_names,
+				candidate_hash,
+				&mut virtual_overseer,
+				systematic_threshold,
+				|i| if i == systematic_threshold - 1 { Has::No } else { Has::Yes },
+			)
+			.await;
+
+		// Regular chunk recovery reuses the chunks received so far and doesn't query the
+		// av-store again.
+		test_state
+			.test_chunk_requests(
+				&req_protocol_names,
+				candidate_hash,
+				&mut virtual_overseer,
+				test_state.threshold() - (systematic_threshold - 1),
+				|_| Has::Yes,
+			)
+			.await;
+
+		assert_eq!(rx.await.unwrap().unwrap(), test_state.available_data);
+		virtual_overseer
+	});
+}
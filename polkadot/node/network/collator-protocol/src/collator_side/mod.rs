@@ -332,9 +332,9 @@ impl State {
 
 /// Distribute a collation.
 ///
-/// Figure out the core our para is assigned to and the relevant validators.
+/// Check that our para is assigned to the given core and figure out the relevant validators.
 /// Issue a connection request to these validators.
-/// If the para is not scheduled or next up on any core, at the relay-parent,
+/// If the para is not scheduled or next up on the core, at the relay-parent,
 /// or the relay-parent isn't in the active-leaves set, we ignore the message
 /// as it must be invalid in that case - although this indicates a logic error
 /// elsewhere in the node.
@@ -348,6 +348,7 @@ async fn distribute_collation<Context>(
 	parent_head_data_hash: Hash,
 	pov: PoV,
 	result_sender: Option<oneshot::Sender<CollationSecondedSignal>>,
+	core_index: CoreIndex,
 ) -> Result<()> {
 	let candidate_relay_parent = receipt.descriptor.relay_parent;
 	let candidate_hash = receipt.hash();
@@ -394,21 +395,31 @@ async fn distribute_collation<Context>(
 		return Ok(())
 	}
 
-	// Determine which core the para collated-on is assigned to.
+	// Check that the para collated-on is assigned to the core of the collation.
 	// If it is not scheduled then ignore the message.
-	let (our_core, num_cores) =
-		match determine_core(ctx.sender(), id, candidate_relay_parent, relay_parent_mode).await? {
-			Some(core) => core,
-			None => {
-				gum::warn!(
-					target: LOG_TARGET,
-					para_id = %id,
-					"looks like no core is assigned to {} at {}", id, candidate_relay_parent,
-				);
+	let (our_core, num_cores) = match determine_core(
+		ctx.sender(),
+		id,
+		candidate_relay_parent,
+		relay_parent_mode,
+		core_index,
+	)
+	.await?
+	{
+		Some(core) => core,
+		None => {
+			gum::warn!(
+				target: LOG_TARGET,
+				para_id = %id,
+				"looks like core {} is not assigned to {} at {}",
+				core_index.0,
+				id,
+				candidate_relay_parent,
+			);
 
-				return Ok(())
-			},
-		};
+			return Ok(())
+		},
+	};
 
 	// Determine the group on that core.
 	//
@@ -506,36 +517,35 @@ async fn distribute_collation<Context>(
 	Ok(())
 }
 
-/// Get the Id of the Core that is assigned to the para being collated on if any
+/// Get the Id of the Core of the collation if it is assigned to the para being collated on,
 /// and the total number of cores.
 async fn determine_core(
 	sender: &mut impl overseer::SubsystemSender<RuntimeApiMessage>,
 	para_id: ParaId,
 	relay_parent: Hash,
 	relay_parent_mode: ProspectiveParachainsMode,
+	core_index: CoreIndex,
 ) -> Result<Option<(CoreIndex, usize)>> {
 	let cores = get_availability_cores(sender, relay_parent).await?;
 
-	for (idx, core) in cores.iter().enumerate() {
-		let core_para_id = match core {
-			CoreState::Scheduled(scheduled) => Some(scheduled.para_id),
-			CoreState::Occupied(occupied) =>
-				if relay_parent_mode.is_enabled() {
-					// With async backing we don't care about the core state,
-					// it is only needed for figuring our validators group.
-					Some(occupied.candidate_descriptor.para_id)
-				} else {
-					None
-				},
-			CoreState::Free => None,
-		};
+	let core_para_id = match cores.get(core_index.0 as usize) {
+		Some(CoreState::Scheduled(scheduled)) => Some(scheduled.para_id),
+		Some(CoreState::Occupied(occupied)) =>
+			if relay_parent_mode.is_enabled() {
+				// With async backing we don't care about the core state,
+				// it is only needed for figuring our validators group.
+				Some(occupied.candidate_descriptor.para_id)
+			} else {
+				None
+			},
+		Some(CoreState::Free) | None => None,
+	};
 
-		if core_para_id == Some(para_id) {
-			return Ok(Some(((idx as u32).into(), cores.len())))
-		}
+	if core_para_id == Some(para_id) {
+		Ok(Some((core_index, cores.len())))
+	} else {
+		Ok(None)
 	}
-
-	Ok(None)
 }
 
 /// Validators of a particular group index.
@@ -763,7 +773,7 @@ async fn process_msg<Context>(
 		CollateOn(id) => {
 			state.collating_on = Some(id);
 		},
-		DistributeCollation(receipt, parent_head_data_hash, pov, result_sender) => {
+		DistributeCollation(receipt, parent_head_data_hash, pov, result_sender, core_index) => {
 			let _span1 = state
 				.span_per_relay_parent
 				.get(&receipt.descriptor.relay_parent)
@@ -792,6 +802,7 @@ async fn process_msg<Context>(
 						parent_head_data_hash,
 						pov,
 						result_sender,
+						core_index,
 					)
 					.await?;
 				},
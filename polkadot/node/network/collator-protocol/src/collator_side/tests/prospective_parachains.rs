@@ -276,6 +276,7 @@ fn distribute_collation_from_implicit_view() {
 					parent_head_data_hash,
 					pov.clone(),
 					None,
+					CoreIndex(0),
 				),
 			)
 			.await;
@@ -356,6 +357,7 @@ fn distribute_collation_up_to_limit() {
 					parent_head_data_hash,
 					pov.clone(),
 					None,
+					CoreIndex(0),
 				),
 			)
 			.await;
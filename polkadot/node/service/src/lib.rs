@@ -66,6 +66,10 @@ use polkadot_node_subsystem_util::database::Database;
 
 #[cfg(feature = "full-node")]
 pub use {
+	polkadot_node_core_pvf::{
+		replay_execution_dump, ExecutionDump as PvfExecutionDump,
+		ExecutionDumpConfig as PvfExecutionDumpConfig,
+	},
 	polkadot_overseer::{Handle, Overseer, OverseerConnector, OverseerHandle},
 	polkadot_primitives::runtime_api::ParachainHost,
 	relay_chain_selection::SelectRelayChain,
@@ -344,10 +348,11 @@ pub fn open_database(db_source: &DatabaseSource) -> Result<Arc<dyn Database>, Er
 			path.clone(),
 			parachains_db::CacheSizes::default(),
 		)?,
-		DatabaseSource::ParityDb { path, .. } => parachains_db::open_creating_paritydb(
-			path.parent().ok_or(Error::DatabasePathRequired)?.into(),
-			parachains_db::CacheSizes::default(),
-		)?,
+		DatabaseSource::ParityDb { path } | DatabaseSource::ParityDbRefCounted { path } =>
+			parachains_db::open_creating_paritydb(
+				path.parent().ok_or(Error::DatabasePathRequired)?.into(),
+				parachains_db::CacheSizes::default(),
+			)?,
 		DatabaseSource::Auto { paritydb_path, rocksdb_path, .. } => {
 			if paritydb_path.is_dir() && paritydb_path.exists() {
 				parachains_db::open_creating_paritydb(
@@ -475,7 +480,7 @@ fn new_partial<ChainSelection>(
 		FullBackend,
 		ChainSelection,
 		sc_consensus::DefaultImportQueue<Block>,
-		sc_transaction_pool::FullPool<Block, FullClient>,
+		sc_transaction_pool::FullTransactionPool<Block, FullClient>,
 		(
 			impl Fn(
 				polkadot_rpc::DenyUnsafe,
@@ -501,7 +506,7 @@ fn new_partial<ChainSelection>(
 where
 	ChainSelection: 'static + SelectChain<Block>,
 {
-	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
+	let transaction_pool = sc_transaction_pool::FullTransactionPool::new_full(
 		config.transaction_pool.clone(),
 		config.role.is_authority().into(),
 		config.prometheus_registry(),
@@ -642,6 +647,8 @@ pub struct NewFullParams<OverseerGenerator: OverseerGen> {
 	pub workers_path: Option<std::path::PathBuf>,
 	/// Optional custom names for the prepare and execute workers.
 	pub workers_names: Option<(String, String)>,
+	/// If set, failed (and optionally sampled) PVF executions are dumped to disk for debugging.
+	pub pvf_execution_dump: Option<PvfExecutionDumpConfig>,
 	pub overseer_gen: OverseerGenerator,
 	pub overseer_message_channel_capacity_override: Option<usize>,
 	#[allow(dead_code)]
@@ -730,6 +737,7 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 		secure_validator_mode,
 		workers_path,
 		workers_names,
+		pvf_execution_dump,
 		overseer_gen,
 		overseer_message_channel_capacity_override,
 		malus_finality_delay: _malus_finality_delay,
@@ -917,6 +925,7 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 				secure_validator_mode,
 				prep_worker_path,
 				exec_worker_path,
+				pvf_execution_dump,
 			})
 		} else {
 			None
@@ -255,7 +255,7 @@ where
 			IncomingRequestReceivers { pov_req_receiver, chunk_req_receiver },
 			Metrics::register(registry)?,
 		))
-		.availability_recovery(AvailabilityRecoverySubsystem::with_chunks_if_pov_large(
+		.availability_recovery(AvailabilityRecoverySubsystem::with_systematic_chunks_if_pov_large(
 			available_data_req_receiver,
 			Metrics::register(registry)?,
 		))
@@ -0,0 +1,38 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct ApprovalsOptions {
+	#[clap(short, long, default_value_t = 89)]
+	/// The last tranche for which assignments are sent. Assignments of later tranches are
+	/// dropped.
+	pub last_considered_tranche: u32,
+	#[clap(short = 'm', long, default_value_t = 1.0)]
+	/// The mean number of candidates signed in a single approval.
+	pub coalesce_mean: f32,
+	#[clap(short = 's', long, default_value_t = 1.0)]
+	/// The standard deviation of the number of candidates signed in a single approval.
+	pub coalesce_std_dev: f32,
+	#[clap(short = 'c', long, default_value_t = 6)]
+	/// The maximum number of candidates signed in a single approval.
+	pub coalesce_max: u32,
+	#[clap(short, long, default_value_t = false)]
+	/// Generate assignments covering multiple cores with a single certificate (v2 assignments).
+	pub enable_assignments_v2: bool,
+}
@@ -0,0 +1,354 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Generates the assignments and approvals the emulated peers send to the node under test.
+
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use polkadot_node_core_approval_voting::criteria::{self, compute_assignments};
+use polkadot_node_network_protocol::{
+	v3::{ApprovalDistributionMessage, ValidationProtocol},
+	Versioned, VersionedValidationProtocol,
+};
+use polkadot_node_primitives::approval::{
+	v1::DelayTranche,
+	v2::{
+		AssignmentCertV2, CandidateBitfield, IndirectAssignmentCertV2, IndirectSignedApprovalVoteV2,
+	},
+};
+use polkadot_primitives::{
+	vstaging::ApprovalVoteMultipleCandidates, CandidateEvent, CandidateHash, CandidateIndex,
+	CoreIndex, GroupIndex, ValidatorIndex, ValidatorPair, ASSIGNMENT_KEY_TYPE_ID,
+};
+use rand::thread_rng;
+use rand_distr::{Distribution, Normal};
+use sc_keystore::LocalKeystore;
+use sp_core::Pair;
+use sp_keystore::Keystore;
+
+use super::{ApprovalsOptions, BlockTestData, Tick, CHECKING_DELAY_TICKS, NODE_UNDER_TEST};
+use crate::core::{
+	configuration::{TestAuthorities, TestConfiguration},
+	mock::session_info_for_peers,
+	network::{compute_grid_routes, NetworkEmulatorHandle},
+};
+
+/// A gossip message an emulated peer sends to the node under test once `tick` is reached.
+pub struct TestMessage {
+	/// The tick at which the message is sent.
+	pub tick: Tick,
+	/// The validator sending the message.
+	pub sent_by: ValidatorIndex,
+	/// The message itself.
+	pub message: VersionedValidationProtocol,
+}
+
+/// The messages the emulated peers send for all the blocks of the test.
+pub struct GeneratedMessages {
+	/// The messages of each block, ordered by the tick they are sent at.
+	pub per_block: Vec<Vec<TestMessage>>,
+	/// The total number of assignments the node under test receives.
+	pub n_assignments: usize,
+	/// The total number of approvals the node under test receives.
+	pub n_approvals: usize,
+}
+
+// An assignment of a validator, covering the `candidates` of a block.
+struct TestAssignment {
+	tranche: DelayTranche,
+	validator: ValidatorIndex,
+	cert: AssignmentCertV2,
+	candidates: Vec<CandidateIndex>,
+}
+
+/// Generates the assignments and approvals of all the validators except the node under test.
+///
+/// Messages are only generated for as many assignments as needed to approve each candidate and
+/// are routed according to the grid topology: a validator that is not a grid neighbour of the
+/// node under test has its messages relayed by a common neighbour.
+pub struct PeerMessagesGenerator<'a> {
+	config: &'a TestConfiguration,
+	options: &'a ApprovalsOptions,
+	assignment_config: criteria::Config,
+	// Per validator keystore holding its assignment key.
+	keystores: Vec<LocalKeystore>,
+	// Per validator key pair used to sign approvals.
+	validator_pairs: Vec<ValidatorPair>,
+	// Per validator peer relaying its messages to the node under test, if any.
+	routes: Vec<Option<ValidatorIndex>>,
+}
+
+impl<'a> PeerMessagesGenerator<'a> {
+	pub fn new(
+		config: &'a TestConfiguration,
+		options: &'a ApprovalsOptions,
+		test_authorities: &TestAuthorities,
+		network: &NetworkEmulatorHandle,
+	) -> Self {
+		let assignment_config =
+			criteria::Config::from(&session_info_for_peers(config, test_authorities));
+
+		let keystores = test_authorities
+			.key_seeds
+			.iter()
+			.map(|seed| {
+				let keystore = LocalKeystore::in_memory();
+				keystore
+					.sr25519_generate_new(ASSIGNMENT_KEY_TYPE_ID, Some(seed))
+					.expect("Insert key into keystore");
+				keystore
+			})
+			.collect();
+
+		let validator_pairs = test_authorities
+			.key_seeds
+			.iter()
+			.map(|seed| ValidatorPair::from_string(seed, None).expect("Seeds are valid"))
+			.collect();
+
+		Self {
+			config,
+			options,
+			assignment_config,
+			keystores,
+			validator_pairs,
+			routes: compute_grid_routes(test_authorities, network, ValidatorIndex(NODE_UNDER_TEST)),
+		}
+	}
+
+	/// Generates the messages of all the `blocks`.
+	pub fn generate(&self, blocks: &[BlockTestData]) -> GeneratedMessages {
+		let mut generated =
+			GeneratedMessages { per_block: Vec::new(), n_assignments: 0, n_approvals: 0 };
+
+		for block in blocks {
+			let messages = self.generate_block_messages(block, &mut generated);
+			generated.per_block.push(messages);
+		}
+
+		generated
+	}
+
+	fn generate_block_messages(
+		&self,
+		block: &BlockTestData,
+		generated: &mut GeneratedMessages,
+	) -> Vec<TestMessage> {
+		let leaving_cores = block
+			.candidates
+			.iter()
+			.map(|event| match event {
+				CandidateEvent::CandidateIncluded(receipt, _, core_index, group_index) =>
+					(receipt.hash(), *core_index, *group_index),
+				_ => unreachable!("Only included candidates are generated; qed"),
+			})
+			.collect_vec();
+
+		let assignments = self.needed_assignments(block, &leaving_cores);
+		let block_tick = block.tick();
+
+		// Messages batched per tick and sending peer.
+		let mut batches: BTreeMap<
+			(Tick, ValidatorIndex),
+			(Vec<(IndirectAssignmentCertV2, CandidateBitfield)>, Vec<IndirectSignedApprovalVoteV2>),
+		> = BTreeMap::new();
+		// Candidates each validator has to approve.
+		let mut to_approve: BTreeMap<ValidatorIndex, Vec<(DelayTranche, CandidateIndex)>> =
+			BTreeMap::new();
+
+		for assignment in assignments {
+			let Some(sent_by) = self.routes[assignment.validator.0 as usize] else { continue };
+
+			to_approve.entry(assignment.validator).or_default().extend(
+				assignment.candidates.iter().map(|candidate| (assignment.tranche, *candidate)),
+			);
+
+			let candidates = CandidateBitfield::try_from(assignment.candidates)
+				.expect("Assignments cover at least a candidate; qed");
+			let cert = IndirectAssignmentCertV2 {
+				block_hash: block.hash,
+				validator: assignment.validator,
+				cert: assignment.cert,
+			};
+			batches
+				.entry((block_tick + assignment.tranche as Tick, sent_by))
+				.or_default()
+				.0
+				.push((cert, candidates));
+			generated.n_assignments += 1;
+		}
+
+		for (validator, candidates) in to_approve {
+			let sent_by = self.routes[validator.0 as usize].expect("Only routed validators; qed");
+
+			for chunk in self.coalesce(candidates) {
+				let tranche = chunk.iter().map(|(tranche, _)| *tranche).max().unwrap_or_default();
+				let candidate_indices =
+					chunk.into_iter().map(|(_, candidate)| candidate).sorted().collect_vec();
+				let approval =
+					self.sign_approval(block, &leaving_cores, validator, candidate_indices);
+
+				batches
+					.entry((block_tick + (tranche + CHECKING_DELAY_TICKS) as Tick, sent_by))
+					.or_default()
+					.1
+					.push(approval);
+				generated.n_approvals += 1;
+			}
+		}
+
+		batches
+			.into_iter()
+			.flat_map(|((tick, sent_by), (assignments, approvals))| {
+				let assignments = (!assignments.is_empty())
+					.then(|| ApprovalDistributionMessage::Assignments(assignments));
+				let approvals = (!approvals.is_empty())
+					.then(|| ApprovalDistributionMessage::Approvals(approvals));
+
+				assignments.into_iter().chain(approvals).map(move |message| TestMessage {
+					tick,
+					sent_by,
+					message: Versioned::V3(ValidationProtocol::ApprovalDistribution(message)),
+				})
+			})
+			.collect()
+	}
+
+	// Computes the assignments of all validators and keeps only the ones needed to approve the
+	// candidates of the block, ordered by tranche.
+	fn needed_assignments(
+		&self,
+		block: &BlockTestData,
+		leaving_cores: &[(CandidateHash, CoreIndex, GroupIndex)],
+	) -> Vec<TestAssignment> {
+		let mut assignments = Vec::new();
+
+		for validator in (0..self.config.n_validators as u32).map(ValidatorIndex) {
+			if validator == ValidatorIndex(NODE_UNDER_TEST) {
+				continue
+			}
+
+			let our_assignments = compute_assignments(
+				&self.keystores[validator.0 as usize],
+				block.relay_vrf_story.clone(),
+				&self.assignment_config,
+				leaving_cores.to_vec(),
+				self.options.enable_assignments_v2,
+			);
+
+			// A v2 certificate covering multiple cores is returned for each of them.
+			let mut validator_assignments: Vec<TestAssignment> = Vec::new();
+			for (core_index, assignment) in
+				our_assignments.into_iter().sorted_by_key(|(core_index, _)| core_index.0)
+			{
+				let candidate_index = leaving_cores
+					.iter()
+					.position(|(_, core, _)| *core == core_index)
+					.expect("Assignments are computed for leaving cores; qed")
+					as CandidateIndex;
+
+				match validator_assignments
+					.iter_mut()
+					.find(|existing| &existing.cert == assignment.cert())
+				{
+					Some(existing) => existing.candidates.push(candidate_index),
+					None => validator_assignments.push(TestAssignment {
+						tranche: assignment.tranche(),
+						validator,
+						cert: assignment.cert().clone(),
+						candidates: vec![candidate_index],
+					}),
+				}
+			}
+
+			assignments.extend(validator_assignments);
+		}
+
+		assignments.sort_by_key(|assignment| assignment.tranche);
+
+		let mut assignments_per_candidate = vec![0; leaving_cores.len()];
+		assignments
+			.into_iter()
+			.filter(|assignment| {
+				if assignment.tranche > self.options.last_considered_tranche {
+					return false
+				}
+
+				// Tranche zero assignments are always sent, later ones only if some of the
+				// candidates still need approvals.
+				let needed = assignment.tranche == 0 ||
+					assignment.candidates.iter().any(|candidate| {
+						assignments_per_candidate[*candidate as usize] <
+							self.config.needed_approvals
+					});
+
+				if needed {
+					for candidate in &assignment.candidates {
+						assignments_per_candidate[*candidate as usize] += 1;
+					}
+				}
+
+				needed
+			})
+			.collect()
+	}
+
+	// Splits the candidates a validator approves into chunks of normally distributed sizes, each
+	// chunk being signed in a single approval.
+	fn coalesce(
+		&self,
+		candidates: Vec<(DelayTranche, CandidateIndex)>,
+	) -> Vec<Vec<(DelayTranche, CandidateIndex)>> {
+		let normal =
+			Normal::new(self.options.coalesce_mean as f64, self.options.coalesce_std_dev as f64)
+				.expect("normal distribution parameters are good");
+		let mut candidates =
+			candidates.into_iter().sorted_by_key(|(tranche, _)| *tranche).peekable();
+
+		let mut chunks = Vec::new();
+		while candidates.peek().is_some() {
+			let size = (normal.sample(&mut thread_rng()).round() as u32)
+				.clamp(1, self.options.coalesce_max.max(1));
+			chunks.push(candidates.by_ref().take(size as usize).collect());
+		}
+
+		chunks
+	}
+
+	fn sign_approval(
+		&self,
+		block: &BlockTestData,
+		leaving_cores: &[(CandidateHash, CoreIndex, GroupIndex)],
+		validator: ValidatorIndex,
+		candidate_indices: Vec<CandidateIndex>,
+	) -> IndirectSignedApprovalVoteV2 {
+		let candidate_hashes = candidate_indices
+			.iter()
+			.map(|candidate_index| leaving_cores[*candidate_index as usize].0)
+			.collect_vec();
+		let payload = ApprovalVoteMultipleCandidates(&candidate_hashes).signing_payload(0);
+
+		IndirectSignedApprovalVoteV2 {
+			block_hash: block.hash,
+			candidate_indices: candidate_indices
+				.try_into()
+				.expect("Approvals cover at least a candidate; qed"),
+			validator,
+			signature: self.validator_pairs[validator.0 as usize].sign(&payload[..]),
+		}
+	}
+}
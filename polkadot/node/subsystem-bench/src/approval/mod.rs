@@ -0,0 +1,479 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmark of the approval-voting and approval-distribution subsystems, processing the
+//! assignments and approvals gossiped by all the emulated peers.
+
+use std::{
+	collections::HashMap,
+	sync::Arc,
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use colored::Colorize;
+use futures::channel::{mpsc::UnboundedSender, oneshot};
+use polkadot_approval_distribution::ApprovalDistribution;
+use polkadot_node_core_approval_voting::{
+	ApprovalVotingSubsystem, Config as ApprovalVotingConfig, Metrics as ApprovalVotingMetrics,
+};
+use polkadot_node_metrics::metrics::Metrics;
+use polkadot_node_network_protocol::{grid_topology::SessionGridTopology, OurView};
+use polkadot_node_primitives::approval::v1::{
+	babe_unsafe_vrf_info, RelayVRFStory, VrfSignature, VrfTranscript,
+};
+use polkadot_node_subsystem::{Overseer, OverseerConnector, SpawnGlue};
+use polkadot_node_subsystem_test_helpers::mock::new_block_import_info;
+use polkadot_node_subsystem_types::{
+	messages::{
+		network_bridge_event::NewGossipTopology, AllMessages, ApprovalDistributionMessage,
+		NetworkBridgeEvent,
+	},
+	Span,
+};
+use polkadot_node_subsystem_util::database::Database;
+use polkadot_overseer::Handle as OverseerHandle;
+use polkadot_primitives::{
+	vstaging::{node_features::FeatureIndex, NodeFeatures},
+	BlockNumber, CandidateEvent, CoreIndex, GroupIndex, Hash, HeadData, Header, Id as ParaId,
+	ValidatorIndex,
+};
+use polkadot_primitives_test_helpers::dummy_candidate_receipt;
+use sc_keystore::LocalKeystore;
+use sc_service::SpawnTaskHandle;
+use sp_consensus_babe::{
+	digests::{CompatibleDigestItem, PreDigest, SecondaryVRFPreDigest},
+	AllowedSlots, BabeEpochConfiguration, Epoch as BabeEpoch, Slot,
+};
+use sp_core::crypto::VrfSecret;
+use sp_keyring::sr25519::Keyring as Sr25519Keyring;
+use sp_runtime::{Digest, DigestItem};
+
+use crate::core::{
+	configuration::{TestAuthorities, TestConfiguration},
+	environment::{TestEnvironment, TestEnvironmentDependencies, GENESIS_HASH},
+	mock::{
+		dummy_builder,
+		network_bridge::{MockNetworkBridgeRx, MockNetworkBridgeTx},
+		validator_groups, AlwaysSupportsParachains, ChainApiState, DumbOracle, MockChainApi,
+		MockRuntimeApi,
+	},
+	network::{
+		generate_peer_connected, generate_peer_view_change, generate_topology, new_network,
+		HandleNetworkMessage, NetworkEmulatorHandle, NetworkMessage,
+	},
+};
+use message_generator::{GeneratedMessages, PeerMessagesGenerator, TestMessage};
+
+mod cli;
+mod message_generator;
+pub use cli::ApprovalsOptions;
+
+const LOG_TARGET: &str = "subsystem-bench::approval";
+
+/// The validator index of the node under test.
+const NODE_UNDER_TEST: u32 = 0;
+/// The slot duration of the emulated relay chain, in milliseconds.
+const SLOT_DURATION_MILLIS: u64 = 6000;
+/// The duration of a tick of the approval-voting clock, in milliseconds.
+const TICK_DURATION_MILLIS: u64 = 500;
+/// The number of ticks an emulated peer needs to check a candidate it is assigned to.
+const CHECKING_DELAY_TICKS: u32 = 2;
+
+/// A tick of the approval-voting clock.
+type Tick = u64;
+
+/// A relay chain block generated for the test.
+pub struct BlockTestData {
+	/// The slot the block was authored in.
+	slot: Slot,
+	/// The hash of the block.
+	hash: Hash,
+	/// The header of the block.
+	header: Header,
+	/// The candidates included in the block, one per core.
+	candidates: Vec<CandidateEvent>,
+	/// The VRF story used to compute approval assignments for the block.
+	relay_vrf_story: RelayVRFStory,
+}
+
+impl BlockTestData {
+	// The tick at which the slot of the block starts.
+	fn tick(&self) -> Tick {
+		u64::from(self.slot) * SLOT_DURATION_MILLIS / TICK_DURATION_MILLIS
+	}
+}
+
+/// The state of the approvals benchmark.
+pub struct ApprovalTestState {
+	/// The relay chain blocks imported during the test.
+	blocks: Vec<BlockTestData>,
+	/// The messages the emulated peers send for each block.
+	messages: GeneratedMessages,
+}
+
+// Emulated peers drop all the gossip they receive from the node under test.
+struct PeerGossipSink;
+
+impl HandleNetworkMessage for PeerGossipSink {
+	fn handle(
+		&self,
+		message: NetworkMessage,
+		_node_sender: &mut UnboundedSender<NetworkMessage>,
+	) -> Option<NetworkMessage> {
+		match message {
+			NetworkMessage::MessageFromNode(_, _) => None,
+			message => Some(message),
+		}
+	}
+}
+
+fn build_overseer_for_approvals(
+	spawn_task_handle: SpawnTaskHandle,
+	runtime_api: MockRuntimeApi,
+	chain_api: MockChainApi,
+	network_bridge: (MockNetworkBridgeTx, MockNetworkBridgeRx),
+	approval_voting: ApprovalVotingSubsystem,
+	approval_distribution: ApprovalDistribution,
+) -> (Overseer<SpawnGlue<SpawnTaskHandle>, AlwaysSupportsParachains>, OverseerHandle) {
+	let overseer_connector = OverseerConnector::with_event_capacity(64000);
+	let dummy = dummy_builder!(spawn_task_handle);
+	let builder = dummy
+		.replace_runtime_api(|_| runtime_api)
+		.replace_chain_api(|_| chain_api)
+		.replace_network_bridge_tx(|_| network_bridge.0)
+		.replace_network_bridge_rx(|_| network_bridge.1)
+		.replace_approval_voting(|_| approval_voting)
+		.replace_approval_distribution(|_| approval_distribution);
+
+	let (overseer, raw_handle) =
+		builder.build_with_connector(overseer_connector).expect("Should not fail");
+
+	(overseer, OverseerHandle::new(raw_handle))
+}
+
+/// Takes a test configuration and uses it to create the `TestEnvironment`.
+pub fn prepare_test(
+	config: TestConfiguration,
+	options: ApprovalsOptions,
+) -> (TestEnvironment, ApprovalTestState) {
+	let dependencies = TestEnvironmentDependencies::default();
+	let test_authorities = config.generate_authorities();
+
+	let babe_epoch = generate_babe_epoch();
+	let blocks = generate_blocks(&config, &babe_epoch);
+
+	let (network, network_interface, network_receiver) =
+		new_network(&config, &dependencies, &test_authorities, vec![Arc::new(PeerGossipSink)]);
+
+	gum::info!(target: LOG_TARGET, "{}", "Generating assignments and approvals".bright_blue());
+	let messages = PeerMessagesGenerator::new(&config, &options, &test_authorities, &network)
+		.generate(&blocks);
+	gum::info!(
+		target: LOG_TARGET,
+		"{}",
+		format!(
+			"Generated {} assignments and {} approvals",
+			messages.n_assignments, messages.n_approvals
+		)
+		.bright_blue()
+	);
+
+	let mut node_features = NodeFeatures::new();
+	node_features.resize(FeatureIndex::FirstUnassigned as usize, false);
+	node_features
+		.set(FeatureIndex::EnableAssignmentsV2 as u8 as usize, options.enable_assignments_v2);
+
+	let runtime_api = MockRuntimeApi::new(
+		config.clone(),
+		test_authorities.clone(),
+		HashMap::new(),
+		blocks.iter().map(|block| (block.hash, block.candidates.clone())).collect(),
+		Some(babe_epoch),
+		node_features,
+	);
+
+	let genesis_header = Header {
+		digest: Default::default(),
+		number: 0,
+		parent_hash: Default::default(),
+		extrinsics_root: Default::default(),
+		state_root: Default::default(),
+	};
+	let block_headers = std::iter::once((GENESIS_HASH, genesis_header))
+		.chain(blocks.iter().map(|block| (block.hash, block.header.clone())))
+		.collect();
+	let chain_api = MockChainApi::new(ChainApiState { block_headers });
+
+	let network_bridge_tx = MockNetworkBridgeTx::new(
+		network.clone(),
+		network_interface.subsystem_sender(),
+		test_authorities.clone(),
+	);
+	let network_bridge_rx = MockNetworkBridgeRx::new(network_receiver, None);
+
+	let db = kvdb_memorydb::create(1);
+	let db: Arc<dyn Database> =
+		Arc::new(polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter::new(db, &[]));
+	// The node under test is an observer: it holds no keys and only checks what it receives.
+	let approval_voting = ApprovalVotingSubsystem::with_config(
+		ApprovalVotingConfig { col_approval_data: 0, slot_duration_millis: SLOT_DURATION_MILLIS },
+		db,
+		Arc::new(LocalKeystore::in_memory()),
+		Box::new(DumbOracle),
+		ApprovalVotingMetrics::try_register(&dependencies.registry).unwrap(),
+	);
+	let approval_distribution =
+		ApprovalDistribution::new(Metrics::try_register(&dependencies.registry).unwrap());
+
+	let (overseer, overseer_handle) = build_overseer_for_approvals(
+		dependencies.task_manager.spawn_handle(),
+		runtime_api,
+		chain_api,
+		(network_bridge_tx, network_bridge_rx),
+		approval_voting,
+		approval_distribution,
+	);
+
+	(
+		TestEnvironment::new(
+			dependencies,
+			config,
+			network,
+			overseer,
+			overseer_handle,
+			test_authorities,
+		),
+		ApprovalTestState { blocks, messages },
+	)
+}
+
+pub async fn benchmark_approvals(env: &mut TestEnvironment, state: ApprovalTestState) {
+	let config = env.config().clone();
+	let authorities = env.authorities().clone();
+	let network = env.network().clone();
+
+	env.metrics().set_n_validators(config.n_validators);
+	env.metrics().set_n_cores(config.n_cores);
+
+	send_topology_and_peers(env, &network, &authorities).await;
+
+	let test_start = Instant::now();
+	let ApprovalTestState { blocks, messages } = state;
+	let mut senders_done = Vec::new();
+
+	for ((block_num, block), block_messages) in
+		(1..).zip(blocks.into_iter()).zip(messages.per_block.into_iter())
+	{
+		// Blocks are imported at the start of their slot.
+		if !sleep_until_tick(block.tick()).await {
+			gum::warn!(target: LOG_TARGET, block_num, "Block imported after the start of its slot");
+		}
+
+		gum::info!(target: LOG_TARGET, "Current block #{}", block_num);
+		env.metrics().set_current_block(block_num);
+
+		env.import_block(new_block_import_info(block.hash, block_num as BlockNumber))
+			.await;
+		env.send_message(AllMessages::ApprovalDistribution(
+			ApprovalDistributionMessage::NetworkBridgeUpdate(NetworkBridgeEvent::OurViewChange(
+				OurView::new(vec![(block.hash, Arc::new(Span::Disabled))], 0),
+			)),
+		))
+		.await;
+		for event in generate_peer_view_change(&network, &authorities, block.hash) {
+			env.send_message(AllMessages::ApprovalDistribution(
+				ApprovalDistributionMessage::NetworkBridgeUpdate(event),
+			))
+			.await;
+		}
+
+		let (done_tx, done_rx) = oneshot::channel();
+		senders_done.push(done_rx);
+		env.spawn("send-approval-messages", {
+			let network = network.clone();
+			let authorities = authorities.clone();
+			async move {
+				send_block_messages(&network, &authorities, block_messages).await;
+				let _ = done_tx.send(());
+			}
+		});
+	}
+
+	gum::info!(target: LOG_TARGET, "Waiting for all emulated peers to send their messages");
+	for done in senders_done {
+		let _ = done.await;
+	}
+
+	gum::info!(
+		target: LOG_TARGET,
+		"Waiting for {} assignments and {} approvals to be imported",
+		messages.n_assignments,
+		messages.n_approvals
+	);
+	env.wait_until_metric_eq(
+		"polkadot_parachain_assignments_imported_total",
+		messages.n_assignments,
+	)
+	.await;
+	env.wait_until_metric_eq("polkadot_parachain_approvals_imported_total", messages.n_approvals)
+		.await;
+
+	let duration: u128 = test_start.elapsed().as_millis();
+	gum::info!(target: LOG_TARGET, "All blocks processed in {}", format!("{:?}ms", duration).cyan());
+	gum::info!(target: LOG_TARGET,
+		"Avg block time: {}",
+		format!("{} ms", duration / config.num_blocks as u128).red()
+	);
+
+	env.display_network_usage();
+	env.display_cpu_usage(&["approval-distribution", "approval-voting"]);
+	env.stop().await;
+}
+
+// Informs approval-distribution about the grid topology and the connected emulated peers.
+async fn send_topology_and_peers(
+	env: &mut TestEnvironment,
+	network: &NetworkEmulatorHandle,
+	authorities: &TestAuthorities,
+) {
+	let topology = generate_topology(authorities);
+	env.send_message(AllMessages::ApprovalDistribution(
+		ApprovalDistributionMessage::NetworkBridgeUpdate(new_gossip_topology(topology)),
+	))
+	.await;
+
+	for event in generate_peer_connected(network, authorities) {
+		env.send_message(AllMessages::ApprovalDistribution(
+			ApprovalDistributionMessage::NetworkBridgeUpdate(event),
+		))
+		.await;
+	}
+}
+
+fn new_gossip_topology<M>(topology: SessionGridTopology) -> NetworkBridgeEvent<M> {
+	NetworkBridgeEvent::NewGossipTopology(NewGossipTopology {
+		session: 0,
+		topology,
+		local_index: Some(ValidatorIndex(NODE_UNDER_TEST)),
+	})
+}
+
+// Sends the messages of a block from the emulated peers, each one once its tick is reached.
+async fn send_block_messages(
+	network: &NetworkEmulatorHandle,
+	authorities: &TestAuthorities,
+	messages: Vec<TestMessage>,
+) {
+	for TestMessage { tick, sent_by, message } in messages {
+		sleep_until_tick(tick).await;
+
+		let peer = &authorities.validator_authority_id[sent_by.0 as usize];
+		if network.send_message_from_peer(peer, message).is_err() {
+			gum::warn!(target: LOG_TARGET, ?sent_by, "Failed to send message from peer");
+		}
+	}
+}
+
+// Sleeps until `tick` is reached. Returns false if it was already in the past.
+async fn sleep_until_tick(tick: Tick) -> bool {
+	let now = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.expect("Time is after the unix epoch")
+		.as_millis() as u64;
+	let due = tick * TICK_DURATION_MILLIS;
+
+	if due < now {
+		return false
+	}
+
+	tokio::time::sleep(Duration::from_millis(due - now)).await;
+	true
+}
+
+fn generate_babe_epoch() -> BabeEpoch {
+	BabeEpoch {
+		epoch_index: 1,
+		start_slot: 0.into(),
+		duration: 200,
+		authorities: vec![(Sr25519Keyring::Alice.public().into(), 1)],
+		randomness: [0u8; 32],
+		config: BabeEpochConfiguration { c: (1, 4), allowed_slots: AllowedSlots::PrimarySlots },
+	}
+}
+
+fn garbage_vrf_signature() -> VrfSignature {
+	let transcript = VrfTranscript::new(b"test-garbage", &[]);
+	Sr25519Keyring::Alice.pair().vrf_sign(&transcript.into())
+}
+
+// Generates the chain of relay blocks of the test, one per slot, starting with the next slot.
+fn generate_blocks(config: &TestConfiguration, babe_epoch: &BabeEpoch) -> Vec<BlockTestData> {
+	let now = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.expect("Time is after the unix epoch")
+		.as_millis() as u64;
+	let first_slot = now / SLOT_DURATION_MILLIS + 1;
+	let n_groups = validator_groups(config).len();
+
+	let mut parent_hash = GENESIS_HASH;
+	(1..=config.num_blocks)
+		.map(|block_num| {
+			let slot = Slot::from(first_slot + block_num as u64 - 1);
+			let digest = Digest {
+				logs: vec![DigestItem::babe_pre_digest(PreDigest::SecondaryVRF(
+					SecondaryVRFPreDigest {
+						authority_index: 0,
+						slot,
+						vrf_signature: garbage_vrf_signature(),
+					},
+				))],
+			};
+			let header = Header {
+				digest,
+				number: block_num as BlockNumber,
+				parent_hash,
+				extrinsics_root: Default::default(),
+				state_root: Default::default(),
+			};
+			let hash = header.hash();
+			parent_hash = hash;
+
+			let relay_vrf_story = babe_unsafe_vrf_info(&header)
+				.expect("Headers are generated with a VRF pre-digest")
+				.compute_randomness(
+					&babe_epoch.authorities,
+					&babe_epoch.randomness,
+					babe_epoch.epoch_index,
+				)
+				.expect("Alice is an authority of the epoch");
+
+			let candidates = (0..config.n_cores)
+				.map(|core_index| {
+					let mut receipt = dummy_candidate_receipt(hash);
+					receipt.descriptor.para_id = ParaId::from(core_index as u32);
+					CandidateEvent::CandidateIncluded(
+						receipt,
+						HeadData(vec![]),
+						CoreIndex(core_index as u32),
+						GroupIndex((core_index % n_groups) as u32),
+					)
+				})
+				.collect();
+
+			BlockTestData { slot, hash, header, candidates, relay_vrf_story }
+		})
+		.collect()
+}
@@ -23,6 +23,8 @@ use polkadot_node_subsystem_util::database::Database;
 
 use polkadot_node_core_av_store::AvailabilityStoreSubsystem;
 
+use crate::core::mock::DumbOracle;
+
 mod columns {
 	pub const DATA: u32 = 0;
 	pub const META: u32 = 1;
@@ -31,18 +33,6 @@ mod columns {
 
 const TEST_CONFIG: Config = Config { col_data: columns::DATA, col_meta: columns::META };
 
-struct DumbOracle;
-
-impl sp_consensus::SyncOracle for DumbOracle {
-	fn is_major_syncing(&self) -> bool {
-		false
-	}
-
-	fn is_offline(&self) -> bool {
-		unimplemented!("oh no!")
-	}
-}
-
 pub fn new_av_store(dependencies: &TestEnvironmentDependencies) -> AvailabilityStoreSubsystem {
 	let metrics = Metrics::try_register(&dependencies.registry).unwrap();
 
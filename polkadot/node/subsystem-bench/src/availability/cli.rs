@@ -34,4 +34,9 @@ pub struct DataAvailabilityReadOptions {
 	/// we don't need to re-construct from chunks. Tipically this is only faster if nodes have
 	/// enough bandwidth.
 	pub fetch_from_backers: bool,
+	#[clap(short = 's', long, default_value_t = false)]
+	#[serde(default)]
+	/// Recover from the systematic chunks first, which only need to be concatenated instead of
+	/// being decoded. Falls back to regular chunks.
+	pub fetch_systematic_chunks: bool,
 }
@@ -69,8 +69,8 @@ use polkadot_node_subsystem_test_helpers::{
 	derive_erasure_chunks_with_proofs_and_root, mock::new_block_import_info,
 };
 use polkadot_primitives::{
-	AvailabilityBitfield, BlockNumber, CandidateHash, CandidateReceipt, GroupIndex, Hash, HeadData,
-	Header, PersistedValidationData, Signed, SigningContext, ValidatorIndex,
+	AvailabilityBitfield, BlockNumber, CandidateHash, CandidateReceipt, CoreIndex, GroupIndex,
+	Hash, HeadData, Header, PersistedValidationData, Signed, SigningContext, ValidatorIndex,
 };
 use polkadot_primitives_test_helpers::{dummy_candidate_receipt, dummy_hash};
 use sc_service::SpawnTaskHandle;
@@ -171,6 +171,9 @@ fn prepare_test_inner(
 		config.clone(),
 		test_authorities.clone(),
 		candidate_hashes,
+		HashMap::new(),
+		None,
+		Default::default(),
 	);
 
 	let availability_state = NetworkAvailabilityState {
@@ -198,6 +201,7 @@ fn prepare_test_inner(
 	let network_bridge_tx = network_bridge::MockNetworkBridgeTx::new(
 		network.clone(),
 		network_interface.subsystem_sender(),
+		test_authorities.clone(),
 	);
 
 	let network_bridge_rx =
@@ -212,6 +216,11 @@ fn prepare_test_inner(
 					collation_req_receiver,
 					Metrics::try_register(&dependencies.registry).unwrap(),
 				)
+			} else if options.fetch_systematic_chunks {
+				AvailabilityRecoverySubsystem::with_systematic_chunks(
+					collation_req_receiver,
+					Metrics::try_register(&dependencies.registry).unwrap(),
+				)
 			} else {
 				AvailabilityRecoverySubsystem::with_chunks_only(
 					collation_req_receiver,
@@ -447,6 +456,7 @@ pub async fn benchmark_availability_read(env: &mut TestEnvironment, mut state: T
 					Some(GroupIndex(
 						candidate_num as u32 % (std::cmp::max(5, config.n_cores) / 5) as u32,
 					)),
+					Some(CoreIndex(candidate_num as u32)),
 					tx,
 				),
 			);
@@ -13,7 +13,7 @@
 
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
-use super::availability::DataAvailabilityReadOptions;
+use super::{approval::ApprovalsOptions, availability::DataAvailabilityReadOptions};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, clap::Parser)]
@@ -32,6 +32,10 @@ pub enum TestObjective {
 	DataAvailabilityRead(DataAvailabilityReadOptions),
 	/// Benchmark availability and bitfield distribution.
 	DataAvailabilityWrite,
+	/// Benchmark approval-voting and approval-distribution.
+	ApprovalVoting(ApprovalsOptions),
+	/// Benchmark statement-distribution.
+	StatementDistribution,
 	/// Run a test sequence specified in a file
 	TestSequence(TestSequenceOptions),
 }
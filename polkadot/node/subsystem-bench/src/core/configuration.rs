@@ -20,9 +20,10 @@ use keyring::Keyring;
 use std::path::Path;
 
 pub use crate::cli::TestObjective;
-use polkadot_primitives::{AuthorityDiscoveryId, ValidatorId};
+use polkadot_primitives::{AssignmentId, AuthorityDiscoveryId, ValidatorId};
 use rand::thread_rng;
 use rand_distr::{Distribution, Normal, Uniform};
+use sc_network::PeerId;
 
 use serde::{Deserialize, Serialize};
 
@@ -65,6 +66,31 @@ fn default_backing_group_size() -> usize {
 	5
 }
 
+// Default needed approvals
+fn default_needed_approvals() -> usize {
+	30
+}
+
+// Default zeroth delay tranche width
+fn default_zeroth_delay_tranche_width() -> usize {
+	0
+}
+
+// Default relay VRF modulo samples
+fn default_relay_vrf_modulo_samples() -> usize {
+	6
+}
+
+// Default number of delay tranches
+fn default_n_delay_tranches() -> usize {
+	89
+}
+
+// Default number of no show slots
+fn default_no_show_slots() -> usize {
+	3
+}
+
 /// The test input parameters
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TestConfiguration {
@@ -101,6 +127,21 @@ pub struct TestConfiguration {
 	pub connectivity: usize,
 	/// Number of blocks to run the test for
 	pub num_blocks: usize,
+	/// The number of validators needed to approve a candidate.
+	#[serde(default = "default_needed_approvals")]
+	pub needed_approvals: usize,
+	/// The width of the zeroth delay tranche for approval assignments.
+	#[serde(default = "default_zeroth_delay_tranche_width")]
+	pub zeroth_delay_tranche_width: usize,
+	/// The number of samples to do of the `RelayVRFModulo` approval assignment criterion.
+	#[serde(default = "default_relay_vrf_modulo_samples")]
+	pub relay_vrf_modulo_samples: usize,
+	/// The number of delay tranches in total.
+	#[serde(default = "default_n_delay_tranches")]
+	pub n_delay_tranches: usize,
+	/// The number of relay chain blocks after which an approval checker is considered a no-show.
+	#[serde(default = "default_no_show_slots")]
+	pub no_show_slots: usize,
 }
 
 fn generate_pov_sizes(count: usize, min_kib: usize, max_kib: usize) -> Vec<usize> {
@@ -139,6 +180,11 @@ pub struct TestAuthorities {
 	pub keyring: Keyring,
 	pub validator_public: Vec<ValidatorId>,
 	pub validator_authority_id: Vec<AuthorityDiscoveryId>,
+	pub validator_assignment_id: Vec<AssignmentId>,
+	/// The seeds the keys of each validator were derived from.
+	pub key_seeds: Vec<String>,
+	/// The network identity of each validator.
+	pub peer_ids: Vec<PeerId>,
 }
 
 impl TestConfiguration {
@@ -162,6 +208,10 @@ impl TestConfiguration {
 	pub fn generate_authorities(&self) -> TestAuthorities {
 		let keyring = Keyring::default();
 
+		let key_seeds = (0..self.n_validators)
+			.map(|peer_index| format!("//Node{}", peer_index))
+			.collect::<Vec<_>>();
+
 		let keys = (0..self.n_validators)
 			.map(|peer_index| keyring.sr25519_new(format!("Node{}", peer_index)))
 			.collect::<Vec<_>>();
@@ -173,7 +223,19 @@ impl TestConfiguration {
 		let validator_authority_id: Vec<AuthorityDiscoveryId> =
 			keys.iter().map(|key| (*key).into()).collect::<Vec<_>>();
 
-		TestAuthorities { keyring, validator_public, validator_authority_id }
+		let validator_assignment_id: Vec<AssignmentId> =
+			keys.iter().map(|key| (*key).into()).collect::<Vec<_>>();
+
+		let peer_ids = (0..self.n_validators).map(|_| PeerId::random()).collect::<Vec<_>>();
+
+		TestAuthorities {
+			keyring,
+			validator_public,
+			validator_authority_id,
+			validator_assignment_id,
+			key_seeds,
+			peer_ids,
+		}
 	}
 
 	/// An unconstrained standard configuration matching Polkadot/Kusama
@@ -199,6 +261,11 @@ impl TestConfiguration {
 			min_pov_size,
 			max_pov_size,
 			connectivity: 100,
+			needed_approvals: default_needed_approvals(),
+			zeroth_delay_tranche_width: default_zeroth_delay_tranche_width(),
+			relay_vrf_modulo_samples: default_relay_vrf_modulo_samples(),
+			n_delay_tranches: default_n_delay_tranches(),
+			no_show_slots: default_no_show_slots(),
 		}
 	}
 
@@ -223,6 +290,11 @@ impl TestConfiguration {
 			min_pov_size,
 			max_pov_size,
 			connectivity: 95,
+			needed_approvals: default_needed_approvals(),
+			zeroth_delay_tranche_width: default_zeroth_delay_tranche_width(),
+			relay_vrf_modulo_samples: default_relay_vrf_modulo_samples(),
+			n_delay_tranches: default_n_delay_tranches(),
+			no_show_slots: default_no_show_slots(),
 		}
 	}
 
@@ -247,6 +319,11 @@ impl TestConfiguration {
 			min_pov_size,
 			max_pov_size,
 			connectivity: 67,
+			needed_approvals: default_needed_approvals(),
+			zeroth_delay_tranche_width: default_zeroth_delay_tranche_width(),
+			relay_vrf_modulo_samples: default_relay_vrf_modulo_samples(),
+			n_delay_tranches: default_n_delay_tranches(),
+			no_show_slots: default_no_show_slots(),
 		}
 	}
 }
@@ -249,7 +249,6 @@ impl TestEnvironment {
 	}
 
 	/// Spawn a named task in the `test-environment` task group.
-	#[allow(unused)]
 	pub fn spawn(&self, name: &'static str, task: impl Future<Output = ()> + Send + 'static) {
 		self.dependencies
 			.task_manager
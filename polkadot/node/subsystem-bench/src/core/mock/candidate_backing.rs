@@ -0,0 +1,165 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+//!
+//! A generic candidate backing subsystem mockup suitable to be used in benchmarks.
+
+use futures::FutureExt;
+use polkadot_node_primitives::{SignedFullStatementWithPVD, StatementWithPVD};
+use polkadot_node_subsystem::{
+	messages::{CandidateBackingMessage, StatementDistributionMessage},
+	overseer, SpawnedSubsystem, SubsystemError,
+};
+use polkadot_node_subsystem_types::OverseerSignal;
+use polkadot_node_subsystem_util::metrics::prometheus::{
+	self, Counter, PrometheusError, Registry, U64,
+};
+use polkadot_primitives::{
+	CandidateHash, Hash, SigningContext, ValidatorId, ValidatorIndex, LEGACY_MIN_BACKING_VOTES,
+};
+use sp_keystore::KeystorePtr;
+use std::collections::{HashMap, HashSet};
+
+const LOG_TARGET: &str = "subsystem-bench::candidate-backing-mock";
+
+/// State used to sign our own statements.
+pub struct NodeBackingState {
+	/// The keystore holding the key of the node under test.
+	pub keystore: KeystorePtr,
+	/// The validator index of the node under test.
+	pub validator_index: ValidatorIndex,
+	/// The validator key of the node under test.
+	pub validator_public: ValidatorId,
+	/// The backing group of the node under test.
+	pub own_backing_group: Vec<ValidatorIndex>,
+}
+
+/// A mocked `candidate-backing` subsystem.
+///
+/// Seconds with a `Valid` statement every candidate seconded in our backing group and
+/// considers a candidate backed once it has gathered enough statements.
+pub struct MockCandidateBacking {
+	state: NodeBackingState,
+	// Number of statements seen per candidate.
+	statements_tracker: HashMap<CandidateHash, u32>,
+	// Candidates we have already announced as backed.
+	backed: HashSet<CandidateHash>,
+	// Number of candidates announced as backed.
+	backed_total: Counter<U64>,
+}
+
+impl MockCandidateBacking {
+	pub fn new(state: NodeBackingState, registry: &Registry) -> Result<Self, PrometheusError> {
+		let backed_total = prometheus::register(
+			Counter::new(
+				"subsystem_benchmark_candidates_backed_total",
+				"Number of candidates announced as backed by the candidate-backing mock.",
+			)?,
+			registry,
+		)?;
+
+		Ok(Self { state, statements_tracker: HashMap::new(), backed: HashSet::new(), backed_total })
+	}
+
+	fn sign_valid_statement(
+		&self,
+		relay_parent: Hash,
+		candidate_hash: CandidateHash,
+	) -> SignedFullStatementWithPVD {
+		let context = SigningContext { parent_hash: relay_parent, session_index: 0 };
+		SignedFullStatementWithPVD::sign(
+			&self.state.keystore,
+			StatementWithPVD::Valid(candidate_hash),
+			&context,
+			self.state.validator_index,
+			&self.state.validator_public,
+		)
+		.ok()
+		.flatten()
+		.expect("should be signed")
+	}
+
+	// Returns true if the candidate just became backed.
+	fn note_statement(&mut self, candidate_hash: CandidateHash) -> bool {
+		let count = self.statements_tracker.entry(candidate_hash).or_default();
+		*count += 1;
+
+		*count >= LEGACY_MIN_BACKING_VOTES && self.backed.insert(candidate_hash)
+	}
+}
+
+#[overseer::subsystem(CandidateBacking, error=SubsystemError, prefix=self::overseer)]
+impl<Context> MockCandidateBacking {
+	fn start(self, ctx: Context) -> SpawnedSubsystem {
+		let future = self.run(ctx).map(|_| Ok(())).boxed();
+
+		SpawnedSubsystem { name: "test-environment", future }
+	}
+}
+
+#[overseer::contextbounds(CandidateBacking, prefix = self::overseer)]
+impl MockCandidateBacking {
+	async fn run<Context>(mut self, mut ctx: Context) {
+		loop {
+			let msg = ctx.recv().await.expect("Overseer never fails us");
+
+			match msg {
+				orchestra::FromOrchestra::Signal(signal) =>
+					if signal == OverseerSignal::Conclude {
+						return
+					},
+				orchestra::FromOrchestra::Communication { msg } => {
+					gum::trace!(target: LOG_TARGET, msg=?msg, "recv message");
+
+					match msg {
+						CandidateBackingMessage::Statement(relay_parent, statement) => {
+							let candidate_hash = statement.payload().candidate_hash();
+							let mut backed = self.note_statement(candidate_hash);
+
+							let is_own_group_seconded =
+								matches!(statement.payload(), StatementWithPVD::Seconded(..)) &&
+									self.state
+										.own_backing_group
+										.contains(&statement.validator_index());
+
+							if is_own_group_seconded {
+								let own_statement =
+									self.sign_valid_statement(relay_parent, candidate_hash);
+								ctx.send_message(StatementDistributionMessage::Share(
+									relay_parent,
+									own_statement,
+								))
+								.await;
+								backed |= self.note_statement(candidate_hash);
+							}
+
+							if backed {
+								gum::debug!(target: LOG_TARGET, ?candidate_hash, "Candidate backed");
+								self.backed_total.inc();
+								ctx.send_message(StatementDistributionMessage::Backed(
+									candidate_hash,
+								))
+								.await;
+							}
+						},
+						_ => {
+							unimplemented!("Unexpected candidate-backing message")
+						},
+					}
+				},
+			}
+		}
+	}
+}
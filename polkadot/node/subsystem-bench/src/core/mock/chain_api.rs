@@ -81,6 +81,25 @@ impl MockChainApi {
 							// For our purposes, no ancestors is fine.
 							let _ = response_channel.send(Ok(Vec::new()));
 						},
+						ChainApiMessage::FinalizedBlockNumber(response_channel) => {
+							// Only the genesis block is ever finalized.
+							let _ = response_channel.send(Ok(0));
+						},
+						ChainApiMessage::FinalizedBlockHash(number, response_channel) => {
+							let _ = response_channel.send(Ok(self
+								.state
+								.block_headers
+								.iter()
+								.find(|(_, header)| header.number == number)
+								.map(|(hash, _)| *hash)));
+						},
+						ChainApiMessage::BlockNumber(hash, response_channel) => {
+							let _ = response_channel.send(Ok(self
+								.state
+								.block_headers
+								.get(&hash)
+								.map(|header| header.number)));
+						},
 						_ => {
 							unimplemented!("Unexpected chain-api message")
 						},
@@ -18,15 +18,32 @@ use polkadot_node_subsystem::HeadSupportsParachains;
 use polkadot_node_subsystem_types::Hash;
 
 pub mod av_store;
+pub mod candidate_backing;
 pub mod chain_api;
 pub mod dummy;
 pub mod network_bridge;
+pub mod prospective_parachains;
 pub mod runtime_api;
 
 pub use av_store::*;
+pub use candidate_backing::*;
 pub use chain_api::*;
+pub use prospective_parachains::*;
 pub use runtime_api::*;
 
+/// A sync oracle that always reports the node as synced and online.
+pub struct DumbOracle;
+
+impl sp_consensus::SyncOracle for DumbOracle {
+	fn is_major_syncing(&self) -> bool {
+		false
+	}
+
+	fn is_offline(&self) -> bool {
+		false
+	}
+}
+
 pub struct AlwaysSupportsParachains {}
 #[async_trait::async_trait]
 impl HeadSupportsParachains for AlwaysSupportsParachains {
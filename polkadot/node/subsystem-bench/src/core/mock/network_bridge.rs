@@ -18,7 +18,10 @@
 //! the emulated network.
 use futures::{channel::mpsc::UnboundedSender, FutureExt, StreamExt};
 use polkadot_node_subsystem_types::{
-	messages::{BitfieldDistributionMessage, NetworkBridgeEvent},
+	messages::{
+		ApprovalDistributionMessage, BitfieldDistributionMessage, NetworkBridgeEvent,
+		StatementDistributionMessage,
+	},
 	OverseerSignal,
 };
 
@@ -28,10 +31,12 @@ use polkadot_node_subsystem::{
 	messages::NetworkBridgeTxMessage, overseer, SpawnedSubsystem, SubsystemError,
 };
 
-use polkadot_node_network_protocol::Versioned;
+use polkadot_node_network_protocol::{Versioned, VersionedValidationProtocol};
+use polkadot_primitives::AuthorityDiscoveryId;
 
-use crate::core::network::{
-	NetworkEmulatorHandle, NetworkInterfaceReceiver, NetworkMessage, RequestExt,
+use crate::core::{
+	configuration::TestAuthorities,
+	network::{NetworkEmulatorHandle, NetworkInterfaceReceiver, NetworkMessage, RequestExt},
 };
 
 const LOG_TARGET: &str = "subsystem-bench::network-bridge";
@@ -44,6 +49,8 @@ pub struct MockNetworkBridgeTx {
 	network: NetworkEmulatorHandle,
 	/// A channel to the network interface,
 	to_network_interface: UnboundedSender<NetworkMessage>,
+	/// Test authorities, used to map `PeerId`s to `AuthorityDiscoveryId`s.
+	test_authorities: TestAuthorities,
 }
 
 /// A mock of the network bridge tx subsystem.
@@ -58,8 +65,35 @@ impl MockNetworkBridgeTx {
 	pub fn new(
 		network: NetworkEmulatorHandle,
 		to_network_interface: UnboundedSender<NetworkMessage>,
+		test_authorities: TestAuthorities,
 	) -> MockNetworkBridgeTx {
-		Self { network, to_network_interface }
+		Self { network, to_network_interface, test_authorities }
+	}
+
+	// Returns the authority id of the validator identified by `peer_id` on the emulated network.
+	fn authority_id(&self, peer_id: &PeerId) -> AuthorityDiscoveryId {
+		let index = self
+			.test_authorities
+			.peer_ids
+			.iter()
+			.position(|id| id == peer_id)
+			.expect("all peers are test authorities");
+		self.test_authorities.validator_authority_id[index].clone()
+	}
+
+	// Sends a gossip `message` to all connected `peers`.
+	fn send_validation_message(&self, peers: Vec<PeerId>, message: VersionedValidationProtocol) {
+		for peer in peers {
+			let authority_id = self.authority_id(&peer);
+			if !self.network.is_peer_connected(&authority_id) {
+				gum::debug!(target: LOG_TARGET, ?peer, "Not sending message to disconnected peer");
+				continue
+			}
+
+			let _ = self
+				.to_network_interface
+				.unbounded_send(NetworkMessage::MessageFromNode(authority_id, message.clone()));
+		}
 	}
 }
 
@@ -105,8 +139,12 @@ impl MockNetworkBridgeTx {
 					NetworkBridgeTxMessage::SendRequests(requests, _if_disconnected) => {
 						for request in requests {
 							gum::debug!(target: LOG_TARGET, request = ?request, "Processing request");
-							let peer_id =
-								request.authority_id().expect("all nodes are authorities").clone();
+							let peer_id = match request.authority_id() {
+								Some(authority_id) => authority_id.clone(),
+								None => self.authority_id(
+									request.peer_id().expect("requests have a recipient"),
+								),
+							};
 
 							if !self.network.is_peer_connected(&peer_id) {
 								// Attempting to send a request to a disconnected peer.
@@ -123,6 +161,14 @@ impl MockNetworkBridgeTx {
 							let _ = self.to_network_interface.unbounded_send(peer_message);
 						}
 					},
+					NetworkBridgeTxMessage::SendValidationMessage(peers, message) => {
+						self.send_validation_message(peers, message);
+					},
+					NetworkBridgeTxMessage::SendValidationMessages(messages) => {
+						for (peers, message) in messages {
+							self.send_validation_message(peers, message);
+						}
+					},
 					NetworkBridgeTxMessage::ReportPeer(_) => {
 						// ingore rep changes
 					},
@@ -145,18 +191,45 @@ impl MockNetworkBridgeRx {
 				maybe_peer_message = from_network_interface.next() => {
 					if let Some(message) = maybe_peer_message {
 						match message {
-							NetworkMessage::MessageFromPeer(message) => match message {
+							NetworkMessage::MessageFromPeer(peer_id, message) => match message {
 								Versioned::V2(
 									polkadot_node_network_protocol::v2::ValidationProtocol::BitfieldDistribution(
 										bitfield,
 									),
 								) => {
 									ctx.send_message(
-										BitfieldDistributionMessage::NetworkBridgeUpdate(NetworkBridgeEvent::PeerMessage(PeerId::random(), polkadot_node_network_protocol::Versioned::V2(bitfield)))
+										BitfieldDistributionMessage::NetworkBridgeUpdate(NetworkBridgeEvent::PeerMessage(peer_id, polkadot_node_network_protocol::Versioned::V2(bitfield)))
+									).await;
+								},
+								Versioned::V3(
+									polkadot_node_network_protocol::v3::ValidationProtocol::BitfieldDistribution(
+										bitfield,
+									),
+								) => {
+									ctx.send_message(
+										BitfieldDistributionMessage::NetworkBridgeUpdate(NetworkBridgeEvent::PeerMessage(peer_id, polkadot_node_network_protocol::Versioned::V3(bitfield)))
+									).await;
+								},
+								Versioned::V3(
+									polkadot_node_network_protocol::v3::ValidationProtocol::ApprovalDistribution(
+										message,
+									),
+								) => {
+									ctx.send_message(
+										ApprovalDistributionMessage::NetworkBridgeUpdate(NetworkBridgeEvent::PeerMessage(peer_id, polkadot_node_network_protocol::Versioned::V3(message)))
+									).await;
+								},
+								Versioned::V3(
+									polkadot_node_network_protocol::v3::ValidationProtocol::StatementDistribution(
+										message,
+									),
+								) => {
+									ctx.send_message(
+										StatementDistributionMessage::NetworkBridgeUpdate(NetworkBridgeEvent::PeerMessage(peer_id, polkadot_node_network_protocol::Versioned::V3(message)))
 									).await;
 								},
 								_ => {
-									unimplemented!("We only talk v2 network protocol")
+									unimplemented!("Unexpected gossip message from peer")
 								},
 							},
 							NetworkMessage::RequestFromPeer(request) => {
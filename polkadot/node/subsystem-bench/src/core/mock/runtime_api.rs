@@ -17,7 +17,10 @@
 //! A generic runtime api subsystem mockup suitable to be used in benchmarks.
 
 use polkadot_primitives::{
-	CandidateReceipt, CoreState, GroupIndex, IndexedVec, OccupiedCore, SessionInfo, ValidatorIndex,
+	async_backing::AsyncBackingParams,
+	vstaging::{ApprovalVotingParams, NodeFeatures},
+	CandidateEvent, CandidateReceipt, CoreState, GroupIndex, GroupRotationInfo, IndexedVec,
+	OccupiedCore, ScheduledCore, SessionInfo, ValidatorIndex, LEGACY_MIN_BACKING_VOTES,
 };
 
 use bitvec::prelude::BitVec;
@@ -26,6 +29,7 @@ use polkadot_node_subsystem::{
 	overseer, SpawnedSubsystem, SubsystemError,
 };
 use polkadot_node_subsystem_types::OverseerSignal;
+use sp_consensus_babe::Epoch as BabeEpoch;
 use sp_core::H256;
 use std::collections::HashMap;
 
@@ -40,6 +44,12 @@ pub struct RuntimeApiState {
 	authorities: TestAuthorities,
 	// Candidate
 	candidate_hashes: HashMap<H256, Vec<CandidateReceipt>>,
+	// Included candidates per block
+	included_candidates: HashMap<H256, Vec<CandidateEvent>>,
+	// The BABE epoch of all blocks in the test.
+	babe_epoch: Option<BabeEpoch>,
+	// The node features enabled in the session.
+	node_features: NodeFeatures,
 }
 
 /// A mocked `runtime-api` subsystem.
@@ -53,34 +63,60 @@ impl MockRuntimeApi {
 		config: TestConfiguration,
 		authorities: TestAuthorities,
 		candidate_hashes: HashMap<H256, Vec<CandidateReceipt>>,
+		included_candidates: HashMap<H256, Vec<CandidateEvent>>,
+		babe_epoch: Option<BabeEpoch>,
+		node_features: NodeFeatures,
 	) -> MockRuntimeApi {
-		Self { state: RuntimeApiState { authorities, candidate_hashes }, config }
+		Self {
+			state: RuntimeApiState {
+				authorities,
+				candidate_hashes,
+				included_candidates,
+				babe_epoch,
+				node_features,
+			},
+			config,
+		}
 	}
 
 	fn session_info(&self) -> SessionInfo {
-		let all_validators = (0..self.config.n_validators)
-			.map(|i| ValidatorIndex(i as _))
-			.collect::<Vec<_>>();
-
-		let validator_groups = all_validators
-			.chunks(self.config.max_validators_per_core)
-			.map(Vec::from)
-			.collect::<Vec<_>>();
-		SessionInfo {
-			validators: self.state.authorities.validator_public.clone().into(),
-			discovery_keys: self.state.authorities.validator_authority_id.clone(),
-			validator_groups: IndexedVec::<GroupIndex, Vec<ValidatorIndex>>::from(validator_groups),
-			assignment_keys: vec![],
-			n_cores: self.config.n_cores as u32,
-			zeroth_delay_tranche_width: 0,
-			relay_vrf_modulo_samples: 0,
-			n_delay_tranches: 0,
-			no_show_slots: 0,
-			needed_approvals: 0,
-			active_validator_indices: vec![],
-			dispute_period: 6,
-			random_seed: [0u8; 32],
-		}
+		session_info_for_peers(&self.config, &self.state.authorities)
+	}
+}
+
+/// Generates the validator groups of the session used in the test.
+pub fn validator_groups(configuration: &TestConfiguration) -> Vec<Vec<ValidatorIndex>> {
+	let all_validators = (0..configuration.n_validators)
+		.map(|i| ValidatorIndex(i as _))
+		.collect::<Vec<_>>();
+
+	all_validators
+		.chunks(configuration.max_validators_per_core)
+		.map(Vec::from)
+		.collect::<Vec<_>>()
+}
+
+/// Generates the `SessionInfo` of the session used in the test.
+pub fn session_info_for_peers(
+	configuration: &TestConfiguration,
+	authorities: &TestAuthorities,
+) -> SessionInfo {
+	SessionInfo {
+		validators: authorities.validator_public.clone().into(),
+		discovery_keys: authorities.validator_authority_id.clone(),
+		validator_groups: IndexedVec::<GroupIndex, Vec<ValidatorIndex>>::from(validator_groups(
+			configuration,
+		)),
+		assignment_keys: authorities.validator_assignment_id.clone(),
+		n_cores: configuration.n_cores as u32,
+		zeroth_delay_tranche_width: configuration.zeroth_delay_tranche_width as u32,
+		relay_vrf_modulo_samples: configuration.relay_vrf_modulo_samples as u32,
+		n_delay_tranches: configuration.n_delay_tranches as u32,
+		no_show_slots: configuration.no_show_slots as u32,
+		needed_approvals: configuration.needed_approvals as u32,
+		active_validator_indices: vec![],
+		dispute_period: 6,
+		random_seed: [0u8; 32],
 	}
 }
 
@@ -130,10 +166,15 @@ impl MockRuntimeApi {
 								sender.send(Ok(self.state.authorities.validator_public.clone()));
 						},
 						RuntimeApiMessage::Request(
-							_block_hash,
+							block_hash,
 							RuntimeApiRequest::CandidateEvents(sender),
 						) => {
-							let _ = sender.send(Ok(Default::default()));
+							let _ = sender.send(Ok(self
+								.state
+								.included_candidates
+								.get(&block_hash)
+								.cloned()
+								.unwrap_or_default()));
 						},
 						RuntimeApiMessage::Request(
 							_block_hash,
@@ -161,7 +202,10 @@ impl MockRuntimeApi {
 									assert!(index < validator_group_count);
 
 									CoreState::Occupied(OccupiedCore {
-										next_up_on_available: None,
+										next_up_on_available: Some(ScheduledCore {
+											para_id: candidate_receipt.descriptor.para_id,
+											collator: None,
+										}),
 										occupied_since: 0,
 										time_out_at: 0,
 										next_up_on_time_out: None,
@@ -179,7 +223,63 @@ impl MockRuntimeApi {
 							_block_hash,
 							RuntimeApiRequest::NodeFeatures(_session_index, sender),
 						) => {
-							let _ = sender.send(Ok(Default::default()));
+							let _ = sender.send(Ok(self.state.node_features.clone()));
+						},
+						RuntimeApiMessage::Request(
+							_block_hash,
+							RuntimeApiRequest::CurrentBabeEpoch(sender),
+						) => {
+							let _ = sender.send(Ok(self
+								.state
+								.babe_epoch
+								.clone()
+								.expect("Babe epoch unpopulated")));
+						},
+						RuntimeApiMessage::Request(
+							_block_hash,
+							RuntimeApiRequest::ApprovalVotingParams(_session_index, sender),
+						) => {
+							let _ = sender.send(Ok(ApprovalVotingParams::default()));
+						},
+						RuntimeApiMessage::Request(
+							_block_hash,
+							RuntimeApiRequest::ValidatorGroups(sender),
+						) => {
+							let group_rotation_info = GroupRotationInfo {
+								session_start_block: 0,
+								group_rotation_frequency: u32::MAX,
+								now: 0,
+							};
+							let _ = sender
+								.send(Ok((validator_groups(&self.config), group_rotation_info)));
+						},
+						RuntimeApiMessage::Request(
+							_block_hash,
+							RuntimeApiRequest::AsyncBackingParams(sender),
+						) => {
+							let _ = sender.send(Ok(AsyncBackingParams {
+								max_candidate_depth: 1,
+								allowed_ancestry_len: 2,
+							}));
+						},
+						RuntimeApiMessage::Request(
+							_block_hash,
+							RuntimeApiRequest::MinimumBackingVotes(_session_index, sender),
+						) => {
+							let _ = sender.send(Ok(LEGACY_MIN_BACKING_VOTES));
+						},
+						RuntimeApiMessage::Request(
+							_block_hash,
+							RuntimeApiRequest::DisabledValidators(sender),
+						) => {
+							let _ = sender.send(Ok(vec![]));
+						},
+						RuntimeApiMessage::Request(
+							_block_hash,
+							RuntimeApiRequest::Version(sender),
+						) => {
+							let _ = sender
+								.send(Ok(RuntimeApiRequest::APPROVAL_VOTING_PARAMS_REQUIREMENT));
 						},
 						// Long term TODO: implement more as needed.
 						message => {
@@ -49,20 +49,23 @@ use futures::{
 };
 
 use net_protocol::{
+	grid_topology::{SessionGridTopology, TopologyPeerInfo},
+	peer_set::ValidationVersion,
 	request_response::{Recipient, Requests, ResponseSender},
-	VersionedValidationProtocol,
+	ObservedRole, VersionedValidationProtocol, View,
 };
 use parity_scale_codec::Encode;
-use polkadot_primitives::AuthorityDiscoveryId;
+use polkadot_node_subsystem_types::messages::NetworkBridgeEvent;
+use polkadot_primitives::{AuthorityDiscoveryId, Hash, ValidatorIndex};
 use prometheus_endpoint::U64;
 use rand::{seq::SliceRandom, thread_rng};
 use sc_network::{
 	request_responses::{IncomingRequest, OutgoingResponse},
-	RequestFailure,
+	PeerId, RequestFailure,
 };
 use sc_service::SpawnTaskHandle;
 use std::{
-	collections::HashMap,
+	collections::{HashMap, HashSet},
 	sync::Arc,
 	time::{Duration, Instant},
 };
@@ -142,7 +145,7 @@ impl RateLimit {
 /// peer(`AuthorityDiscoveryId``).
 pub enum NetworkMessage {
 	/// A gossip message from peer to node.
-	MessageFromPeer(VersionedValidationProtocol),
+	MessageFromPeer(PeerId, VersionedValidationProtocol),
 	/// A gossip message from node to a peer.
 	MessageFromNode(AuthorityDiscoveryId, VersionedValidationProtocol),
 	/// A request originating from our node
@@ -155,9 +158,9 @@ impl NetworkMessage {
 	/// Returns the size of the encoded message or request
 	pub fn size(&self) -> usize {
 		match &self {
-			NetworkMessage::MessageFromPeer(Versioned::V2(message)) => message.encoded_size(),
-			NetworkMessage::MessageFromPeer(Versioned::V1(message)) => message.encoded_size(),
-			NetworkMessage::MessageFromPeer(Versioned::V3(message)) => message.encoded_size(),
+			NetworkMessage::MessageFromPeer(_, Versioned::V2(message)) => message.encoded_size(),
+			NetworkMessage::MessageFromPeer(_, Versioned::V1(message)) => message.encoded_size(),
+			NetworkMessage::MessageFromPeer(_, Versioned::V3(message)) => message.encoded_size(),
 			NetworkMessage::MessageFromNode(_peer_id, Versioned::V2(message)) =>
 				message.encoded_size(),
 			NetworkMessage::MessageFromNode(_peer_id, Versioned::V1(message)) =>
@@ -430,6 +433,8 @@ pub struct EmulatedPeerHandle {
 	messages_tx: UnboundedSender<NetworkMessage>,
 	/// Send actions to be performed by the peer.
 	actions_tx: UnboundedSender<NetworkMessage>,
+	/// The network identity of the peer.
+	peer_id: PeerId,
 }
 
 impl EmulatedPeerHandle {
@@ -441,7 +446,7 @@ impl EmulatedPeerHandle {
 	/// Send a message to the node.
 	pub fn send_message(&self, message: VersionedValidationProtocol) {
 		self.actions_tx
-			.unbounded_send(NetworkMessage::MessageFromPeer(message))
+			.unbounded_send(NetworkMessage::MessageFromPeer(self.peer_id, message))
 			.expect("Peer action channel hangup");
 	}
 
@@ -613,6 +618,7 @@ pub fn new_peer(
 	stats: Arc<PeerEmulatorStats>,
 	to_network_interface: UnboundedSender<NetworkMessage>,
 	latency_ms: usize,
+	peer_id: PeerId,
 ) -> EmulatedPeerHandle {
 	let (messages_tx, messages_rx) = mpsc::unbounded::<NetworkMessage>();
 	let (actions_tx, actions_rx) = mpsc::unbounded::<NetworkMessage>();
@@ -641,7 +647,7 @@ pub fn new_peer(
 		.boxed(),
 	);
 
-	EmulatedPeerHandle { messages_tx, actions_tx }
+	EmulatedPeerHandle { messages_tx, actions_tx, peer_id }
 }
 
 /// Book keeping of sent and received bytes.
@@ -741,7 +747,8 @@ pub fn new_network(
 	// Create a `PeerEmulator` for each peer.
 	let (stats, mut peers): (_, Vec<_>) = (0..n_peers)
 		.zip(authorities.validator_authority_id.clone())
-		.map(|(peer_index, authority_id)| {
+		.zip(authorities.peer_ids.clone())
+		.map(|((peer_index, authority_id), peer_id)| {
 			validator_authority_id_mapping.insert(authority_id, peer_index);
 			let stats = Arc::new(PeerEmulatorStats::new(peer_index, metrics.clone()));
 			(
@@ -753,6 +760,7 @@ pub fn new_network(
 					stats,
 					to_network_interface.clone(),
 					random_latency(config.latency.as_ref()),
+					peer_id,
 				)),
 			)
 		})
@@ -785,6 +793,114 @@ pub fn new_network(
 	(handle, network_interface, network_interface_receiver)
 }
 
+/// Generates a grid topology of all the test authorities, shuffled in their canonical order.
+pub fn generate_topology(test_authorities: &TestAuthorities) -> SessionGridTopology {
+	let canonical_shuffling = test_authorities
+		.validator_authority_id
+		.iter()
+		.zip(test_authorities.peer_ids.iter())
+		.enumerate()
+		.map(|(index, (discovery_id, peer_id))| TopologyPeerInfo {
+			peer_ids: vec![*peer_id],
+			validator_index: ValidatorIndex(index as u32),
+			discovery_id: discovery_id.clone(),
+		})
+		.collect::<Vec<_>>();
+	let shuffled_indices = (0..canonical_shuffling.len()).collect::<Vec<_>>();
+
+	SessionGridTopology::new(shuffled_indices, canonical_shuffling)
+}
+
+/// Computes, for each validator, the connected peer through which its grid messages reach
+/// `target`: the validator itself if it is a grid neighbour of `target`, or otherwise a
+/// connected neighbour of both. Returns `None` for `target` and for validators whose messages
+/// can't reach it.
+pub fn compute_grid_routes(
+	test_authorities: &TestAuthorities,
+	network: &NetworkEmulatorHandle,
+	target: ValidatorIndex,
+) -> Vec<Option<ValidatorIndex>> {
+	let topology = generate_topology(test_authorities);
+	let target_neighbors = topology
+		.compute_grid_neighbors_for(target)
+		.expect("All validators are part of the topology; qed");
+	let is_connected = |validator: &ValidatorIndex| {
+		network.is_peer_connected(&test_authorities.validator_authority_id[validator.0 as usize])
+	};
+
+	(0..test_authorities.validator_authority_id.len() as u32)
+		.map(ValidatorIndex)
+		.map(|validator| {
+			if validator == target {
+				return None
+			}
+
+			let is_target_neighbor = target_neighbors.validator_indices_x.contains(&validator) ||
+				target_neighbors.validator_indices_y.contains(&validator);
+			if is_target_neighbor {
+				return Some(validator).filter(is_connected)
+			}
+
+			let neighbors = topology
+				.compute_grid_neighbors_for(validator)
+				.expect("All validators are part of the topology; qed");
+			target_neighbors
+				.validator_indices_x
+				.iter()
+				.chain(target_neighbors.validator_indices_y.iter())
+				.filter(|relay| {
+					neighbors.validator_indices_x.contains(relay) ||
+						neighbors.validator_indices_y.contains(relay)
+				})
+				.filter(|relay| is_connected(relay))
+				.min()
+				.copied()
+		})
+		.collect()
+}
+
+/// Generates `PeerConnected` events for all the emulated peers connected to the node under test.
+pub fn generate_peer_connected<M>(
+	network: &NetworkEmulatorHandle,
+	test_authorities: &TestAuthorities,
+) -> Vec<NetworkBridgeEvent<M>> {
+	test_authorities
+		.validator_authority_id
+		.iter()
+		.zip(test_authorities.peer_ids.iter())
+		// Our node is always peer 0.
+		.skip(1)
+		.filter(|(authority_id, _)| network.is_peer_connected(authority_id))
+		.map(|(authority_id, peer_id)| {
+			NetworkBridgeEvent::PeerConnected(
+				*peer_id,
+				ObservedRole::Authority,
+				ValidationVersion::V3.into(),
+				Some(HashSet::from([authority_id.clone()])),
+			)
+		})
+		.collect()
+}
+
+/// Generates `PeerViewChange` events announcing `block_hash` for all the emulated peers connected
+/// to the node under test.
+pub fn generate_peer_view_change<M>(
+	network: &NetworkEmulatorHandle,
+	test_authorities: &TestAuthorities,
+	block_hash: Hash,
+) -> Vec<NetworkBridgeEvent<M>> {
+	test_authorities
+		.validator_authority_id
+		.iter()
+		.zip(test_authorities.peer_ids.iter())
+		.skip(1)
+		.filter(|(authority_id, _)| network.is_peer_connected(authority_id))
+		.map(|(_, peer_id)| {
+			NetworkBridgeEvent::PeerViewChange(*peer_id, View::new([block_hash], 0))
+		})
+		.collect()
+}
+
 /// Errors that can happen when sending data to emulated peers.
 pub enum EmulatedPeerError {
 	NotConnected,
@@ -939,6 +1055,8 @@ impl Metrics {
 pub trait RequestExt {
 	/// Get the authority id if any from the request.
 	fn authority_id(&self) -> Option<&AuthorityDiscoveryId>;
+	/// Get the peer id if any from the request.
+	fn peer_id(&self) -> Option<&PeerId>;
 	/// Consume self and return the response sender.
 	fn into_response_sender(self) -> ResponseSender;
 	/// Allows to change the `ResponseSender` in place.
@@ -964,17 +1082,32 @@ impl RequestExt for Requests {
 					None
 				}
 			},
+			// Requested by `PeerId`
+			Requests::AttestedCandidateV2(_) => None,
 			request => {
 				unimplemented!("RequestAuthority not implemented for {:?}", request)
 			},
 		}
 	}
 
+	fn peer_id(&self) -> Option<&PeerId> {
+		match self {
+			Requests::AttestedCandidateV2(request) => match &request.peer {
+				Recipient::Authority(_) => None,
+				Recipient::Peer(peer_id) => Some(peer_id),
+			},
+			request => {
+				unimplemented!("peer_id() is not implemented for {:?}", request)
+			},
+		}
+	}
+
 	fn into_response_sender(self) -> ResponseSender {
 		match self {
 			Requests::ChunkFetchingV1(outgoing_request) => outgoing_request.pending_response,
 			Requests::AvailableDataFetchingV1(outgoing_request) =>
 				outgoing_request.pending_response,
+			Requests::AttestedCandidateV2(outgoing_request) => outgoing_request.pending_response,
 			_ => unimplemented!("unsupported request type"),
 		}
 	}
@@ -986,6 +1119,8 @@ impl RequestExt for Requests {
 				std::mem::replace(&mut outgoing_request.pending_response, new_sender),
 			Requests::AvailableDataFetchingV1(outgoing_request) =>
 				std::mem::replace(&mut outgoing_request.pending_response, new_sender),
+			Requests::AttestedCandidateV2(outgoing_request) =>
+				std::mem::replace(&mut outgoing_request.pending_response, new_sender),
 			_ => unimplemented!("unsupported request type"),
 		}
 	}
@@ -996,6 +1131,8 @@ impl RequestExt for Requests {
 			Requests::ChunkFetchingV1(outgoing_request) => outgoing_request.payload.encoded_size(),
 			Requests::AvailableDataFetchingV1(outgoing_request) =>
 				outgoing_request.payload.encoded_size(),
+			Requests::AttestedCandidateV2(outgoing_request) =>
+				outgoing_request.payload.encoded_size(),
 			_ => unimplemented!("received an unexpected request"),
 		}
 	}
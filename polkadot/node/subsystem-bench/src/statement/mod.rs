@@ -0,0 +1,535 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmark of the statement-distribution subsystem, receiving the seconded candidates of all
+//! the backing groups from the cluster and the grid topology.
+
+use std::{collections::HashMap, ops::Sub, sync::Arc, time::Instant};
+
+use colored::Colorize;
+use futures::channel::mpsc::UnboundedSender;
+use parity_scale_codec::Encode;
+use polkadot_node_metrics::metrics::Metrics;
+use polkadot_node_network_protocol::{
+	request_response::{
+		v2::AttestedCandidateResponse, IncomingRequest, ReqProtocolNames, Requests,
+	},
+	v3::{
+		BackedCandidateManifest, StatementDistributionMessage, StatementFilter, ValidationProtocol,
+	},
+	Versioned, VersionedValidationProtocol,
+};
+use polkadot_node_subsystem::{Overseer, OverseerConnector, SpawnGlue};
+use polkadot_node_subsystem_test_helpers::mock::new_block_import_info;
+use polkadot_node_subsystem_types::messages::{
+	network_bridge_event::NewGossipTopology, AllMessages, NetworkBridgeEvent,
+	StatementDistributionMessage as StatementDistributionSubsystemMessage,
+};
+use polkadot_overseer::Handle as OverseerHandle;
+use polkadot_primitives::{
+	BlockNumber, CandidateHash, CommittedCandidateReceipt, CompactStatement, GroupIndex, Hash,
+	HeadData, Header, Id as ParaId, PersistedValidationData, SignedStatement, SigningContext,
+	ValidatorIndex,
+};
+use polkadot_primitives_test_helpers::{dummy_head_data, dummy_validation_code, make_candidate};
+use polkadot_statement_distribution::StatementDistributionSubsystem;
+use rand::SeedableRng;
+use sc_network::ProtocolName;
+use sc_service::SpawnTaskHandle;
+
+use crate::core::{
+	configuration::{TestAuthorities, TestConfiguration},
+	environment::{TestEnvironment, TestEnvironmentDependencies, GENESIS_HASH},
+	mock::{
+		dummy_builder,
+		network_bridge::{MockNetworkBridgeRx, MockNetworkBridgeTx},
+		validator_groups, AlwaysSupportsParachains, ChainApiState, MockCandidateBacking,
+		MockChainApi, MockProspectiveParachains, MockRuntimeApi, NodeBackingState,
+	},
+	network::{
+		compute_grid_routes, generate_peer_connected, generate_peer_view_change, generate_topology,
+		new_network, HandleNetworkMessage, NetworkEmulatorHandle, NetworkMessage,
+	},
+};
+
+const LOG_TARGET: &str = "subsystem-bench::statement";
+
+/// The validator index of the node under test. It is part of the first backing group.
+const NODE_UNDER_TEST: u32 = 0;
+
+/// A candidate seconded by one of the backing groups.
+struct TestCandidate {
+	receipt: CommittedCandidateReceipt,
+	persisted_validation_data: PersistedValidationData,
+	group_index: GroupIndex,
+	/// The validators of the backing group.
+	group: Vec<ValidatorIndex>,
+	/// The validator which seconded the candidate.
+	seconder: ValidatorIndex,
+	/// The statements of all the group members, except the node under test.
+	statements: Vec<SignedStatement>,
+}
+
+impl TestCandidate {
+	fn position_in_group(&self, validator: ValidatorIndex) -> usize {
+		self.group
+			.iter()
+			.position(|v| *v == validator)
+			.expect("Statements are issued by group members; qed")
+	}
+
+	// The statements known by the group, as advertised in manifests.
+	fn statement_knowledge(&self) -> StatementFilter {
+		let mut filter = StatementFilter::blank(self.group.len());
+		for statement in &self.statements {
+			let position = self.position_in_group(statement.validator_index());
+			match statement.payload() {
+				CompactStatement::Seconded(_) => filter.seconded_in_group.set(position, true),
+				CompactStatement::Valid(_) => filter.validated_in_group.set(position, true),
+			}
+		}
+
+		filter
+	}
+
+	// Answers a request for the candidate, leaving out the statements in `mask`.
+	fn response(&self, mask: &StatementFilter) -> AttestedCandidateResponse {
+		let statements = self
+			.statements
+			.iter()
+			.filter(|statement| {
+				let position = self.position_in_group(statement.validator_index());
+				match statement.payload() {
+					CompactStatement::Seconded(_) => !mask.seconded_in_group[position],
+					CompactStatement::Valid(_) => !mask.validated_in_group[position],
+				}
+			})
+			.map(|statement| statement.as_unchecked().clone())
+			.collect();
+
+		AttestedCandidateResponse {
+			candidate_receipt: self.receipt.clone(),
+			persisted_validation_data: self.persisted_validation_data.clone(),
+			statements,
+		}
+	}
+}
+
+/// The state of the statement-distribution benchmark.
+///
+/// Emulated peers use it to answer the candidate requests of the node under test.
+pub struct StatementTestState {
+	/// The relay chain blocks imported during the test.
+	block_hashes: Vec<Hash>,
+	/// The candidates of each block, one per core.
+	candidates: Vec<Vec<CandidateHash>>,
+	/// All the candidates of the test.
+	candidate_data: HashMap<CandidateHash, TestCandidate>,
+}
+
+impl StatementTestState {
+	/// Generates the candidates of all blocks and signs the statements of their backing groups.
+	pub fn new(config: &TestConfiguration, test_authorities: &TestAuthorities) -> Self {
+		let groups = validator_groups(config);
+		let keystore = test_authorities.keyring.keystore();
+
+		let block_hashes = (1..=config.num_blocks)
+			.map(|block_num| Hash::repeat_byte(block_num as u8))
+			.collect::<Vec<_>>();
+
+		let mut candidates = Vec::new();
+		let mut candidate_data = HashMap::new();
+		for (block_num, block_hash) in (1..).zip(block_hashes.iter()) {
+			let signing_context = SigningContext { parent_hash: *block_hash, session_index: 0 };
+			let mut block_candidates = Vec::new();
+
+			for core_index in 0..config.n_cores {
+				let group_index = GroupIndex(core_index as u32);
+				let group = groups[core_index].clone();
+				let (receipt, persisted_validation_data) = make_candidate(
+					*block_hash,
+					block_num,
+					ParaId::from(core_index as u32),
+					dummy_head_data(),
+					HeadData(vec![core_index as u8]),
+					dummy_validation_code().hash(),
+				);
+				let candidate_hash = receipt.hash();
+
+				let seconder = *group
+					.iter()
+					.find(|v| **v != ValidatorIndex(NODE_UNDER_TEST))
+					.expect("Groups have other validators than the node under test");
+				let statements = group
+					.iter()
+					.filter(|v| **v != ValidatorIndex(NODE_UNDER_TEST))
+					.map(|validator| {
+						let statement = if *validator == seconder {
+							CompactStatement::Seconded(candidate_hash)
+						} else {
+							CompactStatement::Valid(candidate_hash)
+						};
+						SignedStatement::sign(
+							&keystore,
+							statement,
+							&signing_context,
+							*validator,
+							&test_authorities.validator_public[validator.0 as usize],
+						)
+						.ok()
+						.flatten()
+						.expect("should be signed")
+					})
+					.collect();
+
+				block_candidates.push(candidate_hash);
+				candidate_data.insert(
+					candidate_hash,
+					TestCandidate {
+						receipt,
+						persisted_validation_data,
+						group_index,
+						group,
+						seconder,
+						statements,
+					},
+				);
+			}
+
+			candidates.push(block_candidates);
+		}
+
+		Self { block_hashes, candidates, candidate_data }
+	}
+
+	// Generates the messages the emulated peers send to make the node under test aware of the
+	// candidates of a block: the seconded statement for the candidate of our own backing group
+	// and manifests from the grid for the others.
+	fn block_messages(
+		&self,
+		block_index: usize,
+		network: &NetworkEmulatorHandle,
+		test_authorities: &TestAuthorities,
+		routes: &[Option<ValidatorIndex>],
+	) -> Vec<(ValidatorIndex, VersionedValidationProtocol)> {
+		let relay_parent = self.block_hashes[block_index];
+
+		self.candidates[block_index]
+			.iter()
+			.filter_map(|candidate_hash| {
+				let candidate = &self.candidate_data[candidate_hash];
+
+				let (sent_by, message) =
+					if candidate.group.contains(&ValidatorIndex(NODE_UNDER_TEST)) {
+						let seconded = candidate
+							.statements
+							.iter()
+							.find(|statement| statement.validator_index() == candidate.seconder)
+							.expect("The seconder always issues a statement; qed");
+						let peer =
+							&test_authorities.validator_authority_id[candidate.seconder.0 as usize];
+						if !network.is_peer_connected(peer) {
+							return None
+						}
+
+						(
+							candidate.seconder,
+							StatementDistributionMessage::Statement(
+								relay_parent,
+								seconded.as_unchecked().clone(),
+							),
+						)
+					} else {
+						let sent_by = routes[candidate.seconder.0 as usize]?;
+
+						(
+							sent_by,
+							StatementDistributionMessage::BackedCandidateManifest(
+								BackedCandidateManifest {
+									relay_parent,
+									candidate_hash: *candidate_hash,
+									group_index: candidate.group_index,
+									para_id: candidate.receipt.descriptor.para_id,
+									parent_head_data_hash: candidate
+										.persisted_validation_data
+										.parent_head
+										.hash(),
+									statement_knowledge: candidate.statement_knowledge(),
+								},
+							),
+						)
+					};
+
+				Some((sent_by, Versioned::V3(ValidationProtocol::StatementDistribution(message))))
+			})
+			.collect()
+	}
+}
+
+impl HandleNetworkMessage for StatementTestState {
+	fn handle(
+		&self,
+		message: NetworkMessage,
+		_node_sender: &mut UnboundedSender<NetworkMessage>,
+	) -> Option<NetworkMessage> {
+		match message {
+			NetworkMessage::RequestFromNode(peer, request) => match request {
+				Requests::AttestedCandidateV2(outgoing_request) => {
+					let candidate_hash = outgoing_request.payload.candidate_hash;
+					gum::debug!(target: LOG_TARGET, ?candidate_hash, "Received `AttestedCandidateRequest`");
+
+					let candidate = self
+						.candidate_data
+						.get(&candidate_hash)
+						.expect("candidate was generated previously; qed");
+					let response = Ok((
+						candidate.response(&outgoing_request.payload.mask).encode(),
+						ProtocolName::Static("dummy"),
+					));
+
+					if let Err(err) = outgoing_request.pending_response.send(response) {
+						gum::error!(target: LOG_TARGET, ?err, "Failed to send `AttestedCandidateResponse`");
+					}
+
+					None
+				},
+				_ => Some(NetworkMessage::RequestFromNode(peer, request)),
+			},
+			// Emulated peers drop the gossip they receive from the node under test.
+			NetworkMessage::MessageFromNode(_, _) => None,
+			message => Some(message),
+		}
+	}
+}
+
+fn build_overseer_for_statement_distribution(
+	spawn_task_handle: SpawnTaskHandle,
+	runtime_api: MockRuntimeApi,
+	chain_api: MockChainApi,
+	network_bridge: (MockNetworkBridgeTx, MockNetworkBridgeRx),
+	candidate_backing: MockCandidateBacking,
+	prospective_parachains: MockProspectiveParachains,
+	statement_distribution: StatementDistributionSubsystem<rand::rngs::StdRng>,
+) -> (Overseer<SpawnGlue<SpawnTaskHandle>, AlwaysSupportsParachains>, OverseerHandle) {
+	let overseer_connector = OverseerConnector::with_event_capacity(64000);
+	let dummy = dummy_builder!(spawn_task_handle);
+	let builder = dummy
+		.replace_runtime_api(|_| runtime_api)
+		.replace_chain_api(|_| chain_api)
+		.replace_network_bridge_tx(|_| network_bridge.0)
+		.replace_network_bridge_rx(|_| network_bridge.1)
+		.replace_candidate_backing(|_| candidate_backing)
+		.replace_prospective_parachains(|_| prospective_parachains)
+		.replace_statement_distribution(|_| statement_distribution);
+
+	let (overseer, raw_handle) =
+		builder.build_with_connector(overseer_connector).expect("Should not fail");
+
+	(overseer, OverseerHandle::new(raw_handle))
+}
+
+/// Takes a test configuration and uses it to create the `TestEnvironment`.
+pub fn prepare_test(config: TestConfiguration) -> (TestEnvironment, Arc<StatementTestState>) {
+	let dependencies = TestEnvironmentDependencies::default();
+	let test_authorities = config.generate_authorities();
+
+	gum::info!(target: LOG_TARGET, "{}", "Generating candidates and statements".bright_blue());
+	let state = Arc::new(StatementTestState::new(&config, &test_authorities));
+
+	let (network, network_interface, network_receiver) =
+		new_network(&config, &dependencies, &test_authorities, vec![state.clone()]);
+
+	let candidate_hashes = state
+		.block_hashes
+		.iter()
+		.zip(state.candidates.iter())
+		.map(|(block_hash, candidates)| {
+			let receipts = candidates
+				.iter()
+				.map(|candidate_hash| state.candidate_data[candidate_hash].receipt.to_plain())
+				.collect();
+			(*block_hash, receipts)
+		})
+		.collect();
+	let runtime_api = MockRuntimeApi::new(
+		config.clone(),
+		test_authorities.clone(),
+		candidate_hashes,
+		HashMap::new(),
+		None,
+		Default::default(),
+	);
+
+	let block_headers = (0..=config.num_blocks)
+		.map(|block_number| {
+			let parent_hash = match block_number {
+				0 => Default::default(),
+				1 => GENESIS_HASH,
+				_ => Hash::repeat_byte(block_number as u8 - 1),
+			};
+			let hash = match block_number {
+				0 => GENESIS_HASH,
+				_ => Hash::repeat_byte(block_number as u8),
+			};
+			(
+				hash,
+				Header {
+					digest: Default::default(),
+					number: block_number as BlockNumber,
+					parent_hash,
+					extrinsics_root: Default::default(),
+					state_root: Default::default(),
+				},
+			)
+		})
+		.collect();
+	let chain_api = MockChainApi::new(ChainApiState { block_headers });
+
+	let network_bridge_tx = MockNetworkBridgeTx::new(
+		network.clone(),
+		network_interface.subsystem_sender(),
+		test_authorities.clone(),
+	);
+
+	let req_protocol_names = ReqProtocolNames::new(GENESIS_HASH, None);
+	let (v1_req_receiver, _v1_req_cfg) = IncomingRequest::get_config_receiver(&req_protocol_names);
+	let (req_receiver, req_cfg) = IncomingRequest::get_config_receiver(&req_protocol_names);
+	let network_bridge_rx = MockNetworkBridgeRx::new(network_receiver, Some(req_cfg));
+
+	let keystore = test_authorities.keyring.keystore();
+	let own_backing_group = validator_groups(&config)
+		.into_iter()
+		.find(|group| group.contains(&ValidatorIndex(NODE_UNDER_TEST)))
+		.expect("The node under test is part of a backing group");
+	let candidate_backing = MockCandidateBacking::new(
+		NodeBackingState {
+			keystore: keystore.clone(),
+			validator_index: ValidatorIndex(NODE_UNDER_TEST),
+			validator_public: test_authorities.validator_public[NODE_UNDER_TEST as usize].clone(),
+			own_backing_group,
+		},
+		&dependencies.registry,
+	)
+	.unwrap();
+
+	let statement_distribution = StatementDistributionSubsystem::new(
+		keystore,
+		v1_req_receiver,
+		req_receiver,
+		Metrics::try_register(&dependencies.registry).unwrap(),
+		rand::rngs::StdRng::from_entropy(),
+	);
+
+	let (overseer, overseer_handle) = build_overseer_for_statement_distribution(
+		dependencies.task_manager.spawn_handle(),
+		runtime_api,
+		chain_api,
+		(network_bridge_tx, network_bridge_rx),
+		candidate_backing,
+		MockProspectiveParachains {},
+		statement_distribution,
+	);
+
+	(
+		TestEnvironment::new(
+			dependencies,
+			config,
+			network,
+			overseer,
+			overseer_handle,
+			test_authorities,
+		),
+		state,
+	)
+}
+
+pub async fn benchmark_statement_distribution(
+	env: &mut TestEnvironment,
+	state: Arc<StatementTestState>,
+) {
+	let config = env.config().clone();
+	let authorities = env.authorities().clone();
+	let network = env.network().clone();
+
+	env.metrics().set_n_validators(config.n_validators);
+	env.metrics().set_n_cores(config.n_cores);
+
+	env.send_message(AllMessages::StatementDistribution(
+		StatementDistributionSubsystemMessage::NetworkBridgeUpdate(
+			NetworkBridgeEvent::NewGossipTopology(NewGossipTopology {
+				session: 0,
+				topology: generate_topology(&authorities),
+				local_index: Some(ValidatorIndex(NODE_UNDER_TEST)),
+			}),
+		),
+	))
+	.await;
+	for event in generate_peer_connected(&network, &authorities) {
+		env.send_message(AllMessages::StatementDistribution(
+			StatementDistributionSubsystemMessage::NetworkBridgeUpdate(event),
+		))
+		.await;
+	}
+
+	let routes = compute_grid_routes(&authorities, &network, ValidatorIndex(NODE_UNDER_TEST));
+	let test_start = Instant::now();
+	let mut candidates_backed = 0;
+
+	for (block_index, block_hash) in state.block_hashes.iter().enumerate() {
+		let block_num = block_index + 1;
+		gum::info!(target: LOG_TARGET, "Current block #{}", block_num);
+		env.metrics().set_current_block(block_num);
+
+		let block_start_ts = Instant::now();
+		env.import_block(new_block_import_info(*block_hash, block_num as BlockNumber))
+			.await;
+		for event in generate_peer_view_change(&network, &authorities, *block_hash) {
+			env.send_message(AllMessages::StatementDistribution(
+				StatementDistributionSubsystemMessage::NetworkBridgeUpdate(event),
+			))
+			.await;
+		}
+
+		let messages = state.block_messages(block_index, &network, &authorities, &routes);
+		candidates_backed += messages.len();
+
+		for (sent_by, message) in messages {
+			let peer = &authorities.validator_authority_id[sent_by.0 as usize];
+			if network.send_message_from_peer(peer, message).is_err() {
+				gum::warn!(target: LOG_TARGET, ?sent_by, "Failed to send message from peer");
+			}
+		}
+
+		gum::info!(target: LOG_TARGET, "Waiting for {} candidates to be backed", candidates_backed);
+		env.wait_until_metric_eq("subsystem_benchmark_candidates_backed_total", candidates_backed)
+			.await;
+
+		let block_time = Instant::now().sub(block_start_ts).as_millis() as u64;
+		env.metrics().set_block_time(block_time);
+		gum::info!(target: LOG_TARGET, "All work for block completed in {}", format!("{:?}ms", block_time).cyan());
+	}
+
+	let duration: u128 = test_start.elapsed().as_millis();
+	gum::info!(target: LOG_TARGET, "All blocks processed in {}", format!("{:?}ms", duration).cyan());
+	gum::info!(target: LOG_TARGET,
+		"Avg block time: {}",
+		format!("{} ms", test_start.elapsed().as_millis() / env.config().num_blocks as u128).red()
+	);
+
+	env.display_network_usage();
+	env.display_cpu_usage(&["statement-distribution"]);
+	env.stop().await;
+}
@@ -26,14 +26,16 @@ use pyroscope_pprofrs::{pprof_backend, PprofConfig};
 
 use std::path::Path;
 
+pub(crate) mod approval;
 pub(crate) mod availability;
 pub(crate) mod cli;
 pub(crate) mod core;
+pub(crate) mod statement;
 mod valgrind;
 
 const LOG_TARGET: &str = "subsystem-bench";
 
-use availability::{prepare_test, NetworkEmulation, TestState};
+use availability::{NetworkEmulation, TestState};
 use cli::TestObjective;
 
 use core::{
@@ -103,6 +105,34 @@ struct BenchCli {
 	pub objective: cli::TestObjective,
 }
 
+// Prepares the environment of the test objective and runs it.
+fn run_test(test_config: TestConfiguration) {
+	match test_config.objective.clone() {
+		TestObjective::DataAvailabilityRead(_options) => {
+			let mut state = TestState::new(&test_config);
+			let (mut env, _protocol_config) = availability::prepare_test(test_config, &mut state);
+			env.runtime()
+				.block_on(availability::benchmark_availability_read(&mut env, state));
+		},
+		TestObjective::DataAvailabilityWrite => {
+			let mut state = TestState::new(&test_config);
+			let (mut env, _protocol_config) = availability::prepare_test(test_config, &mut state);
+			env.runtime()
+				.block_on(availability::benchmark_availability_write(&mut env, state));
+		},
+		TestObjective::ApprovalVoting(options) => {
+			let (mut env, state) = approval::prepare_test(test_config, options);
+			env.runtime().block_on(approval::benchmark_approvals(&mut env, state));
+		},
+		TestObjective::StatementDistribution => {
+			let (mut env, state) = statement::prepare_test(test_config);
+			env.runtime()
+				.block_on(statement::benchmark_statement_distribution(&mut env, state));
+		},
+		TestObjective::TestSequence(_options) => {},
+	}
+}
+
 impl BenchCli {
 	fn create_test_configuration(&self) -> TestConfiguration {
 		let configuration = &self.standard_configuration;
@@ -167,27 +197,14 @@ impl BenchCli {
 					display_configuration(&test_config);
 
 					match test_config.objective {
-						TestObjective::DataAvailabilityRead(ref _opts) => {
-							let mut state = TestState::new(&test_config);
-							let (mut env, _protocol_config) = prepare_test(test_config, &mut state);
-							env.runtime().block_on(availability::benchmark_availability_read(
-								&mut env, state,
-							));
-						},
-						TestObjective::DataAvailabilityWrite => {
-							let mut state = TestState::new(&test_config);
-							let (mut env, _protocol_config) = prepare_test(test_config, &mut state);
-							env.runtime().block_on(availability::benchmark_availability_write(
-								&mut env, state,
-							));
-						},
-						_ => gum::error!("Invalid test objective in sequence"),
+						TestObjective::TestSequence(_) =>
+							gum::error!("Invalid test objective in sequence"),
+						_ => run_test(test_config),
 					}
 				}
 				return Ok(())
 			},
-			TestObjective::DataAvailabilityRead(ref _options) => self.create_test_configuration(),
-			TestObjective::DataAvailabilityWrite => self.create_test_configuration(),
+			_ => self.create_test_configuration(),
 		};
 
 		let mut latency_config = test_config.latency.clone().unwrap_or_default();
@@ -219,20 +236,7 @@ impl BenchCli {
 
 		display_configuration(&test_config);
 
-		let mut state = TestState::new(&test_config);
-		let (mut env, _protocol_config) = prepare_test(test_config, &mut state);
-
-		match self.objective {
-			TestObjective::DataAvailabilityRead(_options) => {
-				env.runtime()
-					.block_on(availability::benchmark_availability_read(&mut env, state));
-			},
-			TestObjective::DataAvailabilityWrite => {
-				env.runtime()
-					.block_on(availability::benchmark_availability_write(&mut env, state));
-			},
-			TestObjective::TestSequence(_options) => {},
-		}
+		run_test(test_config);
 
 		if let Some(agent_running) = agent_running {
 			let agent_ready = agent_running.stop()?;
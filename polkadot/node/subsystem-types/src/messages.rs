@@ -45,7 +45,7 @@ use polkadot_primitives::{
 	async_backing, slashing,
 	vstaging::{ApprovalVotingParams, NodeFeatures},
 	AuthorityDiscoveryId, BackedCandidate, BlockNumber, CandidateEvent, CandidateHash,
-	CandidateIndex, CandidateReceipt, CollatorId, CommittedCandidateReceipt, CoreState,
+	CandidateIndex, CandidateReceipt, CollatorId, CommittedCandidateReceipt, CoreIndex, CoreState,
 	DisputeState, ExecutorParams, GroupIndex, GroupRotationInfo, Hash, Header as BlockHeader,
 	Id as ParaId, InboundDownwardMessage, InboundHrmpMessage, MultiDisputeStatementSet,
 	OccupiedCoreAssumption, PersistedValidationData, PvfCheckStatement, PvfExecKind, SessionIndex,
@@ -207,7 +207,8 @@ pub enum CollatorProtocolMessage {
 	/// This should be sent before any `DistributeCollation` message.
 	CollateOn(ParaId),
 	/// Provide a collation to distribute to validators with an optional result sender.
-	/// The second argument is the parent head-data hash.
+	/// The second argument is the parent head-data hash and the last one is the index of the
+	/// core the candidate should be backed on.
 	///
 	/// The result sender should be informed when at least one parachain validator seconded the
 	/// collation. It is also completely okay to just drop the sender.
@@ -216,6 +217,7 @@ pub enum CollatorProtocolMessage {
 		Hash,
 		PoV,
 		Option<oneshot::Sender<CollationSecondedSignal>>,
+		CoreIndex,
 	),
 	/// Report a collator as having provided an invalid collation. This should lead to disconnect
 	/// and blacklist of the collator.
@@ -473,6 +475,7 @@ pub enum AvailabilityRecoveryMessage {
 		CandidateReceipt,
 		SessionIndex,
 		Option<GroupIndex>, // Optional backing group to request from first.
+		Option<CoreIndex>,  // Optional core index the candidate was occupying.
 		oneshot::Sender<Result<AvailableData, crate::errors::RecoveryError>>,
 	),
 }
@@ -0,0 +1,194 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Mapping of validators to the availability chunk they are responsible for.
+//!
+//! Without the `AvailabilityChunkMapping` node feature, validator `i` holds chunk `i`. With it,
+//! the mapping is shifted by `core_index * systematic_threshold` so that the systematic chunks
+//! of candidates occupying different cores are held by different validators.
+
+use polkadot_erasure_coding::systematic_recovery_threshold;
+use polkadot_primitives::{
+	vstaging::{node_features::FeatureIndex, NodeFeatures},
+	CoreIndex, ValidatorIndex,
+};
+
+fn is_mapping_enabled(maybe_node_features: Option<&NodeFeatures>) -> bool {
+	maybe_node_features
+		.and_then(|features| features.get(FeatureIndex::AvailabilityChunkMapping as usize))
+		.map(|enabled| *enabled)
+		.unwrap_or(false)
+}
+
+/// Compute the index of the availability chunk that `validator_index` is responsible for, for a
+/// candidate occupying `core_index`.
+///
+/// The chunk index is represented as a `ValidatorIndex`, like the `index` of an `ErasureChunk`.
+pub fn availability_chunk_index(
+	maybe_node_features: Option<&NodeFeatures>,
+	n_validators: usize,
+	core_index: CoreIndex,
+	validator_index: ValidatorIndex,
+) -> Result<ValidatorIndex, polkadot_erasure_coding::Error> {
+	if !is_mapping_enabled(maybe_node_features) {
+		return Ok(validator_index)
+	}
+
+	let systematic_threshold = systematic_recovery_threshold(n_validators)? as u64;
+	let core_start_pos = core_index.0 as u64 * systematic_threshold;
+
+	Ok(ValidatorIndex(((core_start_pos + validator_index.0 as u64) % n_validators as u64) as u32))
+}
+
+/// Compute the availability chunk indices of all validators, for a candidate occupying
+/// `core_index`.
+///
+/// The returned vector is indexed by validator index. Prefer this over calling
+/// [`availability_chunk_index`] for each validator.
+pub fn availability_chunk_indices(
+	maybe_node_features: Option<&NodeFeatures>,
+	n_validators: usize,
+	core_index: CoreIndex,
+) -> Result<Vec<ValidatorIndex>, polkadot_erasure_coding::Error> {
+	let identity = (0..n_validators as u32).map(ValidatorIndex);
+
+	if !is_mapping_enabled(maybe_node_features) {
+		return Ok(identity.collect())
+	}
+
+	let systematic_threshold = systematic_recovery_threshold(n_validators)? as u64;
+	let core_start_pos = core_index.0 as u64 * systematic_threshold;
+
+	Ok(identity
+		.cycle()
+		.skip((core_start_pos % n_validators as u64) as usize)
+		.take(n_validators)
+		.collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::HashSet;
+
+	fn node_features_with_mapping_enabled() -> NodeFeatures {
+		let mut node_features = NodeFeatures::new();
+		node_features.resize(FeatureIndex::AvailabilityChunkMapping as usize + 1, false);
+		node_features.set(FeatureIndex::AvailabilityChunkMapping as usize, true);
+		node_features
+	}
+
+	fn node_features_with_other_bits_enabled() -> NodeFeatures {
+		let mut node_features = NodeFeatures::new();
+		node_features.resize(FeatureIndex::FirstUnassigned as usize + 1, true);
+		node_features.set(FeatureIndex::AvailabilityChunkMapping as usize, false);
+		node_features
+	}
+
+	#[test]
+	fn mapping_is_identity_without_the_feature() {
+		let n_validators = 11;
+		let n_cores = 4;
+
+		for node_features in
+			[None, Some(NodeFeatures::EMPTY), Some(node_features_with_other_bits_enabled())]
+		{
+			for core_index in 0..n_cores {
+				let indices = availability_chunk_indices(
+					node_features.as_ref(),
+					n_validators,
+					CoreIndex(core_index),
+				)
+				.unwrap();
+
+				for validator_index in 0..n_validators {
+					let validator_index = ValidatorIndex(validator_index as u32);
+					assert_eq!(indices[validator_index.0 as usize], validator_index);
+					assert_eq!(
+						availability_chunk_index(
+							node_features.as_ref(),
+							n_validators,
+							CoreIndex(core_index),
+							validator_index,
+						)
+						.unwrap(),
+						validator_index
+					);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn mapping_is_a_per_core_permutation() {
+		let node_features = node_features_with_mapping_enabled();
+
+		for n_validators in [2, 3, 10, 100, 1000] {
+			for core_index in 0..10 {
+				let indices = availability_chunk_indices(
+					Some(&node_features),
+					n_validators,
+					CoreIndex(core_index),
+				)
+				.unwrap();
+
+				assert_eq!(indices.len(), n_validators);
+				assert_eq!(indices.iter().collect::<HashSet<_>>().len(), n_validators);
+
+				for (validator_index, chunk_index) in indices.into_iter().enumerate() {
+					assert_eq!(
+						availability_chunk_index(
+							Some(&node_features),
+							n_validators,
+							CoreIndex(core_index),
+							ValidatorIndex(validator_index as u32),
+						)
+						.unwrap(),
+						chunk_index
+					);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn systematic_chunks_are_spread_over_validators() {
+		let node_features = node_features_with_mapping_enabled();
+		let n_validators = 100;
+		let systematic_threshold = systematic_recovery_threshold(n_validators).unwrap();
+
+		// The first validator holding a systematic chunk moves by `systematic_threshold` with
+		// each core.
+		for core_index in 0..5u32 {
+			let indices = availability_chunk_indices(
+				Some(&node_features),
+				n_validators,
+				CoreIndex(core_index),
+			)
+			.unwrap();
+
+			let first_systematic_holder = indices
+				.iter()
+				.position(|chunk_index| *chunk_index == ValidatorIndex(0))
+				.unwrap();
+			assert_eq!(
+				first_systematic_holder,
+				(n_validators - (core_index as usize * systematic_threshold) % n_validators) %
+					n_validators
+			);
+		}
+	}
+}
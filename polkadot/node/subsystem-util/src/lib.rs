@@ -80,6 +80,9 @@ pub mod inclusion_emulator;
 /// Convenient and efficient runtime info access.
 pub mod runtime;
 
+/// Mapping of validators to the availability chunks they hold.
+pub mod availability_chunks;
+
 /// Helpers for working with unreleased runtime calls
 pub mod vstaging;
 
@@ -91,6 +91,7 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 			secure_validator_mode: false,
 			workers_path,
 			workers_names: None,
+			pvf_execution_dump: None,
 			overseer_gen,
 			overseer_message_channel_capacity_override: None,
 			malus_finality_delay: None,
@@ -187,6 +188,7 @@ pub fn node_config(
 		rpc_max_subs_per_conn: Default::default(),
 		rpc_port: 9944,
 		rpc_message_buffer_capacity: Default::default(),
+		rpc_archive: Default::default(),
 		prometheus_config: None,
 		telemetry_endpoints: None,
 		default_heap_pages: None,
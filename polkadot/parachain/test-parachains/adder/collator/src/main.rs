@@ -90,6 +90,7 @@ fn main() -> Result<()> {
 						secure_validator_mode: false,
 						workers_path: None,
 						workers_names: None,
+						pvf_execution_dump: None,
 
 						overseer_gen: polkadot_service::CollatorOverseerGen,
 						overseer_message_channel_capacity_override: None,
@@ -68,9 +68,328 @@ pub mod node_features {
 		/// The value stored there represents the assumed core index where the candidates
 		/// are backed. This is needed for the elastic scaling MVP.
 		ElasticScalingMVP = 1,
+		/// Enables the use of `CandidateDescriptorV2`, which commits to the core index and the
+		/// session index instead of carrying a collator id and signature.
+		CandidateReceiptV2 = 2,
+		/// Enables the per-core shuffling of the validator-to-availability-chunk mapping, which
+		/// spreads the load of systematic chunk recovery over all validators.
+		AvailabilityChunkMapping = 3,
 		/// First unassigned feature bit.
 		/// Every time a new feature flag is assigned it should take this value.
 		/// and this should be incremented.
-		FirstUnassigned = 2,
+		FirstUnassigned = 4,
+	}
+}
+
+/// A type representing the version of the candidate descriptor and internal version number.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Copy, TypeInfo, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Hash))]
+pub struct InternalVersion(pub u8);
+
+/// A type representing the version of the candidate descriptor.
+#[derive(PartialEq, Eq, Clone, Copy, RuntimeDebug)]
+pub enum CandidateDescriptorVersion {
+	/// The old candidate descriptor version, carrying a collator id and signature.
+	V1,
+	/// Introduced with `CandidateDescriptorV2`, carrying a core index and session index.
+	V2,
+	/// An unknown version.
+	Unknown,
+}
+
+/// A unique descriptor of the candidate receipt, supporting both the v1 and v2 layouts.
+///
+/// The v2 layout reuses the space taken by the collator id and collator signature in
+/// [`CandidateDescriptor`] to commit to the core the candidate is meant to be backed on and
+/// the session of the relay parent. Both layouts have the same encoding, so any
+/// [`CandidateDescriptor`] can be interpreted as a `CandidateDescriptorV2` and vice versa.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Hash))]
+pub struct CandidateDescriptorV2<H = Hash> {
+	/// The ID of the para this is a candidate for.
+	para_id: Id,
+	/// The hash of the relay-chain block this is executed in the context of.
+	relay_parent: H,
+	/// Version field. The raw value here is not exposed, instead it is used
+	/// to determine the `CandidateDescriptorVersion`, see `fn version()`.
+	/// For the current version this field is set to `0` and will be incremented
+	/// by next versions.
+	version: InternalVersion,
+	/// The core index where the candidate is backed.
+	core_index: u16,
+	/// The session index of the candidate relay parent.
+	session_index: SessionIndex,
+	/// Reserved bytes.
+	reserved1: [u8; 25],
+	/// The blake2-256 hash of the persisted validation data. This is extra data derived from
+	/// relay-chain state which may vary based on bitfields included before the candidate.
+	/// Thus it cannot be derived entirely from the relay-parent.
+	persisted_validation_data_hash: Hash,
+	/// The blake2-256 hash of the PoV.
+	pov_hash: Hash,
+	/// The root of a block's erasure encoding Merkle tree.
+	erasure_root: Hash,
+	/// Reserved bytes.
+	reserved2: [u8; 64],
+	/// Hash of the para header that is being generated by this candidate.
+	para_head: Hash,
+	/// The blake2-256 hash of the validation code bytes.
+	validation_code_hash: ValidationCodeHash,
+}
+
+impl<H> CandidateDescriptorV2<H> {
+	/// Constructor for a v2 candidate descriptor.
+	pub fn new(
+		para_id: Id,
+		relay_parent: H,
+		core_index: CoreIndex,
+		session_index: SessionIndex,
+		persisted_validation_data_hash: Hash,
+		pov_hash: Hash,
+		erasure_root: Hash,
+		para_head: Hash,
+		validation_code_hash: ValidationCodeHash,
+	) -> Self {
+		Self {
+			para_id,
+			relay_parent,
+			version: InternalVersion(0),
+			core_index: core_index.0 as u16,
+			session_index,
+			reserved1: [0; 25],
+			persisted_validation_data_hash,
+			pov_hash,
+			erasure_root,
+			reserved2: [0; 64],
+			para_head,
+			validation_code_hash,
+		}
+	}
+
+	/// Returns the descriptor version.
+	///
+	/// Descriptors with non-zero reserved fields carry a collator id and signature and are
+	/// therefore v1 descriptors.
+	pub fn version(&self) -> CandidateDescriptorVersion {
+		if self.reserved1 != [0; 25] || self.reserved2 != [0; 64] {
+			return CandidateDescriptorVersion::V1
+		}
+
+		match self.version.0 {
+			0 => CandidateDescriptorVersion::V2,
+			_ => CandidateDescriptorVersion::Unknown,
+		}
+	}
+
+	/// Returns the core index the candidate is meant to be backed on, for v2 descriptors.
+	pub fn core_index(&self) -> Option<CoreIndex> {
+		match self.version() {
+			CandidateDescriptorVersion::V2 => Some(CoreIndex(self.core_index as u32)),
+			_ => None,
+		}
+	}
+
+	/// Returns the session index of the candidate relay parent, for v2 descriptors.
+	pub fn session_index(&self) -> Option<SessionIndex> {
+		match self.version() {
+			CandidateDescriptorVersion::V2 => Some(self.session_index),
+			_ => None,
+		}
+	}
+
+	/// Returns the ID of the para this is a candidate for.
+	pub fn para_id(&self) -> Id {
+		self.para_id
+	}
+
+	/// Returns the hash of the relay-chain block this is executed in the context of.
+	pub fn relay_parent(&self) -> &H {
+		&self.relay_parent
+	}
+
+	/// Returns the blake2-256 hash of the persisted validation data.
+	pub fn persisted_validation_data_hash(&self) -> Hash {
+		self.persisted_validation_data_hash
+	}
+
+	/// Returns the blake2-256 hash of the PoV.
+	pub fn pov_hash(&self) -> Hash {
+		self.pov_hash
+	}
+
+	/// Returns the root of the block's erasure encoding Merkle tree.
+	pub fn erasure_root(&self) -> Hash {
+		self.erasure_root
+	}
+
+	/// Returns the hash of the para header generated by this candidate.
+	pub fn para_head(&self) -> Hash {
+		self.para_head
+	}
+
+	/// Returns the blake2-256 hash of the validation code bytes.
+	pub fn validation_code_hash(&self) -> ValidationCodeHash {
+		self.validation_code_hash
+	}
+
+	/// Check the core index and session index this descriptor commits to.
+	///
+	/// `assigned_cores` are the cores the para is allowed to be backed on and `session_index`
+	/// is the session of the candidate relay parent. v1 descriptors don't commit to either and
+	/// always pass.
+	pub fn check_core_index_and_session(
+		&self,
+		assigned_cores: &[CoreIndex],
+		session_index: SessionIndex,
+	) -> Result<(), CandidateReceiptError> {
+		match self.version() {
+			CandidateDescriptorVersion::V1 => return Ok(()),
+			CandidateDescriptorVersion::V2 => {},
+			CandidateDescriptorVersion::Unknown =>
+				return Err(CandidateReceiptError::UnknownVersion(self.version)),
+		}
+
+		if assigned_cores.is_empty() {
+			return Err(CandidateReceiptError::NoAssignment)
+		}
+
+		let core_index = CoreIndex(self.core_index as u32);
+		if !assigned_cores.contains(&core_index) {
+			return Err(CandidateReceiptError::CoreIndexMismatch)
+		}
+
+		if self.session_index != session_index {
+			return Err(CandidateReceiptError::SessionIndexMismatch)
+		}
+
+		Ok(())
+	}
+}
+
+impl<H: Encode + Decode> From<CandidateDescriptor<H>> for CandidateDescriptorV2<H> {
+	fn from(descriptor: CandidateDescriptor<H>) -> Self {
+		Decode::decode(&mut &descriptor.encode()[..])
+			.expect("both descriptor versions have the same encoding; qed")
+	}
+}
+
+impl<H: Encode + Decode> From<CandidateDescriptorV2<H>> for CandidateDescriptor<H> {
+	fn from(descriptor: CandidateDescriptorV2<H>) -> Self {
+		Decode::decode(&mut &descriptor.encode()[..])
+			.expect("both descriptor versions have the same encoding; qed")
+	}
+}
+
+/// An error raised when checking the fields committed to by a v2 candidate descriptor.
+#[derive(PartialEq, Eq, Clone, Copy, RuntimeDebug)]
+pub enum CandidateReceiptError {
+	/// The descriptor version is not known.
+	UnknownVersion(InternalVersion),
+	/// The para is not assigned to any core.
+	NoAssignment,
+	/// The core index in the descriptor is not one of the cores assigned to the para.
+	CoreIndexMismatch,
+	/// The session index in the descriptor doesn't match the session of the relay parent.
+	SessionIndexMismatch,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use primitives::sr25519;
+
+	fn dummy_v1_descriptor() -> CandidateDescriptor {
+		CandidateDescriptor {
+			para_id: 1000.into(),
+			relay_parent: Hash::repeat_byte(1),
+			collator: CollatorId::from(sr25519::Public::from_raw([2; 32])),
+			persisted_validation_data_hash: Hash::repeat_byte(3),
+			pov_hash: Hash::repeat_byte(4),
+			erasure_root: Hash::repeat_byte(5),
+			signature: CollatorSignature::from(sr25519::Signature([6; 64])),
+			para_head: Hash::repeat_byte(7),
+			validation_code_hash: ValidationCodeHash::from(Hash::repeat_byte(8)),
+		}
+	}
+
+	fn dummy_v2_descriptor() -> CandidateDescriptorV2 {
+		CandidateDescriptorV2::new(
+			1000.into(),
+			Hash::repeat_byte(1),
+			CoreIndex(3),
+			7,
+			Hash::repeat_byte(3),
+			Hash::repeat_byte(4),
+			Hash::repeat_byte(5),
+			Hash::repeat_byte(7),
+			ValidationCodeHash::from(Hash::repeat_byte(8)),
+		)
+	}
+
+	#[test]
+	fn descriptor_versions_have_the_same_encoding() {
+		let v1 = dummy_v1_descriptor();
+		let v2: CandidateDescriptorV2 = v1.clone().into();
+		assert_eq!(v1.encode(), v2.encode());
+
+		let v2 = dummy_v2_descriptor();
+		let v1: CandidateDescriptor = v2.clone().into();
+		assert_eq!(v1.encode(), v2.encode());
+		assert_eq!(CandidateDescriptorV2::from(v1), v2);
+	}
+
+	#[test]
+	fn v1_descriptor_is_detected() {
+		let v2: CandidateDescriptorV2 = dummy_v1_descriptor().into();
+
+		assert_eq!(v2.version(), CandidateDescriptorVersion::V1);
+		assert_eq!(v2.core_index(), None);
+		assert_eq!(v2.session_index(), None);
+		assert_eq!(v2.para_id(), 1000.into());
+		assert_eq!(v2.pov_hash(), Hash::repeat_byte(4));
+		assert_eq!(v2.check_core_index_and_session(&[], 0), Ok(()));
+	}
+
+	#[test]
+	fn v2_descriptor_fields_are_exposed() {
+		let v2 = dummy_v2_descriptor();
+
+		assert_eq!(v2.version(), CandidateDescriptorVersion::V2);
+		assert_eq!(v2.core_index(), Some(CoreIndex(3)));
+		assert_eq!(v2.session_index(), Some(7));
+		assert_eq!(v2.relay_parent(), &Hash::repeat_byte(1));
+		assert_eq!(v2.para_head(), Hash::repeat_byte(7));
+	}
+
+	#[test]
+	fn unknown_version_is_rejected() {
+		let mut v2 = dummy_v2_descriptor();
+		v2.version = InternalVersion(1);
+
+		assert_eq!(v2.version(), CandidateDescriptorVersion::Unknown);
+		assert_eq!(v2.core_index(), None);
+		assert_eq!(
+			v2.check_core_index_and_session(&[CoreIndex(3)], 7),
+			Err(CandidateReceiptError::UnknownVersion(InternalVersion(1))),
+		);
+	}
+
+	#[test]
+	fn core_index_and_session_are_checked() {
+		let v2 = dummy_v2_descriptor();
+
+		assert_eq!(v2.check_core_index_and_session(&[CoreIndex(1), CoreIndex(3)], 7), Ok(()));
+		assert_eq!(
+			v2.check_core_index_and_session(&[], 7),
+			Err(CandidateReceiptError::NoAssignment),
+		);
+		assert_eq!(
+			v2.check_core_index_and_session(&[CoreIndex(1)], 7),
+			Err(CandidateReceiptError::CoreIndexMismatch),
+		);
+		assert_eq!(
+			v2.check_core_index_and_session(&[CoreIndex(3)], 8),
+			Err(CandidateReceiptError::SessionIndexMismatch),
+		);
 	}
 }
@@ -300,7 +300,13 @@ impl pallet_identity::Config for Test {
 	type PendingUsernameExpiration = ConstU32<100>;
 	type MaxSuffixLength = ConstU32<7>;
 	type MaxUsernameLength = ConstU32<32>;
+	type FieldVerifier = ();
+	type VerificationOracleOrigin = EnsureRoot<AccountId>;
+	type VerificationPeriod = ConstU32<100>;
+	type MaxVerifiedFields = ConstU32<8>;
 	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 impl identity_migrator::Config for Test {
@@ -37,11 +37,12 @@ use frame_system::pallet_prelude::*;
 use pallet_message_queue::OnQueueChanged;
 use parity_scale_codec::{Decode, Encode};
 use primitives::{
-	effective_minimum_backing_votes, supermajority_threshold, well_known_keys,
-	AvailabilityBitfield, BackedCandidate, CandidateCommitments, CandidateDescriptor,
-	CandidateHash, CandidateReceipt, CommittedCandidateReceipt, CoreIndex, GroupIndex, Hash,
-	HeadData, Id as ParaId, SignedAvailabilityBitfields, SigningContext, UpwardMessage,
-	ValidatorId, ValidatorIndex, ValidityAttestation,
+	effective_minimum_backing_votes, supermajority_threshold,
+	vstaging::{node_features::FeatureIndex, CandidateDescriptorV2, CandidateDescriptorVersion},
+	well_known_keys, AvailabilityBitfield, BackedCandidate, CandidateCommitments,
+	CandidateDescriptor, CandidateHash, CandidateReceipt, CommittedCandidateReceipt, CoreIndex,
+	GroupIndex, Hash, HeadData, Id as ParaId, SignedAvailabilityBitfields, SigningContext,
+	UpwardMessage, ValidatorId, ValidatorIndex, ValidityAttestation,
 };
 use scale_info::TypeInfo;
 use sp_runtime::{traits::One, DispatchError, SaturatedConversion, Saturating};
@@ -360,6 +361,12 @@ pub mod pallet {
 		/// either intentionally or as part of a concluded
 		/// invalid dispute.
 		BitfieldReferencesFreedCore,
+		/// The candidate descriptor has an unknown version.
+		UnknownDescriptorVersion,
+		/// The candidate descriptor commits to a core other than the one it is backed on.
+		InvalidCoreIndex,
+		/// The candidate descriptor commits to a session other than the current one.
+		InvalidSessionIndex,
 	}
 
 	/// The latest bitfield for each validator, referred to by their index in the validator set.
@@ -674,6 +681,9 @@ impl<T: Config> Pallet<T> {
 					Ok(rpn) => rpn,
 				};
 
+				check_ctx
+					.verify_descriptor_core_index(backed_candidate.descriptor(), *core_index)?;
+
 				let (validator_indices, _) =
 					backed_candidate.validator_indices_and_core_index(core_index_enabled);
 
@@ -1191,11 +1201,45 @@ impl<T: Config> CandidateCheckContext<T> {
 		Self { config: <configuration::Pallet<T>>::config(), prev_context }
 	}
 
+	/// Whether v2 candidate descriptors are accepted.
+	fn v2_receipts_enabled(&self) -> bool {
+		self.config
+			.node_features
+			.get(FeatureIndex::CandidateReceiptV2 as usize)
+			.map(|b| *b)
+			.unwrap_or(false)
+	}
+
+	/// Interpret the descriptor as a v2 descriptor, if v2 candidate descriptors are accepted.
+	fn descriptor_v2(
+		&self,
+		descriptor: &CandidateDescriptor<T::Hash>,
+	) -> Option<CandidateDescriptorV2<T::Hash>> {
+		self.v2_receipts_enabled()
+			.then(|| CandidateDescriptorV2::from(descriptor.clone()))
+	}
+
+	/// Check that a v2 candidate descriptor commits to the core the candidate is backed on.
+	///
+	/// v1 descriptors don't commit to a core and always pass.
+	pub(crate) fn verify_descriptor_core_index(
+		&self,
+		descriptor: &CandidateDescriptor<T::Hash>,
+		core_index: CoreIndex,
+	) -> Result<(), Error<T>> {
+		match self.descriptor_v2(descriptor).and_then(|d| d.core_index()) {
+			Some(committed_core_index) if committed_core_index != core_index =>
+				Err(Error::<T>::InvalidCoreIndex),
+			_ => Ok(()),
+		}
+	}
+
 	/// Execute verification of the candidate.
 	///
 	/// Assures:
 	///  * relay-parent in-bounds
-	///  * collator signature check passes
+	///  * collator signature check passes for v1 descriptors
+	///  * session index matches the current session for v2 descriptors
 	///  * code hash of commitments matches current code hash
 	///  * para head in the descriptor and commitments match
 	///
@@ -1237,10 +1281,20 @@ impl<T: Config> CandidateCheckContext<T> {
 			);
 		}
 
-		ensure!(
-			backed_candidate_receipt.descriptor().check_collator_signature().is_ok(),
-			Error::<T>::NotCollatorSigned,
-		);
+		let descriptor_v2 = self.descriptor_v2(backed_candidate_receipt.descriptor());
+		match descriptor_v2.as_ref().map(|d| d.version()) {
+			Some(CandidateDescriptorVersion::V2) => ensure!(
+				descriptor_v2.and_then(|d| d.session_index()) ==
+					Some(shared::Pallet::<T>::session_index()),
+				Error::<T>::InvalidSessionIndex,
+			),
+			Some(CandidateDescriptorVersion::Unknown) =>
+				return Err(Error::<T>::UnknownDescriptorVersion),
+			Some(CandidateDescriptorVersion::V1) | None => ensure!(
+				backed_candidate_receipt.descriptor().check_collator_signature().is_ok(),
+				Error::<T>::NotCollatorSigned,
+			),
+		}
 
 		let validation_code_hash = <paras::Pallet<T>>::current_code_hash(para_id)
 			// A candidate for a parachain without current validation code is not scheduled.
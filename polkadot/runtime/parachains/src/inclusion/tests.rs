@@ -27,7 +27,7 @@ use crate::{
 	shared::AllowedRelayParentsTracker,
 };
 use primitives::{
-	effective_minimum_backing_votes, SignedAvailabilityBitfields,
+	effective_minimum_backing_votes, SessionIndex, SignedAvailabilityBitfields,
 	UncheckedSignedAvailabilityBitfields,
 };
 
@@ -2033,6 +2033,240 @@ fn can_include_candidate_with_ok_code_upgrade() {
 	});
 }
 
+#[test]
+fn v2_descriptors_are_checked() {
+	let chain_a = ParaId::from(1_u32);
+
+	// The block number of the relay-parent for testing.
+	const RELAY_PARENT_NUM: BlockNumber = 4;
+
+	let paras = vec![(chain_a, ParaKind::Parachain)];
+	let validators = vec![
+		Sr25519Keyring::Alice,
+		Sr25519Keyring::Bob,
+		Sr25519Keyring::Charlie,
+		Sr25519Keyring::Dave,
+		Sr25519Keyring::Ferdie,
+	];
+	let keystore: KeystorePtr = Arc::new(LocalKeystore::in_memory());
+	for validator in validators.iter() {
+		Keystore::sr25519_generate_new(
+			&*keystore,
+			PARACHAIN_KEY_TYPE_ID,
+			Some(&validator.to_seed()),
+		)
+		.unwrap();
+	}
+	let validator_public = validator_pubkeys(&validators);
+
+	let mut genesis = genesis_config(paras);
+	let node_features = &mut genesis.configuration.config.node_features;
+	node_features.resize(FeatureIndex::CandidateReceiptV2 as usize + 1, false);
+	node_features.set(FeatureIndex::CandidateReceiptV2 as usize, true);
+
+	new_test_ext(genesis).execute_with(|| {
+		shared::Pallet::<Test>::set_active_validators_ascending(validator_public.clone());
+		shared::Pallet::<Test>::set_session_index(5);
+
+		run_to_block(5, |_| None);
+
+		let signing_context =
+			SigningContext { parent_hash: System::parent_hash(), session_index: 5 };
+
+		let group_validators = |group_index: GroupIndex| {
+			match group_index {
+				group_index if group_index == GroupIndex::from(0) => Some(vec![0, 1, 2, 3, 4]),
+				_ => panic!("Group index out of bounds for 1 parachain"),
+			}
+			.map(|vs| vs.into_iter().map(ValidatorIndex).collect::<Vec<_>>())
+		};
+
+		let validator_groups = vec![vec![
+			ValidatorIndex(0),
+			ValidatorIndex(1),
+			ValidatorIndex(2),
+			ValidatorIndex(3),
+			ValidatorIndex(4),
+		]];
+		Scheduler::set_validator_groups(validator_groups);
+
+		let allowed_relay_parents = default_allowed_relay_parent_tracker();
+		let chain_a_assignment = (chain_a, CoreIndex::from(0));
+
+		let make_backed_candidate = |core_index: CoreIndex, session_index: SessionIndex| {
+			let mut candidate = TestCandidateBuilder {
+				para_id: chain_a,
+				relay_parent: System::parent_hash(),
+				pov_hash: Hash::repeat_byte(1),
+				persisted_validation_data_hash: make_vdata_hash(chain_a).unwrap(),
+				hrmp_watermark: RELAY_PARENT_NUM,
+				..Default::default()
+			}
+			.build();
+
+			let descriptor = candidate.descriptor.clone();
+			candidate.descriptor = CandidateDescriptorV2::new(
+				descriptor.para_id,
+				descriptor.relay_parent,
+				core_index,
+				session_index,
+				descriptor.persisted_validation_data_hash,
+				descriptor.pov_hash,
+				descriptor.erasure_root,
+				descriptor.para_head,
+				descriptor.validation_code_hash,
+			)
+			.into();
+
+			back_candidate(
+				candidate,
+				&validators,
+				group_validators(GroupIndex::from(0)).unwrap().as_ref(),
+				&keystore,
+				&signing_context,
+				BackingKind::Threshold,
+				None,
+			)
+		};
+
+		// The descriptor commits to a core other than the one it is backed on.
+		assert_noop!(
+			ParaInclusion::process_candidates(
+				&allowed_relay_parents,
+				vec![(make_backed_candidate(CoreIndex(1), 5), chain_a_assignment.1)],
+				&group_validators,
+				false,
+			),
+			Error::<Test>::InvalidCoreIndex
+		);
+
+		// The descriptor commits to a session other than the current one.
+		assert_noop!(
+			ParaInclusion::process_candidates(
+				&allowed_relay_parents,
+				vec![(make_backed_candidate(CoreIndex(0), 4), chain_a_assignment.1)],
+				&group_validators,
+				false,
+			),
+			Error::<Test>::InvalidSessionIndex
+		);
+
+		// A v2 descriptor needs no collator signature.
+		let ProcessedCandidates { core_indices: occupied_cores, .. } =
+			ParaInclusion::process_candidates(
+				&allowed_relay_parents,
+				vec![(make_backed_candidate(CoreIndex(0), 5), chain_a_assignment.1)],
+				&group_validators,
+				false,
+			)
+			.expect("candidates scheduled, in order, and backed");
+
+		assert_eq!(occupied_cores, vec![(CoreIndex::from(0), chain_a)]);
+	});
+}
+
+#[test]
+fn v2_descriptors_are_rejected_if_not_enabled() {
+	let chain_a = ParaId::from(1_u32);
+
+	// The block number of the relay-parent for testing.
+	const RELAY_PARENT_NUM: BlockNumber = 4;
+
+	let paras = vec![(chain_a, ParaKind::Parachain)];
+	let validators = vec![
+		Sr25519Keyring::Alice,
+		Sr25519Keyring::Bob,
+		Sr25519Keyring::Charlie,
+		Sr25519Keyring::Dave,
+		Sr25519Keyring::Ferdie,
+	];
+	let keystore: KeystorePtr = Arc::new(LocalKeystore::in_memory());
+	for validator in validators.iter() {
+		Keystore::sr25519_generate_new(
+			&*keystore,
+			PARACHAIN_KEY_TYPE_ID,
+			Some(&validator.to_seed()),
+		)
+		.unwrap();
+	}
+	let validator_public = validator_pubkeys(&validators);
+
+	new_test_ext(genesis_config(paras)).execute_with(|| {
+		shared::Pallet::<Test>::set_active_validators_ascending(validator_public.clone());
+		shared::Pallet::<Test>::set_session_index(5);
+
+		run_to_block(5, |_| None);
+
+		let signing_context =
+			SigningContext { parent_hash: System::parent_hash(), session_index: 5 };
+
+		let group_validators = |group_index: GroupIndex| {
+			match group_index {
+				group_index if group_index == GroupIndex::from(0) => Some(vec![0, 1, 2, 3, 4]),
+				_ => panic!("Group index out of bounds for 1 parachain"),
+			}
+			.map(|vs| vs.into_iter().map(ValidatorIndex).collect::<Vec<_>>())
+		};
+
+		let validator_groups = vec![vec![
+			ValidatorIndex(0),
+			ValidatorIndex(1),
+			ValidatorIndex(2),
+			ValidatorIndex(3),
+			ValidatorIndex(4),
+		]];
+		Scheduler::set_validator_groups(validator_groups);
+
+		let allowed_relay_parents = default_allowed_relay_parent_tracker();
+		let chain_a_assignment = (chain_a, CoreIndex::from(0));
+
+		let mut candidate = TestCandidateBuilder {
+			para_id: chain_a,
+			relay_parent: System::parent_hash(),
+			pov_hash: Hash::repeat_byte(1),
+			persisted_validation_data_hash: make_vdata_hash(chain_a).unwrap(),
+			hrmp_watermark: RELAY_PARENT_NUM,
+			..Default::default()
+		}
+		.build();
+
+		let descriptor = candidate.descriptor.clone();
+		candidate.descriptor = CandidateDescriptorV2::new(
+			descriptor.para_id,
+			descriptor.relay_parent,
+			chain_a_assignment.1,
+			5,
+			descriptor.persisted_validation_data_hash,
+			descriptor.pov_hash,
+			descriptor.erasure_root,
+			descriptor.para_head,
+			descriptor.validation_code_hash,
+		)
+		.into();
+
+		let backed = back_candidate(
+			candidate,
+			&validators,
+			group_validators(GroupIndex::from(0)).unwrap().as_ref(),
+			&keystore,
+			&signing_context,
+			BackingKind::Threshold,
+			None,
+		);
+
+		// Without the node feature, the descriptor is treated as an unsigned v1 descriptor.
+		assert_noop!(
+			ParaInclusion::process_candidates(
+				&allowed_relay_parents,
+				vec![(backed, chain_a_assignment.1)],
+				&group_validators,
+				false,
+			),
+			Error::<Test>::NotCollatorSigned
+		);
+	});
+}
+
 #[test]
 fn check_allowed_relay_parents() {
 	let chain_a = ParaId::from(1);
@@ -43,12 +43,14 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 use pallet_babe::{self, ParentBlockRandomness};
 use primitives::{
-	effective_minimum_backing_votes, vstaging::node_features::FeatureIndex, BackedCandidate,
-	CandidateHash, CandidateReceipt, CheckedDisputeStatementSet, CheckedMultiDisputeStatementSet,
-	CoreIndex, DisputeStatementSet, InherentData as ParachainsInherentData,
-	MultiDisputeStatementSet, ScrapedOnChainVotes, SessionIndex, SignedAvailabilityBitfields,
-	SigningContext, UncheckedSignedAvailabilityBitfield, UncheckedSignedAvailabilityBitfields,
-	ValidatorId, ValidatorIndex, ValidityAttestation, PARACHAINS_INHERENT_IDENTIFIER,
+	effective_minimum_backing_votes,
+	vstaging::{node_features::FeatureIndex, CandidateDescriptorV2},
+	BackedCandidate, CandidateHash, CandidateReceipt, CheckedDisputeStatementSet,
+	CheckedMultiDisputeStatementSet, CoreIndex, DisputeStatementSet,
+	InherentData as ParachainsInherentData, MultiDisputeStatementSet, ScrapedOnChainVotes,
+	SessionIndex, SignedAvailabilityBitfields, SigningContext, UncheckedSignedAvailabilityBitfield,
+	UncheckedSignedAvailabilityBitfields, ValidatorId, ValidatorIndex, ValidityAttestation,
+	PARACHAINS_INHERENT_IDENTIFIER,
 };
 use rand::{seq::SliceRandom, SeedableRng};
 use scale_info::TypeInfo;
@@ -1215,6 +1217,8 @@ fn filter_backed_statements_from_disabled_validators<T: shared::Config + schedul
 /// If the para only has one scheduled core and no `CoreIndex` is injected, map the candidate to the
 /// single core. If the para has multiple cores scheduled, only map the candidates which have a
 /// proper core injected. Filter out the rest.
+/// Candidates with a v2 descriptor are mapped to the core committed to in the descriptor, which
+/// must be scheduled and match the injected one, if any.
 /// Also returns whether or not we dropped any candidates.
 fn map_candidates_to_cores<T: configuration::Config + scheduler::Config + inclusion::Config>(
 	allowed_relay_parents: &AllowedRelayParentsTracker<T::Hash, BlockNumberFor<T>>,
@@ -1224,8 +1228,15 @@ fn map_candidates_to_cores<T: configuration::Config + scheduler::Config + inclus
 ) -> Vec<(BackedCandidate<T::Hash>, CoreIndex)> {
 	let mut backed_candidates_with_core = Vec::with_capacity(candidates.len());
 
+	let v2_receipts = configuration::Pallet::<T>::config()
+		.node_features
+		.get(FeatureIndex::CandidateReceiptV2 as usize)
+		.map(|b| *b)
+		.unwrap_or(false);
+
 	// We keep a candidate if the parachain has only one core assigned or if
-	// a core index is provided by block author and it's indeed scheduled.
+	// a core index is provided by block author or committed to by the candidate descriptor
+	// and it's indeed scheduled.
 	for backed_candidate in candidates {
 		let maybe_injected_core_index = get_injected_core_index::<T>(
 			allowed_relay_parents,
@@ -1233,10 +1244,31 @@ fn map_candidates_to_cores<T: configuration::Config + scheduler::Config + inclus
 			core_index_enabled,
 		);
 
+		let maybe_committed_core_index = if v2_receipts {
+			CandidateDescriptorV2::from(backed_candidate.descriptor().clone()).core_index()
+		} else {
+			None
+		};
+
+		let maybe_core_index = match (maybe_committed_core_index, maybe_injected_core_index) {
+			(Some(committed), Some(injected)) if committed != injected => {
+				log::debug!(
+					target: LOG_TARGET,
+					"Candidate {:?} commits to core {:?} but was backed on core {:?}. Dropping the candidate.",
+					backed_candidate.candidate().hash(),
+					committed,
+					injected,
+				);
+				continue
+			},
+			(Some(committed), _) => Some(committed),
+			(None, injected) => injected,
+		};
+
 		let scheduled_cores = scheduled.get_mut(&backed_candidate.descriptor().para_id);
 		// Candidates without scheduled cores are silently filtered out.
 		if let Some(scheduled_cores) = scheduled_cores {
-			if let Some(core_idx) = maybe_injected_core_index {
+			if let Some(core_idx) = maybe_core_index {
 				if scheduled_cores.contains(&core_idx) {
 					scheduled_cores.remove(&core_idx);
 					backed_candidates_with_core.push((backed_candidate, core_idx));
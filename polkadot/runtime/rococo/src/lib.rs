@@ -99,8 +99,12 @@ use sp_staking::SessionIndex;
 #[cfg(any(feature = "std", test))]
 use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
-use xcm::{latest::prelude::*, VersionedLocation};
+use xcm::{latest::prelude::*, VersionedAssetId, VersionedAssets, VersionedLocation, VersionedXcm};
 use xcm_builder::PayOverXcm;
+use xcm_runtime_apis::{
+	dry_run::{CallDryRunEffects, Error as XcmDryRunApiError, XcmDryRunEffects},
+	fees::Error as XcmPaymentApiError,
+};
 
 pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
@@ -670,7 +674,40 @@ impl pallet_identity::Config for Runtime {
 	type PendingUsernameExpiration = ConstU32<{ 7 * DAYS }>;
 	type MaxSuffixLength = ConstU32<7>;
 	type MaxUsernameLength = ConstU32<32>;
+	type FieldVerifier = pallet_identity::SignedChallenge<Signature>;
+	type VerificationOracleOrigin = EnsureRoot<Self::AccountId>;
+	type VerificationPeriod = ConstU32<{ 365 * DAYS }>;
+	type MaxVerifiedFields = ConstU32<8>;
 	type WeightInfo = weights::pallet_identity::WeightInfo<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = IdentityBenchmarkHelper;
+}
+
+/// Creates identities with a verifiable `legal` field for the benchmarks of `pallet_identity`.
+///
+/// The field holds an sr25519 account generated in the benchmark keystore, which signs the
+/// challenge.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct IdentityBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_identity::BenchmarkHelper<IdentityInfo<MaxAdditionalFields>, Signature>
+	for IdentityBenchmarkHelper
+{
+	fn verifiable_identity(
+		challenge: impl FnOnce(&u64, &[u8]) -> Vec<u8>,
+	) -> Option<(IdentityInfo<MaxAdditionalFields>, u64, Signature)> {
+		use pallet_identity::{legacy::IdentityField, Data};
+		use sp_runtime::{traits::IdentifyAccount, MultiSigner};
+
+		let public = sp_io::crypto::sr25519_generate(0.into(), None);
+		let value = MultiSigner::Sr25519(public).into_account().encode();
+		let info =
+			IdentityInfo { legal: Data::Raw(value.clone().try_into().ok()?), ..Default::default() };
+		let field = IdentityField::Legal as u64;
+		let signature = sp_io::crypto::sr25519_sign(0.into(), &public, &challenge(&field, &value))?;
+		Some((info, field, Signature::Sr25519(signature)))
+	}
 }
 
 impl pallet_utility::Config for Runtime {
@@ -2197,6 +2234,36 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl xcm_runtime_apis::fees::XcmPaymentApi<Block> for Runtime {
+		fn query_acceptable_payment_assets(xcm_version: xcm::Version) -> Result<Vec<VersionedAssetId>, XcmPaymentApiError> {
+			let acceptable_assets = vec![AssetId(xcm_config::TokenLocation::get())];
+			XcmPallet::query_acceptable_payment_assets(xcm_version, acceptable_assets)
+		}
+
+		fn query_xcm_weight(message: VersionedXcm<()>) -> Result<Weight, XcmPaymentApiError> {
+			XcmPallet::query_xcm_weight(message)
+		}
+
+		fn query_weight_to_asset_fee(weight: Weight, asset: VersionedAssetId) -> Result<u128, XcmPaymentApiError> {
+			type Trader = <xcm_config::XcmConfig as xcm_executor::Config>::Trader;
+			XcmPallet::query_weight_to_asset_fee::<Trader>(weight, asset)
+		}
+
+		fn query_delivery_fees(destination: VersionedLocation, message: VersionedXcm<()>) -> Result<VersionedAssets, XcmPaymentApiError> {
+			XcmPallet::query_delivery_fees(destination, message)
+		}
+	}
+
+	impl xcm_runtime_apis::dry_run::DryRunApi<Block, RuntimeCall, RuntimeEvent, OriginCaller> for Runtime {
+		fn dry_run_call(origin: OriginCaller, call: RuntimeCall) -> Result<CallDryRunEffects<RuntimeEvent>, XcmDryRunApiError> {
+			XcmPallet::dry_run_call(origin, call)
+		}
+
+		fn dry_run_xcm(origin_location: VersionedLocation, xcm: VersionedXcm<RuntimeCall>) -> Result<XcmDryRunEffects<RuntimeEvent>, XcmDryRunApiError> {
+			XcmPallet::dry_run_xcm(origin_location, xcm)
+		}
+	}
+
 	impl pallet_beefy_mmr::BeefyMmrApi<Block, Hash> for RuntimeApi {
 		fn authority_set_proof() -> beefy_primitives::mmr::BeefyAuthoritySet<Hash> {
 			MmrLeaf::authority_set_proof()
@@ -336,6 +336,7 @@ impl pallet_staking::Config for Runtime {
 	type CurrencyToVote = runtime_common::CurrencyToVote;
 	type RewardRemainder = ();
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type Slash = ();
 	type Reward = ();
 	type SessionsPerEra = SessionsPerEra;
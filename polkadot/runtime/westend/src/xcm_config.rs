@@ -47,7 +47,7 @@ use xcm_builder::{
 	ChildParachainConvertsVia, DescribeBodyTerminal, DescribeFamily, FrameTransactionalProcessor,
 	HashedDescription, IsConcrete, MintLocation, OriginToPluralityVoice, SignedAccountId32AsNative,
 	SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit, TrailingSetTopicAsId,
-	UsingComponents, WeightInfoBounds, WithComputedOrigin, WithUniqueTopic,
+	UsingComponents, WeightInfoBounds, WithComputedOrigin, WithRecording, WithUniqueTopic,
 	XcmFeeManagerFromComponents, XcmFeeToAccount,
 };
 use xcm_executor::XcmExecutor;
@@ -109,8 +109,11 @@ pub type PriceForChildParachainDelivery =
 /// The XCM router. When we want to send an XCM message, we use this type. It amalgamates all of our
 /// individual routers.
 pub type XcmRouter = WithUniqueTopic<
-	// Only one router so far - use DMP to communicate with child parachains.
-	ChildParachainRouter<Runtime, XcmPallet, PriceForChildParachainDelivery>,
+	// Record sent messages so they can be reported when dry-running.
+	WithRecording<
+		// Only one router so far - use DMP to communicate with child parachains.
+		ChildParachainRouter<Runtime, XcmPallet, PriceForChildParachainDelivery>,
+	>,
 >;
 
 parameter_types! {
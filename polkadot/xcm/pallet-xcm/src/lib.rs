@@ -50,17 +50,22 @@ use sp_runtime::{
 use sp_std::{boxed::Box, marker::PhantomData, prelude::*, result::Result, vec};
 use xcm::{latest::QueryResponseInfo, prelude::*};
 use xcm_builder::{
-	ExecuteController, ExecuteControllerWeightInfo, QueryController, QueryControllerWeightInfo,
-	SendController, SendControllerWeightInfo,
+	record_sent_xcms, ExecuteController, ExecuteControllerWeightInfo, QueryController,
+	QueryControllerWeightInfo, SendController, SendControllerWeightInfo,
 };
 use xcm_executor::{
 	traits::{
 		AssetTransferError, CheckSuspension, ClaimAssets, ConvertLocation, ConvertOrigin,
 		DropAssets, MatchesFungible, OnResponse, Properties, QueryHandler, QueryResponseStatus,
-		TransactAsset, TransferType, VersionChangeNotifier, WeightBounds, XcmAssetTransfers,
+		TransactAsset, TransferType, VersionChangeNotifier, WeightBounds, WeightTrader,
+		XcmAssetTransfers,
 	},
 	AssetsInHolding,
 };
+use xcm_runtime_apis::{
+	dry_run::{CallDryRunEffects, Error as XcmDryRunApiError, XcmDryRunEffects},
+	fees::Error as XcmPaymentApiError,
+};
 
 #[cfg(any(feature = "try-runtime", test))]
 use sp_runtime::TryRuntimeError;
@@ -2287,6 +2292,191 @@ impl<T: Config> Pallet<T> {
 		T::XcmRouter::deliver(ticket)
 	}
 
+	/// Dry-run `call` dispatched from `origin` on top of the current state.
+	///
+	/// Returns the dispatch result, the events emitted by the call and the messages sent while
+	/// dispatching it. Messages are only reported if `T::XcmRouter` is wrapped in
+	/// [`xcm_builder::WithRecording`].
+	///
+	/// This does not roll back any changes; it is meant to be called from a runtime API, whose
+	/// state changes are discarded anyway.
+	pub fn dry_run_call<OriginCaller>(
+		origin: OriginCaller,
+		call: <T as Config>::RuntimeCall,
+	) -> Result<CallDryRunEffects<<T as frame_system::Config>::RuntimeEvent>, XcmDryRunApiError>
+	where
+		<T as Config>::RuntimeOrigin: From<OriginCaller>,
+	{
+		frame_system::Pallet::<T>::reset_events();
+		let (execution_result, sent) = record_sent_xcms(|| call.dispatch(origin.into()));
+		let emitted_events = Self::take_dry_run_events();
+		Ok(CallDryRunEffects {
+			execution_result,
+			emitted_events,
+			forwarded_xcms: Self::group_forwarded_xcms(sent),
+		})
+	}
+
+	/// Dry-run the XCM `xcm` executed from `origin_location` on top of the current state.
+	///
+	/// The program is executed without a weight limit and without any weight credit. Like
+	/// [`Self::dry_run_call`], this does not roll back any changes.
+	pub fn dry_run_xcm(
+		origin_location: VersionedLocation,
+		xcm: VersionedXcm<<T as Config>::RuntimeCall>,
+	) -> Result<XcmDryRunEffects<<T as frame_system::Config>::RuntimeEvent>, XcmDryRunApiError> {
+		let origin_location: Location = origin_location.try_into().map_err(|error| {
+			log::error!(
+				target: "xcm::dry_run_xcm",
+				"Location version conversion failed with error: {:?}",
+				error,
+			);
+			XcmDryRunApiError::VersionedConversionFailed
+		})?;
+		let xcm: Xcm<<T as Config>::RuntimeCall> = xcm.try_into().map_err(|error| {
+			log::error!(
+				target: "xcm::dry_run_xcm",
+				"Xcm version conversion failed with error {:?}",
+				error,
+			);
+			XcmDryRunApiError::VersionedConversionFailed
+		})?;
+		let mut hash = xcm.using_encoded(sp_io::hashing::blake2_256);
+
+		frame_system::Pallet::<T>::reset_events();
+		let (execution_result, sent) = record_sent_xcms(|| {
+			T::XcmExecutor::prepare_and_execute(
+				origin_location,
+				xcm,
+				&mut hash,
+				Weight::MAX,
+				Weight::zero(),
+			)
+		});
+		let emitted_events = Self::take_dry_run_events();
+		Ok(XcmDryRunEffects {
+			execution_result,
+			emitted_events,
+			forwarded_xcms: Self::group_forwarded_xcms(sent),
+		})
+	}
+
+	/// Convert the given `asset_ids` into `version`.
+	///
+	/// Meant to be used by runtimes to answer `XcmPaymentApi::query_acceptable_payment_assets`
+	/// with the assets their trader accepts.
+	pub fn query_acceptable_payment_assets(
+		version: XcmVersion,
+		asset_ids: Vec<AssetId>,
+	) -> Result<Vec<VersionedAssetId>, XcmPaymentApiError> {
+		asset_ids
+			.into_iter()
+			.map(|asset_id| {
+				VersionedAssetId::from(asset_id)
+					.into_version(version)
+					.map_err(|_| XcmPaymentApiError::UnhandledXcmVersion)
+			})
+			.collect()
+	}
+
+	/// Returns the weight of executing `message` according to `T::Weigher`.
+	pub fn query_xcm_weight(message: VersionedXcm<()>) -> Result<Weight, XcmPaymentApiError> {
+		let message = Xcm::<()>::try_from(message)
+			.map_err(|_| XcmPaymentApiError::VersionedConversionFailed)?;
+
+		T::Weigher::weight(&mut message.into()).map_err(|()| {
+			log::error!(target: "xcm::pallet_xcm::query_xcm_weight", "Error when querying XCM weight");
+			XcmPaymentApiError::WeightNotComputable
+		})
+	}
+
+	/// Returns the fee for `weight` in `asset`, as charged by `Trader`.
+	///
+	/// The fee is found by buying `weight` from a fresh `Trader` with the maximum amount of
+	/// `asset` and looking at what is left over. Any side effects of the trader are only
+	/// acceptable because this is meant to be called from a runtime API, whose state changes are
+	/// discarded.
+	pub fn query_weight_to_asset_fee<Trader: WeightTrader>(
+		weight: Weight,
+		asset: VersionedAssetId,
+	) -> Result<u128, XcmPaymentApiError> {
+		let asset: AssetId =
+			asset.try_into().map_err(|_| XcmPaymentApiError::VersionedConversionFailed)?;
+
+		let payment: AssetsInHolding = Asset { id: asset.clone(), fun: Fungible(u128::MAX) }.into();
+		let context = XcmContext::with_message_id([0u8; 32]);
+		let unused = Trader::new().buy_weight(weight, payment, &context).map_err(|error| {
+			log::trace!(
+				target: "xcm::pallet_xcm::query_weight_to_asset_fee",
+				"Asset {:?} can't be used to buy weight: {:?}",
+				asset,
+				error,
+			);
+			XcmPaymentApiError::AssetNotFound
+		})?;
+
+		let unused_amount = unused.fungible.get(&asset).copied().unwrap_or(0);
+		Ok(u128::MAX.saturating_sub(unused_amount))
+	}
+
+	/// Returns the fees charged by `T::XcmRouter` for delivering `message` to `destination`.
+	///
+	/// The fees are returned in the version of `message`.
+	pub fn query_delivery_fees(
+		destination: VersionedLocation,
+		message: VersionedXcm<()>,
+	) -> Result<VersionedAssets, XcmPaymentApiError> {
+		let result_version = match message {
+			VersionedXcm::V2(_) => 2,
+			VersionedXcm::V3(_) => 3,
+			VersionedXcm::V4(_) => 4,
+		};
+
+		let destination: Location = destination
+			.try_into()
+			.map_err(|_| XcmPaymentApiError::VersionedConversionFailed)?;
+		let message: Xcm<()> =
+			message.try_into().map_err(|_| XcmPaymentApiError::VersionedConversionFailed)?;
+
+		let (_, fees) = validate_send::<T::XcmRouter>(destination, message).map_err(|error| {
+			log::error!(
+				target: "xcm::pallet_xcm::query_delivery_fees",
+				"Error when querying delivery fees: {:?}",
+				error,
+			);
+			XcmPaymentApiError::Unroutable
+		})?;
+
+		VersionedAssets::from(fees)
+			.into_version(result_version)
+			.map_err(|_| XcmPaymentApiError::VersionedConversionFailed)
+	}
+
+	/// Take all events deposited since the last reset.
+	fn take_dry_run_events() -> Vec<<T as frame_system::Config>::RuntimeEvent> {
+		let events = frame_system::Pallet::<T>::read_events_no_consensus()
+			.map(|record| record.event.clone())
+			.collect();
+		frame_system::Pallet::<T>::reset_events();
+		events
+	}
+
+	/// Group the `sent` messages by their destination, keeping the order of first appearance.
+	fn group_forwarded_xcms(
+		sent: Vec<(Location, Xcm<()>)>,
+	) -> Vec<(VersionedLocation, Vec<VersionedXcm<()>>)> {
+		let mut forwarded: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)> = Vec::new();
+		for (dest, message) in sent {
+			let dest = VersionedLocation::from(dest);
+			let message = VersionedXcm::from(message);
+			match forwarded.iter_mut().find(|(d, _)| *d == dest) {
+				Some((_, messages)) => messages.push(message),
+				None => forwarded.push((dest, vec![message])),
+			}
+		}
+		forwarded
+	}
+
 	pub fn check_account() -> T::AccountId {
 		const ID: PalletId = PalletId(*b"py/xcmch");
 		AccountIdConversion::<T::AccountId>::into_account_truncating(&ID)
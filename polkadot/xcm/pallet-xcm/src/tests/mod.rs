@@ -26,7 +26,10 @@ use crate::{
 use frame_support::{
 	assert_noop, assert_ok,
 	traits::{Currency, Hooks},
-	weights::Weight,
+	weights::{
+		constants::{WEIGHT_PROOF_SIZE_PER_MB, WEIGHT_REF_TIME_PER_SECOND},
+		Weight,
+	},
 };
 use polkadot_parachain_primitives::primitives::Id as ParaId;
 use sp_runtime::traits::{AccountIdConversion, BlakeTwo256, Hash};
@@ -390,6 +393,130 @@ fn execute_withdraw_to_deposit_works() {
 	});
 }
 
+/// Test that dry-running a call reports its result, events and forwarded messages.
+#[test]
+fn dry_run_call_works() {
+	let balances = vec![
+		(ALICE, INITIAL_BALANCE),
+		(ParaId::from(OTHER_PARA_ID).into_account_truncating(), INITIAL_BALANCE),
+	];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let sender: Location = AccountId32 { network: None, id: ALICE.into() }.into();
+		let message = Xcm(vec![ClearOrigin]);
+		let call = RuntimeCall::XcmPallet(crate::Call::send {
+			dest: Box::new(RelayLocation::get().into()),
+			message: Box::new(VersionedXcm::from(message.clone())),
+		});
+
+		let origin = OriginCaller::system(frame_system::RawOrigin::Signed(ALICE));
+		let effects = XcmPallet::dry_run_call(origin, call).unwrap();
+		assert_ok!(effects.execution_result);
+
+		let sent_message =
+			Xcm(vec![DescendOrigin(sender.clone().try_into().unwrap()), ClearOrigin]);
+		assert_eq!(
+			effects.forwarded_xcms,
+			vec![(
+				VersionedLocation::from(RelayLocation::get()),
+				vec![VersionedXcm::from(sent_message.clone())]
+			)]
+		);
+		assert_eq!(
+			effects.emitted_events.last(),
+			Some(&RuntimeEvent::XcmPallet(crate::Event::Sent {
+				origin: sender,
+				destination: RelayLocation::get(),
+				message,
+				message_id: fake_message_hash(&sent_message),
+			}))
+		);
+	});
+}
+
+/// Test that dry-running an XCM reports its outcome and forwarded messages.
+#[test]
+fn dry_run_xcm_works() {
+	let balances = vec![
+		(ALICE, INITIAL_BALANCE),
+		(ParaId::from(OTHER_PARA_ID).into_account_truncating(), INITIAL_BALANCE),
+	];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let origin: Location = AccountId32 { network: None, id: ALICE.into() }.into();
+		let dest: Location = Parachain(OTHER_PARA_ID).into();
+		let beneficiary: Location = AccountId32 { network: None, id: BOB.into() }.into();
+		let xcm = Xcm(vec![
+			WithdrawAsset((Here, SEND_AMOUNT).into()),
+			buy_execution((Here, SEND_AMOUNT)),
+			DepositReserveAsset {
+				assets: AllCounted(1).into(),
+				dest: dest.clone(),
+				xcm: Xcm(vec![DepositAsset { assets: AllCounted(1).into(), beneficiary }]),
+			},
+		]);
+
+		let effects =
+			XcmPallet::dry_run_xcm(VersionedLocation::from(origin), VersionedXcm::from(xcm))
+				.unwrap();
+		assert_eq!(effects.execution_result, Outcome::Complete { used: BaseXcmWeight::get() * 3 });
+		assert_eq!(effects.forwarded_xcms.len(), 1);
+		assert_eq!(effects.forwarded_xcms[0].0, VersionedLocation::from(dest));
+		assert_eq!(effects.forwarded_xcms[0].1.len(), 1);
+		assert!(!effects.emitted_events.is_empty());
+	});
+}
+
+/// Test that the weight and fees of an XCM can be queried.
+#[test]
+fn query_xcm_fees_works() {
+	use xcm_runtime_apis::fees::Error as XcmPaymentApiError;
+	type Trader = <XcmConfig as xcm_executor::Config>::Trader;
+
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let message = Xcm::<()>(vec![ClearOrigin, ClearOrigin]);
+		assert_eq!(
+			XcmPallet::query_xcm_weight(VersionedXcm::from(message.clone())),
+			Ok(BaseXcmWeight::get() * 2)
+		);
+
+		assert_eq!(
+			XcmPallet::query_acceptable_payment_assets(3, vec![AssetId(Here.into())]),
+			Ok(vec![VersionedAssetId::V3(xcm::v3::AssetId::Concrete(xcm::v3::Location::here()))])
+		);
+
+		// `CurrencyPerSecondPerByte` charges one unit per second and one unit per MB.
+		let weight = Weight::from_parts(2 * WEIGHT_REF_TIME_PER_SECOND, WEIGHT_PROOF_SIZE_PER_MB);
+		assert_eq!(
+			XcmPallet::query_weight_to_asset_fee::<Trader>(
+				weight,
+				VersionedAssetId::from(AssetId(Here.into()))
+			),
+			Ok(3)
+		);
+		assert_eq!(
+			XcmPallet::query_weight_to_asset_fee::<Trader>(
+				weight,
+				VersionedAssetId::from(AssetId(Parent.into()))
+			),
+			Err(XcmPaymentApiError::AssetNotFound)
+		);
+
+		assert_eq!(
+			XcmPallet::query_delivery_fees(
+				VersionedLocation::from(Para3000Location::get()),
+				VersionedXcm::from(message.clone())
+			),
+			Ok(VersionedAssets::from(Para3000PaymentAssets::get()))
+		);
+		assert_eq!(
+			XcmPallet::query_delivery_fees(
+				VersionedLocation::from(Location::new(0, [Parachain(8); 8])),
+				VersionedXcm::from(message)
+			),
+			Err(XcmPaymentApiError::Unroutable)
+		);
+	});
+}
+
 /// Test drop/claim assets.
 #[test]
 fn trapped_assets_can_be_claimed() {
@@ -18,9 +18,11 @@
 
 use frame_system::unique;
 use parity_scale_codec::Encode;
-use sp_std::{marker::PhantomData, result::Result};
+use sp_std::{marker::PhantomData, result::Result, vec::Vec};
 use xcm::prelude::*;
 
+environmental::environmental!(recorded_xcms: Vec<(Location, Xcm<()>)>);
+
 /// Wrapper router which, if the message does not already end with a `SetTopic` instruction,
 /// appends one to the message filled with a universally unique ID. This ID is returned from a
 /// successful `deliver`.
@@ -104,3 +106,47 @@ impl<Inner: SendXcm, TopicSource: SourceTopic> SendXcm for WithTopicSource<Inner
 		Ok(unique_id)
 	}
 }
+
+/// Wrapper router which records every message it successfully delivers, together with its
+/// destination, whenever it is called within [`record_sent_xcms`].
+///
+/// Outside of [`record_sent_xcms`] this is a transparent pass-through to `Inner`, so it can be
+/// left in the runtime's router configuration. Messages are only cloned while recording.
+///
+/// Since only delivered messages are recorded, this should wrap the final router tuple, but sit
+/// below any wrapper that mutates the message (like [`WithUniqueTopic`]).
+pub struct WithRecording<Inner>(PhantomData<Inner>);
+impl<Inner: SendXcm> SendXcm for WithRecording<Inner> {
+	type Ticket = (Inner::Ticket, Option<(Location, Xcm<()>)>);
+
+	fn validate(
+		destination: &mut Option<Location>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let record = match (recorded_xcms::with(|_| ()), destination.as_ref(), message.as_ref()) {
+			(Some(()), Some(dest), Some(msg)) => Some((dest.clone(), msg.clone())),
+			_ => None,
+		};
+		let (ticket, assets) = Inner::validate(destination, message)?;
+		Ok(((ticket, record), assets))
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		let (ticket, record) = ticket;
+		let hash = Inner::deliver(ticket)?;
+		if let Some(record) = record {
+			recorded_xcms::with(|recorded| recorded.push(record));
+		}
+		Ok(hash)
+	}
+}
+
+/// Execute `f` while recording all messages delivered through [`WithRecording`].
+///
+/// Returns the result of `f` together with the delivered messages and their destinations, in
+/// the order of delivery. Nothing is recorded for messages that fail to be delivered.
+pub fn record_sent_xcms<R>(f: impl FnOnce() -> R) -> (R, Vec<(Location, Xcm<()>)>) {
+	let mut recorded = Vec::new();
+	let result = recorded_xcms::using(&mut recorded, f);
+	(result, recorded)
+}
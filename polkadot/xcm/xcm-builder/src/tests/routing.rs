@@ -0,0 +1,50 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+
+type Router = WithRecording<TestMessageSender>;
+
+#[test]
+fn with_recording_only_records_within_scope() {
+	let message = Xcm::<()>(vec![ClearOrigin]);
+	let dest: Location = Parachain(1).into();
+
+	// Not recording: the message is sent as usual.
+	assert!(send_xcm::<Router>(dest.clone(), message.clone()).is_ok());
+	assert_eq!(sent_xcm().len(), 1);
+
+	let (result, recorded) = record_sent_xcms(|| {
+		send_xcm::<Router>(dest.clone(), message.clone()).map(|(hash, _)| hash)
+	});
+	assert_eq!(result, Ok(fake_message_hash(&message)));
+	assert_eq!(recorded, vec![(dest, message)]);
+	// Recording does not prevent delivery.
+	assert_eq!(sent_xcm().len(), 2);
+}
+
+#[test]
+fn with_recording_nested_in_unique_topic_records_final_message() {
+	let message = Xcm::<()>(vec![ClearOrigin]);
+	let dest: Location = Parent.into();
+
+	let (result, recorded) =
+		record_sent_xcms(|| send_xcm::<WithUniqueTopic<Router>>(dest.clone(), message.clone()));
+	let (topic, _) = result.unwrap();
+	let mut expected = message;
+	expected.0.push(SetTopic(topic));
+	assert_eq!(recorded, vec![(dest, expected)]);
+}
@@ -0,0 +1,88 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for dry-running calls and XCM programs.
+//!
+//! Dry-running executes the given call or program on top of the current state and reports the
+//! outcome, the emitted events and all XCMs that would have been sent. None of the changes are
+//! persisted.
+
+use codec::{Decode, Encode};
+use frame_support::pallet_prelude::DispatchResultWithPostInfo;
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+use xcm::prelude::*;
+
+/// Effects of dry-running an extrinsic.
+#[derive(Encode, Decode, Debug, TypeInfo)]
+pub struct CallDryRunEffects<Event> {
+	/// The result of executing the call.
+	pub execution_result: DispatchResultWithPostInfo,
+	/// The list of events fired by the call.
+	pub emitted_events: Vec<Event>,
+	/// The XCMs that were queued for sending, grouped by destination.
+	pub forwarded_xcms: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>,
+}
+
+/// Effects of dry-running an XCM program.
+#[derive(Encode, Decode, Debug, TypeInfo)]
+pub struct XcmDryRunEffects<Event> {
+	/// The outcome of the XCM program execution.
+	pub execution_result: Outcome,
+	/// The list of events fired by the XCM program execution.
+	pub emitted_events: Vec<Event>,
+	/// The XCMs that were queued for sending, grouped by destination.
+	pub forwarded_xcms: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// API for dry-running extrinsics and XCM programs.
+	///
+	/// Besides the result of the execution and the emitted events, both calls return the XCMs that
+	/// would have been sent, grouped by their destination. These can be used to find out what
+	/// happens further down the line, e.g. by dry-running them on the destination chain.
+	///
+	/// Calls or XCMs might fail when executed, this doesn't mean the result of these calls will be
+	/// an `Err`. In those cases, there might still be a valid result, with the execution error
+	/// inside it. The only reasons why these calls might return an error are listed in the
+	/// [`Error`] enum.
+	pub trait DryRunApi<Call, Event, OriginCaller>
+	where
+		Call: Encode,
+		Event: Decode,
+		OriginCaller: Encode,
+	{
+		/// Dry run a call.
+		fn dry_run_call(origin: OriginCaller, call: Call) -> Result<CallDryRunEffects<Event>, Error>;
+
+		/// Dry run an XCM program.
+		fn dry_run_xcm(
+			origin_location: VersionedLocation,
+			xcm: VersionedXcm<Call>,
+		) -> Result<XcmDryRunEffects<Event>, Error>;
+	}
+}
+
+/// Errors that can occur when dry-running.
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub enum Error {
+	/// An API call is unsupported.
+	#[codec(index = 0)]
+	Unimplemented,
+
+	/// Converting a versioned data structure from one version to another failed.
+	#[codec(index = 1)]
+	VersionedConversionFailed,
+}
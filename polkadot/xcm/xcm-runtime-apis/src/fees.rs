@@ -0,0 +1,88 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for estimating the fees of XCM programs.
+//!
+//! The total cost of sending an XCM is made of the execution fees, paid on every chain the
+//! program is executed on, and the delivery fees, paid on every chain that forwards a message.
+//! This API exposes both, so that wallets and UIs can estimate them without having to replicate
+//! the configuration of the runtime.
+
+use codec::{Decode, Encode};
+use frame_support::weights::Weight;
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+use xcm::{Version, VersionedAssetId, VersionedAssets, VersionedLocation, VersionedXcm};
+
+sp_api::decl_runtime_apis! {
+	/// API for querying the weight and the fees of XCM programs.
+	///
+	/// To estimate the execution fees of a program:
+	/// - use [`XcmPaymentApi::query_acceptable_payment_assets`] to learn which assets can be used
+	///   to pay for execution;
+	/// - use [`XcmPaymentApi::query_xcm_weight`] to get the weight of the program;
+	/// - use [`XcmPaymentApi::query_weight_to_asset_fee`] to convert that weight into a fee in one
+	///   of the acceptable assets.
+	///
+	/// The delivery fees of a message sent from this chain can be queried with
+	/// [`XcmPaymentApi::query_delivery_fees`].
+	pub trait XcmPaymentApi {
+		/// Returns the list of assets that are accepted by this chain for paying execution fees.
+		///
+		/// The assets are returned in the given `xcm_version`.
+		fn query_acceptable_payment_assets(xcm_version: Version) -> Result<Vec<VersionedAssetId>, Error>;
+
+		/// Returns the weight needed to execute `message` on this chain.
+		fn query_xcm_weight(message: VersionedXcm<()>) -> Result<Weight, Error>;
+
+		/// Converts `weight` into a fee in the fungible `asset`.
+		///
+		/// Fails with [`Error::AssetNotFound`] if `asset` can't be used to buy execution.
+		fn query_weight_to_asset_fee(weight: Weight, asset: VersionedAssetId) -> Result<u128, Error>;
+
+		/// Returns the fees charged by this chain for delivering `message` to `destination`.
+		///
+		/// The fees are returned in the version of `message`.
+		fn query_delivery_fees(destination: VersionedLocation, message: VersionedXcm<()>) -> Result<VersionedAssets, Error>;
+	}
+}
+
+/// Errors that can occur when querying fees.
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub enum Error {
+	/// An API part is unsupported.
+	#[codec(index = 0)]
+	Unimplemented,
+
+	/// Converting a versioned data structure from one version to another failed.
+	#[codec(index = 1)]
+	VersionedConversionFailed,
+
+	/// XCM message weight calculation failed.
+	#[codec(index = 2)]
+	WeightNotComputable,
+
+	/// XCM version not able to be handled.
+	#[codec(index = 3)]
+	UnhandledXcmVersion,
+
+	/// The given asset is not handled as a fee asset.
+	#[codec(index = 4)]
+	AssetNotFound,
+
+	/// Destination is known to be unroutable.
+	#[codec(index = 5)]
+	Unroutable,
+}
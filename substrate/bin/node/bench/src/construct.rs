@@ -278,7 +278,11 @@ impl sc_transaction_pool_api::TransactionPool for Transactions {
 		unimplemented!()
 	}
 
-	fn remove_invalid(&self, _hashes: &[TxHash<Self>]) -> Vec<Arc<Self::InPoolTransaction>> {
+	fn remove_invalid(
+		&self,
+		_at: Option<node_primitives::Hash>,
+		_hashes: &[TxHash<Self>],
+	) -> Vec<Arc<Self::InPoolTransaction>> {
 		Default::default()
 	}
 
@@ -61,6 +61,7 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 			future: PoolLimit { count: 100_000, total_bytes: 100 * 1024 * 1024 },
 			reject_future_transactions: false,
 			ban_time: Duration::from_secs(30 * 60),
+			pool_type: Default::default(),
 		},
 		network: network_config,
 		keystore: KeystoreConfig::InMemory,
@@ -80,6 +81,7 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		rpc_max_subs_per_conn: Default::default(),
 		rpc_port: 9944,
 		rpc_message_buffer_capacity: Default::default(),
+		rpc_archive: Default::default(),
 		prometheus_config: None,
 		telemetry_endpoints: None,
 		default_heap_pages: None,
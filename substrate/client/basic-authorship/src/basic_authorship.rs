@@ -415,7 +415,10 @@ where
 		let mut unqueue_invalid = Vec::new();
 		let mut suspicious_txs = Vec::new();
 
-		let mut t1 = self.transaction_pool.ready_at(self.parent_number).fuse();
+		let mut t1 = self
+			.transaction_pool
+			.ready_at_block(self.parent_hash, self.parent_number)
+			.fuse();
 		let mut t2 =
 			futures_timer::Delay::new(deadline.saturating_duration_since((self.now)()) / 8).fuse();
 
@@ -548,7 +551,7 @@ where
 		if matches!(end_reason, EndProposingReason::HitDeadline) && !transaction_pushed {
 			warn!("Hit deadline `{}` without including any transaction!", block_size_limit,);
 			// If we hits the hard deadline but the block still empty, we ban suspicious txs
-			self.transaction_pool.remove_invalid(&suspicious_txs);
+			self.transaction_pool.remove_invalid(Some(self.parent_hash), &suspicious_txs);
 		}
 
 		if matches!(end_reason, EndProposingReason::HitBlockSizeLimit) && !transaction_pushed {
@@ -558,7 +561,7 @@ where
 			);
 		}
 
-		self.transaction_pool.remove_invalid(&unqueue_invalid);
+		self.transaction_pool.remove_invalid(Some(self.parent_hash), &unqueue_invalid);
 		Ok(end_reason)
 	}
 
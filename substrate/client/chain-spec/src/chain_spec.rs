@@ -317,6 +317,20 @@ struct ClientSpec<E> {
 	/// given block number until the `spec_version` on chain changes.
 	#[serde(default)]
 	code_substitutes: BTreeMap<String, Bytes>,
+	/// Trusted block that warp sync downloads the state of.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	warp_sync_checkpoint: Option<WarpSyncCheckpoint>,
+}
+
+/// A trusted block that warp sync can download the state of, without verifying any finality
+/// proofs.
+///
+/// The checkpoint is trusted as is, so it should only be taken from a trusted source.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct WarpSyncCheckpoint {
+	/// The SCALE encoded header of the block.
+	pub header: Bytes,
 }
 
 /// A type denoting empty extensions.
@@ -446,6 +460,7 @@ impl<G, E, EHF> ChainSpecBuilder<G, E, EHF> {
 			consensus_engine: (),
 			genesis: Default::default(),
 			code_substitutes: BTreeMap::new(),
+			warp_sync_checkpoint: None,
 		};
 
 		ChainSpec {
@@ -530,6 +545,16 @@ impl<G, E, EHF> ChainSpec<G, E, EHF> {
 		&mut self.client_spec.extensions
 	}
 
+	/// Trusted block to warp sync to, if any.
+	pub fn warp_sync_checkpoint(&self) -> Option<&WarpSyncCheckpoint> {
+		self.client_spec.warp_sync_checkpoint.as_ref()
+	}
+
+	/// Set the trusted block to warp sync to.
+	pub fn set_warp_sync_checkpoint(&mut self, checkpoint: Option<WarpSyncCheckpoint>) {
+		self.client_spec.warp_sync_checkpoint = checkpoint;
+	}
+
 	/// Create hardcoded spec.
 	#[deprecated(
 		note = "`from_genesis` is planned to be removed in May 2024. Use `builder()` instead."
@@ -561,6 +586,7 @@ impl<G, E, EHF> ChainSpec<G, E, EHF> {
 			consensus_engine: (),
 			genesis: Default::default(),
 			code_substitutes: BTreeMap::new(),
+			warp_sync_checkpoint: None,
 		};
 
 		ChainSpec {
@@ -764,6 +790,10 @@ where
 			.map(|(h, c)| (h.clone(), c.0.clone()))
 			.collect()
 	}
+
+	fn warp_sync_checkpoint(&self) -> Option<WarpSyncCheckpoint> {
+		ChainSpec::warp_sync_checkpoint(self).cloned()
+	}
 }
 
 /// The `fun` will be called with the value at `path`.
@@ -1268,4 +1298,20 @@ mod tests {
 			&|v| { *v == "0x000102040506" }
 		));
 	}
+
+	#[test]
+	fn warp_sync_checkpoint_is_kept_in_json() {
+		let mut spec = TestSpec::from_json_bytes(Cow::Owned(
+			include_bytes!("../res/chain_spec.json").to_vec(),
+		))
+		.unwrap();
+		assert_eq!(spec.warp_sync_checkpoint(), None);
+
+		let checkpoint = WarpSyncCheckpoint { header: vec![1, 2, 3].into() };
+		spec.set_warp_sync_checkpoint(Some(checkpoint.clone()));
+
+		let json = spec.as_json(false).unwrap();
+		let spec = TestSpec::from_json_bytes(json.into_bytes()).unwrap();
+		assert_eq!(spec.warp_sync_checkpoint(), Some(&checkpoint));
+	}
 }
@@ -237,6 +237,10 @@ pub enum Database {
 	/// ParityDb. <https://github.com/paritytech/parity-db/>
 	#[value(name = "paritydb-experimental")]
 	ParityDbDeprecated,
+	/// ParityDb with trie nodes reference counted by the database instead of journaled. A
+	/// database created with this setting can't be opened with any other one.
+	#[value(name = "paritydb-refcounted")]
+	ParityDbRefCounted,
 }
 
 impl Database {
@@ -247,6 +251,7 @@ impl Database {
 			"rocksdb",
 			"paritydb",
 			"paritydb-experimental",
+			"paritydb-refcounted",
 			"auto",
 		]
 	}
@@ -296,3 +301,23 @@ impl Into<sc_network::config::SyncMode> for SyncMode {
 		}
 	}
 }
+
+/// Type of the transaction pool.
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+#[value(rename_all = "kebab-case")]
+pub enum TransactionPoolType {
+	/// Uses the single state transaction pool.
+	SingleState,
+	/// Uses the fork-aware transaction pool.
+	ForkAware,
+}
+
+impl Into<sc_service::config::TransactionPoolType> for TransactionPoolType {
+	fn into(self) -> sc_service::config::TransactionPoolType {
+		match self {
+			TransactionPoolType::SingleState =>
+				sc_service::config::TransactionPoolType::SingleState,
+			TransactionPoolType::ForkAware => sc_service::config::TransactionPoolType::ForkAware,
+		}
+	}
+}
@@ -30,7 +30,7 @@ use crate::{
 use clap::Parser;
 use regex::Regex;
 use sc_service::{
-	config::{BasePath, PrometheusConfig, TransactionPoolOptions},
+	config::{BasePath, PrometheusConfig, RpcArchiveConfig, TransactionPoolOptions},
 	ChainSpec, Role,
 };
 use sc_telemetry::TelemetryEndpoints;
@@ -113,6 +113,13 @@ pub struct RunCmd {
 	#[arg(long, default_value_t = RPC_DEFAULT_MESSAGE_CAPACITY_PER_CONN)]
 	pub rpc_message_buffer_capacity_per_connection: u32,
 
+	/// Maximum number of changed keys a single `archive_storageDiff` call reports.
+	///
+	/// The call fails with an error once more keys changed. Only applies to archive nodes.
+	/// Defaults to 16384.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_archive_max_storage_diff_keys: Option<usize>,
+
 	/// Specify browser *origins* allowed to access the HTTP & WS RPC servers.
 	///
 	/// A comma-separated list of origins (protocol://domain or special `null`
@@ -399,6 +406,14 @@ impl CliConfiguration for RunCmd {
 		Ok(self.rpc_max_subscriptions_per_connection)
 	}
 
+	fn rpc_archive(&self) -> Result<RpcArchiveConfig> {
+		let mut config = RpcArchiveConfig::default();
+		if let Some(max_storage_diff_keys) = self.rpc_archive_max_storage_diff_keys {
+			config.max_storage_diff_keys = max_storage_diff_keys;
+		}
+		Ok(config)
+	}
+
 	fn transaction_pool(&self, is_dev: bool) -> Result<TransactionPoolOptions> {
 		Ok(self.pool_config.transaction_pool(is_dev))
 	}
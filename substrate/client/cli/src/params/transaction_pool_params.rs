@@ -16,6 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::arg_enums::TransactionPoolType;
 use clap::Args;
 use sc_service::config::TransactionPoolOptions;
 
@@ -35,6 +36,10 @@ pub struct TransactionPoolParams {
 	/// If it is considered invalid. Defaults to 1800s.
 	#[arg(long, value_name = "SECONDS")]
 	pub tx_ban_seconds: Option<u64>,
+
+	/// The type of transaction pool to be instantiated.
+	#[arg(long, value_enum, default_value_t = TransactionPoolType::SingleState)]
+	pub pool_type: TransactionPoolType,
 }
 
 impl TransactionPoolParams {
@@ -59,6 +64,8 @@ impl TransactionPoolParams {
 			std::time::Duration::from_secs(30 * 60)
 		};
 
+		opts.pool_type = self.pool_type.into();
+
 		opts
 	}
 }
@@ -271,6 +271,7 @@ mod tests {
 				rpc_id_provider: Default::default(),
 				rpc_max_subs_per_conn: Default::default(),
 				rpc_message_buffer_capacity: Default::default(),
+				rpc_archive: Default::default(),
 				rpc_port: 9944,
 				prometheus_config: None,
 				telemetry_endpoints: None,
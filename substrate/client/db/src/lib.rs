@@ -36,8 +36,8 @@ mod children;
 mod parity_db;
 mod pinned_blocks_cache;
 mod record_stats_state;
+mod refcounted_state;
 mod stats;
-#[cfg(any(feature = "rocksdb", test))]
 mod upgrade;
 mod utils;
 
@@ -54,6 +54,7 @@ use std::{
 use crate::{
 	pinned_blocks_cache::PinnedBlocksCache,
 	record_stats_state::RecordStatsState,
+	refcounted_state::RefCountedState,
 	stats::StateUsageStats,
 	utils::{meta_keys, read_db, read_meta, DatabaseType, Meta},
 };
@@ -150,6 +151,9 @@ impl<B: BlockT> Drop for RefTrackingState<B> {
 	fn drop(&mut self) {
 		if let Some(hash) = &self.parent_hash {
 			self.storage.state_db.unpin(hash);
+			if let Some(refcounted) = &self.storage.refcounted {
+				refcounted.unpin(hash);
+			}
 		}
 	}
 }
@@ -330,6 +334,41 @@ impl BlocksPruning {
 	}
 }
 
+/// How the trie nodes of the state are stored.
+///
+/// The mode is chosen when the database is created and can't be changed afterwards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StateStorageMode {
+	/// Trie node insertions and deletions are journaled by `sc-state-db` and applied to the
+	/// database once the block is canonicalized or leaves the pruning window.
+	#[default]
+	Journaled,
+	/// Trie nodes are reference counted by the database. The state of pruned and discarded
+	/// blocks is released without keeping a journal.
+	///
+	/// Requires a database with native reference counting, i.e. ParityDb.
+	RefCounted,
+}
+
+impl StateStorageMode {
+	/// Returns the identifier that is stored in the database.
+	pub fn id(&self) -> &'static [u8] {
+		match self {
+			StateStorageMode::Journaled => b"journaled",
+			StateStorageMode::RefCounted => b"refcounted",
+		}
+	}
+
+	/// Parse an identifier that was stored in the database.
+	pub fn from_id(id: &[u8]) -> Option<Self> {
+		match id {
+			b"journaled" => Some(StateStorageMode::Journaled),
+			b"refcounted" => Some(StateStorageMode::RefCounted),
+			_ => None,
+		}
+	}
+}
+
 /// Where to find the database..
 #[derive(Debug, Clone)]
 pub enum DatabaseSource {
@@ -358,6 +397,15 @@ pub enum DatabaseSource {
 		path: PathBuf,
 	},
 
+	/// Load a ParityDb database whose state is stored with
+	/// [`StateStorageMode::RefCounted`] from a given path.
+	///
+	/// A new database is created with this mode. An existing database must use it already.
+	ParityDbRefCounted {
+		/// Path to the database.
+		path: PathBuf,
+	},
+
 	/// Use a custom already-open database.
 	Custom {
 		/// the handle to the custom storage
@@ -379,7 +427,8 @@ impl DatabaseSource {
 			DatabaseSource::Auto { paritydb_path, .. } => Some(paritydb_path),
 			#[cfg(feature = "rocksdb")]
 			DatabaseSource::RocksDb { path, .. } => Some(path),
-			DatabaseSource::ParityDb { path } => Some(path),
+			DatabaseSource::ParityDb { path } | DatabaseSource::ParityDbRefCounted { path } =>
+				Some(path),
 			DatabaseSource::Custom { .. } => None,
 		}
 	}
@@ -396,13 +445,28 @@ impl DatabaseSource {
 				*path = p.into();
 				true
 			},
-			DatabaseSource::ParityDb { ref mut path } => {
+			DatabaseSource::ParityDb { ref mut path } |
+			DatabaseSource::ParityDbRefCounted { ref mut path } => {
 				*path = p.into();
 				true
 			},
 			DatabaseSource::Custom { .. } => false,
 		}
 	}
+
+	/// Return the requested state storage mode.
+	///
+	/// `None` means that the mode of an existing database is used, or the default one for a new
+	/// database.
+	pub fn state_storage(&self) -> Option<StateStorageMode> {
+		match self {
+			DatabaseSource::Auto { .. } | DatabaseSource::Custom { .. } => None,
+			#[cfg(feature = "rocksdb")]
+			DatabaseSource::RocksDb { .. } => Some(StateStorageMode::Journaled),
+			DatabaseSource::ParityDb { .. } => Some(StateStorageMode::Journaled),
+			DatabaseSource::ParityDbRefCounted { .. } => Some(StateStorageMode::RefCounted),
+		}
+	}
 }
 
 impl std::fmt::Display for DatabaseSource {
@@ -412,6 +476,7 @@ impl std::fmt::Display for DatabaseSource {
 			#[cfg(feature = "rocksdb")]
 			DatabaseSource::RocksDb { .. } => "RocksDb",
 			DatabaseSource::ParityDb { .. } => "ParityDb",
+			DatabaseSource::ParityDbRefCounted { .. } => "ParityDbRefCounted",
 			DatabaseSource::Custom { .. } => "Custom",
 		};
 		write!(f, "{}", name)
@@ -1010,9 +1075,36 @@ impl<Block: BlockT> sc_client_api::backend::BlockImportOperation<Block>
 struct StorageDb<Block: BlockT> {
 	pub db: Arc<dyn Database<DbHash>>,
 	pub state_db: StateDb<Block::Hash, Vec<u8>, StateMetaDb>,
+	/// Set when the trie nodes are reference counted by the database instead of `state_db`.
+	refcounted: Option<RefCountedState<Block>>,
 	prefix_keys: bool,
 }
 
+impl<Block: BlockT> StorageDb<Block> {
+	/// The pruning mode that is applied to the state.
+	fn pruning_mode(&self) -> PruningMode {
+		match &self.refcounted {
+			Some(refcounted) => refcounted.pruning_mode().clone(),
+			None => self.state_db.pruning_mode(),
+		}
+	}
+
+	/// Commit a transaction made outside of block import.
+	///
+	/// Pending changes of the reference-counted state are confirmed or reverted accordingly.
+	fn commit(&self, transaction: Transaction<DbHash>) -> ClientResult<()> {
+		let result = self.db.commit(transaction);
+		if let Some(refcounted) = &self.refcounted {
+			if result.is_ok() {
+				refcounted.sync();
+			} else {
+				refcounted.reset();
+			}
+		}
+		result.map_err(Into::into)
+	}
+}
+
 impl<Block: BlockT> sp_state_machine::Storage<HashingFor<Block>> for StorageDb<Block> {
 	fn get(&self, key: &Block::Hash, prefix: Prefix) -> Result<Option<DBValue>, String> {
 		if self.prefix_keys {
@@ -1217,9 +1309,35 @@ impl<Block: BlockT> Backend<Block> {
 		let state_meta_db = StateMetaDb(db.clone());
 		let map_e = sp_blockchain::Error::from_state_db;
 
+		let state_storage = match db.get(columns::META, meta_keys::STATE_STORAGE) {
+			Some(id) => StateStorageMode::from_id(&id).ok_or_else(|| {
+				sp_blockchain::Error::Backend(format!("Unknown state storage mode: {:02x?}", id))
+			})?,
+			None => StateStorageMode::Journaled,
+		};
+
+		let refcounted = match state_storage {
+			StateStorageMode::Journaled => None,
+			StateStorageMode::RefCounted if !db.supports_ref_counting() =>
+				return Err(sp_blockchain::Error::Backend(
+					"Reference-counted state storage requires a database with reference counting"
+						.into(),
+				)),
+			StateStorageMode::RefCounted => Some(RefCountedState::open(
+				db.clone(),
+				requested_state_pruning.clone(),
+				&mut db_init_transaction,
+			)?),
+		};
+
+		// With reference counting, `state_db` only passes the trie nodes through to the database.
 		let (state_db_init_commit_set, state_db) = StateDb::open(
 			state_meta_db,
-			requested_state_pruning,
+			if refcounted.is_some() {
+				Some(PruningMode::ArchiveAll)
+			} else {
+				requested_state_pruning
+			},
 			!db.supports_ref_counting(),
 			should_init,
 		)
@@ -1227,12 +1345,15 @@ impl<Block: BlockT> Backend<Block> {
 
 		apply_state_commit(&mut db_init_transaction, state_db_init_commit_set);
 
-		let state_pruning_used = state_db.pruning_mode();
-		let is_archive_pruning = state_pruning_used.is_archive();
 		let blockchain = BlockchainDb::new(db.clone())?;
 
-		let storage_db =
-			StorageDb { db: db.clone(), state_db, prefix_keys: !db.supports_ref_counting() };
+		let storage_db = StorageDb {
+			db: db.clone(),
+			state_db,
+			refcounted,
+			prefix_keys: !db.supports_ref_counting(),
+		};
+		let is_archive_pruning = storage_db.pruning_mode().is_archive();
 
 		let offchain_storage = offchain::LocalStorage::new(db.clone());
 
@@ -1711,7 +1832,8 @@ impl<Block: BlockT> Backend<Block> {
 						);
 					}
 				} else if number > best_num + One::one() &&
-					number > One::one() && self.blockchain.header(parent_hash)?.is_none()
+					number > One::one() &&
+					self.blockchain.header(parent_hash)?.is_none()
 				{
 					let gap = (best_num + One::one(), number - One::one());
 					transaction.set(columns::META, meta_keys::BLOCK_GAP, &gap.encode());
@@ -1810,6 +1932,10 @@ impl<Block: BlockT> Backend<Block> {
 			apply_state_commit(transaction, commit);
 		}
 
+		if let Some(refcounted) = &self.storage.refcounted {
+			refcounted.note_finalized(&self.blockchain, transaction, f_header, f_hash)?;
+		}
+
 		let new_displaced = self.blockchain.leaves.write().finalize_height(f_num);
 		self.prune_blocks(
 			transaction,
@@ -2106,10 +2232,16 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 				.state_db
 				.reset(state_meta_db)
 				.map_err(sp_blockchain::Error::from_state_db)?;
+			if let Some(refcounted) = &self.storage.refcounted {
+				refcounted.reset();
+			}
 			self.blockchain.clear_pinning_cache();
 			Err(e)
 		} else {
 			self.storage.state_db.sync();
+			if let Some(refcounted) = &self.storage.refcounted {
+				refcounted.sync();
+			}
 			Ok(())
 		}
 	}
@@ -2132,7 +2264,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 			&mut current_transaction_justifications,
 		)?;
 
-		self.storage.db.commit(transaction)?;
+		self.storage.commit(transaction)?;
 		self.blockchain.update_meta(m);
 		Ok(())
 	}
@@ -2268,6 +2400,9 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 				match self.storage.state_db.revert_one() {
 					Some(commit) => {
 						apply_state_commit(&mut transaction, commit);
+						if let Some(refcounted) = &self.storage.refcounted {
+							refcounted.discard(&self.blockchain, &mut transaction, removed_hash)?;
+						}
 
 						number_to_revert = prev_number;
 						hash_to_revert = prev_hash;
@@ -2317,7 +2452,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 							meta_keys::CHILDREN_PREFIX,
 							hash_to_revert,
 						);
-						self.storage.db.commit(transaction)?;
+						self.storage.commit(transaction)?;
 
 						let is_best = number_to_revert < best_number;
 
@@ -2381,6 +2516,9 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 		if let Some(commit) = self.storage.state_db.remove(&hash) {
 			apply_state_commit(&mut transaction, commit);
 		}
+		if let Some(refcounted) = &self.storage.refcounted {
+			refcounted.discard(&self.blockchain, &mut transaction, hash)?;
+		}
 		transaction.remove(columns::KEY_LOOKUP, hash.as_ref());
 
 		let children: Vec<_> = self
@@ -2410,11 +2548,11 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 
 		let remove_outcome = leaves.remove(hash, hdr.number, parent_leaf);
 		leaves.prepare_transaction(&mut transaction, columns::META, meta_keys::LEAF_PREFIX);
-		if let Err(e) = self.storage.db.commit(transaction) {
+		if let Err(e) = self.storage.commit(transaction) {
 			if let Some(outcome) = remove_outcome {
 				leaves.undo().undo_remove(outcome);
 			}
-			return Err(e.into())
+			return Err(e)
 		}
 		self.blockchain().remove_header_metadata(hash);
 		Ok(())
@@ -2445,9 +2583,15 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 						.is_some()
 				};
 
-				if let Ok(()) =
-					self.storage.state_db.pin(&hash, hdr.number.saturated_into::<u64>(), hint)
-				{
+				let pinned = match &self.storage.refcounted {
+					Some(refcounted) => refcounted.pin(&hash, hdr.number, hint),
+					None => self
+						.storage
+						.state_db
+						.pin(&hash, hdr.number.saturated_into::<u64>(), hint)
+						.is_ok(),
+				};
+				if pinned {
 					let root = hdr.state_root;
 					let db_state = DbStateBuilder::<Block>::new(self.storage.clone(), root)
 						.with_optional_cache(
@@ -2468,7 +2612,22 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 	}
 
 	fn have_state_at(&self, hash: Block::Hash, number: NumberFor<Block>) -> bool {
-		if self.is_archive {
+		if let Some(refcounted) = &self.storage.refcounted {
+			// Discarded forks don't have any record left, so check the root node.
+			if refcounted.is_pruned(number) {
+				return false
+			}
+			match self.blockchain.header_metadata(hash) {
+				Ok(header) => sp_state_machine::Storage::get(
+					self.storage.as_ref(),
+					&header.state_root,
+					(&[], None),
+				)
+				.unwrap_or(None)
+				.is_some(),
+				_ => false,
+			}
+		} else if self.is_archive {
 			match self.blockchain.header_metadata(hash) {
 				Ok(header) => sp_state_machine::Storage::get(
 					self.storage.as_ref(),
@@ -2503,7 +2662,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 
 	fn requires_full_sync(&self) -> bool {
 		matches!(
-			self.storage.state_db.pruning_mode(),
+			self.storage.pruning_mode(),
 			PruningMode::ArchiveAll | PruningMode::ArchiveCanonical
 		)
 	}
@@ -2529,6 +2688,14 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 					))
 				},
 			)?;
+			if let Some(refcounted) = &self.storage.refcounted {
+				if !refcounted.pin(&hash, number, hint) {
+					return Err(sp_blockchain::Error::UnknownBlock(format!(
+						"State already discarded for `{:?}`",
+						hash
+					)))
+				}
+			}
 		} else {
 			return Err(ClientError::UnknownBlock(format!(
 				"Can not pin block with hash `{:?}`. Block not found.",
@@ -2545,6 +2712,9 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 
 	fn unpin_block(&self, hash: <Block as BlockT>::Hash) {
 		self.storage.state_db.unpin(&hash);
+		if let Some(refcounted) = &self.storage.refcounted {
+			refcounted.unpin(&hash);
+		}
 
 		if self.blocks_pruning != BlocksPruning::KeepAll {
 			self.blockchain.unpin(hash);
@@ -4432,4 +4602,197 @@ pub(crate) mod tests {
 		backend.unpin_block(fork_hash_3);
 		assert!(bc.body(fork_hash_3).unwrap().is_none());
 	}
+
+	fn refcounted_backend(path: &Path, state_pruning: PruningMode) -> Backend<Block> {
+		Backend::<Block>::new(
+			DatabaseSettings {
+				trie_cache_maximum_size: None,
+				state_pruning: Some(state_pruning),
+				source: DatabaseSource::ParityDbRefCounted { path: path.into() },
+				blocks_pruning: BlocksPruning::KeepAll,
+			},
+			10,
+		)
+		.unwrap()
+	}
+
+	fn insert_block_with_storage(
+		backend: &Backend<Block>,
+		number: u64,
+		parent_hash: H256,
+		changes: &[(&str, Option<&[u8]>)],
+	) -> H256 {
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, parent_hash).unwrap();
+		let (root, overlay) = op.old_state.storage_root(
+			changes.iter().map(|(key, value)| (key.as_bytes(), *value)),
+			StateVersion::V1,
+		);
+		op.update_db_storage(overlay).unwrap();
+
+		let header = Header {
+			number,
+			parent_hash,
+			state_root: root.into(),
+			digest: Default::default(),
+			extrinsics_root: Default::default(),
+		};
+		op.set_block_data(header.clone(), Some(Vec::new()), None, None, NewBlockState::Best)
+			.unwrap();
+		backend.commit_operation(op).unwrap();
+		header.hash()
+	}
+
+	#[test]
+	fn refcounted_state_prunes_finalized_blocks() {
+		let dir = tempfile::tempdir().unwrap();
+		let backend = refcounted_backend(dir.path(), PruningMode::blocks_pruning(2));
+
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0..5 {
+			let hash = insert_header(&backend, i, prev_hash, None, Default::default());
+			blocks.push(hash);
+			prev_hash = hash;
+		}
+		for hash in &blocks[1..] {
+			backend.finalize_block(*hash, None).unwrap();
+		}
+
+		assert!(!backend.have_state_at(blocks[0], 0));
+		assert!(!backend.have_state_at(blocks[2], 2));
+		assert!(backend.have_state_at(blocks[3], 3));
+		assert!(backend.have_state_at(blocks[4], 4));
+		assert!(backend.state_at(blocks[2]).is_err());
+
+		// Every block inserted its parent hash, all of which must survive pruning.
+		let state = backend.state_at(blocks[4]).unwrap();
+		for hash in &blocks[..4] {
+			assert_eq!(state.storage(hash.as_ref()).unwrap(), Some(hash.as_ref().to_vec()));
+		}
+		drop(state);
+		drop(backend);
+
+		// Pruning continues where it stopped, without replaying anything.
+		let backend = refcounted_backend(dir.path(), PruningMode::blocks_pruning(2));
+		assert!(!backend.have_state_at(blocks[2], 2));
+		assert!(backend.have_state_at(blocks[3], 3));
+		let hash = insert_header(&backend, 5, blocks[4], None, Default::default());
+		backend.finalize_block(hash, None).unwrap();
+		assert!(!backend.have_state_at(blocks[3], 3));
+		assert!(backend.state_at(hash).unwrap().storage(blocks[4].as_ref()).unwrap().is_some());
+	}
+
+	#[test]
+	fn refcounted_state_discards_forks() {
+		let dir = tempfile::tempdir().unwrap();
+		let backend = refcounted_backend(dir.path(), PruningMode::ArchiveCanonical);
+
+		// Block tree:
+		//   0 -> a1 -> a2
+		//     \-> b1 -> b2 -> b3
+		// `a1` and `b1` have the same state.
+		let genesis = insert_header(&backend, 0, Default::default(), None, Default::default());
+		let b1 = insert_header(&backend, 1, genesis, None, H256::from([1; 32]));
+		let b2 = insert_header(&backend, 2, b1, None, Default::default());
+		let b3 = insert_header(&backend, 3, b2, None, Default::default());
+		let a1 = insert_header(&backend, 1, genesis, None, Default::default());
+		let a2 = insert_header(&backend, 2, a1, None, Default::default());
+
+		// A pinned block keeps the state of itself and its ancestors on the fork.
+		backend.pin_block(b3).unwrap();
+		backend.finalize_block(a1, None).unwrap();
+
+		let state = backend.state_at(b3).unwrap();
+		assert_eq!(state.storage(b1.as_ref()).unwrap(), Some(b1.as_ref().to_vec()));
+		assert_eq!(state.storage(b2.as_ref()).unwrap(), Some(b2.as_ref().to_vec()));
+		drop(state);
+		backend.unpin_block(b3);
+		backend.finalize_block(a2, None).unwrap();
+
+		// The state shared with the discarded fork is still around.
+		assert!(backend.have_state_at(genesis, 0));
+		let state = backend.state_at(a2).unwrap();
+		assert_eq!(state.storage(genesis.as_ref()).unwrap(), Some(genesis.as_ref().to_vec()));
+		assert_eq!(state.storage(a1.as_ref()).unwrap(), Some(a1.as_ref().to_vec()));
+	}
+
+	#[test]
+	fn refcounted_state_keeps_values_shared_by_keys() {
+		let dir = tempfile::tempdir().unwrap();
+		let backend = refcounted_backend(dir.path(), PruningMode::blocks_pruning(1));
+		// Large enough to be stored in its own trie node.
+		let value = [42u8; 64];
+
+		let genesis = insert_block_with_storage(&backend, 0, Default::default(), &[]);
+		let b1 = insert_block_with_storage(
+			&backend,
+			1,
+			genesis,
+			&[("a", Some(&value[..])), ("b", Some(&value[..]))],
+		);
+		let b2 =
+			insert_block_with_storage(&backend, 2, b1, &[("a", None), ("c", Some(&value[..]))]);
+		let b3 = insert_block_with_storage(&backend, 3, b2, &[("b", None)]);
+
+		// Pruning `b1` must not release the value, which is still referenced twice by `b2`.
+		for hash in [b1, b2, b3] {
+			backend.finalize_block(hash, None).unwrap();
+		}
+
+		assert!(!backend.have_state_at(b2, 2));
+		let state = backend.state_at(b3).unwrap();
+		assert_eq!(state.storage(b"b").unwrap(), None);
+		assert_eq!(state.storage(b"c").unwrap(), Some(value.to_vec()));
+	}
+
+	#[test]
+	fn refcounted_state_keeps_moved_values() {
+		let dir = tempfile::tempdir().unwrap();
+		let backend = refcounted_backend(dir.path(), PruningMode::blocks_pruning(1));
+		let value = [42u8; 64];
+
+		let genesis = insert_block_with_storage(&backend, 0, Default::default(), &[]);
+		let b1 = insert_block_with_storage(&backend, 1, genesis, &[("a", Some(&value[..]))]);
+		let b2 =
+			insert_block_with_storage(&backend, 2, b1, &[("a", None), ("b", Some(&value[..]))]);
+		let b3 = insert_block_with_storage(&backend, 3, b2, &[("b", None), ("c", Some(&b"c"[..]))]);
+
+		// The value moved from `a` to `b` without changing its reference count.
+		backend.finalize_block(b1, None).unwrap();
+		backend.finalize_block(b2, None).unwrap();
+		assert!(!backend.have_state_at(b1, 1));
+		let state = backend.state_at(b2).unwrap();
+		assert_eq!(state.storage(b"a").unwrap(), None);
+		assert_eq!(state.storage(b"b").unwrap(), Some(value.to_vec()));
+		drop(state);
+
+		// Pruning the state that still references the value releases it.
+		backend.finalize_block(b3, None).unwrap();
+		assert!(!backend.have_state_at(b2, 2));
+		let state = backend.state_at(b3).unwrap();
+		assert_eq!(state.storage(b"b").unwrap(), None);
+		assert_eq!(state.storage(b"c").unwrap(), Some(b"c".to_vec()));
+	}
+
+	#[test]
+	fn refcounted_state_mode_is_kept() {
+		let dir = tempfile::tempdir().unwrap();
+		drop(refcounted_backend(dir.path(), PruningMode::blocks_pruning(2)));
+
+		let open = |source| {
+			Backend::<Block>::new(
+				DatabaseSettings {
+					trie_cache_maximum_size: None,
+					state_pruning: None,
+					source,
+					blocks_pruning: BlocksPruning::KeepAll,
+				},
+				10,
+			)
+		};
+		assert!(open(DatabaseSource::ParityDb { path: dir.path().into() }).is_err());
+		let backend = open(DatabaseSource::ParityDbRefCounted { path: dir.path().into() }).unwrap();
+		assert_eq!(backend.storage.pruning_mode(), PruningMode::Constrained(Default::default()));
+	}
 }
@@ -0,0 +1,595 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! State pruning for databases with reference-counted trie nodes.
+//!
+//! With [`StateStorageMode::RefCounted`](crate::StateStorageMode::RefCounted) every trie node
+//! inserted by a block import increments the reference count of the node in the database. No
+//! journal of inserted or deleted nodes is kept. Instead, the nodes of a block that is pruned or
+//! discarded are recomputed by walking its state trie alongside the trie it was derived from:
+//!
+//! - The state of a canonical block `n` is pruned once it leaves the pruning window, by releasing
+//!   the nodes of `n` that are not part of the state of block `n + 1`. These are exactly the nodes
+//!   that were removed when `n + 1` was imported.
+//! - The state of a block on a fork that can no longer be finalized is discarded by releasing the
+//!   nodes of the block that are not part of the state of its parent. These are exactly the nodes
+//!   that were inserted when the block was imported.
+//!
+//! Like the changeset of an import, the difference is counted per node: a node that is stored at
+//! several positions, or that moved to another position, is only released as often as it was
+//! referenced more by one state than by the other.
+//!
+//! The only persisted bookkeeping is the number of the last pruned canonical block, so nothing
+//! needs to be replayed when the database is opened.
+
+use crate::{children, columns, utils::meta_keys, DbHash};
+use codec::{Decode, Encode};
+use hash_db::Hasher;
+use log::{debug, trace, warn};
+use parking_lot::Mutex;
+use sc_state_db::{Constraints, PruningMode, StateDbError};
+use sp_blockchain::{HeaderBackend, Result as ClientResult};
+use sp_core::storage::well_known_keys;
+use sp_database::{Database, Transaction};
+use sp_runtime::traits::{
+	Block as BlockT, HashingFor, Header as HeaderT, NumberFor, SaturatedConversion, Zero,
+};
+use sp_trie::{NodeCodec, NodePlan, ValuePlan};
+use std::{
+	collections::{BTreeMap, HashMap, HashSet},
+	sync::Arc,
+};
+use trie_db::{node::NodeHandlePlan, NodeCodec as _};
+
+const LOG_TARGET: &str = "db::refcounted";
+
+/// Nodes of a discarded block that are kept alive until the pinned blocks are unpinned.
+struct Deferred<Hash> {
+	/// Pinned blocks that still need the nodes.
+	pins: HashSet<Hash>,
+	/// Nodes to release once there are no pins left.
+	nodes: Vec<Hash>,
+	/// Whether the block was discarded in a transaction that has been committed.
+	committed: bool,
+}
+
+struct Inner<Hash> {
+	/// The last canonical block whose state was pruned, as stored in the database.
+	last_pruned: Option<u64>,
+	/// The last canonical block whose state was pruned, including the pending transaction.
+	pending_last_pruned: Option<u64>,
+	/// Pinned blocks and their pin count.
+	pinned: HashMap<Hash, u32>,
+	/// Nodes of discarded blocks that are still pinned.
+	deferred: Vec<Deferred<Hash>>,
+	/// Nodes that are no longer pinned and will be released with the next finalization.
+	releasable: Vec<Hash>,
+	/// Nodes of `releasable` that were released in the pending transaction.
+	flushed: Vec<Hash>,
+}
+
+/// Prunes the state of a database with reference-counted trie nodes.
+pub(crate) struct RefCountedState<Block: BlockT> {
+	db: Arc<dyn Database<DbHash>>,
+	pruning: PruningMode,
+	inner: Mutex<Inner<Block::Hash>>,
+}
+
+impl<Block: BlockT> RefCountedState<Block> {
+	/// Open the reference-counted state of the given database.
+	///
+	/// Writes the pruning mode that is used to `transaction`.
+	pub fn open(
+		db: Arc<dyn Database<DbHash>>,
+		requested: Option<PruningMode>,
+		transaction: &mut Transaction<DbHash>,
+	) -> ClientResult<Self> {
+		let stored = match db.get(columns::META, meta_keys::STATE_PRUNING) {
+			Some(id) => Some(PruningMode::from_id(&id).ok_or_else(|| {
+				sp_blockchain::Error::Backend(format!(
+					"Invalid value stored for the state pruning mode: {:02x?}",
+					id
+				))
+			})?),
+			None => None,
+		};
+		let pruning = match (stored, requested) {
+			(Some(stored), Some(requested)) => choose_pruning_mode(stored, requested)?,
+			(Some(stored), None) => stored,
+			(None, requested) => requested.unwrap_or_default(),
+		};
+		transaction.set(columns::META, meta_keys::STATE_PRUNING, pruning.id());
+
+		let last_pruned = db
+			.get(columns::META, meta_keys::STATE_LAST_PRUNED)
+			.map(|encoded| u64::decode(&mut &encoded[..]))
+			.transpose()
+			.map_err(|e| sp_blockchain::Error::Backend(format!("{e:?}")))?;
+
+		trace!(target: LOG_TARGET, "Opened with {:?}, last pruned: {:?}", pruning, last_pruned);
+
+		Ok(Self {
+			db,
+			pruning,
+			inner: Mutex::new(Inner {
+				last_pruned,
+				pending_last_pruned: last_pruned,
+				pinned: Default::default(),
+				deferred: Default::default(),
+				releasable: Default::default(),
+				flushed: Default::default(),
+			}),
+		})
+	}
+
+	/// The pruning mode that is applied to the state.
+	pub fn pruning_mode(&self) -> &PruningMode {
+		&self.pruning
+	}
+
+	/// Returns `true` if the state of the canonical block with the given number was pruned.
+	pub fn is_pruned(&self, number: NumberFor<Block>) -> bool {
+		let number = number.saturated_into::<u64>();
+		self.inner.lock().pending_last_pruned.map_or(false, |last| number <= last)
+	}
+
+	/// Prevent the state of the given block from being pruned or discarded.
+	///
+	/// `hint` is used to check whether the state is still around. Returns `false` if it isn't.
+	pub fn pin(
+		&self,
+		hash: &Block::Hash,
+		number: NumberFor<Block>,
+		hint: impl Fn() -> bool,
+	) -> bool {
+		let mut inner = self.inner.lock();
+		if let Some(refs) = inner.pinned.get_mut(hash) {
+			*refs += 1;
+			return true
+		}
+		let number = number.saturated_into::<u64>();
+		if inner.pending_last_pruned.map_or(false, |last| number <= last) || !hint() {
+			return false
+		}
+		inner.pinned.insert(*hash, 1);
+		true
+	}
+
+	/// Allow the state of the given block to be pruned or discarded again.
+	pub fn unpin(&self, hash: &Block::Hash) {
+		let mut inner = self.inner.lock();
+		match inner.pinned.get_mut(hash) {
+			Some(refs) if *refs > 1 => *refs -= 1,
+			Some(_) => {
+				inner.pinned.remove(hash);
+				let Inner { deferred, releasable, .. } = &mut *inner;
+				deferred.retain_mut(|deferred| {
+					if !deferred.committed {
+						return true
+					}
+					deferred.pins.remove(hash);
+					if deferred.pins.is_empty() {
+						releasable.append(&mut deferred.nodes);
+						false
+					} else {
+						true
+					}
+				});
+			},
+			None => {},
+		}
+	}
+
+	/// Prune and discard state after the given block was finalized.
+	///
+	/// The state of the siblings of the finalized block and all their descendants is discarded.
+	/// The state of canonical blocks that left the pruning window is pruned. Must be called for
+	/// every finalized block in order.
+	pub fn note_finalized(
+		&self,
+		blockchain: &impl HeaderBackend<Block>,
+		transaction: &mut Transaction<DbHash>,
+		f_header: &Block::Header,
+		f_hash: Block::Hash,
+	) -> ClientResult<()> {
+		let mut inner = self.inner.lock();
+
+		let Inner { releasable, flushed, .. } = &mut *inner;
+		for node in releasable.drain(..) {
+			transaction.remove(columns::STATE, node.as_ref());
+			flushed.push(node);
+		}
+
+		if self.pruning != PruningMode::ArchiveAll && !f_header.number().is_zero() {
+			let siblings: Vec<Block::Hash> = children::read_children(
+				&*self.db,
+				columns::META,
+				meta_keys::CHILDREN_PREFIX,
+				*f_header.parent_hash(),
+			)?;
+			for sibling in siblings.into_iter().filter(|hash| *hash != f_hash) {
+				self.discard_branch(&mut inner, blockchain, transaction, sibling)?;
+			}
+		}
+
+		if let PruningMode::Constrained(Constraints { max_blocks }) = &self.pruning {
+			let f_num = f_header.number().saturated_into::<u64>();
+			let max_to_prune = f_num.checked_sub(max_blocks.unwrap_or(0) as u64);
+			let mut next = inner.pending_last_pruned.map_or(0, |last| last + 1);
+
+			while max_to_prune.map_or(false, |max| next <= max) && next < f_num {
+				if !self.prune_canonical(&inner, blockchain, transaction, next, f_header)? {
+					break
+				}
+				inner.pending_last_pruned = Some(next);
+				next += 1;
+			}
+
+			if inner.pending_last_pruned != inner.last_pruned {
+				if let Some(last_pruned) = inner.pending_last_pruned {
+					transaction.set_from_vec(
+						columns::META,
+						meta_keys::STATE_LAST_PRUNED,
+						last_pruned.encode(),
+					);
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Discard the state of the given block, which must not have any children with state.
+	pub fn discard(
+		&self,
+		blockchain: &impl HeaderBackend<Block>,
+		transaction: &mut Transaction<DbHash>,
+		hash: Block::Hash,
+	) -> ClientResult<()> {
+		let mut inner = self.inner.lock();
+		let pins = inner.pinned.contains_key(&hash).then_some(hash).into_iter().collect();
+		self.discard_block(&mut inner, blockchain, transaction, hash, pins)
+	}
+
+	/// Confirm that the pending transaction was committed to the database.
+	pub fn sync(&self) {
+		let mut inner = self.inner.lock();
+		inner.last_pruned = inner.pending_last_pruned;
+		inner.flushed.clear();
+
+		let Inner { deferred, pinned, releasable, .. } = &mut *inner;
+		deferred.retain_mut(|deferred| {
+			deferred.committed = true;
+			deferred.pins.retain(|hash| pinned.contains_key(hash));
+			if deferred.pins.is_empty() {
+				releasable.append(&mut deferred.nodes);
+				false
+			} else {
+				true
+			}
+		});
+	}
+
+	/// Revert the in-memory changes of a pending transaction that failed to commit.
+	pub fn reset(&self) {
+		let mut inner = self.inner.lock();
+		inner.pending_last_pruned = inner.last_pruned;
+
+		let Inner { deferred, releasable, flushed, .. } = &mut *inner;
+		releasable.append(flushed);
+		deferred.retain(|deferred| deferred.committed);
+	}
+
+	fn have_state(&self, root: &Block::Hash) -> bool {
+		self.db.get(columns::STATE, root.as_ref()).is_some()
+	}
+
+	/// Discard the given block and all its descendants.
+	///
+	/// Returns the pinned blocks of the branch.
+	fn discard_branch(
+		&self,
+		inner: &mut Inner<Block::Hash>,
+		blockchain: &impl HeaderBackend<Block>,
+		transaction: &mut Transaction<DbHash>,
+		hash: Block::Hash,
+	) -> ClientResult<HashSet<Block::Hash>> {
+		let mut pins = HashSet::new();
+		let children: Vec<Block::Hash> =
+			children::read_children(&*self.db, columns::META, meta_keys::CHILDREN_PREFIX, hash)?;
+		for child in children {
+			// The nodes of a block are also needed by the state of its descendants.
+			pins.extend(self.discard_branch(inner, blockchain, transaction, child)?);
+		}
+		if inner.pinned.contains_key(&hash) {
+			pins.insert(hash);
+		}
+
+		self.discard_block(inner, blockchain, transaction, hash, pins.clone())?;
+		Ok(pins)
+	}
+
+	/// Release the nodes that were inserted by the import of the given block.
+	///
+	/// The nodes are kept until all of `pins` are unpinned.
+	fn discard_block(
+		&self,
+		inner: &mut Inner<Block::Hash>,
+		blockchain: &impl HeaderBackend<Block>,
+		transaction: &mut Transaction<DbHash>,
+		hash: Block::Hash,
+		pins: HashSet<Block::Hash>,
+	) -> ClientResult<()> {
+		let Some(header) = blockchain.header(hash)? else { return Ok(()) };
+		if !self.have_state(header.state_root()) {
+			return Ok(())
+		}
+
+		let parent_root = match blockchain.header(*header.parent_hash())? {
+			Some(parent) if self.have_state(parent.state_root()) => *parent.state_root(),
+			_ => {
+				// The state was not derived from its parent, e.g. it was downloaded. Keep the nodes
+				// instead of releasing them more often than they were inserted.
+				debug!(target: LOG_TARGET, "Keeping state of {:?} without parent state", hash);
+				return Ok(())
+			},
+		};
+
+		let mut nodes = Vec::new();
+		if let Err(e) = diff_nodes::<HashingFor<Block>>(
+			&*self.db,
+			*header.state_root(),
+			Some(parent_root),
+			&mut nodes,
+		) {
+			warn!(target: LOG_TARGET, "Failed to discard state of {:?}: {}", hash, e);
+			return Ok(())
+		}
+
+		trace!(target: LOG_TARGET, "Discarding {} nodes of {:?}", nodes.len(), hash);
+		if pins.is_empty() {
+			for node in nodes {
+				transaction.remove(columns::STATE, node.as_ref());
+			}
+		} else {
+			inner.deferred.push(Deferred { pins, nodes, committed: false });
+		}
+		Ok(())
+	}
+
+	/// Prune the state of the canonical block with the given number.
+	///
+	/// Returns `false` if the state can't be pruned yet.
+	fn prune_canonical(
+		&self,
+		inner: &Inner<Block::Hash>,
+		blockchain: &impl HeaderBackend<Block>,
+		transaction: &mut Transaction<DbHash>,
+		number: u64,
+		f_header: &Block::Header,
+	) -> ClientResult<bool> {
+		let Some(hash) = blockchain.hash(number.saturated_into())? else { return Ok(false) };
+		if inner.pinned.contains_key(&hash) {
+			return Ok(false)
+		}
+
+		let next_header = if number + 1 == f_header.number().saturated_into::<u64>() {
+			f_header.clone()
+		} else {
+			match blockchain.hash((number + 1).saturated_into())? {
+				Some(next_hash) => blockchain.expect_header(next_hash)?,
+				None => return Ok(false),
+			}
+		};
+		let header = blockchain.expect_header(hash)?;
+
+		if !self.have_state(next_header.state_root()) {
+			// The state of the finalized block may still be part of the pending transaction.
+			return Ok(*next_header.number() != *f_header.number())
+		}
+		if !self.have_state(header.state_root()) {
+			return Ok(true)
+		}
+
+		let mut nodes = Vec::new();
+		if let Err(e) = diff_nodes::<HashingFor<Block>>(
+			&*self.db,
+			*header.state_root(),
+			Some(*next_header.state_root()),
+			&mut nodes,
+		) {
+			warn!(target: LOG_TARGET, "Failed to prune state of #{} ({:?}): {}", number, hash, e);
+			return Ok(true)
+		}
+
+		trace!(target: LOG_TARGET, "Pruning {} nodes of #{} ({:?})", nodes.len(), number, hash);
+		for node in nodes {
+			transaction.remove(columns::STATE, node.as_ref());
+		}
+		Ok(true)
+	}
+}
+
+fn choose_pruning_mode(stored: PruningMode, requested: PruningMode) -> ClientResult<PruningMode> {
+	match (stored, requested) {
+		(PruningMode::ArchiveAll, PruningMode::ArchiveAll) => Ok(PruningMode::ArchiveAll),
+		(PruningMode::ArchiveCanonical, PruningMode::ArchiveCanonical) =>
+			Ok(PruningMode::ArchiveCanonical),
+		(PruningMode::Constrained(_), PruningMode::Constrained(requested)) =>
+			Ok(PruningMode::Constrained(requested)),
+		(stored, requested) =>
+			Err(sp_blockchain::Error::from_state_db(StateDbError::IncompatiblePruningModes {
+				stored,
+				requested,
+			})),
+	}
+}
+
+/// Where a node is stored in a trie: the nibble path leading to it and whether it is a value node.
+type Position = (Vec<u8>, bool);
+
+/// Walks two tries side by side, skipping all subtries that are equal in both.
+struct TrieDiff<'a, H: Hasher> {
+	db: &'a dyn Database<DbHash>,
+	/// Nodes that still need to be visited, by position and side.
+	queue: BTreeMap<Position, [Option<H::Out>; 2]>,
+	/// Roots of the child tries, by storage key and side.
+	child_roots: BTreeMap<Vec<u8>, [Option<H::Out>; 2]>,
+	/// Whether values under the child storage prefix are child trie roots.
+	with_child_tries: bool,
+	/// The number of times each visited node is referenced by `from`, minus the number of times
+	/// it is referenced by `to`.
+	counts: HashMap<H::Out, i64>,
+}
+
+/// Collect the nodes of the trie `from` that are not part of the trie `to`.
+///
+/// A node is collected as many times as it is referenced more by `from` than by `to`, which
+/// mirrors the reference counts of the changeset that derives one trie from the other. Child
+/// tries are included. If `to` is `None`, all nodes of `from` are collected.
+fn diff_nodes<H: Hasher>(
+	db: &dyn Database<DbHash>,
+	from: H::Out,
+	to: Option<H::Out>,
+	nodes: &mut Vec<H::Out>,
+) -> Result<(), String> {
+	let mut diff = TrieDiff::<H> {
+		db,
+		queue: Default::default(),
+		child_roots: Default::default(),
+		with_child_tries: true,
+		counts: Default::default(),
+	};
+	diff.run(Some(from), to)?;
+
+	diff.with_child_tries = false;
+	for (_, [from, to]) in std::mem::take(&mut diff.child_roots) {
+		diff.run(from, to)?;
+	}
+
+	for (hash, count) in diff.counts {
+		nodes.extend(std::iter::repeat(hash).take(count.max(0) as usize));
+	}
+	Ok(())
+}
+
+impl<'a, H: Hasher> TrieDiff<'a, H> {
+	fn run(&mut self, from: Option<H::Out>, to: Option<H::Out>) -> Result<(), String> {
+		let null_node = NodeCodec::<H>::hashed_null_node();
+		self.queue.insert((Vec::new(), false), [from, to]);
+
+		while let Some(((path, is_value), hashes)) = self.queue.pop_first() {
+			if hashes[0] == hashes[1] {
+				continue
+			}
+			for (side, hash) in hashes.into_iter().enumerate() {
+				let Some(hash) = hash.filter(|hash| *hash != null_node) else { continue };
+				*self.counts.entry(hash).or_default() += if side == 0 { 1 } else { -1 };
+				if !is_value {
+					self.visit(side, path.clone(), hash)?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Queue the nodes referenced by the node `hash` stored at `path`.
+	fn visit(&mut self, side: usize, mut path: Vec<u8>, hash: H::Out) -> Result<(), String> {
+		let data = self
+			.db
+			.get(columns::STATE, hash.as_ref())
+			.ok_or_else(|| format!("Missing trie node {:?}", hash))?;
+		let plan = NodeCodec::<H>::decode_plan(&data)
+			.map_err(|e| format!("Invalid trie node {:?}: {:?}", hash, e))?;
+
+		match plan {
+			NodePlan::Empty => {},
+			NodePlan::Leaf { partial, value } => {
+				let partial = partial.build(&data);
+				path.extend((0..partial.len()).map(|i| partial.at(i)));
+				self.queue_value(side, path, value, &data);
+			},
+			NodePlan::Extension { partial, child } => {
+				let partial = partial.build(&data);
+				path.extend((0..partial.len()).map(|i| partial.at(i)));
+				self.queue_child(side, path, &child, &data);
+			},
+			NodePlan::Branch { value, children } =>
+				self.queue_branch(side, path, value, &children, &data),
+			NodePlan::NibbledBranch { partial, value, children } => {
+				let partial = partial.build(&data);
+				path.extend((0..partial.len()).map(|i| partial.at(i)));
+				self.queue_branch(side, path, value, &children, &data);
+			},
+		}
+		Ok(())
+	}
+
+	fn queue_branch(
+		&mut self,
+		side: usize,
+		path: Vec<u8>,
+		value: Option<ValuePlan>,
+		children: &[Option<NodeHandlePlan>; 16],
+		data: &[u8],
+	) {
+		for (index, child) in children.iter().enumerate() {
+			if let Some(child) = child {
+				let mut child_path = path.clone();
+				child_path.push(index as u8);
+				self.queue_child(side, child_path, child, data);
+			}
+		}
+		if let Some(value) = value {
+			self.queue_value(side, path, value, data);
+		}
+	}
+
+	fn queue_child(&mut self, side: usize, path: Vec<u8>, child: &NodeHandlePlan, data: &[u8]) {
+		// Inline nodes are not stored on their own and can't reference other nodes.
+		if let NodeHandlePlan::Hash(range) = child {
+			self.queue.entry((path, false)).or_default()[side] =
+				Some(decode_hash::<H>(&data[range.clone()]));
+		}
+	}
+
+	fn queue_value(&mut self, side: usize, path: Vec<u8>, value: ValuePlan, data: &[u8]) {
+		match value {
+			ValuePlan::Node(range) => {
+				self.queue.entry((path, true)).or_default()[side] =
+					Some(decode_hash::<H>(&data[range]));
+			},
+			ValuePlan::Inline(range) if self.with_child_tries && path.len() % 2 == 0 => {
+				let key: Vec<u8> = path.chunks(2).map(|n| n[0] << 4 | n[1]).collect();
+				if key.starts_with(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX) &&
+					range.len() == H::LENGTH
+				{
+					self.child_roots.entry(key).or_default()[side] =
+						Some(decode_hash::<H>(&data[range]));
+				}
+			},
+			ValuePlan::Inline(_) => {},
+		}
+	}
+}
+
+fn decode_hash<H: Hasher>(data: &[u8]) -> H::Out {
+	let mut hash = H::Out::default();
+	hash.as_mut().copy_from_slice(data);
+	hash
+}
@@ -18,30 +18,47 @@
 
 //! Database upgrade logic.
 
+use std::{fmt, io};
+#[cfg(any(feature = "rocksdb", test))]
 use std::{
-	fmt, fs,
-	io::{self, ErrorKind, Read, Write},
+	fs,
+	io::{ErrorKind, Read, Write},
 	path::{Path, PathBuf},
 };
 
+#[cfg(any(feature = "rocksdb", test))]
 use crate::{columns, utils::DatabaseType};
+use crate::{
+	utils::{meta_keys, COLUMN_META},
+	DbHash, StateStorageMode,
+};
+#[cfg(any(feature = "rocksdb", test))]
 use codec::{Decode, Encode};
+#[cfg(any(feature = "rocksdb", test))]
 use kvdb_rocksdb::{Database, DatabaseConfig};
+use sp_database::Transaction;
+#[cfg(any(feature = "rocksdb", test))]
 use sp_runtime::traits::Block as BlockT;
 
 /// Version file name.
+#[cfg(any(feature = "rocksdb", test))]
 const VERSION_FILE_NAME: &str = "db_version";
 
 /// Current db version.
+#[cfg(any(feature = "rocksdb", test))]
 const CURRENT_VERSION: u32 = 4;
 
 /// Number of columns in v1.
+#[cfg(any(feature = "rocksdb", test))]
 const V1_NUM_COLUMNS: u32 = 11;
+#[cfg(any(feature = "rocksdb", test))]
 const V2_NUM_COLUMNS: u32 = 12;
+#[cfg(any(feature = "rocksdb", test))]
 const V3_NUM_COLUMNS: u32 = 12;
 
 /// Database upgrade errors.
 #[derive(Debug)]
+#[cfg_attr(not(any(feature = "rocksdb", test)), allow(dead_code))]
 pub enum UpgradeError {
 	/// Database version cannot be read from existing db_version file.
 	UnknownDatabaseVersion,
@@ -53,6 +70,10 @@ pub enum UpgradeError {
 	FutureDatabaseVersion(u32),
 	/// Invalid justification block.
 	DecodingJustificationBlock,
+	/// State storage mode cannot be read from the database.
+	UnknownStateStorageMode,
+	/// The requested state storage mode differs from the one of the database.
+	StateStorageModeMismatch { stored: StateStorageMode, requested: StateStorageMode },
 	/// Common io error.
 	Io(io::Error),
 }
@@ -81,12 +102,21 @@ impl fmt::Display for UpgradeError {
 			UpgradeError::DecodingJustificationBlock => {
 				write!(f, "Decodoning justification block failed")
 			},
+			UpgradeError::UnknownStateStorageMode => {
+				write!(f, "State storage mode cannot be read from the database")
+			},
+			UpgradeError::StateStorageModeMismatch { stored, requested } => write!(
+				f,
+				"Database uses {:?} state storage, which cannot be changed to {:?}",
+				stored, requested
+			),
 			UpgradeError::Io(err) => write!(f, "Io error: {}", err),
 		}
 	}
 }
 
 /// Upgrade database to current version.
+#[cfg(any(feature = "rocksdb", test))]
 pub fn upgrade_db<Block: BlockT>(db_path: &Path, db_type: DatabaseType) -> UpgradeResult<()> {
 	let db_version = current_version(db_path)?;
 	match db_version {
@@ -113,6 +143,7 @@ pub fn upgrade_db<Block: BlockT>(db_path: &Path, db_type: DatabaseType) -> Upgra
 /// Migration from version1 to version2:
 /// 1) the number of columns has changed from 11 to 12;
 /// 2) transactions column is added;
+#[cfg(any(feature = "rocksdb", test))]
 fn migrate_1_to_2<Block: BlockT>(db_path: &Path, _db_type: DatabaseType) -> UpgradeResult<()> {
 	let db_cfg = DatabaseConfig::with_columns(V1_NUM_COLUMNS);
 	let mut db = Database::open(&db_cfg, db_path)?;
@@ -121,6 +152,7 @@ fn migrate_1_to_2<Block: BlockT>(db_path: &Path, _db_type: DatabaseType) -> Upgr
 
 /// Migration from version2 to version3:
 /// - The format of the stored Justification changed to support multiple Justifications.
+#[cfg(any(feature = "rocksdb", test))]
 fn migrate_2_to_3<Block: BlockT>(db_path: &Path, _db_type: DatabaseType) -> UpgradeResult<()> {
 	let db_cfg = DatabaseConfig::with_columns(V2_NUM_COLUMNS);
 	let db = Database::open(&db_cfg, db_path)?;
@@ -153,6 +185,7 @@ fn migrate_2_to_3<Block: BlockT>(db_path: &Path, _db_type: DatabaseType) -> Upgr
 /// Migration from version3 to version4:
 /// 1) the number of columns has changed from 12 to 13;
 /// 2) BODY_INDEX column is added;
+#[cfg(any(feature = "rocksdb", test))]
 fn migrate_3_to_4<Block: BlockT>(db_path: &Path, _db_type: DatabaseType) -> UpgradeResult<()> {
 	let db_cfg = DatabaseConfig::with_columns(V3_NUM_COLUMNS);
 	let mut db = Database::open(&db_cfg, db_path)?;
@@ -161,6 +194,7 @@ fn migrate_3_to_4<Block: BlockT>(db_path: &Path, _db_type: DatabaseType) -> Upgr
 
 /// Reads current database version from the file at given path.
 /// If the file does not exist returns 0.
+#[cfg(any(feature = "rocksdb", test))]
 fn current_version(path: &Path) -> UpgradeResult<u32> {
 	match fs::File::open(version_file_path(path)) {
 		Err(ref err) if err.kind() == ErrorKind::NotFound =>
@@ -176,6 +210,7 @@ fn current_version(path: &Path) -> UpgradeResult<u32> {
 
 /// Writes current database version to the file.
 /// Creates a new file if the version file does not exist yet.
+#[cfg(any(feature = "rocksdb", test))]
 pub fn update_version(path: &Path) -> io::Result<()> {
 	fs::create_dir_all(path)?;
 	let mut file = fs::File::create(version_file_path(path))?;
@@ -184,12 +219,47 @@ pub fn update_version(path: &Path) -> io::Result<()> {
 }
 
 /// Returns the version file path.
+#[cfg(any(feature = "rocksdb", test))]
 fn version_file_path(path: &Path) -> PathBuf {
 	let mut file_path = path.to_owned();
 	file_path.push(VERSION_FILE_NAME);
 	file_path
 }
 
+/// Check the state storage mode of an opened database against the requested one.
+///
+/// Databases without a recorded mode were created before reference-counted state storage existed
+/// and are journaled. The mode of a newly created database is recorded here, after which it can't
+/// be changed anymore. `None` accepts the mode of an existing database.
+pub fn upgrade_state_storage(
+	db: &dyn sp_database::Database<DbHash>,
+	requested: Option<StateStorageMode>,
+) -> UpgradeResult<StateStorageMode> {
+	let recorded = db.get(COLUMN_META, meta_keys::STATE_STORAGE);
+	let stored = match &recorded {
+		Some(id) =>
+			Some(StateStorageMode::from_id(id).ok_or(UpgradeError::UnknownStateStorageMode)?),
+		None if db.get(COLUMN_META, meta_keys::TYPE).is_some() => Some(StateStorageMode::Journaled),
+		None => None,
+	};
+
+	let mode = match (stored, requested) {
+		(Some(stored), Some(requested)) if stored != requested =>
+			return Err(UpgradeError::StateStorageModeMismatch { stored, requested }),
+		(Some(stored), _) => stored,
+		(None, requested) => requested.unwrap_or_default(),
+	};
+
+	if recorded.is_none() {
+		let mut transaction = Transaction::new();
+		transaction.set(COLUMN_META, meta_keys::STATE_STORAGE, mode.id());
+		db.commit(transaction)
+			.map_err(|e| UpgradeError::Io(io::Error::new(io::ErrorKind::Other, e)))?;
+	}
+
+	Ok(mode)
+}
+
 #[cfg(all(test, feature = "rocksdb"))]
 mod tests {
 	use super::*;
@@ -56,6 +56,12 @@ pub mod meta_keys {
 	pub const LEAF_PREFIX: &[u8; 4] = b"leaf";
 	/// Children prefix list key.
 	pub const CHILDREN_PREFIX: &[u8; 8] = b"children";
+	/// State storage mode.
+	pub const STATE_STORAGE: &[u8; 13] = b"state_storage";
+	/// Pruning mode of reference-counted state.
+	pub const STATE_PRUNING: &[u8; 13] = b"state_pruning";
+	/// Last canonical block whose reference-counted state was pruned.
+	pub const STATE_LAST_PRUNED: &[u8; 17] = b"state_last_pruned";
 }
 
 /// Database metadata.
@@ -191,7 +197,8 @@ fn open_database_at<Block: BlockT>(
 	create: bool,
 ) -> OpenDbResult {
 	let db: Arc<dyn Database<DbHash>> = match &db_source {
-		DatabaseSource::ParityDb { path } => open_parity_db::<Block>(path, db_type, create)?,
+		DatabaseSource::ParityDb { path } | DatabaseSource::ParityDbRefCounted { path } =>
+			open_parity_db::<Block>(path, db_type, create)?,
 		#[cfg(feature = "rocksdb")]
 		DatabaseSource::RocksDb { path, cache_size } =>
 			open_kvdb_rocksdb::<Block>(path, db_type, create, *cache_size)?,
@@ -212,6 +219,8 @@ fn open_database_at<Block: BlockT>(
 		},
 	};
 
+	crate::upgrade::upgrade_state_storage(&*db, db_source.state_storage())
+		.map_err(|err| OpenDbError::Internal(err.to_string()))?;
 	check_database_type(&*db, db_type)?;
 	Ok(db)
 }
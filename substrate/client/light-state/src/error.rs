@@ -0,0 +1,76 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Error type of the light state client.
+
+/// Light state client error.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// Failed to connect to the remote node.
+	#[error("Failed to connect: {0}")]
+	Connection(String),
+
+	/// Request to the remote node failed.
+	#[error("RPC request failed: {0}")]
+	Rpc(#[from] substrate_rpc_client::Error),
+
+	/// The remote node doesn't know a block it should know.
+	#[error("Unknown block: {0}")]
+	UnknownBlock(String),
+
+	/// The remote node returned headers that don't form a chain.
+	#[error("Invalid header chain: {0}")]
+	InvalidHeaderChain(String),
+
+	/// The remote node returned an invalid finality proof.
+	#[error("Invalid finality proof: {0}")]
+	InvalidFinalityProof(String),
+
+	/// The header chain contains a forced authority set change, which can't be verified.
+	#[error("Forced authority set change in block #{0}")]
+	ForcedChange(String),
+
+	/// The remote node returned an invalid storage proof.
+	#[error("Invalid storage proof: {0}")]
+	InvalidStorageProof(String),
+
+	/// Failed to call the runtime to derive its metadata.
+	#[error("Runtime call failed: {0}")]
+	Runtime(String),
+
+	/// The runtime metadata has an unsupported version.
+	#[error("Unsupported metadata version: {0}")]
+	UnsupportedMetadata(u32),
+
+	/// The storage entry is not part of the runtime metadata.
+	#[error("Unknown storage entry: {0}::{1}")]
+	UnknownStorageEntry(String, String),
+
+	/// Wrong number of keys for a storage entry.
+	#[error("Storage entry expects {expected} keys, got {got}")]
+	InvalidKeyCount {
+		/// The number of keys of the storage entry.
+		expected: usize,
+		/// The number of keys that were given.
+		got: usize,
+	},
+
+	/// Failed to decode data.
+	#[error("Failed to decode {0}: {1}")]
+	Decode(&'static str, codec::Error),
+}
@@ -0,0 +1,280 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Verification of GRANDPA finality proofs for a chain of headers.
+
+use crate::Error;
+use codec::DecodeAll;
+use sc_consensus_grandpa::{
+	find_forced_change, find_scheduled_change, BlockNumberOps, FinalityProof, GrandpaJustification,
+};
+use sp_consensus_grandpa::{AuthorityList, SetId};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor, One};
+
+/// Follows the finality of a chain, starting at a trusted block.
+///
+/// Every newly finalized block has to be proven by a GRANDPA justification of the current
+/// authority set. Authority set changes are tracked through the digests of the headers that lead
+/// to the finalized block.
+pub struct FinalityVerifier<Block: BlockT> {
+	finalized: Block::Header,
+	set_id: SetId,
+	authorities: AuthorityList,
+	/// Block number at which the pending change is enacted and the next authorities.
+	pending_change: Option<(NumberFor<Block>, AuthorityList)>,
+}
+
+impl<Block: BlockT> FinalityVerifier<Block>
+where
+	NumberFor<Block>: BlockNumberOps,
+{
+	/// Create a new verifier starting at the given trusted block.
+	///
+	/// `set_id` and `authorities` are the authority set that finalizes the children of
+	/// `finalized`. `pending_change` is a standard change that was scheduled, but not yet enacted,
+	/// at `finalized`.
+	pub fn new(
+		finalized: Block::Header,
+		set_id: SetId,
+		authorities: AuthorityList,
+		pending_change: Option<(NumberFor<Block>, AuthorityList)>,
+	) -> Self {
+		Self { finalized, set_id, authorities, pending_change }
+	}
+
+	/// The last verified finalized block.
+	pub fn finalized(&self) -> &Block::Header {
+		&self.finalized
+	}
+
+	/// The id of the current authority set.
+	pub fn set_id(&self) -> SetId {
+		self.set_id
+	}
+
+	/// The current authority set.
+	pub fn authorities(&self) -> &AuthorityList {
+		&self.authorities
+	}
+
+	/// Verify the given finality proof and make its block the finalized block.
+	///
+	/// `headers` must be all headers from the child of the currently finalized block up to the
+	/// block that is justified by the proof, in ascending order.
+	pub fn import(
+		&mut self,
+		proof: &FinalityProof<Block::Header>,
+		headers: &[Block::Header],
+	) -> Result<(), Error> {
+		let Some(target) = headers.last() else {
+			return Err(Error::InvalidHeaderChain("No headers given".into()))
+		};
+		if target.hash() != proof.block {
+			return Err(Error::InvalidHeaderChain(format!(
+				"Last header {} is not the block {} of the finality proof",
+				target.hash(),
+				proof.block,
+			)))
+		}
+
+		let mut parent = &self.finalized;
+		let mut pending_change = self.pending_change.clone();
+		for header in headers {
+			if *header.parent_hash() != parent.hash() ||
+				*header.number() != *parent.number() + One::one()
+			{
+				return Err(Error::InvalidHeaderChain(format!(
+					"Header #{} ({}) is not a child of #{} ({})",
+					header.number(),
+					header.hash(),
+					parent.number(),
+					parent.hash(),
+				)))
+			}
+			if find_forced_change::<Block>(header).is_some() {
+				return Err(Error::ForcedChange(header.number().to_string()))
+			}
+			if let Some(change) = find_scheduled_change::<Block>(header) {
+				if pending_change.is_some() {
+					return Err(Error::InvalidHeaderChain(format!(
+						"Header #{} schedules an authority set change while another one is pending",
+						header.number(),
+					)))
+				}
+				pending_change = Some((*header.number() + change.delay, change.next_authorities));
+			}
+			// Blocks after the enacting block are finalized by the next authority set, so the
+			// proof can't cover them.
+			if pending_change.as_ref().map_or(false, |(enacted, _)| enacted < header.number()) {
+				return Err(Error::InvalidFinalityProof(format!(
+					"Proof for #{} crosses an authority set change",
+					target.number(),
+				)))
+			}
+			parent = header;
+		}
+
+		let justification =
+			GrandpaJustification::<Block>::decode_all(&mut &proof.justification[..])
+				.map_err(|e| Error::Decode("justification", e))?;
+		if justification.target() != (*target.number(), target.hash()) {
+			return Err(Error::InvalidFinalityProof(format!(
+				"Justification doesn't finalize #{} ({})",
+				target.number(),
+				target.hash(),
+			)))
+		}
+		justification
+			.verify(self.set_id, &self.authorities)
+			.map_err(|e| Error::InvalidFinalityProof(e.to_string()))?;
+
+		log::debug!(
+			target: crate::LOG_TARGET,
+			"Verified finality of #{} ({})",
+			target.number(),
+			target.hash(),
+		);
+
+		self.finalized = target.clone();
+		match pending_change {
+			Some((enacted, authorities)) if enacted == *target.number() => {
+				self.set_id += 1;
+				self.authorities = authorities;
+				self.pending_change = None;
+			},
+			pending_change => self.pending_change = pending_change,
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use sp_consensus_grandpa::{ConsensusLog, ScheduledChange, GRANDPA_ENGINE_ID};
+	use sp_runtime::{Digest, DigestItem};
+	use substrate_test_runtime_client::runtime::{Block, Header};
+
+	fn child(parent: &Header, digest: Digest) -> Header {
+		Header::new(
+			parent.number + 1,
+			Default::default(),
+			Default::default(),
+			parent.hash(),
+			digest,
+		)
+	}
+
+	fn change_digest(log: ConsensusLog<u64>) -> Digest {
+		Digest { logs: vec![DigestItem::Consensus(GRANDPA_ENGINE_ID, log.encode())] }
+	}
+
+	fn proof_for(header: &Header) -> FinalityProof<Header> {
+		FinalityProof {
+			block: header.hash(),
+			justification: Vec::new(),
+			unknown_headers: Vec::new(),
+		}
+	}
+
+	#[test]
+	fn headers_must_be_connected_to_finalized_block() {
+		let genesis = Header::new(
+			0,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+		let mut verifier = FinalityVerifier::<Block>::new(genesis.clone(), 0, Vec::new(), None);
+
+		let unrelated = Header::new(
+			1,
+			Default::default(),
+			Default::default(),
+			[1; 32].into(),
+			Default::default(),
+		);
+		assert!(matches!(
+			verifier.import(&proof_for(&unrelated), &[unrelated.clone()]),
+			Err(Error::InvalidHeaderChain(_))
+		));
+
+		let block1 = child(&genesis, Default::default());
+		let block2 = child(&block1, Default::default());
+		assert!(matches!(
+			verifier.import(&proof_for(&block2), &[block2.clone()]),
+			Err(Error::InvalidHeaderChain(_))
+		));
+		assert!(matches!(
+			verifier.import(&proof_for(&block1), &[block1.clone(), block2]),
+			Err(Error::InvalidHeaderChain(_))
+		));
+
+		// The headers are fine, but the justification isn't.
+		assert!(matches!(verifier.import(&proof_for(&block1), &[block1]), Err(Error::Decode(..))));
+		assert_eq!(verifier.finalized(), &genesis);
+	}
+
+	#[test]
+	fn forced_changes_are_rejected() {
+		let genesis = Header::new(
+			0,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+		let mut verifier = FinalityVerifier::<Block>::new(genesis.clone(), 0, Vec::new(), None);
+
+		let change = ScheduledChange { next_authorities: Vec::new(), delay: 0 };
+		let block1 = child(&genesis, change_digest(ConsensusLog::ForcedChange(0, change)));
+		assert!(matches!(
+			verifier.import(&proof_for(&block1), &[block1]),
+			Err(Error::ForcedChange(_))
+		));
+	}
+
+	#[test]
+	fn proofs_must_not_cross_authority_set_changes() {
+		let genesis = Header::new(
+			0,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+		let mut verifier = FinalityVerifier::<Block>::new(genesis.clone(), 0, Vec::new(), None);
+
+		let change = ScheduledChange { next_authorities: Vec::new(), delay: 1 };
+		let block1 = child(&genesis, change_digest(ConsensusLog::ScheduledChange(change)));
+		let block2 = child(&block1, Default::default());
+		let block3 = child(&block2, Default::default());
+		assert!(matches!(
+			verifier.import(&proof_for(&block3), &[block1.clone(), block2.clone(), block3]),
+			Err(Error::InvalidFinalityProof(_))
+		));
+
+		// A proof for the enacting block must be checked against the current set.
+		assert!(matches!(
+			verifier.import(&proof_for(&block2), &[block1, block2]),
+			Err(Error::Decode(..))
+		));
+	}
+}
@@ -0,0 +1,273 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Verified state reads from untrusted RPC nodes.
+//!
+//! [`LightStateClient`] connects to the RPC server of a remote node and starts at a trusted block.
+//! From there it follows the finality of the remote node: the headers of newly finalized blocks
+//! are fetched with `chain_getHeader` and are only accepted together with a GRANDPA finality proof
+//! of the current authority set, as returned by `grandpa_proveFinality`. Authority set changes are
+//! tracked through the header digests.
+//!
+//! Storage is read with `state_getReadProof` at the last verified finalized block and the proof is
+//! checked against the state root of its header. Storage entries of the runtime metadata can be
+//! read and decoded directly with [`LightStateClient::storage_entry`].
+//!
+//! The runtime metadata, which is used to derive storage keys and default values, is not taken from
+//! the remote node. Instead the runtime code is read with a storage proof and its
+//! `Metadata_metadata` runtime API is executed locally.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! # use sc_light_state::LightStateClient;
+//! # use substrate_test_runtime_client::runtime::{Block, Hash};
+//! # async fn example(trusted: Hash) -> Result<(), sc_light_state::Error> {
+//! let mut client =
+//! 	LightStateClient::<Block>::from_trusted_block("ws://127.0.0.1:9944", trusted).await?;
+//! client.sync().await?;
+//!
+//! let block_number: Option<u64> = client.storage_entry("System", "Number", &[]).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+#![warn(missing_docs)]
+
+mod error;
+mod finality;
+mod storage;
+
+pub use error::Error;
+pub use finality::FinalityVerifier;
+pub use sc_consensus_grandpa::BlockNumberOps;
+pub use storage::{verify_metadata_proof, verify_read_proof, StorageEntry, StorageMetadata};
+
+use codec::{Decode, DecodeAll};
+use sc_consensus_grandpa::FinalityProof;
+use sp_consensus_grandpa::{AuthorityList, SetId};
+use sp_core::{
+	storage::{
+		well_known_keys::{CODE, HEAP_PAGES},
+		StorageKey,
+	},
+	Bytes,
+};
+use sp_runtime::traits::{Block as BlockT, HashingFor, Header as HeaderT, NumberFor, One};
+use substrate_rpc_client::{rpc_params, ws_client, ChainApi, ClientT, StateApi, WsClient};
+
+const LOG_TARGET: &str = "light-state";
+
+/// Pending authority set change as stored by `pallet-grandpa`.
+#[derive(Decode)]
+struct StoredPendingChange<N> {
+	scheduled_at: N,
+	delay: N,
+	next_authorities: AuthorityList,
+	forced: Option<N>,
+}
+
+/// Client that reads verified state from an untrusted RPC node.
+pub struct LightStateClient<Block: BlockT> {
+	rpc: WsClient,
+	verifier: FinalityVerifier<Block>,
+	metadata: StorageMetadata,
+}
+
+impl<Block: BlockT> LightStateClient<Block>
+where
+	NumberFor<Block>: BlockNumberOps,
+{
+	/// Connect to the RPC server at `uri` and start following finality with the given verifier.
+	pub async fn new(uri: &str, verifier: FinalityVerifier<Block>) -> Result<Self, Error> {
+		let rpc = ws_client(uri).await.map_err(Error::Connection)?;
+		let metadata = fetch_metadata::<Block>(&rpc, verifier.finalized()).await?;
+		Ok(Self { rpc, verifier, metadata })
+	}
+
+	/// Connect to the RPC server at `uri` and start following finality at the trusted block with
+	/// the given hash.
+	///
+	/// The authority set is read from the `Grandpa` pallet in the state of the trusted block. The
+	/// trusted block must not be a block that enacts an authority set change.
+	pub async fn from_trusted_block(uri: &str, hash: Block::Hash) -> Result<Self, Error> {
+		let rpc = ws_client(uri).await.map_err(Error::Connection)?;
+		let header = fetch_header::<Block>(&rpc, hash).await?;
+		if header.hash() != hash {
+			return Err(Error::UnknownBlock(format!("Remote returned wrong header for {hash}")))
+		}
+
+		let metadata = fetch_metadata::<Block>(&rpc, &header).await?;
+		let entries = [
+			metadata.entry("Grandpa", "Authorities")?,
+			metadata.entry("Grandpa", "CurrentSetId")?,
+			metadata.entry("Grandpa", "PendingChange")?,
+		];
+		let keys = entries.iter().map(|entry| entry.key(&[])).collect::<Result<Vec<_>, _>>()?;
+		let [authorities, set_id, pending_change] = read_storage::<Block>(&rpc, &header, &keys)
+			.await?
+			.try_into()
+			.expect("One value is returned per key; qed");
+
+		let authorities: AuthorityList =
+			decode_entry(&entries[0], authorities)?.unwrap_or_default();
+		let set_id: SetId = decode_entry(&entries[1], set_id)?.unwrap_or_default();
+		let pending_change: Option<StoredPendingChange<NumberFor<Block>>> =
+			decode_entry(&entries[2], pending_change)?;
+		let pending_change = match pending_change {
+			Some(StoredPendingChange { forced: Some(_), scheduled_at, .. }) =>
+				return Err(Error::ForcedChange(scheduled_at.to_string())),
+			Some(change) => Some((change.scheduled_at + change.delay, change.next_authorities)),
+			None => None,
+		};
+
+		let verifier = FinalityVerifier::new(header, set_id, authorities, pending_change);
+		Ok(Self { rpc, verifier, metadata })
+	}
+
+	/// The last verified finalized block.
+	pub fn finalized(&self) -> &Block::Header {
+		self.verifier.finalized()
+	}
+
+	/// The runtime metadata at the block the metadata was last derived at.
+	pub fn metadata(&self) -> &StorageMetadata {
+		&self.metadata
+	}
+
+	/// Derive the runtime metadata from the runtime code at the last verified finalized block.
+	///
+	/// Needs to be called after runtime upgrades that change storage entries.
+	pub async fn update_metadata(&mut self) -> Result<(), Error> {
+		self.metadata = fetch_metadata::<Block>(&self.rpc, self.finalized()).await?;
+		Ok(())
+	}
+
+	/// Follow the finality of the remote node up to its latest finalized block.
+	///
+	/// Returns the last verified finalized block.
+	pub async fn sync(&mut self) -> Result<&Block::Header, Error> {
+		let remote_finalized =
+			ChainApi::<(), Block::Hash, Block::Header, ()>::finalized_head(&self.rpc).await?;
+		let remote_finalized = fetch_header::<Block>(&self.rpc, remote_finalized).await?;
+
+		while self.finalized().number() < remote_finalized.number() {
+			let next = *self.finalized().number() + One::one();
+			let Some(proof) = self
+				.rpc
+				.request::<Option<Bytes>, _>("grandpa_proveFinality", rpc_params![next])
+				.await?
+			else {
+				log::debug!(target: LOG_TARGET, "No finality proof for #{next} available yet");
+				break
+			};
+			let proof = FinalityProof::<Block::Header>::decode_all(&mut &proof[..])
+				.map_err(|e| Error::Decode("finality proof", e))?;
+
+			let headers = self.headers_up_to(proof.block).await?;
+			self.verifier.import(&proof, &headers)?;
+		}
+
+		Ok(self.finalized())
+	}
+
+	/// Read the values of the given keys at the last verified finalized block.
+	pub async fn storage(&self, keys: &[Vec<u8>]) -> Result<Vec<Option<Vec<u8>>>, Error> {
+		read_storage::<Block>(&self.rpc, self.finalized(), keys).await
+	}
+
+	/// Read and decode a storage entry of the runtime metadata at the last verified finalized
+	/// block.
+	///
+	/// `keys` are the SCALE encoded keys of a storage map and must be empty for plain storage
+	/// values. Returns the default value of the entry if there is no value in storage.
+	pub async fn storage_entry<T: Decode>(
+		&self,
+		pallet: &str,
+		entry: &str,
+		keys: &[Vec<u8>],
+	) -> Result<Option<T>, Error> {
+		let entry = self.metadata.entry(pallet, entry)?;
+		let value = self.storage(&[entry.key(keys)?]).await?.pop().flatten();
+		decode_entry(&entry, value)
+	}
+
+	/// Fetch the headers from the child of the finalized block up to the block with `hash`.
+	async fn headers_up_to(&self, hash: Block::Hash) -> Result<Vec<Block::Header>, Error> {
+		let finalized = *self.finalized().number();
+		let mut headers = Vec::new();
+		let mut next = hash;
+		loop {
+			let header = fetch_header::<Block>(&self.rpc, next).await?;
+			if *header.number() <= finalized {
+				return Err(Error::InvalidHeaderChain(format!(
+					"Block {hash} is not a descendant of #{finalized}"
+				)))
+			}
+			next = *header.parent_hash();
+			let done = *header.number() == finalized + One::one();
+			headers.push(header);
+			if done {
+				break
+			}
+		}
+		headers.reverse();
+		Ok(headers)
+	}
+}
+
+/// Decode the value of a storage entry, falling back to its default value.
+fn decode_entry<T: Decode>(
+	entry: &StorageEntry,
+	value: Option<Vec<u8>>,
+) -> Result<Option<T>, Error> {
+	value
+		.as_deref()
+		.or(entry.default_value())
+		.map(|value| T::decode_all(&mut &value[..]))
+		.transpose()
+		.map_err(|e| Error::Decode("storage value", e))
+}
+
+async fn fetch_header<Block: BlockT>(
+	rpc: &WsClient,
+	hash: Block::Hash,
+) -> Result<Block::Header, Error> {
+	ChainApi::<(), Block::Hash, Block::Header, ()>::header(rpc, Some(hash))
+		.await?
+		.ok_or_else(|| Error::UnknownBlock(hash.to_string()))
+}
+
+async fn fetch_metadata<Block: BlockT>(
+	rpc: &WsClient,
+	at: &Block::Header,
+) -> Result<StorageMetadata, Error> {
+	let keys = vec![StorageKey(CODE.to_vec()), StorageKey(HEAP_PAGES.to_vec())];
+	let proof = StateApi::<Block::Hash>::read_proof(rpc, keys, Some(at.hash())).await?;
+	verify_metadata_proof::<HashingFor<Block>>(*at.state_root(), proof.proof)
+}
+
+async fn read_storage<Block: BlockT>(
+	rpc: &WsClient,
+	at: &Block::Header,
+	keys: &[Vec<u8>],
+) -> Result<Vec<Option<Vec<u8>>>, Error> {
+	let storage_keys = keys.iter().cloned().map(StorageKey).collect();
+	let proof = StateApi::<Block::Hash>::read_proof(rpc, storage_keys, Some(at.hash())).await?;
+	verify_read_proof::<HashingFor<Block>>(*at.state_root(), proof.proof, keys)
+}
@@ -0,0 +1,296 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Storage proof verification and storage keys of runtime metadata entries.
+
+use crate::Error;
+use codec::{Codec, Decode, DecodeAll};
+use frame_metadata::{
+	v14::{
+		PalletStorageMetadata, StorageEntryMetadata, StorageEntryModifier, StorageEntryType,
+		StorageHasher,
+	},
+	RuntimeMetadata, RuntimeMetadataPrefixed,
+};
+use sc_executor::WasmExecutor;
+use scale_info::form::PortableForm;
+use sp_core::{
+	storage::well_known_keys,
+	traits::{CallContext, CodeExecutor, RuntimeCode, WrappedRuntimeCode},
+	Bytes, Hasher,
+};
+use sp_state_machine::BasicExternalities;
+use sp_trie::StorageProof;
+
+/// Verify a storage proof against `state_root` and return the values of `keys`.
+///
+/// Fails if the proof doesn't contain all nodes required to read any of the keys.
+pub fn verify_read_proof<H>(
+	state_root: H::Out,
+	proof: Vec<Bytes>,
+	keys: &[Vec<u8>],
+) -> Result<Vec<Option<Vec<u8>>>, Error>
+where
+	H: Hasher + 'static,
+	H::Out: Ord + Codec,
+{
+	let proof = StorageProof::new(proof.into_iter().map(|node| node.0));
+	let mut values = sp_state_machine::read_proof_check::<H, _>(state_root, proof, keys)
+		.map_err(|e| Error::InvalidStorageProof(e.to_string()))?;
+
+	Ok(keys.iter().map(|key| values.remove(key).flatten()).collect())
+}
+
+/// Verify a storage proof of the runtime code against `state_root` and derive the runtime
+/// metadata from the proven code.
+///
+/// The proof has to contain the `:code` and `:heappages` keys.
+pub fn verify_metadata_proof<H>(
+	state_root: H::Out,
+	proof: Vec<Bytes>,
+) -> Result<StorageMetadata, Error>
+where
+	H: Hasher + 'static,
+	H::Out: Ord + Codec,
+{
+	let keys = [well_known_keys::CODE.to_vec(), well_known_keys::HEAP_PAGES.to_vec()];
+	let [code, heap_pages] = verify_read_proof::<H>(state_root, proof, &keys)?
+		.try_into()
+		.expect("One value is returned per key; qed");
+
+	let code = code.ok_or_else(|| Error::Runtime("No runtime code in state".into()))?;
+	let heap_pages = heap_pages
+		.map(|heap_pages| u64::decode_all(&mut &heap_pages[..]))
+		.transpose()
+		.map_err(|e| Error::Decode("heap pages", e))?;
+	StorageMetadata::from_runtime_code(&code, heap_pages)
+}
+
+/// The storage entries of the runtime metadata.
+pub struct StorageMetadata {
+	/// Pallet names and their storage.
+	pallets: Vec<(String, PalletStorageMetadata<PortableForm>)>,
+}
+
+impl StorageMetadata {
+	/// Derive the storage entries by calling `Metadata_metadata` of the given runtime code.
+	pub fn from_runtime_code(code: &[u8], heap_pages: Option<u64>) -> Result<Self, Error> {
+		let executor = WasmExecutor::<sp_io::SubstrateHostFunctions>::builder()
+			.with_allow_missing_host_functions(true)
+			.build();
+		let code_fetcher = WrappedRuntimeCode(code.into());
+		let runtime_code = RuntimeCode {
+			code_fetcher: &code_fetcher,
+			heap_pages,
+			hash: sp_crypto_hashing::blake2_256(code).to_vec(),
+		};
+
+		let metadata = executor
+			.call(
+				&mut BasicExternalities::new_empty(),
+				&runtime_code,
+				"Metadata_metadata",
+				&[],
+				CallContext::Offchain,
+			)
+			.0
+			.map_err(|e| Error::Runtime(e.to_string()))?;
+		let metadata =
+			Vec::<u8>::decode_all(&mut &metadata[..]).map_err(|e| Error::Decode("metadata", e))?;
+		Self::decode(&metadata)
+	}
+
+	/// Decode the storage entries from the SCALE encoded runtime metadata.
+	pub fn decode(metadata: &[u8]) -> Result<Self, Error> {
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+			.map_err(|e| Error::Decode("metadata", e))?;
+
+		let pallets = match metadata.1 {
+			RuntimeMetadata::V14(metadata) => metadata
+				.pallets
+				.into_iter()
+				.filter_map(|pallet| Some((pallet.name, pallet.storage?)))
+				.collect(),
+			RuntimeMetadata::V15(metadata) => metadata
+				.pallets
+				.into_iter()
+				.filter_map(|pallet| Some((pallet.name, pallet.storage?)))
+				.collect(),
+			metadata => return Err(Error::UnsupportedMetadata(metadata.version())),
+		};
+		Ok(Self { pallets })
+	}
+
+	/// Look up a storage entry by the name of its pallet and its own name.
+	pub fn entry(&self, pallet: &str, entry: &str) -> Result<StorageEntry, Error> {
+		self.pallets
+			.iter()
+			.filter(|(name, _)| name == pallet)
+			.find_map(|(_, storage)| {
+				let metadata = storage.entries.iter().find(|e| e.name == entry)?;
+				Some(StorageEntry { prefix: &storage.prefix, metadata })
+			})
+			.ok_or_else(|| Error::UnknownStorageEntry(pallet.into(), entry.into()))
+	}
+}
+
+/// A storage entry of the runtime metadata.
+pub struct StorageEntry<'a> {
+	prefix: &'a str,
+	metadata: &'a StorageEntryMetadata<PortableForm>,
+}
+
+impl<'a> StorageEntry<'a> {
+	/// The storage key of the value with the given SCALE encoded keys.
+	///
+	/// Plain storage values don't have any keys, maps need one key per hasher.
+	pub fn key(&self, keys: &[Vec<u8>]) -> Result<Vec<u8>, Error> {
+		let hashers = match &self.metadata.ty {
+			StorageEntryType::Plain(_) => &[][..],
+			StorageEntryType::Map { hashers, .. } => &hashers[..],
+		};
+		if hashers.len() != keys.len() {
+			return Err(Error::InvalidKeyCount { expected: hashers.len(), got: keys.len() })
+		}
+
+		let mut storage_key = storage_prefix(self.prefix, &self.metadata.name);
+		for (hasher, key) in hashers.iter().zip(keys) {
+			storage_key.extend(hash_key(hasher, key));
+		}
+		Ok(storage_key)
+	}
+
+	/// The SCALE encoded value that is returned if there is no value in storage.
+	pub fn default_value(&self) -> Option<&[u8]> {
+		match self.metadata.modifier {
+			StorageEntryModifier::Default => Some(&self.metadata.default),
+			StorageEntryModifier::Optional => None,
+		}
+	}
+}
+
+/// The storage prefix of all values of a storage entry.
+fn storage_prefix(pallet_prefix: &str, entry: &str) -> Vec<u8> {
+	let mut prefix = sp_crypto_hashing::twox_128(pallet_prefix.as_bytes()).to_vec();
+	prefix.extend(sp_crypto_hashing::twox_128(entry.as_bytes()));
+	prefix
+}
+
+/// Hash a SCALE encoded key of a storage map.
+fn hash_key(hasher: &StorageHasher, key: &[u8]) -> Vec<u8> {
+	match hasher {
+		StorageHasher::Blake2_128 => sp_crypto_hashing::blake2_128(key).to_vec(),
+		StorageHasher::Blake2_256 => sp_crypto_hashing::blake2_256(key).to_vec(),
+		StorageHasher::Blake2_128Concat => [&sp_crypto_hashing::blake2_128(key)[..], key].concat(),
+		StorageHasher::Twox128 => sp_crypto_hashing::twox_128(key).to_vec(),
+		StorageHasher::Twox256 => sp_crypto_hashing::twox_256(key).to_vec(),
+		StorageHasher::Twox64Concat => [&sp_crypto_hashing::twox_64(key)[..], key].concat(),
+		StorageHasher::Identity => key.to_vec(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::{storage::StateVersion, Blake2Hasher, H256};
+	use sp_state_machine::{prove_read, InMemoryBackend};
+
+	#[test]
+	fn storage_keys_match_frame_support() {
+		// `System::Account` of `[1; 32]`.
+		let mut key = storage_prefix("System", "Account");
+		key.extend(hash_key(&StorageHasher::Blake2_128Concat, &[1; 32]));
+
+		assert_eq!(
+			array_bytes::bytes2hex("", &key),
+			"26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da9\
+			c035f853fcd0f0589e30c9e2dc1a0f57\
+			0101010101010101010101010101010101010101010101010101010101010101",
+		);
+	}
+
+	#[test]
+	fn read_proofs_are_verified() {
+		let backend = InMemoryBackend::<Blake2Hasher>::from((
+			vec![(None, vec![(b"key1".to_vec(), Some(b"value1".to_vec()))])],
+			StateVersion::V1,
+		));
+		let root = *backend.root();
+		let proof = prove_read(backend, &[b"key1", b"key2"]).unwrap();
+		let proof: Vec<Bytes> = proof.into_iter_nodes().map(Into::into).collect();
+
+		let keys = vec![b"key1".to_vec(), b"key2".to_vec()];
+		assert_eq!(
+			verify_read_proof::<Blake2Hasher>(root, proof.clone(), &keys).unwrap(),
+			vec![Some(b"value1".to_vec()), None],
+		);
+
+		// The proof doesn't match another root.
+		assert!(verify_read_proof::<Blake2Hasher>(H256::random(), proof.clone(), &keys).is_err());
+
+		// Nodes can't be left out.
+		assert!(verify_read_proof::<Blake2Hasher>(root, proof[1..].to_vec(), &keys).is_err());
+	}
+
+	fn prove_runtime_code(code: Option<Vec<u8>>) -> (H256, Vec<Bytes>) {
+		let backend = InMemoryBackend::<Blake2Hasher>::from((
+			vec![(None, vec![(well_known_keys::CODE.to_vec(), code)])],
+			StateVersion::V1,
+		));
+		let root = *backend.root();
+		let proof =
+			prove_read(backend, &[well_known_keys::CODE, well_known_keys::HEAP_PAGES]).unwrap();
+		(root, proof.into_iter_nodes().map(Into::into).collect())
+	}
+
+	#[test]
+	fn metadata_is_derived_from_proven_code() {
+		let (root, proof) =
+			prove_runtime_code(Some(kitchensink_runtime::wasm_binary_unwrap().to_vec()));
+
+		let metadata = verify_metadata_proof::<Blake2Hasher>(root, proof).unwrap();
+		assert_eq!(
+			metadata.entry("System", "Number").unwrap().key(&[]).unwrap(),
+			storage_prefix("System", "Number"),
+		);
+		assert!(metadata.entry("Grandpa", "Authorities").is_ok());
+	}
+
+	#[test]
+	fn tampered_metadata_is_rejected() {
+		let code = kitchensink_runtime::wasm_binary_unwrap().to_vec();
+		let (root, _) = prove_runtime_code(Some(code.clone()));
+
+		// A remote node serving a different runtime, and with it different metadata, can't prove
+		// its code against the trusted state root.
+		let mut tampered = code;
+		*tampered.last_mut().unwrap() ^= 1;
+		let (_, tampered_proof) = prove_runtime_code(Some(tampered));
+		assert!(matches!(
+			verify_metadata_proof::<Blake2Hasher>(root, tampered_proof),
+			Err(Error::InvalidStorageProof(_)),
+		));
+
+		// State without runtime code doesn't yield any metadata.
+		let (root, proof) = prove_runtime_code(None);
+		assert!(matches!(
+			verify_metadata_proof::<Blake2Hasher>(root, proof),
+			Err(Error::Runtime(_)),
+		));
+	}
+}
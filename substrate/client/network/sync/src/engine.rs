@@ -32,7 +32,7 @@ use crate::{
 		syncing_service::{SyncingService, ToServiceCommand},
 	},
 	strategy::{
-		warp::{EncodedProof, WarpProofRequest, WarpSyncParams},
+		warp::{EncodedProof, TargetBlockFuture, WarpProofRequest, WarpSyncParams},
 		SyncingAction, SyncingConfig, SyncingStrategy,
 	},
 	types::{
@@ -42,11 +42,7 @@ use crate::{
 };
 
 use codec::{Decode, DecodeAll, Encode};
-use futures::{
-	channel::oneshot,
-	future::{BoxFuture, Fuse},
-	FutureExt, StreamExt,
-};
+use futures::{channel::oneshot, future::Fuse, FutureExt, StreamExt};
 use libp2p::{request_response::OutboundFailure, PeerId};
 use log::{debug, error, trace};
 use prometheus_endpoint::{
@@ -279,9 +275,8 @@ pub struct SyncingEngine<B: BlockT, Client> {
 	/// The `PeerId`'s of all boot nodes.
 	boot_node_ids: HashSet<PeerId>,
 
-	/// A channel to get target block header if we skip over proofs downloading during warp sync.
-	warp_sync_target_block_header_rx_fused:
-		Fuse<BoxFuture<'static, Result<B::Header, oneshot::Canceled>>>,
+	/// A future to get target block header if we skip over proofs downloading during warp sync.
+	warp_sync_target_block_header_rx_fused: Fuse<TargetBlockFuture<B>>,
 
 	/// Protocol name used for block announcements
 	block_announce_protocol_name: ProtocolName,
@@ -427,7 +422,7 @@ where
 				.expect("Genesis block exists; qed"),
 		);
 
-		// Split warp sync params into warp sync config and a channel to retreive target block
+		// Split warp sync params into warp sync config and a future to retrieve target block
 		// header.
 		let (warp_sync_config, warp_sync_target_block_header_rx) =
 			warp_sync_params.map_or((None, None), |params| {
@@ -435,10 +430,10 @@ where
 				(Some(config), target_block_rx)
 			});
 
-		// Make sure polling of the target block channel is a no-op if there is no block to
+		// Make sure polling of the target block future is a no-op if there is no block to
 		// retrieve.
 		let warp_sync_target_block_header_rx_fused = warp_sync_target_block_header_rx
-			.map_or(futures::future::pending().boxed().fuse(), |rx| rx.boxed().fuse());
+			.map_or(futures::future::pending().boxed().fuse(), |rx| rx.fuse());
 
 		// Initialize syncing strategy.
 		let strategy =
@@ -948,7 +943,10 @@ where
 		}
 	}
 
-	fn pass_warp_sync_target_block_header(&mut self, header: Result<B::Header, oneshot::Canceled>) {
+	fn pass_warp_sync_target_block_header(
+		&mut self,
+		header: Result<B::Header, Box<dyn std::error::Error + Send + Sync>>,
+	) {
 		match header {
 			Ok(header) =>
 				if let SyncingStrategy::WarpSyncStrategy(warp_sync) = &mut self.strategy {
@@ -26,7 +26,7 @@ use crate::{
 	LOG_TARGET,
 };
 use codec::{Decode, Encode};
-use futures::channel::oneshot;
+use futures::{channel::oneshot, future::BoxFuture, FutureExt};
 use libp2p::PeerId;
 use log::{debug, error, trace};
 use sc_network_common::sync::message::{
@@ -37,7 +37,7 @@ use sp_runtime::{
 	traits::{Block as BlockT, Header, NumberFor, Zero},
 	Justifications, SaturatedConversion,
 };
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{collections::HashMap, error::Error, fmt, sync::Arc};
 
 /// Number of peers that need to be connected before warp sync is started.
 const MIN_PEERS_TO_START_WARP_SYNC: usize = 3;
@@ -80,6 +80,45 @@ pub trait WarpSyncProvider<Block: BlockT>: Send + Sync {
 	fn current_authorities(&self) -> AuthorityList;
 }
 
+/// Provides the header of a trusted block whose state should be downloaded by warp sync.
+///
+/// This is an alternative to [`WarpSyncProvider`] for chains that can't prove finality of the
+/// target block with warp proofs, e.g. parachains or chains without GRANDPA. It is up to the
+/// provider to ensure that the returned header can be trusted.
+#[async_trait::async_trait]
+pub trait WarpSyncTargetProvider<Block: BlockT>: Send + Sync {
+	/// Wait for and return the header of the target block.
+	async fn target_block(&self) -> Result<Block::Header, Box<dyn Error + Send + Sync>>;
+}
+
+/// Warp sync target provider that returns a fixed checkpoint header.
+///
+/// The checkpoint is usually taken from the chain spec and is trusted as is.
+pub struct CheckpointTargetProvider<Block: BlockT> {
+	header: Block::Header,
+}
+
+impl<Block: BlockT> CheckpointTargetProvider<Block> {
+	/// Create a new provider for the given checkpoint header.
+	pub fn new(header: Block::Header) -> Self {
+		Self { header }
+	}
+
+	/// Create a new provider from a SCALE encoded header.
+	pub fn from_encoded(header: &[u8]) -> Result<Self, String> {
+		let header = Block::Header::decode(&mut &header[..])
+			.map_err(|e| format!("Failed to decode checkpoint header: {e}"))?;
+		Ok(Self::new(header))
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block: BlockT> WarpSyncTargetProvider<Block> for CheckpointTargetProvider<Block> {
+	async fn target_block(&self) -> Result<Block::Header, Box<dyn Error + Send + Sync>> {
+		Ok(self.header.clone())
+	}
+}
+
 mod rep {
 	use sc_network::ReputationChange as Rep;
 
@@ -153,6 +192,9 @@ pub enum WarpSyncParams<Block: BlockT> {
 	///
 	/// It is expected that the header provider ensures that the header is trusted.
 	WaitForTarget(oneshot::Receiver<<Block as BlockT>::Header>),
+	/// Skip downloading proofs and download the state of the header returned by the given
+	/// target provider.
+	WithTargetProvider(Arc<dyn WarpSyncTargetProvider<Block>>),
 }
 
 /// Warp sync configuration as accepted by [`WarpSync`].
@@ -165,15 +207,25 @@ pub enum WarpSyncConfig<Block: BlockT> {
 	WaitForTarget,
 }
 
+/// Future resolving to the header of the warp sync target block.
+pub type TargetBlockFuture<Block> =
+	BoxFuture<'static, Result<<Block as BlockT>::Header, Box<dyn Error + Send + Sync>>>;
+
 impl<Block: BlockT> WarpSyncParams<Block> {
-	/// Split `WarpSyncParams` into `WarpSyncConfig` and warp sync target block header receiver.
-	pub fn split(
-		self,
-	) -> (WarpSyncConfig<Block>, Option<oneshot::Receiver<<Block as BlockT>::Header>>) {
+	/// Split `WarpSyncParams` into `WarpSyncConfig` and a future resolving to the warp sync target
+	/// block header.
+	pub fn split(self) -> (WarpSyncConfig<Block>, Option<TargetBlockFuture<Block>>) {
 		match self {
 			WarpSyncParams::WithProvider(provider) =>
 				(WarpSyncConfig::WithProvider(provider), None),
-			WarpSyncParams::WaitForTarget(rx) => (WarpSyncConfig::WaitForTarget, Some(rx)),
+			WarpSyncParams::WaitForTarget(rx) => (
+				WarpSyncConfig::WaitForTarget,
+				Some(rx.map(|header| header.map_err(Into::into)).boxed()),
+			),
+			WarpSyncParams::WithTargetProvider(provider) => (
+				WarpSyncConfig::WaitForTarget,
+				Some(async move { provider.target_block().await }.boxed()),
+			),
 		}
 	}
 }
@@ -1440,4 +1492,21 @@ mod test {
 		assert_eq!(result.target_body, body);
 		assert_eq!(result.target_justifications, justifications);
 	}
+
+	#[test]
+	fn checkpoint_target_provider_returns_checkpoint_header() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let header = client.header(client.chain_info().genesis_hash).unwrap().unwrap();
+
+		let provider = CheckpointTargetProvider::<Block>::from_encoded(&header.encode()).unwrap();
+		let (config, target_block) = WarpSyncParams::WithTargetProvider(Arc::new(provider)).split();
+
+		assert!(matches!(config, WarpSyncConfig::WaitForTarget));
+		assert_eq!(futures::executor::block_on(target_block.unwrap()).unwrap(), header);
+	}
+
+	#[test]
+	fn checkpoint_with_invalid_header_is_rejected() {
+		assert!(CheckpointTargetProvider::<Block>::from_encoded(&[1, 2, 3]).is_err());
+	}
 }
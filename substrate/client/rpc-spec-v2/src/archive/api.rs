@@ -19,7 +19,9 @@
 //! API trait of the archive methods.
 
 use crate::{
-	common::events::{ArchiveStorageResult, PaginatedStorageQuery},
+	common::events::{
+		ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageEvent, PaginatedStorageQuery,
+	},
 	MethodResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
@@ -94,14 +96,44 @@ pub trait ArchiveApi<Hash> {
 
 	/// Returns storage entries at a specific block's state.
 	///
+	/// The results are reported as `storage` events, followed by a single `storageDone` event
+	/// once all items were queried. Descendant queries are iterated page by page and streamed to
+	/// the subscriber, such that arbitrarily large prefixes can be queried.
+	///
 	/// # Unstable
 	///
 	/// This method is unstable and subject to change in the future.
-	#[method(name = "archive_unstable_storage", blocking)]
+	#[subscription(
+		name = "archive_unstable_storage" => "archive_unstable_storageEvent",
+		unsubscribe = "archive_unstable_stopStorage",
+		item = ArchiveStorageEvent,
+	)]
 	fn archive_unstable_storage(
 		&self,
 		hash: Hash,
 		items: Vec<PaginatedStorageQuery<String>>,
 		child_trie: Option<String>,
-	) -> RpcResult<ArchiveStorageResult>;
+	);
+
+	/// Returns the storage difference between two blocks.
+	///
+	/// Every key that starts with the key of one of the provided `items` is compared between
+	/// `hash` and `previous_hash`, and reported as `added`, `modified` or `deleted`. An empty
+	/// list of items compares the full main trie. If `previous_hash` is not provided, the parent
+	/// of `hash` is used.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[subscription(
+		name = "archive_unstable_storageDiff" => "archive_unstable_storageDiffEvent",
+		unsubscribe = "archive_unstable_storageDiff_stopStorageDiff",
+		item = ArchiveStorageDiffEvent,
+	)]
+	fn archive_unstable_storage_diff(
+		&self,
+		hash: Hash,
+		items: Vec<ArchiveStorageDiffItem<String>>,
+		previous_hash: Option<Hash>,
+	);
 }
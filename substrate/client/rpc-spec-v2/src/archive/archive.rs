@@ -19,17 +19,30 @@
 //! API implementation for `archive`.
 
 use crate::{
-	archive::{error::Error as ArchiveError, ArchiveApiServer},
-	common::events::{ArchiveStorageResult, PaginatedStorageQuery},
-	hex_string, MethodResult,
+	archive::{
+		archive_storage::{ArchiveStorage, ArchiveStorageDiff, DiffDetails},
+		error::Error as ArchiveError,
+		ArchiveApiServer,
+	},
+	common::events::{
+		ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageDiffType,
+		PaginatedStorageQuery,
+	},
+	hex_string, MethodResult, SubscriptionTaskExecutor,
 };
 
 use codec::Encode;
-use jsonrpsee::core::{async_trait, RpcResult};
+use futures::FutureExt;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	PendingSubscriptionSink, SubscriptionSink,
+};
 use sc_client_api::{
 	Backend, BlockBackend, BlockchainEvents, CallExecutor, ChildInfo, ExecutorProvider, StorageKey,
 	StorageProvider,
 };
+use sc_rpc::utils::to_sub_message;
+use serde::Serialize;
 use sp_api::{CallApiAt, CallContext};
 use sp_blockchain::{
 	Backend as BlockChainBackend, Error as BlockChainError, HeaderBackend, HeaderMetadata,
@@ -40,16 +53,20 @@ use sp_runtime::{
 	SaturatedConversion,
 };
 use std::{collections::HashSet, marker::PhantomData, sync::Arc};
-
-use super::archive_storage::ArchiveStorage;
+use tokio::sync::mpsc;
 
 /// The configuration of [`Archive`].
+#[derive(Debug, Clone)]
 pub struct ArchiveConfig {
 	/// The maximum number of items the `archive_storage` can return for a descendant query before
 	/// pagination is required.
 	pub max_descendant_responses: usize,
-	/// The maximum number of queried items allowed for the `archive_storage` at a time.
+	/// The maximum number of queried items allowed for the `archive_storage` and
+	/// `archive_storageDiff` at a time.
 	pub max_queried_items: usize,
+	/// The maximum number of changed keys the `archive_storageDiff` reports over all the queried
+	/// items, before the comparison is aborted with an error.
+	pub max_storage_diff_keys: usize,
 }
 
 /// The maximum number of items the `archive_storage` can return for a descendant query before
@@ -64,11 +81,21 @@ const MAX_DESCENDANT_RESPONSES: usize = 5;
 /// `MAX_DESCENDANT_RESPONSES`.
 const MAX_QUERIED_ITEMS: usize = 8;
 
+/// The maximum number of changed keys the `archive_storageDiff` reports over all the queried items.
+const MAX_STORAGE_DIFF_KEYS: usize = 16 * 1024;
+
+/// The buffer capacity of the channel between the generation of storage events and the
+/// subscription sink.
+///
+/// Keeps the memory bounded when the subscriber reads the events slower than they are produced.
+const STORAGE_EVENTS_BUFFER_CAPACITY: usize = 16;
+
 impl Default for ArchiveConfig {
 	fn default() -> Self {
 		Self {
 			max_descendant_responses: MAX_DESCENDANT_RESPONSES,
 			max_queried_items: MAX_QUERIED_ITEMS,
+			max_storage_diff_keys: MAX_STORAGE_DIFF_KEYS,
 		}
 	}
 }
@@ -84,8 +111,14 @@ pub struct Archive<BE: Backend<Block>, Block: BlockT, Client> {
 	/// The maximum number of items the `archive_storage` can return for a descendant query before
 	/// pagination is required.
 	storage_max_descendant_responses: usize,
-	/// The maximum number of queried items allowed for the `archive_storage` at a time.
+	/// The maximum number of queried items allowed for the `archive_storage` and
+	/// `archive_storageDiff` at a time.
 	storage_max_queried_items: usize,
+	/// The maximum number of changed keys the `archive_storageDiff` reports over all the queried
+	/// items.
+	storage_max_diff_keys: usize,
+	/// Executor to spawn subscriptions.
+	executor: SubscriptionTaskExecutor,
 	/// Phantom member to pin the block type.
 	_phantom: PhantomData<Block>,
 }
@@ -97,6 +130,7 @@ impl<BE: Backend<Block>, Block: BlockT, Client> Archive<BE, Block, Client> {
 		backend: Arc<BE>,
 		genesis_hash: GenesisHash,
 		config: ArchiveConfig,
+		executor: SubscriptionTaskExecutor,
 	) -> Self {
 		let genesis_hash = hex_string(&genesis_hash.as_ref());
 		Self {
@@ -105,6 +139,8 @@ impl<BE: Backend<Block>, Block: BlockT, Client> Archive<BE, Block, Client> {
 			genesis_hash,
 			storage_max_descendant_responses: config.max_descendant_responses,
 			storage_max_queried_items: config.max_queried_items,
+			storage_max_diff_keys: config.max_storage_diff_keys,
+			executor,
 			_phantom: PhantomData,
 		}
 	}
@@ -122,6 +158,80 @@ fn parse_hex_param(param: String) -> Result<Vec<u8>, ArchiveError> {
 	array_bytes::hex2bytes(&param).map_err(|_| ArchiveError::InvalidParam(param))
 }
 
+/// Parse the items of the `archive_storage` method.
+fn parse_storage_items(
+	items: Vec<PaginatedStorageQuery<String>>,
+) -> Result<Vec<PaginatedStorageQuery<StorageKey>>, ArchiveError> {
+	items
+		.into_iter()
+		.map(|query| {
+			let key = StorageKey(parse_hex_param(query.key)?);
+			let pagination_start_key = query
+				.pagination_start_key
+				.map(|key| parse_hex_param(key).map(|key| StorageKey(key)))
+				.transpose()?;
+
+			// Paginated start key is only supported
+			if pagination_start_key.is_some() && !query.query_type.is_descendant_query() {
+				return Err(ArchiveError::InvalidParam(
+					"Pagination start key is only supported for descendants queries".to_string(),
+				))
+			}
+
+			Ok(PaginatedStorageQuery { key, query_type: query.query_type, pagination_start_key })
+		})
+		.collect()
+}
+
+/// Parse the items of the `archive_storageDiff` method.
+///
+/// Duplicated items are reported only once. An empty list of items compares the values of the
+/// full main trie.
+fn parse_diff_items(
+	items: Vec<ArchiveStorageDiffItem<String>>,
+) -> Result<Vec<DiffDetails>, ArchiveError> {
+	if items.is_empty() {
+		return Ok(vec![DiffDetails {
+			key: StorageKey(Vec::new()),
+			return_type: ArchiveStorageDiffType::Value,
+			child_trie_key: None,
+		}])
+	}
+
+	let mut parsed: Vec<DiffDetails> = Vec::with_capacity(items.len());
+
+	for item in items {
+		let details = DiffDetails {
+			key: StorageKey(parse_hex_param(item.key)?),
+			return_type: item.return_type,
+			child_trie_key: item
+				.child_trie_key
+				.map(|child_trie_key| parse_hex_param(child_trie_key))
+				.transpose()?
+				.map(ChildInfo::new_default_from_vec),
+		};
+
+		if !parsed.contains(&details) {
+			parsed.push(details);
+		}
+	}
+
+	Ok(parsed)
+}
+
+/// Forward the events produced on the receiving end of the channel to the subscription sink.
+///
+/// Returns when either the producer or the subscriber is gone. Dropping the receiver afterwards
+/// signals the producer to stop.
+async fn forward_events<T: Serialize>(sink: SubscriptionSink, mut receiver: mpsc::Receiver<T>) {
+	while let Some(event) = receiver.recv().await {
+		let msg = to_sub_message(&sink, &event);
+		if sink.send(msg).await.is_err() {
+			return
+		}
+	}
+}
+
 #[async_trait]
 impl<BE, Block, Client> ArchiveApiServer<Block::Hash> for Archive<BE, Block, Client>
 where
@@ -236,45 +346,117 @@ where
 
 	fn archive_unstable_storage(
 		&self,
+		pending: PendingSubscriptionSink,
 		hash: Block::Hash,
 		items: Vec<PaginatedStorageQuery<String>>,
 		child_trie: Option<String>,
-	) -> RpcResult<ArchiveStorageResult> {
-		let items = items
-			.into_iter()
-			.map(|query| {
-				let key = StorageKey(parse_hex_param(query.key)?);
-				let pagination_start_key = query
-					.pagination_start_key
-					.map(|key| parse_hex_param(key).map(|key| StorageKey(key)))
-					.transpose()?;
-
-				// Paginated start key is only supported
-				if pagination_start_key.is_some() && !query.query_type.is_descendant_query() {
-					return Err(ArchiveError::InvalidParam(
-						"Pagination start key is only supported for descendants queries"
-							.to_string(),
-					))
-				}
+	) {
+		let parsed = parse_storage_items(items).and_then(|items| {
+			if items.len() > self.storage_max_queried_items {
+				return Err(ArchiveError::InvalidParam(format!(
+					"Too many items queried: {} (maximum {})",
+					items.len(),
+					self.storage_max_queried_items
+				)))
+			}
 
-				Ok(PaginatedStorageQuery {
-					key,
-					query_type: query.query_type,
-					pagination_start_key,
-				})
-			})
-			.collect::<Result<Vec<_>, ArchiveError>>()?;
-
-		let child_trie = child_trie
-			.map(|child_trie| parse_hex_param(child_trie))
-			.transpose()?
-			.map(ChildInfo::new_default_from_vec);
-
-		let storage_client = ArchiveStorage::new(
-			self.client.clone(),
-			self.storage_max_descendant_responses,
-			self.storage_max_queried_items,
-		);
-		Ok(storage_client.handle_query(hash, items, child_trie))
+			let child_trie = child_trie
+				.map(|child_trie| parse_hex_param(child_trie))
+				.transpose()?
+				.map(ChildInfo::new_default_from_vec);
+
+			Ok((items, child_trie))
+		});
+
+		let executor = self.executor.clone();
+		let storage_client =
+			ArchiveStorage::new(self.client.clone(), self.storage_max_descendant_responses);
+
+		let fut = async move {
+			let (items, child_trie) = match parsed {
+				Ok(parsed) => parsed,
+				Err(error) => {
+					let _ = pending.reject(error).await;
+					return
+				},
+			};
+
+			let Ok(sink) = pending.accept().await else { return };
+
+			// The storage reads block, while the channel provides backpressure to them.
+			let (sender, receiver) = mpsc::channel(STORAGE_EVENTS_BUFFER_CAPACITY);
+			let generate =
+				async move { storage_client.generate_events(hash, items, child_trie, sender) };
+			executor.spawn_blocking("substrate-rpc-subscription", Some("rpc"), generate.boxed());
+			forward_events(sink, receiver).await;
+		};
+
+		self.executor.spawn("substrate-rpc-subscription", Some("rpc"), fut.boxed());
+	}
+
+	fn archive_unstable_storage_diff(
+		&self,
+		pending: PendingSubscriptionSink,
+		hash: Block::Hash,
+		items: Vec<ArchiveStorageDiffItem<String>>,
+		previous_hash: Option<Block::Hash>,
+	) {
+		let parsed = parse_diff_items(items).and_then(|items| {
+			if items.len() > self.storage_max_queried_items {
+				return Err(ArchiveError::InvalidParam(format!(
+					"Too many items queried: {} (maximum {})",
+					items.len(),
+					self.storage_max_queried_items
+				)))
+			}
+
+			Ok(items)
+		});
+
+		let client = self.client.clone();
+		let executor = self.executor.clone();
+		let storage_client =
+			ArchiveStorageDiff::new(self.client.clone(), self.storage_max_diff_keys);
+
+		let fut = async move {
+			let items = match parsed {
+				Ok(items) => items,
+				Err(error) => {
+					let _ = pending.reject(error).await;
+					return
+				},
+			};
+
+			let Ok(sink) = pending.accept().await else { return };
+
+			// The diff is computed against the parent block by default.
+			let previous_hash = match previous_hash {
+				Some(previous_hash) => previous_hash,
+				None => match client.header(hash) {
+					Ok(Some(header)) => *header.parent_hash(),
+					Ok(None) => {
+						let event = ArchiveStorageDiffEvent::err(format!(
+							"Block header is not present: {hash:?}"
+						));
+						let _ = sink.send(to_sub_message(&sink, &event)).await;
+						return
+					},
+					Err(error) => {
+						let event = ArchiveStorageDiffEvent::err(error.to_string());
+						let _ = sink.send(to_sub_message(&sink, &event)).await;
+						return
+					},
+				},
+			};
+
+			// The storage iteration blocks, while the channel provides backpressure to it.
+			let (sender, receiver) = mpsc::channel(STORAGE_EVENTS_BUFFER_CAPACITY);
+			let generate =
+				async move { storage_client.generate_events(hash, previous_hash, items, sender) };
+			executor.spawn_blocking("substrate-rpc-subscription", Some("rpc"), generate.boxed());
+			forward_events(sink, receiver).await;
+		};
+
+		self.executor.spawn("substrate-rpc-subscription", Some("rpc"), fut.boxed());
 	}
 }
@@ -16,40 +16,44 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-//! Implementation of the `archive_storage` method.
+//! Implementation of the `archive_storage` and `archive_storageDiff` methods.
 
-use std::sync::Arc;
+use std::{cmp::Ordering, marker::PhantomData, sync::Arc};
 
 use sc_client_api::{Backend, ChildInfo, StorageKey, StorageProvider};
 use sp_runtime::traits::Block as BlockT;
+use tokio::sync::mpsc;
 
-use crate::common::{
-	events::{ArchiveStorageResult, PaginatedStorageQuery, StorageQueryType},
-	storage::{IterQueryType, QueryIter, Storage},
+use crate::{
+	common::{
+		events::{
+			ArchiveStorageDiffEvent, ArchiveStorageDiffOperationType, ArchiveStorageDiffResult,
+			ArchiveStorageDiffType, ArchiveStorageEvent, PaginatedStorageQuery, StorageQueryType,
+			StorageResult,
+		},
+		storage::{IterQueryType, QueryIter, QueryResult, Storage},
+	},
+	hex_string,
 };
 
+/// Sender of the events produced by the `archive_storage` method.
+pub type ArchiveStorageEventSender = mpsc::Sender<ArchiveStorageEvent>;
+
+/// Sender of the events produced by the `archive_storageDiff` method.
+pub type ArchiveStorageDiffEventSender = mpsc::Sender<ArchiveStorageDiffEvent>;
+
 /// Generates the events of the `archive_storage` method.
 pub struct ArchiveStorage<Client, Block, BE> {
 	/// Storage client.
 	client: Storage<Client, Block, BE>,
-	/// The maximum number of responses the API can return for a descendant query at a time.
+	/// The maximum number of keys that are iterated at once for a descendant query.
 	storage_max_descendant_responses: usize,
-	/// The maximum number of queried items allowed for the `archive_storage` at a time.
-	storage_max_queried_items: usize,
 }
 
 impl<Client, Block, BE> ArchiveStorage<Client, Block, BE> {
 	/// Constructs a new [`ArchiveStorage`].
-	pub fn new(
-		client: Arc<Client>,
-		storage_max_descendant_responses: usize,
-		storage_max_queried_items: usize,
-	) -> Self {
-		Self {
-			client: Storage::new(client),
-			storage_max_descendant_responses,
-			storage_max_queried_items,
-		}
+	pub fn new(client: Arc<Client>, storage_max_descendant_responses: usize) -> Self {
+		Self { client: Storage::new(client), storage_max_descendant_responses }
 	}
 }
 
@@ -59,71 +63,308 @@ where
 	BE: Backend<Block> + 'static,
 	Client: StorageProvider<Block, BE> + 'static,
 {
-	/// Generate the response of the `archive_storage` method.
-	pub fn handle_query(
+	/// Generate the events of the `archive_storage` method.
+	///
+	/// Terminates with a `storageDone` event, unless an error is encountered or the receiving end
+	/// of the `sender` is dropped.
+	///
+	/// This blocks on the storage reads and on the `sender`, which provides backpressure, and must
+	/// therefore be spawned as a blocking task.
+	pub fn generate_events(
 		&self,
 		hash: Block::Hash,
-		mut items: Vec<PaginatedStorageQuery<StorageKey>>,
+		items: Vec<PaginatedStorageQuery<StorageKey>>,
 		child_key: Option<ChildInfo>,
-	) -> ArchiveStorageResult {
-		let discarded_items = items.len().saturating_sub(self.storage_max_queried_items);
-		items.truncate(self.storage_max_queried_items);
+		sender: ArchiveStorageEventSender,
+	) {
+		let child_trie_key =
+			child_key.as_ref().map(|child_key| hex_string(&child_key.storage_key()));
 
-		let mut storage_results = Vec::with_capacity(items.len());
 		for item in items {
-			match item.query_type {
+			let ty = match item.query_type {
 				StorageQueryType::Value => {
-					match self.client.query_value(hash, &item.key, child_key.as_ref()) {
-						Ok(Some(value)) => storage_results.push(value),
-						Ok(None) => continue,
-						Err(error) => return ArchiveStorageResult::err(error),
+					let result = self.client.query_value(hash, &item.key, child_key.as_ref());
+					if !send_result(&sender, result, &child_trie_key) {
+						return
 					}
+					continue
 				},
-				StorageQueryType::Hash =>
-					match self.client.query_hash(hash, &item.key, child_key.as_ref()) {
-						Ok(Some(value)) => storage_results.push(value),
-						Ok(None) => continue,
-						Err(error) => return ArchiveStorageResult::err(error),
-					},
-				StorageQueryType::ClosestDescendantMerkleValue =>
-					match self.client.query_merkle_value(hash, &item.key, child_key.as_ref()) {
-						Ok(Some(value)) => storage_results.push(value),
-						Ok(None) => continue,
-						Err(error) => return ArchiveStorageResult::err(error),
-					},
-				StorageQueryType::DescendantsValues => {
-					match self.client.query_iter_pagination(
-						QueryIter {
-							query_key: item.key,
-							ty: IterQueryType::Value,
-							pagination_start_key: item.pagination_start_key,
-						},
-						hash,
-						child_key.as_ref(),
-						self.storage_max_descendant_responses,
-					) {
-						Ok((results, _)) => storage_results.extend(results),
-						Err(error) => return ArchiveStorageResult::err(error),
+				StorageQueryType::Hash => {
+					let result = self.client.query_hash(hash, &item.key, child_key.as_ref());
+					if !send_result(&sender, result, &child_trie_key) {
+						return
 					}
+					continue
 				},
-				StorageQueryType::DescendantsHashes => {
-					match self.client.query_iter_pagination(
-						QueryIter {
-							query_key: item.key,
-							ty: IterQueryType::Hash,
-							pagination_start_key: item.pagination_start_key,
-						},
-						hash,
-						child_key.as_ref(),
-						self.storage_max_descendant_responses,
-					) {
-						Ok((results, _)) => storage_results.extend(results),
-						Err(error) => return ArchiveStorageResult::err(error),
+				StorageQueryType::ClosestDescendantMerkleValue => {
+					let result =
+						self.client.query_merkle_value(hash, &item.key, child_key.as_ref());
+					if !send_result(&sender, result, &child_trie_key) {
+						return
 					}
+					continue
+				},
+				StorageQueryType::DescendantsValues => IterQueryType::Value,
+				StorageQueryType::DescendantsHashes => IterQueryType::Hash,
+			};
+
+			let query = QueryIter {
+				query_key: item.key,
+				ty,
+				pagination_start_key: item.pagination_start_key,
+			};
+			if !self.send_descendants(hash, query, child_key.as_ref(), &child_trie_key, &sender) {
+				return
+			}
+		}
+
+		blocking_send(&sender, ArchiveStorageEvent::StorageDone);
+	}
+
+	/// Iterate over the descendants of the query key page by page and report them.
+	///
+	/// Only `storage_max_descendant_responses` keys are iterated at once, while the channel to the
+	/// subscriber provides backpressure between the pages.
+	///
+	/// Returns `false` if the generation of events should stop.
+	fn send_descendants(
+		&self,
+		hash: Block::Hash,
+		query: QueryIter,
+		child_key: Option<&ChildInfo>,
+		child_trie_key: &Option<String>,
+		sender: &ArchiveStorageEventSender,
+	) -> bool {
+		let mut next_query = Some(query);
+
+		while let Some(query) = next_query.take() {
+			let (results, next) = match self.client.query_iter_pagination(
+				query,
+				hash,
+				child_key,
+				self.storage_max_descendant_responses,
+			) {
+				Ok(page) => page,
+				Err(error) => {
+					blocking_send(sender, ArchiveStorageEvent::err(error));
+					return false
+				},
+			};
+
+			for result in results {
+				let event = ArchiveStorageEvent::result(result, child_trie_key.clone());
+				if !blocking_send(sender, event) {
+					return false
+				}
+			}
+
+			next_query = next;
+		}
+
+		true
+	}
+}
+
+/// Report the result of a single storage query.
+///
+/// Returns `false` if the generation of events should stop.
+fn send_result(
+	sender: &ArchiveStorageEventSender,
+	result: QueryResult,
+	child_trie_key: &Option<String>,
+) -> bool {
+	match result {
+		Ok(Some(result)) =>
+			blocking_send(sender, ArchiveStorageEvent::result(result, child_trie_key.clone())),
+		Ok(None) => true,
+		Err(error) => {
+			blocking_send(sender, ArchiveStorageEvent::err(error));
+			false
+		},
+	}
+}
+
+/// A parsed item of the `archive_storageDiff` method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffDetails {
+	/// The key prefix of the compared keys.
+	pub key: StorageKey,
+	/// The type of the reported results.
+	pub return_type: ArchiveStorageDiffType,
+	/// The child trie the keys belong to.
+	pub child_trie_key: Option<ChildInfo>,
+}
+
+/// Generates the events of the `archive_storageDiff` method.
+pub struct ArchiveStorageDiff<Client, Block, BE> {
+	/// Storage client.
+	client: Storage<Client, Block, BE>,
+	/// Substrate client.
+	raw_client: Arc<Client>,
+	/// The maximum number of changed keys reported by a single `archive_storageDiff` call, over
+	/// all the items.
+	max_reported_keys: usize,
+	_phantom: PhantomData<(BE, Block)>,
+}
+
+impl<Client, Block, BE> ArchiveStorageDiff<Client, Block, BE> {
+	/// Constructs a new [`ArchiveStorageDiff`].
+	pub fn new(client: Arc<Client>, max_reported_keys: usize) -> Self {
+		Self {
+			client: Storage::new(client.clone()),
+			raw_client: client,
+			max_reported_keys,
+			_phantom: PhantomData,
+		}
+	}
+}
+
+impl<Client, Block, BE> ArchiveStorageDiff<Client, Block, BE>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+	Client: StorageProvider<Block, BE> + 'static,
+{
+	/// Generate the events of the `archive_storageDiff` method.
+	///
+	/// Terminates with a `storageDiffDone` event, unless an error is encountered, more than
+	/// `max_reported_keys` changed keys are found or the receiving end of the `sender` is dropped.
+	///
+	/// This blocks on the storage iteration and on the `sender`, which provides backpressure, and
+	/// must therefore be spawned as a blocking task.
+	pub fn generate_events(
+		&self,
+		hash: Block::Hash,
+		previous_hash: Block::Hash,
+		items: Vec<DiffDetails>,
+		sender: ArchiveStorageDiffEventSender,
+	) {
+		let mut reported_keys = 0;
+
+		for item in items {
+			if let Err(error) =
+				self.send_diff(hash, previous_hash, &item, &mut reported_keys, &sender)
+			{
+				if let Some(error) = error {
+					blocking_send(&sender, ArchiveStorageDiffEvent::err(error));
+				}
+				return
+			}
+		}
+
+		blocking_send(&sender, ArchiveStorageDiffEvent::StorageDiffDone);
+	}
+
+	/// Compare the keys that start with the key of the item and report the differences.
+	///
+	/// The keys of both blocks are iterated in lexicographic order, which allows to walk them side
+	/// by side with a single iterator per block.
+	///
+	/// Returns `Err(Some(_))` with the error to report, or `Err(None)` if the receiving end of the
+	/// `sender` is dropped.
+	fn send_diff(
+		&self,
+		hash: Block::Hash,
+		previous_hash: Block::Hash,
+		item: &DiffDetails,
+		reported_keys: &mut usize,
+		sender: &ArchiveStorageDiffEventSender,
+	) -> Result<(), Option<String>> {
+		let child_key = item.child_trie_key.as_ref();
+		let child_trie_key = child_key.map(|child_key| hex_string(&child_key.storage_key()));
+
+		let mut keys = self.keys(hash, &item.key, child_key)?.peekable();
+		let mut previous_keys = self.keys(previous_hash, &item.key, child_key)?.peekable();
+
+		loop {
+			let (key, operation_type) = match (keys.peek(), previous_keys.peek()) {
+				(None, None) => return Ok(()),
+				(Some(_), None) => (
+					keys.next().expect("Peeked above; qed"),
+					ArchiveStorageDiffOperationType::Added,
+				),
+				(None, Some(_)) => (
+					previous_keys.next().expect("Peeked above; qed"),
+					ArchiveStorageDiffOperationType::Deleted,
+				),
+				(Some(key), Some(previous_key)) => match key.cmp(previous_key) {
+					Ordering::Less => (
+						keys.next().expect("Peeked above; qed"),
+						ArchiveStorageDiffOperationType::Added,
+					),
+					Ordering::Greater => (
+						previous_keys.next().expect("Peeked above; qed"),
+						ArchiveStorageDiffOperationType::Deleted,
+					),
+					Ordering::Equal => {
+						previous_keys.next();
+						(
+							keys.next().expect("Peeked above; qed"),
+							ArchiveStorageDiffOperationType::Modified,
+						)
+					},
 				},
 			};
+
+			if operation_type == ArchiveStorageDiffOperationType::Modified {
+				let hash_now = self.client.query_hash(hash, &key, child_key)?;
+				let hash_before = self.client.query_hash(previous_hash, &key, child_key)?;
+				if hash_now == hash_before {
+					continue
+				}
+			}
+
+			// Deleted keys are reported with their value before the deletion.
+			let at = match operation_type {
+				ArchiveStorageDiffOperationType::Deleted => previous_hash,
+				_ => hash,
+			};
+			let result = match item.return_type {
+				ArchiveStorageDiffType::Value => self.client.query_value(at, &key, child_key)?,
+				ArchiveStorageDiffType::Hash => self.client.query_hash(at, &key, child_key)?,
+			};
+			let Some(StorageResult { key, result }) = result else { continue };
+
+			if *reported_keys >= self.max_reported_keys {
+				return Err(Some(format!(
+					"Too many changed keys (maximum {})",
+					self.max_reported_keys
+				)))
+			}
+			*reported_keys += 1;
+
+			let event = ArchiveStorageDiffEvent::StorageDiff(ArchiveStorageDiffResult {
+				key,
+				result,
+				operation_type,
+				child_trie_key: child_trie_key.clone(),
+			});
+			if !blocking_send(sender, event) {
+				return Err(None)
+			}
 		}
+	}
 
-		ArchiveStorageResult::ok(storage_results, discarded_items)
+	/// Iterate over the keys of the block that start with the given prefix.
+	fn keys(
+		&self,
+		hash: Block::Hash,
+		prefix: &StorageKey,
+		child_key: Option<&ChildInfo>,
+	) -> Result<impl Iterator<Item = StorageKey>, String> {
+		if let Some(child_key) = child_key {
+			self.raw_client
+				.child_storage_keys(hash, child_key.to_owned(), Some(prefix), None)
+		} else {
+			self.raw_client.storage_keys(hash, Some(prefix), None)
+		}
+		.map_err(|error| error.to_string())
 	}
 }
+
+/// Send the event, blocking until there is capacity in the channel.
+///
+/// Returns `false` if the receiving end of the `sender` is dropped.
+fn blocking_send<T>(sender: &mpsc::Sender<T>, event: T) -> bool {
+	futures::executor::block_on(sender.send(event)).is_ok()
+}
@@ -18,7 +18,9 @@
 
 use crate::{
 	common::events::{
-		ArchiveStorageMethodOk, ArchiveStorageResult, PaginatedStorageQuery, StorageQueryType,
+		ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageDiffOperationType,
+		ArchiveStorageDiffResult, ArchiveStorageDiffType, ArchiveStorageEvent,
+		ArchiveStorageResult, PaginatedStorageQuery, StorageQueryType, StorageResult,
 		StorageResultType,
 	},
 	hex_string, MethodResult,
@@ -32,14 +34,14 @@ use super::{
 use assert_matches::assert_matches;
 use codec::{Decode, Encode};
 use jsonrpsee::{
-	core::{EmptyServerParams as EmptyParams, Error},
+	core::{server::Subscription as RpcSubscription, EmptyServerParams as EmptyParams, Error},
 	rpc_params, RpcModule,
 };
 use sc_block_builder::BlockBuilderBuilder;
 use sc_client_api::ChildInfo;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::BlockOrigin;
-use sp_core::{Blake2Hasher, Hasher};
+use sp_core::{testing::TaskExecutor, Blake2Hasher, Hasher};
 use sp_runtime::{
 	traits::{Block as BlockT, Header as HeaderT},
 	SaturatedConversion,
@@ -62,9 +64,54 @@ const CHILD_VALUE: &[u8] = b"child value";
 type Header = substrate_test_runtime_client::runtime::Header;
 type Block = substrate_test_runtime_client::runtime::Block;
 
+async fn get_next_event<T: serde::de::DeserializeOwned>(sub: &mut RpcSubscription) -> T {
+	let (event, _sub_id) = tokio::time::timeout(std::time::Duration::from_secs(60), sub.next())
+		.await
+		.unwrap()
+		.unwrap()
+		.unwrap();
+	event
+}
+
+/// Collect the events of an `archive_storage` subscription until `storageDone` is received.
+async fn get_storage_results(sub: &mut RpcSubscription) -> Vec<StorageResult> {
+	let mut results = Vec::new();
+	loop {
+		match get_next_event::<ArchiveStorageEvent>(sub).await {
+			ArchiveStorageEvent::Storage(ArchiveStorageResult { result, .. }) =>
+				results.push(result),
+			ArchiveStorageEvent::StorageDone => return results,
+			event => panic!("Unexpected event: {event:?}"),
+		}
+	}
+}
+
+/// Collect the events of an `archive_storageDiff` subscription until `storageDiffDone` is
+/// received.
+async fn get_storage_diff_results(sub: &mut RpcSubscription) -> Vec<ArchiveStorageDiffResult> {
+	let mut results = Vec::new();
+	loop {
+		match get_next_event::<ArchiveStorageDiffEvent>(sub).await {
+			ArchiveStorageDiffEvent::StorageDiff(result) => results.push(result),
+			ArchiveStorageDiffEvent::StorageDiffDone => return results,
+			event => panic!("Unexpected event: {event:?}"),
+		}
+	}
+}
+
 fn setup_api(
 	max_descendant_responses: usize,
 	max_queried_items: usize,
+) -> (Arc<Client<Backend>>, RpcModule<Archive<Backend, Block, Client<Backend>>>) {
+	setup_api_with_config(ArchiveConfig {
+		max_descendant_responses,
+		max_queried_items,
+		..Default::default()
+	})
+}
+
+fn setup_api_with_config(
+	config: ArchiveConfig,
 ) -> (Arc<Client<Backend>>, RpcModule<Archive<Backend, Block, Client<Backend>>>) {
 	let child_info = ChildInfo::new_default(CHILD_STORAGE_KEY);
 	let builder = TestClientBuilder::new().add_extra_child_storage(
@@ -79,7 +126,8 @@ fn setup_api(
 		client.clone(),
 		backend,
 		CHAIN_GENESIS,
-		ArchiveConfig { max_descendant_responses, max_queried_items },
+		config,
+		Arc::new(TaskExecutor::default()),
 	)
 	.into_rpc();
 
@@ -379,19 +427,13 @@ async fn archive_storage_hashes_values() {
 		},
 	];
 
-	let result: ArchiveStorageResult = api
-		.call("archive_unstable_storage", rpc_params![&block_hash, items.clone()])
+	let mut sub = api
+		.subscribe_unbounded("archive_unstable_storage", rpc_params![&block_hash, items.clone()])
 		.await
 		.unwrap();
 
-	match result {
-		ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result, discarded_items }) => {
-			// Key has not been imported yet.
-			assert_eq!(result.len(), 0);
-			assert_eq!(discarded_items, 0);
-		},
-		_ => panic!("Unexpected result"),
-	};
+	// Key has not been imported yet.
+	assert!(get_storage_results(&mut sub).await.is_empty());
 
 	// Import a block with the given key value pair.
 	let mut builder = BlockBuilderBuilder::new(&*client)
@@ -407,27 +449,57 @@ async fn archive_storage_hashes_values() {
 	let expected_hash = format!("{:?}", Blake2Hasher::hash(&VALUE));
 	let expected_value = hex_string(&VALUE);
 
-	let result: ArchiveStorageResult = api
-		.call("archive_unstable_storage", rpc_params![&block_hash, items])
+	let mut sub = api
+		.subscribe_unbounded("archive_unstable_storage", rpc_params![&block_hash, items])
 		.await
 		.unwrap();
 
-	match result {
-		ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result, discarded_items }) => {
-			assert_eq!(result.len(), 4);
-			assert_eq!(discarded_items, 0);
+	let result = get_storage_results(&mut sub).await;
+	assert_eq!(result.len(), 4);
+
+	assert_eq!(result[0].key, key);
+	assert_eq!(result[0].result, StorageResultType::Hash(expected_hash.clone()));
+	assert_eq!(result[1].key, key);
+	assert_eq!(result[1].result, StorageResultType::Value(expected_value.clone()));
+	assert_eq!(result[2].key, key);
+	assert_eq!(result[2].result, StorageResultType::Hash(expected_hash));
+	assert_eq!(result[3].key, key);
+	assert_eq!(result[3].result, StorageResultType::Value(expected_value));
+}
 
-			assert_eq!(result[0].key, key);
-			assert_eq!(result[0].result, StorageResultType::Hash(expected_hash.clone()));
-			assert_eq!(result[1].key, key);
-			assert_eq!(result[1].result, StorageResultType::Value(expected_value.clone()));
-			assert_eq!(result[2].key, key);
-			assert_eq!(result[2].result, StorageResultType::Hash(expected_hash));
-			assert_eq!(result[3].key, key);
-			assert_eq!(result[3].result, StorageResultType::Value(expected_value));
-		},
-		_ => panic!("Unexpected result"),
-	};
+#[tokio::test]
+async fn archive_storage_child_trie() {
+	let (client, api) = setup_api(MAX_PAGINATION_LIMIT, MAX_QUERIED_LIMIT);
+	let genesis_hash = format!("{:?}", client.genesis_hash());
+
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storage",
+			rpc_params![
+				&genesis_hash,
+				vec![PaginatedStorageQuery {
+					key: hex_string(&KEY),
+					query_type: StorageQueryType::Value,
+					pagination_start_key: None,
+				}],
+				hex_string(&CHILD_STORAGE_KEY)
+			],
+		)
+		.await
+		.unwrap();
+
+	let event: ArchiveStorageEvent = get_next_event(&mut sub).await;
+	assert_eq!(
+		event,
+		ArchiveStorageEvent::Storage(ArchiveStorageResult {
+			result: StorageResult {
+				key: hex_string(&KEY),
+				result: StorageResultType::Value(hex_string(&CHILD_VALUE)),
+			},
+			child_trie_key: Some(hex_string(&CHILD_STORAGE_KEY)),
+		})
+	);
+	assert!(get_next_event::<ArchiveStorageEvent>(&mut sub).await.is_done());
 }
 
 #[tokio::test]
@@ -444,8 +516,8 @@ async fn archive_storage_closest_merkle_value() {
 		api: &RpcModule<Archive<Backend, Block, Client<Backend>>>,
 		block_hash: String,
 	) -> HashMap<String, String> {
-		let result: ArchiveStorageResult = api
-			.call(
+		let mut sub = api
+			.subscribe_unbounded(
 				"archive_unstable_storage",
 				rpc_params![
 					&block_hash,
@@ -500,19 +572,17 @@ async fn archive_storage_closest_merkle_value() {
 			.await
 			.unwrap();
 
-		let merkle_values: HashMap<_, _> = match result {
-			ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result, .. }) => result
-				.into_iter()
-				.map(|res| {
-					let value = match res.result {
-						StorageResultType::ClosestDescendantMerkleValue(value) => value,
-						_ => panic!("Unexpected StorageResultType"),
-					};
-					(res.key, value)
-				})
-				.collect(),
-			_ => panic!("Unexpected result"),
-		};
+		let merkle_values: HashMap<_, _> = get_storage_results(&mut sub)
+			.await
+			.into_iter()
+			.map(|res| {
+				let value = match res.result {
+					StorageResultType::ClosestDescendantMerkleValue(value) => value,
+					_ => panic!("Unexpected StorageResultType"),
+				};
+				(res.key, value)
+			})
+			.collect();
 
 		// Response for AAAA, AAAB, A and AA.
 		assert_eq!(merkle_values.len(), 4);
@@ -612,8 +682,8 @@ async fn archive_storage_paginate_iterations() {
 
 	// Calling with an invalid hash.
 	let invalid_hash = hex_string(&INVALID_HASH);
-	let result: ArchiveStorageResult = api
-		.call(
+	let mut sub = api
+		.subscribe_unbounded(
 			"archive_unstable_storage",
 			rpc_params![
 				&invalid_hash,
@@ -626,14 +696,12 @@ async fn archive_storage_paginate_iterations() {
 		)
 		.await
 		.unwrap();
-	match result {
-		ArchiveStorageResult::Err(_) => (),
-		_ => panic!("Unexpected result"),
-	};
+	assert_matches!(get_next_event::<ArchiveStorageEvent>(&mut sub).await, event if event.is_err());
 
-	// Valid call with storage at the key.
-	let result: ArchiveStorageResult = api
-		.call(
+	// Valid call with storage at the key. All descendants are reported, even though only one
+	// key is iterated at a time.
+	let mut sub = api
+		.subscribe_unbounded(
 			"archive_unstable_storage",
 			rpc_params![
 				&block_hash,
@@ -646,196 +714,489 @@ async fn archive_storage_paginate_iterations() {
 		)
 		.await
 		.unwrap();
-	match result {
-		ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result, discarded_items }) => {
-			assert_eq!(result.len(), 1);
-			assert_eq!(discarded_items, 0);
 
-			assert_eq!(result[0].key, hex_string(b":m"));
-			assert_eq!(result[0].result, StorageResultType::Value(hex_string(b"a")));
-		},
-		_ => panic!("Unexpected result"),
-	};
+	let expected = [
+		(b":m".as_slice(), b"a".as_slice()),
+		(b":mo", b"ab"),
+		(b":moD", b"abcmoD"),
+		(b":moc", b"abc"),
+		(b":mock", b"abcd"),
+	];
+	let result = get_storage_results(&mut sub).await;
+	assert_eq!(result.len(), expected.len());
+	for (result, (key, value)) in result.into_iter().zip(expected) {
+		assert_eq!(result.key, hex_string(&key));
+		assert_eq!(result.result, StorageResultType::Value(hex_string(&value)));
+	}
 
-	// Continue with pagination.
-	let result: ArchiveStorageResult = api
-		.call(
+	// Continue from a given pagination start key.
+	let mut sub = api
+		.subscribe_unbounded(
 			"archive_unstable_storage",
 			rpc_params![
 				&block_hash,
 				vec![PaginatedStorageQuery {
 					key: hex_string(b":m"),
 					query_type: StorageQueryType::DescendantsValues,
-					pagination_start_key: Some(hex_string(b":m")),
+					pagination_start_key: Some(hex_string(b":moD")),
 				}]
 			],
 		)
 		.await
 		.unwrap();
-	match result {
-		ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result, discarded_items }) => {
-			assert_eq!(result.len(), 1);
-			assert_eq!(discarded_items, 0);
 
-			assert_eq!(result[0].key, hex_string(b":mo"));
-			assert_eq!(result[0].result, StorageResultType::Value(hex_string(b"ab")));
-		},
-		_ => panic!("Unexpected result"),
-	};
+	let result = get_storage_results(&mut sub).await;
+	assert_eq!(result.len(), 2);
+	assert_eq!(result[0].key, hex_string(b":moc"));
+	assert_eq!(result[0].result, StorageResultType::Value(hex_string(b"abc")));
+	assert_eq!(result[1].key, hex_string(b":mock"));
+	assert_eq!(result[1].result, StorageResultType::Value(hex_string(b"abcd")));
 
-	// Continue with pagination.
-	let result: ArchiveStorageResult = api
-		.call(
+	// Continue with pagination after the last key.
+	let mut sub = api
+		.subscribe_unbounded(
 			"archive_unstable_storage",
 			rpc_params![
 				&block_hash,
 				vec![PaginatedStorageQuery {
 					key: hex_string(b":m"),
 					query_type: StorageQueryType::DescendantsValues,
-					pagination_start_key: Some(hex_string(b":mo")),
+					pagination_start_key: Some(hex_string(b":mock")),
 				}]
 			],
 		)
 		.await
 		.unwrap();
-	match result {
-		ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result, discarded_items }) => {
-			assert_eq!(result.len(), 1);
-			assert_eq!(discarded_items, 0);
+	assert!(get_storage_results(&mut sub).await.is_empty());
+}
 
-			assert_eq!(result[0].key, hex_string(b":moD"));
-			assert_eq!(result[0].result, StorageResultType::Value(hex_string(b"abcmoD")));
-		},
-		_ => panic!("Unexpected result"),
-	};
+#[tokio::test]
+async fn archive_storage_too_many_items() {
+	// One query at a time
+	let (mut client, api) = setup_api(MAX_PAGINATION_LIMIT, 1);
 
-	// Continue with pagination.
-	let result: ArchiveStorageResult = api
-		.call(
+	// Import a new block with storage changes.
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap();
+	builder.push_storage_change(b":m".to_vec(), Some(b"a".to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	// Too many items are rejected.
+	let err = api
+		.subscribe_unbounded(
+			"archive_unstable_storage",
+			rpc_params![
+				&block_hash,
+				vec![
+					PaginatedStorageQuery {
+						key: hex_string(b":m"),
+						query_type: StorageQueryType::Value,
+						pagination_start_key: None,
+					},
+					PaginatedStorageQuery {
+						key: hex_string(b":m"),
+						query_type: StorageQueryType::Hash,
+						pagination_start_key: None,
+					}
+				]
+			],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::Call(err) if err.code() == 3001 && err.message().contains("Invalid parameter"));
+
+	// Valid call within the limits.
+	let mut sub = api
+		.subscribe_unbounded(
 			"archive_unstable_storage",
 			rpc_params![
 				&block_hash,
 				vec![PaginatedStorageQuery {
 					key: hex_string(b":m"),
-					query_type: StorageQueryType::DescendantsValues,
-					pagination_start_key: Some(hex_string(b":moD")),
+					query_type: StorageQueryType::Value,
+					pagination_start_key: None,
 				}]
 			],
 		)
 		.await
 		.unwrap();
-	match result {
-		ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result, discarded_items }) => {
-			assert_eq!(result.len(), 1);
-			assert_eq!(discarded_items, 0);
 
-			assert_eq!(result[0].key, hex_string(b":moc"));
-			assert_eq!(result[0].result, StorageResultType::Value(hex_string(b"abc")));
-		},
-		_ => panic!("Unexpected result"),
-	};
+	let result = get_storage_results(&mut sub).await;
+	assert_eq!(result.len(), 1);
+	assert_eq!(result[0].key, hex_string(b":m"));
+	assert_eq!(result[0].result, StorageResultType::Value(hex_string(b"a")));
+}
 
-	// Continue with pagination.
-	let result: ArchiveStorageResult = api
-		.call(
-			"archive_unstable_storage",
+#[tokio::test]
+async fn archive_storage_diff_main_trie() {
+	let (mut client, api) = setup_api(MAX_PAGINATION_LIMIT, MAX_QUERIED_LIMIT);
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap();
+	builder.push_storage_change(b":diffA".to_vec(), Some(b"1".to_vec())).unwrap();
+	builder.push_storage_change(b":diffB".to_vec(), Some(b"2".to_vec())).unwrap();
+	builder.push_storage_change(b":diffC".to_vec(), Some(b"3".to_vec())).unwrap();
+	let prev_block = builder.build().unwrap().block;
+	let prev_hash = format!("{:?}", prev_block.header.hash());
+	client.import(BlockOrigin::Own, prev_block.clone()).await.unwrap();
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(prev_block.hash())
+		.with_parent_block_number(1)
+		.build()
+		.unwrap();
+	builder.push_storage_change(b":diffA".to_vec(), Some(b"1".to_vec())).unwrap();
+	builder.push_storage_change(b":diffB".to_vec(), Some(b"22".to_vec())).unwrap();
+	builder.push_storage_change(b":diffC".to_vec(), None).unwrap();
+	builder.push_storage_change(b":diffD".to_vec(), Some(b"4".to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
 			rpc_params![
 				&block_hash,
-				vec![PaginatedStorageQuery {
-					key: hex_string(b":m"),
-					query_type: StorageQueryType::DescendantsValues,
-					pagination_start_key: Some(hex_string(b":moc")),
-				}]
+				vec![ArchiveStorageDiffItem {
+					key: hex_string(b":diff"),
+					return_type: ArchiveStorageDiffType::Value,
+					child_trie_key: None,
+				}],
+				&prev_hash
 			],
 		)
 		.await
 		.unwrap();
-	match result {
-		ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result, discarded_items }) => {
-			assert_eq!(result.len(), 1);
-			assert_eq!(discarded_items, 0);
 
-			assert_eq!(result[0].key, hex_string(b":mock"));
-			assert_eq!(result[0].result, StorageResultType::Value(hex_string(b"abcd")));
-		},
-		_ => panic!("Unexpected result"),
-	};
+	// Unchanged keys are not reported and deleted keys are reported with their previous value.
+	let result = get_storage_diff_results(&mut sub).await;
+	assert_eq!(
+		result,
+		vec![
+			ArchiveStorageDiffResult {
+				key: hex_string(b":diffB"),
+				result: StorageResultType::Value(hex_string(b"22")),
+				operation_type: ArchiveStorageDiffOperationType::Modified,
+				child_trie_key: None,
+			},
+			ArchiveStorageDiffResult {
+				key: hex_string(b":diffC"),
+				result: StorageResultType::Value(hex_string(b"3")),
+				operation_type: ArchiveStorageDiffOperationType::Deleted,
+				child_trie_key: None,
+			},
+			ArchiveStorageDiffResult {
+				key: hex_string(b":diffD"),
+				result: StorageResultType::Value(hex_string(b"4")),
+				operation_type: ArchiveStorageDiffOperationType::Added,
+				child_trie_key: None,
+			},
+		]
+	);
 
-	// Continue with pagination until no keys are returned.
-	let result: ArchiveStorageResult = api
-		.call(
-			"archive_unstable_storage",
+	// The previous block defaults to the parent and hashes can be requested instead of values.
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
 			rpc_params![
 				&block_hash,
-				vec![PaginatedStorageQuery {
-					key: hex_string(b":m"),
-					query_type: StorageQueryType::DescendantsValues,
-					pagination_start_key: Some(hex_string(b":mock")),
+				vec![ArchiveStorageDiffItem {
+					key: hex_string(b":diffB"),
+					return_type: ArchiveStorageDiffType::Hash,
+					child_trie_key: None,
 				}]
 			],
 		)
 		.await
 		.unwrap();
-	match result {
-		ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result, discarded_items }) => {
-			assert_eq!(result.len(), 0);
-			assert_eq!(discarded_items, 0);
-		},
-		_ => panic!("Unexpected result"),
-	};
+
+	let result = get_storage_diff_results(&mut sub).await;
+	assert_eq!(
+		result,
+		vec![ArchiveStorageDiffResult {
+			key: hex_string(b":diffB"),
+			result: StorageResultType::Hash(format!("{:?}", Blake2Hasher::hash(b"22"))),
+			operation_type: ArchiveStorageDiffOperationType::Modified,
+			child_trie_key: None,
+		}]
+	);
+
+	// Compare against the genesis block, which does not contain any of the keys.
+	let genesis_hash = format!("{:?}", client.genesis_hash());
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![
+				&block_hash,
+				vec![ArchiveStorageDiffItem {
+					key: hex_string(b":diff"),
+					return_type: ArchiveStorageDiffType::Value,
+					child_trie_key: None,
+				}],
+				&genesis_hash
+			],
+		)
+		.await
+		.unwrap();
+
+	let result = get_storage_diff_results(&mut sub).await;
+	assert_eq!(
+		result.iter().map(|result| result.key.clone()).collect::<Vec<_>>(),
+		vec![hex_string(b":diffA"), hex_string(b":diffB"), hex_string(b":diffD")]
+	);
+	assert!(result
+		.iter()
+		.all(|result| result.operation_type == ArchiveStorageDiffOperationType::Added));
 }
 
 #[tokio::test]
-async fn archive_storage_discarded_items() {
-	// One query at a time
-	let (mut client, api) = setup_api(MAX_PAGINATION_LIMIT, 1);
+async fn archive_storage_diff_interleaved_keys() {
+	let (mut client, api) = setup_api(MAX_PAGINATION_LIMIT, MAX_QUERIED_LIMIT);
 
-	// Import a new block with storage changes.
 	let mut builder = BlockBuilderBuilder::new(&*client)
 		.on_parent_block(client.chain_info().genesis_hash)
 		.with_parent_block_number(0)
 		.build()
 		.unwrap();
-	builder.push_storage_change(b":m".to_vec(), Some(b"a".to_vec())).unwrap();
+	builder.push_storage_change(b":diffA".to_vec(), Some(b"1".to_vec())).unwrap();
+	builder.push_storage_change(b":diffC".to_vec(), Some(b"3".to_vec())).unwrap();
+	let prev_block = builder.build().unwrap().block;
+	client.import(BlockOrigin::Own, prev_block.clone()).await.unwrap();
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(prev_block.hash())
+		.with_parent_block_number(1)
+		.build()
+		.unwrap();
+	builder.push_storage_change(b":diffA".to_vec(), None).unwrap();
+	builder.push_storage_change(b":diffB".to_vec(), Some(b"2".to_vec())).unwrap();
+	builder.push_storage_change(b":diffC".to_vec(), Some(b"33".to_vec())).unwrap();
 	let block = builder.build().unwrap().block;
 	let block_hash = format!("{:?}", block.header.hash());
 	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
 
-	// Valid call with storage at the key.
-	let result: ArchiveStorageResult = api
-		.call(
-			"archive_unstable_storage",
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
 			rpc_params![
 				&block_hash,
-				vec![
-					PaginatedStorageQuery {
-						key: hex_string(b":m"),
-						query_type: StorageQueryType::Value,
-						pagination_start_key: None,
-					},
-					PaginatedStorageQuery {
-						key: hex_string(b":m"),
-						query_type: StorageQueryType::Hash,
-						pagination_start_key: None,
-					},
-					PaginatedStorageQuery {
-						key: hex_string(b":m"),
-						query_type: StorageQueryType::Hash,
-						pagination_start_key: None,
-					}
-				]
+				vec![ArchiveStorageDiffItem {
+					key: hex_string(b":diff"),
+					return_type: ArchiveStorageDiffType::Value,
+					child_trie_key: None,
+				}]
 			],
 		)
 		.await
 		.unwrap();
-	match result {
-		ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result, discarded_items }) => {
-			assert_eq!(result.len(), 1);
-			assert_eq!(discarded_items, 2);
 
-			assert_eq!(result[0].key, hex_string(b":m"));
-			assert_eq!(result[0].result, StorageResultType::Value(hex_string(b"a")));
-		},
-		_ => panic!("Unexpected result"),
+	let result = get_storage_diff_results(&mut sub).await;
+	assert_eq!(
+		result
+			.into_iter()
+			.map(|result| (result.key, result.operation_type))
+			.collect::<Vec<_>>(),
+		vec![
+			(hex_string(b":diffA"), ArchiveStorageDiffOperationType::Deleted),
+			(hex_string(b":diffB"), ArchiveStorageDiffOperationType::Added),
+			(hex_string(b":diffC"), ArchiveStorageDiffOperationType::Modified),
+		]
+	);
+}
+
+#[tokio::test]
+async fn archive_storage_diff_too_many_keys() {
+	// Only report two changed keys per call.
+	let (mut client, api) = setup_api_with_config(ArchiveConfig {
+		max_descendant_responses: MAX_PAGINATION_LIMIT,
+		max_queried_items: MAX_QUERIED_LIMIT,
+		max_storage_diff_keys: 2,
+	});
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap();
+	builder.push_storage_change(b":diffA".to_vec(), Some(b"1".to_vec())).unwrap();
+	builder.push_storage_change(b":diffB".to_vec(), Some(b"2".to_vec())).unwrap();
+	builder.push_storage_change(b":diffC".to_vec(), Some(b"3".to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = block.header.hash();
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	// `:diffC` is unchanged.
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(block_hash)
+		.with_parent_block_number(1)
+		.build()
+		.unwrap();
+	builder.push_storage_change(b":diffA".to_vec(), Some(b"11".to_vec())).unwrap();
+	builder.push_storage_change(b":diffB".to_vec(), None).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let item = ArchiveStorageDiffItem {
+		key: hex_string(b":diff"),
+		return_type: ArchiveStorageDiffType::Value,
+		child_trie_key: None,
+	};
+
+	// Unchanged keys don't count towards the limit, reporting exactly as many keys as allowed
+	// succeeds.
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![&block_hash, vec![item.clone()]],
+		)
+		.await
+		.unwrap();
+	assert_eq!(get_storage_diff_results(&mut sub).await.len(), 2);
+
+	// The limit is shared by all the items of the call.
+	let other_item =
+		ArchiveStorageDiffItem { return_type: ArchiveStorageDiffType::Hash, ..item.clone() };
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![&block_hash, vec![item, other_item]],
+		)
+		.await
+		.unwrap();
+
+	for key in [b":diffA", b":diffB"] {
+		assert_matches!(
+			get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await,
+			ArchiveStorageDiffEvent::StorageDiff(result) if result.key == hex_string(key)
+		);
+	}
+	assert_matches!(
+		get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await,
+		ArchiveStorageDiffEvent::StorageDiffError(error) if error.error.contains("Too many changed keys")
+	);
+}
+
+#[tokio::test]
+async fn archive_storage_diff_child_trie() {
+	let (mut client, api) = setup_api(MAX_PAGINATION_LIMIT, MAX_QUERIED_LIMIT);
+
+	let block = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap()
+		.build()
+		.unwrap()
+		.block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let item = ArchiveStorageDiffItem {
+		key: hex_string(&KEY),
+		return_type: ArchiveStorageDiffType::Value,
+		child_trie_key: Some(hex_string(&CHILD_STORAGE_KEY)),
+	};
+
+	// The child trie is unchanged between the genesis and the imported block.
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![&block_hash, vec![item.clone()]],
+		)
+		.await
+		.unwrap();
+	assert!(get_storage_diff_results(&mut sub).await.is_empty());
+
+	// Unknown child tries do not produce any differences.
+	let invalid_child = ArchiveStorageDiffItem {
+		child_trie_key: Some(hex_string(b"unknown child")),
+		..item.clone()
+	};
+	let genesis_hash = format!("{:?}", client.genesis_hash());
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![&genesis_hash, vec![item, invalid_child], &block_hash],
+		)
+		.await
+		.unwrap();
+	assert!(get_storage_diff_results(&mut sub).await.is_empty());
+}
+
+#[tokio::test]
+async fn archive_storage_diff_invalid_params() {
+	let (client, api) = setup_api(MAX_PAGINATION_LIMIT, 1);
+	let genesis_hash = format!("{:?}", client.genesis_hash());
+
+	// Invalid hex-encoded key.
+	let err = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![
+				&genesis_hash,
+				vec![ArchiveStorageDiffItem {
+					key: "0xzz".to_string(),
+					return_type: ArchiveStorageDiffType::Value,
+					child_trie_key: None,
+				}]
+			],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::Call(err) if err.code() == 3001 && err.message().contains("Invalid parameter"));
+
+	// Duplicated items count only once towards the limit.
+	let item = ArchiveStorageDiffItem {
+		key: hex_string(b":diff"),
+		return_type: ArchiveStorageDiffType::Value,
+		child_trie_key: None,
 	};
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![&genesis_hash, vec![item.clone(), item.clone()], &genesis_hash],
+		)
+		.await
+		.unwrap();
+	assert!(get_storage_diff_results(&mut sub).await.is_empty());
+
+	// Too many items are rejected.
+	let other_item =
+		ArchiveStorageDiffItem { return_type: ArchiveStorageDiffType::Hash, ..item.clone() };
+	let err = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![&genesis_hash, vec![item, other_item]],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::Call(err) if err.code() == 3001 && err.message().contains("Invalid parameter"));
+
+	// Unknown blocks are reported as errors.
+	let invalid_hash = hex_string(&INVALID_HASH);
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![
+				&invalid_hash,
+				vec![ArchiveStorageDiffItem {
+					key: hex_string(b":diff"),
+					return_type: ArchiveStorageDiffType::Value,
+					child_trie_key: None,
+				}]
+			],
+		)
+		.await
+		.unwrap();
+	assert!(get_next_event::<ArchiveStorageDiffEvent>(&mut sub).await.is_err());
 }
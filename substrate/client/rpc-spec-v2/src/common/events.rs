@@ -103,36 +103,57 @@ pub struct StorageResultErr {
 	pub error: StorageResultType,
 }
 
-/// The result of a storage call.
+/// The event generated by the `archive_storage` method.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum ArchiveStorageResult {
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "event")]
+pub enum ArchiveStorageEvent {
 	/// Query generated a result.
-	Ok(ArchiveStorageMethodOk),
+	Storage(ArchiveStorageResult),
 	/// Query encountered an error.
-	Err(ArchiveStorageMethodErr),
+	StorageError(ArchiveStorageMethodErr),
+	/// Operation storage is done.
+	StorageDone,
 }
 
-impl ArchiveStorageResult {
-	/// Create a new `ArchiveStorageResult::Ok` result.
-	pub fn ok(result: Vec<StorageResult>, discarded_items: usize) -> Self {
-		Self::Ok(ArchiveStorageMethodOk { result, discarded_items })
+impl ArchiveStorageEvent {
+	/// Create a new `ArchiveStorageEvent::StorageError` event.
+	pub fn err(error: String) -> Self {
+		Self::StorageError(ArchiveStorageMethodErr { error })
 	}
 
-	/// Create a new `ArchiveStorageResult::Err` result.
-	pub fn err(error: String) -> Self {
-		Self::Err(ArchiveStorageMethodErr { error })
+	/// Create a new `ArchiveStorageEvent::Storage` event.
+	pub fn result(result: StorageResult, child_trie_key: Option<String>) -> Self {
+		Self::Storage(ArchiveStorageResult { result, child_trie_key })
+	}
+
+	/// Checks if the event is a `StorageDone` event.
+	pub fn is_done(&self) -> bool {
+		matches!(self, Self::StorageDone)
+	}
+
+	/// Checks if the event is a `StorageError` event.
+	pub fn is_err(&self) -> bool {
+		matches!(self, Self::StorageError(_))
+	}
+
+	/// Checks if the event is a `Storage` event.
+	pub fn is_result(&self) -> bool {
+		matches!(self, Self::Storage(_))
 	}
 }
 
-/// The result of a storage call.
+/// The storage result of an `archive_storage` query.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ArchiveStorageMethodOk {
-	/// Reported results.
-	pub result: Vec<StorageResult>,
-	/// Number of discarded items.
-	pub discarded_items: usize,
+pub struct ArchiveStorageResult {
+	/// The result of the query.
+	#[serde(flatten)]
+	pub result: StorageResult,
+	/// The hex-encoded child trie key, if the query was made against a child trie.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub child_trie_key: Option<String>,
 }
 
 /// The error of a storage call.
@@ -143,6 +164,92 @@ pub struct ArchiveStorageMethodErr {
 	pub error: String,
 }
 
+/// The type of the result returned by `archive_storageDiff` for a key.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArchiveStorageDiffType {
+	/// The result is provided as value of the key.
+	Value,
+	/// The result is provided as hash of the value of the key.
+	Hash,
+}
+
+/// The storage item to query with `archive_storageDiff`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveStorageDiffItem<Key> {
+	/// The provided key; all keys that start with it are compared.
+	pub key: Key,
+	/// The type of the storage query.
+	pub return_type: ArchiveStorageDiffType,
+	/// The child trie key if provided.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub child_trie_key: Option<Key>,
+}
+
+/// The operation that happened to a key between two blocks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArchiveStorageDiffOperationType {
+	/// The key was added.
+	Added,
+	/// The key was modified.
+	Modified,
+	/// The key was removed.
+	Deleted,
+}
+
+/// A single difference reported by `archive_storageDiff`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveStorageDiffResult {
+	/// The hex-encoded key of the result.
+	pub key: String,
+	/// The result of the query.
+	///
+	/// For deleted keys, this is the value (or hash) at the previous block.
+	#[serde(flatten)]
+	pub result: StorageResultType,
+	/// The operation that happened to the key.
+	#[serde(rename = "type")]
+	pub operation_type: ArchiveStorageDiffOperationType,
+	/// The hex-encoded child trie key, if the key belongs to a child trie.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub child_trie_key: Option<String>,
+}
+
+/// The event generated by the `archive_storageDiff` method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "event")]
+pub enum ArchiveStorageDiffEvent {
+	/// The result of a storage diff call.
+	StorageDiff(ArchiveStorageDiffResult),
+	/// The error of a storage diff call.
+	StorageDiffError(ArchiveStorageMethodErr),
+	/// The storage diff is done.
+	StorageDiffDone,
+}
+
+impl ArchiveStorageDiffEvent {
+	/// Create a new `ArchiveStorageDiffEvent::StorageDiffError` event.
+	pub fn err(error: String) -> Self {
+		Self::StorageDiffError(ArchiveStorageMethodErr { error })
+	}
+
+	/// Checks if the event is a `StorageDiffDone` event.
+	pub fn is_done(&self) -> bool {
+		matches!(self, Self::StorageDiffDone)
+	}
+
+	/// Checks if the event is a `StorageDiffError` event.
+	pub fn is_err(&self) -> bool {
+		matches!(self, Self::StorageDiffError(_))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -270,4 +377,127 @@ mod tests {
 		let dec: PaginatedStorageQuery<&str> = serde_json::from_str(exp).unwrap();
 		assert_eq!(dec, item);
 	}
+
+	#[test]
+	fn archive_storage_event() {
+		let event = ArchiveStorageEvent::result(
+			StorageResult { key: "0x1".into(), result: StorageResultType::Value("res".into()) },
+			None,
+		);
+		// Encode
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"storage","key":"0x1","value":"res"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, event);
+
+		let event = ArchiveStorageEvent::result(
+			StorageResult { key: "0x1".into(), result: StorageResultType::Hash("res".into()) },
+			Some("0x2".into()),
+		);
+		// Encode
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"storage","key":"0x1","hash":"res","childTrieKey":"0x2"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, event);
+
+		let event = ArchiveStorageEvent::err("reason".into());
+		// Encode
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"storageError","error":"reason"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, event);
+
+		let event = ArchiveStorageEvent::StorageDone;
+		// Encode
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"storageDone"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, event);
+	}
+
+	#[test]
+	fn archive_storage_diff_item() {
+		let item = ArchiveStorageDiffItem {
+			key: "0x1",
+			return_type: ArchiveStorageDiffType::Hash,
+			child_trie_key: None,
+		};
+		// Encode
+		let ser = serde_json::to_string(&item).unwrap();
+		let exp = r#"{"key":"0x1","returnType":"hash"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageDiffItem<&str> = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, item);
+
+		let item = ArchiveStorageDiffItem {
+			key: "0x1",
+			return_type: ArchiveStorageDiffType::Value,
+			child_trie_key: Some("0x2"),
+		};
+		// Encode
+		let ser = serde_json::to_string(&item).unwrap();
+		let exp = r#"{"key":"0x1","returnType":"value","childTrieKey":"0x2"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageDiffItem<&str> = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, item);
+	}
+
+	#[test]
+	fn archive_storage_diff_event() {
+		let event = ArchiveStorageDiffEvent::StorageDiff(ArchiveStorageDiffResult {
+			key: "0x1".into(),
+			result: StorageResultType::Value("res".into()),
+			operation_type: ArchiveStorageDiffOperationType::Added,
+			child_trie_key: None,
+		});
+		// Encode
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"storageDiff","key":"0x1","value":"res","type":"added"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageDiffEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, event);
+
+		let event = ArchiveStorageDiffEvent::StorageDiff(ArchiveStorageDiffResult {
+			key: "0x1".into(),
+			result: StorageResultType::Hash("res".into()),
+			operation_type: ArchiveStorageDiffOperationType::Deleted,
+			child_trie_key: Some("0x2".into()),
+		});
+		// Encode
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"storageDiff","key":"0x1","hash":"res","type":"deleted","childTrieKey":"0x2"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageDiffEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, event);
+
+		let event = ArchiveStorageDiffEvent::err("reason".into());
+		// Encode
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"storageDiffError","error":"reason"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageDiffEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, event);
+
+		let event = ArchiveStorageDiffEvent::StorageDiffDone;
+		// Encode
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"storageDiffDone"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageDiffEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, event);
+	}
 }
@@ -171,7 +171,7 @@ where
 
 		Ok(self
 			.pool
-			.remove_invalid(&hashes)
+			.remove_invalid(None, &hashes)
 			.into_iter()
 			.map(|tx| tx.hash().clone())
 			.collect())
@@ -52,7 +52,7 @@ use sc_network_light::light_client_requests::handler::LightClientRequestHandler;
 use sc_network_sync::{
 	block_relay_protocol::BlockRelayParams, block_request_handler::BlockRequestHandler,
 	engine::SyncingEngine, service::network::NetworkServiceProvider,
-	state_request_handler::StateRequestHandler,
+	state_request_handler::StateRequestHandler, strategy::warp::CheckpointTargetProvider,
 	warp_request_handler::RequestHandler as WarpSyncRequestHandler, SyncingService, WarpSyncParams,
 };
 use sc_rpc::{
@@ -685,8 +685,8 @@ where
 			client.clone(),
 			backend.clone(),
 			genesis_hash,
-			// Defaults to sensible limits for the `Archive`.
-			sc_rpc_spec_v2::archive::ArchiveConfig::default(),
+			config.rpc_archive.clone(),
+			task_executor.clone(),
 		)
 		.into_rpc();
 		rpc_api.merge(archive_v2).map_err(|e| Error::Application(e.into()))?;
@@ -789,6 +789,15 @@ where
 		block_relay,
 	} = params;
 
+	// Fall back to the checkpoint of the chain spec if no other way to warp sync is configured.
+	let warp_sync_params = match (warp_sync_params, config.chain_spec.warp_sync_checkpoint()) {
+		(None, Some(checkpoint)) if config.network.sync_mode.is_warp() => {
+			let provider = CheckpointTargetProvider::<TBl>::from_encoded(&checkpoint.header)?;
+			Some(WarpSyncParams::WithTargetProvider(Arc::new(provider)))
+		},
+		(warp_sync_params, _) => warp_sync_params,
+	};
+
 	if warp_sync_params.is_none() && config.network.sync_mode.is_warp() {
 		return Err("Warp sync enabled, but no warp sync provider configured.".into())
 	}
@@ -20,7 +20,7 @@
 
 use prometheus_endpoint::Registry;
 use sc_chain_spec::ChainSpec;
-pub use sc_client_db::{BlocksPruning, Database, DatabaseSource, PruningMode};
+pub use sc_client_db::{BlocksPruning, Database, DatabaseSource, PruningMode, StateStorageMode};
 pub use sc_executor::{WasmExecutionMethod, WasmtimeInstantiationStrategy};
 pub use sc_informant::OutputFormat;
 pub use sc_network::{
@@ -33,8 +33,9 @@ pub use sc_network::{
 	},
 	Multiaddr,
 };
+pub use sc_rpc_spec_v2::archive::ArchiveConfig as RpcArchiveConfig;
 pub use sc_telemetry::TelemetryEndpoints;
-pub use sc_transaction_pool::Options as TransactionPoolOptions;
+pub use sc_transaction_pool::{Options as TransactionPoolOptions, TransactionPoolType};
 use sp_core::crypto::SecretString;
 use std::{
 	io, iter,
@@ -106,6 +107,8 @@ pub struct Configuration {
 	pub rpc_port: u16,
 	/// The number of messages the JSON-RPC server is allowed to keep in memory.
 	pub rpc_message_buffer_capacity: u32,
+	/// Limits of the `archive` JSON-RPC methods.
+	pub rpc_archive: RpcArchiveConfig,
 	/// Prometheus endpoint configuration. `None` if disabled.
 	pub prometheus_config: Option<PrometheusConfig>,
 	/// Telemetry service URL. `None` if disabled.
@@ -237,12 +237,41 @@ pub trait TransactionPool: Send + Sync {
 		>,
 	>;
 
+	/// Get an iterator for ready transactions ordered by priority, valid on top of the given
+	/// block.
+	///
+	/// Pools that keep a separate validated state for every fork return the ready set built on
+	/// top of the block `at`. The default implementation does not distinguish forks and waits
+	/// for the pool to be updated at block `number`, see [`TransactionPool::ready_at`].
+	fn ready_at_block(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		number: NumberFor<Self::Block>,
+	) -> Pin<
+		Box<
+			dyn Future<
+					Output = Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>,
+				> + Send,
+		>,
+	> {
+		let _ = at;
+		self.ready_at(number)
+	}
+
 	/// Get an iterator for ready transactions ordered by priority.
 	fn ready(&self) -> Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>;
 
 	// *** Block production
 	/// Remove transactions identified by given hashes (and dependent transactions) from the pool.
-	fn remove_invalid(&self, hashes: &[TxHash<Self>]) -> Vec<Arc<Self::InPoolTransaction>>;
+	///
+	/// `at` is the block at which the transactions were found to be invalid, if any. Pools that
+	/// track several forks only remove the transactions from the state at that block, as they may
+	/// still be valid on other forks.
+	fn remove_invalid(
+		&self,
+		at: Option<<Self::Block as BlockT>::Hash>,
+		hashes: &[TxHash<Self>],
+	) -> Vec<Arc<Self::InPoolTransaction>>;
 
 	// *** logging
 	/// Get futures transaction list.
@@ -0,0 +1,275 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Selection of the transaction pool implementation used by a full node.
+
+use crate::{
+	graph::{self, ExtrinsicHash, IsValidator},
+	ForkAwareFullPool, FullChainApi, FullPool,
+};
+use async_trait::async_trait;
+use prometheus_endpoint::Registry as PrometheusRegistry;
+use sc_transaction_pool_api::{
+	ChainEvent, ImportNotificationStream, LocalTransactionFor, LocalTransactionPool,
+	MaintainedTransactionPool, PoolFuture, PoolStatus, ReadyTransactions, TransactionFor,
+	TransactionPool, TransactionSource, TransactionStatusStreamFor, TxHash,
+};
+use sp_core::traits::SpawnEssentialNamed;
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc};
+
+/// The implementation of the transaction pool used by a full node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransactionPoolType {
+	/// A pool that keeps a single validated state, updated on every new best block.
+	#[default]
+	SingleState,
+	/// A pool that keeps a separate validated state (a view) for every fork.
+	ForkAware,
+}
+
+/// A transaction pool for a full node, of the type selected by [`crate::Options::pool_type`].
+pub enum FullTransactionPool<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	/// The single state pool, see [`crate::BasicPool`].
+	SingleState(Arc<FullPool<Block, Client>>),
+	/// The fork-aware pool, see [`crate::ForkAwareTxPool`].
+	ForkAware(Arc<ForkAwareFullPool<Block, Client>>),
+}
+
+impl<Block, Client> FullTransactionPool<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sc_client_api::ExecutorProvider<Block>
+		+ sc_client_api::UsageProvider<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	/// Create new transaction pool for a full node, of the type given in `options`.
+	pub fn new_full(
+		options: graph::Options,
+		is_validator: IsValidator,
+		prometheus: Option<&PrometheusRegistry>,
+		spawner: impl SpawnEssentialNamed,
+		client: Arc<Client>,
+	) -> Arc<Self> {
+		let pool = match options.pool_type {
+			TransactionPoolType::SingleState => Self::SingleState(FullPool::new_full(
+				options,
+				is_validator,
+				prometheus,
+				spawner,
+				client,
+			)),
+			TransactionPoolType::ForkAware => Self::ForkAware(ForkAwareFullPool::new_full(
+				options,
+				is_validator,
+				prometheus,
+				spawner,
+				client,
+			)),
+		};
+
+		Arc::new(pool)
+	}
+}
+
+/// Calls `$method` on the selected pool.
+macro_rules! delegate {
+	($self:ident.$method:ident($($arg:expr),*)) => {
+		match $self {
+			Self::SingleState(pool) => pool.$method($($arg),*),
+			Self::ForkAware(pool) => pool.$method($($arg),*),
+		}
+	};
+}
+
+impl<Block, Client> TransactionPool for FullTransactionPool<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	type Block = Block;
+	type Hash = ExtrinsicHash<FullChainApi<Client, Block>>;
+	type InPoolTransaction = graph::base_pool::Transaction<TxHash<Self>, TransactionFor<Self>>;
+	type Error = <FullChainApi<Client, Block> as graph::ChainApi>::Error;
+
+	fn submit_at(
+		&self,
+		at: Block::Hash,
+		source: TransactionSource,
+		xts: Vec<TransactionFor<Self>>,
+	) -> PoolFuture<Vec<Result<TxHash<Self>, Self::Error>>, Self::Error> {
+		delegate!(self.submit_at(at, source, xts))
+	}
+
+	fn submit_one(
+		&self,
+		at: Block::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<TxHash<Self>, Self::Error> {
+		delegate!(self.submit_one(at, source, xt))
+	}
+
+	fn submit_and_watch(
+		&self,
+		at: Block::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
+		delegate!(self.submit_and_watch(at, source, xt))
+	}
+
+	fn ready_at(
+		&self,
+		at: NumberFor<Block>,
+	) -> Pin<
+		Box<
+			dyn Future<
+					Output = Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>,
+				> + Send,
+		>,
+	> {
+		delegate!(self.ready_at(at))
+	}
+
+	fn ready_at_block(
+		&self,
+		at: Block::Hash,
+		number: NumberFor<Block>,
+	) -> Pin<
+		Box<
+			dyn Future<
+					Output = Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>,
+				> + Send,
+		>,
+	> {
+		delegate!(self.ready_at_block(at, number))
+	}
+
+	fn ready(&self) -> Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send> {
+		delegate!(self.ready())
+	}
+
+	fn remove_invalid(
+		&self,
+		at: Option<Block::Hash>,
+		hashes: &[TxHash<Self>],
+	) -> Vec<Arc<Self::InPoolTransaction>> {
+		delegate!(self.remove_invalid(at, hashes))
+	}
+
+	fn futures(&self) -> Vec<Self::InPoolTransaction> {
+		delegate!(self.futures())
+	}
+
+	fn status(&self) -> PoolStatus {
+		delegate!(self.status())
+	}
+
+	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>> {
+		delegate!(self.import_notification_stream())
+	}
+
+	fn on_broadcasted(&self, propagations: HashMap<TxHash<Self>, Vec<String>>) {
+		delegate!(self.on_broadcasted(propagations))
+	}
+
+	fn hash_of(&self, xt: &TransactionFor<Self>) -> TxHash<Self> {
+		delegate!(self.hash_of(xt))
+	}
+
+	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>> {
+		delegate!(self.ready_transaction(hash))
+	}
+}
+
+#[async_trait]
+impl<Block, Client> MaintainedTransactionPool for FullTransactionPool<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	async fn maintain(&self, event: ChainEvent<Block>) {
+		match self {
+			Self::SingleState(pool) => pool.maintain(event).await,
+			Self::ForkAware(pool) => pool.maintain(event).await,
+		}
+	}
+}
+
+impl<Block, Client> LocalTransactionPool for FullTransactionPool<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	type Block = Block;
+	type Hash = ExtrinsicHash<FullChainApi<Client, Block>>;
+	type Error = <FullChainApi<Client, Block> as graph::ChainApi>::Error;
+
+	fn submit_local(
+		&self,
+		at: Block::Hash,
+		xt: LocalTransactionFor<Self>,
+	) -> Result<Self::Hash, Self::Error> {
+		delegate!(self.submit_local(at, xt))
+	}
+}
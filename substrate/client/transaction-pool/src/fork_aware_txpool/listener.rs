@@ -0,0 +1,170 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Transaction status listener of the fork-aware pool.
+//!
+//! The views of the fork-aware pool do not notify the watchers directly, since the same
+//! transaction lives in many views at once. Instead, the pool merges the state of the views
+//! and reports the status changes through this listener.
+
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+
+use crate::{
+	graph::{
+		self,
+		watcher::{Sender, Watcher},
+		BlockHash, ExtrinsicHash,
+	},
+	LOG_TARGET,
+};
+
+/// The last status reported for a watched transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ReportedStatus {
+	Future,
+	Ready,
+	InBlock,
+}
+
+struct WatchedTransaction<ChainApi: graph::ChainApi> {
+	sender: Sender<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
+	reported: Option<ReportedStatus>,
+}
+
+/// Dispatches the status updates of the watched transactions.
+pub(crate) struct TxStatusListener<ChainApi: graph::ChainApi> {
+	watched: Mutex<HashMap<ExtrinsicHash<ChainApi>, WatchedTransaction<ChainApi>>>,
+}
+
+impl<ChainApi: graph::ChainApi> TxStatusListener<ChainApi> {
+	/// Creates a listener without any watched transactions.
+	pub(crate) fn new() -> Self {
+		Self { watched: Default::default() }
+	}
+
+	/// Creates a new watcher for the given transaction.
+	pub(crate) fn create_watcher(
+		&self,
+		hash: ExtrinsicHash<ChainApi>,
+	) -> Watcher<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>> {
+		self.watched
+			.lock()
+			.entry(hash)
+			.or_insert_with(|| WatchedTransaction { sender: Default::default(), reported: None })
+			.sender
+			.new_watcher(hash)
+	}
+
+	/// Returns the hashes of all the watched transactions.
+	pub(crate) fn watched(&self) -> Vec<ExtrinsicHash<ChainApi>> {
+		self.watched.lock().keys().cloned().collect()
+	}
+
+	/// Transaction became ready in the most recent view.
+	pub(crate) fn ready(&self, hash: &ExtrinsicHash<ChainApi>) {
+		self.report(hash, Some(ReportedStatus::Ready), |sender| sender.ready());
+	}
+
+	/// Transaction was moved to the future queue of the most recent view.
+	pub(crate) fn future(&self, hash: &ExtrinsicHash<ChainApi>) {
+		self.report(hash, Some(ReportedStatus::Future), |sender| sender.future());
+	}
+
+	/// Transaction was included in the given block of the best chain.
+	pub(crate) fn in_block(
+		&self,
+		hash: &ExtrinsicHash<ChainApi>,
+		block: BlockHash<ChainApi>,
+		index: usize,
+	) {
+		self.notify(hash, None, |sender| sender.in_block(block, index));
+		self.set_reported(hash, Some(ReportedStatus::InBlock));
+	}
+
+	/// The block the transaction was included in was retracted.
+	pub(crate) fn retracted(&self, hash: &ExtrinsicHash<ChainApi>, block: BlockHash<ChainApi>) {
+		self.notify(hash, Some(ReportedStatus::InBlock), |sender| sender.retracted(block));
+		self.set_reported(hash, None);
+	}
+
+	/// The block the transaction was included in was finalized.
+	pub(crate) fn finalized(
+		&self,
+		hash: &ExtrinsicHash<ChainApi>,
+		block: BlockHash<ChainApi>,
+		index: usize,
+	) {
+		self.notify(hash, None, |sender| sender.finalized(block, index));
+	}
+
+	/// Transaction is not valid at any of the views.
+	pub(crate) fn invalid(&self, hash: &ExtrinsicHash<ChainApi>) {
+		log::trace!(target: LOG_TARGET, "[{:?}] Reporting invalid transaction", hash);
+		self.notify(hash, None, |sender| sender.invalid());
+	}
+
+	/// Transaction was broadcast to the given peers.
+	pub(crate) fn broadcasted(&self, hash: &ExtrinsicHash<ChainApi>, peers: Vec<String>) {
+		self.notify(hash, None, |sender| sender.broadcast(peers));
+	}
+
+	/// Reports a ready/future status, unless the same status was already reported.
+	fn report(
+		&self,
+		hash: &ExtrinsicHash<ChainApi>,
+		status: Option<ReportedStatus>,
+		send: impl FnOnce(&mut Sender<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>),
+	) {
+		let mut watched = self.watched.lock();
+		let Some(tx) = watched.get_mut(hash) else { return };
+		if tx.reported == status || tx.reported == Some(ReportedStatus::InBlock) {
+			return
+		}
+		tx.reported = status;
+		send(&mut tx.sender);
+		if tx.sender.is_done() {
+			watched.remove(hash);
+		}
+	}
+
+	/// Sends the notification if the last reported status matches `required` (if given).
+	fn notify(
+		&self,
+		hash: &ExtrinsicHash<ChainApi>,
+		required: Option<ReportedStatus>,
+		send: impl FnOnce(&mut Sender<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>),
+	) {
+		let mut watched = self.watched.lock();
+		let Some(tx) = watched.get_mut(hash) else { return };
+		if required.map_or(false, |required| tx.reported != Some(required)) {
+			return
+		}
+		send(&mut tx.sender);
+		if tx.sender.is_done() {
+			watched.remove(hash);
+		}
+	}
+
+	fn set_reported(&self, hash: &ExtrinsicHash<ChainApi>, status: Option<ReportedStatus>) {
+		if let Some(tx) = self.watched.lock().get_mut(hash) {
+			tx.reported = status;
+		}
+	}
+}
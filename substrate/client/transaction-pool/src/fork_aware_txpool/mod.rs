@@ -0,0 +1,770 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Fork-aware transaction pool.
+//!
+//! Unlike the [`BasicPool`](crate::BasicPool), which keeps a single validated state built on
+//! top of the best block, the fork-aware pool keeps a separate view for every block that may
+//! become the parent of a new block. Every view owns its own [`Pool`](crate::Pool), so the
+//! transactions are validated against the state of the particular fork, and block authorship
+//! can request the ready set built on top of any of the tracked blocks.
+//!
+//! - Every submitted transaction is stored in the mempool, which holds all the transactions that
+//!   are not yet finalized, and is then validated and submitted to all the active views.
+//! - On a new best block a new view is created. If there is a view at some ancestor block, its
+//!   transactions are copied into the new view, the transactions included in the enacted blocks are
+//!   pruned and the remaining ones are revalidated. Finally, the mempool transactions that are not
+//!   known to the view (e.g. coming from retracted blocks) are submitted to it.
+//! - A view can also be created on demand by [`TransactionPool::ready_at_block`], which allows
+//!   authoring blocks on top of a block that is not the best one (e.g. collators building on
+//!   several relay parents).
+//! - On finalization, the views that are not descendants of the finalized block are removed, and
+//!   the transactions included in the finalized blocks are removed from the mempool.
+//!
+//! Status updates of watched transactions are not emitted by the views, but by the pool itself
+//! which merges the state of all the views.
+
+mod listener;
+mod tx_mem_pool;
+mod view;
+mod view_store;
+
+use std::{
+	collections::{HashMap, HashSet},
+	pin::Pin,
+	sync::Arc,
+};
+
+use async_trait::async_trait;
+use futures::{channel::mpsc, prelude::*};
+use parking_lot::Mutex;
+use prometheus_endpoint::Registry as PrometheusRegistry;
+use sc_transaction_pool_api::{
+	error::Error as TxPoolError, ChainEvent, ImportNotificationStream, MaintainedTransactionPool,
+	PoolFuture, PoolStatus, TransactionFor, TransactionPool, TransactionSource,
+	TransactionStatusStreamFor, TxHash,
+};
+use sp_blockchain::{HashAndNumber, TreeRoute};
+use sp_core::traits::SpawnEssentialNamed;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Extrinsic, NumberFor, Zero},
+};
+
+use self::{listener::TxStatusListener, tx_mem_pool::TxMemPool, view_store::ViewStore};
+use crate::{
+	api::FullChainApi,
+	enactment_state::{EnactmentAction, EnactmentState},
+	error,
+	graph::{self, ExtrinsicFor, ExtrinsicHash, IsValidator, ValidatedTransaction},
+	metrics::MetricsLink as PrometheusMetrics,
+	PolledIterator, ReadyIteratorFor, ReadyPoll, LOG_TARGET,
+};
+
+/// Fork-aware transaction pool for a full node.
+pub type ForkAwareFullPool<Block, Client> = ForkAwareTxPool<FullChainApi<Client, Block>, Block>;
+
+/// Transaction pool keeping a separate validated state (a view) for every tracked fork.
+pub struct ForkAwareTxPool<ChainApi, Block>
+where
+	Block: BlockT,
+	ChainApi: graph::ChainApi<Block = Block> + 'static,
+{
+	api: Arc<ChainApi>,
+	mempool: Arc<TxMemPool<ChainApi>>,
+	view_store: Arc<ViewStore<ChainApi>>,
+	listener: Arc<TxStatusListener<ChainApi>>,
+	ready_poll: Arc<Mutex<ReadyPoll<ReadyIteratorFor<ChainApi>, Block>>>,
+	enactment_state: Arc<Mutex<EnactmentState<Block>>>,
+	import_notification_sinks: Arc<Mutex<Vec<mpsc::Sender<ExtrinsicHash<ChainApi>>>>>,
+	metrics: PrometheusMetrics,
+}
+
+impl<ChainApi, Block> ForkAwareTxPool<ChainApi, Block>
+where
+	Block: BlockT,
+	ChainApi: graph::ChainApi<Block = Block> + 'static,
+{
+	/// Create new fork-aware transaction pool with provided api, for tests.
+	pub fn new_test(
+		pool_api: Arc<ChainApi>,
+		best_block_hash: Block::Hash,
+		finalized_hash: Block::Hash,
+	) -> Self {
+		Self::new_with_options(
+			Default::default(),
+			true.into(),
+			pool_api,
+			None,
+			best_block_hash,
+			finalized_hash,
+		)
+	}
+
+	/// Create new fork-aware transaction pool with provided api and options.
+	///
+	/// The initial (empty) view is created at the best block.
+	pub fn new_with_options(
+		options: graph::Options,
+		is_validator: IsValidator,
+		pool_api: Arc<ChainApi>,
+		prometheus: Option<&PrometheusRegistry>,
+		best_block_hash: Block::Hash,
+		finalized_hash: Block::Hash,
+	) -> Self {
+		let mempool = Arc::new(TxMemPool::new(
+			pool_api.clone(),
+			options.ready.count.saturating_add(options.future.count),
+		));
+		let view_store =
+			Arc::new(ViewStore::new(pool_api.clone(), options, Arc::new(is_validator)));
+
+		let best_block_number = match pool_api.block_id_to_number(&BlockId::Hash(best_block_hash)) {
+			Ok(Some(number)) => {
+				view_store.insert_empty(HashAndNumber { hash: best_block_hash, number });
+				view_store.set_most_recent(best_block_hash);
+				number
+			},
+			_ => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Could not get the number of the best block {:?}, no initial view created.",
+					best_block_hash,
+				);
+				Zero::zero()
+			},
+		};
+
+		Self {
+			api: pool_api,
+			mempool,
+			view_store,
+			listener: Arc::new(TxStatusListener::new()),
+			ready_poll: Arc::new(Mutex::new(ReadyPoll::new(best_block_number))),
+			enactment_state: Arc::new(Mutex::new(EnactmentState::new(
+				best_block_hash,
+				finalized_hash,
+			))),
+			import_notification_sinks: Default::default(),
+			metrics: PrometheusMetrics::new(prometheus),
+		}
+	}
+
+	/// Get access to the underlying api.
+	pub fn api(&self) -> &ChainApi {
+		&self.api
+	}
+
+	/// Returns the number of active views.
+	pub fn active_views_count(&self) -> usize {
+		self.view_store.len()
+	}
+
+	/// Returns true if there is a view at the given block.
+	pub fn has_view(&self, at: &Block::Hash) -> bool {
+		self.view_store.get(at).is_some()
+	}
+
+	/// Returns the number of transactions kept in the mempool.
+	pub fn mempool_len(&self) -> usize {
+		self.mempool.len()
+	}
+
+	/// Returns the status of the view at the given block, if there is one.
+	pub fn status_at(&self, at: &Block::Hash) -> Option<PoolStatus> {
+		self.view_store.get(at).map(|view| view.pool.validated_pool().status())
+	}
+
+	/// Reports the ready/future status of the watched transaction, as seen by the most recent
+	/// view.
+	fn report_status(
+		listener: &TxStatusListener<ChainApi>,
+		view_store: &ViewStore<ChainApi>,
+		hash: &ExtrinsicHash<ChainApi>,
+	) {
+		match view_store.most_recent_view() {
+			Some(view) if view.is_ready(hash) => listener.ready(hash),
+			Some(view) if view.is_imported(hash) => listener.future(hash),
+			_ => {},
+		}
+	}
+
+	/// Notifies the import notification stream listeners.
+	fn notify_imported(
+		sinks: &Mutex<Vec<mpsc::Sender<ExtrinsicHash<ChainApi>>>>,
+		hash: ExtrinsicHash<ChainApi>,
+	) {
+		sinks.lock().retain_mut(|sink| match sink.try_send(hash) {
+			Ok(()) => true,
+			Err(e) =>
+				if e.is_full() {
+					log::warn!(
+						target: LOG_TARGET,
+						"[{:?}] Trying to notify an import but the channel is full",
+						hash,
+					);
+					true
+				} else {
+					false
+				},
+		});
+	}
+
+	/// Returns the transactions included in the given block.
+	async fn block_extrinsics(&self, block: Block::Hash) -> Vec<ExtrinsicFor<ChainApi>> {
+		self.api
+			.block_body(block)
+			.await
+			.unwrap_or_else(|e| {
+				log::warn!(target: LOG_TARGET, "Failed to fetch block body {:?}: {}", block, e);
+				None
+			})
+			.unwrap_or_default()
+	}
+
+	/// Returns the hashes of the transactions included in the given block.
+	async fn block_transactions(&self, block: Block::Hash) -> Vec<ExtrinsicHash<ChainApi>> {
+		self.block_extrinsics(block)
+			.await
+			.iter()
+			.map(|xt| self.api.hash_and_length(xt).0)
+			.collect()
+	}
+
+	/// Activates the view at the given best block, creating it if needed.
+	async fn handle_new_best_block(&self, at: HashAndNumber<Block>) {
+		let view = self.view_store.get_or_create_view(at.clone(), &self.mempool).await;
+		self.view_store.set_most_recent(at.hash);
+
+		for hash in self.listener.watched() {
+			if !self.mempool.is_included(&hash) {
+				Self::report_status(&self.listener, &self.view_store, &hash);
+			}
+		}
+
+		self.ready_poll
+			.lock()
+			.trigger(at.number, move || Box::new(view.pool.validated_pool().ready()));
+	}
+
+	/// Handles enactment and retraction of blocks.
+	///
+	/// Watchers are notified about the transactions included in the retracted and enacted
+	/// blocks, and the view at the new best block is activated. Transactions of the retracted
+	/// blocks that were never submitted to this pool are added to the mempool and resubmitted.
+	async fn handle_enactment(&self, tree_route: TreeRoute<Block>) {
+		log::trace!(target: LOG_TARGET, "handle_enactment tree_route: {tree_route:?}");
+		let Some(at) = tree_route.last().cloned() else {
+			log::warn!(
+				target: LOG_TARGET,
+				"Skipping ChainEvent - no last block in tree route {:?}",
+				tree_route,
+			);
+			return
+		};
+
+		let mut resubmitted = Vec::new();
+		for retracted in tree_route.retracted() {
+			for xt in self.block_extrinsics(retracted.hash).await {
+				let hash = self.api.hash_and_length(&xt).0;
+				self.mempool.set_included(&hash, None);
+				self.listener.retracted(&hash, retracted.hash);
+
+				// Transactions that this node has never seen, e.g. the ones of blocks authored
+				// by other nodes, would be lost otherwise.
+				if !self.mempool.contains(&hash) && xt.is_signed().unwrap_or(true) {
+					log::debug!(
+						target: LOG_TARGET,
+						"[{:?}]: Resubmitting from retracted block {:?}",
+						hash,
+						retracted.hash,
+					);
+					resubmitted.push((hash, xt));
+				}
+			}
+		}
+		let xts = resubmitted.iter().map(|(_, xt)| xt.clone()).collect::<Vec<_>>();
+		// These transactions are coming from retracted blocks, we should simply consider them
+		// external.
+		for result in self.mempool.push(TransactionSource::External, &xts) {
+			if let Err(e) = result {
+				log::debug!(target: LOG_TARGET, "Error re-submitting transaction: {}", e);
+			}
+		}
+		self.metrics.report(|metrics| {
+			metrics.block_transactions_resubmitted.inc_by(resubmitted.len() as u64)
+		});
+
+		let mut pruned = 0;
+		for enacted in tree_route.enacted() {
+			for (index, hash) in self.block_transactions(enacted.hash).await.into_iter().enumerate()
+			{
+				if self.mempool.contains(&hash) {
+					pruned += 1;
+					self.mempool.set_included(&hash, Some(enacted.hash));
+					self.listener.in_block(&hash, enacted.hash, index);
+				}
+			}
+		}
+		self.metrics.report(|metrics| metrics.block_transactions_pruned.inc_by(pruned));
+
+		let at_hash = at.hash;
+		self.handle_new_best_block(at).await;
+
+		// A newly created view picks the resubmitted transactions up from the mempool, but a view
+		// that already existed needs them to be submitted explicitly. Transactions already known
+		// to the view are rejected before being validated.
+		let xts = resubmitted
+			.into_iter()
+			.filter(|(hash, _)| self.mempool.contains(hash) && !self.mempool.is_included(hash))
+			.map(|(_, xt)| xt)
+			.collect::<Vec<_>>();
+		let Some(view) = self.view_store.get(&at_hash).filter(|_| !xts.is_empty()) else { return };
+		if let Err(e) = view.pool.submit_at(at_hash, TransactionSource::External, xts).await {
+			log::debug!(target: LOG_TARGET, "[{:?}] Error re-submitting transactions: {}", at_hash, e);
+		}
+	}
+
+	/// Handles finalization of the given block.
+	///
+	/// Watchers are notified about the finalized transactions, which are removed from the
+	/// mempool together with the transactions that are no longer valid at any of the views.
+	/// Views that are not descendants of the finalized block are dropped.
+	async fn handle_finalized(&self, hash: Block::Hash, tree_route: &[Block::Hash]) {
+		let number = match self.api.block_id_to_number(&BlockId::Hash(hash)) {
+			Ok(Some(number)) => number,
+			_ => {
+				log::warn!(target: LOG_TARGET, "Could not get number of finalized block {hash:?}");
+				return
+			},
+		};
+
+		let mut finalized = Vec::new();
+		for block in tree_route.iter().chain(std::iter::once(&hash)) {
+			for (index, tx_hash) in self.block_transactions(*block).await.into_iter().enumerate() {
+				self.listener.finalized(&tx_hash, *block, index);
+				finalized.push(tx_hash);
+			}
+		}
+		self.mempool.remove(&finalized);
+
+		self.view_store.handle_finalized(&HashAndNumber { hash, number });
+
+		let invalid = self
+			.mempool
+			.pending_hashes()
+			.into_iter()
+			.filter(|tx_hash| !self.view_store.is_imported(tx_hash))
+			.collect::<Vec<_>>();
+		if !invalid.is_empty() {
+			log::debug!(
+				target: LOG_TARGET,
+				"Removing transactions not valid at any view: {:?}",
+				invalid,
+			);
+			self.metrics
+				.report(|metrics| metrics.validations_invalid.inc_by(invalid.len() as u64));
+		}
+		self.mempool.remove(&invalid);
+		for tx_hash in &invalid {
+			self.listener.invalid(tx_hash);
+		}
+	}
+}
+
+impl<ChainApi, Block> TransactionPool for ForkAwareTxPool<ChainApi, Block>
+where
+	Block: BlockT,
+	ChainApi: 'static + graph::ChainApi<Block = Block>,
+{
+	type Block = ChainApi::Block;
+	type Hash = graph::ExtrinsicHash<ChainApi>;
+	type InPoolTransaction = graph::base_pool::Transaction<TxHash<Self>, TransactionFor<Self>>;
+	type Error = ChainApi::Error;
+
+	fn submit_at(
+		&self,
+		_: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xts: Vec<TransactionFor<Self>>,
+	) -> PoolFuture<Vec<Result<TxHash<Self>, Self::Error>>, Self::Error> {
+		self.metrics
+			.report(|metrics| metrics.submitted_transactions.inc_by(xts.len() as u64));
+
+		let mempool = self.mempool.clone();
+		let view_store = self.view_store.clone();
+		let sinks = self.import_notification_sinks.clone();
+		let mempool_results = self.mempool.push(source, &xts);
+
+		async move {
+			let to_submit = xts
+				.into_iter()
+				.zip(mempool_results.iter())
+				.filter_map(|(xt, result)| result.is_ok().then_some(xt))
+				.collect::<Vec<_>>();
+			let mut submit_results = view_store.submit_at(source, to_submit).await.into_iter();
+
+			let results = mempool_results
+				.into_iter()
+				.map(|result| {
+					result.and_then(|hash| {
+						let submit_result = submit_results
+							.next()
+							.expect("One result for every submitted transaction; qed");
+						match submit_result {
+							Ok(_) => Self::notify_imported(&sinks, hash),
+							Err(_) => mempool.remove(&[hash]),
+						}
+						submit_result
+					})
+				})
+				.collect();
+
+			Ok(results)
+		}
+		.boxed()
+	}
+
+	fn submit_one(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<TxHash<Self>, Self::Error> {
+		let submit = self.submit_at(at, source, vec![xt]);
+
+		async move {
+			let mut results = submit.await?;
+			results.pop().expect("One extrinsic passed; one result returned; qed")
+		}
+		.boxed()
+	}
+
+	fn submit_and_watch(
+		&self,
+		_: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
+		self.metrics.report(|metrics| metrics.submitted_transactions.inc());
+
+		let mempool = self.mempool.clone();
+		let view_store = self.view_store.clone();
+		let listener = self.listener.clone();
+		let sinks = self.import_notification_sinks.clone();
+		let mempool_result = self
+			.mempool
+			.push(source, std::slice::from_ref(&xt))
+			.pop()
+			.expect("One extrinsic passed; one result returned; qed");
+
+		async move {
+			let hash = mempool_result?;
+			let result = view_store
+				.submit_at(source, vec![xt])
+				.await
+				.pop()
+				.expect("One extrinsic passed; one result returned; qed");
+
+			if let Err(e) = result {
+				mempool.remove(&[hash]);
+				return Err(e)
+			}
+
+			let watcher = listener.create_watcher(hash);
+			Self::report_status(&listener, &view_store, &hash);
+			Self::notify_imported(&sinks, hash);
+
+			Ok(watcher.into_stream().boxed())
+		}
+		.boxed()
+	}
+
+	fn remove_invalid(
+		&self,
+		at: Option<Block::Hash>,
+		hashes: &[TxHash<Self>],
+	) -> Vec<Arc<Self::InPoolTransaction>> {
+		let removed = self.view_store.remove_invalid(at, hashes);
+
+		// Transactions that are still held by a view are valid on another fork and are kept.
+		let dropped = hashes
+			.iter()
+			.copied()
+			.chain(removed.iter().map(|tx| tx.hash))
+			.collect::<HashSet<_>>()
+			.into_iter()
+			.filter(|hash| !self.view_store.is_imported(hash))
+			.collect::<Vec<_>>();
+		self.mempool.remove(&dropped);
+		for hash in &dropped {
+			self.listener.invalid(hash);
+		}
+		self.metrics
+			.report(|metrics| metrics.validations_invalid.inc_by(removed.len() as u64));
+		removed
+	}
+
+	fn status(&self) -> PoolStatus {
+		self.view_store
+			.most_recent_view()
+			.map(|view| view.pool.validated_pool().status())
+			.unwrap_or(PoolStatus { ready: 0, ready_bytes: 0, future: 0, future_bytes: 0 })
+	}
+
+	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>> {
+		const CHANNEL_BUFFER_SIZE: usize = 1024;
+
+		let (sink, stream) = mpsc::channel(CHANNEL_BUFFER_SIZE);
+		self.import_notification_sinks.lock().push(sink);
+		stream
+	}
+
+	fn hash_of(&self, xt: &TransactionFor<Self>) -> TxHash<Self> {
+		self.api.hash_and_length(xt).0
+	}
+
+	fn on_broadcasted(&self, propagations: HashMap<TxHash<Self>, Vec<String>>) {
+		for (hash, peers) in propagations {
+			self.listener.broadcasted(&hash, peers);
+		}
+	}
+
+	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>> {
+		self.view_store
+			.most_recent_view()
+			.and_then(|view| view.pool.validated_pool().ready_by_hash(hash))
+	}
+
+	fn ready_at(&self, at: NumberFor<Self::Block>) -> PolledIterator<ChainApi> {
+		if self.mempool.is_empty() {
+			return async { Box::new(std::iter::empty()) as Box<_> }.boxed()
+		}
+
+		if let Some(view) = self.view_store.most_recent_view() {
+			if view.at.number >= at {
+				let iterator: ReadyIteratorFor<ChainApi> =
+					Box::new(view.pool.validated_pool().ready());
+				return async move { iterator }.boxed()
+			}
+		}
+
+		self.ready_poll
+			.lock()
+			.add(at)
+			.map(|received| {
+				received.unwrap_or_else(|e| {
+					log::warn!(target: LOG_TARGET, "Error receiving pending set: {:?}", e);
+					Box::new(std::iter::empty())
+				})
+			})
+			.boxed()
+	}
+
+	fn ready_at_block(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		number: NumberFor<Self::Block>,
+	) -> PolledIterator<ChainApi> {
+		if let Some(view) = self.view_store.get(&at) {
+			let iterator: ReadyIteratorFor<ChainApi> = Box::new(view.pool.validated_pool().ready());
+			return async move { iterator }.boxed()
+		}
+
+		if self.mempool.is_empty() {
+			return async { Box::new(std::iter::empty()) as Box<_> }.boxed()
+		}
+
+		let view_store = self.view_store.clone();
+		let mempool = self.mempool.clone();
+		async move {
+			log::debug!(target: LOG_TARGET, "Creating view on demand at {:?}", at);
+			let view = view_store
+				.get_or_create_view(HashAndNumber { hash: at, number }, &mempool)
+				.await;
+			Box::new(view.pool.validated_pool().ready()) as ReadyIteratorFor<ChainApi>
+		}
+		.boxed()
+	}
+
+	fn ready(&self) -> ReadyIteratorFor<ChainApi> {
+		match self.view_store.most_recent_view() {
+			Some(view) => Box::new(view.pool.validated_pool().ready()),
+			None => Box::new(std::iter::empty()),
+		}
+	}
+
+	fn futures(&self) -> Vec<Self::InPoolTransaction> {
+		self.view_store
+			.most_recent_view()
+			.map(|view| {
+				let pool = view.pool.validated_pool().pool.read();
+				pool.futures().cloned().collect::<Vec<_>>()
+			})
+			.unwrap_or_default()
+	}
+}
+
+impl<Block, Client> ForkAwareFullPool<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sc_client_api::ExecutorProvider<Block>
+		+ sc_client_api::UsageProvider<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	/// Create new fork-aware transaction pool for a full node with the provided api.
+	pub fn new_full(
+		options: graph::Options,
+		is_validator: IsValidator,
+		prometheus: Option<&PrometheusRegistry>,
+		spawner: impl SpawnEssentialNamed,
+		client: Arc<Client>,
+	) -> Arc<Self> {
+		let pool_api = Arc::new(FullChainApi::new(client.clone(), prometheus, &spawner));
+		Arc::new(Self::new_with_options(
+			options,
+			is_validator,
+			pool_api,
+			prometheus,
+			client.usage_info().chain.best_hash,
+			client.usage_info().chain.finalized_hash,
+		))
+	}
+}
+
+impl<Block, Client> sc_transaction_pool_api::LocalTransactionPool
+	for ForkAwareTxPool<FullChainApi<Client, Block>, Block>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>,
+	Client: Send + Sync + 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	type Block = Block;
+	type Hash = graph::ExtrinsicHash<FullChainApi<Client, Block>>;
+	type Error = <FullChainApi<Client, Block> as graph::ChainApi>::Error;
+
+	fn submit_local(
+		&self,
+		at: Block::Hash,
+		xt: sc_transaction_pool_api::LocalTransactionFor<Self>,
+	) -> Result<Self::Hash, Self::Error> {
+		use graph::ChainApi as _;
+		use sp_runtime::{
+			traits::SaturatedConversion, transaction_validity::TransactionValidityError,
+		};
+
+		let validity = self
+			.api
+			.validate_transaction_blocking(at, TransactionSource::Local, xt.clone())?
+			.map_err(|e| {
+				Self::Error::Pool(match e {
+					TransactionValidityError::Invalid(i) => TxPoolError::InvalidTransaction(i),
+					TransactionValidityError::Unknown(u) => TxPoolError::UnknownTransaction(u),
+				})
+			})?;
+
+		let (hash, bytes) = self.api.hash_and_length(&xt);
+		let block_number = self
+			.api
+			.block_id_to_number(&BlockId::hash(at))?
+			.ok_or_else(|| error::Error::BlockIdConversion(format!("{:?}", at)))?;
+
+		let validated = ValidatedTransaction::valid_at(
+			block_number.saturated_into::<u64>(),
+			hash,
+			TransactionSource::Local,
+			xt.clone(),
+			bytes,
+			validity,
+		);
+
+		self.mempool
+			.push(TransactionSource::Local, std::slice::from_ref(&xt))
+			.pop()
+			.expect("One extrinsic passed; one result returned; qed")?;
+
+		// The transaction is validated at `at` only; other views will pick it up from the
+		// mempool when they are created.
+		let Some(view) = self.view_store.get(&at).or_else(|| self.view_store.most_recent_view())
+		else {
+			return Ok(hash)
+		};
+
+		let result = view.pool.validated_pool().submit(vec![validated]).remove(0);
+		match result {
+			Ok(_) => Self::notify_imported(&self.import_notification_sinks, hash),
+			Err(_) => self.mempool.remove(&[hash]),
+		}
+		result
+	}
+}
+
+#[async_trait]
+impl<ChainApi, Block> MaintainedTransactionPool for ForkAwareTxPool<ChainApi, Block>
+where
+	Block: BlockT,
+	ChainApi: 'static + graph::ChainApi<Block = Block>,
+{
+	async fn maintain(&self, event: ChainEvent<Self::Block>) {
+		let compute_tree_route = |from, to| -> Result<TreeRoute<Block>, String> {
+			self.api.tree_route(from, to).map_err(|e| {
+				format!("Error occurred while computing tree_route from {from:?} to {to:?}: {e}")
+			})
+		};
+		let block_id_to_number =
+			|hash| self.api.block_id_to_number(&BlockId::Hash(hash)).map_err(|e| format!("{}", e));
+
+		let result =
+			self.enactment_state
+				.lock()
+				.update(&event, &compute_tree_route, &block_id_to_number);
+
+		match result {
+			Err(msg) => {
+				log::debug!(target: LOG_TARGET, "{msg}");
+				self.enactment_state.lock().force_update(&event);
+				if let Ok(Some(number)) = block_id_to_number(event.hash()) {
+					self.handle_new_best_block(HashAndNumber { hash: event.hash(), number }).await;
+				}
+			},
+			Ok(EnactmentAction::Skip) => return,
+			Ok(EnactmentAction::HandleFinalization) => {},
+			Ok(EnactmentAction::HandleEnactment(tree_route)) => {
+				self.handle_enactment(tree_route).await;
+			},
+		};
+
+		if let ChainEvent::Finalized { hash, tree_route } = event {
+			log::trace!(target: LOG_TARGET, "on-finalized enacted: {tree_route:?}");
+			self.handle_finalized(hash, &tree_route).await;
+		}
+	}
+}
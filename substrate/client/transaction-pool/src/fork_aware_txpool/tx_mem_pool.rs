@@ -0,0 +1,200 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Transactions memory pool.
+//!
+//! Holds all the transactions submitted to the fork-aware pool until they are finalized or
+//! become invalid at every view. It is the source of transactions for newly created views.
+
+use std::{collections::HashMap, sync::Arc};
+
+use parking_lot::RwLock;
+use sc_transaction_pool_api::{error::Error as TxPoolError, TransactionSource};
+
+use crate::graph::{self, BlockHash, ExtrinsicFor, ExtrinsicHash};
+
+/// A transaction stored in the [`TxMemPool`].
+struct TxInMemPool<ChainApi: graph::ChainApi> {
+	/// The source of the transaction.
+	source: TransactionSource,
+	/// The transaction itself.
+	tx: ExtrinsicFor<ChainApi>,
+	/// The block of the best chain the transaction was included in, if any.
+	included_in: Option<BlockHash<ChainApi>>,
+}
+
+/// Memory pool of the transactions that are not yet finalized.
+pub(crate) struct TxMemPool<ChainApi: graph::ChainApi> {
+	api: Arc<ChainApi>,
+	transactions: RwLock<HashMap<ExtrinsicHash<ChainApi>, TxInMemPool<ChainApi>>>,
+	max_transactions_count: usize,
+}
+
+impl<ChainApi: graph::ChainApi> TxMemPool<ChainApi> {
+	/// Creates a new mempool holding at most `max_transactions_count` transactions.
+	pub(crate) fn new(api: Arc<ChainApi>, max_transactions_count: usize) -> Self {
+		Self { api, transactions: Default::default(), max_transactions_count }
+	}
+
+	/// Returns the number of transactions in the mempool.
+	pub(crate) fn len(&self) -> usize {
+		self.transactions.read().len()
+	}
+
+	/// Returns true if there are no transactions in the mempool.
+	pub(crate) fn is_empty(&self) -> bool {
+		self.transactions.read().is_empty()
+	}
+
+	/// Returns true if the transaction with the given hash is in the mempool.
+	pub(crate) fn contains(&self, hash: &ExtrinsicHash<ChainApi>) -> bool {
+		self.transactions.read().contains_key(hash)
+	}
+
+	/// Returns true if the transaction is known to be included in a block of the best chain.
+	pub(crate) fn is_included(&self, hash: &ExtrinsicHash<ChainApi>) -> bool {
+		self.transactions.read().get(hash).map_or(false, |tx| tx.included_in.is_some())
+	}
+
+	/// Inserts the given transactions into the mempool.
+	///
+	/// Returns the hash of every inserted transaction, or an error if the transaction is
+	/// already known or the mempool is full.
+	pub(crate) fn push(
+		&self,
+		source: TransactionSource,
+		xts: &[ExtrinsicFor<ChainApi>],
+	) -> Vec<Result<ExtrinsicHash<ChainApi>, ChainApi::Error>> {
+		let mut transactions = self.transactions.write();
+		xts.iter()
+			.map(|xt| {
+				let hash = self.api.hash_and_length(xt).0;
+				if transactions.contains_key(&hash) {
+					Err(TxPoolError::AlreadyImported(Box::new(hash)).into())
+				} else if transactions.len() >= self.max_transactions_count {
+					Err(TxPoolError::ImmediatelyDropped.into())
+				} else {
+					transactions
+						.insert(hash, TxInMemPool { source, tx: xt.clone(), included_in: None });
+					Ok(hash)
+				}
+			})
+			.collect()
+	}
+
+	/// Removes the given transactions from the mempool.
+	pub(crate) fn remove(&self, hashes: &[ExtrinsicHash<ChainApi>]) {
+		let mut transactions = self.transactions.write();
+		for hash in hashes {
+			transactions.remove(hash);
+		}
+	}
+
+	/// Records the block of the best chain the transaction was included in.
+	///
+	/// `None` means that the block was retracted and the transaction shall be submitted to the
+	/// views again.
+	pub(crate) fn set_included(
+		&self,
+		hash: &ExtrinsicHash<ChainApi>,
+		block: Option<BlockHash<ChainApi>>,
+	) {
+		if let Some(tx) = self.transactions.write().get_mut(hash) {
+			tx.included_in = block;
+		}
+	}
+
+	/// Returns the hashes of all the transactions that are not included in any block.
+	pub(crate) fn pending_hashes(&self) -> Vec<ExtrinsicHash<ChainApi>> {
+		self.transactions
+			.read()
+			.iter()
+			.filter(|(_, tx)| tx.included_in.is_none())
+			.map(|(hash, _)| *hash)
+			.collect()
+	}
+
+	/// Returns copies of all the transactions that are not included in any block, grouped by
+	/// their source.
+	pub(crate) fn pending_by_source(
+		&self,
+	) -> Vec<(TransactionSource, Vec<ExtrinsicFor<ChainApi>>)> {
+		let mut grouped: Vec<(TransactionSource, Vec<ExtrinsicFor<ChainApi>>)> = Vec::new();
+		for tx in self.transactions.read().values().filter(|tx| tx.included_in.is_none()) {
+			match grouped.iter_mut().find(|(source, _)| *source == tx.source) {
+				Some((_, xts)) => xts.push(tx.tx.clone()),
+				None => grouped.push((tx.source, vec![tx.tx.clone()])),
+			}
+		}
+		grouped
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{uxt, TestApi};
+	use substrate_test_runtime::{AccountId, Transfer, H256};
+	use substrate_test_runtime_client::AccountKeyring::Alice;
+
+	fn transfer(nonce: u64) -> ExtrinsicFor<TestApi> {
+		uxt(Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce,
+		})
+	}
+
+	#[test]
+	fn push_rejects_duplicates_and_respects_limit() {
+		let api = Arc::new(TestApi::default());
+		let mempool = TxMemPool::new(api, 2);
+
+		let results = mempool.push(TransactionSource::External, &[transfer(0), transfer(0)]);
+		assert!(results[0].is_ok());
+		assert!(results[1].is_err());
+
+		let results = mempool.push(TransactionSource::External, &[transfer(1), transfer(2)]);
+		assert!(results[0].is_ok());
+		assert!(results[1].is_err());
+		assert_eq!(mempool.len(), 2);
+	}
+
+	#[test]
+	fn included_transactions_are_not_pending() {
+		let api = Arc::new(TestApi::default());
+		let mempool = TxMemPool::new(api, 10);
+
+		let hashes = mempool
+			.push(TransactionSource::External, &[transfer(0), transfer(1)])
+			.into_iter()
+			.map(|r| r.expect("Mempool is not full"))
+			.collect::<Vec<_>>();
+
+		mempool.set_included(&hashes[0], Some(H256::repeat_byte(1)));
+		assert!(mempool.is_included(&hashes[0]));
+		assert_eq!(mempool.pending_hashes(), vec![hashes[1]]);
+
+		mempool.set_included(&hashes[0], None);
+		assert_eq!(mempool.pending_hashes().len(), 2);
+
+		mempool.remove(&hashes);
+		assert!(mempool.is_empty());
+	}
+}
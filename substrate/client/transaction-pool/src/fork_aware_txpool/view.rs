@@ -0,0 +1,116 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Transaction pool view.
+//!
+//! A view represents the state of the transaction pool at a given block. It owns a
+//! [`graph::Pool`] holding the transactions validated against the state of that block.
+
+use std::sync::Arc;
+
+use sp_blockchain::HashAndNumber;
+
+use crate::{
+	graph::{self, ExtrinsicHash, IsValidator, ValidatedTransaction},
+	prune_known_txs_for_block,
+	revalidation::RevalidationQueue,
+	LOG_TARGET,
+};
+
+/// The state of the transaction pool at a given block.
+pub(crate) struct View<ChainApi: graph::ChainApi> {
+	/// The block this view was created at.
+	pub(crate) at: HashAndNumber<ChainApi::Block>,
+	/// The transactions validated at `at`.
+	pub(crate) pool: Arc<graph::Pool<ChainApi>>,
+	/// Queue used to revalidate the transactions of the view.
+	revalidation_queue: RevalidationQueue<ChainApi>,
+}
+
+impl<ChainApi> View<ChainApi>
+where
+	ChainApi: graph::ChainApi + 'static,
+{
+	/// Creates an empty view at the given block.
+	pub(crate) fn new(
+		api: Arc<ChainApi>,
+		at: HashAndNumber<ChainApi::Block>,
+		options: graph::Options,
+		is_validator: IsValidator,
+	) -> Self {
+		let pool = Arc::new(graph::Pool::new(options, is_validator, api.clone()));
+		let revalidation_queue = RevalidationQueue::new(api, pool.clone());
+		Self { at, pool, revalidation_queue }
+	}
+
+	/// Creates a new view at `at` holding a copy of every transaction of `self`.
+	///
+	/// The copied transactions are not revalidated, [`View::update`] shall be called to bring
+	/// the new view up to date.
+	pub(crate) fn new_from_other(
+		&self,
+		api: Arc<ChainApi>,
+		at: HashAndNumber<ChainApi::Block>,
+		options: graph::Options,
+		is_validator: IsValidator,
+	) -> Self {
+		let view = Self::new(api, at, options, is_validator);
+		let transactions = {
+			let pool = self.pool.validated_pool().pool.read();
+			pool.ready()
+				.map(|tx| tx.duplicate())
+				.chain(pool.futures().map(|tx| tx.duplicate()))
+				.collect::<Vec<_>>()
+		};
+		log::trace!(
+			target: LOG_TARGET,
+			"Copying {} transactions from view at {:?} to view at {:?}",
+			transactions.len(),
+			self.at,
+			view.at,
+		);
+		let _ = view
+			.pool
+			.validated_pool()
+			.submit(transactions.into_iter().map(ValidatedTransaction::Valid));
+		view
+	}
+
+	/// Brings the view up to date after it was copied from a view of an ancestor block.
+	///
+	/// Transactions included in the `enacted` blocks are pruned, and all the remaining ready
+	/// transactions are revalidated at the view's block.
+	pub(crate) async fn update(&self, api: &ChainApi, enacted: &[HashAndNumber<ChainApi::Block>]) {
+		for block in enacted {
+			prune_known_txs_for_block(block.hash, api, &*self.pool).await;
+		}
+
+		let hashes = self.pool.validated_pool().ready().map(|tx| tx.hash).collect();
+		self.revalidation_queue.revalidate_later(self.at.hash, hashes).await;
+	}
+
+	/// Returns true if the transaction is in the ready or future queue of the view.
+	pub(crate) fn is_imported(&self, hash: &ExtrinsicHash<ChainApi>) -> bool {
+		self.pool.validated_pool().pool.read().is_imported(hash)
+	}
+
+	/// Returns true if the transaction is in the ready queue of the view.
+	pub(crate) fn is_ready(&self, hash: &ExtrinsicHash<ChainApi>) -> bool {
+		self.pool.validated_pool().ready_by_hash(hash).is_some()
+	}
+}
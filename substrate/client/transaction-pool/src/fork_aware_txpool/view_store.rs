@@ -0,0 +1,280 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Collection of the views of the fork-aware transaction pool.
+
+use std::{collections::HashMap, sync::Arc};
+
+use parking_lot::RwLock;
+use sc_transaction_pool_api::{error::Error as TxPoolError, TransactionSource};
+use sp_blockchain::{HashAndNumber, TreeRoute};
+
+use super::{tx_mem_pool::TxMemPool, view::View};
+use crate::{
+	graph::{self, BlockHash, ExtrinsicFor, ExtrinsicHash, IsValidator, TransactionFor},
+	LOG_TARGET,
+};
+
+/// Set of the views of the fork-aware transaction pool, keyed by the block hash.
+pub(crate) struct ViewStore<ChainApi: graph::ChainApi> {
+	api: Arc<ChainApi>,
+	options: graph::Options,
+	is_validator: Arc<IsValidator>,
+	views: RwLock<HashMap<BlockHash<ChainApi>, Arc<View<ChainApi>>>>,
+	most_recent_view: RwLock<Option<BlockHash<ChainApi>>>,
+}
+
+impl<ChainApi> ViewStore<ChainApi>
+where
+	ChainApi: graph::ChainApi + 'static,
+{
+	/// Creates an empty view store.
+	pub(crate) fn new(
+		api: Arc<ChainApi>,
+		options: graph::Options,
+		is_validator: Arc<IsValidator>,
+	) -> Self {
+		Self {
+			api,
+			options,
+			is_validator,
+			views: Default::default(),
+			most_recent_view: Default::default(),
+		}
+	}
+
+	/// Returns the number of views.
+	pub(crate) fn len(&self) -> usize {
+		self.views.read().len()
+	}
+
+	/// Returns the view at the given block, if there is one.
+	pub(crate) fn get(&self, at: &BlockHash<ChainApi>) -> Option<Arc<View<ChainApi>>> {
+		self.views.read().get(at).cloned()
+	}
+
+	/// Returns the view of the most recent best block.
+	pub(crate) fn most_recent_view(&self) -> Option<Arc<View<ChainApi>>> {
+		let most_recent_view = (*self.most_recent_view.read())?;
+		self.get(&most_recent_view)
+	}
+
+	/// Marks the view at the given block as the most recent one.
+	pub(crate) fn set_most_recent(&self, at: BlockHash<ChainApi>) {
+		*self.most_recent_view.write() = Some(at);
+	}
+
+	/// Returns all the views, the most recent one being the first.
+	pub(crate) fn views(&self) -> Vec<Arc<View<ChainApi>>> {
+		let most_recent_view = *self.most_recent_view.read();
+		let mut views = self.views.read().values().cloned().collect::<Vec<_>>();
+		views.sort_by_key(|view| Some(view.at.hash) != most_recent_view);
+		views
+	}
+
+	/// Inserts an empty view at the given block.
+	pub(crate) fn insert_empty(&self, at: HashAndNumber<ChainApi::Block>) {
+		let view = View::new(self.api.clone(), at, self.options.clone(), self.view_is_validator());
+		self.views.write().insert(view.at.hash, Arc::new(view));
+	}
+
+	/// Returns true if any of the views holds the given transaction.
+	pub(crate) fn is_imported(&self, hash: &ExtrinsicHash<ChainApi>) -> bool {
+		self.views.read().values().any(|view| view.is_imported(hash))
+	}
+
+	/// Returns the view at the given block, creating it if it does not exist yet.
+	///
+	/// If there is a view at an ancestor of `at`, the new view is derived from it: the
+	/// transactions are copied, the ones included in the blocks between both views are pruned
+	/// and the rest is revalidated. Then all pending transactions of the `mempool` that are not
+	/// known to the view are validated and submitted to it.
+	pub(crate) async fn get_or_create_view(
+		&self,
+		at: HashAndNumber<ChainApi::Block>,
+		mempool: &TxMemPool<ChainApi>,
+	) -> Arc<View<ChainApi>> {
+		if let Some(view) = self.get(&at.hash) {
+			return view
+		}
+
+		let view = match self.find_best_ancestor_view(&at) {
+			Some((origin, tree_route)) => {
+				log::debug!(
+					target: LOG_TARGET,
+					"Creating view at {:?} from view at {:?}",
+					at,
+					origin.at,
+				);
+				let view = origin.new_from_other(
+					self.api.clone(),
+					at,
+					self.options.clone(),
+					self.view_is_validator(),
+				);
+				view.update(&*self.api, tree_route.enacted()).await;
+				view
+			},
+			None => {
+				log::debug!(target: LOG_TARGET, "Creating empty view at {:?}", at);
+				View::new(self.api.clone(), at, self.options.clone(), self.view_is_validator())
+			},
+		};
+
+		for (source, xts) in mempool.pending_by_source() {
+			if let Err(e) = view.pool.submit_at(view.at.hash, source, xts).await {
+				log::debug!(
+					target: LOG_TARGET,
+					"Error submitting mempool transactions to view at {:?}: {}",
+					view.at,
+					e,
+				);
+			}
+		}
+
+		let view = Arc::new(view);
+		self.views.write().insert(view.at.hash, view.clone());
+		view
+	}
+
+	/// Imports a bunch of unverified extrinsics to every view.
+	///
+	/// A transaction is reported as imported if it was accepted by at least one view,
+	/// otherwise the error reported by the most recent view is returned.
+	pub(crate) async fn submit_at(
+		&self,
+		source: TransactionSource,
+		xts: Vec<ExtrinsicFor<ChainApi>>,
+	) -> Vec<Result<ExtrinsicHash<ChainApi>, ChainApi::Error>> {
+		let views = self.views();
+		let results = futures::future::join_all(
+			views.iter().map(|view| view.pool.submit_at(view.at.hash, source, xts.clone())),
+		)
+		.await;
+
+		let mut combined = (0..xts.len()).map(|_| None).collect::<Vec<_>>();
+		for (view, view_results) in views.iter().zip(results) {
+			let view_results = match view_results {
+				Ok(view_results) => view_results,
+				Err(e) => {
+					log::debug!(
+						target: LOG_TARGET,
+						"Error submitting transactions to view at {:?}: {}",
+						view.at,
+						e,
+					);
+					continue
+				},
+			};
+
+			for (result, combined) in view_results.into_iter().zip(combined.iter_mut()) {
+				let imported = matches!(combined, Some(Ok(_)));
+				match result {
+					Ok(hash) if !imported => *combined = Some(Ok(hash)),
+					Err(e) if combined.is_none() => *combined = Some(Err(e)),
+					_ => {},
+				}
+			}
+		}
+
+		combined
+			.into_iter()
+			.map(|result| {
+				result.unwrap_or_else(|| {
+					Err(TxPoolError::InvalidBlockId("No active views".into()).into())
+				})
+			})
+			.collect()
+	}
+
+	/// Removes the given transactions (and their dependencies) from the view at `at`, or from all
+	/// the views if no block is given.
+	pub(crate) fn remove_invalid(
+		&self,
+		at: Option<BlockHash<ChainApi>>,
+		hashes: &[ExtrinsicHash<ChainApi>],
+	) -> Vec<TransactionFor<ChainApi>> {
+		let views = match at {
+			Some(at) => self.get(&at).into_iter().collect(),
+			None => self.views(),
+		};
+
+		let mut removed = HashMap::new();
+		for view in views {
+			for tx in view.pool.validated_pool().remove_invalid(hashes) {
+				removed.entry(tx.hash).or_insert(tx);
+			}
+		}
+		removed.into_values().collect()
+	}
+
+	/// Removes the views that are not descendants of the finalized block.
+	///
+	/// The view at the finalized block is kept, since it may still be used for authoring.
+	pub(crate) fn handle_finalized(&self, finalized: &HashAndNumber<ChainApi::Block>) {
+		let mut views = self.views.write();
+		views.retain(|hash, view| {
+			if *hash == finalized.hash {
+				return true
+			}
+			if view.at.number <= finalized.number {
+				return false
+			}
+			self.api
+				.tree_route(finalized.hash, *hash)
+				.map(|tree_route| tree_route.retracted().is_empty())
+				.unwrap_or(false)
+		});
+
+		let mut most_recent_view = self.most_recent_view.write();
+		if (*most_recent_view).map_or(true, |hash| !views.contains_key(&hash)) {
+			*most_recent_view =
+				views.values().max_by_key(|view| view.at.number).map(|view| view.at.hash);
+		}
+
+		log::debug!(
+			target: LOG_TARGET,
+			"Finalized {:?}, active views: {:?}",
+			finalized,
+			views.keys().collect::<Vec<_>>(),
+		);
+	}
+
+	/// Finds the view at the closest ancestor of `at`, along with the tree route leading from
+	/// the ancestor to `at`.
+	fn find_best_ancestor_view(
+		&self,
+		at: &HashAndNumber<ChainApi::Block>,
+	) -> Option<(Arc<View<ChainApi>>, TreeRoute<ChainApi::Block>)> {
+		self.views()
+			.into_iter()
+			.filter(|view| view.at.number < at.number)
+			.filter_map(|view| {
+				let tree_route = self.api.tree_route(view.at.hash, at.hash).ok()?;
+				tree_route.retracted().is_empty().then(|| (view, tree_route))
+			})
+			.max_by_key(|(view, _)| view.at.number)
+	}
+
+	fn view_is_validator(&self) -> IsValidator {
+		let is_validator = self.is_validator.clone();
+		IsValidator::from(
+			Box::new(move || is_validator.is_validator()) as Box<dyn Fn() -> bool + Send + Sync>
+		)
+	}
+}
@@ -39,6 +39,8 @@ pub mod watcher;
 
 pub use self::{
 	base_pool::Transaction,
-	pool::{BlockHash, ChainApi, ExtrinsicFor, ExtrinsicHash, NumberFor, Options, Pool},
+	pool::{
+		BlockHash, ChainApi, ExtrinsicFor, ExtrinsicHash, NumberFor, Options, Pool, TransactionFor,
+	},
 };
 pub use validated_pool::{IsValidator, ValidatedTransaction};
@@ -119,6 +119,9 @@ pub struct Options {
 	pub reject_future_transactions: bool,
 	/// How long the extrinsic is banned for.
 	pub ban_time: Duration,
+	/// The implementation of the pool used by a full node, see
+	/// [`FullTransactionPool`](crate::FullTransactionPool).
+	pub pool_type: crate::TransactionPoolType,
 }
 
 impl Default for Options {
@@ -128,6 +131,7 @@ impl Default for Options {
 			future: base::Limit { count: 512, total_bytes: 1 * 1024 * 1024 },
 			reject_future_transactions: false,
 			ban_time: Duration::from_secs(60 * 30),
+			pool_type: Default::default(),
 		}
 	}
 }
@@ -100,6 +100,13 @@ impl From<Box<dyn Fn() -> bool + Send + Sync>> for IsValidator {
 	}
 }
 
+impl IsValidator {
+	/// Returns true if the local node is a validator that can author blocks.
+	pub(crate) fn is_validator(&self) -> bool {
+		(self.0)()
+	}
+}
+
 /// Pool that deals with validated transactions.
 pub struct ValidatedPool<B: ChainApi> {
 	api: Arc<B>,
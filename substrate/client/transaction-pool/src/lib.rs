@@ -23,8 +23,10 @@
 #![warn(unused_extern_crates)]
 
 mod api;
+mod builder;
 mod enactment_state;
 pub mod error;
+mod fork_aware_txpool;
 mod graph;
 mod metrics;
 mod revalidation;
@@ -33,7 +35,9 @@ mod tests;
 
 pub use crate::api::FullChainApi;
 use async_trait::async_trait;
+pub use builder::{FullTransactionPool, TransactionPoolType};
 use enactment_state::{EnactmentAction, EnactmentState};
+pub use fork_aware_txpool::{ForkAwareFullPool, ForkAwareTxPool};
 use futures::{
 	channel::oneshot,
 	future::{self, ready},
@@ -303,7 +307,11 @@ where
 		.boxed()
 	}
 
-	fn remove_invalid(&self, hashes: &[TxHash<Self>]) -> Vec<Arc<Self::InPoolTransaction>> {
+	fn remove_invalid(
+		&self,
+		_at: Option<<Self::Block as BlockT>::Hash>,
+		hashes: &[TxHash<Self>],
+	) -> Vec<Arc<Self::InPoolTransaction>> {
 		let removed = self.pool.validated_pool().remove_invalid(hashes);
 		self.metrics
 			.report(|metrics| metrics.validations_invalid.inc_by(removed.len() as u64));
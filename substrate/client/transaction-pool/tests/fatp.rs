@@ -0,0 +1,217 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the fork-aware transaction pool.
+
+use futures::executor::{block_on, block_on_stream};
+use sc_transaction_pool::ForkAwareTxPool;
+use sc_transaction_pool_api::{
+	ChainEvent, MaintainedTransactionPool, TransactionPool, TransactionStatus,
+};
+use sp_runtime::transaction_validity::TransactionSource;
+use std::sync::Arc;
+use substrate_test_runtime_client::{
+	runtime::{Block, Hash},
+	AccountKeyring::*,
+};
+use substrate_test_runtime_transaction_pool::{uxt, TestApi};
+
+const SOURCE: TransactionSource = TransactionSource::External;
+
+fn pool() -> (ForkAwareTxPool<TestApi, Block>, Arc<TestApi>) {
+	let api = Arc::new(TestApi::with_alice_nonce(200));
+	let genesis = api.expect_hash_from_number(0);
+	(ForkAwareTxPool::new_test(api.clone(), genesis, genesis), api)
+}
+
+fn new_best_block_event(hash: Hash) -> ChainEvent<Block> {
+	ChainEvent::NewBestBlock { hash, tree_route: None }
+}
+
+#[test]
+fn fatp_new_best_block_prunes_included_transactions() {
+	let (pool, api) = pool();
+	let genesis = api.expect_hash_from_number(0);
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	block_on(pool.submit_at(genesis, SOURCE, vec![xt0.clone(), xt1.clone()])).unwrap();
+	assert_eq!(pool.status().ready, 2);
+	assert_eq!(pool.mempool_len(), 2);
+
+	let header = api.push_block(1, vec![xt0.clone()], true);
+	api.increment_nonce(Alice.into());
+	block_on(pool.maintain(new_best_block_event(header.hash())));
+
+	assert_eq!(pool.active_views_count(), 2);
+	assert_eq!(pool.status().ready, 1);
+	assert_eq!(pool.status_at(&genesis).unwrap().ready, 2);
+	// included transaction is kept until finalized
+	assert_eq!(pool.mempool_len(), 2);
+}
+
+#[test]
+fn fatp_ready_at_block_creates_view_for_non_best_fork() {
+	let (pool, api) = pool();
+	let genesis = api.expect_hash_from_number(0);
+
+	let xt0 = uxt(Alice, 200);
+	block_on(pool.submit_one(genesis, SOURCE, xt0.clone())).unwrap();
+
+	let a1 = api.push_block(1, vec![xt0.clone()], true);
+	block_on(pool.maintain(new_best_block_event(a1.hash())));
+	assert_eq!(pool.status().ready, 0);
+
+	let b1 = api.push_block_with_parent(genesis, vec![], false);
+	assert!(!pool.has_view(&b1.hash()));
+
+	let ready = block_on(pool.ready_at_block(b1.hash(), 1))
+		.map(|tx| tx.hash)
+		.collect::<Vec<_>>();
+	assert_eq!(ready, vec![pool.hash_of(&xt0)]);
+	assert!(pool.has_view(&b1.hash()));
+	assert_eq!(pool.active_views_count(), 3);
+}
+
+#[test]
+fn fatp_finalization_prunes_views_and_notifies_watchers() {
+	let (pool, api) = pool();
+	let genesis = api.expect_hash_from_number(0);
+
+	let xt0 = uxt(Alice, 200);
+	let watcher = block_on(pool.submit_and_watch(genesis, SOURCE, xt0.clone())).unwrap();
+
+	let a1 = api.push_block(1, vec![xt0.clone()], true);
+	api.increment_nonce(Alice.into());
+	block_on(pool.maintain(new_best_block_event(a1.hash())));
+
+	let b1 = api.push_block_with_parent(genesis, vec![], false);
+	let _ = block_on(pool.ready_at_block(b1.hash(), 1));
+	assert_eq!(pool.active_views_count(), 3);
+
+	block_on(
+		pool.maintain(ChainEvent::Finalized { hash: a1.hash(), tree_route: Arc::from(vec![]) }),
+	);
+	assert_eq!(pool.active_views_count(), 1);
+	assert!(pool.has_view(&a1.hash()));
+	assert_eq!(pool.mempool_len(), 0);
+
+	let mut stream = block_on_stream(watcher);
+	assert_eq!(stream.next(), Some(TransactionStatus::Ready));
+	assert_eq!(stream.next(), Some(TransactionStatus::InBlock((a1.hash(), 0))));
+	assert_eq!(stream.next(), Some(TransactionStatus::Finalized((a1.hash(), 0))));
+	assert_eq!(stream.next(), None);
+}
+
+#[test]
+fn fatp_invalid_transactions_are_only_removed_from_the_reporting_view() {
+	let (pool, api) = pool();
+	let genesis = api.expect_hash_from_number(0);
+
+	let xt0 = uxt(Alice, 200);
+	let xt0_hash = pool.hash_of(&xt0);
+	let watcher = block_on(pool.submit_and_watch(genesis, SOURCE, xt0.clone())).unwrap();
+
+	let a1 = api.push_block(1, vec![], true);
+	block_on(pool.maintain(new_best_block_event(a1.hash())));
+	assert_eq!(pool.status_at(&a1.hash()).unwrap().ready, 1);
+
+	// Invalid at `a1`, but still valid at the genesis.
+	pool.remove_invalid(Some(a1.hash()), &[xt0_hash]);
+	assert_eq!(pool.status_at(&a1.hash()).unwrap().ready, 0);
+	assert_eq!(pool.status_at(&genesis).unwrap().ready, 1);
+	assert_eq!(pool.mempool_len(), 1);
+
+	// Dropped once no view holds it any more.
+	pool.remove_invalid(Some(genesis), &[xt0_hash]);
+	assert_eq!(pool.status_at(&genesis).unwrap().ready, 0);
+	assert_eq!(pool.mempool_len(), 0);
+
+	let mut stream = block_on_stream(watcher);
+	assert_eq!(stream.next(), Some(TransactionStatus::Ready));
+	assert_eq!(stream.next(), Some(TransactionStatus::Invalid));
+	assert_eq!(stream.next(), None);
+}
+
+#[test]
+fn fatp_retracted_transactions_are_resubmitted_to_new_fork() {
+	let (pool, api) = pool();
+	let genesis = api.expect_hash_from_number(0);
+
+	let xt0 = uxt(Alice, 200);
+	let watcher = block_on(pool.submit_and_watch(genesis, SOURCE, xt0.clone())).unwrap();
+
+	let a1 = api.push_block(1, vec![xt0.clone()], true);
+	block_on(pool.maintain(new_best_block_event(a1.hash())));
+	assert_eq!(pool.status().ready, 0);
+
+	let b1 = api.push_block_with_parent(genesis, vec![], true);
+	let b2 = api.push_block_with_parent(b1.hash(), vec![], true);
+	block_on(pool.maintain(new_best_block_event(b2.hash())));
+	assert_eq!(pool.status().ready, 1);
+
+	let mut stream = block_on_stream(watcher);
+	assert_eq!(stream.next(), Some(TransactionStatus::Ready));
+	assert_eq!(stream.next(), Some(TransactionStatus::InBlock((a1.hash(), 0))));
+	assert_eq!(stream.next(), Some(TransactionStatus::Retracted(a1.hash())));
+	assert_eq!(stream.next(), Some(TransactionStatus::Ready));
+}
+
+#[test]
+fn fatp_unknown_transactions_of_retracted_blocks_are_resubmitted() {
+	let (pool, api) = pool();
+	let genesis = api.expect_hash_from_number(0);
+
+	// The transaction was included by another node, this pool has never seen it.
+	let xt0 = uxt(Alice, 200);
+	let a1 = api.push_block(1, vec![xt0.clone()], true);
+	block_on(pool.maintain(new_best_block_event(a1.hash())));
+	assert_eq!(pool.mempool_len(), 0);
+
+	let b1 = api.push_block_with_parent(genesis, vec![], true);
+	let b2 = api.push_block_with_parent(b1.hash(), vec![], true);
+	block_on(pool.maintain(new_best_block_event(b2.hash())));
+
+	assert_eq!(pool.mempool_len(), 1);
+	assert_eq!(pool.ready().map(|tx| tx.hash).collect::<Vec<_>>(), vec![pool.hash_of(&xt0)]);
+}
+
+#[test]
+fn fatp_unknown_transactions_of_retracted_blocks_are_resubmitted_to_existing_view() {
+	let (pool, api) = pool();
+	let genesis = api.expect_hash_from_number(0);
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Bob, 0);
+	block_on(pool.submit_one(genesis, SOURCE, xt1.clone())).unwrap();
+
+	let a1 = api.push_block(1, vec![xt0.clone()], true);
+	block_on(pool.maintain(new_best_block_event(a1.hash())));
+
+	// The view at the new best block is created before the reorg is notified.
+	let b1 = api.push_block_with_parent(genesis, vec![], true);
+	let b2 = api.push_block_with_parent(b1.hash(), vec![], true);
+	let _ = block_on(pool.ready_at_block(b2.hash(), 2));
+	assert_eq!(pool.status_at(&b2.hash()).unwrap().ready, 1);
+
+	block_on(pool.maintain(new_best_block_event(b2.hash())));
+
+	assert_eq!(pool.mempool_len(), 2);
+	assert_eq!(pool.status().ready, 2);
+	assert!(pool.ready_transaction(&pool.hash_of(&xt0)).is_some());
+}
@@ -104,6 +104,7 @@ parameter_types! {
 	pub const MaxAdditionalFields: u32 = 2;
 	pub const MaxRegistrars: u32 = 20;
 	pub const PendingUsernameExpiration: u64 = 100;
+	pub const VerificationPeriod: u64 = 100;
 }
 ord_parameter_types! {
 	pub const One: u64 = 1;
@@ -133,7 +134,13 @@ impl pallet_identity::Config for Test {
 	type PendingUsernameExpiration = PendingUsernameExpiration;
 	type MaxSuffixLength = ConstU32<7>;
 	type MaxUsernameLength = ConstU32<32>;
+	type FieldVerifier = ();
+	type VerificationOracleOrigin = EnsureOneOrRoot;
+	type VerificationPeriod = VerificationPeriod;
+	type MaxVerifiedFields = ConstU32<8>;
 	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 #[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo)]
@@ -28,6 +28,7 @@ use frame_support::{
 	},
 };
 use frame_system::RawOrigin as SystemOrigin;
+use sp_arithmetic::Percent;
 use sp_core::Get;
 use sp_std::{marker::PhantomData, prelude::*};
 
@@ -156,6 +157,71 @@ where
 	(lp_token, liquidity1, liquidity2)
 }
 
+/// Creates a pool of the given `kind` for every hop of a swap path of length `n`, and provides a
+/// liquidity large enough for the pool curve to be significant.
+///
+/// Returns the swap path along with the liquidity provided to each of its pools.
+fn create_path_with_kind<T: Config>(
+	caller: &T::AccountId,
+	n: u32,
+	kind: impl Fn(&T::AssetKind, &T::AssetKind) -> PoolKind<T::AssetKind>,
+) -> (Vec<Box<T::AssetKind>>, T::Balance)
+where
+	T::Assets: Create<T::AccountId> + Mutate<T::AccountId>,
+{
+	let mut path = vec![];
+	let mut liquidity = T::Balance::zero();
+
+	create_fee_asset::<T>(caller);
+	for n in 1..n {
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(n - 1, n);
+		if path.len() == 0 {
+			path = vec![Box::new(asset1.clone()), Box::new(asset2.clone())];
+		} else {
+			path.push(Box::new(asset2.clone()));
+		}
+
+		let (liquidity1, liquidity2) = valid_liquidity_amount::<T>(
+			T::Assets::minimum_balance(asset1.clone()),
+			T::Assets::minimum_balance(asset2.clone()),
+		);
+		liquidity = liquidity1.max(liquidity2) * T::Balance::from(100u32);
+		create_asset::<T>(caller, &asset1, liquidity);
+		create_asset::<T>(caller, &asset2, liquidity);
+		let lp_token = AssetConversion::<T>::get_next_pool_asset_id();
+		mint_setup_fee_asset::<T>(caller, &asset1, &asset2, &lp_token);
+
+		assert_ok!(AssetConversion::<T>::create_pool_with_kind(
+			SystemOrigin::Signed(caller.clone()).into(),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			kind(&asset1, &asset2),
+		));
+		assert_ok!(AssetConversion::<T>::add_liquidity(
+			SystemOrigin::Signed(caller.clone()).into(),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			liquidity,
+			liquidity,
+			T::Balance::one(),
+			T::Balance::zero(),
+			caller.clone(),
+		));
+	}
+
+	(path, liquidity)
+}
+
+/// The kind of the StableSwap pools used in the benchmarks.
+fn stable_swap_kind<T: Config>(_: &T::AssetKind, _: &T::AssetKind) -> PoolKind<T::AssetKind> {
+	PoolKind::StableSwap { amplification: 100 }
+}
+
+/// The kind of the weighted pools used in the benchmarks.
+fn weighted_kind<T: Config>(asset1: &T::AssetKind, _: &T::AssetKind) -> PoolKind<T::AssetKind> {
+	PoolKind::Weighted { asset: asset1.clone(), weight: Percent::from_percent(49) }
+}
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
@@ -358,5 +424,141 @@ mod benchmarks {
 		assert_eq!(actual_balance, init_caller_balance + T::Balance::one());
 	}
 
+	#[benchmark]
+	fn create_pool_with_kind() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		create_asset::<T>(&caller, &asset1, T::Assets::minimum_balance(asset1.clone()));
+		create_asset::<T>(&caller, &asset2, T::Assets::minimum_balance(asset2.clone()));
+
+		let lp_token = AssetConversion::<T>::get_next_pool_asset_id();
+		create_fee_asset::<T>(&caller);
+		mint_setup_fee_asset::<T>(&caller, &asset1, &asset2, &lp_token);
+		let kind = weighted_kind::<T>(&asset1, &asset2);
+
+		#[extrinsic_call]
+		_(
+			SystemOrigin::Signed(caller.clone()),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			kind.clone(),
+		);
+
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		let pool_account = T::PoolLocator::address(&pool_id).unwrap();
+		assert_eq!(PoolKinds::<T>::get(&pool_id), kind);
+		assert_last_event::<T>(
+			Event::PoolCreated { creator: caller, pool_account, pool_id, lp_token }.into(),
+		);
+	}
+
+	#[benchmark]
+	fn swap_exact_tokens_for_tokens_stable_swap(n: Linear<2, { T::MaxSwapPathLength::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let (path, liquidity) = create_path_with_kind::<T>(&caller, n, stable_swap_kind::<T>);
+		let swap_amount = liquidity / T::Balance::from(10u32);
+
+		let asset_in = *path.first().unwrap().clone();
+		assert_ok!(T::Assets::mint_into(
+			asset_in.clone(),
+			&caller,
+			swap_amount + T::Balance::one()
+		));
+		let init_caller_balance = T::Assets::balance(asset_in.clone(), &caller);
+
+		#[extrinsic_call]
+		swap_exact_tokens_for_tokens(
+			SystemOrigin::Signed(caller.clone()),
+			path,
+			swap_amount,
+			T::Balance::one(),
+			caller.clone(),
+			true,
+		);
+
+		let actual_balance = T::Assets::balance(asset_in, &caller);
+		assert_eq!(actual_balance, init_caller_balance - swap_amount);
+	}
+
+	#[benchmark]
+	fn swap_tokens_for_exact_tokens_stable_swap(n: Linear<2, { T::MaxSwapPathLength::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let (path, liquidity) = create_path_with_kind::<T>(&caller, n, stable_swap_kind::<T>);
+		let swap_amount = liquidity / T::Balance::from(100u32);
+		let max_swap_amount = liquidity / T::Balance::from(10u32);
+
+		let asset_in = *path.first().unwrap().clone();
+		let asset_out = *path.last().unwrap().clone();
+		assert_ok!(T::Assets::mint_into(asset_in, &caller, max_swap_amount));
+		let init_caller_balance = T::Assets::balance(asset_out.clone(), &caller);
+
+		#[extrinsic_call]
+		swap_tokens_for_exact_tokens(
+			SystemOrigin::Signed(caller.clone()),
+			path,
+			swap_amount,
+			max_swap_amount,
+			caller.clone(),
+			true,
+		);
+
+		let actual_balance = T::Assets::balance(asset_out, &caller);
+		assert_eq!(actual_balance, init_caller_balance + swap_amount);
+	}
+
+	#[benchmark]
+	fn swap_exact_tokens_for_tokens_weighted(n: Linear<2, { T::MaxSwapPathLength::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let (path, liquidity) = create_path_with_kind::<T>(&caller, n, weighted_kind::<T>);
+		let swap_amount = liquidity / T::Balance::from(10u32);
+
+		let asset_in = *path.first().unwrap().clone();
+		assert_ok!(T::Assets::mint_into(
+			asset_in.clone(),
+			&caller,
+			swap_amount + T::Balance::one()
+		));
+		let init_caller_balance = T::Assets::balance(asset_in.clone(), &caller);
+
+		#[extrinsic_call]
+		swap_exact_tokens_for_tokens(
+			SystemOrigin::Signed(caller.clone()),
+			path,
+			swap_amount,
+			T::Balance::one(),
+			caller.clone(),
+			true,
+		);
+
+		let actual_balance = T::Assets::balance(asset_in, &caller);
+		assert_eq!(actual_balance, init_caller_balance - swap_amount);
+	}
+
+	#[benchmark]
+	fn swap_tokens_for_exact_tokens_weighted(n: Linear<2, { T::MaxSwapPathLength::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let (path, liquidity) = create_path_with_kind::<T>(&caller, n, weighted_kind::<T>);
+		let swap_amount = liquidity / T::Balance::from(100u32);
+		let max_swap_amount = liquidity / T::Balance::from(10u32);
+
+		let asset_in = *path.first().unwrap().clone();
+		let asset_out = *path.last().unwrap().clone();
+		assert_ok!(T::Assets::mint_into(asset_in, &caller, max_swap_amount));
+		let init_caller_balance = T::Assets::balance(asset_out.clone(), &caller);
+
+		#[extrinsic_call]
+		swap_tokens_for_exact_tokens(
+			SystemOrigin::Signed(caller.clone()),
+			path,
+			swap_amount,
+			max_swap_amount,
+			caller.clone(),
+			true,
+		);
+
+		let actual_balance = T::Assets::balance(asset_out, &caller);
+		assert_eq!(actual_balance, init_caller_balance + swap_amount);
+	}
+
 	impl_benchmark_test_suite!(AssetConversion, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -0,0 +1,411 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pricing curves of the [`PoolKind::StableSwap`] and [`PoolKind::Weighted`] pools.
+//!
+//! All the amounts are rounded in favour of the pool.
+
+use super::*;
+
+/// The maximum number of Newton iterations to compute the StableSwap invariant or reserves.
+const MAX_ITERATIONS: u32 = 255;
+
+impl<T: Config> Pallet<T> {
+	/// Calculates amount out of a [`PoolKind::StableSwap`] pool.
+	///
+	/// Given an input amount of an asset, pair reserves and the `amplification` coefficient of the
+	/// pool, returns the maximum output amount of the other asset after taking a `fee` in 10ths of
+	/// a percent.
+	pub fn get_stable_swap_amount_out(
+		amount_in: &T::Balance,
+		reserve_in: &T::Balance,
+		reserve_out: &T::Balance,
+		amplification: u32,
+		fee: u32,
+	) -> Result<T::Balance, Error<T>> {
+		let amount_in = T::HigherPrecisionBalance::from(*amount_in);
+		let reserve_in = T::HigherPrecisionBalance::from(*reserve_in);
+		let reserve_out = T::HigherPrecisionBalance::from(*reserve_out);
+
+		if reserve_in.is_zero() || reserve_out.is_zero() {
+			return Err(Error::<T>::ZeroLiquidity)
+		}
+
+		let amount_in = Self::deduct_fee(amount_in, fee)?;
+		let amp = Self::stable_swap_amp(amplification)?;
+		let invariant = Self::stable_swap_invariant(reserve_in, reserve_out, amp)?;
+		let reserve_in_after = reserve_in.checked_add(&amount_in).ok_or(Error::<T>::Overflow)?;
+		let reserve_out_after = Self::stable_swap_reserve(reserve_in_after, invariant, amp)?;
+
+		let result = reserve_out
+			.checked_sub(&reserve_out_after)
+			.and_then(|r| r.checked_sub(&One::one()))
+			.unwrap_or_else(Zero::zero);
+
+		result.try_into().map_err(|_| Error::<T>::Overflow)
+	}
+
+	/// Calculates amount in of a [`PoolKind::StableSwap`] pool.
+	///
+	/// Given an output amount of an asset, pair reserves and the `amplification` coefficient of
+	/// the pool, returns a required input amount of the other asset including a `fee` in 10ths
+	/// of a percent.
+	pub fn get_stable_swap_amount_in(
+		amount_out: &T::Balance,
+		reserve_in: &T::Balance,
+		reserve_out: &T::Balance,
+		amplification: u32,
+		fee: u32,
+	) -> Result<T::Balance, Error<T>> {
+		let amount_out = T::HigherPrecisionBalance::from(*amount_out);
+		let reserve_in = T::HigherPrecisionBalance::from(*reserve_in);
+		let reserve_out = T::HigherPrecisionBalance::from(*reserve_out);
+
+		if reserve_in.is_zero() || reserve_out.is_zero() {
+			Err(Error::<T>::ZeroLiquidity)?
+		}
+
+		if amount_out >= reserve_out {
+			Err(Error::<T>::AmountOutTooHigh)?
+		}
+
+		let amp = Self::stable_swap_amp(amplification)?;
+		let invariant = Self::stable_swap_invariant(reserve_in, reserve_out, amp)?;
+		let reserve_out_after = reserve_out.checked_sub(&amount_out).ok_or(Error::<T>::Overflow)?;
+		let reserve_in_after = Self::stable_swap_reserve(reserve_out_after, invariant, amp)?;
+
+		let amount_in = reserve_in_after
+			.checked_sub(&reserve_in)
+			.ok_or(Error::<T>::Overflow)?
+			.checked_add(&One::one())
+			.ok_or(Error::<T>::Overflow)?;
+		let result = Self::add_fee(amount_in, fee)?;
+
+		result.try_into().map_err(|_| Error::<T>::Overflow)
+	}
+
+	/// Calculates amount out of a [`PoolKind::Weighted`] pool.
+	///
+	/// Given an input amount of an asset, pair reserves and the weights of the assets, returns the
+	/// maximum output amount of the other asset after taking a `fee` in 10ths of a percent.
+	pub fn get_weighted_amount_out(
+		amount_in: &T::Balance,
+		reserve_in: &T::Balance,
+		reserve_out: &T::Balance,
+		weight_in: u32,
+		weight_out: u32,
+		fee: u32,
+	) -> Result<T::Balance, Error<T>> {
+		let amount_in = T::HigherPrecisionBalance::from(*amount_in);
+		let reserve_in = T::HigherPrecisionBalance::from(*reserve_in);
+		let reserve_out = T::HigherPrecisionBalance::from(*reserve_out);
+
+		if reserve_in.is_zero() || reserve_out.is_zero() {
+			return Err(Error::<T>::ZeroLiquidity)
+		}
+
+		let amount_in = Self::deduct_fee(amount_in, fee)?;
+		let reserve_in_after = reserve_in.checked_add(&amount_in).ok_or(Error::<T>::Overflow)?;
+
+		// amount_out = reserve_out * (1 - (reserve_in / reserve_in_after) ^ (weight_in /
+		// weight_out))
+		let ratio = Self::fixed_div(reserve_in, reserve_in_after, true)?;
+		let factor = Self::fixed_pow_ratio(ratio, weight_in, weight_out, true)?;
+		let result = Self::fixed_mul(
+			reserve_out,
+			Self::fixed_one().checked_sub(&factor).ok_or(Error::<T>::Overflow)?,
+			false,
+		)?;
+
+		result.try_into().map_err(|_| Error::<T>::Overflow)
+	}
+
+	/// Calculates amount in of a [`PoolKind::Weighted`] pool.
+	///
+	/// Given an output amount of an asset, pair reserves and the weights of the assets, returns a
+	/// required input amount of the other asset including a `fee` in 10ths of a percent.
+	pub fn get_weighted_amount_in(
+		amount_out: &T::Balance,
+		reserve_in: &T::Balance,
+		reserve_out: &T::Balance,
+		weight_in: u32,
+		weight_out: u32,
+		fee: u32,
+	) -> Result<T::Balance, Error<T>> {
+		let amount_out = T::HigherPrecisionBalance::from(*amount_out);
+		let reserve_in = T::HigherPrecisionBalance::from(*reserve_in);
+		let reserve_out = T::HigherPrecisionBalance::from(*reserve_out);
+
+		if reserve_in.is_zero() || reserve_out.is_zero() {
+			Err(Error::<T>::ZeroLiquidity)?
+		}
+
+		if amount_out >= reserve_out {
+			Err(Error::<T>::AmountOutTooHigh)?
+		}
+
+		// amount_in = reserve_in * (1 / factor - 1), where
+		// factor = (reserve_out_after / reserve_out) ^ (weight_out / weight_in)
+		let reserve_out_after = reserve_out.checked_sub(&amount_out).ok_or(Error::<T>::Overflow)?;
+		let ratio = Self::fixed_div(reserve_out_after, reserve_out, false)?;
+		let factor = Self::fixed_pow_ratio(ratio, weight_out, weight_in, false)?;
+		if factor.is_zero() {
+			Err(Error::<T>::AmountOutTooHigh)?
+		}
+		let amount_in = Self::fixed_div(
+			Self::fixed_mul(
+				reserve_in,
+				Self::fixed_one().checked_sub(&factor).ok_or(Error::<T>::Overflow)?,
+				true,
+			)?,
+			factor,
+			true,
+		)?;
+		let result = Self::add_fee(amount_in, fee)?;
+
+		result.try_into().map_err(|_| Error::<T>::Overflow)
+	}
+
+	/// Deducts the `fee` in 10ths of a percent from `amount`.
+	fn deduct_fee(
+		amount: T::HigherPrecisionBalance,
+		fee: u32,
+	) -> Result<T::HigherPrecisionBalance, Error<T>> {
+		amount
+			.checked_mul(&(T::HigherPrecisionBalance::from(1000u32) - fee.into()))
+			.ok_or(Error::<T>::Overflow)?
+			.checked_div(&1000u32.into())
+			.ok_or(Error::<T>::Overflow)
+	}
+
+	/// Adds the `fee` in 10ths of a percent to `amount`, so that deducting it again gives back at
+	/// least `amount`.
+	fn add_fee(
+		amount: T::HigherPrecisionBalance,
+		fee: u32,
+	) -> Result<T::HigherPrecisionBalance, Error<T>> {
+		let denominator = T::HigherPrecisionBalance::from(1000u32) - fee.into();
+		amount
+			.checked_mul(&1000u32.into())
+			.ok_or(Error::<T>::Overflow)?
+			.checked_add(&(denominator - One::one()))
+			.ok_or(Error::<T>::Overflow)?
+			.checked_div(&denominator)
+			.ok_or(Error::<T>::Overflow)
+	}
+
+	/// The amplification coefficient of a StableSwap pool multiplied by `n^n`, for `n = 2` assets.
+	fn stable_swap_amp(amplification: u32) -> Result<T::HigherPrecisionBalance, Error<T>> {
+		T::HigherPrecisionBalance::from(amplification)
+			.checked_mul(&4u32.into())
+			.ok_or(Error::<T>::Overflow)
+	}
+
+	/// Computes the StableSwap invariant `d` of the reserves `x` and `y`, satisfying
+	/// `amp * (x + y) + d = amp * d + d^3 / (4 * x * y)`.
+	fn stable_swap_invariant(
+		x: T::HigherPrecisionBalance,
+		y: T::HigherPrecisionBalance,
+		amp: T::HigherPrecisionBalance,
+	) -> Result<T::HigherPrecisionBalance, Error<T>> {
+		let two = T::HigherPrecisionBalance::from(2u32);
+		let sum = x.checked_add(&y).ok_or(Error::<T>::Overflow)?;
+		let amp_sum = amp.checked_mul(&sum).ok_or(Error::<T>::Overflow)?;
+
+		let mut d = sum;
+		for _ in 0..MAX_ITERATIONS {
+			// d_p = d^3 / (4 * x * y)
+			let d_p = d
+				.checked_mul(&d)
+				.ok_or(Error::<T>::Overflow)?
+				.checked_div(&x.checked_mul(&two).ok_or(Error::<T>::Overflow)?)
+				.ok_or(Error::<T>::Overflow)?
+				.checked_mul(&d)
+				.ok_or(Error::<T>::Overflow)?
+				.checked_div(&y.checked_mul(&two).ok_or(Error::<T>::Overflow)?)
+				.ok_or(Error::<T>::Overflow)?;
+			// d = (amp * sum + 2 * d_p) * d / ((amp - 1) * d + 3 * d_p)
+			let numerator = d_p
+				.checked_mul(&two)
+				.and_then(|n| n.checked_add(&amp_sum))
+				.and_then(|n| n.checked_mul(&d))
+				.ok_or(Error::<T>::Overflow)?;
+			let denominator = (amp - One::one())
+				.checked_mul(&d)
+				.and_then(|n| n.checked_add(&d_p.checked_mul(&3u32.into())?))
+				.ok_or(Error::<T>::Overflow)?;
+			let d_next = numerator.checked_div(&denominator).ok_or(Error::<T>::Overflow)?;
+
+			if Self::converged(d, d_next) {
+				return Ok(d_next)
+			}
+			d = d_next;
+		}
+		Err(Error::<T>::CurveNotConverged)
+	}
+
+	/// Computes the StableSwap reserve `y` of one asset given the reserve `x` of the other asset
+	/// and the `invariant` of the pool.
+	fn stable_swap_reserve(
+		x: T::HigherPrecisionBalance,
+		invariant: T::HigherPrecisionBalance,
+		amp: T::HigherPrecisionBalance,
+	) -> Result<T::HigherPrecisionBalance, Error<T>> {
+		let two = T::HigherPrecisionBalance::from(2u32);
+		// y^2 + (b - d) * y = c, where b = x + d / amp and c = d^3 / (4 * x * amp)
+		let b = invariant
+			.checked_div(&amp)
+			.and_then(|b| b.checked_add(&x))
+			.ok_or(Error::<T>::Overflow)?;
+		let c = invariant
+			.checked_mul(&invariant)
+			.ok_or(Error::<T>::Overflow)?
+			.checked_div(&x.checked_mul(&two).ok_or(Error::<T>::Overflow)?)
+			.ok_or(Error::<T>::Overflow)?
+			.checked_mul(&invariant)
+			.ok_or(Error::<T>::Overflow)?
+			.checked_div(&amp.checked_mul(&two).ok_or(Error::<T>::Overflow)?)
+			.ok_or(Error::<T>::Overflow)?;
+
+		let mut y = invariant;
+		for _ in 0..MAX_ITERATIONS {
+			// y = (y^2 + c) / (2 * y + b - d)
+			let numerator =
+				y.checked_mul(&y).and_then(|n| n.checked_add(&c)).ok_or(Error::<T>::Overflow)?;
+			let denominator = y
+				.checked_mul(&two)
+				.and_then(|n| n.checked_add(&b))
+				.and_then(|n| n.checked_sub(&invariant))
+				.ok_or(Error::<T>::Overflow)?;
+			let y_next = numerator.checked_div(&denominator).ok_or(Error::<T>::Overflow)?;
+
+			if Self::converged(y, y_next) {
+				return Ok(y_next)
+			}
+			y = y_next;
+		}
+		Err(Error::<T>::CurveNotConverged)
+	}
+
+	/// Whether two consecutive Newton iterations differ by at most one.
+	fn converged(previous: T::HigherPrecisionBalance, next: T::HigherPrecisionBalance) -> bool {
+		let difference = if next > previous { next - previous } else { previous - next };
+		difference <= One::one()
+	}
+
+	/// `1` in the fixed point representation with 18 decimals used by the weighted curve.
+	fn fixed_one() -> T::HigherPrecisionBalance {
+		T::HigherPrecisionBalance::from(1_000_000_000u32) *
+			T::HigherPrecisionBalance::from(1_000_000_000u32)
+	}
+
+	/// Multiplies the fixed point numbers `a` and `b`.
+	fn fixed_mul(
+		a: T::HigherPrecisionBalance,
+		b: T::HigherPrecisionBalance,
+		round_up: bool,
+	) -> Result<T::HigherPrecisionBalance, Error<T>> {
+		let one = Self::fixed_one();
+		let product = a.checked_mul(&b).ok_or(Error::<T>::Overflow)?;
+		let product = if round_up {
+			product.checked_add(&(one - One::one())).ok_or(Error::<T>::Overflow)?
+		} else {
+			product
+		};
+		product.checked_div(&one).ok_or(Error::<T>::Overflow)
+	}
+
+	/// Divides the fixed point numbers `a` and `b`.
+	fn fixed_div(
+		a: T::HigherPrecisionBalance,
+		b: T::HigherPrecisionBalance,
+		round_up: bool,
+	) -> Result<T::HigherPrecisionBalance, Error<T>> {
+		let numerator = a.checked_mul(&Self::fixed_one()).ok_or(Error::<T>::Overflow)?;
+		let numerator = if round_up {
+			numerator.checked_add(&(b - One::one())).ok_or(Error::<T>::Overflow)?
+		} else {
+			numerator
+		};
+		numerator.checked_div(&b).ok_or(Error::<T>::Overflow)
+	}
+
+	/// Raises the fixed point number `x` to the power of `exponent`.
+	fn fixed_pow(
+		x: T::HigherPrecisionBalance,
+		exponent: u32,
+		round_up: bool,
+	) -> Result<T::HigherPrecisionBalance, Error<T>> {
+		let mut result = Self::fixed_one();
+		let mut base = x;
+		let mut exponent = exponent;
+		while exponent > 0 {
+			if exponent & 1 == 1 {
+				result = Self::fixed_mul(result, base, round_up)?;
+			}
+			exponent >>= 1;
+			if exponent > 0 {
+				base = Self::fixed_mul(base, base, round_up)?;
+			}
+		}
+		Ok(result)
+	}
+
+	/// Raises the fixed point number `x`, at most one, to the power of `numerator / denominator`.
+	///
+	/// The root is found by a binary search over the fixed point numbers between zero and one.
+	fn fixed_pow_ratio(
+		x: T::HigherPrecisionBalance,
+		numerator: u32,
+		denominator: u32,
+		round_up: bool,
+	) -> Result<T::HigherPrecisionBalance, Error<T>> {
+		let (mut a, mut b) = (numerator, denominator);
+		while b != 0 {
+			(a, b) = (b, a % b);
+		}
+		let (numerator, denominator) = (numerator / a, denominator / a);
+
+		let power = Self::fixed_pow(x, numerator, round_up)?;
+		if denominator == 1 {
+			return Ok(power)
+		}
+
+		let two = T::HigherPrecisionBalance::from(2u32);
+		let (mut low, mut high) = (Zero::zero(), Self::fixed_one());
+		while low < high {
+			if round_up {
+				// the smallest root raised to the `denominator` not less than `power`
+				let mid = (low + high) / two;
+				if Self::fixed_pow(mid, denominator, true)? >= power {
+					high = mid;
+				} else {
+					low = mid + One::one();
+				}
+			} else {
+				// the largest root raised to the `denominator` not greater than `power`
+				let mid = (low + high + One::one()) / two;
+				if Self::fixed_pow(mid, denominator, false)? <= power {
+					low = mid;
+				} else {
+					high = mid - One::one();
+				}
+			}
+		}
+		Ok(low)
+	}
+}
@@ -23,7 +23,8 @@
 //!
 //! This pallet allows you to:
 //!
-//!  - [create a liquidity pool](`Pallet::create_pool()`) for 2 assets
+//!  - [create a liquidity pool](`Pallet::create_pool()`) for 2 assets, or [one with a different
+//!    curve](`Pallet::create_pool_with_kind()`) like StableSwap or a weighted product
 //!  - [provide the liquidity](`Pallet::add_liquidity()`) and receive back an LP token
 //!  - [exchange the LP token back to assets](`Pallet::remove_liquidity()`)
 //!  - [swap a specific amount of assets for another](`Pallet::swap_exact_tokens_for_tokens()`) if
@@ -56,6 +57,7 @@
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+mod curves;
 #[cfg(test)]
 mod mock;
 mod swap;
@@ -100,7 +102,10 @@ pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
-	use sp_arithmetic::{traits::Unsigned, Permill};
+	use sp_arithmetic::{
+		traits::{Bounded, Unsigned},
+		Percent, Permill,
+	};
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
@@ -115,6 +120,7 @@ pub mod pallet {
 
 		/// A type used for calculations concerning the `Balance` type to avoid possible overflows.
 		type HigherPrecisionBalance: IntegerSquareRoot
+			+ Copy
 			+ One
 			+ Ensure
 			+ Unsigned
@@ -196,6 +202,12 @@ pub mod pallet {
 	pub type Pools<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::PoolId, PoolInfo<T::PoolAssetId>, OptionQuery>;
 
+	/// Map from `PoolId` to the kind of the pool. Pools without an entry use the
+	/// [`PoolKind::ConstantProduct`] curve.
+	#[pallet::storage]
+	pub type PoolKinds<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PoolId, PoolKind<T::AssetKind>, ValueQuery>;
+
 	/// Stores the `PoolAssetId` that is going to be used for the next lp token.
 	/// This gets incremented whenever a new lp pool is created.
 	#[pallet::storage]
@@ -334,6 +346,10 @@ pub mod pallet {
 		IncorrectPoolAssetId,
 		/// The destination account cannot exist with the swapped funds.
 		BelowMinimum,
+		/// The pool kind is not valid for the asset pair.
+		InvalidPoolKind,
+		/// The invariant or the reserves of the pool curve could not be computed.
+		CurveNotConverged,
 	}
 
 	#[pallet::hooks]
@@ -361,49 +377,7 @@ pub mod pallet {
 			asset2: Box<T::AssetKind>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			ensure!(asset1 != asset2, Error::<T>::InvalidAssetPair);
-
-			// prepare pool_id
-			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2)
-				.map_err(|_| Error::<T>::InvalidAssetPair)?;
-			ensure!(!Pools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
-
-			let pool_account =
-				T::PoolLocator::address(&pool_id).map_err(|_| Error::<T>::InvalidAssetPair)?;
-
-			// pay the setup fee
-			let fee =
-				Self::withdraw(T::PoolSetupFeeAsset::get(), &sender, T::PoolSetupFee::get(), true)?;
-			T::PoolSetupFeeTarget::on_unbalanced(fee);
-
-			if T::Assets::should_touch(*asset1.clone(), &pool_account) {
-				T::Assets::touch(*asset1, &pool_account, &sender)?
-			};
-
-			if T::Assets::should_touch(*asset2.clone(), &pool_account) {
-				T::Assets::touch(*asset2, &pool_account, &sender)?
-			};
-
-			let lp_token = NextPoolAssetId::<T>::get()
-				.or(T::PoolAssetId::initial_value())
-				.ok_or(Error::<T>::IncorrectPoolAssetId)?;
-			let next_lp_token_id = lp_token.increment().ok_or(Error::<T>::IncorrectPoolAssetId)?;
-			NextPoolAssetId::<T>::set(Some(next_lp_token_id));
-
-			T::PoolAssets::create(lp_token.clone(), pool_account.clone(), false, 1u32.into())?;
-			T::PoolAssets::touch(lp_token.clone(), &pool_account, &sender)?;
-
-			let pool_info = PoolInfo { lp_token: lp_token.clone() };
-			Pools::<T>::insert(pool_id.clone(), pool_info);
-
-			Self::deposit_event(Event::PoolCreated {
-				creator: sender,
-				pool_id,
-				pool_account,
-				lp_token,
-			});
-
-			Ok(())
+			Self::do_create_pool(sender, *asset1, *asset2, PoolKind::ConstantProduct)
 		}
 
 		/// Provide liquidity into the pool of `asset1` and `asset2`.
@@ -603,7 +577,11 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_exact_tokens_for_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32))]
+		#[pallet::weight(
+			T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32)
+				.max(T::WeightInfo::swap_exact_tokens_for_tokens_stable_swap(path.len() as u32))
+				.max(T::WeightInfo::swap_exact_tokens_for_tokens_weighted(path.len() as u32))
+		)]
 		pub fn swap_exact_tokens_for_tokens(
 			origin: OriginFor<T>,
 			path: Vec<Box<T::AssetKind>>,
@@ -631,7 +609,11 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_tokens_for_exact_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32))]
+		#[pallet::weight(
+			T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32)
+				.max(T::WeightInfo::swap_tokens_for_exact_tokens_stable_swap(path.len() as u32))
+				.max(T::WeightInfo::swap_tokens_for_exact_tokens_weighted(path.len() as u32))
+		)]
 		pub fn swap_tokens_for_exact_tokens(
 			origin: OriginFor<T>,
 			path: Vec<Box<T::AssetKind>>,
@@ -651,9 +633,82 @@ pub mod pallet {
 			)?;
 			Ok(())
 		}
+
+		/// Creates an empty liquidity pool of the given `kind`, which determines the curve pricing
+		/// the swaps against it, and an associated new `lp_token` asset.
+		///
+		/// See [`Pallet::create_pool`].
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::create_pool_with_kind())]
+		pub fn create_pool_with_kind(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+			kind: PoolKind<T::AssetKind>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_create_pool(sender, *asset1, *asset2, kind)
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Creates an empty liquidity pool of the given `kind` and an associated new `lp_token`
+		/// asset.
+		pub(crate) fn do_create_pool(
+			sender: T::AccountId,
+			asset1: T::AssetKind,
+			asset2: T::AssetKind,
+			kind: PoolKind<T::AssetKind>,
+		) -> DispatchResult {
+			ensure!(asset1 != asset2, Error::<T>::InvalidAssetPair);
+
+			// prepare pool_id
+			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2)
+				.map_err(|_| Error::<T>::InvalidAssetPair)?;
+			ensure!(!Pools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
+			ensure!(kind.is_valid_for(&asset1, &asset2), Error::<T>::InvalidPoolKind);
+
+			let pool_account =
+				T::PoolLocator::address(&pool_id).map_err(|_| Error::<T>::InvalidAssetPair)?;
+
+			// pay the setup fee
+			let fee =
+				Self::withdraw(T::PoolSetupFeeAsset::get(), &sender, T::PoolSetupFee::get(), true)?;
+			T::PoolSetupFeeTarget::on_unbalanced(fee);
+
+			if T::Assets::should_touch(asset1.clone(), &pool_account) {
+				T::Assets::touch(asset1, &pool_account, &sender)?
+			};
+
+			if T::Assets::should_touch(asset2.clone(), &pool_account) {
+				T::Assets::touch(asset2, &pool_account, &sender)?
+			};
+
+			let lp_token = NextPoolAssetId::<T>::get()
+				.or(T::PoolAssetId::initial_value())
+				.ok_or(Error::<T>::IncorrectPoolAssetId)?;
+			let next_lp_token_id = lp_token.increment().ok_or(Error::<T>::IncorrectPoolAssetId)?;
+			NextPoolAssetId::<T>::set(Some(next_lp_token_id));
+
+			T::PoolAssets::create(lp_token.clone(), pool_account.clone(), false, 1u32.into())?;
+			T::PoolAssets::touch(lp_token.clone(), &pool_account, &sender)?;
+
+			let pool_info = PoolInfo { lp_token: lp_token.clone() };
+			Pools::<T>::insert(pool_id.clone(), pool_info);
+			if kind != PoolKind::ConstantProduct {
+				PoolKinds::<T>::insert(pool_id.clone(), kind);
+			}
+
+			Self::deposit_event(Event::PoolCreated {
+				creator: sender,
+				pool_id,
+				pool_account,
+				lp_token,
+			});
+
+			Ok(())
+		}
+
 		/// Swap exactly `amount_in` of asset `path[0]` for asset `path[1]`.
 		/// If an `amount_out_min` is specified, it will return an error if it is unable to acquire
 		/// the amount desired.
@@ -994,8 +1049,16 @@ pub mod pallet {
 					},
 				};
 				let (reserve_in, reserve_out) = Self::get_reserves(asset1.clone(), asset2.clone())?;
+				let pool_kind = Self::get_pool_kind(asset1, &asset2)?;
 				balance_path.push((asset2, amount_in));
-				amount_in = Self::get_amount_in(&amount_in, &reserve_in, &reserve_out)?;
+				amount_in = Self::get_pool_amount_in(
+					&pool_kind,
+					asset1,
+					&amount_in,
+					&reserve_in,
+					&reserve_out,
+					true,
+				)?;
 			}
 			balance_path.reverse();
 
@@ -1020,8 +1083,17 @@ pub mod pallet {
 					},
 				};
 				let (reserve_in, reserve_out) = Self::get_reserves(asset1.clone(), asset2.clone())?;
+				let pool_kind = Self::get_pool_kind(&asset1, asset2)?;
+				let next_amount_out = Self::get_pool_amount_out(
+					&pool_kind,
+					&asset1,
+					&amount_out,
+					&reserve_in,
+					&reserve_out,
+					true,
+				)?;
 				balance_path.push((asset1, amount_out));
-				amount_out = Self::get_amount_out(&amount_out, &reserve_in, &reserve_out)?;
+				amount_out = next_amount_out;
 			}
 			Ok(balance_path)
 		}
@@ -1034,15 +1106,20 @@ pub mod pallet {
 			include_fee: bool,
 		) -> Option<T::Balance> {
 			let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
+			let pool_kind = Self::get_pool_kind(&asset1, &asset2).ok()?;
 
-			let balance1 = Self::get_balance(&pool_account, asset1);
+			let balance1 = Self::get_balance(&pool_account, asset1.clone());
 			let balance2 = Self::get_balance(&pool_account, asset2);
 			if !balance1.is_zero() {
-				if include_fee {
-					Self::get_amount_out(&amount, &balance1, &balance2).ok()
-				} else {
-					Self::quote(&amount, &balance1, &balance2).ok()
-				}
+				Self::get_pool_amount_out(
+					&pool_kind,
+					&asset1,
+					&amount,
+					&balance1,
+					&balance2,
+					include_fee,
+				)
+				.ok()
 			} else {
 				None
 			}
@@ -1056,20 +1133,125 @@ pub mod pallet {
 			include_fee: bool,
 		) -> Option<T::Balance> {
 			let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
+			let pool_kind = Self::get_pool_kind(&asset1, &asset2).ok()?;
 
-			let balance1 = Self::get_balance(&pool_account, asset1);
+			let balance1 = Self::get_balance(&pool_account, asset1.clone());
 			let balance2 = Self::get_balance(&pool_account, asset2);
 			if !balance1.is_zero() {
-				if include_fee {
-					Self::get_amount_in(&amount, &balance1, &balance2).ok()
-				} else {
-					Self::quote(&amount, &balance2, &balance1).ok()
-				}
+				Self::get_pool_amount_in(
+					&pool_kind,
+					&asset1,
+					&amount,
+					&balance1,
+					&balance2,
+					include_fee,
+				)
+				.ok()
 			} else {
 				None
 			}
 		}
 
+		/// Returns the kind of the pool of `asset1` and `asset2`.
+		pub fn get_pool_kind(
+			asset1: &T::AssetKind,
+			asset2: &T::AssetKind,
+		) -> Result<PoolKind<T::AssetKind>, Error<T>> {
+			let pool_id = T::PoolLocator::pool_id(asset1, asset2)
+				.map_err(|_| Error::<T>::InvalidAssetPair)?;
+			Ok(PoolKinds::<T>::get(pool_id))
+		}
+
+		/// Calculates amount out of a pool of the given `pool_kind`.
+		///
+		/// Given an input amount of `asset_in` and pair reserves, returns the maximum output
+		/// amount of the other asset. Without `include_fee`, the amount out is quoted at the
+		/// current price for [`PoolKind::ConstantProduct`] pools.
+		pub(crate) fn get_pool_amount_out(
+			pool_kind: &PoolKind<T::AssetKind>,
+			asset_in: &T::AssetKind,
+			amount_in: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+			include_fee: bool,
+		) -> Result<T::Balance, Error<T>> {
+			let fee = if include_fee { T::LPFee::get() } else { 0 };
+			match pool_kind {
+				PoolKind::ConstantProduct if include_fee =>
+					Self::get_amount_out(amount_in, reserve_in, reserve_out),
+				PoolKind::ConstantProduct => Self::quote(amount_in, reserve_in, reserve_out),
+				PoolKind::StableSwap { amplification } => Self::get_stable_swap_amount_out(
+					amount_in,
+					reserve_in,
+					reserve_out,
+					*amplification,
+					fee,
+				),
+				PoolKind::Weighted { asset, weight } => {
+					let (weight_in, weight_out) = Self::weights(asset == asset_in, weight);
+					Self::get_weighted_amount_out(
+						amount_in,
+						reserve_in,
+						reserve_out,
+						weight_in,
+						weight_out,
+						fee,
+					)
+				},
+			}
+		}
+
+		/// Calculates amount in of a pool of the given `pool_kind`.
+		///
+		/// Given an output amount of the other asset than `asset_in` and pair reserves, returns a
+		/// required input amount of `asset_in`. Without `include_fee`, the amount in is quoted at
+		/// the current price for [`PoolKind::ConstantProduct`] pools.
+		pub(crate) fn get_pool_amount_in(
+			pool_kind: &PoolKind<T::AssetKind>,
+			asset_in: &T::AssetKind,
+			amount_out: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+			include_fee: bool,
+		) -> Result<T::Balance, Error<T>> {
+			let fee = if include_fee { T::LPFee::get() } else { 0 };
+			match pool_kind {
+				PoolKind::ConstantProduct if include_fee =>
+					Self::get_amount_in(amount_out, reserve_in, reserve_out),
+				PoolKind::ConstantProduct => Self::quote(amount_out, reserve_out, reserve_in),
+				PoolKind::StableSwap { amplification } => Self::get_stable_swap_amount_in(
+					amount_out,
+					reserve_in,
+					reserve_out,
+					*amplification,
+					fee,
+				),
+				PoolKind::Weighted { asset, weight } => {
+					let (weight_in, weight_out) = Self::weights(asset == asset_in, weight);
+					Self::get_weighted_amount_in(
+						amount_out,
+						reserve_in,
+						reserve_out,
+						weight_in,
+						weight_out,
+						fee,
+					)
+				},
+			}
+		}
+
+		/// Returns the weights of the assets in and out of a [`PoolKind::Weighted`] pool, where
+		/// `weight` is the weight of the asset in if `weighted_in`, or of the asset out otherwise.
+		fn weights(weighted_in: bool, weight: &Percent) -> (u32, u32) {
+			let weight = weight.deconstruct() as u32;
+			let other_weight = Percent::max_value().deconstruct() as u32 - weight;
+			if weighted_in {
+				(weight, other_weight)
+			} else {
+				(other_weight, weight)
+			}
+		}
+
 		/// Calculates the optimal amount from the reserves.
 		pub fn quote(
 			amount: &T::Balance,
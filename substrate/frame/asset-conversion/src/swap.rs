@@ -112,6 +112,34 @@ pub trait SwapCredit<AccountId> {
 	) -> Result<(Self::Credit, Self::Credit), (Self::Credit, DispatchError)>;
 }
 
+/// Trait providing methods to quote the price of swaps between the various asset classes.
+pub trait QuotePrice {
+	/// Measure units of the asset classes for quoting.
+	type Balance: Balance;
+	/// Kind of assets that are going to be quoted.
+	type AssetKind;
+
+	/// Quote the amount of `asset2` that would be acquired for exactly `amount` of `asset1`.
+	///
+	/// Returns `None` if there is no pool for the pair or it lacks the liquidity for the swap.
+	fn quote_price_exact_tokens_for_tokens(
+		asset1: Self::AssetKind,
+		asset2: Self::AssetKind,
+		amount: Self::Balance,
+		include_fee: bool,
+	) -> Option<Self::Balance>;
+
+	/// Quote the amount of `asset1` that would be taken to acquire exactly `amount` of `asset2`.
+	///
+	/// Returns `None` if there is no pool for the pair or it lacks the liquidity for the swap.
+	fn quote_price_tokens_for_exact_tokens(
+		asset1: Self::AssetKind,
+		asset2: Self::AssetKind,
+		amount: Self::Balance,
+		include_fee: bool,
+	) -> Option<Self::Balance>;
+}
+
 impl<T: Config> Swap<T::AccountId> for Pallet<T> {
 	type Balance = T::Balance;
 	type AssetKind = T::AssetKind;
@@ -210,3 +238,26 @@ impl<T: Config> SwapCredit<T::AccountId> for Pallet<T> {
 		.map_err(|_| (Self::Credit::zero(credit_asset), DispatchError::Corruption))?
 	}
 }
+
+impl<T: Config> QuotePrice for Pallet<T> {
+	type Balance = T::Balance;
+	type AssetKind = T::AssetKind;
+
+	fn quote_price_exact_tokens_for_tokens(
+		asset1: Self::AssetKind,
+		asset2: Self::AssetKind,
+		amount: Self::Balance,
+		include_fee: bool,
+	) -> Option<Self::Balance> {
+		Self::quote_price_exact_tokens_for_tokens(asset1, asset2, amount, include_fee)
+	}
+
+	fn quote_price_tokens_for_exact_tokens(
+		asset1: Self::AssetKind,
+		asset2: Self::AssetKind,
+		amount: Self::Balance,
+		include_fee: bool,
+	) -> Option<Self::Balance> {
+		Self::quote_price_tokens_for_exact_tokens(asset1, asset2, amount, include_fee)
+	}
+}
@@ -27,7 +27,7 @@ use frame_support::{
 		Get,
 	},
 };
-use sp_arithmetic::Permill;
+use sp_arithmetic::{Percent, Permill};
 use sp_runtime::{DispatchError, TokenError};
 
 fn events() -> Vec<Event<Test>> {
@@ -2493,3 +2493,465 @@ fn swap_credit_invalid_path() {
 		assert_eq!(error, (expected_credit_in, Error::<Test>::InvalidPath.into()));
 	});
 }
+
+#[test]
+fn can_create_pool_with_kind() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+		let token_4 = NativeOrWithId::WithId(4);
+		let pool_id_1_2 = (token_1.clone(), token_2.clone());
+		let pool_id_1_3 = (token_1.clone(), token_3.clone());
+		let pool_id_1_4 = (token_1.clone(), token_4.clone());
+
+		create_tokens(user, vec![token_2.clone(), token_3.clone(), token_4.clone()]);
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 1000));
+
+		let stable_swap = PoolKind::StableSwap { amplification: 100 };
+		assert_ok!(AssetConversion::create_pool_with_kind(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			stable_swap.clone(),
+		));
+		let weighted =
+			PoolKind::Weighted { asset: token_3.clone(), weight: Percent::from_percent(80) };
+		assert_ok!(AssetConversion::create_pool_with_kind(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_3.clone()),
+			weighted.clone(),
+		));
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_4.clone())
+		));
+
+		assert_eq!(pools(), vec![pool_id_1_2.clone(), pool_id_1_3.clone(), pool_id_1_4.clone()]);
+		assert_eq!(PoolKinds::<Test>::get(&pool_id_1_2), stable_swap.clone());
+		assert_eq!(PoolKinds::<Test>::get(&pool_id_1_3), weighted.clone());
+		assert_eq!(PoolKinds::<Test>::get(&pool_id_1_4), PoolKind::ConstantProduct);
+		assert!(!PoolKinds::<Test>::contains_key(&pool_id_1_4));
+		assert_eq!(AssetConversion::get_pool_kind(&token_2, &token_1), Ok(stable_swap));
+		assert_eq!(AssetConversion::get_pool_kind(&token_1, &token_3), Ok(weighted));
+		let setup_fee = <<Test as Config>::PoolSetupFee as Get<<Test as Config>::Balance>>::get();
+		let asset_account_deposit: u128 =
+			<mock::Test as pallet_assets::Config<Instance1>>::AssetAccountDeposit::get();
+		assert_eq!(
+			balance(user, NativeOrWithId::Native),
+			1000 - (setup_fee + asset_account_deposit) * 3
+		);
+
+		assert_noop!(
+			AssetConversion::create_pool_with_kind(
+				RuntimeOrigin::signed(user),
+				Box::new(token_2.clone()),
+				Box::new(token_1.clone()),
+				PoolKind::StableSwap { amplification: 50 },
+			),
+			Error::<Test>::PoolExists
+		);
+	});
+}
+
+#[test]
+fn create_pool_with_invalid_kind_should_fail() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+
+		create_tokens(user, vec![token_2.clone(), token_3.clone()]);
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 1000));
+
+		for kind in [
+			PoolKind::StableSwap { amplification: 0 },
+			PoolKind::StableSwap { amplification: MAX_AMPLIFICATION + 1 },
+			PoolKind::Weighted { asset: token_3.clone(), weight: Percent::from_percent(50) },
+			PoolKind::Weighted { asset: token_2.clone(), weight: Percent::from_percent(0) },
+			PoolKind::Weighted { asset: token_2.clone(), weight: Percent::from_percent(100) },
+		] {
+			assert_noop!(
+				AssetConversion::create_pool_with_kind(
+					RuntimeOrigin::signed(user),
+					Box::new(token_1.clone()),
+					Box::new(token_2.clone()),
+					kind,
+				),
+				Error::<Test>::InvalidPoolKind
+			);
+		}
+
+		assert_ok!(AssetConversion::create_pool_with_kind(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			PoolKind::StableSwap { amplification: MAX_AMPLIFICATION },
+		));
+	});
+}
+
+#[test]
+fn can_swap_in_stable_swap_pool() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+		let amplification = 100;
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool_with_kind(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			PoolKind::StableSwap { amplification },
+		));
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 20000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 20000));
+
+		let liquidity1 = 10000;
+		let liquidity2 = 10000;
+
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			liquidity1,
+			liquidity2,
+			1,
+			1,
+			user,
+		));
+
+		let input_amount = 1000;
+		let expect_receive = AssetConversion::get_stable_swap_amount_out(
+			&input_amount,
+			&liquidity2,
+			&liquidity1,
+			amplification,
+			<Test as Config>::LPFee::get(),
+		)
+		.unwrap();
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(
+				token_2.clone(),
+				token_1.clone(),
+				input_amount,
+				true,
+			),
+			Some(expect_receive)
+		);
+		// near the parity the stable swap curve gives a better rate than the constant product
+		assert!(
+			expect_receive >
+				AssetConversion::get_amount_out(&input_amount, &liquidity2, &liquidity1).unwrap()
+		);
+
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_2.clone(), token_1.clone()],
+			input_amount,
+			expect_receive,
+			user,
+			false,
+		));
+
+		let pallet_account = <Test as Config>::PoolLocator::address(&pool_id).unwrap();
+		assert_eq!(balance(user, token_1.clone()), 10000 + expect_receive + ed);
+		assert_eq!(balance(user, token_2.clone()), 10000 - input_amount);
+		assert_eq!(balance(pallet_account, token_1.clone()), liquidity1 - expect_receive);
+		assert_eq!(balance(pallet_account, token_2.clone()), liquidity2 + input_amount);
+
+		let reserve1 = liquidity1 - expect_receive;
+		let reserve2 = liquidity2 + input_amount;
+		let output_amount = 500;
+		let expect_in = AssetConversion::get_stable_swap_amount_in(
+			&output_amount,
+			&reserve1,
+			&reserve2,
+			amplification,
+			<Test as Config>::LPFee::get(),
+		)
+		.unwrap();
+		assert_eq!(
+			AssetConversion::quote_price_tokens_for_exact_tokens(
+				token_1.clone(),
+				token_2.clone(),
+				output_amount,
+				true,
+			),
+			Some(expect_in)
+		);
+
+		assert_ok!(AssetConversion::swap_tokens_for_exact_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1.clone(), token_2.clone()],
+			output_amount,
+			expect_in,
+			user,
+			false,
+		));
+
+		assert_eq!(balance(user, token_1.clone()), 10000 + expect_receive + ed - expect_in);
+		assert_eq!(balance(user, token_2.clone()), 10000 - input_amount + output_amount);
+		assert_eq!(balance(pallet_account, token_1.clone()), reserve1 + expect_in);
+		assert_eq!(balance(pallet_account, token_2.clone()), reserve2 - output_amount);
+	});
+}
+
+#[test]
+fn can_swap_in_weighted_pool() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+		// the native asset weighs 80% of the pool
+		let (weight1, weight2) = (80, 20);
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool_with_kind(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			PoolKind::Weighted {
+				asset: token_1.clone(),
+				weight: Percent::from_percent(weight1 as u8)
+			},
+		));
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 20000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 20000));
+
+		let liquidity1 = 10000;
+		let liquidity2 = 10000;
+
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			liquidity1,
+			liquidity2,
+			1,
+			1,
+			user,
+		));
+
+		let input_amount = 1000;
+		let expect_receive = AssetConversion::get_weighted_amount_out(
+			&input_amount,
+			&liquidity1,
+			&liquidity2,
+			weight1,
+			weight2,
+			<Test as Config>::LPFee::get(),
+		)
+		.unwrap();
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(
+				token_1.clone(),
+				token_2.clone(),
+				input_amount,
+				true,
+			),
+			Some(expect_receive)
+		);
+		// the heavier asset in buys more of the lighter asset than in a constant product pool
+		assert!(
+			expect_receive >
+				AssetConversion::get_amount_out(&input_amount, &liquidity1, &liquidity2).unwrap()
+		);
+
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1.clone(), token_2.clone()],
+			input_amount,
+			expect_receive,
+			user,
+			false,
+		));
+
+		let pallet_account = <Test as Config>::PoolLocator::address(&pool_id).unwrap();
+		assert_eq!(balance(user, token_1.clone()), 10000 + ed - input_amount);
+		assert_eq!(balance(user, token_2.clone()), 10000 + expect_receive);
+		assert_eq!(balance(pallet_account, token_1.clone()), liquidity1 + input_amount);
+		assert_eq!(balance(pallet_account, token_2.clone()), liquidity2 - expect_receive);
+
+		let reserve1 = liquidity1 + input_amount;
+		let reserve2 = liquidity2 - expect_receive;
+		let output_amount = 500;
+		let expect_in = AssetConversion::get_weighted_amount_in(
+			&output_amount,
+			&reserve2,
+			&reserve1,
+			weight2,
+			weight1,
+			<Test as Config>::LPFee::get(),
+		)
+		.unwrap();
+		assert_eq!(
+			AssetConversion::quote_price_tokens_for_exact_tokens(
+				token_2.clone(),
+				token_1.clone(),
+				output_amount,
+				true,
+			),
+			Some(expect_in)
+		);
+
+		assert_ok!(AssetConversion::swap_tokens_for_exact_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_2.clone(), token_1.clone()],
+			output_amount,
+			expect_in,
+			user,
+			false,
+		));
+
+		assert_eq!(balance(user, token_1.clone()), 10000 + ed - input_amount + output_amount);
+		assert_eq!(balance(user, token_2.clone()), 10000 + expect_receive - expect_in);
+		assert_eq!(balance(pallet_account, token_1.clone()), reserve1 - output_amount);
+		assert_eq!(balance(pallet_account, token_2.clone()), reserve2 + expect_in);
+	});
+}
+
+#[test]
+fn swap_in_multi_hops_across_pool_kinds() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+		let amplification = 200;
+
+		create_tokens(user, vec![token_2.clone(), token_3.clone()]);
+		assert_ok!(AssetConversion::create_pool_with_kind(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			PoolKind::StableSwap { amplification },
+		));
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_2.clone()),
+			Box::new(token_3.clone())
+		));
+
+		let ed = get_native_ed();
+		let base1 = 10000;
+		let base2 = 10000;
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, base1 * 2 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, base2 * 2));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 3, user, base2));
+
+		let liquidity1 = 10000;
+		let liquidity2 = 9000;
+		let liquidity3 = 2000;
+
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			liquidity1,
+			liquidity2,
+			1,
+			1,
+			user,
+		));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_2.clone()),
+			Box::new(token_3.clone()),
+			liquidity2,
+			liquidity3,
+			1,
+			1,
+			user,
+		));
+
+		let input_amount = 500;
+		let expect_out2 = AssetConversion::get_stable_swap_amount_out(
+			&input_amount,
+			&liquidity1,
+			&liquidity2,
+			amplification,
+			<Test as Config>::LPFee::get(),
+		)
+		.unwrap();
+		let expect_out3 =
+			AssetConversion::get_amount_out(&expect_out2, &liquidity2, &liquidity3).unwrap();
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(
+				token_1.clone(),
+				token_2.clone(),
+				input_amount,
+				true,
+			),
+			Some(expect_out2)
+		);
+
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1.clone(), token_2.clone(), token_3.clone()],
+			input_amount,
+			expect_out3,
+			user,
+			true,
+		));
+
+		let pool_id1 = (token_1.clone(), token_2.clone());
+		let pool_id2 = (token_2.clone(), token_3.clone());
+		let pallet_account1 = <Test as Config>::PoolLocator::address(&pool_id1).unwrap();
+		let pallet_account2 = <Test as Config>::PoolLocator::address(&pool_id2).unwrap();
+
+		assert_eq!(balance(user, token_1.clone()), base1 + ed - input_amount);
+		assert_eq!(balance(pallet_account1, token_1.clone()), liquidity1 + input_amount);
+		assert_eq!(balance(pallet_account1, token_2.clone()), liquidity2 - expect_out2);
+		assert_eq!(balance(pallet_account2, token_2.clone()), liquidity2 + expect_out2);
+		assert_eq!(balance(pallet_account2, token_3.clone()), liquidity3 - expect_out3);
+		assert_eq!(balance(user, token_3.clone()), base2 - liquidity3 + expect_out3);
+
+		let reserve1 = liquidity1 + input_amount;
+		let reserve2 = liquidity2 - expect_out2;
+		let output_amount = 100;
+		let expect_in2 = AssetConversion::get_amount_in(
+			&output_amount,
+			&(liquidity2 + expect_out2),
+			&(liquidity3 - expect_out3),
+		)
+		.unwrap();
+		let expect_in1 = AssetConversion::get_stable_swap_amount_in(
+			&expect_in2,
+			&reserve1,
+			&reserve2,
+			amplification,
+			<Test as Config>::LPFee::get(),
+		)
+		.unwrap();
+
+		assert_ok!(AssetConversion::swap_tokens_for_exact_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1.clone(), token_2.clone(), token_3.clone()],
+			output_amount,
+			expect_in1,
+			user,
+			true,
+		));
+
+		assert_eq!(balance(user, token_1.clone()), base1 + ed - input_amount - expect_in1);
+		assert_eq!(balance(pallet_account1, token_1.clone()), reserve1 + expect_in1);
+		assert_eq!(balance(pallet_account1, token_2.clone()), reserve2 - expect_in2);
+		assert_eq!(
+			balance(user, token_3.clone()),
+			base2 - liquidity3 + expect_out3 + output_amount
+		);
+	});
+}
@@ -17,7 +17,10 @@
 
 use super::*;
 use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::DefaultNoBound;
 use scale_info::TypeInfo;
+use sp_arithmetic::{traits::Bounded, Percent};
+use sp_runtime::RuntimeDebug;
 use sp_std::marker::PhantomData;
 
 /// Represents a swap path with associated asset amounts indicating how much of the asset needs to
@@ -40,6 +43,51 @@ pub struct PoolInfo<PoolAssetId> {
 	pub lp_token: PoolAssetId,
 }
 
+/// The maximum amplification coefficient of a [`PoolKind::StableSwap`] pool.
+pub const MAX_AMPLIFICATION: u32 = 1_000_000;
+
+/// The curve of a pool, which determines the prices of the swaps against it.
+///
+/// Adding and removing liquidity is proportional to the reserves for all kinds of pools.
+#[derive(
+	Decode, Encode, DefaultNoBound, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub enum PoolKind<AssetKind> {
+	/// The constant product curve `x * y = k` of Uniswap V2.
+	#[default]
+	ConstantProduct,
+	/// The StableSwap curve, which is close to a constant sum `x + y = k` around balanced
+	/// reserves and suits assets expected to trade at parity, like two stablecoins.
+	StableSwap {
+		/// The amplification coefficient. The higher it is, the flatter the curve is around
+		/// balanced reserves.
+		amplification: u32,
+	},
+	/// The weighted product curve `x^w_x * y^w_y = k` of Balancer, for pools in which the assets
+	/// are not equally weighted.
+	Weighted {
+		/// One of the assets of the pool.
+		asset: AssetKind,
+		/// The weight of `asset`, the other asset of the pool has the remaining weight.
+		weight: Percent,
+	},
+}
+
+impl<AssetKind: PartialEq> PoolKind<AssetKind> {
+	/// Whether a pool of this kind can be created for the pair of `asset1` and `asset2`.
+	pub fn is_valid_for(&self, asset1: &AssetKind, asset2: &AssetKind) -> bool {
+		match self {
+			PoolKind::ConstantProduct => true,
+			PoolKind::StableSwap { amplification } =>
+				(1..=MAX_AMPLIFICATION).contains(amplification),
+			PoolKind::Weighted { asset, weight } =>
+				(asset == asset1 || asset == asset2) &&
+					*weight > Percent::zero() &&
+					*weight < Percent::max_value(),
+		}
+	}
+}
+
 /// Provides means to resolve the `PoolId` and `AccountId` from a pair of assets.
 ///
 /// Resulting `PoolId` remains consistent whether the asset pair is presented as (asset1, asset2)
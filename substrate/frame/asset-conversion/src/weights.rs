@@ -22,6 +22,12 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `cob`, CPU: `<UNKNOWN>`
 //! WASM-EXECUTION: `Compiled`, CHAIN: `Some("dev")`, DB CACHE: `1024`
+//!
+//! NOTE: the weights of `create_pool_with_kind` and of the `*_stable_swap` and `*_weighted`
+//! swaps were NOT generated by the benchmark CLI. They are hand-written placeholders derived from
+//! the benchmarked `create_pool` and constant product swaps of this file, with an estimated upper
+//! bound of the curve math of each hop added to the swaps. They must be regenerated with
+//! `benchmark pallet` before this file is merged.
 
 // Executed Command:
 // ./target/debug/substrate-node
@@ -52,6 +58,11 @@ pub trait WeightInfo {
 	fn remove_liquidity() -> Weight;
 	fn swap_exact_tokens_for_tokens(n: u32, ) -> Weight;
 	fn swap_tokens_for_exact_tokens(n: u32, ) -> Weight;
+	fn create_pool_with_kind() -> Weight;
+	fn swap_exact_tokens_for_tokens_stable_swap(n: u32, ) -> Weight;
+	fn swap_tokens_for_exact_tokens_stable_swap(n: u32, ) -> Weight;
+	fn swap_exact_tokens_for_tokens_weighted(n: u32, ) -> Weight;
+	fn swap_tokens_for_exact_tokens_weighted(n: u32, ) -> Weight;
 }
 
 /// Weights for `pallet_asset_conversion` using the Substrate node and recommended hardware.
@@ -152,6 +163,77 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 5218).saturating_mul(n.into()))
 	}
+	/// PLACEHOLDER, not generated by the benchmark CLI: the weight of `create_pool` plus the
+	/// write of the `AssetConversion::PoolKinds` entry. Regenerate before merging.
+	fn create_pool_with_kind() -> Weight {
+		Weight::from_parts(1_668_000_000, 6360)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+	/// PLACEHOLDER, not generated by the benchmark CLI: the weight of
+	/// `swap_exact_tokens_for_tokens` plus one `AssetConversion::PoolKinds` read per hop, and a
+	/// per hop bound of the curve math of 2 * 255 Newton iterations, the most the two StableSwap
+	/// loops may run, at an estimated 1µs each. Regenerate before merging.
+	/// The range of component `n` is the one of `swap_exact_tokens_for_tokens`.
+	fn swap_exact_tokens_for_tokens_stable_swap(n: u32, ) -> Weight {
+		Weight::from_parts(941_000_000, 990)
+			.saturating_add(Weight::from_parts(205_862_068, 0).saturating_mul(n.into()))
+			// Curve math bound per hop.
+			.saturating_add(Weight::from_parts(510_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5218).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2508).saturating_mul(n.into()))
+	}
+	/// PLACEHOLDER, not generated by the benchmark CLI: the weight of
+	/// `swap_tokens_for_exact_tokens` plus one `AssetConversion::PoolKinds` read per hop, and a
+	/// per hop bound of the curve math of 2 * 255 Newton iterations, the most the two StableSwap
+	/// loops may run, at an estimated 1µs each. Regenerate before merging.
+	/// The range of component `n` is the one of `swap_tokens_for_exact_tokens`.
+	fn swap_tokens_for_exact_tokens_stable_swap(n: u32, ) -> Weight {
+		Weight::from_parts(947_000_000, 990)
+			.saturating_add(Weight::from_parts(218_275_862, 0).saturating_mul(n.into()))
+			// Curve math bound per hop.
+			.saturating_add(Weight::from_parts(510_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5218).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2508).saturating_mul(n.into()))
+	}
+	/// PLACEHOLDER, not generated by the benchmark CLI: the weight of
+	/// `swap_exact_tokens_for_tokens` plus one `AssetConversion::PoolKinds` read per hop, and a
+	/// per hop bound of the curve math of a 64 step binary search, each raising to a power of at
+	/// most 99, at an estimated 4µs per step. Regenerate before merging.
+	/// The range of component `n` is the one of `swap_exact_tokens_for_tokens`.
+	fn swap_exact_tokens_for_tokens_weighted(n: u32, ) -> Weight {
+		Weight::from_parts(941_000_000, 990)
+			.saturating_add(Weight::from_parts(205_862_068, 0).saturating_mul(n.into()))
+			// Curve math bound per hop.
+			.saturating_add(Weight::from_parts(256_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5218).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2508).saturating_mul(n.into()))
+	}
+	/// PLACEHOLDER, not generated by the benchmark CLI: the weight of
+	/// `swap_tokens_for_exact_tokens` plus one `AssetConversion::PoolKinds` read per hop, and a
+	/// per hop bound of the curve math of a 64 step binary search, each raising to a power of at
+	/// most 99, at an estimated 4µs per step. Regenerate before merging.
+	/// The range of component `n` is the one of `swap_tokens_for_exact_tokens`.
+	fn swap_tokens_for_exact_tokens_weighted(n: u32, ) -> Weight {
+		Weight::from_parts(947_000_000, 990)
+			.saturating_add(Weight::from_parts(218_275_862, 0).saturating_mul(n.into()))
+			// Curve math bound per hop.
+			.saturating_add(Weight::from_parts(256_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5218).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2508).saturating_mul(n.into()))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -251,4 +333,75 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 5218).saturating_mul(n.into()))
 	}
+	/// PLACEHOLDER, not generated by the benchmark CLI: the weight of `create_pool` plus the
+	/// write of the `AssetConversion::PoolKinds` entry. Regenerate before merging.
+	fn create_pool_with_kind() -> Weight {
+		Weight::from_parts(1_668_000_000, 6360)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+	/// PLACEHOLDER, not generated by the benchmark CLI: the weight of
+	/// `swap_exact_tokens_for_tokens` plus one `AssetConversion::PoolKinds` read per hop, and a
+	/// per hop bound of the curve math of 2 * 255 Newton iterations, the most the two StableSwap
+	/// loops may run, at an estimated 1µs each. Regenerate before merging.
+	/// The range of component `n` is the one of `swap_exact_tokens_for_tokens`.
+	fn swap_exact_tokens_for_tokens_stable_swap(n: u32, ) -> Weight {
+		Weight::from_parts(941_000_000, 990)
+			.saturating_add(Weight::from_parts(205_862_068, 0).saturating_mul(n.into()))
+			// Curve math bound per hop.
+			.saturating_add(Weight::from_parts(510_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5218).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2508).saturating_mul(n.into()))
+	}
+	/// PLACEHOLDER, not generated by the benchmark CLI: the weight of
+	/// `swap_tokens_for_exact_tokens` plus one `AssetConversion::PoolKinds` read per hop, and a
+	/// per hop bound of the curve math of 2 * 255 Newton iterations, the most the two StableSwap
+	/// loops may run, at an estimated 1µs each. Regenerate before merging.
+	/// The range of component `n` is the one of `swap_tokens_for_exact_tokens`.
+	fn swap_tokens_for_exact_tokens_stable_swap(n: u32, ) -> Weight {
+		Weight::from_parts(947_000_000, 990)
+			.saturating_add(Weight::from_parts(218_275_862, 0).saturating_mul(n.into()))
+			// Curve math bound per hop.
+			.saturating_add(Weight::from_parts(510_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5218).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2508).saturating_mul(n.into()))
+	}
+	/// PLACEHOLDER, not generated by the benchmark CLI: the weight of
+	/// `swap_exact_tokens_for_tokens` plus one `AssetConversion::PoolKinds` read per hop, and a
+	/// per hop bound of the curve math of a 64 step binary search, each raising to a power of at
+	/// most 99, at an estimated 4µs per step. Regenerate before merging.
+	/// The range of component `n` is the one of `swap_exact_tokens_for_tokens`.
+	fn swap_exact_tokens_for_tokens_weighted(n: u32, ) -> Weight {
+		Weight::from_parts(941_000_000, 990)
+			.saturating_add(Weight::from_parts(205_862_068, 0).saturating_mul(n.into()))
+			// Curve math bound per hop.
+			.saturating_add(Weight::from_parts(256_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5218).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2508).saturating_mul(n.into()))
+	}
+	/// PLACEHOLDER, not generated by the benchmark CLI: the weight of
+	/// `swap_tokens_for_exact_tokens` plus one `AssetConversion::PoolKinds` read per hop, and a
+	/// per hop bound of the curve math of a 64 step binary search, each raising to a power of at
+	/// most 99, at an estimated 4µs per step. Regenerate before merging.
+	/// The range of component `n` is the one of `swap_tokens_for_exact_tokens`.
+	fn swap_tokens_for_exact_tokens_weighted(n: u32, ) -> Weight {
+		Weight::from_parts(947_000_000, 990)
+			.saturating_add(Weight::from_parts(218_275_862, 0).saturating_mul(n.into()))
+			// Curve math bound per hop.
+			.saturating_add(Weight::from_parts(256_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5218).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2508).saturating_mul(n.into()))
+	}
 }
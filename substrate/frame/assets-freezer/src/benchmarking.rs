@@ -0,0 +1,130 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Assets freezer pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as AssetsFreezer;
+use pallet_assets::Pallet as Assets;
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::{
+	fungibles::{Create, InspectFreeze, InspectHold, Mutate, MutateFreeze, MutateHold},
+	tokens::Precision,
+};
+use pallet_assets::BenchmarkHelper as _;
+
+/// Create an asset and mint `amount` of it into the account of the whitelisted caller.
+fn create_minted_asset<T: Config<I>, I: 'static>(amount: T::Balance) -> (T::AssetId, T::AccountId) {
+	let asset: T::AssetId =
+		<T as pallet_assets::Config<I>>::BenchmarkHelper::create_asset_id_parameter(0).into();
+	let caller: T::AccountId = whitelisted_caller();
+	assert!(<Assets<T, I> as Create<T::AccountId>>::create(
+		asset.clone(),
+		caller.clone(),
+		true,
+		1u32.into()
+	)
+	.is_ok());
+	assert!(
+		<Assets<T, I> as Mutate<T::AccountId>>::mint_into(asset.clone(), &caller, amount).is_ok()
+	);
+	(asset, caller)
+}
+
+#[instance_benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_freeze() {
+		let (asset, caller) = create_minted_asset::<T, I>(100u32.into());
+		let reason = <T as Config<I>>::BenchmarkHelper::freeze_reason();
+
+		#[block]
+		{
+			assert!(AssetsFreezer::<T, I>::set_freeze(
+				asset.clone(),
+				&reason,
+				&caller,
+				50u32.into()
+			)
+			.is_ok());
+		}
+
+		assert_eq!(AssetsFreezer::<T, I>::balance_frozen(asset, &reason, &caller), 50u32.into());
+	}
+
+	#[benchmark]
+	fn thaw() {
+		let (asset, caller) = create_minted_asset::<T, I>(100u32.into());
+		let reason = <T as Config<I>>::BenchmarkHelper::freeze_reason();
+		assert!(AssetsFreezer::<T, I>::set_freeze(asset.clone(), &reason, &caller, 50u32.into())
+			.is_ok());
+
+		#[block]
+		{
+			assert!(AssetsFreezer::<T, I>::thaw(asset.clone(), &reason, &caller).is_ok());
+		}
+
+		assert!(FrozenBalances::<T, I>::get(asset, &caller).is_none());
+	}
+
+	#[benchmark]
+	fn hold() {
+		let (asset, caller) = create_minted_asset::<T, I>(100u32.into());
+		let reason = <T as Config<I>>::BenchmarkHelper::hold_reason();
+
+		#[block]
+		{
+			assert!(
+				AssetsFreezer::<T, I>::hold(asset.clone(), &reason, &caller, 50u32.into()).is_ok()
+			);
+		}
+
+		assert_eq!(AssetsFreezer::<T, I>::balance_on_hold(asset, &reason, &caller), 50u32.into());
+	}
+
+	#[benchmark]
+	fn release() {
+		let (asset, caller) = create_minted_asset::<T, I>(100u32.into());
+		let reason = <T as Config<I>>::BenchmarkHelper::hold_reason();
+		assert!(AssetsFreezer::<T, I>::hold(asset.clone(), &reason, &caller, 50u32.into()).is_ok());
+
+		#[block]
+		{
+			assert!(AssetsFreezer::<T, I>::release(
+				asset.clone(),
+				&reason,
+				&caller,
+				50u32.into(),
+				Precision::Exact
+			)
+			.is_ok());
+		}
+
+		assert!(BalancesOnHold::<T, I>::get(asset, &caller).is_none());
+	}
+
+	impl_benchmark_test_suite! {
+		AssetsFreezer,
+		crate::mock::new_test_ext(),
+		crate::mock::Test,
+	}
+}
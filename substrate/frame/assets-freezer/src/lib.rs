@@ -0,0 +1,340 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Assets Freezer Pallet
+//!
+//! A pallet capable of freezing and holding fungibles from `pallet-assets`.
+//!
+//! ## Pallet API
+//!
+//! See the [`pallet`] module for more information about the interfaces this pallet exposes,
+//! including its configuration trait, dispatchables, storage items, events and errors.
+//!
+//! ## Overview
+//!
+//! This pallet extends an instance of `pallet-assets` with freezes and holds, keyed by the
+//! runtime's [`Config::RuntimeFreezeReason`] and [`Config::RuntimeHoldReason`] respectively. Both
+//! are stored in this pallet, outside of the `Account` record of `pallet-assets`, so enabling them
+//! requires no migration of existing asset accounts.
+//!
+//! - A freeze prevents the frozen amount from being transferred. The largest freeze of an account
+//!   is reported to `pallet-assets` through [`FrozenBalance`], which takes it into account when
+//!   computing the reducible balance of the account.
+//! - A hold moves funds out of the `pallet-assets` balance of an account and into this pallet. The
+//!   total held is reported to `pallet-assets` through [`BalanceOnHold`], so that it counts towards
+//!   the total balance of the account and prevents the account from being reaped.
+//!
+//! As in `pallet-balances`, held funds count towards the frozen amount of an account.
+//!
+//! This pallet must be configured as both the `Freezer` and the `Holder` of the `pallet-assets`
+//! instance it extends. It then implements the following traits for that instance:
+//!
+//! - [`InspectFreeze`](frame_support::traits::fungibles::InspectFreeze) and
+//!   [`MutateFreeze`](frame_support::traits::fungibles::MutateFreeze).
+//! - [`InspectHold`](frame_support::traits::fungibles::InspectHold),
+//!   [`UnbalancedHold`](frame_support::traits::fungibles::UnbalancedHold) and
+//!   [`MutateHold`](frame_support::traits::fungibles::MutateHold).
+//!
+//! It also implements [`Inspect`](frame_support::traits::fungibles::Inspect) and
+//! [`Unbalanced`](frame_support::traits::fungibles::Unbalanced) by delegating to `pallet-assets`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{VariantCount, VariantCountOf},
+	BoundedSlice,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_assets::{BalanceOnHold, FrozenBalance};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{CheckedAdd, Zero},
+	ArithmeticError, RuntimeDebug,
+};
+
+#[cfg(any(test, feature = "try-runtime"))]
+use sp_runtime::TryRuntimeError;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod impls;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+/// An identifier and balance.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct IdAmount<Id, Balance> {
+	/// An identifier for this item.
+	pub id: Id,
+	/// Some amount for this item.
+	pub amount: Balance,
+}
+
+/// The freezes of an asset account.
+pub type FreezesOf<T, I> = BoundedVec<
+	IdAmount<<T as Config<I>>::RuntimeFreezeReason, <T as pallet_assets::Config<I>>::Balance>,
+	VariantCountOf<<T as Config<I>>::RuntimeFreezeReason>,
+>;
+
+/// The holds of an asset account.
+pub type HoldsOf<T, I> = BoundedVec<
+	IdAmount<<T as Config<I>>::RuntimeHoldReason, <T as pallet_assets::Config<I>>::Balance>,
+	VariantCountOf<<T as Config<I>>::RuntimeHoldReason>,
+>;
+
+/// Helper trait for benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<FreezeReason, HoldReason> {
+	/// Returns a freeze reason to benchmark with.
+	fn freeze_reason() -> FreezeReason;
+	/// Returns a hold reason to benchmark with.
+	fn hold_reason() -> HoldReason;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config + pallet_assets::Config<I> {
+		/// The overarching freeze reason.
+		type RuntimeFreezeReason: Parameter + Member + MaxEncodedLen + Copy + VariantCount;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: Parameter + Member + MaxEncodedLen + Copy + VariantCount;
+
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Weight information for the freeze and hold operations of this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Helper trait for benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::RuntimeFreezeReason, Self::RuntimeHoldReason>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// Number of freezes on an account would exceed the number of freeze reasons.
+		TooManyFreezes,
+		/// Number of holds on an account would exceed the number of hold reasons.
+		TooManyHolds,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// Some amount of `asset_id` was frozen in the account of `who`.
+		Frozen { who: T::AccountId, asset_id: T::AssetId, amount: T::Balance },
+		/// Some amount of `asset_id` was thawed in the account of `who`.
+		Thawed { who: T::AccountId, asset_id: T::AssetId, amount: T::Balance },
+		/// Some amount of `asset_id` was placed on hold in the account of `who`.
+		Held {
+			who: T::AccountId,
+			asset_id: T::AssetId,
+			reason: T::RuntimeHoldReason,
+			amount: T::Balance,
+		},
+		/// Some amount of `asset_id` was released from hold in the account of `who`.
+		Released {
+			who: T::AccountId,
+			asset_id: T::AssetId,
+			reason: T::RuntimeHoldReason,
+			amount: T::Balance,
+		},
+		/// Some amount of `asset_id` on hold in the account of `who` was burned.
+		BurnedHeld {
+			who: T::AccountId,
+			asset_id: T::AssetId,
+			reason: T::RuntimeHoldReason,
+			amount: T::Balance,
+		},
+	}
+
+	/// A map that stores freezes applied on an account for a given asset.
+	#[pallet::storage]
+	pub type Freezes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		FreezesOf<T, I>,
+		ValueQuery,
+	>;
+
+	/// A map that stores the current total frozen balance for every account on a given asset.
+	///
+	/// This is the largest of the [`Freezes`] of the account.
+	#[pallet::storage]
+	pub type FrozenBalances<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		T::Balance,
+	>;
+
+	/// A map that stores holds applied on an account for a given asset.
+	#[pallet::storage]
+	pub type Holds<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		HoldsOf<T, I>,
+		ValueQuery,
+	>;
+
+	/// A map that stores the current total balance on hold for every account on a given asset.
+	///
+	/// This is the sum of the [`Holds`] of the account.
+	#[pallet::storage]
+	pub type BalancesOnHold<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		T::Balance,
+	>;
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+			Self::do_try_state()
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Store the given `freezes` of `who` and update its frozen balance accordingly.
+	fn update_freezes(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		freezes: BoundedSlice<
+			IdAmount<T::RuntimeFreezeReason, T::Balance>,
+			VariantCountOf<T::RuntimeFreezeReason>,
+		>,
+	) -> DispatchResult {
+		let prev_frozen = FrozenBalances::<T, I>::get(asset.clone(), who).unwrap_or_default();
+		let mut after_frozen: T::Balance = Zero::zero();
+		for f in freezes.iter() {
+			after_frozen = after_frozen.max(f.amount);
+		}
+		if freezes.is_empty() {
+			Freezes::<T, I>::remove(asset.clone(), who);
+			FrozenBalances::<T, I>::remove(asset.clone(), who);
+		} else {
+			Freezes::<T, I>::insert(asset.clone(), who, freezes);
+			FrozenBalances::<T, I>::insert(asset.clone(), who, after_frozen);
+		}
+		if prev_frozen > after_frozen {
+			let amount = prev_frozen.saturating_sub(after_frozen);
+			Self::deposit_event(Event::Thawed { asset_id: asset, who: who.clone(), amount });
+		} else if after_frozen > prev_frozen {
+			let amount = after_frozen.saturating_sub(prev_frozen);
+			Self::deposit_event(Event::Frozen { asset_id: asset, who: who.clone(), amount });
+		}
+		Ok(())
+	}
+
+	/// Store the given `holds` of `who` and update its balance on hold accordingly.
+	fn update_holds(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		holds: BoundedSlice<
+			IdAmount<T::RuntimeHoldReason, T::Balance>,
+			VariantCountOf<T::RuntimeHoldReason>,
+		>,
+	) -> DispatchResult {
+		let mut total_held: T::Balance = Zero::zero();
+		for h in holds.iter() {
+			total_held = total_held.checked_add(&h.amount).ok_or(ArithmeticError::Overflow)?;
+		}
+		if holds.is_empty() {
+			Holds::<T, I>::remove(asset.clone(), who);
+			BalancesOnHold::<T, I>::remove(asset, who);
+		} else {
+			Holds::<T, I>::insert(asset.clone(), who, holds);
+			BalancesOnHold::<T, I>::insert(asset, who, total_held);
+		}
+		Ok(())
+	}
+
+	#[cfg(any(test, feature = "try-runtime"))]
+	fn do_try_state() -> Result<(), TryRuntimeError> {
+		for (asset, who, _) in FrozenBalances::<T, I>::iter() {
+			let max_frozen_amount =
+				Freezes::<T, I>::get(asset.clone(), who.clone()).iter().map(|l| l.amount).max();
+
+			frame_support::ensure!(
+				FrozenBalances::<T, I>::get(asset.clone(), who.clone()) == max_frozen_amount,
+				"The `FrozenBalances` is not equal to the maximum amount in `Freezes`"
+			);
+		}
+
+		for (asset, who, _) in Freezes::<T, I>::iter() {
+			frame_support::ensure!(
+				FrozenBalances::<T, I>::contains_key(asset.clone(), who.clone()),
+				"Account with `Freezes` has no `FrozenBalances` entry"
+			);
+		}
+
+		for (asset, who, held) in BalancesOnHold::<T, I>::iter() {
+			let holds = Holds::<T, I>::get(asset.clone(), who.clone());
+			let total_held = holds
+				.iter()
+				.try_fold(T::Balance::zero(), |acc, h| acc.checked_add(&h.amount))
+				.ok_or("The sum of `Holds` overflows")?;
+
+			frame_support::ensure!(
+				held == total_held,
+				"The `BalancesOnHold` is not equal to the sum of `Holds`"
+			);
+			frame_support::ensure!(
+				pallet_assets::Pallet::<T, I>::maybe_balance(asset, who).is_some(),
+				"Account with funds on hold does not exist in `pallet-assets`"
+			);
+		}
+
+		for (asset, who, _) in Holds::<T, I>::iter() {
+			frame_support::ensure!(
+				BalancesOnHold::<T, I>::contains_key(asset, who),
+				"Account with `Holds` has no `BalancesOnHold` entry"
+			);
+		}
+
+		Ok(())
+	}
+}
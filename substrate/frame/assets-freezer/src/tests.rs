@@ -0,0 +1,232 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for pallet-assets-freezer.
+
+use crate::{mock::*, BalancesOnHold, Event, FrozenBalances};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungibles::{Inspect, InspectFreeze, InspectHold, MutateFreeze, MutateHold},
+		tokens::{Fortitude, Precision, Preservation},
+	},
+};
+use sp_runtime::TokenError;
+
+const ASSET: u32 = 1;
+const WHO: u64 = 1;
+
+fn last_event() -> RuntimeEvent {
+	System::events().pop().expect("RuntimeEvent expected").event
+}
+
+fn reducible(who: u64) -> u64 {
+	<AssetsFreezer as Inspect<_>>::reducible_balance(
+		ASSET,
+		&who,
+		Preservation::Expendable,
+		Fortitude::Polite,
+	)
+}
+
+mod freeze {
+	use super::*;
+
+	#[test]
+	fn set_freeze_works() {
+		build_and_execute(|| {
+			assert_ok!(AssetsFreezer::set_freeze(ASSET, &DummyFreezeReason::Governance, &WHO, 50));
+			assert_eq!(
+				AssetsFreezer::balance_frozen(ASSET, &DummyFreezeReason::Governance, &WHO),
+				50
+			);
+			assert_eq!(FrozenBalances::<Test>::get(ASSET, WHO), Some(50));
+			assert_eq!(
+				last_event(),
+				Event::<Test>::Frozen { asset_id: ASSET, who: WHO, amount: 50 }.into()
+			);
+
+			// setting a freeze replaces it, and zero thaws it.
+			assert_ok!(AssetsFreezer::set_freeze(ASSET, &DummyFreezeReason::Governance, &WHO, 20));
+			assert_eq!(FrozenBalances::<Test>::get(ASSET, WHO), Some(20));
+			assert_ok!(AssetsFreezer::set_freeze(ASSET, &DummyFreezeReason::Governance, &WHO, 0));
+			assert_eq!(FrozenBalances::<Test>::get(ASSET, WHO), None);
+			assert_eq!(
+				last_event(),
+				Event::<Test>::Thawed { asset_id: ASSET, who: WHO, amount: 20 }.into()
+			);
+		});
+	}
+
+	#[test]
+	fn frozen_balance_is_the_largest_freeze() {
+		build_and_execute(|| {
+			assert_ok!(AssetsFreezer::set_freeze(ASSET, &DummyFreezeReason::Governance, &WHO, 20));
+			assert_ok!(AssetsFreezer::set_freeze(ASSET, &DummyFreezeReason::Staking, &WHO, 50));
+			assert_eq!(FrozenBalances::<Test>::get(ASSET, WHO), Some(50));
+
+			// extending never reduces a freeze.
+			assert_ok!(AssetsFreezer::extend_freeze(ASSET, &DummyFreezeReason::Staking, &WHO, 30));
+			assert_eq!(AssetsFreezer::balance_frozen(ASSET, &DummyFreezeReason::Staking, &WHO), 50);
+			assert_ok!(AssetsFreezer::extend_freeze(ASSET, &DummyFreezeReason::Other, &WHO, 60));
+			assert_eq!(FrozenBalances::<Test>::get(ASSET, WHO), Some(60));
+
+			assert_ok!(AssetsFreezer::thaw(ASSET, &DummyFreezeReason::Other, &WHO));
+			assert_eq!(FrozenBalances::<Test>::get(ASSET, WHO), Some(50));
+			assert_ok!(AssetsFreezer::thaw(ASSET, &DummyFreezeReason::Staking, &WHO));
+			assert_eq!(FrozenBalances::<Test>::get(ASSET, WHO), Some(20));
+			assert_ok!(AssetsFreezer::thaw(ASSET, &DummyFreezeReason::Governance, &WHO));
+			assert_eq!(FrozenBalances::<Test>::get(ASSET, WHO), None);
+		});
+	}
+
+	#[test]
+	fn frozen_funds_cannot_be_transferred() {
+		build_and_execute(|| {
+			assert_eq!(reducible(WHO), 100);
+			assert_ok!(AssetsFreezer::set_freeze(ASSET, &DummyFreezeReason::Governance, &WHO, 50));
+			// the frozen amount and the minimum balance are untouchable.
+			assert_eq!(reducible(WHO), 49);
+			assert_noop!(
+				Assets::transfer(RuntimeOrigin::signed(WHO), ASSET, 2, 50),
+				pallet_assets::Error::<Test>::BalanceLow
+			);
+			assert_ok!(Assets::transfer(RuntimeOrigin::signed(WHO), ASSET, 2, 49));
+
+			// once thawed, the account can be emptied and its freezes are cleaned up.
+			assert_ok!(AssetsFreezer::thaw(ASSET, &DummyFreezeReason::Governance, &WHO));
+			assert_ok!(Assets::transfer(RuntimeOrigin::signed(WHO), ASSET, 2, 51));
+			assert_eq!(Assets::maybe_balance(ASSET, WHO), None);
+		});
+	}
+}
+
+mod hold {
+	use super::*;
+
+	#[test]
+	fn hold_and_release_works() {
+		build_and_execute(|| {
+			assert_ok!(AssetsFreezer::hold(ASSET, &DummyHoldReason::Deposit, &WHO, 30));
+			assert_eq!(
+				last_event(),
+				Event::<Test>::Held {
+					asset_id: ASSET,
+					who: WHO,
+					reason: DummyHoldReason::Deposit,
+					amount: 30
+				}
+				.into()
+			);
+
+			// held funds leave the free balance but still count towards the total balance.
+			assert_eq!(Assets::balance(ASSET, WHO), 70);
+			assert_eq!(AssetsFreezer::balance_on_hold(ASSET, &DummyHoldReason::Deposit, &WHO), 30);
+			assert_eq!(AssetsFreezer::total_balance_on_hold(ASSET, &WHO), 30);
+			assert_eq!(<AssetsFreezer as Inspect<_>>::total_balance(ASSET, &WHO), 100);
+			assert_eq!(<AssetsFreezer as Inspect<_>>::total_issuance(ASSET), 100);
+
+			// the account cannot be reaped while it has funds on hold.
+			assert_eq!(reducible(WHO), 69);
+			assert_noop!(
+				Assets::transfer(RuntimeOrigin::signed(WHO), ASSET, 2, 70),
+				pallet_assets::Error::<Test>::BalanceLow
+			);
+
+			assert_ok!(AssetsFreezer::hold(ASSET, &DummyHoldReason::Staking, &WHO, 20));
+			assert_eq!(BalancesOnHold::<Test>::get(ASSET, WHO), Some(50));
+
+			assert_ok!(AssetsFreezer::release(
+				ASSET,
+				&DummyHoldReason::Deposit,
+				&WHO,
+				30,
+				Precision::Exact
+			));
+			assert_eq!(
+				last_event(),
+				Event::<Test>::Released {
+					asset_id: ASSET,
+					who: WHO,
+					reason: DummyHoldReason::Deposit,
+					amount: 30
+				}
+				.into()
+			);
+			assert_eq!(Assets::balance(ASSET, WHO), 80);
+			assert_eq!(BalancesOnHold::<Test>::get(ASSET, WHO), Some(20));
+
+			assert_ok!(AssetsFreezer::release(
+				ASSET,
+				&DummyHoldReason::Staking,
+				&WHO,
+				20,
+				Precision::Exact
+			));
+			assert_eq!(Assets::balance(ASSET, WHO), 100);
+			assert_eq!(BalancesOnHold::<Test>::get(ASSET, WHO), None);
+		});
+	}
+
+	#[test]
+	fn cannot_hold_more_than_reducible_balance() {
+		build_and_execute(|| {
+			assert_noop!(
+				AssetsFreezer::hold(ASSET, &DummyHoldReason::Deposit, &WHO, 100),
+				TokenError::FundsUnavailable
+			);
+			// funds cannot be held in an account that does not exist.
+			assert_noop!(
+				AssetsFreezer::hold(ASSET, &DummyHoldReason::Deposit, &2, 1),
+				TokenError::CannotCreateHold
+			);
+		});
+	}
+
+	#[test]
+	fn burn_held_reduces_issuance() {
+		build_and_execute(|| {
+			assert_ok!(AssetsFreezer::hold(ASSET, &DummyHoldReason::Deposit, &WHO, 30));
+			assert_ok!(AssetsFreezer::burn_held(
+				ASSET,
+				&DummyHoldReason::Deposit,
+				&WHO,
+				10,
+				Precision::Exact,
+				Fortitude::Polite
+			));
+			assert_eq!(AssetsFreezer::balance_on_hold(ASSET, &DummyHoldReason::Deposit, &WHO), 20);
+			assert_eq!(<AssetsFreezer as Inspect<_>>::total_balance(ASSET, &WHO), 90);
+			assert_eq!(<AssetsFreezer as Inspect<_>>::total_issuance(ASSET), 90);
+		});
+	}
+
+	#[test]
+	fn held_funds_count_towards_frozen_balance() {
+		build_and_execute(|| {
+			assert_ok!(AssetsFreezer::set_freeze(ASSET, &DummyFreezeReason::Governance, &WHO, 50));
+			assert_eq!(reducible(WHO), 49);
+
+			assert_ok!(AssetsFreezer::hold(ASSET, &DummyHoldReason::Deposit, &WHO, 30));
+			// only 20 of the frozen amount remains in the free balance.
+			assert_eq!(Assets::balance(ASSET, WHO), 70);
+			assert_eq!(reducible(WHO), 49);
+			assert_ok!(Assets::transfer(RuntimeOrigin::signed(WHO), ASSET, 2, 49));
+			assert_eq!(Assets::balance(ASSET, WHO), 21);
+		});
+	}
+}
@@ -0,0 +1,96 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_assets_freezer.
+//!
+//! These weights are placeholders until the benchmarks in `benchmarking.rs` are run on reference
+//! hardware: they only account for the storage accesses of each operation. No runtime in this
+//! repository includes the pallet yet, so they are to be regenerated with
+//! `benchmark pallet --pallet=pallet_assets_freezer` on the first runtime that does.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for pallet_assets_freezer.
+pub trait WeightInfo {
+	fn set_freeze() -> Weight;
+	fn thaw() -> Weight;
+	fn hold() -> Weight;
+	fn release() -> Weight;
+}
+
+/// Weights for pallet_assets_freezer, only accounting for the storage accesses of the database of
+/// the runtime `T`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `AssetsFreezer::Freezes` (r:1 w:1)
+	/// Storage: `AssetsFreezer::FrozenBalances` (r:1 w:1)
+	fn set_freeze() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads_writes(2_u64, 2_u64))
+	}
+	/// Storage: `AssetsFreezer::Freezes` (r:1 w:1)
+	/// Storage: `AssetsFreezer::FrozenBalances` (r:1 w:1)
+	fn thaw() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads_writes(2_u64, 2_u64))
+	}
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Storage: `Assets::Account` (r:1 w:1)
+	/// Storage: `AssetsFreezer::FrozenBalances` (r:1 w:0)
+	/// Storage: `AssetsFreezer::Holds` (r:1 w:1)
+	/// Storage: `AssetsFreezer::BalancesOnHold` (r:1 w:1)
+	fn hold() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads_writes(5_u64, 4_u64))
+	}
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Storage: `Assets::Account` (r:1 w:1)
+	/// Storage: `AssetsFreezer::Holds` (r:1 w:1)
+	/// Storage: `AssetsFreezer::BalancesOnHold` (r:1 w:1)
+	fn release() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads_writes(4_u64, 4_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn set_freeze() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads_writes(2_u64, 2_u64))
+	}
+	fn thaw() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads_writes(2_u64, 2_u64))
+	}
+	fn hold() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads_writes(5_u64, 4_u64))
+	}
+	fn release() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads_writes(4_u64, 4_u64))
+	}
+}
@@ -186,8 +186,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			return Frozen
 		}
 		if let Some(rest) = account.balance.checked_sub(&amount) {
+			let held = T::Holder::balance_on_hold(id.clone(), who).unwrap_or_else(Zero::zero);
 			if let Some(frozen) = T::Freezer::frozen_balance(id.clone(), who) {
-				match frozen.checked_add(&details.min_balance) {
+				// Held funds count towards the frozen amount.
+				match frozen.saturating_sub(held).checked_add(&details.min_balance) {
 					Some(required) if rest < required => return Frozen,
 					None => return Overflow,
 					_ => {},
@@ -195,7 +197,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			}
 
 			if rest < details.min_balance {
-				if keep_alive {
+				if keep_alive || !held.is_zero() {
 					WouldDie
 				} else {
 					ReducedToZero(rest)
@@ -221,14 +223,18 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let account = Account::<T, I>::get(&id, who).ok_or(Error::<T, I>::NoAccount)?;
 		ensure!(!account.status.is_frozen(), Error::<T, I>::Frozen);
 
+		let held = T::Holder::balance_on_hold(id.clone(), who).unwrap_or_else(Zero::zero);
 		let amount = if let Some(frozen) = T::Freezer::frozen_balance(id, who) {
-			// Frozen balance: account CANNOT be deleted
-			let required =
-				frozen.checked_add(&details.min_balance).ok_or(ArithmeticError::Overflow)?;
+			// Frozen balance: account CANNOT be deleted. Held funds count towards the frozen
+			// amount.
+			let required = frozen
+				.saturating_sub(held)
+				.checked_add(&details.min_balance)
+				.ok_or(ArithmeticError::Overflow)?;
 			account.balance.saturating_sub(required)
 		} else {
-			if keep_alive {
-				// We want to keep the account around.
+			if keep_alive || !held.is_zero() {
+				// We want to keep the account around, or it has funds on hold.
 				account.balance.saturating_sub(details.min_balance)
 			} else {
 				// Don't care if the account dies
@@ -349,6 +355,14 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let mut details = Asset::<T, I>::get(&id).ok_or(Error::<T, I>::Unknown)?;
 		ensure!(matches!(details.status, Live | Frozen), Error::<T, I>::IncorrectStatus);
 		ensure!(account.balance.is_zero() || allow_burn, Error::<T, I>::WouldBurn);
+		ensure!(
+			T::Holder::balance_on_hold(id.clone(), &who).is_none(),
+			Error::<T, I>::ContainsHolds
+		);
+		ensure!(
+			T::Freezer::frozen_balance(id.clone(), &who).is_none(),
+			Error::<T, I>::ContainsFreezes
+		);
 
 		if let Some(deposit) = account.reason.take_deposit() {
 			T::Currency::unreserve(&who, deposit);
@@ -364,7 +378,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 		Asset::<T, I>::insert(&id, details);
 		// Executing a hook here is safe, since it is not in a `mutate`.
-		T::Freezer::died(id, &who);
+		T::Freezer::died(id.clone(), &who);
+		T::Holder::died(id, &who);
 		Ok(())
 	}
 
@@ -382,6 +397,14 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		ensure!(!account.status.is_frozen(), Error::<T, I>::Frozen);
 		ensure!(caller == &depositor || caller == &details.admin, Error::<T, I>::NoPermission);
 		ensure!(account.balance.is_zero(), Error::<T, I>::WouldBurn);
+		ensure!(
+			T::Holder::balance_on_hold(id.clone(), who).is_none(),
+			Error::<T, I>::ContainsHolds
+		);
+		ensure!(
+			T::Freezer::frozen_balance(id.clone(), who).is_none(),
+			Error::<T, I>::ContainsFreezes
+		);
 
 		T::Currency::unreserve(&depositor, deposit);
 
@@ -395,7 +418,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 		Asset::<T, I>::insert(&id, details);
 		// Executing a hook here is safe, since it is not in a `mutate`.
-		T::Freezer::died(id, &who);
+		T::Freezer::died(id.clone(), &who);
+		T::Holder::died(id, &who);
 		return Ok(())
 	}
 
@@ -563,7 +587,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		// Execute hook outside of `mutate`.
 		if let Some(Remove) = target_died {
-			T::Freezer::died(id, target);
+			T::Freezer::died(id.clone(), target);
+			T::Holder::died(id, target);
 		}
 		Ok(actual)
 	}
@@ -587,7 +612,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let (balance, died) =
 			Self::transfer_and_die(id.clone(), source, dest, amount, maybe_need_admin, f)?;
 		if let Some(Remove) = died {
-			T::Freezer::died(id, source);
+			T::Freezer::died(id.clone(), source);
+			T::Holder::died(id, source);
 		}
 		Ok(balance)
 	}
@@ -785,6 +811,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		for who in &dead_accounts {
 			T::Freezer::died(id.clone(), &who);
+			T::Holder::died(id.clone(), &who);
 		}
 
 		Self::deposit_event(Event::AccountsDestroyed {
@@ -945,7 +972,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		// Execute hook outside of `mutate`.
 		if let Some(Remove) = owner_died {
-			T::Freezer::died(id, owner);
+			T::Freezer::died(id.clone(), owner);
+			T::Holder::died(id, owner);
 		}
 		Ok(())
 	}
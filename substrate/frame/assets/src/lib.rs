@@ -306,6 +306,10 @@ pub mod pallet {
 		/// respected in all permissionless operations.
 		type Freezer: FrozenBalance<Self::AssetId, Self::AccountId, Self::Balance>;
 
+		/// A hook to inspect the balance an account holds outside of its `Account` record. Held
+		/// funds count towards the total balance of an account and prevent it from being reaped.
+		type Holder: BalanceOnHold<Self::AssetId, Self::AccountId, Self::Balance>;
+
 		/// Additional data to be stored with an account's asset balance.
 		type Extra: Member + Parameter + Default + MaxEncodedLen;
 
@@ -567,6 +571,10 @@ pub mod pallet {
 		NotFrozen,
 		/// Callback action resulted in error
 		CallbackFailed,
+		/// The asset-account has funds on hold and cannot be removed.
+		ContainsHolds,
+		/// The asset-account has frozen funds and cannot be removed.
+		ContainsFreezes,
 	}
 
 	#[pallet::call(weight(<T as Config<I>>::WeightInfo))]
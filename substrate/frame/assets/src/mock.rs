@@ -147,6 +147,7 @@ impl Config for Test {
 	type ApprovalDeposit = ConstU64<1>;
 	type StringLimit = ConstU32<50>;
 	type Freezer = TestFreezer;
+	type Holder = TestHolder;
 	type WeightInfo = ();
 	type CallbackHandle = AssetsCallbackHandle;
 	type Extra = ();
@@ -163,6 +164,7 @@ pub enum Hook {
 }
 parameter_types! {
 	static Frozen: HashMap<(u32, u64), u64> = Default::default();
+	static Held: HashMap<(u32, u64), u64> = Default::default();
 	static Hooks: Vec<Hook> = Default::default();
 }
 
@@ -192,6 +194,31 @@ pub(crate) fn clear_frozen_balance(asset: u32, who: u64) {
 	});
 }
 
+pub struct TestHolder;
+impl BalanceOnHold<u32, u64, u64> for TestHolder {
+	fn balance_on_hold(asset: u32, who: &u64) -> Option<u64> {
+		Held::get().get(&(asset, *who)).cloned()
+	}
+
+	fn died(asset: u32, who: &u64) {
+		Held::mutate(|v| {
+			v.remove(&(asset, *who));
+		});
+	}
+}
+
+pub(crate) fn set_balance_on_hold(asset: u32, who: u64, amount: u64) {
+	Held::mutate(|v| {
+		v.insert((asset, who), amount);
+	});
+}
+
+pub(crate) fn clear_balance_on_hold(asset: u32, who: u64) {
+	Held::mutate(|v| {
+		v.remove(&(asset, who));
+	});
+}
+
 pub(crate) fn hooks() -> Vec<Hook> {
 	Hooks::get().clone()
 }
@@ -1328,6 +1328,77 @@ fn freezer_should_work() {
 	});
 }
 
+#[test]
+fn holder_should_work() {
+	use frame_support::traits::fungibles::Inspect;
+
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		Balances::make_free_balance_be(&2, 100);
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, false, 10));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+		assert_eq!(Assets::balance(0, 1), 100);
+
+		// 20 held outside of the account record.
+		set_balance_on_hold(0, 1, 20);
+		assert_eq!(<Assets as Inspect<_>>::balance(0, &1), 100);
+		assert_eq!(<Assets as Inspect<_>>::total_balance(0, &1), 120);
+
+		// the account cannot be reaped while it has funds on hold...
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 100),
+			Error::<Test>::BalanceLow
+		);
+		// ...nor refunded.
+		assert_noop!(
+			Assets::refund(RuntimeOrigin::signed(1), 0, true),
+			Error::<Test>::ContainsHolds
+		);
+		// but it can go down to the minimum balance.
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 90));
+		assert_eq!(Assets::balance(0, 1), 10);
+
+		// once released, the account can be removed completely.
+		clear_balance_on_hold(0, 1);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 10));
+		assert_eq!(hooks(), vec![Hook::Died(0, 1)]);
+	});
+}
+
+#[test]
+fn held_balance_counts_towards_frozen_balance() {
+	use frame_support::traits::{
+		fungibles::Inspect,
+		tokens::{Fortitude, Preservation},
+	};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 10));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+
+		// freeze 50 of it, of which 20 is on hold.
+		set_frozen_balance(0, 1, 50);
+		set_balance_on_hold(0, 1, 20);
+
+		// only 30 of the frozen amount and the minimum balance are untouchable.
+		assert_eq!(
+			<Assets as Inspect<_>>::reducible_balance(
+				0,
+				&1,
+				Preservation::Expendable,
+				Fortitude::Polite
+			),
+			60
+		);
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 61),
+			Error::<Test>::BalanceLow
+		);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 60));
+		assert_eq!(Assets::balance(0, 1), 40);
+	});
+}
+
 #[test]
 fn imbalances_should_work() {
 	use frame_support::traits::fungibles::Balanced;
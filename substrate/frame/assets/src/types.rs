@@ -232,6 +232,32 @@ impl<AssetId, AccountId, Balance> FrozenBalance<AssetId, AccountId, Balance> for
 	fn died(_: AssetId, _: &AccountId) {}
 }
 
+/// Trait for specifying a balance that is distinct from the free balance of an account, and is
+/// held outside of the main `Account` record. Held funds still belong to the account and count
+/// towards its total balance, but cannot be transferred.
+pub trait BalanceOnHold<AssetId, AccountId, Balance> {
+	/// Return the balance on hold.
+	///
+	/// The account balance as stored in `Account` does not include this amount. While this is
+	/// `Some` and non-zero, the account must be kept alive, i.e. its balance may not go below the
+	/// asset's `minimum_balance`.
+	///
+	/// If `None` is returned, then no funds are held.
+	fn balance_on_hold(asset: AssetId, who: &AccountId) -> Option<Balance>;
+
+	/// Called after an account has been removed.
+	///
+	/// NOTE: It is possible that the asset does no longer exist when this hook is called.
+	fn died(asset: AssetId, who: &AccountId);
+}
+
+impl<AssetId, AccountId, Balance> BalanceOnHold<AssetId, AccountId, Balance> for () {
+	fn balance_on_hold(_: AssetId, _: &AccountId) -> Option<Balance> {
+		None
+	}
+	fn died(_: AssetId, _: &AccountId) {}
+}
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub(super) struct TransferFlags {
 	/// The debited account must stay alive at the end of the operation; an error is returned if
@@ -918,6 +918,198 @@ mod benches {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn enable_auto_renew() -> Result<(), BenchmarkError> {
+		setup_and_start_sale::<T>()?;
+		let region_len = Configuration::<T>::get().unwrap().region_length;
+
+		advance_to::<T>(2);
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::set_balance(
+			&caller.clone(),
+			T::Currency::minimum_balance().saturating_add(20u32.into()),
+		);
+
+		let region = Broker::<T>::do_purchase(caller.clone(), 10u32.into())
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		Broker::<T>::do_assign(region, None, 1001, Final)
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		advance_to::<T>((T::TimeslicePeriod::get() * region_len.into()).try_into().ok().unwrap());
+
+		// Worst case: the registry is filled up to its limit by this call.
+		let records = (1..T::MaxAutoRenewals::get())
+			.map(|i| AutoRenewalRecord {
+				core: MAX_CORE_COUNT.saturating_add(i as u16),
+				task: 2000 + i,
+				payer: account("payer", i, SEED),
+				next_renewal: region.begin + region_len * 2,
+			})
+			.collect::<Vec<_>>();
+		AutoRenewals::<T>::put(BoundedVec::try_from(records).unwrap());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), region.core, 1001, None);
+
+		let core = AutoRenewals::<T>::get()
+			.iter()
+			.find(|r| r.task == 1001)
+			.map(|r| r.core)
+			.ok_or(BenchmarkError::Weightless)?;
+		assert_last_event::<T>(
+			Event::AutoRenewalEnabled { core, task: 1001, payer: caller }.into(),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn disable_auto_renew() -> Result<(), BenchmarkError> {
+		let core = setup_and_start_sale::<T>()?;
+
+		let caller: T::AccountId = whitelisted_caller();
+		let records = (0..T::MaxAutoRenewals::get())
+			.map(|i| AutoRenewalRecord {
+				core: core.saturating_add(i as u16),
+				task: 1001 + i,
+				payer: caller.clone(),
+				next_renewal: 10,
+			})
+			.collect::<Vec<_>>();
+		AutoRenewals::<T>::put(BoundedVec::try_from(records).unwrap());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), core, 1001);
+
+		assert_last_event::<T>(Event::AutoRenewalDisabled { core, task: 1001 }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn list_region() -> Result<(), BenchmarkError> {
+		setup_and_start_sale::<T>()?;
+
+		advance_to::<T>(2);
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::set_balance(
+			&caller.clone(),
+			T::Currency::minimum_balance().saturating_add(10u32.into()),
+		);
+
+		let region = Broker::<T>::do_purchase(caller.clone(), 10u32.into())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let price = ListingPrice::Descending {
+			start: 20u32.into(),
+			end: 10u32.into(),
+			duration: 10u32.into(),
+		};
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), region, price);
+
+		assert_last_event::<T>(
+			Event::RegionListed { region_id: region, seller: caller, price }.into(),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn unlist_region() -> Result<(), BenchmarkError> {
+		setup_and_start_sale::<T>()?;
+
+		advance_to::<T>(2);
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::set_balance(
+			&caller.clone(),
+			T::Currency::minimum_balance().saturating_add(10u32.into()),
+		);
+
+		let region = Broker::<T>::do_purchase(caller.clone(), 10u32.into())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		Broker::<T>::do_list_region(caller.clone(), region, ListingPrice::Fixed(10u32.into()))
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), region);
+
+		assert_last_event::<T>(Event::RegionUnlisted { region_id: region }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn purchase_region() -> Result<(), BenchmarkError> {
+		setup_and_start_sale::<T>()?;
+
+		advance_to::<T>(2);
+
+		let seller: T::AccountId = account("seller", 0, SEED);
+		T::Currency::set_balance(
+			&seller.clone(),
+			T::Currency::minimum_balance().saturating_add(10u32.into()),
+		);
+
+		let region = Broker::<T>::do_purchase(seller.clone(), 10u32.into())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let price = ListingPrice::Descending {
+			start: 20u32.into(),
+			end: 10u32.into(),
+			duration: 10u32.into(),
+		};
+		Broker::<T>::do_list_region(seller.clone(), region, price)
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::set_balance(
+			&caller.clone(),
+			T::Currency::minimum_balance().saturating_add(20u32.into()),
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), region, 20u32.into());
+
+		assert_last_event::<T>(
+			Event::RegionSold { region_id: region, seller, buyer: caller, price: 20u32.into() }
+				.into(),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn expire_listing() -> Result<(), BenchmarkError> {
+		setup_and_start_sale::<T>()?;
+
+		advance_to::<T>(2);
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::set_balance(
+			&caller.clone(),
+			T::Currency::minimum_balance().saturating_add(10u32.into()),
+		);
+
+		let region = Broker::<T>::do_purchase(caller.clone(), 10u32.into())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		Broker::<T>::do_list_region(caller, region, ListingPrice::Fixed(10u32.into()))
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let end = Regions::<T>::get(region).ok_or(BenchmarkError::Weightless)?.end;
+
+		#[block]
+		{
+			Broker::<T>::expire_listings(end);
+		}
+
+		assert_eq!(Listings::<T>::iter_prefix(end).count(), 0);
+
+		Ok(())
+	}
+
 	// Implements a test for each benchmark. Execute with:
 	// `cargo test -p pallet-broker --features runtime-benchmarks`.
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -181,6 +181,8 @@ impl<T: Config> Pallet<T> {
 			ensure!(check_owner == region.owner, Error::<T>::NotOwner);
 		}
 
+		Self::cancel_listing(&region_id, region.end);
+
 		let old_owner = region.owner;
 		region.owner = new_owner;
 		Regions::<T>::insert(&region_id, &region);
@@ -209,6 +211,8 @@ impl<T: Config> Pallet<T> {
 		ensure!(pivot < region.end, Error::<T>::PivotTooLate);
 		ensure!(pivot > region_id.begin, Error::<T>::PivotTooEarly);
 
+		Self::cancel_listing(&region_id, region.end);
+
 		region.paid = None;
 		let new_region_ids = (region_id, RegionId { begin: pivot, ..region_id });
 
@@ -234,6 +238,8 @@ impl<T: Config> Pallet<T> {
 		ensure!(!pivot.is_void(), Error::<T>::VoidPivot);
 		ensure!(pivot != region_id.mask, Error::<T>::CompletePivot);
 
+		Self::cancel_listing(&region_id, region.end);
+
 		// The old region should be removed.
 		Regions::<T>::remove(&region_id);
 
@@ -437,4 +443,123 @@ impl<T: Config> Pallet<T> {
 		Self::deposit_event(Event::AllowedRenewalDropped { core, when });
 		Ok(())
 	}
+
+	pub(crate) fn do_enable_auto_renew(
+		who: T::AccountId,
+		core: CoreIndex,
+		task: TaskId,
+		workload_end_hint: Option<Timeslice>,
+	) -> DispatchResult {
+		let sale = SaleInfo::<T>::get().ok_or(Error::<T>::NoSales)?;
+
+		let mut renewals = AutoRenewals::<T>::get();
+		let maybe_index = renewals.binary_search_by_key(&core, |r| r.core);
+		if let Ok(index) = maybe_index {
+			let record = &renewals[index];
+			ensure!(record.task == task, Error::<T>::NonTaskAutoRenewal);
+			ensure!(Self::can_manage_auto_renewal(record, &who), Error::<T>::NotOwner);
+		}
+
+		// If the workload can be renewed in the ongoing sale, then renew it right away, otherwise
+		// it must become renewable once it ends.
+		let renewal_id = AllowedRenewalId { core, when: sale.region_begin };
+		let (core, next_renewal) = if let Some(record) = AllowedRenewals::<T>::get(renewal_id) {
+			Self::ensure_task_workload(&record, task)?;
+			(Self::do_renew(who.clone(), core)?, sale.region_end)
+		} else {
+			let when = workload_end_hint.ok_or(Error::<T>::NotAllowed)?;
+			let record = AllowedRenewals::<T>::get(AllowedRenewalId { core, when })
+				.ok_or(Error::<T>::NotAllowed)?;
+			Self::ensure_task_workload(&record, task)?;
+			(core, when)
+		};
+
+		if let Ok(index) = maybe_index {
+			renewals.remove(index);
+		}
+		let index = match renewals.binary_search_by_key(&core, |r| r.core) {
+			Ok(index) => {
+				renewals.remove(index);
+				index
+			},
+			Err(index) => index,
+		};
+		let renewal = AutoRenewalRecord { core, task, payer: who.clone(), next_renewal };
+		renewals
+			.try_insert(index, renewal)
+			.map_err(|_| Error::<T>::TooManyAutoRenewals)?;
+		AutoRenewals::<T>::put(renewals);
+		Self::deposit_event(Event::AutoRenewalEnabled { core, task, payer: who });
+		Ok(())
+	}
+
+	pub(crate) fn do_disable_auto_renew(
+		who: T::AccountId,
+		core: CoreIndex,
+		task: TaskId,
+	) -> DispatchResult {
+		let mut renewals = AutoRenewals::<T>::get();
+		let index = renewals
+			.binary_search_by_key(&core, |r| r.core)
+			.map_err(|_| Error::<T>::AutoRenewalNotEnabled)?;
+		let record = &renewals[index];
+		ensure!(record.task == task, Error::<T>::AutoRenewalNotEnabled);
+		ensure!(Self::can_manage_auto_renewal(record, &who), Error::<T>::NotOwner);
+		renewals.remove(index);
+		AutoRenewals::<T>::put(renewals);
+		Self::deposit_event(Event::AutoRenewalDisabled { core, task });
+		Ok(())
+	}
+
+	pub(crate) fn do_list_region(
+		who: T::AccountId,
+		region_id: RegionId,
+		price: ListingPriceOf<T>,
+	) -> DispatchResult {
+		let status = Status::<T>::get().ok_or(Error::<T>::Uninitialized)?;
+		let region = Regions::<T>::get(&region_id).ok_or(Error::<T>::UnknownRegion)?;
+		ensure!(who == region.owner, Error::<T>::NotOwner);
+		ensure!(region.end > status.last_committed_timeslice, Error::<T>::AlreadyExpired);
+		if let ListingPrice::Descending { start, end, duration } = price {
+			ensure!(start >= end && !duration.is_zero(), Error::<T>::InvalidListingPrice);
+		}
+
+		let listed_at = frame_system::Pallet::<T>::block_number();
+		let listing = ListingRecord { seller: who.clone(), price, listed_at };
+		Listings::<T>::insert(region.end, &region_id, &listing);
+		Self::deposit_event(Event::RegionListed { region_id, seller: who, price });
+		Ok(())
+	}
+
+	pub(crate) fn do_unlist_region(who: T::AccountId, region_id: RegionId) -> DispatchResult {
+		let region = Regions::<T>::get(&region_id).ok_or(Error::<T>::UnknownRegion)?;
+		let listing = Listings::<T>::get(region.end, &region_id).ok_or(Error::<T>::NotListed)?;
+		ensure!(who == listing.seller, Error::<T>::NotOwner);
+
+		Self::cancel_listing(&region_id, region.end);
+		Ok(())
+	}
+
+	pub(crate) fn do_purchase_region(
+		who: T::AccountId,
+		region_id: RegionId,
+		max_price: BalanceOf<T>,
+	) -> DispatchResult {
+		let region = Regions::<T>::get(&region_id).ok_or(Error::<T>::UnknownRegion)?;
+		let listing = Listings::<T>::take(region.end, &region_id).ok_or(Error::<T>::NotListed)?;
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let price = Self::listing_price(&listing, now);
+		ensure!(price <= max_price, Error::<T>::Overpriced);
+
+		T::Currency::transfer(&who, &listing.seller, price, Expendable)?;
+		Self::do_transfer(region_id, Some(listing.seller.clone()), who.clone())?;
+		Self::deposit_event(Event::RegionSold {
+			region_id,
+			seller: listing.seller,
+			buyer: who,
+			price,
+		});
+		Ok(())
+	}
 }
@@ -56,7 +56,7 @@ pub mod pallet {
 		PalletId,
 	};
 	use frame_system::pallet_prelude::*;
-	use sp_runtime::traits::{Convert, ConvertBack};
+	use sp_runtime::traits::{Convert, ConvertBack, MaybeConvert};
 	use sp_std::vec::Vec;
 
 	#[pallet::pallet]
@@ -105,6 +105,14 @@ pub mod pallet {
 		/// Maximum number of system cores.
 		#[pallet::constant]
 		type MaxReservedCores: Get<u32>;
+
+		/// Type used for getting the sovereign account of a task. This account may always manage
+		/// the auto-renewal of the task's workload, and is typically used to pay for it.
+		type SovereignAccountOf: MaybeConvert<TaskId, Self::AccountId>;
+
+		/// Maximum number of cores whose workload can be renewed automatically.
+		#[pallet::constant]
+		type MaxAutoRenewals: Get<u32>;
 	}
 
 	/// The current configuration of this pallet.
@@ -136,6 +144,19 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type Regions<T> = StorageMap<_, Blake2_128Concat, RegionId, RegionRecordOf<T>, OptionQuery>;
 
+	/// The Regions listed for sale, keyed by the end of the Region. Listings are removed once the
+	/// end of their Region has been committed.
+	#[pallet::storage]
+	pub type Listings<T> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		Timeslice,
+		Blake2_128Concat,
+		RegionId,
+		ListingRecordOf<T>,
+		OptionQuery,
+	>;
+
 	/// The work we plan on having each core do at a particular time in the future.
 	#[pallet::storage]
 	pub type Workplan<T> =
@@ -163,6 +184,12 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type CoreCountInbox<T> = StorageValue<_, CoreIndex, OptionQuery>;
 
+	/// The cores whose workload is renewed automatically at the start of every sale, sorted by
+	/// core index.
+	#[pallet::storage]
+	pub type AutoRenewals<T: Config> =
+		StorageValue<_, BoundedVec<AutoRenewalRecordOf<T>, T::MaxAutoRenewals>, ValueQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -410,6 +437,60 @@ pub mod pallet {
 			/// The core whose workload is no longer available to be renewed for `when`.
 			core: CoreIndex,
 		},
+		/// The workload of a core will be renewed automatically at the start of every sale.
+		AutoRenewalEnabled {
+			/// The core on which the workload is currently scheduled.
+			core: CoreIndex,
+			/// The task whose workload will be renewed.
+			task: TaskId,
+			/// The account which pays for the renewals.
+			payer: T::AccountId,
+		},
+		/// The workload of a core will no longer be renewed automatically.
+		AutoRenewalDisabled {
+			/// The core on which the workload is currently scheduled.
+			core: CoreIndex,
+			/// The task whose workload will no longer be renewed.
+			task: TaskId,
+		},
+		/// The automatic renewal of the workload of a core failed, and the workload will no longer
+		/// be renewed automatically.
+		AutoRenewalFailed {
+			/// The core on which the workload was scheduled.
+			core: CoreIndex,
+			/// The task whose workload could not be renewed.
+			task: TaskId,
+			/// The account which was meant to pay for the renewal.
+			payer: T::AccountId,
+			/// The reason for which the renewal failed.
+			error: DispatchError,
+		},
+		/// A Region has been listed for sale.
+		RegionListed {
+			/// The Region which has been listed.
+			region_id: RegionId,
+			/// The owner of the Region, who receives the payment.
+			seller: T::AccountId,
+			/// The price of the Region.
+			price: ListingPriceOf<T>,
+		},
+		/// A Region is no longer listed for sale, either because the listing was cancelled or
+		/// because the Region changed.
+		RegionUnlisted {
+			/// The Region which is no longer listed.
+			region_id: RegionId,
+		},
+		/// A listed Region has been sold.
+		RegionSold {
+			/// The Region which has been sold.
+			region_id: RegionId,
+			/// The previous owner of the Region.
+			seller: T::AccountId,
+			/// The new owner of the Region.
+			buyer: T::AccountId,
+			/// The price paid for the Region.
+			price: BalanceOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -474,6 +555,18 @@ pub mod pallet {
 		AlreadyExpired,
 		/// The configuration could not be applied because it is invalid.
 		InvalidConfig,
+		/// The maximum number of cores with auto-renewal enabled has already been reached.
+		TooManyAutoRenewals,
+		/// Only workloads made of a single task occupying the whole core can be renewed
+		/// automatically.
+		NonTaskAutoRenewal,
+		/// Auto-renewal is not enabled for the given core and task.
+		AutoRenewalNotEnabled,
+		/// The Region is not listed for sale.
+		NotListed,
+		/// The listing price is invalid; a descending price must not increase and must decline
+		/// over a non-zero number of blocks.
+		InvalidListingPrice,
 	}
 
 	#[pallet::hooks]
@@ -786,5 +879,97 @@ pub mod pallet {
 			Self::do_notify_core_count(core_count)?;
 			Ok(())
 		}
+
+		/// Enable the automatic renewal of the workload of a core at the start of every sale.
+		///
+		/// If the workload is renewable in the ongoing sale it is renewed immediately. Otherwise,
+		/// `workload_end_hint` must be the timeslice at which the workload ends and its renewal
+		/// becomes possible.
+		///
+		/// - `origin`: Must be a Signed origin of the account which pays for the renewals. This is
+		///   typically the sovereign account of `task`, e.g. dispatched by the task's parachain
+		///   through XCM. If auto-renewal is already enabled for `core`, then this must be either
+		///   its current payer or the sovereign account of the task.
+		/// - `core`: The core on which the workload of `task` is scheduled.
+		/// - `task`: The task whose workload should be renewed. It must be the only assignment of
+		///   the whole core.
+		/// - `workload_end_hint`: The timeslice at which the workload ends, if it is not renewable
+		///   in the ongoing sale.
+		#[pallet::call_index(20)]
+		pub fn enable_auto_renew(
+			origin: OriginFor<T>,
+			core: CoreIndex,
+			task: TaskId,
+			workload_end_hint: Option<Timeslice>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_enable_auto_renew(who, core, task, workload_end_hint)?;
+			Ok(())
+		}
+
+		/// Disable the automatic renewal of the workload of a core.
+		///
+		/// - `origin`: Must be a Signed origin of either the account which pays for the renewals or
+		///   the sovereign account of `task`.
+		/// - `core`: The core on which the workload of `task` is scheduled.
+		/// - `task`: The task whose workload should no longer be renewed.
+		#[pallet::call_index(21)]
+		pub fn disable_auto_renew(
+			origin: OriginFor<T>,
+			core: CoreIndex,
+			task: TaskId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_disable_auto_renew(who, core, task)?;
+			Ok(())
+		}
+
+		/// List a Region for sale, replacing any existing listing of it.
+		///
+		/// The listing is cancelled if the Region is transferred, partitioned, interlaced or
+		/// assigned, and removed once the end of the Region has been committed.
+		///
+		/// - `origin`: Must be a Signed origin of the owner of the Region.
+		/// - `region_id`: The Region which should be listed.
+		/// - `price`: The price for which the Region may be bought.
+		#[pallet::call_index(22)]
+		pub fn list_region(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			price: ListingPriceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_list_region(who, region_id, price)?;
+			Ok(())
+		}
+
+		/// Cancel the listing of a Region.
+		///
+		/// - `origin`: Must be a Signed origin of the seller of the Region.
+		/// - `region_id`: The Region which should no longer be listed.
+		#[pallet::call_index(23)]
+		pub fn unlist_region(origin: OriginFor<T>, region_id: RegionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_unlist_region(who, region_id)?;
+			Ok(())
+		}
+
+		/// Purchase a listed Region. The payment is transferred to the seller and the Region to
+		/// the buyer.
+		///
+		/// - `origin`: Must be a Signed origin with at least enough funds to pay the current price
+		///   of the listing.
+		/// - `region_id`: The Region which should be purchased.
+		/// - `max_price`: An amount no less than the current price of the listing.
+		#[pallet::call_index(24)]
+		pub fn purchase_region(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			max_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_purchase_region(who, region_id, max_price)?;
+			Ok(())
+		}
 	}
 }
@@ -31,7 +31,7 @@ use frame_system::{EnsureRoot, EnsureSignedBy};
 use sp_arithmetic::Perbill;
 use sp_core::{ConstU32, ConstU64};
 use sp_runtime::{
-	traits::{BlockNumberProvider, Identity},
+	traits::{BlockNumberProvider, Identity, MaybeConvert},
 	BuildStorage, Saturating,
 };
 use sp_std::collections::btree_map::BTreeMap;
@@ -187,6 +187,14 @@ ord_parameter_types! {
 }
 type EnsureOneOrRoot = EitherOfDiverse<EnsureRoot<u64>, EnsureSignedBy<One, u64>>;
 
+/// The sovereign account of a task in the tests is its id offset by 1000.
+pub struct TaskSovereignAccount;
+impl MaybeConvert<TaskId, u64> for TaskSovereignAccount {
+	fn maybe_convert(task: TaskId) -> Option<u64> {
+		Some(1000 + task as u64)
+	}
+}
+
 impl crate::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = ItemOf<TestFungibles<(), u64, (), ConstU64<0>, ()>, (), u64>;
@@ -200,6 +208,8 @@ impl crate::Config for Test {
 	type PalletId = TestBrokerId;
 	type AdminOrigin = EnsureOneOrRoot;
 	type PriceAdapter = Linear;
+	type SovereignAccountOf = TaskSovereignAccount;
+	type MaxAutoRenewals = ConstU32<3>;
 }
 
 pub fn advance_to(b: u64) {
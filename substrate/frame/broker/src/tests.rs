@@ -1064,3 +1064,226 @@ fn config_works() {
 		assert_noop!(Broker::configure(Root.into(), cfg), Error::<Test>::InvalidConfig);
 	});
 }
+
+#[test]
+fn auto_renewal_works() {
+	TestExt::new().endow(1, 1000).execute_with(|| {
+		assert_ok!(Broker::do_start_sales(100, 1));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+		assert_eq!(balance(1), 900);
+		// Only renewable workloads can be renewed automatically.
+		assert_noop!(
+			Broker::do_enable_auto_renew(1, region.core, 1001, Some(region.end)),
+			Error::<Test>::NotAllowed
+		);
+		assert_ok!(Broker::do_assign(region, None, 1001, Final));
+		// The end of the workload must be given if it cannot be renewed in the ongoing sale.
+		assert_noop!(
+			Broker::do_enable_auto_renew(1, region.core, 1001, None),
+			Error::<Test>::NotAllowed
+		);
+		assert_noop!(
+			Broker::do_enable_auto_renew(1, region.core, 1002, Some(region.end)),
+			Error::<Test>::NonTaskAutoRenewal
+		);
+		assert_ok!(Broker::do_enable_auto_renew(1, region.core, 1001, Some(region.end)));
+		System::assert_last_event(
+			Event::AutoRenewalEnabled { core: region.core, task: 1001, payer: 1 }.into(),
+		);
+		let record =
+			AutoRenewalRecord { core: region.core, task: 1001, payer: 1, next_renewal: region.end };
+		assert_eq!(AutoRenewals::<Test>::get().to_vec(), vec![record]);
+
+		// Renewed at the start of the next sale.
+		advance_to(6);
+		assert_eq!(balance(1), 800);
+		let record = AutoRenewals::<Test>::get()[0].clone();
+		assert_eq!(record.next_renewal, region.end + 3);
+		assert_noop!(Broker::do_renew(1, record.core), Error::<Test>::SoldOut);
+
+		// And again at the start of the one after.
+		advance_to(12);
+		assert_eq!(balance(1), 690);
+		assert_eq!(AutoRenewals::<Test>::get()[0].next_renewal, region.end + 6);
+	});
+}
+
+#[test]
+fn auto_renewal_can_be_enabled_during_renewal_period() {
+	TestExt::new().endow(1, 1000).endow(2, 1000).execute_with(|| {
+		assert_ok!(Broker::do_start_sales(100, 1));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+		assert_ok!(Broker::do_assign(region, None, 1001, Final));
+		advance_to(6);
+		// Anyone may pay for the renewal; it is renewed right away.
+		assert_ok!(Broker::do_enable_auto_renew(2, region.core, 1001, None));
+		assert_eq!(balance(2), 900);
+		let record = AutoRenewals::<Test>::get()[0].clone();
+		assert_eq!(record.payer, 2);
+		assert_eq!(record.next_renewal, region.end + 3);
+	});
+}
+
+#[test]
+fn auto_renewal_failure_removes_record() {
+	TestExt::new().endow(1, 150).execute_with(|| {
+		assert_ok!(Broker::do_start_sales(100, 1));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+		assert_ok!(Broker::do_assign(region, None, 1001, Final));
+		assert_ok!(Broker::do_enable_auto_renew(1, region.core, 1001, Some(region.end)));
+		advance_to(6);
+		// Not enough funds to pay for the renewal.
+		assert_eq!(balance(1), 50);
+		assert!(AutoRenewals::<Test>::get().is_empty());
+		assert!(System::events().iter().any(|e| matches!(
+			e.event,
+			RuntimeEvent::Broker(Event::AutoRenewalFailed { task: 1001, payer: 1, .. })
+		)));
+		// The core is still renewable manually.
+		assert_eq!(AllowedRenewals::<Test>::iter().count(), 1);
+	});
+}
+
+#[test]
+fn disable_auto_renew_works() {
+	TestExt::new().endow(1, 1000).execute_with(|| {
+		assert_ok!(Broker::do_start_sales(100, 1));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+		assert_ok!(Broker::do_assign(region, None, 1001, Final));
+		assert_ok!(Broker::do_enable_auto_renew(1, region.core, 1001, Some(region.end)));
+
+		// Only the payer or the sovereign account of the task may change the record.
+		assert_noop!(
+			Broker::do_enable_auto_renew(2, region.core, 1001, Some(region.end)),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(Broker::do_disable_auto_renew(2, region.core, 1001), Error::<Test>::NotOwner);
+		assert_noop!(
+			Broker::do_disable_auto_renew(1, region.core, 1002),
+			Error::<Test>::AutoRenewalNotEnabled
+		);
+		assert_ok!(Broker::do_disable_auto_renew(2001, region.core, 1001));
+		System::assert_last_event(
+			Event::AutoRenewalDisabled { core: region.core, task: 1001 }.into(),
+		);
+		assert!(AutoRenewals::<Test>::get().is_empty());
+		assert_noop!(
+			Broker::do_disable_auto_renew(1, region.core, 1001),
+			Error::<Test>::AutoRenewalNotEnabled
+		);
+
+		// Not renewed once disabled.
+		advance_to(6);
+		assert_eq!(balance(1), 900);
+	});
+}
+
+#[test]
+fn fixed_price_listing_works() {
+	TestExt::new().endow(1, 1000).endow(2, 1000).execute_with(|| {
+		assert_ok!(Broker::do_start_sales(100, 1));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+		assert_eq!(balance(1), 900);
+		assert_noop!(
+			Broker::do_list_region(2, region, ListingPrice::Fixed(50)),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(Broker::do_list_region(1, region, ListingPrice::Fixed(50)));
+		System::assert_last_event(
+			Event::RegionListed { region_id: region, seller: 1, price: ListingPrice::Fixed(50) }
+				.into(),
+		);
+
+		assert_noop!(Broker::do_purchase_region(2, region, 40), Error::<Test>::Overpriced);
+		assert_ok!(Broker::do_purchase_region(2, region, 50));
+		System::assert_last_event(
+			Event::RegionSold { region_id: region, seller: 1, buyer: 2, price: 50 }.into(),
+		);
+		assert_eq!(balance(1), 950);
+		assert_eq!(balance(2), 950);
+		assert_eq!(Regions::<Test>::get(region).unwrap().owner, 2);
+		assert_noop!(Broker::do_purchase_region(1, region, 50), Error::<Test>::NotListed);
+	});
+}
+
+#[test]
+fn descending_price_listing_works() {
+	TestExt::new().endow(1, 1000).execute_with(|| {
+		assert_ok!(Broker::do_start_sales(100, 1));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+		let increasing = ListingPrice::Descending { start: 20, end: 100, duration: 8 };
+		assert_noop!(
+			Broker::do_list_region(1, region, increasing),
+			Error::<Test>::InvalidListingPrice
+		);
+		let instant = ListingPrice::Descending { start: 100, end: 20, duration: 0 };
+		assert_noop!(
+			Broker::do_list_region(1, region, instant),
+			Error::<Test>::InvalidListingPrice
+		);
+
+		let price = ListingPrice::Descending { start: 100, end: 20, duration: 8 };
+		assert_ok!(Broker::do_list_region(1, region, price));
+		let region_end = Regions::<Test>::get(region).unwrap().end;
+		let listing = Listings::<Test>::get(region_end, region).unwrap();
+		assert_eq!(listing, ListingRecord { seller: 1, price, listed_at: 2 });
+		assert_eq!(Broker::listing_price(&listing, 2), 100);
+		assert_eq!(Broker::listing_price(&listing, 4), 80);
+		assert_eq!(Broker::listing_price(&listing, 6), 60);
+		assert_eq!(Broker::listing_price(&listing, 10), 20);
+		assert_eq!(Broker::listing_price(&listing, 100), 20);
+	});
+}
+
+#[test]
+fn listing_is_cancelled_when_region_changes() {
+	TestExt::new().endow(1, 1000).execute_with(|| {
+		assert_ok!(Broker::do_start_sales(100, 1));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+
+		assert_ok!(Broker::do_list_region(1, region, ListingPrice::Fixed(50)));
+		assert_noop!(Broker::do_unlist_region(2, region), Error::<Test>::NotOwner);
+		assert_ok!(Broker::do_unlist_region(1, region));
+		System::assert_last_event(Event::RegionUnlisted { region_id: region }.into());
+		assert_noop!(Broker::do_unlist_region(1, region), Error::<Test>::NotListed);
+
+		assert_ok!(Broker::do_list_region(1, region, ListingPrice::Fixed(50)));
+		assert_ok!(Broker::do_transfer(region, Some(1), 2));
+		assert_eq!(Listings::<Test>::iter().count(), 0);
+
+		assert_ok!(Broker::do_list_region(2, region, ListingPrice::Fixed(50)));
+		let (region1, region2) = Broker::do_partition(region, Some(2), 1).unwrap();
+		assert_eq!(Listings::<Test>::iter().count(), 0);
+
+		assert_ok!(Broker::do_list_region(2, region1, ListingPrice::Fixed(50)));
+		assert_ok!(Broker::do_list_region(2, region2, ListingPrice::Fixed(50)));
+		assert_ok!(Broker::do_assign(region1, Some(2), 1001, Final));
+		assert_ok!(Broker::do_interlace(region2, Some(2), 0x00000_fffff_fffff_00000.into()));
+		assert_eq!(Listings::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn listing_expires_with_region() {
+	TestExt::new().endow(1, 1000).execute_with(|| {
+		assert_ok!(Broker::do_start_sales(100, 1));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+		assert_ok!(Broker::do_list_region(1, region, ListingPrice::Fixed(50)));
+		advance_to(11);
+		assert_eq!(Listings::<Test>::iter().count(), 1);
+		advance_to(12);
+		assert_eq!(Listings::<Test>::iter().count(), 0);
+		assert_noop!(
+			Broker::do_list_region(1, region, ListingPrice::Fixed(50)),
+			Error::<Test>::AlreadyExpired
+		);
+	});
+}
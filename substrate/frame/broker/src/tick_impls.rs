@@ -16,7 +16,11 @@
 // limitations under the License.
 
 use super::*;
-use frame_support::{pallet_prelude::*, weights::WeightMeter};
+use frame_support::{
+	pallet_prelude::*,
+	storage::{with_transaction, TransactionOutcome},
+	weights::WeightMeter,
+};
 use sp_arithmetic::{
 	traits::{One, SaturatedConversion, Saturating, Zero},
 	FixedPointNumber,
@@ -33,6 +37,8 @@ impl<T: Config> Pallet<T> {
 	/// - Processes reports of Instantaneous Core Market Revenue
 	/// - Commit a timeslice
 	/// - Rotate the sale period
+	/// - Renew the cores registered for auto-renewal
+	/// - Remove the listings of Regions which have been committed entirely
 	/// - Request revenue information for a previous timeslice
 	/// - Initialize an instantaneous core pool historical revenue record
 	pub(crate) fn do_tick() -> Weight {
@@ -59,12 +65,18 @@ impl<T: Config> Pallet<T> {
 					// Sale can be rotated.
 					Self::rotate_sale(sale, &config, &status);
 					meter.consume(T::WeightInfo::rotate_sale(status.core_count.into()));
+
+					let renewals = Self::renew_cores();
+					meter.consume(T::WeightInfo::renew().saturating_mul(renewals.into()));
 				}
 			}
 
 			Self::process_pool(commit_timeslice, &mut status);
 			meter.consume(T::WeightInfo::process_pool());
 
+			let expired = Self::expire_listings(commit_timeslice);
+			meter.consume(T::WeightInfo::expire_listing().saturating_mul(expired.into()));
+
 			let timeslice_period = T::TimeslicePeriod::get();
 			let rc_begin = RelayBlockNumberOf::<T>::from(commit_timeslice) * timeslice_period;
 			for core in 0..status.core_count {
@@ -269,6 +281,56 @@ impl<T: Config> Pallet<T> {
 		Some(())
 	}
 
+	/// Renew all cores registered in `AutoRenewals` which are renewable in the current sale.
+	///
+	/// Records which fail to renew, or which missed their renewal, are removed from the
+	/// registry. Returns the number of renewals attempted.
+	pub(crate) fn renew_cores() -> u32 {
+		let Some(sale) = SaleInfo::<T>::get() else { return 0 };
+		let mut attempted = 0u32;
+		let mut renewals = AutoRenewals::<T>::get().into_inner();
+		renewals.retain_mut(|record| {
+			if record.next_renewal > sale.region_begin {
+				return true
+			}
+			let result = if record.next_renewal < sale.region_begin {
+				// The renewal window of this record has already been missed.
+				Err(Error::<T>::NotAllowed.into())
+			} else {
+				attempted.saturating_inc();
+				with_transaction(|| match Self::do_renew(record.payer.clone(), record.core) {
+					Ok(core) => TransactionOutcome::Commit(Ok(core)),
+					Err(error) => TransactionOutcome::Rollback(Err(error)),
+				})
+			};
+			match result {
+				Ok(core) => {
+					record.core = core;
+					record.next_renewal = sale.region_end;
+					true
+				},
+				Err(error) => {
+					Self::deposit_event(Event::AutoRenewalFailed {
+						core: record.core,
+						task: record.task,
+						payer: record.payer.clone(),
+						error,
+					});
+					false
+				},
+			}
+		});
+		renewals.sort_by_key(|record| record.core);
+		AutoRenewals::<T>::put(BoundedVec::truncate_from(renewals));
+		attempted
+	}
+
+	/// Remove the listings of all Regions ending at `when`. Returns the number of listings
+	/// removed.
+	pub(crate) fn expire_listings(when: Timeslice) -> u32 {
+		Listings::<T>::drain_prefix(when).count().saturated_into()
+	}
+
 	pub(crate) fn process_pool(when: Timeslice, status: &mut StatusRecord) {
 		let pool_io = InstaPoolIo::<T>::take(when);
 		status.private_pool_size = (status.private_pool_size as SignedCoreMaskBitCount)
@@ -90,6 +90,37 @@ pub struct RegionRecord<AccountId, Balance> {
 }
 pub type RegionRecordOf<T> = RegionRecord<<T as SConfig>::AccountId, BalanceOf<T>>;
 
+/// The price at which a listed Region may be bought.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ListingPrice<Balance, BlockNumber> {
+	/// The Region may be bought for a fixed price.
+	Fixed(Balance),
+	/// The price of the Region declines linearly from `start` to `end` over `duration` blocks
+	/// after it was listed, and stays at `end` thereafter.
+	Descending {
+		/// The price at the time of listing.
+		start: Balance,
+		/// The lowest price of the Region.
+		end: Balance,
+		/// The number of blocks over which the price declines.
+		duration: BlockNumber,
+	},
+}
+pub type ListingPriceOf<T> = ListingPrice<BalanceOf<T>, BlockNumberFor<T>>;
+
+/// A Region listed for sale.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ListingRecord<AccountId, Balance, BlockNumber> {
+	/// The owner of the Region at the time of listing, who receives the payment.
+	pub seller: AccountId,
+	/// The price of the Region.
+	pub price: ListingPrice<Balance, BlockNumber>,
+	/// The block number at which the Region was listed.
+	pub listed_at: BlockNumber,
+}
+pub type ListingRecordOf<T> =
+	ListingRecord<<T as SConfig>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
+
 /// An distinct item which can be scheduled on a Polkadot Core.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct ScheduleItem {
@@ -172,6 +203,21 @@ pub struct AllowedRenewalRecord<Balance> {
 }
 pub type AllowedRenewalRecordOf<T> = AllowedRenewalRecord<BalanceOf<T>>;
 
+/// A record of a core whose workload is renewed automatically at the start of every sale.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AutoRenewalRecord<AccountId> {
+	/// The core on which the workload to be renewed is currently scheduled.
+	pub core: CoreIndex,
+	/// The task whose workload is renewed.
+	pub task: TaskId,
+	/// The account which pays the renewal price.
+	pub payer: AccountId,
+	/// The timeslice at which the next renewal is due, i.e. the `region_begin` of the sale in
+	/// which it will be made.
+	pub next_renewal: Timeslice,
+}
+pub type AutoRenewalRecordOf<T> = AutoRenewalRecord<<T as frame_system::Config>::AccountId>;
+
 /// General status of the system.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct StatusRecord {
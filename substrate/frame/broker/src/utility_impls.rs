@@ -29,7 +29,7 @@ use sp_arithmetic::{
 	traits::{SaturatedConversion, Saturating},
 	FixedPointNumber, FixedU64,
 };
-use sp_runtime::traits::{AccountIdConversion, BlockNumberProvider};
+use sp_runtime::traits::{AccountIdConversion, BlockNumberProvider, MaybeConvert};
 
 impl<T: Config> Pallet<T> {
 	pub fn current_timeslice() -> Timeslice {
@@ -66,6 +66,25 @@ impl<T: Config> Pallet<T> {
 		T::PriceAdapter::leadin_factor_at(through).saturating_mul_int(sale.price)
 	}
 
+	/// The price of a listed Region at block `now`.
+	pub fn listing_price(listing: &ListingRecordOf<T>, now: BlockNumberFor<T>) -> BalanceOf<T> {
+		match listing.price {
+			ListingPrice::Fixed(price) => price,
+			ListingPrice::Descending { start, end, duration } => {
+				let num = now.saturating_sub(listing.listed_at).min(duration).saturated_into();
+				let through = FixedU64::from_rational(num, duration.saturated_into());
+				start.saturating_sub(through.saturating_mul_int(start.saturating_sub(end)))
+			},
+		}
+	}
+
+	/// Remove the listing of a Region ending at `end`, if there is any.
+	pub(crate) fn cancel_listing(region_id: &RegionId, end: Timeslice) {
+		if Listings::<T>::take(end, region_id).is_some() {
+			Self::deposit_event(Event::RegionUnlisted { region_id: *region_id });
+		}
+	}
+
 	pub(crate) fn charge(who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
 		let credit = T::Currency::withdraw(&who, amount, Exact, Expendable, Polite)?;
 		T::OnRevenue::on_unbalanced(credit);
@@ -97,6 +116,7 @@ impl<T: Config> Pallet<T> {
 			ensure!(check_owner == region.owner, Error::<T>::NotOwner);
 		}
 
+		Self::cancel_listing(&region_id, region.end);
 		Regions::<T>::remove(&region_id);
 
 		let last_committed_timeslice = status.last_committed_timeslice;
@@ -118,4 +138,30 @@ impl<T: Config> Pallet<T> {
 
 		Ok(Some((region_id, region)))
 	}
+
+	/// Whether `who` may change the auto-renewal `record`: either its payer or the sovereign
+	/// account of its task.
+	pub(crate) fn can_manage_auto_renewal(
+		record: &AutoRenewalRecordOf<T>,
+		who: &T::AccountId,
+	) -> bool {
+		&record.payer == who ||
+			T::SovereignAccountOf::maybe_convert(record.task).as_ref() == Some(who)
+	}
+
+	/// Ensure that the workload of the renewal `record` consists only of `task`, occupying the
+	/// whole core.
+	pub(crate) fn ensure_task_workload(
+		record: &AllowedRenewalRecordOf<T>,
+		task: TaskId,
+	) -> DispatchResult {
+		let workload = record.completion.complete().ok_or(Error::<T>::IncompleteAssignment)?;
+		ensure!(
+			workload.len() == 1 &&
+				workload[0].mask == CoreMask::complete() &&
+				workload[0].assignment == CoreAssignment::Task(task),
+			Error::<T>::NonTaskAutoRenewal
+		);
+		Ok(())
+	}
 }
@@ -0,0 +1,42 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for conviction-voting pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_conviction_voting::VotingSummary;
+
+sp_api::decl_runtime_apis! {
+	/// Runtime api for accessing the voting activity of accounts.
+	pub trait ConvictionVotingApi<AccountId, Balance, BlockNumber, Class, PollIndex>
+		where
+			AccountId: Codec,
+			Balance: Codec,
+			BlockNumber: Codec,
+			Class: Codec,
+			PollIndex: Codec,
+	{
+		/// Returns the votes and delegations of `who` per class, along with the locks which
+		/// expire at a known block, the votes which can be removed with `remove_vote` and the
+		/// aggregate locked balance.
+		fn voting_summary(
+			who: AccountId,
+		) -> VotingSummary<Class, AccountId, Balance, BlockNumber, PollIndex>;
+	}
+}
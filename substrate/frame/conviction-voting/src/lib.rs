@@ -50,7 +50,10 @@ pub mod weights;
 pub use self::{
 	conviction::Conviction,
 	pallet::*,
-	types::{Delegations, Tally, UnvoteScope},
+	types::{
+		ClassSummary, ClassVoting, Delegations, ExpiringLock, PollVote, Tally, UnvoteScope,
+		VoteStatus, VotingSummary,
+	},
 	vote::{AccountVote, Casting, Delegating, Vote, Voting},
 	weights::WeightInfo,
 };
@@ -82,6 +85,13 @@ type PollIndexOf<T, I = ()> = <<T as Config<I>>::Polls as Polling<TallyOf<T, I>>
 #[cfg(feature = "runtime-benchmarks")]
 type IndexOf<T, I = ()> = <<T as Config<I>>::Polls as Polling<TallyOf<T, I>>>::Index;
 type ClassOf<T, I = ()> = <<T as Config<I>>::Polls as Polling<TallyOf<T, I>>>::Class;
+pub type VotingSummaryOf<T, I = ()> = VotingSummary<
+	ClassOf<T, I>,
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T, I>,
+	BlockNumberFor<T>,
+	PollIndexOf<T, I>,
+>;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -488,6 +498,95 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		})
 	}
 
+	/// Return a summary of the voting activity of `who` across all classes.
+	///
+	/// This covers the votes and delegations per class, the locks which expire at a known block,
+	/// the votes which can be removed with `remove_vote` and the aggregate locked balance.
+	pub fn voting_summary(who: &T::AccountId) -> VotingSummaryOf<T, I> {
+		let now = frame_system::Pallet::<T>::block_number();
+		let class_locks = ClassLocksFor::<T, I>::get(who);
+		let mut summary = VotingSummary {
+			classes: Vec::new(),
+			expiring_locks: Vec::new(),
+			unlockable: Vec::new(),
+			locked: class_locks.iter().map(|x| x.1).max().unwrap_or(Zero::zero()),
+		};
+		let mut votings = VotingFor::<T, I>::iter_prefix(who).collect::<Vec<_>>();
+		votings.sort_by(|a, b| a.0.cmp(&b.0));
+		for (class, voting) in votings {
+			let (voting, prior) = match voting {
+				Voting::Casting(Casting { votes, delegations, prior }) => {
+					let mut poll_votes = Vec::with_capacity(votes.len());
+					for (poll_index, vote) in votes {
+						let status = Self::vote_status(poll_index, vote, now);
+						match status {
+							VoteStatus::Ongoing => {},
+							VoteStatus::Unlockable =>
+								summary.unlockable.push((class.clone(), poll_index)),
+							VoteStatus::LockedUntil(unlock_at) =>
+								summary.expiring_locks.push(ExpiringLock {
+									class: class.clone(),
+									poll_index: Some(poll_index),
+									amount: vote.balance(),
+									unlock_at,
+								}),
+						}
+						poll_votes.push(PollVote { poll_index, vote, status });
+					}
+					(ClassVoting::Casting { votes: poll_votes, delegations }, prior)
+				},
+				Voting::Delegating(Delegating {
+					balance,
+					target,
+					conviction,
+					delegations,
+					prior,
+				}) => (ClassVoting::Delegating { target, balance, conviction, delegations }, prior),
+			};
+			let prior = (!prior.locked().is_zero()).then(|| (prior.until(), prior.locked()));
+			if let Some((unlock_at, amount)) = prior {
+				summary.expiring_locks.push(ExpiringLock {
+					class: class.clone(),
+					poll_index: None,
+					amount,
+					unlock_at,
+				});
+			}
+			let locked = class_locks.iter().find(|x| x.0 == class).map_or(Zero::zero(), |x| x.1);
+			summary.classes.push(ClassSummary { class, voting, prior, locked });
+		}
+		summary
+	}
+
+	/// Determine the state of the `vote` cast on poll `poll_index` as of block `now`.
+	fn vote_status(
+		poll_index: PollIndexOf<T, I>,
+		vote: AccountVote<BalanceOf<T, I>>,
+		now: BlockNumberFor<T>,
+	) -> VoteStatus<BlockNumberFor<T>> {
+		// Accessing an ongoing poll may alter its state, so it is checked for separately.
+		if T::Polls::as_ongoing(poll_index).is_some() {
+			return VoteStatus::Ongoing
+		}
+		T::Polls::access_poll(poll_index, |poll_status| match poll_status {
+			PollStatus::Ongoing(..) => VoteStatus::Ongoing,
+			PollStatus::Completed(end, approved) => match vote.locked_if(approved) {
+				Some((lock_periods, _)) => {
+					let unlock_at = end.saturating_add(
+						T::VoteLockingPeriod::get().saturating_mul(lock_periods.into()),
+					);
+					if now < unlock_at {
+						VoteStatus::LockedUntil(unlock_at)
+					} else {
+						VoteStatus::Unlockable
+					}
+				},
+				None => VoteStatus::Unlockable,
+			},
+			PollStatus::None => VoteStatus::Unlockable,
+		})
+	}
+
 	/// Return the number of votes for `who`.
 	fn increase_upstream_delegation(
 		who: &T::AccountId,
@@ -881,3 +881,109 @@ fn errors_with_remove_vote_work() {
 		);
 	});
 }
+
+#[test]
+fn voting_summary_works() {
+	new_test_ext().execute_with(|| {
+		Polls::set(
+			vec![
+				(3, Ongoing(Tally::new(0), 0)),
+				(4, Ongoing(Tally::new(0), 0)),
+				(5, Ongoing(Tally::new(0), 0)),
+			]
+			.into_iter()
+			.collect(),
+		);
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 3, aye(10, 2)));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 4, nay(5, 1)));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 5, aye(2, 0)));
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(1), 1, 2, Conviction::Locked1x, 3));
+		Polls::set(
+			vec![
+				(3, Completed(1, true)),
+				(4, Completed(1, true)),
+				(5, Ongoing(Tally::from_parts(2, 0, 2), 0)),
+			]
+			.into_iter()
+			.collect(),
+		);
+		run_to(2);
+
+		assert_eq!(
+			Voting::voting_summary(&1),
+			VotingSummary {
+				classes: vec![
+					ClassSummary {
+						class: 0,
+						voting: ClassVoting::Casting {
+							votes: vec![
+								PollVote {
+									poll_index: 3,
+									vote: aye(10, 2),
+									status: VoteStatus::LockedUntil(7),
+								},
+								PollVote {
+									poll_index: 4,
+									vote: nay(5, 1),
+									status: VoteStatus::Unlockable,
+								},
+								PollVote {
+									poll_index: 5,
+									vote: aye(2, 0),
+									status: VoteStatus::Ongoing,
+								},
+							],
+							delegations: Default::default(),
+						},
+						prior: None,
+						locked: 10,
+					},
+					ClassSummary {
+						class: 1,
+						voting: ClassVoting::Delegating {
+							target: 2,
+							balance: 3,
+							conviction: Conviction::Locked1x,
+							delegations: Default::default(),
+						},
+						prior: None,
+						locked: 3,
+					},
+				],
+				expiring_locks: vec![ExpiringLock {
+					class: 0,
+					poll_index: Some(3),
+					amount: 10,
+					unlock_at: 7,
+				}],
+				unlockable: vec![(0, 4)],
+				locked: 10,
+			}
+		);
+		// The delegate sees the delegation it received.
+		assert_eq!(
+			Voting::voting_summary(&2).classes,
+			vec![ClassSummary {
+				class: 1,
+				voting: ClassVoting::Casting {
+					votes: vec![],
+					delegations: Delegations { votes: 3, capital: 3 },
+				},
+				prior: None,
+				locked: 0,
+			}]
+		);
+
+		// Removing a vote which is still locked turns it into a prior lock.
+		assert_ok!(Voting::remove_vote(RuntimeOrigin::signed(1), Some(0), 3));
+		assert_ok!(Voting::remove_vote(RuntimeOrigin::signed(1), Some(0), 4));
+		let summary = Voting::voting_summary(&1);
+		assert_eq!(summary.classes[0].prior, Some((7, 10)));
+		assert_eq!(
+			summary.expiring_locks,
+			vec![ExpiringLock { class: 0, poll_index: None, amount: 10, unlock_at: 7 }]
+		);
+		assert!(summary.unlockable.is_empty());
+		assert_eq!(summary.locked, 10);
+	});
+}
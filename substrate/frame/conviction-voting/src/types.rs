@@ -262,3 +262,92 @@ pub enum UnvoteScope {
 	/// Permitted to do only the changes that do not need the owner's permission.
 	OnlyExpired,
 }
+
+/// The state of a single vote of an account, as seen from the poll it was cast on.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum VoteStatus<BlockNumber> {
+	/// The poll is ongoing.
+	Ongoing,
+	/// The poll is over or was cancelled and the vote no longer requires any funds to be locked.
+	/// It can be removed with `remove_vote`.
+	Unlockable,
+	/// The poll is over and the vote was cast with conviction on the winning side. Its balance
+	/// stays locked until the given block.
+	LockedUntil(BlockNumber),
+}
+
+/// A vote cast by an account on a poll, along with its current state.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PollVote<PollIndex, Balance, BlockNumber> {
+	/// The poll which was voted on.
+	pub poll_index: PollIndex,
+	/// The vote itself.
+	pub vote: AccountVote<Balance>,
+	/// The state of the vote, given the state of the poll.
+	pub status: VoteStatus<BlockNumber>,
+}
+
+/// What an account is doing within a voting class.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum ClassVoting<AccountId, Balance, BlockNumber, PollIndex> {
+	/// The account is voting directly.
+	Casting {
+		/// The votes of the account, ordered by poll index.
+		votes: Vec<PollVote<PollIndex, Balance, BlockNumber>>,
+		/// The delegations that the account has received.
+		delegations: Delegations<Balance>,
+	},
+	/// The account is delegating its voting power.
+	Delegating {
+		/// The account to which the voting power is delegated.
+		target: AccountId,
+		/// The amount of balance delegated.
+		balance: Balance,
+		/// The conviction with which the voting power is delegated.
+		conviction: Conviction,
+		/// The delegations that the account has received.
+		delegations: Delegations<Balance>,
+	},
+}
+
+/// The voting activity of an account within a single voting class.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ClassSummary<Class, AccountId, Balance, BlockNumber, PollIndex> {
+	/// The voting class.
+	pub class: Class,
+	/// The votes or delegation of the account in this class.
+	pub voting: ClassVoting<AccountId, Balance, BlockNumber, PollIndex>,
+	/// The lock left over from past voting or delegating, as the block at which it expires and
+	/// the amount locked, if any.
+	pub prior: Option<(BlockNumber, Balance)>,
+	/// The amount which is currently locked on behalf of this class.
+	pub locked: Balance,
+}
+
+/// A lock of an account which expires at a known block.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ExpiringLock<Class, PollIndex, Balance, BlockNumber> {
+	/// The voting class of the lock.
+	pub class: Class,
+	/// The poll whose vote requires the lock, or `None` for a lock left over from past voting or
+	/// delegating.
+	pub poll_index: Option<PollIndex>,
+	/// The amount locked.
+	pub amount: Balance,
+	/// The block from which the lock may be removed.
+	pub unlock_at: BlockNumber,
+}
+
+/// The voting activity of an account across all voting classes.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct VotingSummary<Class, AccountId, Balance, BlockNumber, PollIndex> {
+	/// The votes and delegations of the account, per class.
+	pub classes: Vec<ClassSummary<Class, AccountId, Balance, BlockNumber, PollIndex>>,
+	/// The locks of the account which expire at a known block.
+	pub expiring_locks: Vec<ExpiringLock<Class, PollIndex, Balance, BlockNumber>>,
+	/// The votes which can be removed with `remove_vote` without leaving any lock behind. A
+	/// following call to `unlock` may then free up funds.
+	pub unlockable: Vec<(Class, PollIndex)>,
+	/// The aggregate balance locked by this pallet across all classes.
+	pub locked: Balance,
+}
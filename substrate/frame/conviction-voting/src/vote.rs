@@ -141,6 +141,11 @@ impl<BlockNumber: Ord + Copy + Zero, Balance: Ord + Copy + Zero> PriorLock<Block
 		self.1
 	}
 
+	/// The block number from which the lock may be removed.
+	pub fn until(&self) -> BlockNumber {
+		self.0
+	}
+
 	pub fn rejig(&mut self, now: BlockNumber) {
 		if now >= self.0 {
 			self.0 = Zero::zero();
@@ -0,0 +1,1081 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for pallet-delegated-staking.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok, traits::fungible::InspectHold};
+use pallet_nomination_pools::{Error as PoolsError, Event as PoolsEvent};
+use pallet_staking::{Error as StakingError, RewardDestination};
+use sp_staking::{DelegationInterface, Stake, StakingInterface};
+
+#[test]
+fn create_an_agent_with_first_delegator() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent: AccountId = 200;
+		let reward_account: AccountId = 201;
+		let delegator: AccountId = 202;
+
+		// set intention to accept delegation.
+		fund(&agent, 1000);
+		assert_ok!(DelegatedStaking::register_agent(
+			RawOrigin::Signed(agent).into(),
+			reward_account
+		));
+
+		// delegate to this account
+		fund(&delegator, 1000);
+		assert_ok!(DelegatedStaking::delegate_to_agent(
+			RawOrigin::Signed(delegator).into(),
+			agent,
+			100
+		));
+
+		// verify
+		assert!(DelegatedStaking::is_agent(&agent));
+		assert_eq!(DelegatedStaking::stakeable_balance(&agent), 100);
+		assert_eq!(
+			Balances::balance_on_hold(&HoldReason::StakingDelegation.into(), &delegator),
+			100
+		);
+		assert_eq!(DelegatedStaking::held_balance_of(&delegator), 100);
+		assert_eq!(
+			events_since_last_call(),
+			vec![Event::Delegated { agent, delegator, amount: 100 }]
+		);
+	});
+}
+
+#[test]
+fn cannot_become_agent() {
+	ExtBuilder::default().build_and_execute(|| {
+		// cannot set reward account same as agent account
+		assert_noop!(
+			DelegatedStaking::register_agent(RawOrigin::Signed(100).into(), 100),
+			Error::<T>::InvalidRewardDestination
+		);
+
+		// an existing validator cannot become agent
+		assert_noop!(
+			DelegatedStaking::register_agent(RawOrigin::Signed(GENESIS_VALIDATOR).into(), 100),
+			Error::<T>::AlreadyStaking
+		);
+
+		// an existing direct staker to `CoreStaking` cannot become an agent.
+		assert_noop!(
+			DelegatedStaking::register_agent(RawOrigin::Signed(GENESIS_NOMINATOR_ONE).into(), 100),
+			Error::<T>::AlreadyStaking
+		);
+		assert_noop!(
+			DelegatedStaking::register_agent(RawOrigin::Signed(GENESIS_NOMINATOR_TWO).into(), 100),
+			Error::<T>::AlreadyStaking
+		);
+	});
+}
+
+#[test]
+fn create_multiple_delegators() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent: AccountId = 200;
+		let reward_account: AccountId = 201;
+
+		// stakeable balance is 0 for non agent
+		fund(&agent, 1000);
+		assert!(!DelegatedStaking::is_agent(&agent));
+		assert_eq!(DelegatedStaking::stakeable_balance(&agent), 0);
+
+		// set intention to accept delegation.
+		assert_ok!(DelegatedStaking::register_agent(
+			RawOrigin::Signed(agent).into(),
+			reward_account
+		));
+
+		// create 100 delegators
+		for i in 202..302 {
+			fund(&i, 100 + ExistentialDeposit::get());
+			assert_ok!(DelegatedStaking::delegate_to_agent(
+				RawOrigin::Signed(i).into(),
+				agent,
+				100
+			));
+			// Balance of 100 held on delegator account for delegating to the agent.
+			assert_eq!(DelegatedStaking::held_balance_of(&i), 100);
+		}
+
+		// verify
+		assert!(DelegatedStaking::is_agent(&agent));
+		assert_eq!(DelegatedStaking::stakeable_balance(&agent), 100 * 100);
+	});
+}
+
+#[test]
+fn agent_restrictions() {
+	// Similar to creating a nomination pool
+	ExtBuilder::default().build_and_execute(|| {
+		let agent_one = 200;
+		let delegator_one = 210;
+		fund(&agent_one, 100);
+		assert_ok!(DelegatedStaking::register_agent(
+			RawOrigin::Signed(agent_one).into(),
+			agent_one + 1
+		));
+		fund(&delegator_one, 200);
+		assert_ok!(DelegatedStaking::delegate_to_agent(
+			RawOrigin::Signed(delegator_one).into(),
+			agent_one,
+			100
+		));
+
+		let agent_two = 300;
+		let delegator_two = 310;
+		fund(&agent_two, 100);
+		assert_ok!(DelegatedStaking::register_agent(
+			RawOrigin::Signed(agent_two).into(),
+			agent_two + 1
+		));
+		fund(&delegator_two, 200);
+		assert_ok!(DelegatedStaking::delegate_to_agent(
+			RawOrigin::Signed(delegator_two).into(),
+			agent_two,
+			100
+		));
+
+		// agent one tries to delegate to agent 2
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(RawOrigin::Signed(agent_one).into(), agent_two, 10),
+			Error::<T>::InvalidDelegation
+		);
+
+		// agent one tries to delegate to a delegator
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(
+				RawOrigin::Signed(agent_one).into(),
+				delegator_one,
+				10
+			),
+			Error::<T>::InvalidDelegation
+		);
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(
+				RawOrigin::Signed(agent_one).into(),
+				delegator_two,
+				10
+			),
+			Error::<T>::InvalidDelegation
+		);
+
+		// delegator one tries to delegate to agent 2 as well (it already delegates to agent
+		// 1)
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(
+				RawOrigin::Signed(delegator_one).into(),
+				agent_two,
+				10
+			),
+			Error::<T>::InvalidDelegation
+		);
+
+		// cannot delegate to non agents.
+		let non_agent = 201;
+		// give it some funds
+		fund(&non_agent, 200);
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(
+				RawOrigin::Signed(delegator_one).into(),
+				non_agent,
+				10
+			),
+			Error::<T>::InvalidDelegation
+		);
+
+		// cannot delegate to a delegator
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(
+				RawOrigin::Signed(delegator_one).into(),
+				delegator_two,
+				10
+			),
+			Error::<T>::InvalidDelegation
+		);
+
+		// delegator cannot delegate to self
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(
+				RawOrigin::Signed(delegator_one).into(),
+				delegator_one,
+				10
+			),
+			Error::<T>::InvalidDelegation
+		);
+
+		// agent cannot delegate to self
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(RawOrigin::Signed(agent_one).into(), agent_one, 10),
+			Error::<T>::InvalidDelegation
+		);
+	});
+}
+
+#[test]
+fn apply_pending_slash() {
+	ExtBuilder::default().build_and_execute(|| {
+		start_era(1);
+		let agent: AccountId = 200;
+		let reward_acc: AccountId = 201;
+		let delegators: Vec<AccountId> = (301..=350).collect();
+		let reporter: AccountId = 400;
+
+		let total_staked = setup_delegation_stake(agent, reward_acc, delegators.clone(), 10, 10);
+
+		start_era(4);
+		// slash half of the stake
+		pallet_staking::slashing::do_slash::<T>(
+			&agent,
+			total_staked / 2,
+			&mut Default::default(),
+			&mut Default::default(),
+			3,
+		);
+
+		// agent cannot slash an account that is not its delegator.
+		setup_delegation_stake(210, 211, (351..=352).collect(), 100, 0);
+		assert_noop!(
+			<DelegatedStaking as DelegationInterface>::delegator_slash(&agent, &351, 1, Some(400)),
+			Error::<T>::NotAgent
+		);
+		// or a non delegator account
+		fund(&353, 100);
+		assert_noop!(
+			<DelegatedStaking as DelegationInterface>::delegator_slash(&agent, &353, 1, Some(400)),
+			Error::<T>::NotDelegator
+		);
+
+		// ensure bookkept pending slash is correct.
+		assert_eq!(get_agent(&agent).ledger.pending_slash, total_staked / 2);
+		fund(&reporter, 100);
+		let mut old_reporter_balance = Balances::free_balance(reporter);
+
+		// lets apply the slash on delegators.
+		for i in delegators {
+			// balance before slash
+			let initial_pending_slash = get_agent(&agent).ledger.pending_slash;
+			assert!(initial_pending_slash > 0);
+			let unslashed_balance = DelegatedStaking::held_balance_of(&i);
+			let slash = unslashed_balance / 2;
+			// slash half of delegator's delegation.
+			assert_ok!(<DelegatedStaking as DelegationInterface>::delegator_slash(
+				&agent,
+				&i,
+				slash,
+				Some(400)
+			));
+
+			// balance after slash.
+			assert_eq!(DelegatedStaking::held_balance_of(&i), unslashed_balance - slash);
+			// pending slash is reduced by the amount slashed.
+			assert_eq!(get_agent(&agent).ledger.pending_slash, initial_pending_slash - slash);
+			// reporter get 10% of the slash amount.
+			assert_eq!(
+				Balances::free_balance(reporter) - old_reporter_balance,
+				SlashRewardFraction::get() * slash,
+			);
+			// update old balance
+			old_reporter_balance = Balances::free_balance(reporter);
+		}
+
+		// nothing to slash anymore
+		assert_eq!(get_agent(&agent).ledger.pending_slash, 0);
+
+		// cannot slash anymore
+		assert_noop!(
+			<DelegatedStaking as DelegationInterface>::delegator_slash(&agent, &350, 1, None),
+			Error::<T>::NothingToSlash
+		);
+	});
+}
+
+/// Integration tests with pallet-staking.
+mod staking_integration {
+	use super::*;
+
+	#[test]
+	fn bond() {
+		ExtBuilder::default().build_and_execute(|| {
+			let agent: AccountId = 99;
+			let reward_acc: AccountId = 100;
+			assert!(Staking::status(&agent).is_err());
+
+			// set intention to become an agent
+			fund(&agent, 100);
+			assert_ok!(DelegatedStaking::register_agent(
+				RawOrigin::Signed(agent).into(),
+				reward_acc
+			));
+			assert_eq!(DelegatedStaking::stakeable_balance(&agent), 0);
+
+			let mut delegated_balance: Balance = 0;
+
+			// set some delegations
+			for delegator in 200..250 {
+				fund(&delegator, 200);
+				assert_ok!(DelegatedStaking::delegate_to_agent(
+					RawOrigin::Signed(delegator).into(),
+					agent,
+					100
+				));
+				delegated_balance += 100;
+				assert_eq!(
+					Balances::balance_on_hold(&HoldReason::StakingDelegation.into(), &delegator),
+					100
+				);
+				assert_eq!(
+					<DelegatedStaking as DelegationInterface>::delegator_balance(&delegator),
+					Some(100)
+				);
+
+				let agent_obj = get_agent(&agent);
+				assert_eq!(agent_obj.ledger.stakeable_balance(), delegated_balance);
+				assert_eq!(agent_obj.available_to_bond(), 0);
+				assert_eq!(agent_obj.bonded_stake(), delegated_balance);
+			}
+
+			assert_eq!(Staking::stake(&agent).unwrap(), Stake { total: 50 * 100, active: 50 * 100 })
+		});
+	}
+
+	#[test]
+	fn withdraw_test() {
+		ExtBuilder::default().build_and_execute(|| {
+			// initial era
+			start_era(1);
+			let agent: AccountId = 200;
+			let reward_acc: AccountId = 201;
+			let delegators: Vec<AccountId> = (301..=350).collect();
+			let total_staked =
+				setup_delegation_stake(agent, reward_acc, delegators.clone(), 10, 10);
+
+			// lets go to a new era
+			start_era(2);
+
+			assert!(eq_stake(agent, total_staked, total_staked));
+			// Withdrawing without unbonding would fail.
+			assert_noop!(
+				DelegatedStaking::release_delegation(RawOrigin::Signed(agent).into(), 301, 50, 0),
+				Error::<T>::NotEnoughFunds
+			);
+
+			// 305 wants to unbond 50 in era 2, withdrawable in era 5.
+			assert_ok!(Staking::unbond(&agent, 50));
+
+			// 310 wants to unbond 100 in era 3, withdrawable in era 6.
+			start_era(3);
+			assert_ok!(Staking::unbond(&agent, 100));
+
+			// 320 wants to unbond 200 in era 4, withdrawable in era 7.
+			start_era(4);
+			assert_ok!(Staking::unbond(&agent, 200));
+
+			// active stake is now reduced..
+			let expected_active = total_staked - (50 + 100 + 200);
+			assert!(eq_stake(agent, total_staked, expected_active));
+
+			// nothing to withdraw at era 4
+			assert_noop!(
+				DelegatedStaking::release_delegation(RawOrigin::Signed(agent).into(), 305, 50, 0),
+				Error::<T>::NotEnoughFunds
+			);
+
+			assert_eq!(get_agent(&agent).available_to_bond(), 0);
+			// full amount is still delegated
+			assert_eq!(get_agent(&agent).ledger.effective_balance(), total_staked);
+
+			start_era(5);
+			// at era 5, 50 tokens are withdrawable.
+			assert_ok!(DelegatedStaking::release_delegation(
+				RawOrigin::Signed(agent).into(),
+				305,
+				50,
+				0
+			));
+			// the withdrawn funds are fully released, nothing more to claim.
+			assert_eq!(get_agent(&agent).ledger.unclaimed_withdrawals, 0);
+			assert_noop!(
+				DelegatedStaking::release_delegation(RawOrigin::Signed(agent).into(), 301, 10, 0),
+				Error::<T>::NotEnoughFunds
+			);
+
+			// at era 7, the rest of the unbonded funds are withdrawable.
+			start_era(7);
+			assert_ok!(DelegatedStaking::release_delegation(
+				RawOrigin::Signed(agent).into(),
+				310,
+				100,
+				0
+			));
+			// the funds unbonded in era 4 are already withdrawn but not claimed yet.
+			assert_eq!(get_agent(&agent).ledger.unclaimed_withdrawals, 200);
+			assert_ok!(DelegatedStaking::release_delegation(
+				RawOrigin::Signed(agent).into(),
+				320,
+				200,
+				0
+			));
+
+			// all delegations of these delegators are released.
+			for delegator in [305, 310, 320] {
+				assert!(!DelegatedStaking::is_delegator(&delegator));
+				assert_eq!(DelegatedStaking::held_balance_of(&delegator), 0);
+			}
+			assert_eq!(Balances::free_balance(320), 200 + ExistentialDeposit::get());
+
+			// stake is reduced by the withdrawn amount.
+			let expected_total = total_staked - (50 + 100 + 200);
+			assert!(eq_stake(agent, expected_total, expected_total));
+			assert_eq!(get_agent(&agent).available_to_bond(), 0);
+			assert_eq!(get_agent(&agent).ledger.unclaimed_withdrawals, 0);
+
+			assert_eq!(
+				events_since_last_call().into_iter().rev().take(3).collect::<Vec<_>>(),
+				vec![
+					Event::Released { agent, delegator: 320, amount: 200 },
+					Event::Released { agent, delegator: 310, amount: 100 },
+					Event::Released { agent, delegator: 305, amount: 50 },
+				]
+			);
+		});
+	}
+
+	#[test]
+	fn withdraw_happens_with_unbonded_balance_first() {
+		ExtBuilder::default().build_and_execute(|| {
+			start_era(1);
+			let agent = 200;
+			setup_delegation_stake(agent, 201, (300..350).collect(), 100, 0);
+
+			// verify withdraw not possible yet
+			assert_noop!(
+				DelegatedStaking::release_delegation(RawOrigin::Signed(agent).into(), 300, 100, 0),
+				Error::<T>::NotEnoughFunds
+			);
+
+			// fill up unlocking chunks in core staking.
+			// 10 is the max chunks
+			for i in 2..=11 {
+				start_era(i);
+				assert_ok!(Staking::unbond(&agent, 10));
+				// no withdrawals from core staking yet.
+				assert_eq!(get_agent(&agent).ledger.unclaimed_withdrawals, 0);
+			}
+
+			// another unbond would trigger withdrawal
+			start_era(12);
+			assert_ok!(Staking::unbond(&agent, 10));
+
+			// 8 previous unbonds would be withdrawn as they were already unlocked. Unlocking period
+			// is 3 eras.
+			assert_eq!(get_agent(&agent).ledger.unclaimed_withdrawals, 8 * 10);
+
+			// release some delegation now.
+			assert_ok!(DelegatedStaking::release_delegation(
+				RawOrigin::Signed(agent).into(),
+				300,
+				40,
+				0
+			));
+			assert_eq!(get_agent(&agent).ledger.unclaimed_withdrawals, 80 - 40);
+
+			// cannot release more than available
+			assert_noop!(
+				DelegatedStaking::release_delegation(RawOrigin::Signed(agent).into(), 300, 50, 0),
+				Error::<T>::NotEnoughFunds
+			);
+			assert_ok!(DelegatedStaking::release_delegation(
+				RawOrigin::Signed(agent).into(),
+				300,
+				40,
+				0
+			));
+
+			assert_eq!(DelegatedStaking::held_balance_of(&300), 100 - 80);
+		});
+	}
+
+	#[test]
+	fn reward_destination_restrictions() {
+		ExtBuilder::default().build_and_execute(|| {
+			// give some funds to 200
+			fund(&200, 1000);
+			let balance_200 = Balances::free_balance(200);
+
+			// `Agent` account cannot be reward destination
+			assert_noop!(
+				DelegatedStaking::register_agent(RawOrigin::Signed(200).into(), 200),
+				Error::<T>::InvalidRewardDestination
+			);
+
+			// different reward account works
+			assert_ok!(DelegatedStaking::register_agent(RawOrigin::Signed(200).into(), 201));
+			// add some delegations to it
+			fund(&300, 1000);
+			assert_ok!(DelegatedStaking::delegate_to_agent(
+				RawOrigin::Signed(300).into(),
+				200,
+				100
+			));
+
+			// update_payee to self fails.
+			assert_noop!(
+				<Staking as StakingInterface>::update_payee(&200, &200),
+				StakingError::<T>::RewardDestinationRestricted
+			);
+
+			// passing correct reward destination works
+			assert_ok!(<Staking as StakingInterface>::update_payee(&200, &201));
+
+			// amount is staked correctly
+			assert!(eq_stake(200, 100, 100));
+			assert_eq!(get_agent(&200).available_to_bond(), 0);
+			assert_eq!(get_agent(&200).ledger.effective_balance(), 100);
+
+			// free balance of delegate is untouched
+			assert_eq!(Balances::free_balance(200), balance_200);
+		});
+	}
+
+	#[test]
+	fn agent_restrictions() {
+		ExtBuilder::default().build_and_execute(|| {
+			setup_delegation_stake(200, 201, (202..203).collect(), 100, 0);
+
+			// Registering again is noop
+			assert_noop!(
+				DelegatedStaking::register_agent(RawOrigin::Signed(200).into(), 201),
+				Error::<T>::NotAllowed
+			);
+			// a delegator cannot become delegate
+			assert_noop!(
+				DelegatedStaking::register_agent(RawOrigin::Signed(202).into(), 203),
+				Error::<T>::NotAllowed
+			);
+			// existing staker cannot become a delegate
+			assert_noop!(
+				DelegatedStaking::register_agent(
+					RawOrigin::Signed(GENESIS_NOMINATOR_ONE).into(),
+					201
+				),
+				Error::<T>::AlreadyStaking
+			);
+			assert_noop!(
+				DelegatedStaking::register_agent(RawOrigin::Signed(GENESIS_VALIDATOR).into(), 201),
+				Error::<T>::AlreadyStaking
+			);
+		});
+	}
+
+	#[test]
+	fn migration_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			// add a nominator
+			let staked_amount = 4000;
+			let agent_amount = 5000;
+			fund(&200, agent_amount);
+
+			assert_ok!(Staking::bond(
+				RuntimeOrigin::signed(200),
+				staked_amount,
+				RewardDestination::Account(201)
+			));
+			assert_ok!(Staking::nominate(RuntimeOrigin::signed(200), vec![GENESIS_VALIDATOR],));
+			let init_stake = Staking::stake(&200).unwrap();
+
+			// scenario: 200 is a pool account, and the stake comes from its 4 delegators (300..304)
+			// in equal parts. lets try to migrate this nominator into delegate based stake.
+
+			// all balance currently is in 200
+			assert_eq!(Balances::total_balance(&200), agent_amount);
+
+			// to migrate, nominator needs to set an account as a proxy delegator where staked funds
+			// will be moved and delegated back to this old nominator account.
+			let proxy_delegator = DelegatedStaking::sub_account(AccountType::ProxyDelegator, 200);
+
+			assert_ok!(DelegatedStaking::migrate_to_agent(RawOrigin::Signed(200).into(), 201));
+
+			// verify all went well
+			let mut expected_proxy_delegated_amount = agent_amount;
+			assert_eq!(
+				Balances::balance_on_hold(&HoldReason::StakingDelegation.into(), &proxy_delegator),
+				expected_proxy_delegated_amount
+			);
+			// stake amount is transferred from delegate to proxy delegator account.
+			assert_eq!(Balances::free_balance(200), 0);
+			assert_eq!(Staking::stake(&200).unwrap(), init_stake);
+			assert_eq!(get_agent(&200).ledger.effective_balance(), agent_amount);
+			assert_eq!(get_agent(&200).available_to_bond(), 0);
+			assert_eq!(get_agent(&200).ledger.unclaimed_withdrawals, agent_amount - staked_amount);
+
+			// now lets migrate the delegators
+			let delegator_share = agent_amount / 4;
+			for delegator in 300..304 {
+				assert_eq!(Balances::free_balance(delegator), 0);
+				// fund them with ED
+				fund(&delegator, ExistentialDeposit::get());
+				// migrate 1/4th amount into each delegator
+				assert_ok!(DelegatedStaking::migrate_delegation(
+					RawOrigin::Signed(200).into(),
+					delegator,
+					delegator_share
+				));
+				assert_eq!(
+					Balances::balance_on_hold(&HoldReason::StakingDelegation.into(), &delegator),
+					delegator_share
+				);
+				expected_proxy_delegated_amount -= delegator_share;
+				assert_eq!(
+					Balances::balance_on_hold(
+						&HoldReason::StakingDelegation.into(),
+						&proxy_delegator
+					),
+					expected_proxy_delegated_amount
+				);
+
+				// delegate stake is unchanged.
+				assert_eq!(Staking::stake(&200).unwrap(), init_stake);
+				assert_eq!(get_agent(&200).ledger.effective_balance(), agent_amount);
+				assert_eq!(get_agent(&200).available_to_bond(), 0);
+				assert_eq!(
+					get_agent(&200).ledger.unclaimed_withdrawals,
+					agent_amount - staked_amount
+				);
+			}
+
+			// all delegations of the proxy delegator are migrated.
+			assert!(!DelegatedStaking::is_delegator(&proxy_delegator));
+
+			// cannot use migrate delegator anymore
+			assert_noop!(
+				DelegatedStaking::migrate_delegation(RawOrigin::Signed(200).into(), 305, 1),
+				Error::<T>::NotEnoughFunds
+			);
+		});
+	}
+}
+
+/// Integration tests with pallet-nomination-pools.
+mod pool_integration {
+	use super::*;
+	use pallet_nomination_pools::{
+		adapter::{StakeStrategy, StakeStrategyType},
+		BondExtra, LastPoolId, PoolId,
+	};
+
+	#[test]
+	fn create_pool_test() {
+		ExtBuilder::default().build_and_execute(|| {
+			let creator: AccountId = 100;
+			fund(&creator, 500);
+			let delegate_amount = 200;
+
+			// nothing held initially
+			assert_eq!(DelegatedStaking::held_balance_of(&creator), 0);
+
+			// create pool
+			assert_ok!(Pools::create(
+				RawOrigin::Signed(creator).into(),
+				delegate_amount,
+				creator,
+				creator,
+				creator
+			));
+
+			// correct amount is locked in depositor's account.
+			assert_eq!(DelegatedStaking::held_balance_of(&creator), delegate_amount);
+
+			let pool_account = Pools::create_bonded_account(1);
+			let agent = get_agent(&pool_account);
+
+			// verify state
+			assert_eq!(agent.ledger.effective_balance(), delegate_amount);
+			assert_eq!(agent.available_to_bond(), 0);
+			assert_eq!(agent.total_unbonded(), 0);
+
+			assert_eq!(
+				pool_events_since_last_call(),
+				vec![
+					PoolsEvent::Created { depositor: creator, pool_id: 1 },
+					PoolsEvent::Bonded {
+						member: creator,
+						pool_id: 1,
+						bonded: delegate_amount,
+						joined: true,
+					}
+				]
+			);
+		});
+	}
+
+	#[test]
+	fn join_pool() {
+		ExtBuilder::default().build_and_execute(|| {
+			// create a pool
+			let pool_id = create_pool(100, 200);
+			// keep track of staked amount.
+			let mut staked_amount: Balance = 200;
+
+			// fund delegator
+			let delegator: AccountId = 300;
+			fund(&delegator, 500);
+			// nothing held initially
+			assert_eq!(DelegatedStaking::held_balance_of(&delegator), 0);
+
+			// delegator joins pool
+			assert_ok!(Pools::join(RawOrigin::Signed(delegator).into(), 100, pool_id));
+			staked_amount += 100;
+
+			// correct amount is locked in depositor's account.
+			assert_eq!(DelegatedStaking::held_balance_of(&delegator), 100);
+
+			// delegator is not actively exposed to core staking.
+			assert_eq!(Staking::status(&delegator), Err(StakingError::<T>::NotStash.into()));
+
+			let pool_agent = get_agent(&Pools::create_bonded_account(1));
+			// verify state
+			assert_eq!(pool_agent.ledger.effective_balance(), staked_amount);
+			assert_eq!(pool_agent.bonded_stake(), staked_amount);
+			assert_eq!(pool_agent.available_to_bond(), 0);
+			assert_eq!(pool_agent.total_unbonded(), 0);
+
+			// cannot reap agent in staking.
+			assert_noop!(
+				Staking::reap_stash(RuntimeOrigin::signed(100), pool_agent.key, 0),
+				StakingError::<T>::VirtualStakerNotAllowed
+			);
+
+			// let a bunch of delegators join this pool
+			for i in 301..350 {
+				fund(&i, 500);
+				assert_ok!(Pools::join(RawOrigin::Signed(i).into(), 100 + i, pool_id));
+				staked_amount += 100 + i;
+				assert_eq!(DelegatedStaking::held_balance_of(&i), 100 + i);
+			}
+
+			let pool_agent = pool_agent.refresh().unwrap();
+			assert_eq!(pool_agent.ledger.effective_balance(), staked_amount);
+			assert_eq!(pool_agent.bonded_stake(), staked_amount);
+			assert_eq!(pool_agent.available_to_bond(), 0);
+			assert_eq!(pool_agent.total_unbonded(), 0);
+		});
+	}
+
+	#[test]
+	fn bond_extra_to_pool() {
+		ExtBuilder::default().build_and_execute(|| {
+			let pool_id = create_pool(100, 200);
+			add_delegators_to_pool(pool_id, (300..310).collect(), 100);
+			let mut staked_amount = 200 + 100 * 10;
+			assert_eq!(get_pool_agent(pool_id).bonded_stake(), staked_amount);
+
+			// bond extra to pool
+			for i in 300..310 {
+				assert_ok!(Pools::bond_extra(
+					RawOrigin::Signed(i).into(),
+					BondExtra::FreeBalance(50)
+				));
+				staked_amount += 50;
+				assert_eq!(get_pool_agent(pool_id).bonded_stake(), staked_amount);
+				assert_eq!(DelegatedStaking::held_balance_of(&i), 150);
+			}
+		});
+	}
+
+	#[test]
+	fn withdraw_from_pool() {
+		ExtBuilder::default().build_and_execute(|| {
+			// initial era
+			start_era(1);
+
+			let pool_id = create_pool(100, 1000);
+			let bond_amount = 200;
+			add_delegators_to_pool(pool_id, (300..310).collect(), bond_amount);
+			let total_staked = 1000 + bond_amount * 10;
+			let pool_acc = Pools::create_bonded_account(pool_id);
+
+			start_era(2);
+			// nothing to release yet.
+			assert_noop!(
+				Pools::withdraw_unbonded(RawOrigin::Signed(301).into(), 301, 0),
+				PoolsError::<T>::SubPoolsNotFound
+			);
+
+			// 301 wants to unbond 50 in era 2, withdrawable in era 5.
+			assert_ok!(Pools::unbond(RawOrigin::Signed(301).into(), 301, 50));
+
+			// 302 wants to unbond 100 in era 3, withdrawable in era 6.
+			start_era(3);
+			assert_ok!(Pools::unbond(RawOrigin::Signed(302).into(), 302, 100));
+
+			// 303 wants to unbond 200 in era 4, withdrawable in era 7.
+			start_era(4);
+			assert_ok!(Pools::unbond(RawOrigin::Signed(303).into(), 303, 200));
+
+			// active stake is now reduced..
+			let expected_active = total_staked - (50 + 100 + 200);
+			assert!(eq_stake(pool_acc, total_staked, expected_active));
+
+			// nothing to withdraw at era 4
+			for i in 301..310 {
+				assert_noop!(
+					Pools::withdraw_unbonded(RawOrigin::Signed(i).into(), i, 0),
+					PoolsError::<T>::CannotWithdrawAny
+				);
+			}
+
+			assert!(eq_stake(pool_acc, total_staked, expected_active));
+
+			start_era(5);
+			// at era 5, 301 can withdraw.
+			let held_301 = DelegatedStaking::held_balance_of(&301);
+			let free_301 = Balances::free_balance(301);
+
+			assert_ok!(Pools::withdraw_unbonded(RawOrigin::Signed(301).into(), 301, 0));
+			assert_eq!(DelegatedStaking::held_balance_of(&301), held_301 - 50);
+			assert_eq!(Balances::free_balance(301), free_301 + 50);
+
+			start_era(7);
+			// era 7 both delegators can withdraw.
+			assert_ok!(Pools::withdraw_unbonded(RawOrigin::Signed(302).into(), 302, 0));
+			assert_ok!(Pools::withdraw_unbonded(RawOrigin::Signed(303).into(), 303, 0));
+
+			assert_eq!(DelegatedStaking::held_balance_of(&302), bond_amount - 100);
+			// 303 fully unbonded and left the pool.
+			assert_eq!(DelegatedStaking::held_balance_of(&303), 0);
+			assert!(!DelegatedStaking::is_delegator(&303));
+		});
+	}
+
+	#[test]
+	fn pool_slashes_are_applied_lazily() {
+		ExtBuilder::default().build_and_execute(|| {
+			start_era(1);
+			let creator = 100;
+			let creator_stake = 500;
+			let pool_id = create_pool(creator, creator_stake);
+			let delegator_stake = 100;
+			add_delegators_to_pool(pool_id, (300..306).collect(), delegator_stake);
+			let pool_acc = Pools::create_bonded_account(pool_id);
+
+			let total_staked = creator_stake + delegator_stake * 6;
+			assert_eq!(Staking::stake(&pool_acc).unwrap().total, total_staked);
+
+			// slash half of the pool stake.
+			pallet_staking::slashing::do_slash::<T>(
+				&pool_acc,
+				total_staked / 2,
+				&mut Default::default(),
+				&mut Default::default(),
+				1,
+			);
+
+			// the slash is only booked against the pool.
+			assert_eq!(
+				<DelegatedStaking as DelegationInterface>::pending_slash(&pool_acc),
+				total_staked / 2
+			);
+			for i in 300..306 {
+				assert_eq!(DelegatedStaking::held_balance_of(&i), delegator_stake);
+			}
+
+			// 300 leaves the pool. Their pending slash is applied when they withdraw.
+			start_era(2);
+			assert_ok!(Pools::unbond(RawOrigin::Signed(300).into(), 300, delegator_stake));
+			start_era(5);
+			assert_ok!(Pools::withdraw_unbonded(RawOrigin::Signed(300).into(), 300, 0));
+			assert_eq!(DelegatedStaking::held_balance_of(&300), 0);
+			// funded with 200, half of the 100 delegated is slashed.
+			assert_eq!(Balances::free_balance(300), 200 - delegator_stake / 2);
+			assert_eq!(
+				<DelegatedStaking as DelegationInterface>::pending_slash(&pool_acc),
+				total_staked / 2 - delegator_stake / 2
+			);
+
+			// anyone can apply the pending slash of the other members and get rewarded for it.
+			let reporter = 400;
+			fund(&reporter, 100);
+			for i in 301..306 {
+				let pre_reporter_balance = Balances::free_balance(reporter);
+				assert_ok!(Pools::apply_slash(RawOrigin::Signed(reporter).into(), i));
+				assert_eq!(DelegatedStaking::held_balance_of(&i), delegator_stake / 2);
+				assert_eq!(
+					Balances::free_balance(reporter),
+					pre_reporter_balance + SlashRewardFraction::get() * (delegator_stake / 2)
+				);
+
+				// slash cannot be applied twice.
+				assert_noop!(
+					Pools::apply_slash(RawOrigin::Signed(reporter).into(), i),
+					PoolsError::<T>::NothingToSlash
+				);
+			}
+
+			// the creator's slash is applied as well.
+			assert_ok!(Pools::apply_slash(RawOrigin::Signed(reporter).into(), creator));
+			assert_eq!(DelegatedStaking::held_balance_of(&creator), creator_stake / 2);
+
+			// no pending slash left.
+			assert_eq!(<DelegatedStaking as DelegationInterface>::pending_slash(&pool_acc), 0);
+			assert_noop!(
+				Pools::apply_slash(RawOrigin::Signed(reporter).into(), creator),
+				PoolsError::<T>::NothingToSlash
+			);
+		});
+	}
+
+	#[test]
+	fn pool_migration_e2e() {
+		ExtBuilder::default().build_and_execute(|| {
+			start_era(1);
+
+			// create a pool with the legacy transfer stake strategy.
+			LegacyAdapter::set(true);
+			assert_eq!(MockAdapter::strategy_type(), StakeStrategyType::Transfer);
+			let creator = 100;
+			let creator_stake = 1000;
+			let pool_id = create_pool(creator, creator_stake);
+			let delegator_stake = 100;
+			add_delegators_to_pool(pool_id, (300..310).collect(), delegator_stake);
+			let pool_acc = Pools::create_bonded_account(pool_id);
+			let total_staked = creator_stake + delegator_stake * 10;
+			// the pool account keeps an existential deposit free next to the held stake.
+			let ed = ExistentialDeposit::get();
+
+			// all funds are transferred to the pool account.
+			assert_eq!(Balances::total_balance(&pool_acc), total_staked + ed);
+			assert_eq!(
+				Staking::stake(&pool_acc).unwrap(),
+				Stake { total: total_staked, active: total_staked }
+			);
+			assert_eq!(DelegatedStaking::held_balance_of(&300), 0);
+
+			// migration is not possible with transfer stake strategy.
+			assert_noop!(
+				Pools::migrate_pool_to_delegate_stake(RawOrigin::Signed(creator).into(), pool_id),
+				PoolsError::<T>::NotSupported
+			);
+
+			// switch to delegate stake strategy.
+			LegacyAdapter::set(false);
+
+			// members cannot unbond until the pool and themselves are migrated.
+			assert_noop!(
+				Pools::unbond(RawOrigin::Signed(300).into(), 300, 50),
+				PoolsError::<T>::NotMigrated
+			);
+			// members cannot migrate before the pool.
+			assert_noop!(
+				Pools::migrate_delegation(RawOrigin::Signed(300).into(), 300),
+				PoolsError::<T>::NotMigrated
+			);
+
+			// anyone can migrate the pool.
+			assert_ok!(Pools::migrate_pool_to_delegate_stake(
+				RawOrigin::Signed(400).into(),
+				pool_id
+			));
+			assert_noop!(
+				Pools::migrate_pool_to_delegate_stake(RawOrigin::Signed(400).into(), pool_id),
+				PoolsError::<T>::AlreadyMigrated
+			);
+
+			// funds are held by the proxy delegator now.
+			let proxy_delegator =
+				DelegatedStaking::sub_account(AccountType::ProxyDelegator, pool_acc);
+			assert_eq!(DelegatedStaking::held_balance_of(&proxy_delegator), total_staked + ed);
+			assert_eq!(Balances::free_balance(pool_acc), 0);
+			assert_eq!(
+				Staking::stake(&pool_acc).unwrap(),
+				Stake { total: total_staked, active: total_staked }
+			);
+			assert_eq!(get_agent(&pool_acc).ledger.effective_balance(), total_staked + ed);
+
+			// members still need to migrate.
+			assert_noop!(
+				Pools::unbond(RawOrigin::Signed(300).into(), 300, 50),
+				PoolsError::<T>::NotMigrated
+			);
+
+			let mut expected_proxy_balance = total_staked + ed;
+			for i in 300..310 {
+				let pre_migrate_free = Balances::free_balance(i);
+				assert_ok!(Pools::migrate_delegation(RawOrigin::Signed(400).into(), i));
+				expected_proxy_balance -= delegator_stake;
+
+				// member funds are back in their account, held for the pool.
+				assert_eq!(DelegatedStaking::held_balance_of(&i), delegator_stake);
+				assert_eq!(Balances::free_balance(i), pre_migrate_free);
+				assert_eq!(
+					DelegatedStaking::held_balance_of(&proxy_delegator),
+					expected_proxy_balance
+				);
+
+				// cannot migrate twice.
+				assert_noop!(
+					Pools::migrate_delegation(RawOrigin::Signed(400).into(), i),
+					PoolsError::<T>::AlreadyMigrated
+				);
+			}
+
+			// lastly, the creator migrates.
+			assert_ok!(Pools::migrate_delegation(RawOrigin::Signed(creator).into(), creator));
+			assert_eq!(DelegatedStaking::held_balance_of(&creator), creator_stake);
+			// only the existential deposit of the pool account is left with the proxy delegator.
+			assert_eq!(DelegatedStaking::held_balance_of(&proxy_delegator), ed);
+
+			// pool stake is unchanged.
+			assert_eq!(
+				Staking::stake(&pool_acc).unwrap(),
+				Stake { total: total_staked, active: total_staked }
+			);
+
+			// migrated members can unbond.
+			assert_ok!(Pools::unbond(RawOrigin::Signed(300).into(), 300, 50));
+		});
+	}
+
+	fn create_pool(creator: AccountId, amount: Balance) -> PoolId {
+		fund(&creator, amount * 2);
+		assert_ok!(Pools::create(
+			RawOrigin::Signed(creator).into(),
+			amount,
+			creator,
+			creator,
+			creator
+		));
+
+		LastPoolId::<T>::get()
+	}
+
+	fn add_delegators_to_pool(pool_id: PoolId, delegators: Vec<AccountId>, amount: Balance) {
+		for delegator in delegators {
+			fund(&delegator, amount * 2);
+			assert_ok!(Pools::join(RawOrigin::Signed(delegator).into(), amount, pool_id));
+		}
+	}
+
+	fn get_pool_agent(pool_id: PoolId) -> Agent<T> {
+		get_agent(&Pools::create_bonded_account(pool_id))
+	}
+}
@@ -0,0 +1,240 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Multi-block election pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as MultiBlock;
+
+use frame_benchmarking::v2::*;
+use frame_support::{assert_ok, traits::Hooks, BoundedVec};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, SaturatedConversion};
+
+const SEED: u32 = 999;
+
+/// Fill the data provider with `t` targets, and `v` voters that each vote for as many of these
+/// targets as they can.
+fn set_up_data_provider<T: Config>(v: u32, t: u32) {
+	T::DataProvider::clear();
+
+	let targets = (0..t)
+		.map(|i| {
+			let target = account::<T::AccountId>("Target", i, SEED);
+			T::DataProvider::add_target(target.clone());
+			target
+		})
+		.collect::<Vec<_>>();
+
+	let max_votes = <T::DataProvider as ElectionDataProvider>::MaxVotesPerVoter::get() as usize;
+	let votes = BoundedVec::truncate_from(targets.into_iter().take(max_votes).collect());
+	let weight = T::Currency::minimum_balance().saturated_into::<u64>().max(1) * 1000;
+	(0..v).for_each(|i| {
+		let voter = account::<T::AccountId>("Voter", i, SEED);
+		T::DataProvider::add_voter(voter, weight, votes.clone());
+	});
+}
+
+/// Fetch all the pages of the snapshot, which moves the pallet to the signed phase.
+fn create_snapshot<T: Config>() {
+	for page in (MultiBlock::<T>::lsp()..=MultiBlock::<T>::msp()).rev() {
+		MultiBlock::<T>::create_snapshot_page(page);
+	}
+	assert!(CurrentPhase::<T>::get().is_signed());
+}
+
+/// An account that can afford to register and submit all pages of a solution.
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who = account::<T::AccountId>(name, index, SEED);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
+
+/// Mine a solution for the snapshot, and register and submit all of its pages as `who`.
+fn submit_solution<T: Config>(who: &T::AccountId) {
+	let PagedRawSolution { pages, score } =
+		Miner::<T>::mine_paged_solution().expect("the snapshot exists; qed");
+	assert_ok!(MultiBlock::<T>::do_register(who, score));
+	for (page, solution) in pages.into_iter().enumerate() {
+		assert_ok!(MultiBlock::<T>::do_submit_page(who, page as PageIndex, Some(solution)));
+	}
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn on_initialize_nothing() {
+		assert!(CurrentPhase::<T>::get().is_off());
+
+		#[block]
+		{
+			MultiBlock::<T>::on_initialize(1u32.into());
+		}
+
+		assert!(CurrentPhase::<T>::get().is_off());
+	}
+
+	#[benchmark]
+	fn on_initialize_snapshot_msp(
+		v: Linear<1, { T::VoterSnapshotPerBlock::get() }>,
+		t: Linear<1, { T::TargetSnapshotPerBlock::get() }>,
+	) {
+		set_up_data_provider::<T>(v, t);
+
+		#[block]
+		{
+			MultiBlock::<T>::create_snapshot_page(MultiBlock::<T>::msp());
+		}
+
+		assert!(TargetSnapshot::<T>::exists());
+		assert!(PagedVoterSnapshot::<T>::contains_key(MultiBlock::<T>::msp()));
+	}
+
+	#[benchmark]
+	fn on_initialize_snapshot_page(v: Linear<1, { T::VoterSnapshotPerBlock::get() }>) {
+		set_up_data_provider::<T>(v * T::Pages::get(), T::TargetSnapshotPerBlock::get());
+		MultiBlock::<T>::create_snapshot_page(MultiBlock::<T>::msp());
+		let page = MultiBlock::<T>::msp().saturating_sub(1);
+
+		#[block]
+		{
+			MultiBlock::<T>::create_snapshot_page(page);
+		}
+
+		assert!(PagedVoterSnapshot::<T>::contains_key(page));
+	}
+
+	#[benchmark]
+	fn on_initialize_into_signed_validation() {
+		MultiBlock::<T>::phase_transition(Phase::Signed);
+
+		#[block]
+		{
+			MultiBlock::<T>::phase_transition(Phase::SignedValidation);
+		}
+
+		assert!(CurrentPhase::<T>::get().is_signed_validation());
+	}
+
+	#[benchmark]
+	fn on_initialize_verify_page(v: Linear<1, { T::VoterSnapshotPerBlock::get() }>) {
+		set_up_data_provider::<T>(v * T::Pages::get(), T::TargetSnapshotPerBlock::get());
+		create_snapshot::<T>();
+		submit_solution::<T>(&funded_account::<T>("submitter", 0));
+		MultiBlock::<T>::phase_transition(Phase::SignedValidation);
+
+		#[block]
+		{
+			MultiBlock::<T>::verify_next_page();
+		}
+
+		assert!(QueuedSolutionPages::<T>::contains_key(MultiBlock::<T>::msp()));
+	}
+
+	#[benchmark]
+	fn on_initialize_verify_final(p: Linear<1, { T::Pages::get() }>) {
+		// only the `p` most significant pages have voters.
+		set_up_data_provider::<T>(
+			p * T::VoterSnapshotPerBlock::get(),
+			T::TargetSnapshotPerBlock::get(),
+		);
+		create_snapshot::<T>();
+		submit_solution::<T>(&funded_account::<T>("submitter", 0));
+		MultiBlock::<T>::phase_transition(Phase::SignedValidation);
+		for _ in MultiBlock::<T>::lsp()..MultiBlock::<T>::msp() {
+			MultiBlock::<T>::verify_next_page();
+		}
+
+		#[block]
+		{
+			MultiBlock::<T>::verify_next_page();
+		}
+
+		assert!(QueuedSolutionScore::<T>::exists());
+	}
+
+	#[benchmark]
+	fn register() {
+		MultiBlock::<T>::phase_transition(Phase::Signed);
+		// fill the queue, so that the worst submission is ejected.
+		for i in 0..T::MaxSubmissions::get() {
+			let score = ElectionScore { minimal_stake: i.into(), ..Default::default() };
+			assert_ok!(MultiBlock::<T>::do_register(&funded_account::<T>("submitter", i), score));
+		}
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		let score = ElectionScore { minimal_stake: u128::MAX, ..Default::default() };
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), score);
+
+		assert!(SubmissionMetadataStorage::<T>::contains_key(&caller));
+	}
+
+	#[benchmark]
+	fn submit_page() {
+		set_up_data_provider::<T>(
+			T::Pages::get() * T::VoterSnapshotPerBlock::get(),
+			T::TargetSnapshotPerBlock::get(),
+		);
+		create_snapshot::<T>();
+		let PagedRawSolution { mut pages, score } =
+			Miner::<T>::mine_paged_solution().expect("the snapshot exists; qed");
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		assert_ok!(MultiBlock::<T>::do_register(&caller, score));
+		let page = MultiBlock::<T>::msp();
+		let solution = pages.swap_remove(page as usize);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), page, Some(Box::new(solution)));
+
+		assert!(SubmissionStorage::<T>::contains_key(&caller, page));
+	}
+
+	#[benchmark]
+	fn elect_page() {
+		set_up_data_provider::<T>(
+			T::Pages::get() * T::VoterSnapshotPerBlock::get(),
+			T::TargetSnapshotPerBlock::get(),
+		);
+		create_snapshot::<T>();
+		submit_solution::<T>(&funded_account::<T>("submitter", 0));
+		MultiBlock::<T>::phase_transition(Phase::SignedValidation);
+		for _ in MultiBlock::<T>::lsp()..=MultiBlock::<T>::msp() {
+			MultiBlock::<T>::verify_next_page();
+		}
+		assert!(QueuedSolutionScore::<T>::exists());
+
+		#[block]
+		{
+			assert!(MultiBlock::<T>::elect(MultiBlock::<T>::msp()).is_ok());
+		}
+	}
+
+	impl_benchmark_test_suite!(
+		MultiBlock,
+		crate::mock::ExtBuilder::default().build(),
+		crate::mock::Runtime
+	);
+}
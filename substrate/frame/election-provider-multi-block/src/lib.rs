@@ -0,0 +1,617 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Multi-block election provider pallet
+//!
+//! A paged, multi-block variant of `pallet-election-provider-multi-phase`. The snapshot, the
+//! signed solutions and their verification are all spread across [`Config::Pages`] pages, so
+//! that elections with many more voters than a single block can process can still be fully
+//! executed on-chain.
+//!
+//! ## Phases
+//!
+//! The timeline of the pallet is as follows. At each block,
+//! [`frame_election_provider_support::ElectionDataProvider::next_election_prediction`] is used to
+//! estimate the time remaining to the next call to [`ElectionProvider::elect`]. Based on this,
+//! a phase is chosen.
+//!
+//! ```ignore
+//!                                                                    elect()
+//!                 +   <--T::SignedPhase-->   +  <--T::SignedValidationPhase-->   +
+//!   +----------------------------------------------------------------------------------+
+//!    Off | Snapshot |        Signed          |          SignedValidation          | Off
+//!   +----------------------------------------------------------------------------------+
+//!   <--T::Pages-->
+//! ```
+//!
+//! ### Snapshot
+//!
+//! In each block of the snapshot phase, a single page of voters is fetched from the data provider
+//! via [`frame_election_provider_support::ElectionDataProvider::electing_voters_paged`], starting
+//! from the most significant page. Along with the most significant page, the targets and the
+//! desired number of targets are also fetched. The targets are not paged, and are shared by all
+//! pages.
+//!
+//! If any of the pages cannot be fetched, the pallet enters [`Phase::Emergency`], and
+//! [`Config::Fallback`] is used for this round.
+//!
+//! ### Signed
+//!
+//! During the signed phase, any account can first [`Pallet::register`] a claimed score, and then
+//! [`Pallet::submit_page`] each page of its solution. Each page of a solution is a
+//! [`Config::Solution`] that only refers to the voters of the corresponding page of the snapshot,
+//! and the targets of the (single) target snapshot. A deposit is reserved for the registration, and
+//! for each submitted page.
+//!
+//! At most [`Config::MaxSubmissions`] solutions can be registered. Once the queue is full, a new
+//! registration is only accepted if its claimed score is better than the worst one, which is then
+//! ejected and refunded.
+//!
+//! ### Signed Validation
+//!
+//! Once the signed phase is over, the best registered submission is verified, one page per block,
+//! from the most significant page down to page `0`. Pages that were never submitted are treated
+//! as empty. Each page goes through a feasibility check, similar to that of the multi-phase
+//! pallet. Once all pages are checked, their supports are merged, and the score of the merged
+//! result must match the claimed score, and the number of winners must be equal to the desired
+//! number of targets.
+//!
+//! If the submission is valid, it is queued, and the submitter is rewarded with
+//! [`Config::SignedRewardBase`]. Otherwise, its deposit is slashed, and the next best submission
+//! is verified in the following blocks.
+//!
+//! ## Election
+//!
+//! [`ElectionProvider::elect`] returns the requested page of the queued solution. If no solution
+//! is queued, [`Config::Fallback`] is used instead, also page by page. Requesting page `0` always
+//! concludes the round: all remaining submissions are refunded, all the storage of this round is
+//! cleared and the pallet goes back to [`Phase::Off`].
+//!
+//! ## Mining
+//!
+//! [`Miner`] can be used to compute a paged solution from the snapshot. It runs
+//! [`Config::Solver`] over the voters of all pages, and splits the resulting assignments back into
+//! one solution per page.
+//!
+//! ## Future Plans
+//!
+//! **Unsigned solutions.** Similar to the multi-phase pallet, an unsigned phase could be added,
+//! in which validators submit their solutions through unsigned transactions.
+//!
+//! **Trimming.** The miner does not yet trim its solutions to fit the weight and length limits of a
+//! single page.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_election_provider_support::{
+	bounds::{CountBound, DataProviderBounds},
+	BoundedSupportsOf, ElectionDataProvider, ElectionProvider, ElectionProviderBase, NposSolution,
+	NposSolver, PageIndex,
+};
+use frame_support::{
+	dispatch::DispatchClass,
+	ensure,
+	traits::{Currency, Get, OnUnbalanced, ReservableCurrency},
+	weights::Weight,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_arithmetic::traits::{Saturating, Zero};
+use sp_npos_elections::ElectionScore;
+use sp_std::prelude::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+#[macro_use]
+pub mod helpers;
+
+const LOG_TARGET: &str = "runtime::multiblock-election";
+
+pub mod miner;
+pub mod signed;
+pub mod types;
+pub mod verifier;
+pub mod weights;
+
+pub use miner::{Miner, MinerError, PagedRawSolution};
+pub use types::{
+	BalanceOf, NegativeImbalanceOf, Phase, PositiveImbalanceOf, Status, SubmissionMetadata,
+	SupportsPageOf, VoterOf, VoterPageOf,
+};
+pub use verifier::FeasibilityError;
+pub use weights::WeightInfo;
+
+/// The solution type used by this crate.
+pub type SolutionOf<T> = <T as Config>::Solution;
+
+/// The voter index. Derived from [`SolutionOf`].
+pub type SolutionVoterIndexOf<T> = <SolutionOf<T> as NposSolution>::VoterIndex;
+/// The target index. Derived from [`SolutionOf`].
+pub type SolutionTargetIndexOf<T> = <SolutionOf<T> as NposSolution>::TargetIndex;
+/// The accuracy of the election, when submitted from offchain. Derived from [`SolutionOf`].
+pub type SolutionAccuracyOf<T> = <SolutionOf<T> as NposSolution>::Accuracy;
+/// The fallback election type error.
+pub type FallbackErrorOf<T> = <<T as Config>::Fallback as ElectionProviderBase>::Error;
+
+/// Errors that can happen in the election provider.
+#[derive(frame_support::DebugNoBound)]
+pub enum ElectionError<T: Config> {
+	/// An error happened in the feasibility check sub-system.
+	Feasibility(FeasibilityError),
+	/// An error happened in the data provider.
+	DataProvider(&'static str),
+	/// An error nested in the fallback.
+	Fallback(FallbackErrorOf<T>),
+}
+
+impl<T: Config> From<FeasibilityError> for ElectionError<T> {
+	fn from(e: FeasibilityError) -> Self {
+		ElectionError::Feasibility(e)
+	}
+}
+
+pub use pallet::*;
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>
+			+ TryInto<Event<Self>>;
+
+		/// Currency type.
+		type Currency: ReservableCurrency<Self::AccountId> + Currency<Self::AccountId>;
+
+		/// Something that will provide the election data.
+		type DataProvider: ElectionDataProvider<
+			AccountId = Self::AccountId,
+			BlockNumber = BlockNumberFor<Self>,
+		>;
+
+		/// The number of pages of the snapshot, and of each solution.
+		///
+		/// This is also the duration of the snapshot phase, as one page is fetched per block.
+		#[pallet::constant]
+		type Pages: Get<PageIndex>;
+
+		/// The maximum number of voters in each page of the snapshot.
+		#[pallet::constant]
+		type VoterSnapshotPerBlock: Get<u32>;
+
+		/// The maximum number of targets in the snapshot.
+		///
+		/// Targets are not paged, and are all fetched along with the most significant page of
+		/// voters.
+		#[pallet::constant]
+		type TargetSnapshotPerBlock: Get<u32>;
+
+		/// Duration of the signed phase.
+		#[pallet::constant]
+		type SignedPhase: Get<BlockNumberFor<Self>>;
+
+		/// Duration of the signed validation phase.
+		///
+		/// This must be at least [`Config::Pages`], so that at least one full solution can be
+		/// verified.
+		#[pallet::constant]
+		type SignedValidationPhase: Get<BlockNumberFor<Self>>;
+
+		/// Maximum number of signed submissions that can be registered in each round.
+		#[pallet::constant]
+		type MaxSubmissions: Get<u32>;
+
+		/// Base deposit for registering a signed solution.
+		#[pallet::constant]
+		type SignedDepositBase: Get<BalanceOf<Self>>;
+
+		/// Deposit for each page of a signed solution.
+		#[pallet::constant]
+		type SignedDepositPerPage: Get<BalanceOf<Self>>;
+
+		/// Base reward for a signed solution.
+		#[pallet::constant]
+		type SignedRewardBase: Get<BalanceOf<Self>>;
+
+		/// Handler for the slashed deposits.
+		type SlashHandler: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Handler for the rewards.
+		type RewardHandler: OnUnbalanced<PositiveImbalanceOf<Self>>;
+
+		/// The solution type of a single page.
+		type Solution: codec::FullCodec
+			+ Default
+			+ PartialEq
+			+ Eq
+			+ Clone
+			+ sp_std::fmt::Debug
+			+ Ord
+			+ NposSolution
+			+ TypeInfo;
+
+		/// The maximum number of winners in each page of the election result.
+		///
+		/// Since each page can be backing all winners, this is also the maximum number of winners
+		/// of the whole election.
+		#[pallet::constant]
+		type MaxWinnersPerPage: Get<u32>;
+
+		/// Configuration for the fallback, used when no solution has been verified by the time
+		/// the election is requested.
+		type Fallback: ElectionProvider<
+			AccountId = Self::AccountId,
+			BlockNumber = BlockNumberFor<Self>,
+			DataProvider = Self::DataProvider,
+			MaxWinners = Self::MaxWinnersPerPage,
+		>;
+
+		/// The solver used by the [`Miner`].
+		type Solver: NposSolver<AccountId = Self::AccountId, Accuracy = SolutionAccuracyOf<Self>>;
+
+		/// The weight of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let next_election = T::DataProvider::next_election_prediction(now).max(now);
+
+			let validation_deadline = T::SignedValidationPhase::get();
+			let signed_deadline = T::SignedPhase::get().saturating_add(validation_deadline);
+			let snapshot_deadline = signed_deadline.saturating_add(T::Pages::get().into());
+
+			let remaining = next_election - now;
+			let current_phase = CurrentPhase::<T>::get();
+
+			log!(
+				trace,
+				"current phase {:?}, next election {:?}, remaining {:?}",
+				current_phase,
+				next_election,
+				remaining,
+			);
+
+			match current_phase {
+				Phase::Off if remaining <= snapshot_deadline && remaining > Zero::zero() =>
+					Self::create_snapshot_page(Self::msp()),
+				Phase::Snapshot(page) => Self::create_snapshot_page(page),
+				Phase::Signed if remaining <= validation_deadline => {
+					Self::phase_transition(Phase::SignedValidation);
+					T::WeightInfo::on_initialize_into_signed_validation()
+						.saturating_add(Self::verify_next_page())
+				},
+				// the data provider is fetching the result, stop verifying.
+				Phase::SignedValidation if remaining.is_zero() =>
+					T::WeightInfo::on_initialize_nothing(),
+				Phase::SignedValidation => Self::verify_next_page(),
+				_ => T::WeightInfo::on_initialize_nothing(),
+			}
+		}
+
+		fn integrity_test() {
+			use sp_std::mem::size_of;
+			// The index type of both voters and targets need to be smaller than that of usize (very
+			// unlikely to be the case, but anyhow).
+			assert!(size_of::<SolutionVoterIndexOf<T>>() <= size_of::<usize>());
+			assert!(size_of::<SolutionTargetIndexOf<T>>() <= size_of::<usize>());
+
+			// A page of the snapshot, and the target snapshot, must be indexable by the solution.
+			assert!(<SolutionVoterIndexOf<T>>::try_from(
+				T::VoterSnapshotPerBlock::get().saturating_sub(1) as usize
+			)
+			.is_ok());
+			assert!(<SolutionTargetIndexOf<T>>::try_from(
+				T::TargetSnapshotPerBlock::get().saturating_sub(1) as usize
+			)
+			.is_ok());
+
+			// We only accept data provider who's maximum votes per voter matches our
+			// `T::Solution`'s `LIMIT`.
+			assert_eq!(
+				<T::DataProvider as ElectionDataProvider>::MaxVotesPerVoter::get(),
+				<SolutionOf<T> as NposSolution>::LIMIT as u32,
+			);
+
+			assert!(T::Pages::get() > 0, "there must be at least one page");
+			assert!(
+				T::SignedValidationPhase::get() >= T::Pages::get().into(),
+				"the signed validation phase must be long enough to verify one full solution"
+			);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a signed solution with the given claimed score.
+		///
+		/// The dispatch origin for this call must be __signed__.
+		///
+		/// A deposit of [`Config::SignedDepositBase`] is reserved, and returned if the solution is
+		/// either verified or never processed. If the queue of registered solutions is full, the
+		/// worst one is ejected and refunded, as long as `claimed_score` is better than its score.
+		///
+		/// The pages of the solution are then submitted with [`Pallet::submit_page`].
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::register())]
+		pub fn register(origin: OriginFor<T>, claimed_score: ElectionScore) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(CurrentPhase::<T>::get().is_signed(), Error::<T>::PhaseNotSigned);
+
+			Self::do_register(&who, claimed_score)?;
+
+			Self::deposit_event(Event::Registered { round: Round::<T>::get(), who, claimed_score });
+			Ok(())
+		}
+
+		/// Submit a single page of a registered signed solution.
+		///
+		/// The dispatch origin for this call must be __signed__, and must have registered a
+		/// solution in the current round.
+		///
+		/// A deposit of [`Config::SignedDepositPerPage`] is reserved for each submitted page.
+		/// Submitting `None` removes a previously submitted page, and returns its deposit.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::submit_page())]
+		pub fn submit_page(
+			origin: OriginFor<T>,
+			page: PageIndex,
+			maybe_solution: Option<Box<SolutionOf<T>>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(CurrentPhase::<T>::get().is_signed(), Error::<T>::PhaseNotSigned);
+
+			Self::do_submit_page(&who, page, maybe_solution.map(|s| *s))?;
+
+			Self::deposit_event(Event::Stored { round: Round::<T>::get(), who, page });
+			Ok(())
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// There was a phase transition in a given round.
+		PhaseTransitioned { from: Phase, to: Phase, round: u32 },
+		/// A signed solution was registered with the given claimed score.
+		Registered { round: u32, who: T::AccountId, claimed_score: ElectionScore },
+		/// A page of a registered signed solution was stored, or removed.
+		Stored { round: u32, who: T::AccountId, page: PageIndex },
+		/// A registered signed solution was ejected, in favour of a better one.
+		Ejected { round: u32, who: T::AccountId },
+		/// All pages of a signed solution were verified, and the solution is queued.
+		Queued { round: u32, who: T::AccountId, score: ElectionScore },
+		/// The verification of a signed solution failed.
+		VerificationFailed { round: u32, who: T::AccountId },
+		/// An account has been rewarded for their signed submission being finalized.
+		Rewarded { account: T::AccountId, value: BalanceOf<T> },
+		/// An account has been slashed for submitting an invalid signed submission.
+		Slashed { account: T::AccountId, value: BalanceOf<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Submission was sent outside of the signed phase.
+		PhaseNotSigned,
+		/// The account has already registered a solution in this round.
+		Duplicate,
+		/// The queue is full, and the claimed score is not better than the worst registered one.
+		QueueFull,
+		/// The account has not registered a solution in this round.
+		NotRegistered,
+		/// The page index is out of bounds.
+		BadPageIndex,
+	}
+
+	/// Internal counter for the number of rounds.
+	///
+	/// This is incremented every time that the least significant page is requested through
+	/// `elect`.
+	#[pallet::storage]
+	pub type Round<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Current phase.
+	#[pallet::storage]
+	pub type CurrentPhase<T: Config> = StorageValue<_, Phase, ValueQuery>;
+
+	/// The pages of the voter snapshot, indexed by [`PageIndex`].
+	#[pallet::storage]
+	pub type PagedVoterSnapshot<T: Config> = StorageMap<_, Twox64Concat, PageIndex, VoterPageOf<T>>;
+
+	/// The target snapshot, shared by all pages.
+	#[pallet::storage]
+	pub type TargetSnapshot<T: Config> = StorageValue<_, Vec<T::AccountId>>;
+
+	/// Desired number of targets to elect for this round.
+	#[pallet::storage]
+	pub type DesiredTargets<T> = StorageValue<_, u32>;
+
+	/// The registered signed solutions and their claimed scores, sorted from the worst to the
+	/// best score.
+	#[pallet::storage]
+	pub type SortedScores<T: Config> =
+		StorageValue<_, BoundedVec<(T::AccountId, ElectionScore), T::MaxSubmissions>, ValueQuery>;
+
+	/// The metadata of each registered signed solution.
+	#[pallet::storage]
+	pub type SubmissionMetadataStorage<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, SubmissionMetadata<T>>;
+
+	/// The submitted pages of each registered signed solution.
+	#[pallet::storage]
+	pub type SubmissionStorage<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, PageIndex, SolutionOf<T>>;
+
+	/// The status of the verification of the best registered solution.
+	#[pallet::storage]
+	pub type VerificationStatus<T: Config> = StorageValue<_, Status, ValueQuery>;
+
+	/// The verified pages of the solution that is being verified, or that is queued.
+	#[pallet::storage]
+	pub type QueuedSolutionPages<T: Config> =
+		StorageMap<_, Twox64Concat, PageIndex, SupportsPageOf<T>>;
+
+	/// The score of the queued solution.
+	///
+	/// Only set once all the pages of a solution are verified. If this is `None`, the pages in
+	/// [`QueuedSolutionPages`] are not to be used.
+	#[pallet::storage]
+	pub type QueuedSolutionScore<T: Config> = StorageValue<_, ElectionScore>;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+}
+
+impl<T: Config> Pallet<T> {
+	/// Internal logic of the snapshot phase: fetch and store the given page of the snapshot.
+	///
+	/// The targets and the desired targets are fetched along with the most significant page.
+	fn create_snapshot_page(page: PageIndex) -> Weight {
+		let outcome = if page == Self::msp() {
+			Self::create_targets_snapshot().and_then(|targets| {
+				Self::create_voters_snapshot_page(page)
+					.map(|voters| T::WeightInfo::on_initialize_snapshot_msp(voters, targets))
+			})
+		} else {
+			Self::create_voters_snapshot_page(page).map(T::WeightInfo::on_initialize_snapshot_page)
+		};
+
+		match outcome {
+			Ok(weight) => {
+				let next_phase =
+					if page.is_zero() { Phase::Signed } else { Phase::Snapshot(page - 1) };
+				Self::phase_transition(next_phase);
+				weight
+			},
+			Err(why) => {
+				// Not much we can do about this at this point, the fallback is used for this round.
+				log!(warn, "failed to create page {} of the snapshot due to {:?}", page, why);
+				Self::phase_transition(Phase::Emergency);
+				T::WeightInfo::on_initialize_snapshot_page(T::VoterSnapshotPerBlock::get())
+			},
+		}
+	}
+
+	/// Fetch and store the target snapshot and the desired targets. Returns the number of
+	/// targets.
+	fn create_targets_snapshot() -> Result<u32, ElectionError<T>> {
+		let desired_targets =
+			Self::desired_targets_checked().map_err(ElectionError::DataProvider)?;
+
+		let limit = T::TargetSnapshotPerBlock::get();
+		let bounds = DataProviderBounds { count: Some(CountBound(limit)), size: None };
+		let targets =
+			T::DataProvider::electable_targets(bounds).map_err(ElectionError::DataProvider)?;
+		if targets.len() > limit as usize {
+			return Err(ElectionError::DataProvider("Snapshot too big for submission."))
+		}
+
+		let count = targets.len() as u32;
+		DesiredTargets::<T>::put(desired_targets);
+		TargetSnapshot::<T>::put(targets);
+		Ok(count)
+	}
+
+	/// Fetch and store a single page of the voter snapshot. Returns the number of voters.
+	fn create_voters_snapshot_page(page: PageIndex) -> Result<u32, ElectionError<T>> {
+		let limit = T::VoterSnapshotPerBlock::get();
+		let bounds = DataProviderBounds { count: Some(CountBound(limit)), size: None };
+		let voters = T::DataProvider::electing_voters_paged(bounds, page)
+			.map_err(ElectionError::DataProvider)?;
+		if voters.len() > limit as usize {
+			return Err(ElectionError::DataProvider("Snapshot too big for submission."))
+		}
+
+		let count = voters.len() as u32;
+		PagedVoterSnapshot::<T>::insert(page, voters);
+		Ok(count)
+	}
+
+	/// Logic for a phase transition.
+	pub(crate) fn phase_transition(to: Phase) {
+		let round = Round::<T>::get();
+		let from = CurrentPhase::<T>::get();
+		log!(debug, "Starting phase {:?}, round {}.", to, round);
+		Self::deposit_event(Event::PhaseTransitioned { from, to, round });
+		CurrentPhase::<T>::put(to);
+	}
+
+	/// Conclude the current round: refund all the remaining signed submissions, clear all the
+	/// storage of this round and go back to [`Phase::Off`].
+	fn conclude_round() {
+		Self::refund_all_submissions();
+		Self::clear_verification();
+
+		let _ = PagedVoterSnapshot::<T>::clear(u32::MAX, None);
+		TargetSnapshot::<T>::kill();
+		DesiredTargets::<T>::kill();
+
+		Round::<T>::mutate(|r| *r = r.saturating_add(1));
+		Self::phase_transition(Phase::Off);
+	}
+
+	/// Register some amount of weight directly with the system pallet.
+	///
+	/// This is always mandatory weight.
+	fn register_weight(weight: Weight) {
+		<frame_system::Pallet<T>>::register_extra_weight_unchecked(
+			weight,
+			DispatchClass::Mandatory,
+		);
+	}
+}
+
+impl<T: Config> ElectionProviderBase for Pallet<T> {
+	type AccountId = T::AccountId;
+	type BlockNumber = BlockNumberFor<T>;
+	type Error = ElectionError<T>;
+	type MaxWinners = T::MaxWinnersPerPage;
+	type Pages = T::Pages;
+	type DataProvider = T::DataProvider;
+}
+
+impl<T: Config> ElectionProvider for Pallet<T> {
+	fn ongoing() -> bool {
+		!CurrentPhase::<T>::get().is_off()
+	}
+
+	fn elect(page: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		let outcome = if QueuedSolutionScore::<T>::exists() {
+			// pages that are not stored were submitted empty.
+			Ok(QueuedSolutionPages::<T>::get(page).unwrap_or_default())
+		} else {
+			log!(warn, "no solution is queued, using the fallback for page {}", page);
+			T::Fallback::elect(page).map_err(ElectionError::Fallback)
+		};
+
+		if page == Self::lsp() {
+			Self::conclude_round();
+		}
+
+		Self::register_weight(T::WeightInfo::elect_page());
+		outcome
+	}
+}
@@ -0,0 +1,122 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A miner for paged solutions of the multi-block election provider.
+//!
+//! The miner runs [`crate::Config::Solver`] once over the voters of all pages of the snapshot, and
+//! then splits the resulting assignments into one solution per page, based on the page that each
+//! voter belongs to.
+
+use crate::{
+	helpers, verifier, Config, DesiredTargets, FeasibilityError, PagedVoterSnapshot, Pallet,
+	SolutionOf, TargetSnapshot, VoterPageOf,
+};
+use frame_election_provider_support::{NposSolution, NposSolver, PageIndex};
+use frame_support::traits::Get;
+use sp_npos_elections::{ElectionResult, ElectionScore, EvaluateSupport};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// Error type for operations related to the paged solution miner.
+#[derive(Debug, Eq, PartialEq)]
+pub enum MinerError {
+	/// An internal error in the NPoS elections crate.
+	NposElections(sp_npos_elections::Error),
+	/// Snapshot data was unavailable unexpectedly.
+	SnapshotUnAvailable,
+	/// The solution generated from the miner is not feasible.
+	Feasibility(FeasibilityError),
+	/// An error from the solver.
+	Solver,
+}
+
+impl From<sp_npos_elections::Error> for MinerError {
+	fn from(e: sp_npos_elections::Error) -> Self {
+		MinerError::NposElections(e)
+	}
+}
+
+impl From<FeasibilityError> for MinerError {
+	fn from(e: FeasibilityError) -> Self {
+		MinerError::Feasibility(e)
+	}
+}
+
+/// A paged solution, along with its score, as it is expected to be registered and submitted.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PagedRawSolution<S> {
+	/// The pages of the solution, indexed by [`PageIndex`].
+	pub pages: Vec<S>,
+	/// The score of the whole solution.
+	pub score: ElectionScore,
+}
+
+/// A miner for paged solutions, based on the snapshot of the current round.
+pub struct Miner<T: Config>(PhantomData<T>);
+
+impl<T: Config> Miner<T> {
+	/// Mine a new paged solution from the snapshot that is currently in storage.
+	///
+	/// The returned solution contains one (potentially empty) solution for each page, and its
+	/// score is computed exactly like the verifier would compute it.
+	pub fn mine_paged_solution() -> Result<PagedRawSolution<SolutionOf<T>>, MinerError> {
+		let targets = TargetSnapshot::<T>::get().ok_or(MinerError::SnapshotUnAvailable)?;
+		let desired_targets = DesiredTargets::<T>::get().ok_or(MinerError::SnapshotUnAvailable)?;
+		let voter_pages = (0..T::Pages::get())
+			.map(|page| PagedVoterSnapshot::<T>::get(page).ok_or(MinerError::SnapshotUnAvailable))
+			.collect::<Result<Vec<VoterPageOf<T>>, _>>()?;
+
+		let all_voters = voter_pages.iter().flatten().cloned().collect::<Vec<_>>();
+		let ElectionResult { assignments, .. } =
+			T::Solver::solve(desired_targets as usize, targets.clone(), all_voters).map_err(
+				|e| {
+					log!(error, "solver failed to mine a paged solution: {:?}", e);
+					MinerError::Solver
+				},
+			)?;
+
+		// ----- Split the assignments into pages, and convert each page into a solution.
+		let target_index = helpers::target_index_fn::<T>(&targets);
+		let pages = voter_pages
+			.iter()
+			.map(|voters| {
+				let cache = helpers::generate_voter_cache::<T>(voters);
+				let voter_index = helpers::voter_index_fn::<T>(&cache);
+				let page_assignments = assignments
+					.iter()
+					.filter(|assignment| cache.contains_key(&assignment.who))
+					.cloned()
+					.collect::<Vec<_>>();
+				SolutionOf::<T>::from_assignment(&page_assignments, voter_index, &target_index)
+					.map_err(Into::into)
+			})
+			.collect::<Result<Vec<_>, MinerError>>()?;
+
+		// ----- Compute the score from the solutions themselves, like the verifier does.
+		let supports = pages
+			.iter()
+			.cloned()
+			.enumerate()
+			.map(|(page, solution)| {
+				Pallet::<T>::feasibility_check_page(solution, page as PageIndex)
+					.map(|supports| supports.into_inner())
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		let score = verifier::merge_supports(supports).evaluate();
+
+		Ok(PagedRawSolution { pages, score })
+	}
+}
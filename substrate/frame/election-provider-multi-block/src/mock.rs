@@ -0,0 +1,361 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{self as multi_block};
+use frame_election_provider_support::{
+	bounds::{DataProviderBounds, ElectionBounds, ElectionBoundsBuilder},
+	data_provider, onchain, ElectionDataProvider, SequentialPhragmen,
+};
+pub use frame_support::derive_impl;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, Hooks},
+	weights::{constants, Weight},
+};
+use sp_core::H256;
+use sp_npos_elections::BalancingConfig;
+use sp_runtime::{
+	bounded_vec,
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, PerU16, Perbill,
+};
+
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic =
+	sp_runtime::generic::UncheckedExtrinsic<AccountId, RuntimeCall, (), ()>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Balances: pallet_balances,
+		MultiBlock: multi_block,
+	}
+);
+
+pub(crate) type Balance = u64;
+pub(crate) type AccountId = u64;
+pub(crate) type BlockNumber = u64;
+pub(crate) type VoterIndex = u32;
+pub(crate) type TargetIndex = u16;
+
+frame_election_provider_support::generate_solution_type!(
+	#[compact]
+	pub struct TestNposSolution::<
+		VoterIndex = VoterIndex,
+		TargetIndex = TargetIndex,
+		Accuracy = PerU16,
+		MaxVoters = ConstU32::<2_000>
+	>(16)
+);
+
+/// All events of this pallet.
+pub(crate) fn multi_block_events() -> Vec<super::Event<Runtime>> {
+	System::read_events_for_pallet::<super::Event<Runtime>>()
+}
+
+/// To from `now` to block `n`.
+pub fn roll_to(n: BlockNumber) {
+	let now = System::block_number();
+	for i in now + 1..=n {
+		System::set_block_number(i);
+		MultiBlock::on_initialize(i);
+	}
+}
+
+pub fn roll_to_signed() {
+	while !CurrentPhase::<Runtime>::get().is_signed() {
+		roll_to(System::block_number() + 1);
+	}
+}
+
+pub fn roll_to_signed_validation() {
+	while !CurrentPhase::<Runtime>::get().is_signed_validation() {
+		roll_to(System::block_number() + 1);
+	}
+}
+
+/// Request all the pages of the election, from the most significant one to page `0`.
+pub fn elect_all_pages() -> Vec<Result<BoundedSupportsOf<MultiBlock>, ElectionError<Runtime>>> {
+	(0..Pages::get()).rev().map(|page| MultiBlock::elect(page)).collect()
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type SS58Prefix = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type RuntimeOrigin = RuntimeOrigin;
+	type Nonce = u64;
+	type RuntimeCall = RuntimeCall;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ();
+	type DbWeight = ();
+	type BlockLength = ();
+	type BlockWeights = BlockWeights;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+const NORMAL_DISPATCH_RATIO: Perbill = Perbill::from_percent(75);
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+	pub BlockWeights: frame_system::limits::BlockWeights = frame_system::limits::BlockWeights
+		::with_sensible_defaults(
+			Weight::from_parts(2u64 * constants::WEIGHT_REF_TIME_PER_SECOND, u64::MAX),
+			NORMAL_DISPATCH_RATIO,
+		);
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+}
+
+parameter_types! {
+	pub static Targets: Vec<AccountId> = vec![10, 20, 30, 40];
+	pub static Voters: Vec<VoterOf<Runtime>> = vec![
+		(1, 10, bounded_vec![10, 20]),
+		(2, 10, bounded_vec![30, 40]),
+		(3, 10, bounded_vec![40]),
+		(4, 10, bounded_vec![10, 20, 30, 40]),
+		// self votes.
+		(10, 10, bounded_vec![10]),
+		(20, 20, bounded_vec![20]),
+		(30, 30, bounded_vec![30]),
+		(40, 40, bounded_vec![40]),
+	];
+	// the number of voters that the data provider returns in each page.
+	pub static VotersPerPage: u32 = 3;
+
+	pub static DesiredTargets: u32 = 2;
+	pub static Pages: PageIndex = 3;
+	pub static SignedPhase: BlockNumber = 5;
+	pub static SignedValidationPhase: BlockNumber = 4;
+	pub static MaxSubmissions: u32 = 3;
+	pub static SignedDepositBase: Balance = 5;
+	pub static SignedDepositPerPage: Balance = 1;
+	pub static SignedRewardBase: Balance = 7;
+	pub static MaxWinnersPerPage: u32 = 10;
+	pub static EpochLength: u64 = 30;
+	pub static Balancing: Option<BalancingConfig> = Some(BalancingConfig { iterations: 0, tolerance: 0 });
+	pub static OnChainElectionsBounds: ElectionBounds = ElectionBoundsBuilder::default().build();
+	// only used in testing to manipulate mock behaviour
+	pub static DataProviderFails: bool = false;
+}
+
+pub struct OnChainSeqPhragmen;
+impl onchain::Config for OnChainSeqPhragmen {
+	type System = Runtime;
+	type Solver = SequentialPhragmen<AccountId, SolutionAccuracyOf<Runtime>, Balancing>;
+	type DataProvider = StakingMock;
+	type WeightInfo = ();
+	type MaxWinners = MaxWinnersPerPage;
+	type Bounds = OnChainElectionsBounds;
+}
+
+impl crate::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type DataProvider = StakingMock;
+	type Pages = Pages;
+	type VoterSnapshotPerBlock = ConstU32<4>;
+	type TargetSnapshotPerBlock = ConstU32<8>;
+	type SignedPhase = SignedPhase;
+	type SignedValidationPhase = SignedValidationPhase;
+	type MaxSubmissions = MaxSubmissions;
+	type SignedDepositBase = SignedDepositBase;
+	type SignedDepositPerPage = SignedDepositPerPage;
+	type SignedRewardBase = SignedRewardBase;
+	type SlashHandler = ();
+	type RewardHandler = ();
+	type Solution = TestNposSolution;
+	type MaxWinnersPerPage = MaxWinnersPerPage;
+	type Fallback = onchain::OnChainExecution<OnChainSeqPhragmen>;
+	type Solver = SequentialPhragmen<AccountId, SolutionAccuracyOf<Runtime>, Balancing>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub MaxNominations: u32 = <TestNposSolution as NposSolution>::LIMIT as u32;
+}
+
+/// A paged data provider. The voters are returned in chunks of [`VotersPerPage`], starting from the
+/// most significant page.
+pub struct StakingMock;
+impl ElectionDataProvider for StakingMock {
+	type BlockNumber = BlockNumber;
+	type AccountId = AccountId;
+	type MaxVotesPerVoter = MaxNominations;
+
+	fn electable_targets(bounds: DataProviderBounds) -> data_provider::Result<Vec<AccountId>> {
+		let targets = Targets::get();
+
+		if bounds.count.map_or(false, |max_len| targets.len() > max_len.0 as usize) {
+			return Err("Targets too big")
+		}
+
+		Ok(targets)
+	}
+
+	fn electing_voters(bounds: DataProviderBounds) -> data_provider::Result<Vec<VoterOf<Runtime>>> {
+		let mut voters = Voters::get();
+		if let Some(max_len) = bounds.count {
+			voters.truncate(max_len.0 as usize)
+		}
+
+		Ok(voters)
+	}
+
+	fn electing_voters_paged(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<VoterOf<Runtime>>> {
+		if DataProviderFails::get() {
+			return Err("Data provider failed")
+		}
+
+		let index = Pages::get().saturating_sub(1).saturating_sub(page) as usize;
+		let mut voters = Voters::get()
+			.chunks(VotersPerPage::get() as usize)
+			.nth(index)
+			.map(|chunk| chunk.to_vec())
+			.unwrap_or_default();
+		if let Some(max_len) = bounds.count {
+			voters.truncate(max_len.0 as usize)
+		}
+
+		Ok(voters)
+	}
+
+	fn desired_targets() -> data_provider::Result<u32> {
+		Ok(DesiredTargets::get())
+	}
+
+	fn next_election_prediction(now: u64) -> u64 {
+		now + EpochLength::get() - now % EpochLength::get()
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn put_snapshot(
+		voters: Vec<VoterOf<Runtime>>,
+		targets: Vec<AccountId>,
+		_target_stake: Option<sp_npos_elections::VoteWeight>,
+	) {
+		Targets::set(targets);
+		Voters::set(voters);
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn clear() {
+		Targets::set(vec![]);
+		Voters::set(vec![]);
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add_voter(
+		voter: AccountId,
+		weight: sp_npos_elections::VoteWeight,
+		targets: frame_support::BoundedVec<AccountId, Self::MaxVotesPerVoter>,
+	) {
+		let mut current = Voters::get();
+		current.push((voter, weight, targets));
+		Voters::set(current);
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add_target(target: AccountId) {
+		let mut current = Targets::get();
+		current.push(target);
+		Targets::set(current);
+	}
+}
+
+#[derive(Default)]
+pub struct ExtBuilder {}
+
+impl ExtBuilder {
+	pub fn pages(self, pages: PageIndex) -> Self {
+		<Pages>::set(pages);
+		self
+	}
+	pub fn desired_targets(self, t: u32) -> Self {
+		<DesiredTargets>::set(t);
+		self
+	}
+	pub fn signed_validation_phase(self, length: BlockNumber) -> Self {
+		<SignedValidationPhase>::set(length);
+		self
+	}
+	pub fn max_submissions(self, count: u32) -> Self {
+		<MaxSubmissions>::set(count);
+		self
+	}
+	pub fn data_provider_fails(self, fails: bool) -> Self {
+		<DataProviderFails>::set(fails);
+		self
+	}
+	pub fn build(self) -> sp_io::TestExternalities {
+		sp_tracing::try_init_simple();
+		let mut storage =
+			frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+		let _ = pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![
+				// bunch of account for submitting stuff only.
+				(99, 100),
+				(100, 100),
+				(101, 100),
+				(102, 100),
+				(999, 100),
+			],
+		}
+		.assimilate_storage(&mut storage);
+
+		sp_io::TestExternalities::from(storage)
+	}
+
+	pub fn build_and_execute(self, test: impl FnOnce() -> ()) {
+		self.build().execute_with(test)
+	}
+}
+
+pub(crate) fn balances(who: &AccountId) -> (Balance, Balance) {
+	(Balances::free_balance(who), Balances::reserved_balance(who))
+}
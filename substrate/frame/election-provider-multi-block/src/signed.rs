@@ -0,0 +1,180 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The signed phase of the multi-block election provider.
+//!
+//! Submitters first register their claimed score, and then submit their solution page by page.
+//! Registrations are kept in [`crate::SortedScores`], from the worst to the best claimed score,
+//! and the best one is the next to be verified.
+
+use crate::{
+	BalanceOf, Config, Error, Event, Pallet, Round, SolutionOf, SortedScores, SubmissionMetadata,
+	SubmissionMetadataStorage, SubmissionStorage,
+};
+use frame_election_provider_support::PageIndex;
+use frame_support::{
+	ensure,
+	traits::{Currency, Defensive, Get, OnUnbalanced, ReservableCurrency},
+	BoundedVec,
+};
+use sp_arithmetic::traits::{Saturating, Zero};
+use sp_npos_elections::ElectionScore;
+use sp_runtime::{DispatchResult, Perbill};
+use sp_std::prelude::*;
+
+impl<T: Config> Pallet<T> {
+	/// Register `who` with the given `claimed_score`, reserving the base deposit.
+	///
+	/// If the queue is full, the submission with the worst score is ejected and refunded, as long
+	/// as `claimed_score` is better than it.
+	pub(crate) fn do_register(who: &T::AccountId, claimed_score: ElectionScore) -> DispatchResult {
+		ensure!(!SubmissionMetadataStorage::<T>::contains_key(who), Error::<T>::Duplicate);
+
+		let mut sorted_scores = SortedScores::<T>::get();
+		// Equal scores are placed before the existing ones, so that earlier submissions are
+		// preferred.
+		let position = sorted_scores.partition_point(|(_, score)| {
+			claimed_score.strict_threshold_better(*score, Perbill::zero())
+		});
+		let maybe_ejected = sorted_scores
+			.force_insert_keep_right(position, (who.clone(), claimed_score))
+			.map_err(|_| Error::<T>::QueueFull)?;
+
+		let deposit = T::SignedDepositBase::get();
+		T::Currency::reserve(who, deposit)?;
+
+		if let Some((ejected, _)) = maybe_ejected {
+			Self::refund_submission(&ejected);
+			Self::deposit_event(Event::Ejected { round: Round::<T>::get(), who: ejected });
+		}
+
+		let pages = BoundedVec::truncate_from(vec![false; T::Pages::get() as usize]);
+		SubmissionMetadataStorage::<T>::insert(
+			who,
+			SubmissionMetadata { claimed_score, deposit, pages },
+		);
+		SortedScores::<T>::put(sorted_scores);
+
+		Ok(())
+	}
+
+	/// Store, or remove if `maybe_solution` is `None`, a single page of the solution of `who`.
+	///
+	/// The per-page deposit is reserved when a page is first stored, and unreserved when it is
+	/// removed.
+	pub(crate) fn do_submit_page(
+		who: &T::AccountId,
+		page: PageIndex,
+		maybe_solution: Option<SolutionOf<T>>,
+	) -> DispatchResult {
+		ensure!(page < T::Pages::get(), Error::<T>::BadPageIndex);
+		let mut metadata =
+			SubmissionMetadataStorage::<T>::get(who).ok_or(Error::<T>::NotRegistered)?;
+		let submitted = metadata.pages.get_mut(page as usize).ok_or(Error::<T>::BadPageIndex)?;
+		let page_deposit = T::SignedDepositPerPage::get();
+
+		match maybe_solution {
+			Some(solution) => {
+				if !*submitted {
+					T::Currency::reserve(who, page_deposit)?;
+					metadata.deposit = metadata.deposit.saturating_add(page_deposit);
+					*submitted = true;
+				}
+				SubmissionStorage::<T>::insert(who, page, solution);
+			},
+			None =>
+				if *submitted {
+					let _remaining = T::Currency::unreserve(who, page_deposit);
+					debug_assert!(_remaining.is_zero());
+					metadata.deposit = metadata.deposit.saturating_sub(page_deposit);
+					*submitted = false;
+					SubmissionStorage::<T>::remove(who, page);
+				},
+		}
+
+		SubmissionMetadataStorage::<T>::insert(who, metadata);
+		Ok(())
+	}
+
+	/// Helper function for the case where the best solution is verified and queued.
+	///
+	/// Removes the solution from the queue, unreserves its deposit and rewards the submitter.
+	///
+	/// Infallible
+	pub(crate) fn finalize_signed_accept_solution(who: &T::AccountId) {
+		let Some(deposit) = Self::take_best_submission(who) else { return };
+
+		let reward = T::SignedRewardBase::get();
+		// emit reward event
+		Self::deposit_event(Event::Rewarded { account: who.clone(), value: reward });
+
+		// Unreserve deposit.
+		let _remaining = T::Currency::unreserve(who, deposit);
+		debug_assert!(_remaining.is_zero());
+
+		// Reward.
+		let positive_imbalance = T::Currency::deposit_creating(who, reward);
+		T::RewardHandler::on_unbalanced(positive_imbalance);
+	}
+
+	/// Helper function for the case where the best solution fails its verification.
+	///
+	/// Removes the solution from the queue and slashes its deposit.
+	///
+	/// Infallible
+	pub(crate) fn finalize_signed_reject_solution(who: &T::AccountId) {
+		let Some(deposit) = Self::take_best_submission(who) else { return };
+
+		Self::deposit_event(Event::Slashed { account: who.clone(), value: deposit });
+		let (negative_imbalance, _remaining) = T::Currency::slash_reserved(who, deposit);
+		debug_assert!(_remaining.is_zero());
+		T::SlashHandler::on_unbalanced(negative_imbalance);
+	}
+
+	/// Refund and remove all the registered submissions that were not processed.
+	pub(crate) fn refund_all_submissions() {
+		SortedScores::<T>::take()
+			.into_iter()
+			.for_each(|(who, _)| Self::refund_submission(&who));
+	}
+
+	/// Remove the best submission from the queue, along with all of its data, and return its
+	/// deposit. `who` is expected to be the owner of the best submission.
+	fn take_best_submission(who: &T::AccountId) -> Option<BalanceOf<T>> {
+		SortedScores::<T>::mutate(|sorted_scores| {
+			let _best = sorted_scores.pop();
+			debug_assert!(_best.map_or(false, |(best, _)| &best == who));
+		});
+		Self::remove_submission(who)
+	}
+
+	/// Remove all the data of the submission of `who`, and unreserve its deposit.
+	fn refund_submission(who: &T::AccountId) {
+		if let Some(deposit) = Self::remove_submission(who) {
+			let _remaining = T::Currency::unreserve(who, deposit);
+			debug_assert!(_remaining.is_zero());
+		}
+	}
+
+	/// Remove all the data of the submission of `who`, and return its deposit.
+	fn remove_submission(who: &T::AccountId) -> Option<BalanceOf<T>> {
+		let _ = SubmissionStorage::<T>::clear_prefix(who, u32::MAX, None);
+		SubmissionMetadataStorage::<T>::take(who)
+			.defensive_proof("registered submissions always have metadata; qed")
+			.map(|metadata| metadata.deposit)
+	}
+}
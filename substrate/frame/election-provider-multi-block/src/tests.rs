@@ -0,0 +1,361 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok};
+use sp_npos_elections::EvaluateSupport;
+
+fn phase_transitions() -> Vec<(Phase, Phase, u32)> {
+	multi_block_events()
+		.into_iter()
+		.filter_map(|e| match e {
+			Event::PhaseTransitioned { from, to, round } => Some((from, to, round)),
+			_ => None,
+		})
+		.collect()
+}
+
+fn voters_of_page(page: PageIndex) -> Vec<AccountId> {
+	PagedVoterSnapshot::<Runtime>::get(page)
+		.unwrap()
+		.into_iter()
+		.map(|(voter, _, _)| voter)
+		.collect()
+}
+
+fn register_and_submit(who: AccountId, solution: PagedRawSolution<TestNposSolution>) {
+	assert_ok!(MultiBlock::register(RuntimeOrigin::signed(who), solution.score));
+	for (page, solution) in solution.pages.into_iter().enumerate() {
+		assert_ok!(MultiBlock::submit_page(
+			RuntimeOrigin::signed(who),
+			page as PageIndex,
+			Some(Box::new(solution))
+		));
+	}
+}
+
+mod phase_rotation {
+	use super::*;
+
+	#[test]
+	fn phases_rotate_and_snapshot_is_paged() {
+		ExtBuilder::default().build_and_execute(|| {
+			// next election is at block 30.
+			roll_to(17);
+			assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Off);
+			assert!(!MultiBlock::ongoing());
+
+			// the most significant page comes along with the targets.
+			roll_to(18);
+			assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Snapshot(1));
+			assert!(MultiBlock::ongoing());
+			assert_eq!(voters_of_page(2), vec![1, 2, 3]);
+			assert!(PagedVoterSnapshot::<Runtime>::get(1).is_none());
+			assert_eq!(TargetSnapshot::<Runtime>::get().unwrap(), vec![10, 20, 30, 40]);
+			assert_eq!(crate::DesiredTargets::<Runtime>::get(), Some(2));
+
+			roll_to(19);
+			assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Snapshot(0));
+			assert_eq!(voters_of_page(1), vec![4, 10, 20]);
+
+			roll_to(20);
+			assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Signed);
+			assert_eq!(voters_of_page(0), vec![30, 40]);
+
+			roll_to(25);
+			assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Signed);
+
+			roll_to(26);
+			assert_eq!(CurrentPhase::<Runtime>::get(), Phase::SignedValidation);
+
+			// nothing was submitted, the fallback is used.
+			roll_to(29);
+			assert!(elect_all_pages().into_iter().all(|page| page.is_ok()));
+
+			assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Off);
+			assert_eq!(Round::<Runtime>::get(), 1);
+			assert_eq!(PagedVoterSnapshot::<Runtime>::iter().count(), 0);
+			assert!(TargetSnapshot::<Runtime>::get().is_none());
+			assert!(crate::DesiredTargets::<Runtime>::get().is_none());
+
+			assert_eq!(
+				phase_transitions(),
+				vec![
+					(Phase::Off, Phase::Snapshot(1), 0),
+					(Phase::Snapshot(1), Phase::Snapshot(0), 0),
+					(Phase::Snapshot(0), Phase::Signed, 0),
+					(Phase::Signed, Phase::SignedValidation, 0),
+					(Phase::SignedValidation, Phase::Off, 0),
+				]
+			);
+
+			// the next round starts in time for the next election.
+			roll_to(47);
+			assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Off);
+			roll_to(48);
+			assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Snapshot(1));
+		})
+	}
+
+	#[test]
+	fn single_page_snapshot_goes_straight_to_signed() {
+		ExtBuilder::default().pages(1).build_and_execute(|| {
+			roll_to(20);
+			assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Off);
+
+			roll_to(21);
+			assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Signed);
+			assert_eq!(voters_of_page(0), vec![1, 2, 3]);
+		})
+	}
+
+	#[test]
+	fn snapshot_failure_enters_emergency() {
+		ExtBuilder::default().data_provider_fails(true).build_and_execute(|| {
+			roll_to(18);
+			assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Emergency);
+			assert!(MultiBlock::ongoing());
+
+			// stays in emergency until the election is concluded.
+			roll_to(29);
+			assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Emergency);
+
+			// the fallback uses the same data provider.
+			assert!(matches!(MultiBlock::elect(2), Err(ElectionError::Fallback(_))));
+			DataProviderFails::set(false);
+			assert_ok!(MultiBlock::elect(1));
+			assert_ok!(MultiBlock::elect(0));
+
+			assert_eq!(CurrentPhase::<Runtime>::get(), Phase::Off);
+			assert_eq!(Round::<Runtime>::get(), 1);
+		})
+	}
+}
+
+mod signed {
+	use super::*;
+
+	#[test]
+	fn register_and_submit_pages() {
+		ExtBuilder::default().build_and_execute(|| {
+			let score = ElectionScore { minimal_stake: 10, ..Default::default() };
+
+			roll_to(19);
+			assert_noop!(
+				MultiBlock::register(RuntimeOrigin::signed(99), score),
+				Error::<Runtime>::PhaseNotSigned
+			);
+
+			roll_to_signed();
+			assert_ok!(MultiBlock::register(RuntimeOrigin::signed(99), score));
+			assert_eq!(balances(&99), (95, 5));
+			assert_noop!(
+				MultiBlock::register(RuntimeOrigin::signed(99), score),
+				Error::<Runtime>::Duplicate
+			);
+
+			assert_noop!(
+				MultiBlock::submit_page(RuntimeOrigin::signed(100), 0, Some(Default::default())),
+				Error::<Runtime>::NotRegistered
+			);
+			assert_noop!(
+				MultiBlock::submit_page(RuntimeOrigin::signed(99), 3, Some(Default::default())),
+				Error::<Runtime>::BadPageIndex
+			);
+
+			assert_ok!(MultiBlock::submit_page(
+				RuntimeOrigin::signed(99),
+				0,
+				Some(Default::default())
+			));
+			assert_eq!(balances(&99), (94, 6));
+			assert!(SubmissionStorage::<Runtime>::contains_key(99, 0));
+
+			// re-submitting a page does not reserve again.
+			assert_ok!(MultiBlock::submit_page(
+				RuntimeOrigin::signed(99),
+				0,
+				Some(Default::default())
+			));
+			assert_eq!(balances(&99), (94, 6));
+
+			// removing a page returns its deposit.
+			assert_ok!(MultiBlock::submit_page(RuntimeOrigin::signed(99), 0, None));
+			assert_eq!(balances(&99), (95, 5));
+			assert!(!SubmissionStorage::<Runtime>::contains_key(99, 0));
+
+			roll_to_signed_validation();
+			assert_noop!(
+				MultiBlock::submit_page(RuntimeOrigin::signed(99), 0, Some(Default::default())),
+				Error::<Runtime>::PhaseNotSigned
+			);
+		})
+	}
+
+	#[test]
+	fn full_queue_ejects_the_worst_submission() {
+		ExtBuilder::default().max_submissions(2).build_and_execute(|| {
+			roll_to_signed();
+			let score = |minimal_stake| ElectionScore { minimal_stake, ..Default::default() };
+
+			assert_ok!(MultiBlock::register(RuntimeOrigin::signed(99), score(10)));
+			assert_ok!(MultiBlock::register(RuntimeOrigin::signed(100), score(20)));
+			assert_eq!(
+				SortedScores::<Runtime>::get().into_inner(),
+				vec![(99, score(10)), (100, score(20))]
+			);
+
+			// not better than the worst one.
+			assert_noop!(
+				MultiBlock::register(RuntimeOrigin::signed(101), score(10)),
+				Error::<Runtime>::QueueFull
+			);
+
+			assert_ok!(MultiBlock::register(RuntimeOrigin::signed(101), score(15)));
+			assert_eq!(
+				SortedScores::<Runtime>::get().into_inner(),
+				vec![(101, score(15)), (100, score(20))]
+			);
+			assert_eq!(balances(&99), (100, 0));
+			assert!(!SubmissionMetadataStorage::<Runtime>::contains_key(99));
+			assert!(multi_block_events().contains(&Event::Ejected { round: 0, who: 99 }));
+		})
+	}
+}
+
+mod verification {
+	use super::*;
+
+	#[test]
+	fn valid_solution_is_queued_and_rewarded() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_signed();
+			let solution = Miner::<Runtime>::mine_paged_solution().unwrap();
+			let score = solution.score;
+			assert_eq!(solution.pages.len(), 3);
+
+			register_and_submit(99, solution);
+			assert_eq!(balances(&99), (92, 8));
+
+			// the most significant page is verified right away.
+			roll_to_signed_validation();
+			assert_eq!(VerificationStatus::<Runtime>::get(), Status::Ongoing(1));
+			assert!(QueuedSolutionPages::<Runtime>::contains_key(2));
+			assert!(QueuedSolutionScore::<Runtime>::get().is_none());
+
+			roll_to(28);
+			assert_eq!(VerificationStatus::<Runtime>::get(), Status::Nothing);
+			assert_eq!(QueuedSolutionScore::<Runtime>::get(), Some(score));
+			assert_eq!(balances(&99), (107, 0));
+			assert!(SortedScores::<Runtime>::get().is_empty());
+			assert!(multi_block_events().contains(&Event::Queued { round: 0, who: 99, score }));
+			assert!(multi_block_events().contains(&Event::Rewarded { account: 99, value: 7 }));
+
+			let supports = elect_all_pages()
+				.into_iter()
+				.map(|page| page.unwrap().into_inner())
+				.collect::<Vec<_>>();
+			let supports = verifier::merge_supports(supports);
+			assert_eq!(supports.len(), 2);
+			assert_eq!(supports.evaluate(), score);
+
+			assert_eq!(Round::<Runtime>::get(), 1);
+			assert!(QueuedSolutionScore::<Runtime>::get().is_none());
+			assert_eq!(QueuedSolutionPages::<Runtime>::iter().count(), 0);
+		})
+	}
+
+	#[test]
+	fn invalid_score_is_slashed_and_next_best_is_verified() {
+		ExtBuilder::default().signed_validation_phase(8).build_and_execute(|| {
+			roll_to_signed();
+			let solution = Miner::<Runtime>::mine_paged_solution().unwrap();
+			let score = solution.score;
+
+			// 100 claims a better score than its solution has, and is verified first.
+			let mut bad_solution = solution.clone();
+			bad_solution.score.minimal_stake += 1;
+			register_and_submit(100, bad_solution);
+			register_and_submit(99, solution);
+
+			roll_to_signed_validation();
+			assert_eq!(System::block_number(), 22);
+			roll_to(24);
+			assert_eq!(balances(&100), (92, 0));
+			assert!(
+				multi_block_events().contains(&Event::VerificationFailed { round: 0, who: 100 })
+			);
+			assert!(multi_block_events().contains(&Event::Slashed { account: 100, value: 8 }));
+			assert!(QueuedSolutionScore::<Runtime>::get().is_none());
+			assert_eq!(QueuedSolutionPages::<Runtime>::iter().count(), 0);
+
+			roll_to(27);
+			assert_eq!(QueuedSolutionScore::<Runtime>::get(), Some(score));
+			assert_eq!(balances(&99), (107, 0));
+		})
+	}
+
+	#[test]
+	fn invalid_page_is_slashed_and_fallback_is_used() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_signed();
+			let mut solution = Miner::<Runtime>::mine_paged_solution().unwrap();
+			// the most significant page only has 3 voters.
+			solution.pages[2] = TestNposSolution { votes1: vec![(5, 0)], ..Default::default() };
+			register_and_submit(99, solution);
+
+			roll_to_signed_validation();
+			assert_eq!(balances(&99), (92, 0));
+			assert!(multi_block_events().contains(&Event::VerificationFailed { round: 0, who: 99 }));
+			assert_eq!(VerificationStatus::<Runtime>::get(), Status::Nothing);
+			assert_eq!(QueuedSolutionPages::<Runtime>::iter().count(), 0);
+
+			let expected = (0..Pages::get())
+				.rev()
+				.map(|page| <Runtime as Config>::Fallback::elect(page).unwrap())
+				.collect::<Vec<_>>();
+			let supports =
+				elect_all_pages().into_iter().map(|page| page.unwrap()).collect::<Vec<_>>();
+			assert_eq!(supports, expected);
+		})
+	}
+
+	#[test]
+	fn unprocessed_submissions_are_refunded() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_signed();
+			let solution = Miner::<Runtime>::mine_paged_solution().unwrap();
+			register_and_submit(99, solution.clone());
+
+			// a worse submission, never verified.
+			let mut worse_solution = solution;
+			worse_solution.score.minimal_stake -= 1;
+			register_and_submit(100, worse_solution);
+			assert_eq!(balances(&100), (92, 8));
+
+			roll_to(29);
+			assert!(QueuedSolutionScore::<Runtime>::get().is_some());
+			assert_eq!(SortedScores::<Runtime>::get().len(), 1);
+
+			assert!(elect_all_pages().into_iter().all(|page| page.is_ok()));
+			assert_eq!(balances(&100), (100, 0));
+			assert!(SortedScores::<Runtime>::get().is_empty());
+			assert_eq!(SubmissionStorage::<Runtime>::iter().count(), 0);
+		})
+	}
+}
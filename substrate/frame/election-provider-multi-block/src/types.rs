@@ -0,0 +1,123 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Common types of the multi-block election provider.
+
+use crate::Config;
+use codec::{Decode, Encode};
+use frame_election_provider_support::PageIndex;
+use frame_support::{
+	traits::Currency, BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use scale_info::TypeInfo;
+use sp_npos_elections::{BoundedSupports, ElectionScore};
+use sp_runtime::RuntimeDebug;
+
+/// A voter, as stored in a page of the snapshot.
+pub type VoterOf<T> = frame_election_provider_support::VoterOf<<T as Config>::DataProvider>;
+
+/// A single page of the voter snapshot.
+pub type VoterPageOf<T> = sp_std::vec::Vec<VoterOf<T>>;
+
+/// A single page of a verified solution.
+pub type SupportsPageOf<T> =
+	BoundedSupports<<T as frame_system::Config>::AccountId, <T as Config>::MaxWinnersPerPage>;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type PositiveImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::PositiveImbalance;
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+/// Current phase of the pallet.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, Debug, TypeInfo)]
+pub enum Phase {
+	/// Nothing, the election is not happening.
+	Off,
+	/// The snapshot is being created, one page per block. The inner value is the next page that
+	/// is going to be fetched.
+	Snapshot(PageIndex),
+	/// Signed phase is open, solutions can be registered and submitted page by page.
+	Signed,
+	/// The signed submissions are being verified, one page per block, starting from the best
+	/// registered score.
+	SignedValidation,
+	/// The snapshot could not be created, and the fallback is going to be used for this round.
+	///
+	/// This phase is left once the election is concluded, i.e. when the least significant page
+	/// is requested through `elect`.
+	Emergency,
+}
+
+impl Default for Phase {
+	fn default() -> Self {
+		Phase::Off
+	}
+}
+
+impl Phase {
+	/// Whether the phase is signed or not.
+	pub fn is_signed(&self) -> bool {
+		matches!(self, Phase::Signed)
+	}
+
+	/// Whether the phase is signed validation or not.
+	pub fn is_signed_validation(&self) -> bool {
+		matches!(self, Phase::SignedValidation)
+	}
+
+	/// Whether the phase is off or not.
+	pub fn is_off(&self) -> bool {
+		matches!(self, Phase::Off)
+	}
+
+	/// Whether the phase is emergency or not.
+	pub fn is_emergency(&self) -> bool {
+		matches!(self, Phase::Emergency)
+	}
+}
+
+/// The status of the verification of the current best signed submission.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo, Default)]
+pub enum Status {
+	/// No submission is being verified.
+	#[default]
+	Nothing,
+	/// A submission is being verified, and the inner value is the next page to verify.
+	Ongoing(PageIndex),
+}
+
+/// The metadata of a registered signed submission.
+///
+/// The pages of the solution itself are stored separately, see
+/// [`crate::SubmissionStorage`].
+#[derive(
+	Encode, Decode, TypeInfo, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct SubmissionMetadata<T: Config> {
+	/// The score that this submission claims to have.
+	pub claimed_score: ElectionScore,
+	/// The total amount that is reserved from the submitter, for registering and for each of
+	/// the submitted pages.
+	pub deposit: BalanceOf<T>,
+	/// Which of the pages have been submitted, indexed by [`PageIndex`].
+	pub pages: BoundedVec<bool, T::Pages>,
+}
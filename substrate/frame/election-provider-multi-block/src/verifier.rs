@@ -0,0 +1,254 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The verifier of the multi-block election provider.
+//!
+//! During [`crate::Phase::SignedValidation`], the best registered signed solution is verified one
+//! page per block, starting from the most significant page. Each verified page is stored in
+//! [`crate::QueuedSolutionPages`]. Once the least significant page is verified, the supports of
+//! all pages are merged and checked against the claimed score. Only then is the solution
+//! considered queued, see [`crate::QueuedSolutionScore`].
+
+use crate::{
+	helpers, Config, DesiredTargets, Event, PagedVoterSnapshot, Pallet, QueuedSolutionPages,
+	QueuedSolutionScore, Round, SolutionOf, SortedScores, Status, SubmissionStorage,
+	SupportsPageOf, TargetSnapshot, VerificationStatus, WeightInfo,
+};
+use frame_election_provider_support::{ElectionProviderBase, NposSolution, PageIndex};
+use frame_support::{
+	ensure,
+	traits::{DefensiveResult, Get},
+	weights::Weight,
+};
+use sp_arithmetic::traits::Zero;
+use sp_npos_elections::{
+	assignment_ratio_to_staked_normalized, ElectionScore, EvaluateSupport, IdentifierT, Support,
+	Supports,
+};
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+/// Errors that can happen in the feasibility check.
+#[derive(Debug, Eq, PartialEq)]
+pub enum FeasibilityError {
+	/// Wrong number of winners presented.
+	WrongWinnerCount,
+	/// The snapshot is not available.
+	///
+	/// Kinda defensive: The pallet should technically never attempt to do a feasibility check when
+	/// no snapshot is present.
+	SnapshotUnavailable,
+	/// Internal error from the election crate.
+	NposElection(sp_npos_elections::Error),
+	/// A vote is invalid.
+	InvalidVote,
+	/// A voter is invalid.
+	InvalidVoter,
+	/// The given score was invalid.
+	InvalidScore,
+	/// Data Provider returned too many desired targets
+	TooManyDesiredTargets,
+	/// Conversion into bounded types failed.
+	///
+	/// Should never happen under correct configurations.
+	BoundedConversionFailed,
+}
+
+impl From<sp_npos_elections::Error> for FeasibilityError {
+	fn from(e: sp_npos_elections::Error) -> Self {
+		FeasibilityError::NposElection(e)
+	}
+}
+
+/// Merge multiple pages of supports into one, accumulating the backing of each winner across all
+/// pages.
+pub fn merge_supports<AccountId: IdentifierT>(
+	pages: impl IntoIterator<Item = Supports<AccountId>>,
+) -> Supports<AccountId> {
+	let mut merged: BTreeMap<AccountId, Support<AccountId>> = BTreeMap::new();
+	pages.into_iter().flatten().for_each(|(who, support)| {
+		let entry = merged.entry(who).or_default();
+		entry.total = entry.total.saturating_add(support.total);
+		entry.voters.extend(support.voters);
+	});
+	merged.into_iter().collect()
+}
+
+impl<T: Config> Pallet<T> {
+	/// Checks the feasibility of a single page of a solution, against the same page of the
+	/// snapshot.
+	///
+	/// Returns the supports of this page, if the page is feasible. Note that this does not check
+	/// the score, which can only be checked once all pages are known, see
+	/// [`Pallet::feasibility_check_final`].
+	pub fn feasibility_check_page(
+		solution: SolutionOf<T>,
+		page: PageIndex,
+	) -> Result<SupportsPageOf<T>, FeasibilityError> {
+		let snapshot_voters =
+			PagedVoterSnapshot::<T>::get(page).ok_or(FeasibilityError::SnapshotUnavailable)?;
+		let snapshot_targets =
+			TargetSnapshot::<T>::get().ok_or(FeasibilityError::SnapshotUnavailable)?;
+		let desired_targets =
+			DesiredTargets::<T>::get().ok_or(FeasibilityError::SnapshotUnavailable)?;
+
+		// Fail early if targets requested by data provider exceed maximum winners supported.
+		ensure!(
+			desired_targets <= T::MaxWinnersPerPage::get(),
+			FeasibilityError::TooManyDesiredTargets
+		);
+		// A single page cannot back more winners than the whole election.
+		ensure!(
+			solution.unique_targets().len() as u32 <= desired_targets,
+			FeasibilityError::WrongWinnerCount
+		);
+
+		// ----- Start building. First, we need some closures.
+		let cache = helpers::generate_voter_cache::<T>(&snapshot_voters);
+		let voter_at = helpers::voter_at_fn::<T>(&snapshot_voters);
+		let target_at = helpers::target_at_fn::<T>(&snapshot_targets);
+		let voter_index = helpers::voter_index_fn_usize::<T>(&cache);
+
+		// Then convert solution -> assignment. This will fail if any of the indices are gibberish,
+		// namely any of the voters or targets.
+		let assignments = solution
+			.into_assignment(voter_at, target_at)
+			.map_err::<FeasibilityError, _>(Into::into)?;
+
+		// Ensure that assignments is correct.
+		let _ = assignments.iter().try_for_each(|assignment| {
+			// Defensive-only: must exist in the snapshot.
+			let snapshot_index =
+				voter_index(&assignment.who).ok_or(FeasibilityError::InvalidVoter)?;
+			// Defensive-only: index comes from the snapshot, must exist.
+			let (_voter, _stake, targets) =
+				snapshot_voters.get(snapshot_index).ok_or(FeasibilityError::InvalidVoter)?;
+
+			// Check that all of the targets are valid based on the snapshot.
+			if assignment.distribution.iter().any(|(d, _)| !targets.contains(d)) {
+				return Err(FeasibilityError::InvalidVote)
+			}
+			Ok(())
+		})?;
+
+		// ----- Start building support. First, we need one more closure.
+		let stake_of = helpers::stake_of_fn::<T>(&snapshot_voters, &cache);
+
+		// This might fail if the normalization fails. Very unlikely.
+		let staked_assignments = assignment_ratio_to_staked_normalized(assignments, stake_of)
+			.map_err::<FeasibilityError, _>(Into::into)?;
+		let supports = sp_npos_elections::to_supports(&staked_assignments);
+
+		// Size of winners is at most `desired_targets` <= `MaxWinnersPerPage`.
+		supports
+			.try_into()
+			.defensive_map_err(|_| FeasibilityError::BoundedConversionFailed)
+	}
+
+	/// Checks the feasibility of a solution once all of its pages are verified and stored in
+	/// [`QueuedSolutionPages`].
+	///
+	/// The merged supports of all pages must elect exactly the desired number of targets, and
+	/// their score must be equal to `claimed_score`.
+	pub fn feasibility_check_final(claimed_score: ElectionScore) -> Result<(), FeasibilityError> {
+		let desired_targets =
+			DesiredTargets::<T>::get().ok_or(FeasibilityError::SnapshotUnavailable)?;
+
+		let supports =
+			merge_supports(QueuedSolutionPages::<T>::iter_values().map(|page| page.into_inner()));
+
+		ensure!(supports.len() as u32 == desired_targets, FeasibilityError::WrongWinnerCount);
+
+		let known_score = supports.evaluate();
+		ensure!(known_score == claimed_score, FeasibilityError::InvalidScore);
+
+		Ok(())
+	}
+
+	/// Verify the next page of the best registered signed solution.
+	///
+	/// Does nothing if a solution is already queued, or if no solution is registered. Returns the
+	/// consumed weight.
+	pub(crate) fn verify_next_page() -> Weight {
+		if QueuedSolutionScore::<T>::exists() {
+			return T::WeightInfo::on_initialize_nothing()
+		}
+
+		let Some((who, claimed_score)) = SortedScores::<T>::get().last().cloned() else {
+			return T::WeightInfo::on_initialize_nothing()
+		};
+
+		let page = match VerificationStatus::<T>::get() {
+			Status::Ongoing(page) => page,
+			Status::Nothing => Self::msp(),
+		};
+
+		let (outcome, voters) = match SubmissionStorage::<T>::get(&who, page) {
+			Some(solution) => {
+				let voters = solution.voter_count() as u32;
+				(Self::feasibility_check_page(solution, page), voters)
+			},
+			// pages that were never submitted are empty.
+			None => (Ok(Default::default()), 0),
+		};
+		let mut weight = T::WeightInfo::on_initialize_verify_page(voters);
+
+		let outcome = outcome.and_then(|supports| {
+			QueuedSolutionPages::<T>::insert(page, supports);
+			if page.is_zero() {
+				weight
+					.saturating_accrue(T::WeightInfo::on_initialize_verify_final(T::Pages::get()));
+				Self::feasibility_check_final(claimed_score)
+			} else {
+				VerificationStatus::<T>::put(Status::Ongoing(page - 1));
+				Ok(())
+			}
+		});
+
+		match outcome {
+			Ok(()) if page.is_zero() => {
+				log!(info, "queued signed solution of {:?} with score {:?}", who, claimed_score);
+				VerificationStatus::<T>::kill();
+				QueuedSolutionScore::<T>::put(claimed_score);
+				Self::deposit_event(Event::Queued {
+					round: Round::<T>::get(),
+					who: who.clone(),
+					score: claimed_score,
+				});
+				Self::finalize_signed_accept_solution(&who);
+			},
+			Ok(()) => {},
+			Err(why) => {
+				log!(warn, "signed solution of {:?} failed at page {} due to {:?}", who, page, why);
+				Self::clear_verification();
+				Self::deposit_event(Event::VerificationFailed {
+					round: Round::<T>::get(),
+					who: who.clone(),
+				});
+				Self::finalize_signed_reject_solution(&who);
+			},
+		}
+
+		weight
+	}
+
+	/// Clear all the storage items of the verifier.
+	pub(crate) fn clear_verification() {
+		VerificationStatus::<T>::kill();
+		QueuedSolutionScore::<T>::kill();
+		let _ = QueuedSolutionPages::<T>::clear(u32::MAX, None);
+	}
+}
@@ -0,0 +1,137 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_election_provider_multi_block.
+//!
+//! These weights are placeholders until the benchmarks in `benchmarking.rs` are run on reference
+//! hardware. They account for the storage accesses of each operation, but their computational
+//! part is an estimate. No runtime in this repository includes the pallet yet, so they are to be
+//! regenerated with `benchmark pallet --pallet=pallet_election_provider_multi_block` on the first
+//! runtime that does.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for pallet_election_provider_multi_block.
+pub trait WeightInfo {
+	fn on_initialize_nothing() -> Weight;
+	fn on_initialize_snapshot_msp(v: u32, t: u32) -> Weight;
+	fn on_initialize_snapshot_page(v: u32) -> Weight;
+	fn on_initialize_into_signed_validation() -> Weight;
+	fn on_initialize_verify_page(v: u32) -> Weight;
+	fn on_initialize_verify_final(p: u32) -> Weight;
+	fn register() -> Weight;
+	fn submit_page() -> Weight;
+	fn elect_page() -> Weight;
+}
+
+/// Weights for pallet_election_provider_multi_block, only accounting for the storage accesses of
+/// the database of the runtime `T`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn on_initialize_nothing() -> Weight {
+		T::DbWeight::get().reads(1_u64)
+	}
+	fn on_initialize_snapshot_msp(v: u32, t: u32) -> Weight {
+		Weight::from_parts(1_000_000, 0)
+			.saturating_add(Weight::from_parts(100_000, 0).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_add(v.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn on_initialize_snapshot_page(v: u32) -> Weight {
+		Weight::from_parts(1_000_000, 0)
+			.saturating_add(Weight::from_parts(100_000, 0).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_add(v.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn on_initialize_into_signed_validation() -> Weight {
+		T::DbWeight::get().reads_writes(1_u64, 1_u64)
+	}
+	fn on_initialize_verify_page(v: u32) -> Weight {
+		Weight::from_parts(1_000_000, 0)
+			.saturating_add(Weight::from_parts(200_000, 0).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn on_initialize_verify_final(p: u32) -> Weight {
+		Weight::from_parts(1_000_000, 0)
+			.saturating_add(Weight::from_parts(500_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_add(p.into())))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_add(p.into())))
+	}
+	fn register() -> Weight {
+		T::DbWeight::get().reads_writes(4_u64, 3_u64)
+	}
+	fn submit_page() -> Weight {
+		T::DbWeight::get().reads_writes(4_u64, 3_u64)
+	}
+	fn elect_page() -> Weight {
+		T::DbWeight::get().reads(3_u64)
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn on_initialize_nothing() -> Weight {
+		RocksDbWeight::get().reads(1_u64)
+	}
+	fn on_initialize_snapshot_msp(v: u32, t: u32) -> Weight {
+		Weight::from_parts(1_000_000, 0)
+			.saturating_add(Weight::from_parts(100_000, 0).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_add(v.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn on_initialize_snapshot_page(v: u32) -> Weight {
+		Weight::from_parts(1_000_000, 0)
+			.saturating_add(Weight::from_parts(100_000, 0).saturating_mul(v.into()))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_add(v.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn on_initialize_into_signed_validation() -> Weight {
+		RocksDbWeight::get().reads_writes(1_u64, 1_u64)
+	}
+	fn on_initialize_verify_page(v: u32) -> Weight {
+		Weight::from_parts(1_000_000, 0)
+			.saturating_add(Weight::from_parts(200_000, 0).saturating_mul(v.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn on_initialize_verify_final(p: u32) -> Weight {
+		Weight::from_parts(1_000_000, 0)
+			.saturating_add(Weight::from_parts(500_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_add(p.into())))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_add(p.into())))
+	}
+	fn register() -> Weight {
+		RocksDbWeight::get().reads_writes(4_u64, 3_u64)
+	}
+	fn submit_page() -> Weight {
+		RocksDbWeight::get().reads_writes(4_u64, 3_u64)
+	}
+	fn elect_page() -> Weight {
+		RocksDbWeight::get().reads(3_u64)
+	}
+}
@@ -116,7 +116,7 @@ pub fn roll_to_round(n: u32) {
 
 	while MultiPhase::round() != n {
 		roll_to_signed();
-		frame_support::assert_ok!(MultiPhase::elect());
+		frame_support::assert_ok!(MultiPhase::elect(0));
 	}
 }
 
@@ -332,6 +332,7 @@ impl ElectionProviderBase for MockFallback {
 	type Error = &'static str;
 	type DataProvider = StakingMock;
 	type MaxWinners = MaxWinners;
+	type Pages = ConstU32<1>;
 }
 
 impl InstantElectionProvider for MockFallback {
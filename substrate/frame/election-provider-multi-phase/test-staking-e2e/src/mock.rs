@@ -257,6 +257,7 @@ impl pallet_staking::Config for Runtime {
 	type CurrencyToVote = ();
 	type RewardRemainder = ();
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type Slash = (); // burn slashes
 	type Reward = (); // rewards are minted from the void
 	type SessionsPerEra = SessionsPerEra;
@@ -441,8 +442,8 @@ impl Default for BalancesExtBuilder {
 			(90, 100),
 			(100, 100),
 			(200, 100),
-			// stashes
-			(11, 1000),
+			// stashes, with at least an existential deposit on top of their stake.
+			(11, 1000 + 1),
 			(21, 2000),
 			(31, 3000),
 			(41, 4000),
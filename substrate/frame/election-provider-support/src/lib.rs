@@ -56,8 +56,22 @@
 //!
 //! To accommodate both type of elections in one trait, the traits lean toward **stateful
 //! election**, as it is more general than the stateless. This is why [`ElectionProvider::elect`]
-//! has no parameters. All value and type parameter must be provided by the [`ElectionDataProvider`]
-//! trait, even if the election happens immediately.
+//! only receives the index of the page that is requested. All value and type parameter must be
+//! provided by the [`ElectionDataProvider`] trait, even if the election happens immediately.
+//!
+//! ## Paged Elections
+//!
+//! An election provider can spread its work over multiple pages, as specified by
+//! [`ElectionProviderBase::Pages`]. In that case, the receiver of the election is expected to call
+//! [`ElectionProvider::elect`] for each page, starting from the most significant page
+//! ([`ElectionProviderBase::msp`]) down to the least significant page
+//! ([`ElectionProviderBase::lsp`]), which is always `0`. The union of all returned pages makes up
+//! the full election result. Election providers that are not paged set `Pages` to `1`, and return
+//! their full result for page `0`.
+//!
+//! Similarly, the data provider can be queried for a single page of voters via
+//! [`ElectionDataProvider::electing_voters_paged`]. The default implementation of this function is
+//! not paged: it returns all the voters in page `0` and nothing in other pages.
 //!
 //! ## Election Data
 //!
@@ -139,12 +153,12 @@
 //!         type Error = &'static str;
 //!         type DataProvider = T::DataProvider;
 //!         type MaxWinners = ConstU32<{ u32::MAX }>;
-//!
+//!         type Pages = ConstU32<1>;
 //!     }
 //!
 //!     impl<T: Config> ElectionProvider for GenericElectionProvider<T> {
 //!         fn ongoing() -> bool { false }
-//!         fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
+//!         fn elect(_page: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error> {
 //!             Self::DataProvider::electable_targets(DataProviderBounds::default())
 //!                 .map_err(|_| "failed to elect")
 //!                 .map(|t| bounded_vec![(t[0], Support::default())])
@@ -177,6 +191,7 @@ pub mod bounds;
 pub mod onchain;
 pub mod traits;
 
+use frame_support::traits::ConstU32;
 use sp_runtime::{
 	traits::{Bounded, Saturating, Zero},
 	RuntimeDebug,
@@ -273,6 +288,11 @@ pub type IndexAssignmentOf<C> = IndexAssignment<
 	<C as NposSolution>::Accuracy,
 >;
 
+/// The index of a page of a paged election.
+///
+/// Page `0` is the least significant page, and is always the last one to be processed.
+pub type PageIndex = u32;
+
 /// Types that are used by the data provider trait.
 pub mod data_provider {
 	/// Alias for the result type of the election data provider.
@@ -306,6 +326,28 @@ pub trait ElectionDataProvider {
 	/// appropriate weight at the end of execution with the system pallet directly.
 	fn electing_voters(bounds: DataProviderBounds) -> data_provider::Result<Vec<VoterOf<Self>>>;
 
+	/// A single page of the voters that participate in the election.
+	///
+	/// Pages are requested from the most significant page down to page `0`, and each call is
+	/// expected to return the voters that follow the ones returned by the previous page. Once page
+	/// `0` is returned, the next call starts over from the first voter. `bounds` apply to the
+	/// returned page only.
+	///
+	/// By default, the data provider is not paged and all voters are returned in page `0`.
+	///
+	/// This should be implemented as a self-weighing function. The implementor should register its
+	/// appropriate weight at the end of execution with the system pallet directly.
+	fn electing_voters_paged(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<VoterOf<Self>>> {
+		if page.is_zero() {
+			Self::electing_voters(bounds)
+		} else {
+			Ok(Default::default())
+		}
+	}
+
 	/// The number of targets to elect.
 	///
 	/// This should be implemented as a self-weighing function. The implementor should register its
@@ -381,12 +423,27 @@ pub trait ElectionProviderBase {
 	/// truncate the output to meet this bound.
 	type MaxWinners: Get<u32>;
 
+	/// The number of pages that this election provider returns its result in.
+	///
+	/// `MaxWinners` applies to each page.
+	type Pages: Get<PageIndex>;
+
 	/// The data provider of the election.
 	type DataProvider: ElectionDataProvider<
 		AccountId = Self::AccountId,
 		BlockNumber = Self::BlockNumber,
 	>;
 
+	/// The most significant page of the election, the first one to be requested.
+	fn msp() -> PageIndex {
+		Self::Pages::get().saturating_sub(1)
+	}
+
+	/// The least significant page of the election, the last one to be requested.
+	fn lsp() -> PageIndex {
+		Zero::zero()
+	}
+
 	/// checked call to `Self::DataProvider::desired_targets()` ensuring the value never exceeds
 	/// [`Self::MaxWinners`].
 	fn desired_targets_checked() -> data_provider::Result<u32> {
@@ -411,10 +468,14 @@ pub trait ElectionProvider: ElectionProviderBase {
 	/// Indicate if this election provider is currently ongoing an asynchronous election or not.
 	fn ongoing() -> bool;
 
-	/// Performs the election. This should be implemented as a self-weighing function. The
-	/// implementor should register its appropriate weight at the end of execution with the
-	/// system pallet directly.
-	fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error>;
+	/// Performs the election, and returns the given `page` of its result. This should be
+	/// implemented as a self-weighing function. The implementor should register its appropriate
+	/// weight at the end of execution with the system pallet directly.
+	///
+	/// Pages are requested from [`ElectionProviderBase::msp`] down to
+	/// [`ElectionProviderBase::lsp`]. Requesting the least significant page concludes the
+	/// election.
+	fn elect(page: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error>;
 }
 
 /// A (almost) marker trait that signifies an election provider as working synchronously. i.e. being
@@ -443,6 +504,7 @@ where
 	type BlockNumber = BlockNumber;
 	type Error = &'static str;
 	type MaxWinners = MaxWinners;
+	type Pages = ConstU32<1>;
 	type DataProvider = DataProvider;
 }
 
@@ -456,7 +518,7 @@ where
 		false
 	}
 
-	fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
+	fn elect(_page: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error> {
 		Err("`NoElection` cannot do anything.")
 	}
 }
@@ -22,9 +22,12 @@
 use crate::{
 	bounds::{DataProviderBounds, ElectionBounds, ElectionBoundsBuilder},
 	BoundedSupportsOf, Debug, ElectionDataProvider, ElectionProvider, ElectionProviderBase,
-	InstantElectionProvider, NposSolver, WeightInfo,
+	InstantElectionProvider, NposSolver, PageIndex, WeightInfo,
+};
+use frame_support::{
+	dispatch::DispatchClass,
+	traits::{ConstU32, Get},
 };
-use frame_support::{dispatch::DispatchClass, traits::Get};
 use sp_npos_elections::{
 	assignment_ratio_to_staked_normalized, to_supports, BoundedSupports, ElectionResult, VoteWeight,
 };
@@ -52,6 +55,11 @@ impl From<sp_npos_elections::Error> for Error {
 ///
 /// This implements both `ElectionProvider` and `InstantElectionProvider`.
 ///
+/// As an `ElectionProvider`, it is not paged on its own, but it can serve any page that is
+/// requested from it: each page is elected independently, using the voters of that page (see
+/// [`ElectionDataProvider::electing_voters_paged`]) and all the electable targets. This makes it
+/// usable as the fallback of a paged election provider.
+///
 /// This type has some utilities to make it safe. Nonetheless, it should be used with utmost care. A
 /// thoughtful value must be set as [`Config::Bounds`] to ensure the size of the input is sensible.
 pub struct OnChainExecution<T: Config>(PhantomData<T>);
@@ -96,12 +104,20 @@ pub type OnChainBoundedSupportsOf<E> = BoundedSupports<
 	<E as Config>::MaxWinners,
 >;
 
+/// Run the election with the given input `bounds`.
+///
+/// If `maybe_page` is `None`, all the voters are fetched at once, otherwise only the voters of the
+/// given page are used.
 fn elect_with_input_bounds<T: Config>(
 	bounds: ElectionBounds,
+	maybe_page: Option<PageIndex>,
 ) -> Result<OnChainBoundedSupportsOf<T>, Error> {
-	let (voters, targets) = T::DataProvider::electing_voters(bounds.voters)
-		.and_then(|voters| Ok((voters, T::DataProvider::electable_targets(bounds.targets)?)))
-		.map_err(Error::DataProvider)?;
+	let (voters, targets) = match maybe_page {
+		Some(page) => T::DataProvider::electing_voters_paged(bounds.voters, page),
+		None => T::DataProvider::electing_voters(bounds.voters),
+	}
+	.and_then(|voters| Ok((voters, T::DataProvider::electable_targets(bounds.targets)?)))
+	.map_err(Error::DataProvider)?;
 
 	let desired_targets = T::DataProvider::desired_targets().map_err(Error::DataProvider)?;
 
@@ -150,6 +166,7 @@ impl<T: Config> ElectionProviderBase for OnChainExecution<T> {
 	type BlockNumber = frame_system::pallet_prelude::BlockNumberFor<T::System>;
 	type Error = Error;
 	type MaxWinners = T::MaxWinners;
+	type Pages = ConstU32<1>;
 	type DataProvider = T::DataProvider;
 }
 
@@ -163,7 +180,7 @@ impl<T: Config> InstantElectionProvider for OnChainExecution<T> {
 			.targets_or_lower(forced_input_targets_bounds)
 			.build();
 
-		elect_with_input_bounds::<T>(elections_bounds)
+		elect_with_input_bounds::<T>(elections_bounds, None)
 	}
 }
 
@@ -172,9 +189,9 @@ impl<T: Config> ElectionProvider for OnChainExecution<T> {
 		false
 	}
 
-	fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
+	fn elect(page: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error> {
 		let election_bounds = ElectionBoundsBuilder::from(T::Bounds::get()).build();
-		elect_with_input_bounds::<T>(election_bounds)
+		elect_with_input_bounds::<T>(election_bounds, Some(page))
 	}
 }
 
@@ -291,7 +308,7 @@ mod tests {
 	fn onchain_seq_phragmen_works() {
 		sp_io::TestExternalities::new_empty().execute_with(|| {
 			assert_eq!(
-				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect().unwrap(),
+				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect(0).unwrap(),
 				vec![
 					(10, Support { total: 25, voters: vec![(1, 10), (3, 15)] }),
 					(30, Support { total: 35, voters: vec![(2, 20), (3, 15)] })
@@ -308,7 +325,7 @@ mod tests {
 			MaxWinners::set(9);
 
 			assert_noop!(
-				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect(),
+				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect(0),
 				Error::TooManyWinners,
 			);
 		})
@@ -318,7 +335,7 @@ mod tests {
 	fn onchain_phragmms_works() {
 		sp_io::TestExternalities::new_empty().execute_with(|| {
 			assert_eq!(
-				<OnChainExecution::<PhragMMSParams> as ElectionProvider>::elect().unwrap(),
+				<OnChainExecution::<PhragMMSParams> as ElectionProvider>::elect(0).unwrap(),
 				vec![
 					(10, Support { total: 25, voters: vec![(1, 10), (3, 15)] }),
 					(30, Support { total: 35, voters: vec![(2, 20), (3, 15)] })
@@ -326,4 +343,21 @@ mod tests {
 			);
 		})
 	}
+
+	#[test]
+	fn onchain_elect_other_pages_of_unpaged_data_provider() {
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			// the data provider returns all of its voters in page 0, nothing is elected in other
+			// pages.
+			assert!(<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect(1)
+				.unwrap()
+				.is_empty());
+			assert_eq!(
+				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect(0)
+					.unwrap()
+					.len(),
+				2
+			);
+		})
+	}
 }
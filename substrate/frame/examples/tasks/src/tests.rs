@@ -130,3 +130,110 @@ fn task_execution_fails_for_invalid_task() {
 		);
 	});
 }
+
+#[cfg(feature = "experimental")]
+#[test]
+fn task_execution_works_unsigned() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Numbers::<Runtime>::insert(1, 4);
+
+		let task =
+			<Runtime as frame_system::Config>::RuntimeTask::TasksExample(crate::pallet::Task::<
+				Runtime,
+			>::AddNumberIntoTotal {
+				i: 1u32,
+			});
+		assert_ok!(System::do_task(RuntimeOrigin::none(), task.clone()));
+		assert_eq!(crate::Total::<Runtime>::get(), (1, 4));
+		System::assert_last_event(frame_system::Event::<Runtime>::TaskCompleted { task }.into());
+	});
+}
+
+#[cfg(feature = "experimental")]
+#[test]
+fn task_validate_unsigned_works() {
+	use frame_support::unsigned::ValidateUnsigned;
+	use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+	new_test_ext().execute_with(|| {
+		Numbers::<Runtime>::insert(1, 4);
+
+		let task = |i| {
+			<Runtime as frame_system::Config>::RuntimeTask::TasksExample(crate::pallet::Task::<
+				Runtime,
+			>::AddNumberIntoTotal {
+				i,
+			})
+		};
+
+		let valid = System::validate_unsigned(
+			TransactionSource::Local,
+			&frame_system::Call::do_task { task: task(1) },
+		)
+		.unwrap();
+		// The same task always provides the same tag.
+		assert_eq!(
+			valid.provides,
+			System::validate_unsigned(
+				TransactionSource::InBlock,
+				&frame_system::Call::do_task { task: task(1) },
+			)
+			.unwrap()
+			.provides
+		);
+		assert_eq!(valid.priority, 0);
+		assert!(!valid.propagate);
+
+		// Tasks gossiped by other nodes are rejected.
+		assert_eq!(
+			System::validate_unsigned(
+				TransactionSource::External,
+				&frame_system::Call::do_task { task: task(1) },
+			),
+			Err(InvalidTransaction::Call.into())
+		);
+
+		assert_eq!(
+			System::validate_unsigned(
+				TransactionSource::Local,
+				&frame_system::Call::do_task { task: task(0) },
+			),
+			Err(InvalidTransaction::Call.into())
+		);
+	});
+}
+
+#[cfg(feature = "experimental")]
+#[test]
+fn valid_tasks_are_submitted_by_offchain_worker() {
+	use codec::Decode;
+	use sp_core::offchain::{testing::TestTransactionPoolExt, TransactionPoolExt};
+
+	let (pool, state) = TestTransactionPoolExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		Numbers::<Runtime>::insert(0, 1);
+		Numbers::<Runtime>::insert(1, 4);
+
+		frame_system::offchain::submit_valid_tasks::<Runtime>();
+
+		let do_task = |i| {
+			RuntimeCall::System(frame_system::Call::do_task {
+				task: RuntimeTask::TasksExample(crate::pallet::Task::AddNumberIntoTotal { i }),
+			})
+		};
+		let submitted = state
+			.read()
+			.transactions
+			.iter()
+			.map(|tx| UncheckedExtrinsic::decode(&mut &tx[..]).unwrap())
+			.collect::<Vec<_>>();
+		assert_eq!(submitted.len(), 2);
+		assert!(submitted.iter().all(|xt| xt.signature.is_none()));
+		assert!(submitted.iter().any(|xt| xt.function == do_task(0)));
+		assert!(submitted.iter().any(|xt| xt.function == do_task(1)));
+	});
+}
@@ -119,6 +119,7 @@
 use codec::{Codec, Encode};
 use frame_support::{
 	dispatch::{DispatchClass, DispatchInfo, GetDispatchInfo, PostDispatchInfo},
+	migrations::MultiStepMigrator,
 	pallet_prelude::InvalidTransaction,
 	traits::{
 		BeforeAllRuntimeMigrations, EnsureInherentsAreFirst, ExecuteBlock, OffchainWorker,
@@ -152,6 +153,11 @@ use ::{
 #[allow(dead_code)]
 const LOG_TARGET: &str = "runtime::executive";
 
+/// The maximal number of steps that try-runtime uses to drive the multi-block migrations to
+/// completion, before it considers them stuck.
+#[cfg(feature = "try-runtime")]
+const MAX_MULTI_STEP_MIGRATION_STEPS: u32 = 100_000;
+
 pub type CheckedOf<E, C> = <E as Checkable<C>>::Checked;
 pub type CallOf<E, C> = <CheckedOf<E, C> as Applyable>::Call;
 pub type OriginOf<E, C> = <CallOf<E, C> as Dispatchable>::RuntimeOrigin;
@@ -167,6 +173,8 @@ pub type OriginOf<E, C> = <CallOf<E, C> as Dispatchable>::RuntimeOrigin;
 ///   used to call hooks e.g. `on_initialize`.
 /// - `OnRuntimeUpgrade`: Custom logic that should be called after a runtime upgrade. Modules are
 ///   already called by `AllPalletsWithSystem`. It will be called before all modules will be called.
+/// - `MultiStepMigrator`: Drives multi-block migrations. Extrinsics other than inherents are
+///   rejected while it reports ongoing migrations.
 pub struct Executive<
 	System,
 	Block,
@@ -174,6 +182,7 @@ pub struct Executive<
 	UnsignedValidator,
 	AllPalletsWithSystem,
 	OnRuntimeUpgrade = (),
+	MultiStepMigrator = (),
 >(
 	PhantomData<(
 		System,
@@ -182,6 +191,7 @@ pub struct Executive<
 		UnsignedValidator,
 		AllPalletsWithSystem,
 		OnRuntimeUpgrade,
+		MultiStepMigrator,
 	)>,
 );
 
@@ -200,8 +210,17 @@ impl<
 			+ OnFinalize<BlockNumberFor<System>>
 			+ OffchainWorker<BlockNumberFor<System>>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
+		MultiStepMigratorT: MultiStepMigrator,
 	> ExecuteBlock<Block>
-	for Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+	for Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		MultiStepMigratorT,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
@@ -218,6 +237,7 @@ where
 			UnsignedValidator,
 			AllPalletsWithSystem,
 			COnRuntimeUpgrade,
+			MultiStepMigratorT,
 		>::execute_block(block);
 	}
 }
@@ -240,7 +260,17 @@ impl<
 			+ TryState<BlockNumberFor<System>>
 			+ TryDecodeEntireStorage,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
-	> Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+		MultiStepMigratorT: MultiStepMigrator,
+	>
+	Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		MultiStepMigratorT,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
@@ -383,6 +413,8 @@ where
 			),
 		);
 
+		let multi_step_weight = Self::try_drive_multi_step_migrations()?;
+
 		// Nothing should modify the state after the migrations ran:
 		let _guard = StorageNoopGuard::default();
 
@@ -400,7 +432,43 @@ where
 			)?;
 		}
 
-		Ok(before_all_weight.saturating_add(try_on_runtime_upgrade_weight))
+		Ok(before_all_weight
+			.saturating_add(try_on_runtime_upgrade_weight)
+			.saturating_add(multi_step_weight))
+	}
+
+	/// Step the [`MultiStepMigrator`] until it reports that no migrations are ongoing anymore.
+	///
+	/// Each step is executed as if it were the only step of a block, this does not advance the
+	/// block number though. Errors if the migrations did not complete within
+	/// [`MAX_MULTI_STEP_MIGRATION_STEPS`] steps.
+	fn try_drive_multi_step_migrations() -> Result<Weight, TryRuntimeError> {
+		let mut weight = Weight::zero();
+		let mut steps = 0u32;
+
+		while MultiStepMigratorT::ongoing() {
+			if steps >= MAX_MULTI_STEP_MIGRATION_STEPS {
+				log::error!(
+					target: LOG_TARGET,
+					"try-runtime: multi-block migrations did not complete within {} steps",
+					MAX_MULTI_STEP_MIGRATION_STEPS,
+				);
+				return Err("Multi-block migrations did not complete".into())
+			}
+
+			weight.saturating_accrue(MultiStepMigratorT::step());
+			steps += 1;
+		}
+
+		if steps > 0 {
+			log::info!(
+				target: LOG_TARGET,
+				"try-runtime: multi-block migrations completed in {} steps",
+				steps,
+			);
+		}
+
+		Ok(weight)
 	}
 
 	/// Logs the result of trying to decode the entire state.
@@ -451,7 +519,17 @@ impl<
 			+ OnFinalize<BlockNumberFor<System>>
 			+ OffchainWorker<BlockNumberFor<System>>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
-	> Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+		MultiStepMigratorT: MultiStepMigrator,
+	>
+	Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		MultiStepMigratorT,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
@@ -510,6 +588,11 @@ where
 		weight = weight.saturating_add(<AllPalletsWithSystem as OnInitialize<
 			BlockNumberFor<System>,
 		>>::on_initialize(*block_number));
+		// Multi-block migrations are stepped after all pallets were initialized, such that they
+		// see a consistent state.
+		if MultiStepMigratorT::ongoing() {
+			weight = weight.saturating_add(MultiStepMigratorT::step());
+		}
 		weight = weight.saturating_add(
 			<System::BlockWeights as frame_support::traits::Get<_>>::get().base_block,
 		);
@@ -629,6 +712,13 @@ where
 				ext=?sp_core::hexdisplay::HexDisplay::from(&encoded)));
 		// Verify that the signature is good.
 		let xt = uxt.check(&Default::default())?;
+		let dispatch_info = xt.get_dispatch_info();
+
+		// Only inherents may be applied while multi-block migrations are ongoing. The error is
+		// not fatal for the transaction, so that it can be included once the migrations are done.
+		if dispatch_info.class != DispatchClass::Mandatory && MultiStepMigratorT::ongoing() {
+			return Err(InvalidTransaction::ExhaustsResources.into())
+		}
 
 		// We don't need to make sure to `note_extrinsic` only after we know it's going to be
 		// executed to prevent it from leaking in storage since at this point, it will either
@@ -637,8 +727,7 @@ where
 
 		// AUDIT: Under no circumstances may this function panic from here onwards.
 
-		// Decode parameters and dispatch
-		let dispatch_info = xt.get_dispatch_info();
+		// Dispatch
 		let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)?;
 
 		// Mandatory(inherents) are not allowed to fail.
@@ -1011,6 +1100,28 @@ mod tests {
 		}
 	}
 
+	parameter_types! {
+		pub static MbmActive: bool = false;
+		pub static MbmStepsLeft: u32 = 0;
+	}
+
+	/// Multi-block migrator that is ongoing while `MbmStepsLeft` is non-zero and `MbmActive` set.
+	struct MockedMigrator;
+	impl MultiStepMigrator for MockedMigrator {
+		fn ongoing() -> bool {
+			MbmActive::get()
+		}
+
+		fn step() -> Weight {
+			let left = MbmStepsLeft::get().saturating_sub(1);
+			MbmStepsLeft::set(left);
+			if left == 0 {
+				MbmActive::set(false);
+			}
+			Weight::from_parts(10, 0)
+		}
+	}
+
 	type Executive = super::Executive<
 		Runtime,
 		Block<TestXt>,
@@ -1018,6 +1129,7 @@ mod tests {
 		Runtime,
 		AllPalletsWithSystem,
 		CustomOnRuntimeUpgrade,
+		MockedMigrator,
 	>;
 
 	fn extra(nonce: u64, fee: Balance) -> SignedExtra {
@@ -1697,4 +1809,74 @@ mod tests {
 			);
 		})
 	}
+
+	#[test]
+	fn extrinsics_are_rejected_while_migrations_are_ongoing() {
+		let inherent = TestXt::new(RuntimeCall::Custom(custom::Call::inherent_call {}), None);
+		let xt = TestXt::new(call_transfer(33, 0), sign_extra(1, 0, 0));
+
+		new_test_ext(1).execute_with(|| {
+			MbmActive::set(true);
+			MbmStepsLeft::set(2);
+
+			Executive::initialize_block(&Header::new(
+				1,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			));
+			assert_eq!(MbmStepsLeft::get(), 1);
+
+			Executive::apply_extrinsic(inherent.clone()).unwrap().unwrap();
+			assert_err!(
+				Executive::apply_extrinsic(xt.clone()),
+				TransactionValidityError::Invalid(InvalidTransaction::ExhaustsResources)
+			);
+			Executive::finalize_block();
+
+			Executive::initialize_block(&Header::new(
+				2,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			));
+			assert_eq!(MbmStepsLeft::get(), 0);
+			assert!(!MbmActive::get());
+
+			Executive::apply_extrinsic(xt).unwrap().unwrap();
+		});
+	}
+
+	#[test]
+	fn migration_steps_weight_is_registered() {
+		let header = |number| {
+			Header::new(
+				number,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			)
+		};
+
+		new_test_ext(1).execute_with(|| {
+			// The first block executes the runtime upgrade, which registers additional weight.
+			Executive::initialize_block(&header(1));
+			Executive::finalize_block();
+
+			Executive::initialize_block(&header(2));
+			let without_step = System::block_weight().get(DispatchClass::Mandatory).ref_time();
+			Executive::finalize_block();
+
+			MbmActive::set(true);
+			MbmStepsLeft::set(5);
+			Executive::initialize_block(&header(3));
+			let with_step = System::block_weight().get(DispatchClass::Mandatory).ref_time();
+
+			assert_eq!(with_step, without_step + 10);
+			assert_eq!(MbmStepsLeft::get(), 4);
+		});
+	}
 }
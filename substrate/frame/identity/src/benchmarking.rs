@@ -733,5 +733,45 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn verify_field() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let deadline = frame_system::Pallet::<T>::block_number();
+		let (info, field, proof) = T::BenchmarkHelper::verifiable_identity(|field, value| {
+			Identity::<T>::verification_challenge(&caller, field, value, deadline)
+		})
+		.ok_or(BenchmarkError::Weightless)?;
+		let _ =
+			T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		Identity::<T>::set_identity(RawOrigin::Signed(caller.clone()).into(), Box::new(info))?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), field.clone(), proof, deadline);
+
+		ensure!(Identity::<T>::is_field_verified(&caller, &field), "Field not verified");
+		Ok(())
+	}
+
+	#[benchmark]
+	fn attest_field() -> Result<(), BenchmarkError> {
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		let (info, field, _) = T::BenchmarkHelper::verifiable_identity(|_, _| Vec::new())
+			.ok_or(BenchmarkError::Weightless)?;
+		let value_hash = T::Hashing::hash(&info.field_value(&field).ok_or("Field not set")?);
+		let _ =
+			T::Currency::make_free_balance_be(&target, BalanceOf::<T>::max_value() / 2u32.into());
+		Identity::<T>::set_identity(RawOrigin::Signed(target.clone()).into(), Box::new(info))?;
+
+		let origin = T::VerificationOracleOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, target_lookup, field.clone(), value_hash);
+
+		ensure!(Identity::<T>::is_field_verified(&target, &field), "Field not verified");
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Identity, crate::tests::new_test_ext(), crate::tests::Test);
 }
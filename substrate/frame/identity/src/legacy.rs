@@ -129,6 +129,21 @@ impl<FieldLimit: Get<u32> + 'static> IdentityInformationProvider for IdentityInf
 		self.fields().bits() & fields == fields
 	}
 
+	fn field_value(&self, field: &Self::FieldsIdentifier) -> Option<Vec<u8>> {
+		let field = BitFlags::<IdentityField>::from_bits(*field).ok()?.exactly_one()?;
+		let value = match field {
+			IdentityField::Display => self.display.as_bytes(),
+			IdentityField::Legal => self.legal.as_bytes(),
+			IdentityField::Web => self.web.as_bytes(),
+			IdentityField::Riot => self.riot.as_bytes(),
+			IdentityField::Email => self.email.as_bytes(),
+			IdentityField::PgpFingerprint => self.pgp_fingerprint.as_ref().map(|f| &f[..]),
+			IdentityField::Image => self.image.as_bytes(),
+			IdentityField::Twitter => self.twitter.as_bytes(),
+		};
+		value.map(|v| v.to_vec())
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	fn create_identity_info() -> Self {
 		let data = Data::Raw(vec![0; 32].try_into().unwrap());
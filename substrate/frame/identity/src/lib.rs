@@ -52,6 +52,15 @@
 //! Users can have multiple usernames that map to the same `AccountId`, however one `AccountId` can
 //! only map to a single username, known as the _primary_.
 //!
+//! ### Field Verification
+//!
+//! Besides registrar judgements, users can prove control of the values of individual identity
+//! fields on-chain. A user either submits a proof over a challenge which is specific to the chain,
+//! the account and the field value, which is checked by the configured `FieldVerifier`, or has a
+//! verification oracle attest to it (e.g. after checking a DNS record). Verifications are recorded
+//! per field, expire after `VerificationPeriod` and only hold for as long as the field keeps the
+//! value which was verified. Other pallets can query them through [`VerifiedFieldsProvider`].
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -66,6 +75,7 @@
 //! * `remove_expired_approval` - Remove a username that was issued but never accepted.
 //! * `set_primary_username` - Set a given username as an account's primary.
 //! * `remove_dangling_username` - Remove a username that maps to an account without an identity.
+//! * `verify_field` - Verify control of the value of an identity field by providing a proof.
 //!
 //! #### For General Users with Sub-Identities
 //! * `set_subs` - Set the sub-accounts of an identity.
@@ -88,6 +98,9 @@
 //! * `add_username_authority` - Add an account with the ability to issue usernames.
 //! * `remove_username_authority` - Remove an account with the ability to issue usernames.
 //!
+//! #### For Verification Oracles
+//! * `attest_field` - Attest that an account controls the value of an identity field.
+//!
 //! [`Call`]: ./enum.Call.html
 //! [`Config`]: ./trait.Config.html
 
@@ -101,7 +114,9 @@ mod tests;
 mod types;
 pub mod weights;
 
-use crate::types::{AuthorityPropertiesOf, Suffix, Username};
+use crate::types::{
+	AuthorityPropertiesOf, FieldProofOf, FieldVerificationOf, FieldsIdentifierOf, Suffix, Username,
+};
 use codec::Encode;
 use frame_support::{
 	ensure,
@@ -109,13 +124,15 @@ use frame_support::{
 	traits::{BalanceStatus, Currency, Get, OnUnbalanced, ReservableCurrency, StorageVersion},
 	BoundedVec,
 };
+use frame_system::pallet_prelude::BlockNumberFor;
 pub use pallet::*;
 use sp_runtime::traits::{
 	AppendZerosInput, Hash, IdentifyAccount, Saturating, StaticLookup, Verify, Zero,
 };
 use sp_std::prelude::*;
 pub use types::{
-	Data, IdentityInformationProvider, Judgement, RegistrarIndex, RegistrarInfo, Registration,
+	Data, FieldVerification, FieldVerifier, IdentityInformationProvider, Judgement, RegistrarIndex,
+	RegistrarInfo, Registration, SignedChallenge, VerifiedFieldsProvider,
 };
 pub use weights::WeightInfo;
 
@@ -126,12 +143,35 @@ type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
 >>::NegativeImbalance;
 type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
 
+/// The context prepended to challenges which prove control of identity field values.
+const VERIFICATION_CONTEXT: &[u8] = b"pallet-identity:verify-field";
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	#[cfg(feature = "runtime-benchmarks")]
+	pub trait BenchmarkHelper<IdentityInformation: IdentityInformationProvider, Proof> {
+		/// Return identity information with a verifiable field set, the identifier of that field
+		/// and a proof of control of its value over the challenge built by `challenge`, or `None`
+		/// if no such proof can be created.
+		fn verifiable_identity(
+			challenge: impl FnOnce(&IdentityInformation::FieldsIdentifier, &[u8]) -> Vec<u8>,
+		) -> Option<(IdentityInformation, IdentityInformation::FieldsIdentifier, Proof)>;
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	impl<IdentityInformation: IdentityInformationProvider, Proof>
+		BenchmarkHelper<IdentityInformation, Proof> for ()
+	{
+		fn verifiable_identity(
+			_: impl FnOnce(&IdentityInformation::FieldsIdentifier, &[u8]) -> Vec<u8>,
+		) -> Option<(IdentityInformation, IdentityInformation::FieldsIdentifier, Proof)> {
+			None
+		}
+	}
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
@@ -198,8 +238,27 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxUsernameLength: Get<u32>;
 
+		/// Means of checking proofs that an account controls the value of an identity field.
+		type FieldVerifier: FieldVerifier<Self::AccountId>;
+
+		/// The origin which may attest that an account controls the value of an identity field,
+		/// e.g. after checking it off-chain.
+		type VerificationOracleOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The number of blocks for which the verification of an identity field holds.
+		#[pallet::constant]
+		type VerificationPeriod: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of verified fields that an identity may have.
+		#[pallet::constant]
+		type MaxVerifiedFields: Get<u32>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
+
+		/// Helper to create verifiable identities in benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::IdentityInformation, FieldProofOf<Self>>;
 	}
 
 	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
@@ -297,6 +356,18 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The fields of the identity of an account which were verified on-chain.
+	///
+	/// TWOX-NOTE: OK ― `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type VerifiedFields<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<(FieldsIdentifierOf<T>, FieldVerificationOf<T>), T::MaxVerifiedFields>,
+		ValueQuery,
+	>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Too many subs-accounts.
@@ -351,6 +422,16 @@ pub mod pallet {
 		NoUsername,
 		/// The username cannot be forcefully removed because it can still be accepted.
 		NotExpired,
+		/// The field is not set or cannot be verified.
+		FieldNotVerifiable,
+		/// The proof of control of the field value is not valid.
+		InvalidProof,
+		/// The deadline of the verification challenge has passed.
+		ChallengeExpired,
+		/// The field value is not the one which was attested.
+		FieldValueMismatch,
+		/// The identity has too many verified fields.
+		TooManyVerifiedFields,
 	}
 
 	#[pallet::event]
@@ -392,6 +473,12 @@ pub mod pallet {
 		/// A dangling username (as in, a username corresponding to an account that has removed its
 		/// identity) has been removed.
 		DanglingUsernameRemoved { who: T::AccountId, username: Username<T> },
+		/// A field of the identity of `who` was verified until `expires`.
+		FieldVerified {
+			who: T::AccountId,
+			field: FieldsIdentifierOf<T>,
+			expires: BlockNumberFor<T>,
+		},
 	}
 
 	#[pallet::call]
@@ -576,6 +663,7 @@ pub mod pallet {
 			if let Some(username) = maybe_username {
 				AccountOfUsername::<T>::remove(username);
 			}
+			VerifiedFields::<T>::remove(&sender);
 
 			let err_amount = T::Currency::unreserve(&sender, deposit);
 			debug_assert!(err_amount.is_zero());
@@ -888,6 +976,7 @@ pub mod pallet {
 			if let Some(username) = maybe_username {
 				AccountOfUsername::<T>::remove(username);
 			}
+			VerifiedFields::<T>::remove(&target);
 			// Slash their deposit from them.
 			T::Slashed::on_unbalanced(T::Currency::slash_reserved(&target, deposit).0);
 
@@ -1192,6 +1281,65 @@ pub mod pallet {
 			Self::deposit_event(Event::DanglingUsernameRemoved { who: who.clone(), username });
 			Ok(Pays::No.into())
 		}
+
+		/// Verify that the sender controls the value of one of its identity fields.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity with `field` set.
+		///
+		/// - `field`: The single field to verify.
+		/// - `proof`: The proof of control of the field value over the challenge given by
+		///   `verification_challenge`, as checked by `T::FieldVerifier`.
+		/// - `deadline`: The last block at which the proof is accepted. It is part of the
+		///   challenge.
+		///
+		/// Emits `FieldVerified` if successful.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::verify_field())]
+		pub fn verify_field(
+			origin: OriginFor<T>,
+			field: FieldsIdentifierOf<T>,
+			proof: FieldProofOf<T>,
+			deadline: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= deadline,
+				Error::<T>::ChallengeExpired
+			);
+			let value = Self::field_value(&who, &field).ok_or(Error::<T>::FieldNotVerifiable)?;
+			let challenge = Self::verification_challenge(&who, &field, &value, deadline);
+			ensure!(
+				T::FieldVerifier::verify(&who, &value, &challenge, &proof),
+				Error::<T>::InvalidProof
+			);
+			Self::record_verification(&who, field, &value)
+		}
+
+		/// Attest that an account controls the value of one of its identity fields.
+		///
+		/// The dispatch origin for this call must be `T::VerificationOracleOrigin`.
+		///
+		/// - `target`: The account whose identity field is attested.
+		/// - `field`: The single field to attest.
+		/// - `value_hash`: The hash of the field value which was checked. The attestation fails if
+		///   the field no longer has this value.
+		///
+		/// Emits `FieldVerified` if successful.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::attest_field())]
+		pub fn attest_field(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			field: FieldsIdentifierOf<T>,
+			value_hash: T::Hash,
+		) -> DispatchResult {
+			T::VerificationOracleOrigin::ensure_origin(origin)?;
+			let target = T::Lookup::lookup(target)?;
+			let value = Self::field_value(&target, &field).ok_or(Error::<T>::FieldNotVerifiable)?;
+			ensure!(T::Hashing::hash(&value) == value_hash, Error::<T>::FieldValueMismatch);
+			Self::record_verification(&target, field, &value)
+		}
 	}
 }
 
@@ -1234,6 +1382,60 @@ impl<T: Config> Pallet<T> {
 			.map_or(false, |(registration, _username)| (registration.info.has_identity(fields)))
 	}
 
+	/// Get the value of the single field `field` of the identity of `who`, if it is set.
+	fn field_value(who: &T::AccountId, field: &FieldsIdentifierOf<T>) -> Option<Vec<u8>> {
+		IdentityOf::<T>::get(who).and_then(|(registration, _)| registration.info.field_value(field))
+	}
+
+	/// The challenge over which `who` proves control of the `value` of its identity `field`, with
+	/// the proof being accepted up to `deadline`.
+	///
+	/// The challenge is specific to this chain, as it includes its genesis hash.
+	pub fn verification_challenge(
+		who: &T::AccountId,
+		field: &FieldsIdentifierOf<T>,
+		value: &[u8],
+		deadline: BlockNumberFor<T>,
+	) -> Vec<u8> {
+		let genesis_hash = frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero());
+		(VERIFICATION_CONTEXT, genesis_hash, who, field, value, deadline).encode()
+	}
+
+	/// Record that `who` controls the `value` of its identity `field`.
+	fn record_verification(
+		who: &T::AccountId,
+		field: FieldsIdentifierOf<T>,
+		value: &[u8],
+	) -> DispatchResult {
+		let now = frame_system::Pallet::<T>::block_number();
+		let expires = now.saturating_add(T::VerificationPeriod::get());
+		let verification = FieldVerification { value_hash: T::Hashing::hash(value), expires };
+		VerifiedFields::<T>::try_mutate(who, |verified| -> DispatchResult {
+			// Drop any previous verification of the field, along with expired ones.
+			verified.retain(|(f, v)| f != &field && v.expires > now);
+			verified
+				.try_push((field.clone(), verification))
+				.map_err(|_| Error::<T>::TooManyVerifiedFields)?;
+			Ok(())
+		})?;
+		Self::deposit_event(Event::FieldVerified { who: who.clone(), field, expires });
+		Ok(())
+	}
+
+	/// Check if the single field `field` of the identity of `who` was verified, with the
+	/// verification still holding for its current value.
+	pub fn is_field_verified(who: &T::AccountId, field: &FieldsIdentifierOf<T>) -> bool {
+		let now = frame_system::Pallet::<T>::block_number();
+		VerifiedFields::<T>::get(who)
+			.into_iter()
+			.find(|(f, _)| f == field)
+			.filter(|(_, verification)| verification.expires > now)
+			.map_or(false, |(_, verification)| {
+				Self::field_value(who, field)
+					.map_or(false, |value| T::Hashing::hash(&value) == verification.value_hash)
+			})
+	}
+
 	/// Calculate the deposit required for an identity.
 	fn calculate_identity_deposit(info: &T::IdentityInformation) -> BalanceOf<T> {
 		let bytes = info.encoded_size() as u32;
@@ -1347,6 +1549,7 @@ impl<T: Config> Pallet<T> {
 		// `take` any storage items keyed by `target`
 		// identity
 		let (id, _maybe_username) = <IdentityOf<T>>::take(&who).ok_or(Error::<T>::NoIdentity)?;
+		<VerifiedFields<T>>::remove(&who);
 		let registrars = id.judgements.len() as u32;
 		let encoded_byte_size = id.info.encoded_size() as u32;
 
@@ -1455,3 +1658,9 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 }
+
+impl<T: Config> VerifiedFieldsProvider<T::AccountId, FieldsIdentifierOf<T>> for Pallet<T> {
+	fn is_field_verified(who: &T::AccountId, field: &FieldsIdentifierOf<T>) -> bool {
+		Self::is_field_verified(who, field)
+	}
+}
@@ -30,7 +30,7 @@ use frame_support::{
 	BoundedVec,
 };
 use frame_system::EnsureRoot;
-use sp_core::H256;
+use sp_core::{sr25519, H256};
 use sp_io::crypto::{sr25519_generate, sr25519_sign};
 use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
 use sp_runtime::{
@@ -119,7 +119,29 @@ impl pallet_identity::Config for Test {
 	type PendingUsernameExpiration = ConstU64<100>;
 	type MaxSuffixLength = ConstU32<7>;
 	type MaxUsernameLength = ConstU32<32>;
+	type FieldVerifier = SignedChallenge<MultiSignature>;
+	type VerificationOracleOrigin = EnsureRoot<Self::AccountId>;
+	type VerificationPeriod = ConstU64<100>;
+	type MaxVerifiedFields = ConstU32<2>;
 	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = BenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct BenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_identity::BenchmarkHelper<IdentityInfo<MaxAdditionalFields>, MultiSignature>
+	for BenchmarkHelper
+{
+	fn verifiable_identity(
+		challenge: impl FnOnce(&u64, &[u8]) -> Vec<u8>,
+	) -> Option<(IdentityInfo<MaxAdditionalFields>, u64, MultiSignature)> {
+		let (info, value, public) = other_chain_identity();
+		let field = IdentityField::Legal as u64;
+		let signature = sr25519_sign(0.into(), &public, &challenge(&field, &value))?;
+		Some((info, field, MultiSignature::Sr25519(signature)))
+	}
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -222,6 +244,20 @@ fn infoof_twenty() -> IdentityInfo<MaxAdditionalFields> {
 	}
 }
 
+// Identity information whose legal field holds an account of another chain, along with the
+// encoded account and its public key.
+fn other_chain_identity() -> (IdentityInfo<MaxAdditionalFields>, Vec<u8>, sr25519::Public) {
+	let public = sr25519_generate(0.into(), None);
+	let other_account: AccountIdOf<Test> = MultiSigner::Sr25519(public).into_account().into();
+	let value = other_account.encode();
+	let info = IdentityInfo {
+		display: Data::Raw(b"ten".to_vec().try_into().unwrap()),
+		legal: Data::Raw(value.clone().try_into().unwrap()),
+		..Default::default()
+	};
+	(info, value, public)
+}
+
 fn id_deposit(id: &IdentityInfo<MaxAdditionalFields>) -> u64 {
 	let base_deposit: u64 = <<Test as Config>::BasicDeposit as Get<u64>>::get();
 	let byte_deposit: u64 = <<Test as Config>::ByteDeposit as Get<u64>>::get() *
@@ -1677,3 +1713,150 @@ fn removing_dangling_usernames_should_work() {
 		assert!(AccountOfUsername::<Test>::get::<&Username<Test>>(&username_two_to_sign).is_none());
 	});
 }
+
+#[test]
+fn verify_field_works() {
+	new_test_ext().execute_with(|| {
+		let ten = account(10);
+		let legal = IdentityField::Legal as u64;
+		let (info, value, public) = other_chain_identity();
+		let sign = |field: u64, deadline: u64| {
+			let challenge = Identity::verification_challenge(&ten, &field, &value, deadline);
+			MultiSignature::Sr25519(sr25519_sign(0.into(), &public, &challenge).unwrap())
+		};
+
+		// Needs an identity with the field set.
+		assert_noop!(
+			Identity::verify_field(RuntimeOrigin::signed(ten.clone()), legal, sign(legal, 10), 10),
+			Error::<Test>::FieldNotVerifiable
+		);
+		assert_ok!(Identity::set_identity(
+			RuntimeOrigin::signed(ten.clone()),
+			Box::new(info.clone())
+		));
+		let web = IdentityField::Web as u64;
+		assert_noop!(
+			Identity::verify_field(RuntimeOrigin::signed(ten.clone()), web, sign(web, 10), 10),
+			Error::<Test>::FieldNotVerifiable
+		);
+		// Only single fields can be verified.
+		let fields = legal | IdentityField::Display as u64;
+		assert_noop!(
+			Identity::verify_field(
+				RuntimeOrigin::signed(ten.clone()),
+				fields,
+				sign(fields, 10),
+				10
+			),
+			Error::<Test>::FieldNotVerifiable
+		);
+		// The deadline is part of the challenge.
+		assert_noop!(
+			Identity::verify_field(RuntimeOrigin::signed(ten.clone()), legal, sign(legal, 10), 11),
+			Error::<Test>::InvalidProof
+		);
+		// Someone else cannot reuse the proof.
+		assert_ok!(Identity::set_identity(
+			RuntimeOrigin::signed(account(20)),
+			Box::new(info.clone())
+		));
+		assert_noop!(
+			Identity::verify_field(RuntimeOrigin::signed(account(20)), legal, sign(legal, 10), 10),
+			Error::<Test>::InvalidProof
+		);
+
+		assert!(!Identity::is_field_verified(&ten, &legal));
+		assert_ok!(Identity::verify_field(
+			RuntimeOrigin::signed(ten.clone()),
+			legal,
+			sign(legal, 10),
+			10
+		));
+		System::assert_last_event(
+			Event::FieldVerified { who: ten.clone(), field: legal, expires: 101 }.into(),
+		);
+		assert!(Identity::is_field_verified(&ten, &legal));
+		assert!(!Identity::is_field_verified(&ten, &(IdentityField::Display as u64)));
+
+		// The verification only holds for as long as the field keeps its value.
+		assert_ok!(Identity::set_identity(
+			RuntimeOrigin::signed(ten.clone()),
+			Box::new(infoof_ten())
+		));
+		assert!(!Identity::is_field_verified(&ten, &legal));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(ten.clone()), Box::new(info)));
+		assert!(<Identity as VerifiedFieldsProvider<_, _>>::is_field_verified(&ten, &legal));
+
+		// It expires.
+		run_to_block(101);
+		assert!(!Identity::is_field_verified(&ten, &legal));
+		assert_noop!(
+			Identity::verify_field(RuntimeOrigin::signed(ten.clone()), legal, sign(legal, 10), 10),
+			Error::<Test>::ChallengeExpired
+		);
+		assert_ok!(Identity::verify_field(
+			RuntimeOrigin::signed(ten.clone()),
+			legal,
+			sign(legal, 101),
+			101
+		));
+		assert!(Identity::is_field_verified(&ten, &legal));
+		assert_eq!(VerifiedFields::<Test>::get(&ten).len(), 1);
+	});
+}
+
+#[test]
+fn attest_field_works() {
+	new_test_ext().execute_with(|| {
+		let ten = account(10);
+		let display = IdentityField::Display as u64;
+		let value_hash = BlakeTwo256::hash(b"ten");
+		assert_ok!(Identity::set_identity(
+			RuntimeOrigin::signed(ten.clone()),
+			Box::new(infoof_ten())
+		));
+
+		assert_noop!(
+			Identity::attest_field(
+				RuntimeOrigin::signed(ten.clone()),
+				ten.clone(),
+				display,
+				value_hash
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			Identity::attest_field(
+				RuntimeOrigin::root(),
+				ten.clone(),
+				display,
+				BlakeTwo256::hash(b"twenty")
+			),
+			Error::<Test>::FieldValueMismatch
+		);
+		assert_noop!(
+			Identity::attest_field(
+				RuntimeOrigin::root(),
+				ten.clone(),
+				IdentityField::Web as u64,
+				value_hash
+			),
+			Error::<Test>::FieldNotVerifiable
+		);
+
+		assert_ok!(Identity::attest_field(RuntimeOrigin::root(), ten.clone(), display, value_hash));
+		assert!(Identity::is_field_verified(&ten, &display));
+		// Attesting again replaces the previous verification.
+		run_to_block(10);
+		assert_ok!(Identity::attest_field(RuntimeOrigin::root(), ten.clone(), display, value_hash));
+		assert_eq!(
+			VerifiedFields::<Test>::get(&ten).into_inner(),
+			vec![(display, FieldVerification { value_hash, expires: 110 })]
+		);
+
+		// Verifications are removed along with the identity.
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(ten.clone())));
+		assert!(VerifiedFields::<Test>::get(&ten).is_empty());
+		assert!(!Identity::is_field_verified(&ten, &display));
+	});
+}
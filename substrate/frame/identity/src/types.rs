@@ -16,10 +16,10 @@
 // limitations under the License.
 
 use super::*;
-use codec::{Decode, Encode, MaxEncodedLen};
+use codec::{Decode, DecodeAll, Encode, MaxEncodedLen};
 use frame_support::{
 	traits::{ConstU32, Get},
-	BoundedVec, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+	BoundedVec, CloneNoBound, Parameter, PartialEqNoBound, RuntimeDebugNoBound,
 };
 use scale_info::{
 	build::{Fields, Variants},
@@ -29,7 +29,7 @@ use sp_runtime::{
 	traits::{Member, Zero},
 	RuntimeDebug,
 };
-use sp_std::{fmt::Debug, iter::once, ops::Add, prelude::*};
+use sp_std::{fmt::Debug, iter::once, marker::PhantomData, ops::Add, prelude::*};
 
 /// An identifier for a single name registrar/identity verification service.
 pub type RegistrarIndex = u32;
@@ -62,6 +62,19 @@ impl Data {
 	pub fn is_none(&self) -> bool {
 		self == &Data::None
 	}
+
+	/// The bytes held by this data: the data itself if stored directly, otherwise its hash.
+	/// Returns `None` if there is no data.
+	pub fn as_bytes(&self) -> Option<&[u8]> {
+		match self {
+			Data::None => None,
+			Data::Raw(ref x) => Some(&x[..]),
+			Data::BlakeTwo256(ref h) |
+			Data::Sha256(ref h) |
+			Data::Keccak256(ref h) |
+			Data::ShaThree256(ref h) => Some(&h[..]),
+		}
+	}
 }
 
 impl Decode for Data {
@@ -240,6 +253,14 @@ pub trait IdentityInformationProvider:
 	/// Check if an identity registered information for some given `fields`.
 	fn has_identity(&self, fields: Self::FieldsIdentifier) -> bool;
 
+	/// Return the value of the single field identified by `field`, or `None` if the field is not
+	/// set or `field` does not identify exactly one field.
+	///
+	/// Only fields for which a value is returned can be verified on-chain.
+	fn field_value(&self, _field: &Self::FieldsIdentifier) -> Option<Vec<u8>> {
+		None
+	}
+
 	/// Create a basic instance of the identity information.
 	#[cfg(feature = "runtime-benchmarks")]
 	fn create_identity_info() -> Self;
@@ -249,6 +270,65 @@ pub trait IdentityInformationProvider:
 	fn all_fields() -> Self::FieldsIdentifier;
 }
 
+/// A means of checking on-chain that an account controls the value of one of its identity
+/// fields, without involving a registrar.
+///
+/// The account proves control by providing a `Proof` over a challenge, which is specific to the
+/// chain, the account, the field and its value. See `Pallet::verification_challenge`.
+pub trait FieldVerifier<AccountId> {
+	/// The proof which an account submits to show it controls the value of a field.
+	type Proof: Parameter;
+
+	/// Check that `proof` shows that `who` controls the field `value`, given the `challenge`.
+	fn verify(who: &AccountId, value: &[u8], challenge: &[u8], proof: &Self::Proof) -> bool;
+}
+
+impl<AccountId> FieldVerifier<AccountId> for () {
+	type Proof = ();
+
+	fn verify(_: &AccountId, _: &[u8], _: &[u8], _: &Self::Proof) -> bool {
+		false
+	}
+}
+
+/// A [`FieldVerifier`] for fields which hold an account of some other chain.
+///
+/// The field value must be the encoded account and the proof a signature of that account over the
+/// challenge. The type of `Signature` determines the kind of accounts which can be verified.
+pub struct SignedChallenge<Signature>(PhantomData<Signature>);
+
+impl<AccountId, Signature> FieldVerifier<AccountId> for SignedChallenge<Signature>
+where
+	Signature: Verify + Parameter,
+	<Signature::Signer as IdentifyAccount>::AccountId: Decode,
+{
+	type Proof = Signature;
+
+	fn verify(_: &AccountId, value: &[u8], challenge: &[u8], proof: &Self::Proof) -> bool {
+		<<Signature::Signer as IdentifyAccount>::AccountId as DecodeAll>::decode_all(
+			&mut &value[..],
+		)
+		.map_or(false, |signer| proof.verify(challenge, &signer))
+	}
+}
+
+/// The on-chain verification of an identity field.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct FieldVerification<Hash, BlockNumber> {
+	/// The hash of the field value which was verified. The verification only holds for as long as
+	/// the field keeps this value.
+	pub value_hash: Hash,
+	/// The block from which the verification no longer holds.
+	pub expires: BlockNumber,
+}
+
+/// Exposes which fields of the identities of accounts were verified on-chain.
+pub trait VerifiedFieldsProvider<AccountId, FieldsIdentifier> {
+	/// Returns whether the single field `field` of the identity of `who` was verified, with the
+	/// verification still holding for its current value.
+	fn is_field_verified(who: &AccountId, field: &FieldsIdentifier) -> bool;
+}
+
 /// Information on an identity along with judgements from registrars.
 ///
 /// NOTE: This is stored separately primarily to facilitate the addition of extra fields in a
@@ -341,6 +421,18 @@ pub struct AuthorityProperties<Suffix> {
 /// A byte vec used to represent a username.
 pub(crate) type Username<T> = BoundedVec<u8, <T as Config>::MaxUsernameLength>;
 
+/// The identifier of identity fields for a given pallet configuration.
+pub type FieldsIdentifierOf<T> =
+	<<T as Config>::IdentityInformation as IdentityInformationProvider>::FieldsIdentifier;
+/// Field verification for a given pallet configuration.
+pub type FieldVerificationOf<T> = FieldVerification<
+	<T as frame_system::Config>::Hash,
+	frame_system::pallet_prelude::BlockNumberFor<T>,
+>;
+/// The proof of control of a field value for a given pallet configuration.
+pub type FieldProofOf<T> =
+	<<T as Config>::FieldVerifier as FieldVerifier<<T as frame_system::Config>::AccountId>>::Proof;
+
 #[cfg(test)]
 mod tests {
 	use super::*;
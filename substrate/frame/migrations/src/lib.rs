@@ -0,0 +1,748 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Multi-Block Migrations
+//!
+//! Executes a queue of [`SteppedMigration`]s over multiple blocks.
+//!
+//! ## WARNING
+//!
+//! NOT YET AUDITED. DO NOT USE IN PRODUCTION.
+//!
+//! ## Pallet API
+//!
+//! See the [`pallet`] module for more information about the interfaces this pallet exposes,
+//! including its configuration trait, dispatchables, storage items, events and errors.
+//!
+//! ## Overview
+//!
+//! Migrations that do not fit into a single block are implemented as [`SteppedMigration`]s. Each
+//! step of such a migration is limited by a [`WeightMeter`] and returns a cursor that is passed
+//! back into the next step. The pallet executes the migrations of [`Config::Migrations`] one
+//! after another, starting with the block that enacts a runtime upgrade.
+//!
+//! While migrations are ongoing, the pallet reports so through its [`MultiStepMigrator`]
+//! implementation. `frame_executive` uses it to step the migrations at the beginning of each
+//! block and to reject all extrinsics that are not inherents, since they could otherwise observe
+//! a half-migrated storage. A stuck upgrade is not reported as ongoing, such that governance can
+//! still dispatch the calls that are needed to recover from it.
+//!
+//! ### Example
+//!
+//! ```ignore
+//! impl pallet_migrations::Config for Runtime {
+//! 	type RuntimeEvent = RuntimeEvent;
+//! 	type Migrations = (MyFirstMigration, MySecondMigration);
+//! 	type CursorMaxLen = ConstU32<65_536>;
+//! 	type IdentifierMaxLen = ConstU32<256>;
+//! 	type MigrationStatusHandler = ();
+//! 	type FailedMigrationHandler = FreezeChainOnFailedMigration;
+//! 	type MaxServiceWeight = MbmServiceWeight;
+//! 	type WeightInfo = ();
+//! }
+//!
+//! pub type Executive = frame_executive::Executive<
+//! 	Runtime,
+//! 	Block,
+//! 	frame_system::ChainContext<Runtime>,
+//! 	Runtime,
+//! 	AllPalletsWithSystem,
+//! 	(),
+//! 	MultiBlockMigrations,
+//! >;
+//! ```
+//!
+//! ## Low Level / Implementation Details
+//!
+//! The progress of the migrations is tracked by the [`Cursor`] storage item. It holds the index
+//! of the current migration and its inner cursor. Every block, the pallet executes as many steps
+//! as fit into [`Config::MaxServiceWeight`], but at most one step per migration. Once a migration
+//! completes, its identifier is added to [`Historic`], such that it is skipped if it is ever
+//! executed again.
+//!
+//! ### Failure handling
+//!
+//! A migration fails if it returns an error, exceeds its
+//! [`max_steps`](SteppedMigration::max_steps) or cannot make progress with the full
+//! [`Config::MaxServiceWeight`]. The [`Config::FailedMigrationHandler`] then decides how to
+//! continue. By default the chain stays stuck, which means that no migration is executed anymore
+//! until governance intervenes through the `force_*` calls of this pallet. Extrinsics are
+//! accepted again while the chain is stuck, otherwise these calls could not be dispatched.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(rustdoc::broken_intra_doc_links)]
+
+mod benchmarking;
+mod mock;
+pub mod mock_helpers;
+mod tests;
+pub mod weights;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::ops::ControlFlow;
+use frame_support::{
+	defensive, defensive_assert,
+	migrations::*,
+	traits::Get,
+	weights::{Weight, WeightMeter},
+	BoundedVec,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, Pallet as System};
+use sp_runtime::Saturating;
+use sp_std::vec::Vec;
+
+const LOG_TARGET: &str = "runtime::migrations";
+
+/// Points to the next migration to execute.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
+pub enum MigrationCursor<Cursor, BlockNumber> {
+	/// Points to the currently active migration and its inner cursor.
+	Active(ActiveCursor<Cursor, BlockNumber>),
+
+	/// Migration got stuck and cannot proceed. This is bad.
+	///
+	/// No migration is executed until governance sets a new cursor. Extrinsics are not blocked in
+	/// this state.
+	Stuck,
+}
+
+impl<Cursor, BlockNumber> MigrationCursor<Cursor, BlockNumber> {
+	/// Try to return self as an [`ActiveCursor`].
+	pub fn as_active(&self) -> Option<&ActiveCursor<Cursor, BlockNumber>> {
+		match self {
+			MigrationCursor::Active(active) => Some(active),
+			MigrationCursor::Stuck => None,
+		}
+	}
+}
+
+impl<Cursor, BlockNumber> From<ActiveCursor<Cursor, BlockNumber>>
+	for MigrationCursor<Cursor, BlockNumber>
+{
+	fn from(active: ActiveCursor<Cursor, BlockNumber>) -> Self {
+		MigrationCursor::Active(active)
+	}
+}
+
+/// Points to the currently active migration and its inner cursor.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
+pub struct ActiveCursor<Cursor, BlockNumber> {
+	/// The index of the migration in the MBM tuple.
+	pub index: u32,
+	/// The cursor of the migration that is referenced by `index`.
+	pub inner_cursor: Option<Cursor>,
+	/// The number of steps that the migration referenced by `index` took so far.
+	///
+	/// This is checked against the [`max_steps`](SteppedMigration::max_steps) of the migration.
+	pub steps: u32,
+	/// The block number that the migration started at.
+	///
+	/// This is used to calculate how many blocks it took.
+	pub started_at: BlockNumber,
+}
+
+impl<Cursor, BlockNumber: Copy> ActiveCursor<Cursor, BlockNumber> {
+	/// Advance the overarching cursor to the next migration.
+	pub(crate) fn goto_next_migration(&mut self, current_block: BlockNumber) {
+		self.index.saturating_inc();
+		self.inner_cursor = None;
+		self.steps = 0;
+		self.started_at = current_block;
+	}
+}
+
+/// How to clear the records of historic migrations.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode, scale_info::TypeInfo)]
+pub enum HistoricCleanupSelector<Id> {
+	/// Clear exactly these entries.
+	///
+	/// This is the advised way of doing it.
+	Specific(Vec<Id>),
+
+	/// Clear all entries.
+	///
+	/// This is not advised and can lead to dangerous state corruption when migrations get
+	/// re-executed.
+	Wildcard {
+		/// How many should be cleared in this call at most.
+		limit: Option<u32>,
+		/// The cursor that was emitted from any previous `HistoricCleared`.
+		///
+		/// Does not need to be passed when clearing the first batch.
+		previous_cursor: Option<Vec<u8>>,
+	},
+}
+
+/// The default number of entries that should be cleared by a `HistoricCleanupSelector::Wildcard`.
+///
+/// The caller can explicitly specify a higher amount. Benchmarks are run with twice this value.
+const DEFAULT_HISTORIC_BATCH_CLEAR_SIZE: u32 = 128;
+
+impl<Id> HistoricCleanupSelector<Id> {
+	/// The maximal number of entries that this will remove.
+	///
+	/// Needed for weight calculation.
+	pub fn limit(&self) -> u32 {
+		match self {
+			Self::Specific(ids) => ids.len() as u32,
+			Self::Wildcard { limit, .. } => limit.unwrap_or(DEFAULT_HISTORIC_BATCH_CLEAR_SIZE),
+		}
+	}
+}
+
+/// Convenience alias for [`MigrationCursor`].
+pub type CursorOf<T> = MigrationCursor<RawCursorOf<T>, BlockNumberFor<T>>;
+
+/// Convenience alias for the raw inner cursor of a migration.
+pub type RawCursorOf<T> = BoundedVec<u8, <T as Config>::CursorMaxLen>;
+
+/// Convenience alias for the identifier of a migration.
+pub type IdentifierOf<T> = BoundedVec<u8, <T as Config>::IdentifierMaxLen>;
+
+/// Convenience alias for [`ActiveCursor`].
+pub type ActiveCursorOf<T> = ActiveCursor<RawCursorOf<T>, BlockNumberFor<T>>;
+
+/// Migrations whose outcome can be configured, such that all code paths can be benchmarked.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait MockedMigrations: SteppedMigrations {
+	/// The migration should fail after `n` steps.
+	fn set_fail_after(n: u32);
+	/// The migration should succeed after `n` steps.
+	fn set_success_after(n: u32);
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type of the runtime.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// All the multi-block migrations to run.
+		///
+		/// Should only be updated in a runtime-upgrade once all the old migrations have completed.
+		/// (Check that [`Cursor`] is `None`).
+		#[cfg(not(feature = "runtime-benchmarks"))]
+		type Migrations: SteppedMigrations;
+
+		/// Mocked migrations for benchmarking only.
+		///
+		/// Should be configured to [`crate::mock_helpers::MockedMigrations`] in benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type Migrations: MockedMigrations;
+
+		/// The maximal length of an encoded cursor.
+		///
+		/// A good default needs to be selected such that no migration will ever have a cursor
+		/// with MEL above this limit. This is statically checked in `integrity_test`.
+		#[pallet::constant]
+		type CursorMaxLen: Get<u32>;
+
+		/// The maximal length of an encoded identifier.
+		///
+		/// A good default needs to be selected such that no migration will ever have an identifier
+		/// with MEL above this limit. This is statically checked in `integrity_test`.
+		#[pallet::constant]
+		type IdentifierMaxLen: Get<u32>;
+
+		/// Notifications for status updates of a runtime upgrade.
+		///
+		/// Could be used to pause XCM etc.
+		type MigrationStatusHandler: MigrationStatusHandler;
+
+		/// Handler for failed migrations.
+		type FailedMigrationHandler: FailedMigrationHandler;
+
+		/// The maximum weight to spend each block to execute migrations.
+		type MaxServiceWeight: Get<Weight>;
+
+		/// Weight information for the calls and functions of this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The currently active migration to run and its cursor.
+	///
+	/// `None` indicates that no migration is running.
+	#[pallet::storage]
+	pub type Cursor<T: Config> = StorageValue<_, CursorOf<T>, OptionQuery>;
+
+	/// Set of all successfully executed migrations.
+	///
+	/// This is used as blacklist, to not re-execute migrations that have not been removed from the
+	/// codebase yet. Governance can regularly clear this out via `clear_historic`.
+	#[pallet::storage]
+	pub type Historic<T: Config> = StorageMap<_, Twox64Concat, IdentifierOf<T>, (), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A Runtime upgrade started.
+		///
+		/// Its end is indicated by `UpgradeCompleted` or `UpgradeFailed`.
+		UpgradeStarted {
+			/// The number of migrations that this upgrade contains.
+			///
+			/// This can be used to design a progress indicator in combination with counting the
+			/// `MigrationCompleted` and `MigrationSkipped` events.
+			migrations: u32,
+		},
+		/// The current runtime upgrade completed.
+		///
+		/// This implies that all of its migrations completed successfully as well.
+		UpgradeCompleted,
+		/// Runtime upgrade failed.
+		///
+		/// This is very bad and will require governance intervention.
+		UpgradeFailed,
+		/// A migration was skipped since it was already executed in the past.
+		MigrationSkipped {
+			/// The index of the skipped migration within the [`Config::Migrations`] list.
+			index: u32,
+		},
+		/// A migration progressed.
+		MigrationAdvanced {
+			/// The index of the migration within the [`Config::Migrations`] list.
+			index: u32,
+			/// The number of blocks that this migration took so far.
+			took: BlockNumberFor<T>,
+		},
+		/// A Migration completed.
+		MigrationCompleted {
+			/// The index of the migration within the [`Config::Migrations`] list.
+			index: u32,
+			/// The number of blocks that this migration took so far.
+			took: BlockNumberFor<T>,
+		},
+		/// A Migration failed.
+		///
+		/// This implies that the whole upgrade failed and governance intervention is required.
+		MigrationFailed {
+			/// The index of the migration within the [`Config::Migrations`] list.
+			index: u32,
+			/// The number of blocks that this migration took so far.
+			took: BlockNumberFor<T>,
+		},
+		/// The set of historical migrations has been cleared.
+		HistoricCleared {
+			/// Should be passed to `clear_historic` in a successive call.
+			next_cursor: Option<Vec<u8>>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The operation cannot complete since some MBMs are ongoing.
+		Ongoing,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			Self::onboard_new_mbms()
+		}
+
+		#[cfg(feature = "std")]
+		fn integrity_test() {
+			// The per-block service weight is sane.
+			{
+				let want = T::MaxServiceWeight::get();
+				let max = <T as frame_system::Config>::BlockWeights::get().max_block;
+
+				assert!(
+					want.all_lte(max),
+					"Service weight is larger than a block: {want:?} > {max:?}"
+				);
+			}
+
+			// Cursor MEL
+			{
+				let mel = T::Migrations::cursor_max_encoded_len();
+				let max_mel = T::CursorMaxLen::get() as usize;
+				assert!(
+					mel <= max_mel,
+					"A Cursor is not guaranteed to fit into the storage: {mel} > {max_mel}",
+				);
+			}
+
+			// Identifier MEL
+			{
+				let mel = T::Migrations::identifier_max_encoded_len();
+				let max_mel = T::IdentifierMaxLen::get() as usize;
+				assert!(
+					mel <= max_mel,
+					"An Identifier is not guaranteed to fit into the storage: {mel} > {max_mel}",
+				);
+			}
+		}
+	}
+
+	#[pallet::call(weight = T::WeightInfo)]
+	impl<T: Config> Pallet<T> {
+		/// Allows root to set a cursor to forcefully start, stop or forward the migration process.
+		///
+		/// Should normally not be needed and is only in place as emergency measure. Note that
+		/// restarting the migration process in this manner will not call the
+		/// [`MigrationStatusHandler::started`] hook or emit an `UpgradeStarted` event.
+		#[pallet::call_index(0)]
+		pub fn force_set_cursor(
+			origin: OriginFor<T>,
+			cursor: Option<CursorOf<T>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Cursor::<T>::set(cursor);
+
+			Ok(())
+		}
+
+		/// Allows root to set an active cursor to forcefully start/forward the migration process.
+		///
+		/// This is an edge-case version of [`Self::force_set_cursor`] that allows to set the
+		/// `started_at` value to the next block number. Otherwise this would not be possible,
+		/// since `force_set_cursor` takes an absolute block number. Setting `started_at` to `None`
+		/// indicates that the current block number plus one should be used. The step count of the
+		/// migration starts from zero again.
+		#[pallet::call_index(1)]
+		pub fn force_set_active_cursor(
+			origin: OriginFor<T>,
+			index: u32,
+			inner_cursor: Option<RawCursorOf<T>>,
+			started_at: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let started_at = started_at.unwrap_or(
+				System::<T>::block_number().saturating_add(sp_runtime::traits::One::one()),
+			);
+			Cursor::<T>::put(MigrationCursor::Active(ActiveCursor {
+				index,
+				inner_cursor,
+				steps: 0,
+				started_at,
+			}));
+
+			Ok(())
+		}
+
+		/// Forces the onboarding of the migrations.
+		///
+		/// This process happens automatically on a runtime upgrade. It is in place as an emergency
+		/// measurement. The cursor needs to be `None` for this to succeed.
+		#[pallet::call_index(2)]
+		pub fn force_onboard_mbms(origin: OriginFor<T>) -> DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(!Cursor::<T>::exists(), Error::<T>::Ongoing);
+			Self::onboard_new_mbms();
+
+			Ok(())
+		}
+
+		/// Clears the `Historic` set.
+		///
+		/// `map_cursor` must be set to the last value that was returned by the
+		/// `HistoricCleared` event. The first time `None` can be used. `limit` must be chosen in a
+		/// way that will result in a sensible weight.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::clear_historic(selector.limit()))]
+		pub fn clear_historic(
+			origin: OriginFor<T>,
+			selector: HistoricCleanupSelector<IdentifierOf<T>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			match &selector {
+				HistoricCleanupSelector::Specific(ids) => {
+					for id in ids {
+						Historic::<T>::remove(id);
+					}
+					Self::deposit_event(Event::HistoricCleared { next_cursor: None });
+				},
+				HistoricCleanupSelector::Wildcard { previous_cursor, .. } => {
+					let next = Historic::<T>::clear(selector.limit(), previous_cursor.as_deref());
+					Self::deposit_event(Event::HistoricCleared { next_cursor: next.maybe_cursor });
+				},
+			}
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Onboard all new Multi-Block-Migrations and start the process of executing them.
+	///
+	/// Should only be called once all previous migrations completed.
+	fn onboard_new_mbms() -> Weight {
+		if let Some(cursor) = Cursor::<T>::get() {
+			log::error!(target: LOG_TARGET, "Ongoing migrations interrupted - chain stuck");
+
+			let maybe_index = cursor.as_active().map(|c| c.index);
+			Self::upgrade_failed(maybe_index);
+			return T::WeightInfo::onboard_new_mbms()
+		}
+
+		let migrations = T::Migrations::len();
+		log::debug!(target: LOG_TARGET, "Onboarding {migrations} new MBM migrations");
+
+		if migrations > 0 {
+			// Set the cursor to the first migration:
+			Cursor::<T>::set(Some(
+				ActiveCursor {
+					index: 0,
+					inner_cursor: None,
+					steps: 0,
+					started_at: System::<T>::block_number(),
+				}
+				.into(),
+			));
+			Self::deposit_event(Event::UpgradeStarted { migrations });
+			T::MigrationStatusHandler::started();
+		}
+
+		T::WeightInfo::onboard_new_mbms()
+	}
+
+	/// Tries to make progress on the Multi-Block-Migrations process.
+	fn progress_mbms(n: BlockNumberFor<T>) -> Weight {
+		let mut meter = WeightMeter::with_limit(T::MaxServiceWeight::get());
+		meter.consume(T::WeightInfo::progress_mbms_none());
+
+		let mut cursor = match Cursor::<T>::get() {
+			None => {
+				log::trace!(target: LOG_TARGET, "[Block {n:?}] Waiting for cursor to become `Some`.");
+				return meter.consumed()
+			},
+			Some(MigrationCursor::Active(cursor)) => {
+				log::debug!(target: LOG_TARGET, "Progressing MBM #{}", cursor.index);
+				cursor
+			},
+			Some(MigrationCursor::Stuck) => {
+				log::error!(target: LOG_TARGET, "Migration stuck. Governance intervention required.");
+				return meter.consumed()
+			},
+		};
+		debug_assert!(Self::ongoing());
+
+		// The limit here is a defensive measure to prevent an infinite loop. It expresses that we
+		// allow no more than 8 MBMs to finish in a single block. This should be harmless, since we
+		// generally expect *Multi*-Block-Migrations to take *multiple* blocks.
+		for i in 0..8 {
+			match Self::exec_migration(cursor, i == 0, &mut meter) {
+				None => return meter.consumed(),
+				Some(ControlFlow::Continue(next_cursor)) => {
+					cursor = next_cursor;
+				},
+				Some(ControlFlow::Break(last_cursor)) => {
+					cursor = last_cursor;
+					break
+				},
+			}
+		}
+
+		Cursor::<T>::set(Some(cursor.into()));
+
+		meter.consumed()
+	}
+
+	/// Try to make progress on the current migration.
+	///
+	/// Returns whether processing should continue or break for this block. The return value means:
+	/// - `None`: The migration process is completely finished.
+	/// - `ControlFlow::Break`: Continue in the *next* block with the given cursor.
+	/// - `ControlFlow::Continue`: Continue in the *current* block with the given cursor.
+	fn exec_migration(
+		mut cursor: ActiveCursorOf<T>,
+		is_first: bool,
+		meter: &mut WeightMeter,
+	) -> Option<ControlFlow<ActiveCursorOf<T>, ActiveCursorOf<T>>> {
+		// The differences between the single branches' weights is not that big. And since we do
+		// only one step per block, we can just use the maximum instead of more precise accounting.
+		if meter.try_consume(Self::exec_migration_max_weight()).is_err() {
+			defensive_assert!(!is_first, "There should be at least weight to do one migration.");
+			return Some(ControlFlow::Break(cursor))
+		}
+
+		let Some(id) = T::Migrations::nth_id(cursor.index) else {
+			// No more migrations in the tuple - we are done.
+			defensive_assert!(cursor.index == T::Migrations::len(), "Inconsistent MBMs tuple");
+			Self::deposit_event(Event::UpgradeCompleted);
+			Cursor::<T>::kill();
+			T::MigrationStatusHandler::completed();
+			return None
+		};
+
+		let Ok(bounded_id): Result<IdentifierOf<T>, _> = id.try_into() else {
+			defensive!("integrity_test ensures that all identifiers' MEL bounds fit into IdentifierMaxLen; qed.");
+			Self::upgrade_failed(Some(cursor.index));
+			return None
+		};
+
+		if Historic::<T>::contains_key(&bounded_id) {
+			Self::deposit_event(Event::MigrationSkipped { index: cursor.index });
+			cursor.goto_next_migration(System::<T>::block_number());
+			return Some(ControlFlow::Continue(cursor))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		if cursor.inner_cursor.is_none() {
+			try_runtime::pre_upgrade::<T>(cursor.index);
+		}
+
+		let max_steps = T::Migrations::nth_max_steps(cursor.index);
+		let next_cursor = T::Migrations::nth_transactional_step(
+			cursor.index,
+			cursor.inner_cursor.clone().map(|c| c.into_inner()),
+			meter,
+		);
+		let Some((max_steps, next_cursor)) = max_steps.zip(next_cursor) else {
+			defensive!("integrity_test ensures that the tuple is valid; qed");
+			Self::upgrade_failed(Some(cursor.index));
+			return None
+		};
+
+		let took = System::<T>::block_number().saturating_sub(cursor.started_at);
+		match next_cursor {
+			Ok(Some(next_cursor)) => {
+				let Ok(bound_next_cursor) = next_cursor.try_into() else {
+					defensive!("The integrity check ensures that all cursors' MEL bound fits into CursorMaxLen; qed");
+					Self::upgrade_failed(Some(cursor.index));
+					return None
+				};
+
+				Self::deposit_event(Event::MigrationAdvanced { index: cursor.index, took });
+				cursor.inner_cursor = Some(bound_next_cursor);
+				cursor.steps.saturating_inc();
+
+				if max_steps.map_or(false, |max| cursor.steps > max) {
+					Self::deposit_event(Event::MigrationFailed { index: cursor.index, took });
+					Self::upgrade_failed(Some(cursor.index));
+					None
+				} else {
+					// A migration cannot progress more than one step per block, we therefore break.
+					Some(ControlFlow::Break(cursor))
+				}
+			},
+			Ok(None) => {
+				// A migration is done when it returns cursor `None`.
+				#[cfg(feature = "try-runtime")]
+				try_runtime::post_upgrade::<T>(cursor.index);
+
+				Self::deposit_event(Event::MigrationCompleted { index: cursor.index, took });
+				Historic::<T>::insert(&bounded_id, ());
+				cursor.goto_next_migration(System::<T>::block_number());
+				Some(ControlFlow::Continue(cursor))
+			},
+			Err(SteppedMigrationError::InsufficientWeight { required }) => {
+				if is_first || required.any_gt(meter.limit()) {
+					Self::deposit_event(Event::MigrationFailed { index: cursor.index, took });
+					Self::upgrade_failed(Some(cursor.index));
+					None
+				} else {
+					// Hope that it gets better in the next block.
+					Some(ControlFlow::Break(cursor))
+				}
+			},
+			Err(SteppedMigrationError::InvalidCursor | SteppedMigrationError::Failed) => {
+				Self::deposit_event(Event::MigrationFailed { index: cursor.index, took });
+				Self::upgrade_failed(Some(cursor.index));
+				None
+			},
+		}
+	}
+
+	/// Fail the current runtime upgrade, caused by `migration`.
+	fn upgrade_failed(migration: Option<u32>) {
+		use FailedMigrationHandling::*;
+		Self::deposit_event(Event::UpgradeFailed);
+
+		match T::FailedMigrationHandler::failed(migration) {
+			KeepStuck => Cursor::<T>::set(Some(MigrationCursor::Stuck)),
+			ForceUnstuck => Cursor::<T>::kill(),
+			Ignore => {},
+		}
+	}
+
+	/// The maximal weight of a single call to [`Self::exec_migration`], excluding the weight of
+	/// the migration step itself.
+	fn exec_migration_max_weight() -> Weight {
+		T::WeightInfo::exec_migration_complete()
+			.max(T::WeightInfo::exec_migration_completed())
+			.max(T::WeightInfo::exec_migration_skipped_historic())
+			.max(T::WeightInfo::exec_migration_advance())
+			.max(T::WeightInfo::exec_migration_fail())
+	}
+}
+
+impl<T: Config> MultiStepMigrator for Pallet<T> {
+	fn ongoing() -> bool {
+		// A stuck upgrade does not make progress anymore, blocking extrinsics would only prevent
+		// governance from fixing it.
+		matches!(Cursor::<T>::get(), Some(MigrationCursor::Active(_)))
+	}
+
+	fn step() -> Weight {
+		Self::progress_mbms(System::<T>::block_number())
+	}
+}
+
+/// Runs the `pre_upgrade` and `post_upgrade` checks of the stepped migrations.
+///
+/// The data returned by `pre_upgrade` is kept in a temporary storage item until the migration
+/// completed.
+#[cfg(feature = "try-runtime")]
+mod try_runtime {
+	use super::*;
+	use frame_support::storage::unhashed;
+
+	/// Storage key of the `pre_upgrade` data of the migration with the given index.
+	fn pre_upgrade_key(index: u32) -> Vec<u8> {
+		(b":pallet_migrations:pre_upgrade:", index).encode()
+	}
+
+	pub(crate) fn pre_upgrade<T: Config>(index: u32) {
+		match T::Migrations::nth_pre_upgrade(index) {
+			Some(Ok(state)) => unhashed::put_raw(&pre_upgrade_key(index), &state),
+			Some(Err(err)) => {
+				log::error!(target: LOG_TARGET, "Pre-upgrade of MBM #{index} failed: {err:?}");
+				panic!("Pre-upgrade of MBM #{index} failed");
+			},
+			None => {},
+		}
+	}
+
+	pub(crate) fn post_upgrade<T: Config>(index: u32) {
+		let key = pre_upgrade_key(index);
+		let state = unhashed::get_raw(&key).unwrap_or_default();
+		unhashed::kill(&key);
+
+		if let Some(Err(err)) = T::Migrations::nth_post_upgrade(index, state) {
+			log::error!(target: LOG_TARGET, "Post-upgrade of MBM #{index} failed: {err:?}");
+			panic!("Post-upgrade of MBM #{index} failed");
+		}
+	}
+}
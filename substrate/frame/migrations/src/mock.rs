@@ -0,0 +1,178 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mocked runtime for testing the migrations pallet.
+
+#![cfg(test)]
+
+use crate::{mock_helpers::*, Event, Historic};
+
+use frame_support::{
+	derive_impl,
+	migrations::*,
+	parameter_types,
+	traits::{OnFinalize, OnInitialize, OnRuntimeUpgrade},
+	weights::Weight,
+};
+use frame_system::EventRecord;
+use sp_core::ConstU32;
+use sp_runtime::testing::{Block as TestBlock, TestXt};
+
+/// The signed extensions of the test extrinsics.
+pub type SignedExtra = frame_system::CheckNonZeroSender<Test>;
+/// An extrinsic of the mock runtime.
+pub type Extrinsic = TestXt<RuntimeCall, SignedExtra>;
+type Block = TestBlock<Extrinsic>;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Migrations: crate,
+		Sudo: pallet_sudo,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+}
+
+#[derive_impl(pallet_sudo::config_preludes::TestDefaultConfig as pallet_sudo::DefaultConfig)]
+impl pallet_sudo::Config for Test {}
+
+/// Executes blocks of the mock runtime and steps its migrations.
+pub type Executive = frame_executive::Executive<
+	Test,
+	Block,
+	frame_system::ChainContext<Test>,
+	Test,
+	AllPalletsWithSystem,
+	(),
+	Migrations,
+>;
+
+parameter_types! {
+	pub const MaxServiceWeight: Weight = Weight::from_parts(1_000_000_000_000, 1_000_000);
+}
+
+impl crate::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Migrations = MockedMigrations;
+	type CursorMaxLen = ConstU32<65_536>;
+	type IdentifierMaxLen = ConstU32<256>;
+	type MigrationStatusHandler = MockedMigrationStatusHandler;
+	type FailedMigrationHandler = MockedFailedMigrationHandler;
+	type MaxServiceWeight = MaxServiceWeight;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	/// The number of started upgrades.
+	pub static UpgradesStarted: u32 = 0;
+	/// The number of completed upgrades.
+	pub static UpgradesCompleted: u32 = 0;
+	/// The migrations that failed.
+	pub static UpgradesFailed: Vec<Option<u32>> = vec![];
+	/// Return value of [`MockedFailedMigrationHandler::failed`].
+	pub static FailedUpgradeResponse: FailedMigrationHandling = FailedMigrationHandling::KeepStuck;
+}
+
+/// Records all started and completed upgrades in `UpgradesStarted` and `UpgradesCompleted`.
+pub struct MockedMigrationStatusHandler;
+impl MigrationStatusHandler for MockedMigrationStatusHandler {
+	fn started() {
+		log::info!("MigrationStatusHandler started");
+		UpgradesStarted::mutate(|v| *v += 1);
+	}
+
+	fn completed() {
+		log::info!("MigrationStatusHandler completed");
+		UpgradesCompleted::mutate(|v| *v += 1);
+	}
+}
+
+/// Records all failed upgrades in `UpgradesFailed`.
+pub struct MockedFailedMigrationHandler;
+impl FailedMigrationHandler for MockedFailedMigrationHandler {
+	fn failed(migration: Option<u32>) -> FailedMigrationHandling {
+		UpgradesFailed::mutate(|v| v.push(migration));
+		let res = FailedUpgradeResponse::get();
+		log::error!("FailedMigrationHandler failed at: {migration:?}, handling as {res:?}");
+		res
+	}
+}
+
+/// Returns the number of `(started, completed, failed)` upgrades and resets their numbers.
+pub fn upgrades_started_completed_failed() -> (u32, u32, u32) {
+	(UpgradesStarted::take(), UpgradesCompleted::take(), UpgradesFailed::take().len() as u32)
+}
+
+/// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = sp_io::TestExternalities::new(Default::default());
+	// Events are not deposited in the genesis block.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Run this closure in test externalities.
+pub fn test_closure<R>(f: impl FnOnce() -> R) -> R {
+	let mut ext = new_test_ext();
+	ext.execute_with(f)
+}
+
+/// Run to the given block.
+///
+/// Simulates what `frame_executive` does: the migrations are stepped after `on_initialize`.
+pub fn run_to_block(n: u32) {
+	while System::block_number() < n as u64 {
+		log::debug!("Block {}", System::block_number());
+		System::set_block_number(System::block_number() + 1);
+		System::on_initialize(System::block_number());
+		Migrations::on_initialize(System::block_number());
+		if <Migrations as MultiStepMigrator>::ongoing() {
+			<Migrations as MultiStepMigrator>::step();
+		}
+		Migrations::on_finalize(System::block_number());
+		System::on_finalize(System::block_number());
+	}
+}
+
+/// Simulate a runtime upgrade that onboards the configured migrations.
+pub fn upgrade_runtime() {
+	Migrations::on_runtime_upgrade();
+}
+
+/// Returns the identifiers of all historic migrations.
+pub fn historic() -> Vec<MockedIdentifier> {
+	let mut historic = Historic::<Test>::iter_keys().collect::<Vec<_>>();
+	historic.sort();
+	historic
+}
+
+/// Asserts that the events of this pallet are exactly `events`, and clears them.
+pub fn assert_events(events: Vec<Event<Test>>) {
+	let got = System::events()
+		.into_iter()
+		.filter_map(|EventRecord { event, .. }| match event {
+			RuntimeEvent::Migrations(event) => Some(event),
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(got, events);
+	System::reset_events();
+}
@@ -0,0 +1,349 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(test)]
+
+use crate::{
+	mock::{Test as T, *},
+	mock_helpers::{MockedMigrationKind::*, *},
+	ActiveCursor, Cursor, Error, Event, HistoricCleanupSelector, MigrationCursor,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	migrations::{FailedMigrationHandling, MultiStepMigrator},
+	traits::{BuildGenesisConfig, OnRuntimeUpgrade},
+	weights::Weight,
+};
+use frame_system::RawOrigin;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::Header as _, Digest, DispatchError};
+
+#[test]
+fn simple_works() {
+	use Event::*;
+	test_closure(|| {
+		// Add three migrations, each taking one more step than the previous.
+		set_mocked_migrations(vec![(SucceedAfter, 0), (SucceedAfter, 1), (SucceedAfter, 2)]);
+
+		upgrade_runtime();
+		assert!(<Migrations as MultiStepMigrator>::ongoing());
+		run_to_block(10);
+
+		// Check that the executed migrations are recorded in `Historical`.
+		assert_eq!(
+			historic(),
+			vec![
+				mocked_id(SucceedAfter, 0),
+				mocked_id(SucceedAfter, 1),
+				mocked_id(SucceedAfter, 2)
+			]
+		);
+
+		// Check that we got all events.
+		assert_events(vec![
+			UpgradeStarted { migrations: 3 },
+			MigrationCompleted { index: 0, took: 1 },
+			MigrationAdvanced { index: 1, took: 0 },
+			MigrationCompleted { index: 1, took: 1 },
+			MigrationAdvanced { index: 2, took: 0 },
+			MigrationAdvanced { index: 2, took: 2 },
+			MigrationCompleted { index: 2, took: 3 },
+			UpgradeCompleted,
+		]);
+		assert!(!<Migrations as MultiStepMigrator>::ongoing());
+		assert_eq!(upgrades_started_completed_failed(), (1, 1, 0));
+	});
+}
+
+#[test]
+fn failing_migration_sets_cursor_to_stuck() {
+	test_closure(|| {
+		FailedUpgradeResponse::set(FailedMigrationHandling::KeepStuck);
+		set_mocked_migrations(vec![(FailAfter, 2)]);
+
+		upgrade_runtime();
+		run_to_block(10);
+
+		// Failed migrations are not recorded in `Historical`.
+		assert!(historic().is_empty());
+		assert_events(vec![
+			Event::UpgradeStarted { migrations: 1 },
+			Event::MigrationAdvanced { index: 0, took: 1 },
+			Event::MigrationAdvanced { index: 0, took: 2 },
+			Event::MigrationFailed { index: 0, took: 3 },
+			Event::UpgradeFailed,
+		]);
+
+		// Check that the handler was called correctly.
+		assert_eq!(upgrades_started_completed_failed(), (1, 0, 1));
+		assert_eq!(Cursor::<T>::get(), Some(MigrationCursor::Stuck));
+		// A stuck chain does not block extrinsics, such that governance can intervene.
+		assert!(!<Migrations as MultiStepMigrator>::ongoing());
+	});
+}
+
+#[test]
+fn failing_migration_force_unstuck_works() {
+	test_closure(|| {
+		FailedUpgradeResponse::set(FailedMigrationHandling::ForceUnstuck);
+		set_mocked_migrations(vec![(FailAfter, 2)]);
+
+		upgrade_runtime();
+		run_to_block(10);
+
+		assert_eq!(upgrades_started_completed_failed(), (1, 0, 1));
+		assert!(Cursor::<T>::get().is_none());
+		assert!(!<Migrations as MultiStepMigrator>::ongoing());
+	});
+}
+
+#[test]
+fn timeout_migration_fails_after_max_steps() {
+	test_closure(|| {
+		set_mocked_migrations(vec![(TimeoutAfter, 3)]);
+
+		upgrade_runtime();
+		run_to_block(10);
+
+		assert_events(vec![
+			Event::UpgradeStarted { migrations: 1 },
+			Event::MigrationAdvanced { index: 0, took: 1 },
+			Event::MigrationAdvanced { index: 0, took: 2 },
+			Event::MigrationAdvanced { index: 0, took: 3 },
+			Event::MigrationAdvanced { index: 0, took: 4 },
+			Event::MigrationFailed { index: 0, took: 4 },
+			Event::UpgradeFailed,
+		]);
+		assert_eq!(upgrades_started_completed_failed(), (1, 0, 1));
+	});
+}
+
+#[test]
+fn max_steps_counts_steps_not_blocks() {
+	test_closure(|| {
+		set_mocked_migrations(vec![(TimeoutAfter, 3)]);
+		run_to_block(100);
+
+		// The migration started long ago, but did not take any step yet.
+		assert_ok!(Migrations::force_set_active_cursor(RawOrigin::Root.into(), 0, None, Some(1)));
+		run_to_block(110);
+
+		assert_events(vec![
+			Event::MigrationAdvanced { index: 0, took: 100 },
+			Event::MigrationAdvanced { index: 0, took: 101 },
+			Event::MigrationAdvanced { index: 0, took: 102 },
+			Event::MigrationAdvanced { index: 0, took: 103 },
+			Event::MigrationFailed { index: 0, took: 103 },
+			Event::UpgradeFailed,
+		]);
+		assert_eq!(upgrades_started_completed_failed(), (0, 0, 1));
+	});
+}
+
+#[test]
+fn insufficient_weight_on_first_step_fails() {
+	test_closure(|| {
+		set_mocked_migrations(vec![(HighWeightAfter(Weight::MAX), 0)]);
+
+		upgrade_runtime();
+		run_to_block(10);
+
+		assert_events(vec![
+			Event::UpgradeStarted { migrations: 1 },
+			Event::MigrationFailed { index: 0, took: 1 },
+			Event::UpgradeFailed,
+		]);
+		assert_eq!(Cursor::<T>::get(), Some(MigrationCursor::Stuck));
+	});
+}
+
+#[test]
+fn historic_migrations_are_skipped() {
+	test_closure(|| {
+		set_mocked_migrations(vec![(SucceedAfter, 0), (SucceedAfter, 1)]);
+
+		upgrade_runtime();
+		run_to_block(10);
+		assert_eq!(upgrades_started_completed_failed(), (1, 1, 0));
+		System::reset_events();
+
+		// Running the same migrations again skips them.
+		upgrade_runtime();
+		run_to_block(20);
+
+		assert_events(vec![
+			Event::UpgradeStarted { migrations: 2 },
+			Event::MigrationSkipped { index: 0 },
+			Event::MigrationSkipped { index: 1 },
+			Event::UpgradeCompleted,
+		]);
+		assert_eq!(upgrades_started_completed_failed(), (1, 1, 0));
+	});
+}
+
+#[test]
+fn upgrade_while_ongoing_fails() {
+	test_closure(|| {
+		set_mocked_migrations(vec![(SucceedAfter, 5)]);
+
+		upgrade_runtime();
+		run_to_block(3);
+		System::reset_events();
+
+		// A new runtime upgrade interrupts the ongoing migrations.
+		Migrations::on_runtime_upgrade();
+
+		assert_events(vec![Event::UpgradeFailed]);
+		assert_eq!(upgrades_started_completed_failed(), (1, 0, 1));
+		assert_eq!(Cursor::<T>::get(), Some(MigrationCursor::Stuck));
+	});
+}
+
+#[test]
+fn no_migrations_does_not_start_upgrade() {
+	test_closure(|| {
+		upgrade_runtime();
+		run_to_block(10);
+
+		assert_events(vec![]);
+		assert!(!<Migrations as MultiStepMigrator>::ongoing());
+		assert_eq!(upgrades_started_completed_failed(), (0, 0, 0));
+	});
+}
+
+#[test]
+fn force_set_cursor_works() {
+	test_closure(|| {
+		assert_noop!(
+			Migrations::force_set_cursor(RawOrigin::Signed(1).into(), None),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(Migrations::force_set_cursor(
+			RawOrigin::Root.into(),
+			Some(MigrationCursor::Stuck)
+		));
+		assert!(!<Migrations as MultiStepMigrator>::ongoing());
+
+		let active = ActiveCursor { index: 0, inner_cursor: None, steps: 0, started_at: 1 };
+		assert_ok!(Migrations::force_set_cursor(RawOrigin::Root.into(), Some(active.into())));
+		assert!(<Migrations as MultiStepMigrator>::ongoing());
+
+		assert_ok!(Migrations::force_set_cursor(RawOrigin::Root.into(), None));
+		assert!(!<Migrations as MultiStepMigrator>::ongoing());
+	});
+}
+
+#[test]
+fn force_set_cursor_can_be_dispatched_on_stuck_chain() {
+	test_closure(|| {
+		pallet_sudo::GenesisConfig::<T> { key: Some(1) }.build();
+		FailedUpgradeResponse::set(FailedMigrationHandling::KeepStuck);
+		set_mocked_migrations(vec![(FailAfter, 0)]);
+
+		// The first block enacts the runtime upgrade, whose only migration fails right away.
+		Executive::initialize_block(&Header::new(
+			1,
+			H256::default(),
+			H256::default(),
+			[69u8; 32].into(),
+			Digest::default(),
+		));
+		assert_eq!(Cursor::<T>::get(), Some(MigrationCursor::Stuck));
+		assert_eq!(upgrades_started_completed_failed(), (1, 0, 1));
+
+		let call = RuntimeCall::Migrations(crate::Call::force_set_cursor { cursor: None });
+		let xt = Extrinsic::new(
+			RuntimeCall::Sudo(pallet_sudo::Call::sudo { call: Box::new(call) }),
+			Some((1, SignedExtra::new())),
+		);
+		assert_ok!(Executive::apply_extrinsic(xt).unwrap());
+		Executive::finalize_block();
+
+		assert!(Cursor::<T>::get().is_none());
+		assert!(!<Migrations as MultiStepMigrator>::ongoing());
+	});
+}
+
+#[test]
+fn force_set_active_cursor_works() {
+	test_closure(|| {
+		set_mocked_migrations(vec![(FailAfter, 0), (SucceedAfter, 0)]);
+
+		assert_ok!(Migrations::force_set_active_cursor(RawOrigin::Root.into(), 1, None, None));
+		assert_eq!(
+			Cursor::<T>::get(),
+			Some(MigrationCursor::Active(ActiveCursor {
+				index: 1,
+				inner_cursor: None,
+				steps: 0,
+				started_at: 2
+			}))
+		);
+
+		// Only the second migration is executed.
+		run_to_block(2);
+		assert_eq!(historic(), vec![mocked_id(SucceedAfter, 0)]);
+		assert_events(vec![
+			Event::MigrationCompleted { index: 1, took: 0 },
+			Event::UpgradeCompleted,
+		]);
+	});
+}
+
+#[test]
+fn force_onboard_mbms_works() {
+	test_closure(|| {
+		set_mocked_migrations(vec![(SucceedAfter, 2)]);
+
+		assert_ok!(Migrations::force_onboard_mbms(RawOrigin::Root.into()));
+		assert_noop!(Migrations::force_onboard_mbms(RawOrigin::Root.into()), Error::<T>::Ongoing);
+
+		run_to_block(10);
+		assert_eq!(upgrades_started_completed_failed(), (1, 1, 0));
+		assert_ok!(Migrations::force_onboard_mbms(RawOrigin::Root.into()));
+	});
+}
+
+#[test]
+fn clear_historic_works() {
+	test_closure(|| {
+		set_mocked_migrations(vec![(SucceedAfter, 0), (SucceedAfter, 1), (SucceedAfter, 2)]);
+
+		upgrade_runtime();
+		run_to_block(10);
+		assert_eq!(historic().len(), 3);
+		System::reset_events();
+
+		assert_ok!(Migrations::clear_historic(
+			RawOrigin::Root.into(),
+			HistoricCleanupSelector::Specific(vec![mocked_id(SucceedAfter, 1)]),
+		));
+		assert_eq!(historic(), vec![mocked_id(SucceedAfter, 0), mocked_id(SucceedAfter, 2)]);
+
+		assert_ok!(Migrations::clear_historic(
+			RawOrigin::Root.into(),
+			HistoricCleanupSelector::Wildcard { limit: None, previous_cursor: None },
+		));
+		assert!(historic().is_empty());
+
+		assert_events(vec![
+			Event::HistoricCleared { next_cursor: None },
+			Event::HistoricCleared { next_cursor: None },
+		]);
+	});
+}
@@ -341,11 +341,27 @@
 //! in addition to the unbonding pools. For maintenance simplicity these are not implemented.
 //! Related: <https://github.com/paritytech/substrate/issues/10860>
 //!
+//! ### Stake strategies
+//!
+//! How the funds of members are staked is decided by [`Config::StakeAdapter`]:
+//!
+//! * [`adapter::TransferStake`]: funds of members are transferred into the pool account which bonds
+//!   them in the staking system. Slashes are applied to the pool account right away.
+//! * [`adapter::DelegateStake`]: funds of members stay in their own accounts, held on behalf of the
+//!   pool account which is registered as an agent in a delegation system. Slashes are recorded
+//!   against the pool and applied lazily to each member, at the latest when they withdraw, or
+//!   permissionlessly through [`Call::apply_slash`].
+//!
+//! Runtimes switching from the former to the latter need to migrate each pool with
+//! [`Call::migrate_pool_to_delegate_stake`] and then each member with
+//! [`Call::migrate_delegation`]. Until a pool and its member are migrated, the member cannot
+//! bond extra, unbond or withdraw.
+//!
 //! ### Limitations
 //!
-//! * PoolMembers cannot vote with their staked funds because they are transferred into the pools
-//!   account. In the future this can be overcome by allowing the members to vote with their bonded
-//!   funds via vote splitting.
+//! * With [`adapter::TransferStake`], PoolMembers cannot vote with their staked funds because they
+//!   are transferred into the pools account. With [`adapter::DelegateStake`], the funds stay in the
+//!   member account and can be used for voting.
 //! * PoolMembers cannot quickly transfer to another pool if they do no like nominations, instead
 //!   they must wait for the unbonding duration.
 
@@ -397,6 +413,7 @@ pub mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod adapter;
 pub mod migration;
 pub mod weights;
 
@@ -425,7 +442,7 @@ pub enum ConfigOp<T: Codec + Debug> {
 }
 
 /// The type of bonding that can happen to a pool.
-enum BondType {
+pub enum BondType {
 	/// Someone is bonding into the pool upon creation.
 	Create,
 	/// Someone is adding more funds later to this pool.
@@ -545,7 +562,6 @@ impl<T: Config> PoolMember<T> {
 
 	/// Total balance of the member, both active and unbonding.
 	/// Doesn't mutate state.
-	#[cfg(any(feature = "try-runtime", feature = "fuzzing", test, debug_assertions))]
 	fn total_balance(&self) -> BalanceOf<T> {
 		let pool = BondedPool::<T>::get(self.pool_id).unwrap();
 		let active_balance = pool.points_to_balance(self.active_points());
@@ -991,8 +1007,7 @@ impl<T: Config> BondedPool<T> {
 	///
 	/// This is often used for bonding and issuing new funds into the pool.
 	fn balance_to_point(&self, new_funds: BalanceOf<T>) -> BalanceOf<T> {
-		let bonded_balance =
-			T::Staking::active_stake(&self.bonded_account()).unwrap_or(Zero::zero());
+		let bonded_balance = T::StakeAdapter::active_stake(&self.bonded_account());
 		Pallet::<T>::balance_to_point(bonded_balance, self.points, new_funds)
 	}
 
@@ -1000,8 +1015,7 @@ impl<T: Config> BondedPool<T> {
 	///
 	/// This is often used for unbonding.
 	fn points_to_balance(&self, points: BalanceOf<T>) -> BalanceOf<T> {
-		let bonded_balance =
-			T::Staking::active_stake(&self.bonded_account()).unwrap_or(Zero::zero());
+		let bonded_balance = T::StakeAdapter::active_stake(&self.bonded_account());
 		Pallet::<T>::point_to_balance(bonded_balance, self.points, points)
 	}
 
@@ -1050,14 +1064,7 @@ impl<T: Config> BondedPool<T> {
 
 	/// The pools balance that is transferable provided it is expendable by staking pallet.
 	fn transferable_balance(&self) -> BalanceOf<T> {
-		let account = self.bonded_account();
-		// Note on why we can't use `Currency::reducible_balance`: Since pooled account has a
-		// provider (staking pallet), the account can not be set expendable by
-		// `pallet-nomination-pool`. This means reducible balance always returns balance preserving
-		// ED in the account. What we want though is transferable balance given the account can be
-		// dusted.
-		T::Currency::balance(&account)
-			.saturating_sub(T::Staking::active_stake(&account).unwrap_or_default())
+		T::StakeAdapter::transferable_balance(&self.bonded_account())
 	}
 
 	fn is_root(&self, who: &T::AccountId) -> bool {
@@ -1123,8 +1130,7 @@ impl<T: Config> BondedPool<T> {
 	fn ok_to_be_open(&self) -> Result<(), DispatchError> {
 		ensure!(!self.is_destroying(), Error::<T>::CanNotChangeState);
 
-		let bonded_balance =
-			T::Staking::active_stake(&self.bonded_account()).unwrap_or(Zero::zero());
+		let bonded_balance = T::StakeAdapter::active_stake(&self.bonded_account());
 		ensure!(!bonded_balance.is_zero(), Error::<T>::OverflowRisk);
 
 		let points_to_balance_ratio_floor = self
@@ -1243,8 +1249,9 @@ impl<T: Config> BondedPool<T> {
 	/// Bond exactly `amount` from `who`'s funds into this pool. Increases the [`TotalValueLocked`]
 	/// by `amount`.
 	///
-	/// If the bond is [`BondType::Create`], [`Staking::bond`] is called, and `who` is allowed to be
-	/// killed. Otherwise, [`Staking::bond_extra`] is called and `who` cannot be killed.
+	/// If the bond is [`BondType::Create`], [`adapter::StakeStrategy::pledge_bond`] bonds the pool
+	/// for the first time, and `who` is allowed to be killed. Otherwise, it bonds extra and `who`
+	/// cannot be killed.
 	///
 	/// Returns `Ok(points_issues)`, `Err` otherwise.
 	fn try_bond_funds(
@@ -1253,28 +1260,20 @@ impl<T: Config> BondedPool<T> {
 		amount: BalanceOf<T>,
 		ty: BondType,
 	) -> Result<BalanceOf<T>, DispatchError> {
-		// Cache the value
-		let bonded_account = self.bonded_account();
-		T::Currency::transfer(
-			who,
-			&bonded_account,
-			amount,
-			match ty {
-				BondType::Create => Preservation::Expendable,
-				BondType::Later => Preservation::Preserve,
-			},
-		)?;
 		// We must calculate the points issued *before* we bond who's funds, else points:balance
 		// ratio will be wrong.
 		let points_issued = self.issue(amount);
 
-		match ty {
-			BondType::Create => T::Staking::bond(&bonded_account, amount, &self.reward_account())?,
-			// The pool should always be created in such a way its in a state to bond extra, but if
-			// the active balance is slashed below the minimum bonded or the account cannot be
-			// found, we exit early.
-			BondType::Later => T::Staking::bond_extra(&bonded_account, amount)?,
-		}
+		// The pool should always be created in such a way its in a state to bond extra, but if
+		// the active balance is slashed below the minimum bonded or the account cannot be
+		// found, we exit early.
+		T::StakeAdapter::pledge_bond(
+			who,
+			&self.bonded_account(),
+			&self.reward_account(),
+			amount,
+			ty,
+		)?;
 		TotalValueLocked::<T>::mutate(|tvl| {
 			tvl.saturating_accrue(amount);
 		});
@@ -1298,17 +1297,17 @@ impl<T: Config> BondedPool<T> {
 	/// [`BondedPool::bonded_account`].
 	///
 	/// Also reduces the [`TotalValueLocked`] by the difference of the
-	/// [`T::Staking::total_stake`] of the [`BondedPool::bonded_account`] that might occur by
-	/// [`T::Staking::withdraw_unbonded`].
+	/// [`adapter::StakeStrategy::total_stake`] of the [`BondedPool::bonded_account`] that might
+	/// occur by [`adapter::StakeStrategy::withdraw_unbonded`].
 	///
-	/// Returns the result of [`T::Staking::withdraw_unbonded`]
+	/// Returns the result of [`adapter::StakeStrategy::withdraw_unbonded`]
 	fn withdraw_from_staking(&self, num_slashing_spans: u32) -> Result<bool, DispatchError> {
 		let bonded_account = self.bonded_account();
 
-		let prev_total = T::Staking::total_stake(&bonded_account.clone()).unwrap_or_default();
-		let outcome = T::Staking::withdraw_unbonded(bonded_account.clone(), num_slashing_spans);
-		let diff = prev_total
-			.defensive_saturating_sub(T::Staking::total_stake(&bonded_account).unwrap_or_default());
+		let prev_total = T::StakeAdapter::total_stake(&bonded_account.clone());
+		let outcome = T::StakeAdapter::withdraw_unbonded(&bonded_account, num_slashing_spans);
+		let diff =
+			prev_total.defensive_saturating_sub(T::StakeAdapter::total_stake(&bonded_account));
 		TotalValueLocked::<T>::mutate(|tvl| {
 			tvl.saturating_reduce(diff);
 		});
@@ -1586,7 +1585,7 @@ impl<T: Config> Get<u32> for TotalUnbondingPools<T> {
 		// NOTE: this may be dangerous in the scenario bonding_duration gets decreased because
 		// we would no longer be able to decode `BoundedBTreeMap::<EraIndex, UnbondPool<T>,
 		// TotalUnbondingPools<T>>`, which uses `TotalUnbondingPools` as the bound
-		T::Staking::bonding_duration() + T::PostUnbondingPoolsWindow::get()
+		T::StakeAdapter::bonding_duration() + T::PostUnbondingPoolsWindow::get()
 	}
 }
 
@@ -1662,8 +1661,15 @@ pub mod pallet {
 		/// Infallible method for converting `U256` to `Currency::Balance`.
 		type U256ToBalance: Convert<U256, BalanceOf<Self>>;
 
-		/// The interface for nominating.
-		type Staking: StakingInterface<Balance = BalanceOf<Self>, AccountId = Self::AccountId>;
+		/// The adapter used to interact with the staking system.
+		///
+		/// Decides whether the funds of members are transferred to the pool account
+		/// ([`adapter::TransferStake`]) or stay in the member accounts and are delegated to the
+		/// pool account ([`adapter::DelegateStake`]).
+		type StakeAdapter: adapter::StakeStrategy<
+			Balance = BalanceOf<Self>,
+			AccountId = Self::AccountId,
+		>;
 
 		/// The amount of eras a `SubPools::with_era` pool can exist before it gets merged into the
 		/// `SubPools::no_era` pool. In other words, this is the amount of eras a member will be
@@ -1964,6 +1970,14 @@ pub mod pallet {
 		BondExtraRestricted,
 		/// No imbalance in the ED deposit for the pool.
 		NothingToAdjust,
+		/// No slash pending that can be applied to the member.
+		NothingToSlash,
+		/// The pool or member delegation has already migrated to delegate stake.
+		AlreadyMigrated,
+		/// The pool or member delegation has not migrated yet to delegate stake.
+		NotMigrated,
+		/// This call is not allowed in the current state of the pallet.
+		NotSupported,
 	}
 
 	#[derive(Encode, Decode, PartialEq, TypeInfo, PalletError, RuntimeDebug)]
@@ -1979,6 +1993,8 @@ pub mod pallet {
 		/// The bonded account should only be killed by the staking system when the depositor is
 		/// withdrawing
 		BondedStashKilledPrematurely,
+		/// The delegation feature is unsupported.
+		DelegationUnsupported,
 	}
 
 	impl<T> From<DefensiveError> for Error<T> {
@@ -2022,6 +2038,7 @@ pub mod pallet {
 
 			let mut bonded_pool = BondedPool::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
 			bonded_pool.ok_to_join()?;
+			Self::ensure_migrated(&bonded_pool.bonded_account(), None)?;
 
 			let mut reward_pool = RewardPools::<T>::get(pool_id)
 				.defensive_ok_or::<Error<T>>(DefensiveError::RewardPoolNotFound.into())?;
@@ -2138,6 +2155,7 @@ pub mod pallet {
 			let (mut member, mut bonded_pool, mut reward_pool) =
 				Self::get_member_with_pools(&member_account)?;
 
+			Self::ensure_migrated(&bonded_pool.bonded_account(), Some(&member_account))?;
 			bonded_pool.ok_to_unbond_with(&who, &member_account, &member, unbonding_points)?;
 
 			// Claim the the payout prior to unbonding. Once the user is unbonding their points no
@@ -2155,12 +2173,12 @@ pub mod pallet {
 				&mut reward_pool,
 			)?;
 
-			let current_era = T::Staking::current_era();
-			let unbond_era = T::Staking::bonding_duration().saturating_add(current_era);
+			let current_era = T::StakeAdapter::current_era();
+			let unbond_era = T::StakeAdapter::bonding_duration().saturating_add(current_era);
 
 			// Unbond in the actual underlying nominator.
 			let unbonding_balance = bonded_pool.dissolve(unbonding_points);
-			T::Staking::unbond(&bonded_pool.bonded_account(), unbonding_balance)?;
+			T::StakeAdapter::unbond(&bonded_pool.bonded_account(), unbonding_balance)?;
 
 			// Note that we lazily create the unbonding pools here if they don't already exist
 			let mut sub_pools = SubPoolsStorage::<T>::get(member.pool_id)
@@ -2260,7 +2278,7 @@ pub mod pallet {
 			let member_account = T::Lookup::lookup(member_account)?;
 			let mut member =
 				PoolMembers::<T>::get(&member_account).ok_or(Error::<T>::PoolMemberNotFound)?;
-			let current_era = T::Staking::current_era();
+			let current_era = T::StakeAdapter::current_era();
 
 			let bonded_pool = BondedPool::<T>::get(member.pool_id)
 				.defensive_ok_or::<Error<T>>(DefensiveError::PoolNotFound.into())?;
@@ -2268,6 +2286,15 @@ pub mod pallet {
 				SubPoolsStorage::<T>::get(member.pool_id).ok_or(Error::<T>::SubPoolsNotFound)?;
 
 			bonded_pool.ok_to_withdraw_unbonded_with(&caller, &member_account)?;
+			let pool_account = bonded_pool.bonded_account();
+			Self::ensure_migrated(&pool_account, Some(&member_account))?;
+
+			// If the member has a pending slash, it must be applied before the member can
+			// withdraw, so that the withdrawn balance matches the held balance of the member.
+			let pending_slash = Self::member_pending_slash(&member_account, &member)?;
+			if !pending_slash.is_zero() {
+				T::StakeAdapter::member_slash(&member_account, &pool_account, pending_slash, None)?;
+			}
 
 			// NOTE: must do this after we have done the `ok_to_withdraw_unbonded_other_with` check.
 			let withdrawn_points = member.withdraw_unlocked(current_era);
@@ -2310,11 +2337,11 @@ pub mod pallet {
 				// order to ensure members can leave the pool and it can be destroyed.
 				.min(bonded_pool.transferable_balance());
 
-			T::Currency::transfer(
-				&bonded_pool.bonded_account(),
+			T::StakeAdapter::member_withdraw(
 				&member_account,
+				&pool_account,
 				balance_to_unbond,
-				Preservation::Expendable,
+				num_slashing_spans,
 			)
 			.defensive()?;
 
@@ -2431,7 +2458,7 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			let bonded_pool = BondedPool::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
 			ensure!(bonded_pool.can_nominate(&who), Error::<T>::NotNominator);
-			T::Staking::nominate(&bonded_pool.bonded_account(), validators)
+			T::StakeAdapter::nominate(&bonded_pool.bonded_account(), validators)
 		}
 
 		/// Set a new state for the pool.
@@ -2604,7 +2631,7 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			let bonded_pool = BondedPool::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
 			ensure!(bonded_pool.can_nominate(&who), Error::<T>::NotNominator);
-			T::Staking::chill(&bonded_pool.bonded_account())
+			T::StakeAdapter::chill(&bonded_pool.bonded_account())
 		}
 
 		/// `origin` bonds funds from `extra` for some pool member `member` into their respective
@@ -2804,6 +2831,114 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Apply a pending slash on a member.
+		///
+		/// Fails unless [`crate::pallet::Config::StakeAdapter`] is of strategy type:
+		/// [`adapter::StakeStrategyType::Delegate`].
+		///
+		/// This call can be dispatched permissionlessly (i.e. by any account). If the member has
+		/// slash to be applied, caller may be rewarded with the part of the slash.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::apply_slash())]
+		pub fn apply_slash(
+			origin: OriginFor<T>,
+			member_account: AccountIdLookupOf<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure!(
+				T::StakeAdapter::strategy_type() == adapter::StakeStrategyType::Delegate,
+				Error::<T>::NotSupported
+			);
+
+			let who = ensure_signed(origin)?;
+			let member_account = T::Lookup::lookup(member_account)?;
+			Self::do_apply_slash(&member_account, Some(who))?;
+
+			// If successful, refund the fees.
+			Ok(Pays::No.into())
+		}
+
+		/// Migrates delegated funds from the pool account to the `member_account`.
+		///
+		/// Fails unless [`crate::pallet::Config::StakeAdapter`] is of strategy type:
+		/// [`adapter::StakeStrategyType::Delegate`].
+		///
+		/// This is a permission-less call and refunds any fee if claim is successful.
+		///
+		/// If the pool has migrated to delegation based staking, the staked tokens of pool members
+		/// can be moved and held in their own account. See [`adapter::DelegateStake`]
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::migrate_delegation())]
+		pub fn migrate_delegation(
+			origin: OriginFor<T>,
+			member_account: AccountIdLookupOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let _caller = ensure_signed(origin)?;
+
+			ensure!(
+				T::StakeAdapter::strategy_type() == adapter::StakeStrategyType::Delegate,
+				Error::<T>::NotSupported
+			);
+
+			let member_account = T::Lookup::lookup(member_account)?;
+			let member =
+				PoolMembers::<T>::get(&member_account).ok_or(Error::<T>::PoolMemberNotFound)?;
+
+			// ensure pool is migrated.
+			let pool_account = Self::create_bonded_account(member.pool_id);
+			ensure!(
+				T::StakeAdapter::pool_strategy(&pool_account) ==
+					adapter::StakeStrategyType::Delegate,
+				Error::<T>::NotMigrated
+			);
+
+			// ensure member is not already migrated.
+			ensure!(
+				T::StakeAdapter::member_delegation_balance(&member_account).is_zero(),
+				Error::<T>::AlreadyMigrated
+			);
+
+			let delegation = member.total_balance();
+			T::StakeAdapter::migrate_delegation(&pool_account, &member_account, delegation)?;
+
+			// if successful, we refund the fee.
+			Ok(Pays::No.into())
+		}
+
+		/// Migrate pool from [`adapter::StakeStrategyType::Transfer`] to
+		/// [`adapter::StakeStrategyType::Delegate`].
+		///
+		/// Fails unless [`crate::pallet::Config::StakeAdapter`] is of strategy type:
+		/// [`adapter::StakeStrategyType::Delegate`].
+		///
+		/// This call can be dispatched permissionlessly, and refunds any fee if successful.
+		///
+		/// If the pool has already migrated to delegation based staking, this call will fail.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::pool_migrate())]
+		pub fn migrate_pool_to_delegate_stake(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+		) -> DispatchResultWithPostInfo {
+			// gate this call to be called only if `DelegateStake` strategy is used.
+			ensure!(
+				T::StakeAdapter::strategy_type() == adapter::StakeStrategyType::Delegate,
+				Error::<T>::NotSupported
+			);
+
+			let _caller = ensure_signed(origin)?;
+			// ensure pool exists.
+			ensure!(BondedPools::<T>::contains_key(pool_id), Error::<T>::PoolNotFound);
+			// ensure pool is not already migrated.
+			ensure!(
+				T::StakeAdapter::pool_strategy(&Self::create_bonded_account(pool_id)) ==
+					adapter::StakeStrategyType::Transfer,
+				Error::<T>::AlreadyMigrated
+			);
+
+			Self::migrate_to_delegate_stake(pool_id)?;
+			Ok(Pays::No.into())
+		}
 	}
 
 	#[pallet::hooks]
@@ -2819,7 +2954,7 @@ pub mod pallet {
 				"Minimum points to balance ratio must be greater than 0"
 			);
 			assert!(
-				T::Staking::bonding_duration() < TotalUnbondingPools::<T>::get(),
+				T::StakeAdapter::bonding_duration() < TotalUnbondingPools::<T>::get(),
 				"There must be more unbonding pools then the bonding duration /
 				so a slash can be applied to relevant unboding pools. (We assume /
 				the bonding duration > slash deffer duration.",
@@ -2837,7 +2972,7 @@ impl<T: Config> Pallet<T> {
 	/// It is essentially `max { MinNominatorBond, MinCreateBond, MinJoinBond }`, where the former
 	/// is coming from the staking pallet and the latter two are configured in this pallet.
 	pub fn depositor_min_bond() -> BalanceOf<T> {
-		T::Staking::minimum_nominator_bond()
+		T::StakeAdapter::minimum_nominator_bond()
 			.max(MinCreateBond::<T>::get())
 			.max(MinJoinBond::<T>::get())
 			.max(T::Currency::minimum_balance())
@@ -2845,7 +2980,7 @@ impl<T: Config> Pallet<T> {
 	/// Remove everything related to the given bonded pool.
 	///
 	/// Metadata and all of the sub-pools are also deleted. All accounts are dusted and the leftover
-	/// of the reward account is returned to the depositor.
+	/// of the reward and bonded accounts is returned to the depositor.
 	pub fn dissolve_pool(bonded_pool: BondedPool<T>) {
 		let reward_account = bonded_pool.reward_account();
 		let bonded_account = bonded_pool.bonded_account();
@@ -2873,7 +3008,7 @@ impl<T: Config> Pallet<T> {
 			"bonded account of dissolving pool should have no consumers"
 		);
 		defensive_assert!(
-			T::Staking::total_stake(&bonded_account).unwrap_or_default() == Zero::zero(),
+			T::StakeAdapter::total_stake(&bonded_account) == Zero::zero(),
 			"dissolving pool should not have any stake in the staking pallet"
 		);
 
@@ -2895,8 +3030,24 @@ impl<T: Config> Pallet<T> {
 			T::Currency::total_balance(&reward_account) == Zero::zero(),
 			"could not transfer all amount to depositor while dissolving pool"
 		);
+
+		// The bonded account can be left with the existential deposit that was topped up when the
+		// pool was created, see [`adapter::TransferStake`]. It goes back to the depositor as well.
+		let bonded_pool_remaining = T::Currency::reducible_balance(
+			&bonded_account,
+			Preservation::Expendable,
+			Fortitude::Polite,
+		);
+		if !bonded_pool_remaining.is_zero() {
+			let _ = T::Currency::transfer(
+				&bonded_account,
+				&bonded_pool.roles.depositor,
+				bonded_pool_remaining,
+				Preservation::Expendable,
+			);
+		}
 		defensive_assert!(
-			T::Currency::total_balance(&bonded_pool.bonded_account()) == Zero::zero(),
+			T::StakeAdapter::total_balance(&bonded_pool.bonded_account()) == Zero::zero(),
 			"dissolving pool should not have any balance"
 		);
 		// NOTE: Defensively force set balance to zero.
@@ -2922,6 +3073,86 @@ impl<T: Config> Pallet<T> {
 		T::PalletId::get().into_sub_account_truncating((AccountType::Reward, id))
 	}
 
+	/// Migrate the bonded account of pool `pool_id` from a direct nominator to a delegation
+	/// based agent of the staking system.
+	fn migrate_to_delegate_stake(id: PoolId) -> DispatchResult {
+		T::StakeAdapter::migrate_nominator_to_agent(
+			&Self::create_bonded_account(id),
+			&Self::create_reward_account(id),
+		)
+	}
+
+	/// Ensure the pool, and optionally the member, are using the staking strategy of
+	/// [`Config::StakeAdapter`].
+	///
+	/// With [`adapter::StakeStrategyType::Delegate`], funds of a pool or a member that have not
+	/// been migrated yet are still held by the pool account and cannot be moved until
+	/// [`Call::migrate_pool_to_delegate_stake`] and [`Call::migrate_delegation`] are called.
+	fn ensure_migrated(
+		pool_account: &T::AccountId,
+		maybe_member: Option<&T::AccountId>,
+	) -> DispatchResult {
+		if T::StakeAdapter::strategy_type() != adapter::StakeStrategyType::Delegate {
+			// nothing to migrate.
+			return Ok(())
+		}
+
+		ensure!(
+			T::StakeAdapter::pool_strategy(pool_account) == adapter::StakeStrategyType::Delegate,
+			Error::<T>::NotMigrated
+		);
+
+		if let Some(member_account) = maybe_member {
+			ensure!(
+				!T::StakeAdapter::member_delegation_balance(member_account).is_zero(),
+				Error::<T>::NotMigrated
+			);
+		}
+
+		Ok(())
+	}
+
+	/// Returns the unapplied slash of a member.
+	///
+	/// Pending slash is only applicable with [`adapter::DelegateStake`] strategy. It is the
+	/// difference between the balance delegated by the member and the balance the member owns in
+	/// the pool after the pool got slashed.
+	fn member_pending_slash(
+		member_account: &T::AccountId,
+		member: &PoolMember<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let pool_account = Self::create_bonded_account(member.pool_id);
+		let pool_pending_slash = T::StakeAdapter::pending_slash(&pool_account);
+
+		// if the pool doesn't have any pending slash, the member does not have one either.
+		if pool_pending_slash.is_zero() {
+			return Ok(Zero::zero())
+		}
+
+		let actual_balance = T::StakeAdapter::member_delegation_balance(member_account);
+		let expected_balance = member.total_balance();
+
+		Ok(actual_balance.saturating_sub(expected_balance).min(pool_pending_slash))
+	}
+
+	/// Apply the pending slash of `member_account`, rewarding `reporter` if any.
+	fn do_apply_slash(
+		member_account: &T::AccountId,
+		reporter: Option<T::AccountId>,
+	) -> DispatchResult {
+		let member = PoolMembers::<T>::get(member_account).ok_or(Error::<T>::PoolMemberNotFound)?;
+
+		let pending_slash = Self::member_pending_slash(member_account, &member)?;
+		ensure!(!pending_slash.is_zero(), Error::<T>::NothingToSlash);
+
+		T::StakeAdapter::member_slash(
+			member_account,
+			&Self::create_bonded_account(member.pool_id),
+			pending_slash,
+			reporter,
+		)
+	}
+
 	/// Get the member with their associated bonded and reward pool.
 	fn get_member_with_pools(
 		who: &T::AccountId,
@@ -3143,6 +3374,7 @@ impl<T: Config> Pallet<T> {
 
 		let (mut member, mut bonded_pool, mut reward_pool) =
 			Self::get_member_with_pools(&member_account)?;
+		Self::ensure_migrated(&bonded_pool.bonded_account(), Some(&member_account))?;
 
 		// payout related stuff: we must claim the payouts, and updated recorded payout data
 		// before updating the bonded pool points, similar to that of `join` transaction.
@@ -3446,8 +3678,7 @@ impl<T: Config> Pallet<T> {
 				pool is being destroyed and the depositor is the last member",
 			);
 
-			expected_tvl +=
-				T::Staking::total_stake(&bonded_pool.bonded_account()).unwrap_or_default();
+			expected_tvl += T::StakeAdapter::total_stake(&bonded_pool.bonded_account());
 
 			Ok(())
 		})?;
@@ -3476,8 +3707,8 @@ impl<T: Config> Pallet<T> {
 			let subs = SubPoolsStorage::<T>::get(pool_id).unwrap_or_default();
 
 			let sum_unbonding_balance = subs.sum_unbonding_balance();
-			let bonded_balance = T::Staking::active_stake(&pool_account).unwrap_or_default();
-			let total_balance = T::Currency::total_balance(&pool_account);
+			let bonded_balance = T::StakeAdapter::active_stake(&pool_account);
+			let total_balance = T::StakeAdapter::total_balance(&pool_account);
 
 			assert!(
 				total_balance >= bonded_balance + sum_unbonding_balance,
@@ -3580,8 +3811,7 @@ impl<T: Config> Pallet<T> {
 	/// If the pool ID does not exist, returns 0 ratio balance to points. Used by runtime API.
 	pub fn api_balance_to_points(pool_id: PoolId, new_funds: BalanceOf<T>) -> BalanceOf<T> {
 		if let Some(pool) = BondedPool::<T>::get(pool_id) {
-			let bonded_balance =
-				T::Staking::active_stake(&pool.bonded_account()).unwrap_or(Zero::zero());
+			let bonded_balance = T::StakeAdapter::active_stake(&pool.bonded_account());
 			Pallet::<T>::balance_to_point(bonded_balance, pool.points, new_funds)
 		} else {
 			Zero::zero()
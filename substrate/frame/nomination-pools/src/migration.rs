@@ -56,6 +56,108 @@ pub mod versioned {
 	>;
 }
 
+/// Migrations that are not tied to a storage version of this pallet.
+pub mod unversioned {
+	use super::*;
+
+	/// Migrates up to `MaxPools` pools from [`adapter::StakeStrategyType::Transfer`] to
+	/// [`adapter::StakeStrategyType::Delegate`].
+	///
+	/// Pools that have already migrated are skipped. Members of the migrated pools still need to
+	/// call [`Call::migrate_delegation`] to move their funds back to their own accounts.
+	///
+	/// Only useful for runtimes that switch [`Config::StakeAdapter`] to
+	/// [`adapter::DelegateStake`]. This migration can be applied multiple times until all pools
+	/// are migrated.
+	pub struct DelegationStakeMigration<T, MaxPools>(sp_std::marker::PhantomData<(T, MaxPools)>);
+
+	impl<T: Config, MaxPools: Get<u32>> OnRuntimeUpgrade for DelegationStakeMigration<T, MaxPools> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut count: u32 = 0;
+
+			BondedPools::<T>::iter_keys().take(MaxPools::get() as usize).for_each(|id| {
+				let pool_acc = Pallet::<T>::create_bonded_account(id);
+
+				// only migrate if the pool is in Transfer Strategy.
+				if T::StakeAdapter::pool_strategy(&pool_acc) == adapter::StakeStrategyType::Transfer
+				{
+					let _ = Pallet::<T>::migrate_to_delegate_stake(id).map_err(|err| {
+						log!(
+							warn,
+							"failed to migrate pool {:?} to delegate stake strategy with err: {:?}",
+							id,
+							err
+						)
+					});
+					count.saturating_inc();
+				}
+			});
+
+			log!(info, "migrated {:?} pools to delegate stake strategy", count);
+
+			// reads: (bonded pool key + current pool strategy) * MaxPools (worst case)
+			T::DbWeight::get()
+				.reads_writes(2, 0)
+				.saturating_mul(MaxPools::get() as u64)
+				// migration weight: `pool_migrate` weight * count
+				.saturating_add(T::WeightInfo::pool_migrate().saturating_mul(count.into()))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			// ensure stake adapter is correct.
+			ensure!(
+				T::StakeAdapter::strategy_type() == adapter::StakeStrategyType::Delegate,
+				"Current strategy is not `Delegate`"
+			);
+
+			if BondedPools::<T>::count() > MaxPools::get() {
+				// we log a warning if the number of pools exceeds the bound.
+				log!(
+					warn,
+					"Number of pools {} exceeds the maximum bound {}. This would leave some pools \
+					unmigrated.",
+					BondedPools::<T>::count(),
+					MaxPools::get()
+				);
+			}
+
+			let mut expected_pool_balances = Vec::new();
+			for id in BondedPools::<T>::iter_keys().take(MaxPools::get() as usize) {
+				let pool_account = Pallet::<T>::create_bonded_account(id);
+				if T::StakeAdapter::pool_strategy(&pool_account) ==
+					adapter::StakeStrategyType::Transfer
+				{
+					expected_pool_balances.push((id, T::Currency::total_balance(&pool_account)));
+				}
+			}
+
+			Ok(expected_pool_balances.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(data: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let expected_pool_balances: Vec<(PoolId, BalanceOf<T>)> =
+				Decode::decode(&mut &data[..]).map_err(|_| "failed to decode pre-upgrade data")?;
+
+			for (id, balance) in expected_pool_balances {
+				let pool_account = Pallet::<T>::create_bonded_account(id);
+				ensure!(
+					T::StakeAdapter::pool_strategy(&pool_account) ==
+						adapter::StakeStrategyType::Delegate,
+					"Pool has not migrated to delegate stake strategy"
+				);
+				ensure!(
+					T::StakeAdapter::total_balance(&pool_account) == balance,
+					"Pool balance is not the same after migration"
+				);
+			}
+
+			Ok(())
+		}
+	}
+}
+
 pub mod v8 {
 	use super::{v7::V7BondedPoolInner, *};
 
@@ -161,10 +263,9 @@ pub(crate) mod v7 {
 		fn calculate_tvl_by_total_stake() -> BalanceOf<T> {
 			BondedPools::<T>::iter()
 				.map(|(id, inner)| {
-					T::Staking::total_stake(
+					T::StakeAdapter::total_stake(
 						&V7BondedPool { id, inner: inner.clone() }.bonded_account(),
 					)
-					.unwrap_or_default()
 				})
 				.reduce(|acc, total_balance| acc + total_balance)
 				.unwrap_or_default()
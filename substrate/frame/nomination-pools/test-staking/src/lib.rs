@@ -43,7 +43,12 @@ fn pool_lifecycle_e2e() {
 
 		assert_eq!(
 			staking_events_since_last_call(),
-			vec![StakingEvent::Bonded { stash: POOL1_BONDED, amount: 50 }]
+			vec![
+				// the existential deposit of the pool account is not staked at first, and is
+				// topped up by the pool.
+				StakingEvent::Bonded { stash: POOL1_BONDED, amount: 45 },
+				StakingEvent::Bonded { stash: POOL1_BONDED, amount: 5 },
+			]
 		);
 		assert_eq!(
 			pool_events_since_last_call(),
@@ -204,7 +209,10 @@ fn pool_slash_e2e() {
 
 		assert_eq!(
 			staking_events_since_last_call(),
-			vec![StakingEvent::Bonded { stash: POOL1_BONDED, amount: 40 }]
+			vec![
+				StakingEvent::Bonded { stash: POOL1_BONDED, amount: 39 },
+				StakingEvent::Bonded { stash: POOL1_BONDED, amount: 1 },
+			]
 		);
 		assert_eq!(
 			pool_events_since_last_call(),
@@ -414,7 +422,10 @@ fn pool_slash_proportional() {
 
 		assert_eq!(
 			staking_events_since_last_call(),
-			vec![StakingEvent::Bonded { stash: POOL1_BONDED, amount: 40 }]
+			vec![
+				StakingEvent::Bonded { stash: POOL1_BONDED, amount: 39 },
+				StakingEvent::Bonded { stash: POOL1_BONDED, amount: 1 },
+			]
 		);
 		assert_eq!(
 			pool_events_since_last_call(),
@@ -549,7 +560,10 @@ fn pool_slash_non_proportional_only_bonded_pool() {
 		assert_ok!(Pools::create(RuntimeOrigin::signed(10), 40, 10, 10, 10));
 		assert_eq!(
 			staking_events_since_last_call(),
-			vec![StakingEvent::Bonded { stash: POOL1_BONDED, amount: 40 }]
+			vec![
+				StakingEvent::Bonded { stash: POOL1_BONDED, amount: 39 },
+				StakingEvent::Bonded { stash: POOL1_BONDED, amount: 1 },
+			]
 		);
 		assert_eq!(
 			pool_events_since_last_call(),
@@ -628,7 +642,10 @@ fn pool_slash_non_proportional_bonded_pool_and_chunks() {
 		assert_ok!(Pools::create(RuntimeOrigin::signed(10), 40, 10, 10, 10));
 		assert_eq!(
 			staking_events_since_last_call(),
-			vec![StakingEvent::Bonded { stash: POOL1_BONDED, amount: 40 }]
+			vec![
+				StakingEvent::Bonded { stash: POOL1_BONDED, amount: 39 },
+				StakingEvent::Bonded { stash: POOL1_BONDED, amount: 1 },
+			]
 		);
 		assert_eq!(
 			pool_events_since_last_call(),
@@ -0,0 +1,138 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mocked runtime for testing the parameters pallet.
+
+#![cfg(test)]
+
+use frame_support::{
+	construct_runtime, derive_impl,
+	dynamic_params::{dynamic_pallet_params, dynamic_params},
+	traits::EnsureOriginWithArg,
+};
+
+use crate as pallet_parameters;
+use crate::*;
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type Block = frame_system::mocking::MockBlock<Runtime>;
+	type AccountData = pallet_balances::AccountData<<Self as pallet_balances::Config>::Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig as pallet_balances::DefaultConfig)]
+impl pallet_balances::Config for Runtime {
+	type ReserveIdentifier = [u8; 8];
+	type AccountStore = System;
+}
+
+#[dynamic_params(RuntimeParameters, pallet_parameters::Pallet::<Runtime>)]
+pub mod dynamic_params {
+	use super::*;
+
+	#[dynamic_pallet_params]
+	#[codec(index = 3)]
+	pub mod pallet1 {
+		#[codec(index = 0)]
+		pub static Key1: u64 = 0;
+		#[codec(index = 1)]
+		pub static Key2: u32 = 1;
+		#[codec(index = 2)]
+		pub static Key3: u128 = 2;
+	}
+
+	#[dynamic_pallet_params]
+	#[codec(index = 1)]
+	pub mod pallet2 {
+		#[codec(index = 2)]
+		pub static Key1: u64 = 0;
+		#[codec(index = 1)]
+		pub static Key2: u32 = 2;
+		#[codec(index = 0)]
+		pub static Key3: u128 = 4;
+	}
+}
+
+impl pallet_example_basic::Config for Runtime {
+	// Use the dynamic key in the pallet config:
+	type MagicNumber = dynamic_params::pallet1::Key1;
+
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+}
+
+/// Allows `Pallet1` keys to be set by root and `Pallet2` keys by the signed account `123`.
+pub struct AdminOrigin;
+impl EnsureOriginWithArg<RuntimeOrigin, RuntimeParametersKey> for AdminOrigin {
+	type Success = ();
+
+	fn try_origin(
+		origin: RuntimeOrigin,
+		key: &RuntimeParametersKey,
+	) -> Result<Self::Success, RuntimeOrigin> {
+		match key {
+			RuntimeParametersKey::Pallet1(_) => {
+				frame_system::ensure_root(origin.clone()).map_err(|_| origin)?;
+				Ok(())
+			},
+			RuntimeParametersKey::Pallet2(_) => {
+				let who =
+					frame_system::ensure_signed(origin.clone()).map_err(|_| origin.clone())?;
+				if who != 123 {
+					return Err(origin)
+				}
+				Ok(())
+			},
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin(_key: &RuntimeParametersKey) -> Result<RuntimeOrigin, ()> {
+		Ok(RuntimeOrigin::root())
+	}
+}
+
+#[derive_impl(pallet_parameters::config_preludes::TestDefaultConfig as pallet_parameters::DefaultConfig)]
+impl Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeParameters = RuntimeParameters;
+	type AdminOrigin = AdminOrigin;
+}
+
+construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Balances: pallet_balances,
+		ModuleUnderTest: pallet_parameters,
+		Example: pallet_example_basic,
+	}
+);
+
+/// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = sp_io::TestExternalities::new(Default::default());
+	// Events are not deposited in the genesis block.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Asserts that the last event of the runtime is `event`.
+pub(crate) fn assert_last_event(event: RuntimeEvent) {
+	let events = frame_system::Pallet::<Runtime>::events();
+	assert!(!events.is_empty(), "No events were deposited");
+	assert_eq!(events.last().unwrap().event, event);
+}
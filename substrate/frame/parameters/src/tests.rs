@@ -0,0 +1,207 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(test)]
+
+use crate::mock::{assert_last_event, dynamic_params::*, *};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{dynamic_params::AggregatedKeyValue, Get},
+};
+use sp_runtime::DispatchError;
+
+#[test]
+fn set_parameters_example() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(pallet1::Key3::get(), 2, "Default works");
+
+		// This gets rejected since the origin is not root.
+		assert_noop!(
+			ModuleUnderTest::set_parameter(
+				RuntimeOrigin::signed(1),
+				RuntimeParameters::Pallet1(pallet1::Parameters::Key3(pallet1::Key3, Some(123))),
+			),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(ModuleUnderTest::set_parameter(
+			RuntimeOrigin::root(),
+			RuntimeParameters::Pallet1(pallet1::Parameters::Key3(pallet1::Key3, Some(123))),
+		));
+
+		assert_eq!(pallet1::Key3::get(), 123, "Update works");
+		assert_last_event(
+			crate::Event::Updated {
+				key: RuntimeParametersKey::Pallet1(pallet1::ParametersKey::Key3(pallet1::Key3)),
+				old_value: None,
+				new_value: Some(RuntimeParametersValue::Pallet1(pallet1::ParametersValue::Key3(
+					123,
+				))),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn set_parameters_same_is_noop() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ModuleUnderTest::set_parameter(
+			RuntimeOrigin::root(),
+			RuntimeParameters::Pallet1(pallet1::Parameters::Key3(pallet1::Key3, Some(123))),
+		));
+
+		assert_ok!(ModuleUnderTest::set_parameter(
+			RuntimeOrigin::root(),
+			RuntimeParameters::Pallet1(pallet1::Parameters::Key3(pallet1::Key3, Some(123))),
+		));
+
+		assert_eq!(pallet1::Key3::get(), 123);
+		assert_last_event(
+			crate::Event::Updated {
+				key: RuntimeParametersKey::Pallet1(pallet1::ParametersKey::Key3(pallet1::Key3)),
+				old_value: Some(RuntimeParametersValue::Pallet1(pallet1::ParametersValue::Key3(
+					123,
+				))),
+				new_value: Some(RuntimeParametersValue::Pallet1(pallet1::ParametersValue::Key3(
+					123,
+				))),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn set_parameters_twice_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(pallet1::Key3::get(), 2, "Default works");
+
+		assert_ok!(ModuleUnderTest::set_parameter(
+			RuntimeOrigin::root(),
+			RuntimeParameters::Pallet1(pallet1::Parameters::Key3(pallet1::Key3, Some(123))),
+		));
+		assert_eq!(pallet1::Key3::get(), 123, "Update works");
+
+		assert_ok!(ModuleUnderTest::set_parameter(
+			RuntimeOrigin::root(),
+			RuntimeParameters::Pallet1(pallet1::Parameters::Key3(pallet1::Key3, Some(432))),
+		));
+		assert_eq!(pallet1::Key3::get(), 432, "Update works");
+	});
+}
+
+#[test]
+fn unset_parameters_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ModuleUnderTest::set_parameter(
+			RuntimeOrigin::root(),
+			RuntimeParameters::Pallet1(pallet1::Parameters::Key3(pallet1::Key3, Some(123))),
+		));
+		assert_eq!(pallet1::Key3::get(), 123, "Update works");
+
+		assert_ok!(ModuleUnderTest::set_parameter(
+			RuntimeOrigin::root(),
+			RuntimeParameters::Pallet1(pallet1::Parameters::Key3(pallet1::Key3, None)),
+		));
+		assert_eq!(pallet1::Key3::get(), 2, "Falls back to the default");
+		assert_last_event(
+			crate::Event::Updated {
+				key: RuntimeParametersKey::Pallet1(pallet1::ParametersKey::Key3(pallet1::Key3)),
+				old_value: Some(RuntimeParametersValue::Pallet1(pallet1::ParametersValue::Key3(
+					123,
+				))),
+				new_value: None,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn set_parameters_checks_origin_per_key() {
+	new_test_ext().execute_with(|| {
+		// `Pallet2` keys can only be set by account `123`.
+		assert_noop!(
+			ModuleUnderTest::set_parameter(
+				RuntimeOrigin::root(),
+				RuntimeParameters::Pallet2(pallet2::Parameters::Key1(pallet2::Key1, Some(5))),
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ModuleUnderTest::set_parameter(
+				RuntimeOrigin::signed(1),
+				RuntimeParameters::Pallet2(pallet2::Parameters::Key1(pallet2::Key1, Some(5))),
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(ModuleUnderTest::set_parameter(
+			RuntimeOrigin::signed(123),
+			RuntimeParameters::Pallet2(pallet2::Parameters::Key1(pallet2::Key1, Some(5))),
+		));
+
+		// But not the keys of `Pallet1`.
+		assert_noop!(
+			ModuleUnderTest::set_parameter(
+				RuntimeOrigin::signed(123),
+				RuntimeParameters::Pallet1(pallet1::Parameters::Key1(pallet1::Key1, Some(5))),
+			),
+			DispatchError::BadOrigin
+		);
+
+		assert_eq!(pallet2::Key1::get(), 5);
+		assert_eq!(pallet1::Key1::get(), 0, "Keys of different modules are independent");
+	});
+}
+
+#[test]
+fn dynamic_params_can_be_used_as_pallet_config() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(<Runtime as pallet_example_basic::Config>::MagicNumber::get(), 0);
+
+		assert_ok!(ModuleUnderTest::set_parameter(
+			RuntimeOrigin::root(),
+			RuntimeParameters::Pallet1(pallet1::Parameters::Key1(pallet1::Key1, Some(42))),
+		));
+
+		assert_eq!(<Runtime as pallet_example_basic::Config>::MagicNumber::get(), 42);
+	});
+}
+
+#[test]
+fn into_parts_splits_key_and_value() {
+	let kv = RuntimeParameters::Pallet2(pallet2::Parameters::Key3(pallet2::Key3, Some(7)));
+
+	assert_eq!(
+		kv.into_parts(),
+		(
+			RuntimeParametersKey::Pallet2(pallet2::ParametersKey::Key3(pallet2::Key3)),
+			Some(RuntimeParametersValue::Pallet2(pallet2::ParametersValue::Key3(7))),
+		)
+	);
+}
+
+#[test]
+fn codec_index_works() {
+	use codec::Encode;
+
+	let enc = RuntimeParameters::Pallet1(pallet1::Parameters::Key1(pallet1::Key1, None)).encode();
+	assert_eq!(enc, vec![3, 0, 0]);
+	let enc = RuntimeParameters::Pallet2(pallet2::Parameters::Key1(pallet2::Key1, None)).encode();
+	assert_eq!(enc, vec![1, 2, 0]);
+}
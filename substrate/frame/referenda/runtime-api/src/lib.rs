@@ -0,0 +1,36 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for referenda pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_referenda::{DecisionOutlook, ReferendumIndex};
+
+sp_api::decl_runtime_apis! {
+	/// Runtime api for accessing information about referenda.
+	pub trait ReferendaApi<TrackId, BlockNumber>
+		where
+			TrackId: Codec,
+			BlockNumber: Codec,
+	{
+		/// Returns how the referendum `index` would fare if it were decided now, as measured
+		/// against the curves of its track, or `None` if it is not ongoing.
+		fn decision_outlook(index: ReferendumIndex) -> Option<DecisionOutlook<TrackId, BlockNumber>>;
+	}
+}
@@ -95,10 +95,10 @@ use self::branch::{BeginDecidingBranch, OneFewerDecidingBranch, ServiceBranch};
 pub use self::{
 	pallet::*,
 	types::{
-		BalanceOf, BoundedCallOf, CallOf, Curve, DecidingStatus, DecidingStatusOf, Deposit,
-		InsertSorted, NegativeImbalanceOf, PalletsOriginOf, ReferendumIndex, ReferendumInfo,
-		ReferendumInfoOf, ReferendumStatus, ReferendumStatusOf, ScheduleAddressOf, TallyOf,
-		TrackIdOf, TrackInfo, TrackInfoOf, TracksInfo, VotesOf,
+		BalanceOf, BoundedCallOf, CallOf, Curve, DecidingStatus, DecidingStatusOf, DecisionOutlook,
+		DecisionOutlookOf, Deposit, InsertSorted, NegativeImbalanceOf, PalletsOriginOf,
+		ReferendumIndex, ReferendumInfo, ReferendumInfoOf, ReferendumStatus, ReferendumStatusOf,
+		ScheduleAddressOf, TallyOf, TrackIdOf, TrackInfo, TrackInfoOf, TracksInfo, VotesOf,
 	},
 	weights::WeightInfo,
 };
@@ -865,6 +865,38 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 	}
 
+	/// Returns how the referendum `index` would fare if it were decided now, or `None` if it is
+	/// not ongoing or its track does not exist.
+	pub fn decision_outlook(index: ReferendumIndex) -> Option<DecisionOutlookOf<T, I>> {
+		let status = Self::ensure_ongoing(index).ok()?;
+		let track = T::Tracks::info(status.track)?;
+		let elapsed = if let Some(deciding) = &status.deciding {
+			frame_system::Pallet::<T>::block_number().saturating_sub(deciding.since)
+		} else {
+			Zero::zero()
+		};
+		let x = Perbill::from_rational(elapsed.min(track.decision_period), track.decision_period);
+		let approval = status.tally.approval(status.track);
+		let support = status.tally.support(status.track);
+		let min_approval = track.min_approval.threshold(x);
+		let min_support = track.min_support.threshold(x);
+		let decision_time = status
+			.deciding
+			.as_ref()
+			.map(|deciding| Self::decision_time(deciding, &status.tally, status.track, track));
+		Some(DecisionOutlook {
+			track: status.track,
+			deciding: status.deciding,
+			elapsed,
+			approval,
+			min_approval,
+			support,
+			min_support,
+			passing: approval >= min_approval && support >= min_support,
+			decision_time,
+		})
+	}
+
 	// Enqueue a proposal from a referendum which has presumably passed.
 	fn schedule_enactment(
 		index: ReferendumIndex,
@@ -303,6 +303,61 @@ fn decision_time_is_correct() {
 	});
 }
 
+#[test]
+fn decision_outlook_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_eq!(Referenda::decision_outlook(0), None);
+		assert_ok!(Referenda::submit(
+			RuntimeOrigin::signed(1),
+			Box::new(RawOrigin::Root.into()),
+			set_balance_proposal_bounded(1),
+			DispatchTime::At(10),
+		));
+		// Not yet deciding: the curves are taken at the start of the decision period.
+		assert_eq!(
+			Referenda::decision_outlook(0),
+			Some(DecisionOutlook {
+				track: 0,
+				deciding: None,
+				elapsed: 0,
+				approval: Perbill::zero(),
+				min_approval: Perbill::one(),
+				support: Perbill::zero(),
+				min_support: Perbill::one(),
+				passing: false,
+				decision_time: None,
+			})
+		);
+
+		assert_ok!(Referenda::place_decision_deposit(RuntimeOrigin::signed(2), 0));
+		run_to(6);
+		set_tally(0, 60, 40);
+		// A quarter into the decision period, with neither curve passing.
+		assert_eq!(
+			Referenda::decision_outlook(0),
+			Some(DecisionOutlook {
+				track: 0,
+				deciding: Some(DecidingStatus { since: 5, confirming: None }),
+				elapsed: 1,
+				approval: Perbill::from_percent(60),
+				min_approval: Perbill::from_parts(875_000_000),
+				support: Perbill::from_percent(60),
+				min_support: Perbill::from_percent(75),
+				passing: false,
+				decision_time: Some(9),
+			})
+		);
+
+		set_tally(0, 100, 0);
+		assert!(Referenda::decision_outlook(0).unwrap().passing);
+		run_to(7);
+		assert_eq!(Referenda::decision_outlook(0).unwrap().decision_time, Some(9));
+		run_to(9);
+		assert_eq!(approved_since(0), 9);
+		assert_eq!(Referenda::decision_outlook(0), None);
+	});
+}
+
 #[test]
 fn auto_timeout_should_happen_with_nothing_but_submit() {
 	ExtBuilder::default().build_and_execute(|| {
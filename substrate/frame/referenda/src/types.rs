@@ -61,6 +61,7 @@ pub type ReferendumStatusOf<T, I> = ReferendumStatus<
 	ScheduleAddressOf<T, I>,
 >;
 pub type DecidingStatusOf<T> = DecidingStatus<BlockNumberFor<T>>;
+pub type DecisionOutlookOf<T, I> = DecisionOutlook<TrackIdOf<T, I>, BlockNumberFor<T>>;
 pub type TrackInfoOf<T, I = ()> = TrackInfo<BalanceOf<T, I>, BlockNumberFor<T>>;
 pub type TrackIdOf<T, I> =
 	<<T as Config<I>>::Tracks as TracksInfo<BalanceOf<T, I>, BlockNumberFor<T>>>::Id;
@@ -193,6 +194,31 @@ pub struct ReferendumStatus<
 	pub alarm: Option<(Moment, ScheduleAddress)>,
 }
 
+/// A projection of how an ongoing referendum would fare if it were decided now, as measured
+/// against the curves of its track.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DecisionOutlook<TrackId, Moment> {
+	/// The track of the referendum.
+	pub track: TrackId,
+	/// The status of the decision being made. If `None`, it has not entered the deciding period.
+	pub deciding: Option<DecidingStatus<Moment>>,
+	/// The time elapsed since the decision period began.
+	pub elapsed: Moment,
+	/// The current approval of the referendum.
+	pub approval: Perbill,
+	/// The approval needed for the referendum to pass at this point of the decision period.
+	pub min_approval: Perbill,
+	/// The current support of the referendum.
+	pub support: Perbill,
+	/// The support needed for the referendum to pass at this point of the decision period.
+	pub min_support: Perbill,
+	/// Whether both the approval and support criteria are currently met.
+	pub passing: bool,
+	/// The block at which the referendum would be decided with the current tally, if deciding.
+	/// This is the end of the confirmation period if it is confirming.
+	pub decision_time: Option<Moment>,
+}
+
 /// Info regarding a referendum, present or past.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum ReferendumInfo<
@@ -239,7 +265,8 @@ impl<
 		Tally: Eq + PartialEq + Debug + Encode + Decode + TypeInfo + Clone,
 		AccountId: Eq + PartialEq + Debug + Encode + Decode + TypeInfo + Clone,
 		ScheduleAddress: Eq + PartialEq + Debug + Encode + Decode + TypeInfo + Clone,
-	> ReferendumInfo<TrackId, RuntimeOrigin, Moment, Call, Balance, Tally, AccountId, ScheduleAddress>
+	>
+	ReferendumInfo<TrackId, RuntimeOrigin, Moment, Call, Balance, Tally, AccountId, ScheduleAddress>
 {
 	/// Take the Decision Deposit from `self`, if there is one. Returns an `Err` if `self` is not
 	/// in a valid state for the Decision Deposit to be refunded.
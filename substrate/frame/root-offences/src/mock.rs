@@ -24,7 +24,7 @@ use frame_election_provider_support::{
 };
 use frame_support::{
 	derive_impl, parameter_types,
-	traits::{ConstU32, ConstU64, Hooks, OneSessionHandler},
+	traits::{ConstU32, ConstU64, Get, Hooks, OneSessionHandler},
 };
 use pallet_staking::StakerStatus;
 use sp_core::H256;
@@ -123,7 +123,7 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
-	type RuntimeHoldReason = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type RuntimeFreezeReason = ();
 }
 
@@ -170,6 +170,7 @@ impl pallet_staking::Config for Test {
 	type CurrencyToVote = ();
 	type RewardRemainder = ();
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type Slash = ();
 	type Reward = ();
 	type SessionsPerEra = SessionsPerEra;
@@ -249,6 +250,7 @@ impl Default for ExtBuilder {
 impl ExtBuilder {
 	fn build(self) -> sp_io::TestExternalities {
 		let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		let ed = <Test as pallet_balances::Config>::ExistentialDeposit::get();
 
 		pallet_balances::GenesisConfig::<Test> {
 			balances: vec![
@@ -257,11 +259,12 @@ impl ExtBuilder {
 				(20, self.balance_factor * 50),
 				(30, self.balance_factor * 50),
 				(40, self.balance_factor * 50),
-				// stashes
-				(11, self.balance_factor * 1000),
-				(21, self.balance_factor * 1000),
-				(31, self.balance_factor * 500),
-				(41, self.balance_factor * 1000),
+				// stashes, with an existential deposit on top of their stake, as held funds do not
+				// count towards it.
+				(11, self.balance_factor * 1000 + ed),
+				(21, self.balance_factor * 1000 + ed),
+				(31, self.balance_factor * 500 + ed),
+				(41, self.balance_factor * 1000 + ed),
 			],
 		}
 		.assimilate_storage(&mut storage)
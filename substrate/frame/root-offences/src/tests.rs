@@ -17,7 +17,8 @@
 
 use super::*;
 use frame_support::{assert_err, assert_ok};
-use mock::{active_era, start_session, Balances, ExtBuilder, RootOffences, RuntimeOrigin, System};
+use mock::{active_era, start_session, ExtBuilder, RootOffences, RuntimeOrigin, System, Test};
+use pallet_staking::asset;
 
 #[test]
 fn create_offence_fails_given_signed_origin() {
@@ -35,18 +36,18 @@ fn create_offence_works_given_root_origin() {
 
 		assert_eq!(active_era(), 0);
 
-		assert_eq!(Balances::free_balance(11), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 1000);
 
 		let offenders = [(11, Perbill::from_percent(50))].to_vec();
 		assert_ok!(RootOffences::create_offence(RuntimeOrigin::root(), offenders.clone()));
 
 		System::assert_last_event(Event::OffenceCreated { offenders }.into());
 		// the slash should be applied right away.
-		assert_eq!(Balances::free_balance(11), 500);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 500);
 
 		// the other validator should keep their balance, because we only created
 		// an offences for the first validator.
-		assert_eq!(Balances::free_balance(21), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&21), 1000);
 	})
 }
 
@@ -58,7 +59,7 @@ fn create_offence_wont_slash_non_active_validators() {
 		assert_eq!(active_era(), 0);
 
 		// 31 is not an active validator.
-		assert_eq!(Balances::free_balance(31), 500);
+		assert_eq!(asset::stakeable_balance::<Test>(&31), 500);
 
 		let offenders = [(31, Perbill::from_percent(20)), (11, Perbill::from_percent(20))].to_vec();
 		assert_ok!(RootOffences::create_offence(RuntimeOrigin::root(), offenders.clone()));
@@ -66,10 +67,10 @@ fn create_offence_wont_slash_non_active_validators() {
 		System::assert_last_event(Event::OffenceCreated { offenders }.into());
 
 		// so 31 didn't get slashed.
-		assert_eq!(Balances::free_balance(31), 500);
+		assert_eq!(asset::stakeable_balance::<Test>(&31), 500);
 
 		// but 11 is an active validator so they got slashed.
-		assert_eq!(Balances::free_balance(11), 800);
+		assert_eq!(asset::stakeable_balance::<Test>(&11), 800);
 	})
 }
 
@@ -81,7 +82,7 @@ fn create_offence_wont_slash_idle() {
 		assert_eq!(active_era(), 0);
 
 		// 41 is idle.
-		assert_eq!(Balances::free_balance(41), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&41), 1000);
 
 		let offenders = [(41, Perbill::from_percent(50))].to_vec();
 		assert_ok!(RootOffences::create_offence(RuntimeOrigin::root(), offenders.clone()));
@@ -89,6 +90,6 @@ fn create_offence_wont_slash_idle() {
 		System::assert_last_event(Event::OffenceCreated { offenders }.into());
 
 		// 41 didn't get slashed.
-		assert_eq!(Balances::free_balance(41), 1000);
+		assert_eq!(asset::stakeable_balance::<Test>(&41), 1000);
 	})
 }
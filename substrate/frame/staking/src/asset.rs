@@ -0,0 +1,110 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Contains all the interactions with [`Config::Currency`] to manipulate the stake of accounts.
+//!
+//! All changes to the funds backing a [`crate::StakingLedger`] go through this module. Stake is
+//! secured with a `fungible` hold of reason [`HoldReason::Staking`]. Ledgers which were bonded
+//! while staking still used a lock with [`crate::STAKING_ID`] are moved over by
+//! [`crate::migrations::v15::MigrateLocksToHolds`].
+//!
+//! Held funds do not count towards the existential deposit, so an account always keeps at least
+//! the existential deposit free next to its stake.
+
+use crate::{BalanceOf, Config, HoldReason, NegativeImbalanceOf};
+use frame_support::traits::{
+	fungible::{Inspect, InspectHold, MutateHold},
+	tokens::{Fortitude, Precision, Preservation},
+	Currency, Imbalance,
+};
+use sp_runtime::{DispatchResult, Saturating};
+
+/// Existential deposit of [`Config::Currency`].
+pub fn existential_deposit<T: Config>() -> BalanceOf<T> {
+	<T::Currency as Inspect<T::AccountId>>::minimum_balance()
+}
+
+/// Total issuance of [`Config::Currency`].
+pub fn total_issuance<T: Config>() -> BalanceOf<T> {
+	<T::Currency as Inspect<T::AccountId>>::total_issuance()
+}
+
+/// Total balance of `who`, including what is staked.
+pub fn total_balance<T: Config>(who: &T::AccountId) -> BalanceOf<T> {
+	<T::Currency as Inspect<T::AccountId>>::total_balance(who)
+}
+
+/// The balance of `who` which is currently staked.
+pub fn staked<T: Config>(who: &T::AccountId) -> BalanceOf<T> {
+	T::Currency::balance_on_hold(&HoldReason::Staking.into(), who)
+}
+
+/// The balance of `who` which is free and can still be staked.
+pub fn free_to_stake<T: Config>(who: &T::AccountId) -> BalanceOf<T> {
+	T::Currency::reducible_balance(who, Preservation::Preserve, Fortitude::Force)
+}
+
+/// The balance of `who` which can be staked, including what is already staked.
+pub fn stakeable_balance<T: Config>(who: &T::AccountId) -> BalanceOf<T> {
+	free_to_stake::<T>(who).saturating_add(staked::<T>(who))
+}
+
+/// Secure `amount` of the funds of `who` as stake, replacing any previous stake.
+///
+/// Fails if `who` does not have `amount` of [`stakeable_balance`].
+pub fn update_stake<T: Config>(who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+	T::Currency::set_on_hold(&HoldReason::Staking.into(), who, amount)
+}
+
+/// Release all the staked funds of `who`.
+pub fn kill_stake<T: Config>(who: &T::AccountId) -> DispatchResult {
+	T::Currency::release_all(&HoldReason::Staking.into(), who, Precision::BestEffort).map(|_| ())
+}
+
+/// Slash up to `value` of the stake of `who`.
+///
+/// Returns the slashed imbalance and the amount which could not be slashed.
+pub fn slash<T: Config>(
+	who: &T::AccountId,
+	value: BalanceOf<T>,
+) -> (NegativeImbalanceOf<T>, BalanceOf<T>) {
+	// release the stake first, so that the slash is taken from the stake and not from the rest of
+	// the free balance or from funds held for other reasons.
+	let released =
+		T::Currency::release(&HoldReason::Staking.into(), who, value, Precision::BestEffort)
+			.unwrap_or_default();
+	let (imbalance, _) = <T::Currency as Currency<T::AccountId>>::slash(who, released);
+	let missing = value.saturating_sub(imbalance.peek());
+	(imbalance, missing)
+}
+
+/// Set the stakeable balance of `who` to `value`.
+///
+/// If `value` is less than what is staked, the stake is reduced to `value`. The existential
+/// deposit is always kept free on top of `value`.
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+pub fn set_stakeable_balance<T: Config>(who: &T::AccountId, value: BalanceOf<T>) {
+	let ed = existential_deposit::<T>();
+	let staked = staked::<T>(who);
+	let free = if value > staked {
+		value - staked + ed
+	} else {
+		update_stake::<T>(who, value).expect("can release what is staked; qed");
+		ed
+	};
+	let _ = <T::Currency as Currency<T::AccountId>>::make_free_balance_be(who, free);
+}
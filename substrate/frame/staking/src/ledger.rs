@@ -25,21 +25,19 @@
 //!   instances of [`StakingLedger`] keyed by the staker's controller account and should be mutated
 //!   and read through the [`StakingLedger`] API;
 //! * [`Payee`]: mutates and reads the reward destination preferences for a bonded stash.
-//! * Staking locks: mutates the locks for staking.
+//! * Staking holds: mutates the funds held for staking.
 //!
 //! NOTE: All the storage operations related to the staking ledger (both reads and writes) *MUST* be
 //! performed through the methods exposed by the [`StakingLedger`] implementation in order to ensure
 //! state consistency.
 
-use frame_support::{
-	defensive,
-	traits::{LockableCurrency, WithdrawReasons},
-};
+use frame_support::defensive;
 use sp_staking::StakingAccount;
 use sp_std::prelude::*;
 
 use crate::{
-	BalanceOf, Bonded, Config, Error, Ledger, Payee, RewardDestination, StakingLedger, STAKING_ID,
+	asset, BalanceOf, Bonded, Config, Error, Ledger, Pallet, Payee, RewardDestination,
+	StakingLedger, VirtualStakers,
 };
 
 #[cfg(any(feature = "runtime-benchmarks", test))]
@@ -156,17 +154,22 @@ impl<T: Config> StakingLedger<T> {
 
 	/// Inserts/updates a staking ledger account.
 	///
-	/// Bonds the ledger if it is not bonded yet, signalling that this is a new ledger. The staking
-	/// locks of the stash account are updated accordingly.
+	/// Bonds the ledger if it is not bonded yet, signalling that this is a new ledger. The stake
+	/// held from the stash account is updated accordingly.
 	///
-	/// Note: To ensure lock consistency, all the [`Ledger`] storage updates should be made through
+	/// Note: To ensure hold consistency, all the [`Ledger`] storage updates should be made through
 	/// this helper function.
 	pub(crate) fn update(self) -> Result<(), Error<T>> {
 		if !<Bonded<T>>::contains_key(&self.stash) {
 			return Err(Error::<T>::NotStash)
 		}
 
-		T::Currency::set_lock(STAKING_ID, &self.stash, self.total, WithdrawReasons::all());
+		// Virtual stakers are managed by other pallets, which are responsible for keeping their
+		// funds in place.
+		if !Pallet::<T>::is_virtual_staker(&self.stash) {
+			asset::update_stake::<T>(&self.stash, self.total)
+				.map_err(|_| Error::<T>::NotEnoughFunds)?;
+		}
 		Ledger::<T>::insert(
 			&self.controller().ok_or_else(|| {
 				defensive!("update called on a ledger that is not bonded.");
@@ -202,16 +205,17 @@ impl<T: Config> StakingLedger<T> {
 	}
 
 	/// Clears all data related to a staking ledger and its bond in both [`Ledger`] and [`Bonded`]
-	/// storage items and updates the stash staking lock.
+	/// storage items and releases the stake of the stash.
 	pub(crate) fn kill(stash: &T::AccountId) -> Result<(), Error<T>> {
 		let controller = <Bonded<T>>::get(stash).ok_or(Error::<T>::NotStash)?;
 
 		<Ledger<T>>::get(&controller).ok_or(Error::<T>::NotController).map(|ledger| {
-			T::Currency::remove_lock(STAKING_ID, &ledger.stash);
+			asset::kill_stake::<T>(&ledger.stash).map_err(|_| Error::<T>::BadState)?;
 			Ledger::<T>::remove(controller);
 
 			<Bonded<T>>::remove(&stash);
 			<Payee<T>>::remove(&stash);
+			<VirtualStakers<T>>::remove(&stash);
 
 			Ok(())
 		})?
@@ -289,6 +289,7 @@ pub(crate) mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod asset;
 pub mod election_size_tracker;
 pub mod inflation;
 pub mod ledger;
@@ -324,6 +325,7 @@ pub use weights::WeightInfo;
 
 pub use pallet::{pallet::*, UseNominatorsAndValidatorsMap, UseValidatorsMap};
 
+/// The lock which secured the stake before it was moved to holds, see [`migrations::v15`].
 pub(crate) const STAKING_ID: LockIdentifier = *b"staking ";
 pub(crate) const LOG_TARGET: &str = "runtime::staking";
 
@@ -435,7 +437,7 @@ pub struct UnlockChunk<Balance: HasCompact + MaxEncodedLen> {
 ///
 /// Note: All the reads and mutations to the [`Ledger`], [`Bonded`] and [`Payee`] storage items
 /// *MUST* be performed through the methods exposed by this struct, to ensure the consistency of
-/// ledger's data and corresponding staking lock
+/// ledger's data and corresponding staking hold
 ///
 /// TODO: move struct definition and full implementation into `/src/ledger.rs`. Currently
 /// leaving here to enforce a clean PR diff, given how critical this logic is. Tracking issue
@@ -452,7 +454,7 @@ pub struct UnlockChunk<Balance: HasCompact + MaxEncodedLen> {
 )]
 #[scale_info(skip_type_params(T))]
 pub struct StakingLedger<T: Config> {
-	/// The stash account whose balance is actually locked and at stake.
+	/// The stash account whose balance is actually held and at stake.
 	pub stash: T::AccountId,
 
 	/// The total amount of the stash's balance that we are currently accounting for.
@@ -544,6 +546,28 @@ impl<T: Config> StakingLedger<T> {
 		(self, unlocking_balance)
 	}
 
+	/// Remove `value` from the ledger, e.g. because it is not backed by the stash anymore.
+	///
+	/// The value is removed from the active balance first, and then from the unlocking chunks
+	/// which are unlocked last.
+	pub(crate) fn remove_unheld(&mut self, value: BalanceOf<T>) {
+		let from_active = value.min(self.active);
+		self.active -= from_active;
+		let mut remaining = value - from_active;
+
+		while !remaining.is_zero() {
+			let Some(last) = self.unlocking.last_mut() else { break };
+			let from_chunk = remaining.min(last.value);
+			last.value -= from_chunk;
+			remaining -= from_chunk;
+			if last.value.is_zero() {
+				self.unlocking.pop();
+			}
+		}
+
+		self.total = self.total.saturating_sub(value.saturating_sub(remaining));
+	}
+
 	/// Slash the staker for a given amount of balance.
 	///
 	/// This implements a proportional slashing system, whereby we set our preference to slash as
@@ -950,6 +974,46 @@ impl Default for Forcing {
 	}
 }
 
+/// Progress of the paged voter snapshot, as requested by the election provider through
+/// [`frame_election_provider_support::ElectionDataProvider::electing_voters_paged`].
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum SnapshotStatus<AccountId> {
+	/// No paged snapshot is being taken. The next page starts from the first voter.
+	Waiting,
+	/// The snapshot is ongoing, the next page starts right after the given voter.
+	Ongoing(AccountId),
+	/// All the voters have been returned, the remaining pages are empty.
+	Consumed,
+}
+
+impl<AccountId> Default for SnapshotStatus<AccountId> {
+	fn default() -> Self {
+		SnapshotStatus::Waiting
+	}
+}
+
+/// Progress of fetching the pages of a paged election result for the next era.
+///
+/// The pages are fetched one per block, starting [`ElectionProviderBase::Pages`] blocks before the
+/// next era is planned.
+///
+/// [`ElectionProviderBase::Pages`]: frame_election_provider_support::ElectionProviderBase::Pages
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum PagedElectionStatus {
+	/// No page was fetched for the next era yet.
+	Waiting,
+	/// Pages are being fetched, the given page is the next one.
+	Ongoing(frame_election_provider_support::PageIndex),
+	/// All the pages were fetched, waiting for the next era to be planned.
+	Fetched,
+}
+
+impl Default for PagedElectionStatus {
+	fn default() -> Self {
+		PagedElectionStatus::Waiting
+	}
+}
+
 /// A `Convert` implementation that finds the stash of the given controller account,
 /// if any.
 pub struct StashOf<T>(sp_std::marker::PhantomData<T>);
@@ -1192,6 +1256,48 @@ impl<T: Config> EraInfo<T> {
 		});
 	}
 
+	/// Add `exposure` to the exposure of an elected validator in the era.
+	///
+	/// Used for paged elections, where the backing of a validator may be spread over multiple
+	/// pages of the election result. The nominators of `exposure` are appended to the last page of
+	/// the existing exposure, and new pages are created once it is full. If the validator has no
+	/// exposure in the era yet, this is the same as [`Self::set_exposure`].
+	pub(crate) fn upsert_exposure(
+		era: EraIndex,
+		validator: &T::AccountId,
+		exposure: Exposure<T::AccountId, BalanceOf<T>>,
+	) {
+		let Some(mut overview) = <ErasStakersOverview<T>>::get(era, validator) else {
+			return Self::set_exposure(era, validator, exposure)
+		};
+		let page_size = T::MaxExposurePageSize::get().defensive_max(1);
+
+		overview.total.saturating_accrue(exposure.total);
+		overview.own.saturating_accrue(exposure.own);
+		overview.nominator_count.saturating_accrue(exposure.others.len() as u32);
+
+		let mut others = exposure.others;
+		if let Some(last_page) = overview.page_count.checked_sub(1) {
+			let mut exposure_page = <ErasStakersPaged<T>>::get((era, validator, last_page))
+				.defensive_unwrap_or_default();
+			let room = (page_size as usize).saturating_sub(exposure_page.others.len());
+			for individual in others.drain(..room.min(others.len())) {
+				exposure_page.page_total.saturating_accrue(individual.value);
+				exposure_page.others.push(individual);
+			}
+			<ErasStakersPaged<T>>::insert((era, validator, last_page), exposure_page);
+		}
+
+		let (_, exposure_pages) =
+			Exposure { total: Zero::zero(), own: Zero::zero(), others }.into_pages(page_size);
+		for exposure_page in exposure_pages {
+			<ErasStakersPaged<T>>::insert((era, validator, overview.page_count), exposure_page);
+			overview.page_count.saturating_inc();
+		}
+
+		<ErasStakersOverview<T>>::insert(era, validator, overview);
+	}
+
 	/// Store total exposure for all the elected validators in the era.
 	pub(crate) fn set_total_stake(era: EraIndex, total_stake: BalanceOf<T>) {
 		<ErasTotalStake<T>>::insert(era, total_stake);
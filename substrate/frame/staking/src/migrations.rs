@@ -59,6 +59,127 @@ impl Default for ObsoleteReleases {
 #[storage_alias]
 type StorageVersion<T: Config> = StorageValue<Pallet<T>, ObsoleteReleases, ValueQuery>;
 
+/// Multi-block migration of the funds backing the staking ledgers from the staking lock to a
+/// hold of [`HoldReason::Staking`].
+/// Changelog: [v15.](https://github.com/paritytech/polkadot-sdk/blob/master/substrate/frame/staking/CHANGELOG.md#v15)
+pub mod v15 {
+	use super::*;
+	use frame_support::{
+		migrations::{migrate_lock_to_hold, SteppedMigration, SteppedMigrationError},
+		traits::StorageVersion as PalletStorageVersion,
+		weights::WeightMeter,
+	};
+
+	/// Migrates the stake of each [`Ledger`] from the [`STAKING_ID`] lock to a hold.
+	///
+	/// Virtual stakers are skipped, since their funds are managed by other pallets. Any part of a
+	/// ledger which cannot be put on hold, because it is needed to keep the stash alive, is
+	/// removed from the ledger and stays free in the stash.
+	///
+	/// Sets the on-chain storage version to 15 once all ledgers are migrated.
+	pub struct MigrateLocksToHolds<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> MigrateLocksToHolds<T> {
+		/// The weight to migrate a single ledger.
+		pub fn weight_per_ledger() -> Weight {
+			// ledger, virtual stakers, locks, account and holds.
+			T::DbWeight::get().reads_writes(5, 4)
+		}
+
+		/// Migrate the stake of the ledger of `controller` to a hold.
+		fn migrate_ledger(controller: &T::AccountId, mut ledger: StakingLedger<T>) {
+			let stash = ledger.stash.clone();
+			if Pallet::<T>::is_virtual_staker(&stash) {
+				return
+			}
+
+			// the stake may already be partially held, if the ledger was updated after the
+			// upgrade but before this migration reached it.
+			let to_hold = ledger.total.saturating_sub(asset::staked::<T>(&stash));
+			match migrate_lock_to_hold::<_, T::Currency, T::Currency>(
+				STAKING_ID,
+				&HoldReason::Staking.into(),
+				&stash,
+				to_hold,
+			) {
+				Ok(outcome) if outcome.dust.is_zero() => (),
+				Ok(outcome) => {
+					log!(
+						warn,
+						"could not hold {:?} of the stake of {:?}, removing it from the ledger.",
+						outcome.dust,
+						stash,
+					);
+					ledger.remove_unheld(outcome.dust);
+					Ledger::<T>::insert(controller, ledger);
+				},
+				Err(err) => {
+					log!(error, "failed to migrate the stake of {:?} to a hold: {:?}", stash, err);
+				},
+			}
+		}
+	}
+
+	impl<T: Config> SteppedMigration for MigrateLocksToHolds<T> {
+		type Cursor = T::AccountId;
+		type Identifier = [u8; 26];
+
+		fn id() -> Self::Identifier {
+			*b"staking-locks-to-holds-v15"
+		}
+
+		fn step(
+			cursor: Option<Self::Cursor>,
+			meter: &mut WeightMeter,
+		) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+			if Pallet::<T>::on_chain_storage_version() >= 15 {
+				return Ok(None)
+			}
+
+			let required = Self::weight_per_ledger();
+			if !meter.can_consume(required) {
+				return Err(SteppedMigrationError::InsufficientWeight { required })
+			}
+
+			let mut iter = match cursor {
+				Some(last) => Ledger::<T>::iter_from(Ledger::<T>::hashed_key_for(last)),
+				None => Ledger::<T>::iter(),
+			};
+			let mut last = None;
+			while meter.try_consume(required).is_ok() {
+				match iter.next() {
+					Some((controller, ledger)) => {
+						Self::migrate_ledger(&controller, ledger);
+						last = Some(controller);
+					},
+					None => {
+						PalletStorageVersion::new(15).put::<Pallet<T>>();
+						log!(info, "v15 applied successfully.");
+						return Ok(None)
+					},
+				}
+			}
+
+			Ok(last)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 15, "v15 not applied");
+			for (_, ledger) in Ledger::<T>::iter() {
+				if Pallet::<T>::is_virtual_staker(&ledger.stash) {
+					continue
+				}
+				ensure!(
+					asset::staked::<T>(&ledger.stash) == ledger.total,
+					"the stake of a ledger is not on hold"
+				);
+			}
+			Ok(())
+		}
+	}
+}
+
 /// Migration of era exposure storage items to paged exposures.
 /// Changelog: [v14.](https://github.com/paritytech/substrate/blob/ankan/paged-rewards-rebased2/frame/staking/CHANGELOG.md#14)
 pub mod v14 {
@@ -20,7 +20,8 @@
 use crate::{self as pallet_staking, *};
 use frame_election_provider_support::{
 	bounds::{ElectionBounds, ElectionBoundsBuilder},
-	onchain, SequentialPhragmen, VoteWeight,
+	onchain, BoundedSupportsOf, ElectionProvider, ElectionProviderBase, PageIndex,
+	SequentialPhragmen, Support, VoteWeight,
 };
 use frame_support::{
 	assert_ok, derive_impl, ord_parameter_types, parameter_types,
@@ -163,7 +164,7 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
-	type RuntimeHoldReason = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type RuntimeFreezeReason = ();
 }
 
@@ -265,6 +266,52 @@ impl onchain::Config for OnChainSeqPhragmen {
 	type Bounds = ElectionsBounds;
 }
 
+parameter_types! {
+	pub static ElectionPages: PageIndex = 1;
+	pub static ElectedPages: Vec<(BlockNumber, PageIndex)> = vec![];
+}
+
+/// Splits the result of [`OnChainSeqPhragmen`] into [`ElectionPages`] pages, by dealing the
+/// voters of each winner over the pages. Records the block at which each page is requested in
+/// [`ElectedPages`].
+pub struct PagedOnChain;
+impl ElectionProviderBase for PagedOnChain {
+	type AccountId = AccountId;
+	type BlockNumber = BlockNumber;
+	type Error = onchain::Error;
+	type MaxWinners = MaxWinners;
+	type Pages = ElectionPages;
+	type DataProvider = Staking;
+}
+
+impl ElectionProvider for PagedOnChain {
+	fn ongoing() -> bool {
+		false
+	}
+
+	fn elect(page: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		ElectedPages::mutate(|pages| pages.push((System::block_number(), page)));
+
+		let pages = ElectionPages::get().max(1) as usize;
+		let supports = onchain::OnChainExecution::<OnChainSeqPhragmen>::elect(0)?
+			.into_iter()
+			.filter_map(|(winner, support)| {
+				let voters = support
+					.voters
+					.into_iter()
+					.enumerate()
+					.filter(|(index, _)| index % pages == page as usize)
+					.map(|(_, voter)| voter)
+					.collect::<Vec<_>>();
+				let total = voters.iter().map(|(_, stake)| stake).sum();
+				(!voters.is_empty()).then(|| (winner, Support { total, voters }))
+			})
+			.collect::<Vec<_>>();
+
+		Ok(supports.try_into().expect("a subset of a bounded set of supports; qed"))
+	}
+}
+
 pub struct MockReward {}
 impl OnUnbalanced<PositiveImbalanceOf<Test>> for MockReward {
 	fn on_unbalanced(_: PositiveImbalanceOf<Test>) {
@@ -297,6 +344,7 @@ impl crate::pallet::pallet::Config for Test {
 	type CurrencyToVote = ();
 	type RewardRemainder = RewardRemainderMock;
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type Slash = ();
 	type Reward = MockReward;
 	type SessionsPerEra = SessionsPerEra;
@@ -308,8 +356,8 @@ impl crate::pallet::pallet::Config for Test {
 	type NextNewSession = Session;
 	type MaxExposurePageSize = MaxExposurePageSize;
 	type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
-	type ElectionProvider = onchain::OnChainExecution<OnChainSeqPhragmen>;
-	type GenesisElectionProvider = Self::ElectionProvider;
+	type ElectionProvider = PagedOnChain;
+	type GenesisElectionProvider = onchain::OnChainExecution<OnChainSeqPhragmen>;
 	// NOTE: consider a macro and use `UseNominatorsAndValidatorsMap<Self>` as well.
 	type VoterList = VoterBagsList;
 	type TargetList = UseValidatorsMap<Self>;
@@ -465,6 +513,7 @@ impl ExtBuilder {
 	fn build(self) -> sp_io::TestExternalities {
 		sp_tracing::try_init_simple();
 		let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		let ed = ExistentialDeposit::get();
 
 		let _ = pallet_balances::GenesisConfig::<Test> {
 			balances: vec![
@@ -479,21 +528,24 @@ impl ExtBuilder {
 				(40, self.balance_factor),
 				(50, self.balance_factor),
 				// stashes
-				(11, self.balance_factor * 1000),
-				(21, self.balance_factor * 2000),
-				(31, self.balance_factor * 2000),
-				(41, self.balance_factor * 2000),
-				(51, self.balance_factor * 2000),
+				// Note: stake is held, and held funds do not count towards the existential
+				// deposit. Stakers therefore get an extra existential deposit, so that they can
+				// still stake the rest of their balance.
+				(11, self.balance_factor * 1000 + ed),
+				(21, self.balance_factor * 2000 + ed),
+				(31, self.balance_factor * 2000 + ed),
+				(41, self.balance_factor * 2000 + ed),
+				(51, self.balance_factor * 2000 + ed),
 				// optional nominator
-				(100, self.balance_factor * 2000),
-				(101, self.balance_factor * 2000),
+				(100, self.balance_factor * 2000 + ed),
+				(101, self.balance_factor * 2000 + ed),
 				// aux accounts
 				(60, self.balance_factor),
-				(61, self.balance_factor * 2000),
+				(61, self.balance_factor * 2000 + ed),
 				(70, self.balance_factor),
-				(71, self.balance_factor * 2000),
+				(71, self.balance_factor * 2000 + ed),
 				(80, self.balance_factor),
-				(81, self.balance_factor * 2000),
+				(81, self.balance_factor * 2000 + ed),
 				// This allows us to have a total_payout different from 0.
 				(999, 1_000_000_000_000),
 			],
@@ -606,7 +658,7 @@ pub(crate) fn current_era() -> EraIndex {
 }
 
 pub(crate) fn bond(who: AccountId, val: Balance) {
-	let _ = Balances::make_free_balance_be(&who, val);
+	asset::set_stakeable_balance::<Test>(&who, val);
 	assert_ok!(Staking::bond(RuntimeOrigin::signed(who), val, RewardDestination::Stash));
 }
 
@@ -625,6 +677,21 @@ pub(crate) fn bond_nominator(who: AccountId, val: Balance, target: Vec<AccountId
 	assert_ok!(Staking::nominate(RuntimeOrigin::signed(who), target));
 }
 
+pub(crate) fn bond_virtual_nominator(
+	who: AccountId,
+	payee: AccountId,
+	val: Balance,
+	target: Vec<AccountId>,
+) {
+	// In a real scenario, `who` is a keyless account managed by another pallet which provides for
+	// it.
+	System::inc_providers(&who);
+
+	// Bond who virtually.
+	assert_ok!(<Staking as sp_staking::StakingUnchecked>::virtual_bond(&who, val, &payee));
+	assert_ok!(Staking::nominate(RuntimeOrigin::signed(who), target));
+}
+
 /// Progress to the given block, triggering session and era changes as we progress.
 ///
 /// This will finalize the previous block, initialize up to the given block, essentially simulating
@@ -860,6 +927,9 @@ pub(crate) fn staking_events_since_last_call() -> Vec<crate::Event<Test>> {
 	all.into_iter().skip(seen).collect()
 }
 
+/// The stakeable balance of `who`, and the balance it has reserved or on hold for other reasons
+/// than staking.
 pub(crate) fn balances(who: &AccountId) -> (Balance, Balance) {
-	(Balances::free_balance(who), Balances::reserved_balance(who))
+	let staked = asset::staked::<Test>(who);
+	(asset::stakeable_balance::<Test>(who), Balances::reserved_balance(who) - staked)
 }
@@ -20,7 +20,8 @@
 use frame_election_provider_support::{
 	bounds::{CountBound, SizeBound},
 	data_provider, BoundedSupportsOf, DataProviderBounds, ElectionDataProvider, ElectionProvider,
-	ScoreProvider, SortedListProvider, VoteWeight, VoterOf,
+	ElectionProviderBase, PageIndex, ScoreProvider, SortedListProvider, Support, VoteWeight,
+	VoterOf,
 };
 use frame_support::{
 	defensive,
@@ -35,23 +36,26 @@ use frame_support::{
 use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
 use pallet_session::historical;
 use sp_runtime::{
-	traits::{Bounded, Convert, One, SaturatedConversion, Saturating, StaticLookup, Zero},
+	traits::{
+		Bounded, CheckedSub, Convert, One, SaturatedConversion, Saturating, StaticLookup, Zero,
+	},
 	Perbill,
 };
 use sp_staking::{
 	currency_to_vote::CurrencyToVote,
 	offence::{DisableStrategy, OffenceDetails, OnOffenceHandler},
-	EraIndex, Page, SessionIndex, Stake,
+	EraIndex, OnStakingUpdate, Page, SessionIndex, Stake,
 	StakingAccount::{self, Controller, Stash},
-	StakingInterface,
+	StakingInterface, StakingUnchecked,
 };
-use sp_std::prelude::*;
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 use crate::{
-	election_size_tracker::StaticTracker, log, slashing, weights::WeightInfo, ActiveEraInfo,
+	asset, election_size_tracker::StaticTracker, log, slashing, weights::WeightInfo, ActiveEraInfo,
 	BalanceOf, EraInfo, EraPayout, Exposure, ExposureOf, Forcing, IndividualExposure,
-	MaxNominationsOf, MaxWinnersOf, Nominations, NominationsQuota, PositiveImbalanceOf,
-	RewardDestination, SessionInterface, StakingLedger, ValidatorPrefs,
+	MaxNominationsOf, MaxWinnersOf, Nominations, NominationsQuota, PagedElectionStatus,
+	PositiveImbalanceOf, RewardDestination, SessionInterface, SnapshotStatus, StakingLedger,
+	ValidatorPrefs,
 };
 
 use super::pallet::*;
@@ -69,6 +73,16 @@ use sp_runtime::TryRuntimeError;
 /// times and then give up.
 const NPOS_MAX_ITERATIONS_COEFFICIENT: u32 = 2;
 
+/// How far the voter list was iterated while collecting the voters of the election.
+enum VoterProgress<AccountId> {
+	/// No voter was consumed from the list.
+	Stalled,
+	/// The list was consumed up to and including the given voter.
+	After(AccountId),
+	/// The list was fully consumed.
+	Exhausted,
+}
+
 impl<T: Config> Pallet<T> {
 	/// Fetches the ledger associated with a controller or stash account, if any.
 	pub fn ledger(account: StakingAccount<T::AccountId>) -> Result<StakingLedger<T>, Error<T>> {
@@ -105,7 +119,7 @@ impl<T: Config> Pallet<T> {
 	pub fn weight_of_fn() -> Box<dyn Fn(&T::AccountId) -> VoteWeight> {
 		// NOTE: changing this to unboxed `impl Fn(..)` return type and the pallet will still
 		// compile, while some types in mock fail to resolve.
-		let issuance = T::Currency::total_issuance();
+		let issuance = asset::total_issuance::<T>();
 		Box::new(move |who: &T::AccountId| -> VoteWeight {
 			Self::slashable_balance_of_vote_weight(who, issuance)
 		})
@@ -113,10 +127,42 @@ impl<T: Config> Pallet<T> {
 
 	/// Same as `weight_of_fn`, but made for one time use.
 	pub fn weight_of(who: &T::AccountId) -> VoteWeight {
-		let issuance = T::Currency::total_issuance();
+		let issuance = asset::total_issuance::<T>();
 		Self::slashable_balance_of_vote_weight(who, issuance)
 	}
 
+	pub(super) fn do_bond_extra(stash: &T::AccountId, additional: BalanceOf<T>) -> DispatchResult {
+		let mut ledger = Self::ledger(StakingAccount::Stash(stash.clone()))?;
+
+		// for virtual stakers, we don't need to check the balance. Since they are only accessed
+		// via low level apis, we can assume that the caller has done the due diligence.
+		let extra = if Self::is_virtual_staker(stash) {
+			additional
+		} else {
+			let stash_balance = asset::stakeable_balance::<T>(stash);
+			match stash_balance.checked_sub(&ledger.total) {
+				Some(extra) => extra.min(additional),
+				None => return Ok(()),
+			}
+		};
+
+		ledger.total += extra;
+		ledger.active += extra;
+		// Last check: the new active amount of ledger must be more than ED.
+		ensure!(ledger.active >= asset::existential_deposit::<T>(), Error::<T>::InsufficientBond);
+
+		// NOTE: ledger must be updated prior to calling `Self::weight_of`.
+		ledger.update()?;
+		// update this staker in the sorted list, if they exist in it.
+		if T::VoterList::contains(stash) {
+			let _ = T::VoterList::on_update(stash, Self::weight_of(stash)).defensive();
+		}
+
+		Self::deposit_event(Event::<T>::Bonded { stash: stash.clone(), amount: extra });
+
+		Ok(())
+	}
+
 	pub(super) fn do_withdraw_unbonded(
 		controller: &T::AccountId,
 		num_slashing_spans: u32,
@@ -129,7 +175,7 @@ impl<T: Config> Pallet<T> {
 		let new_total = ledger.total;
 
 		let used_weight =
-			if ledger.unlocking.is_empty() && ledger.active < T::Currency::minimum_balance() {
+			if ledger.unlocking.is_empty() && ledger.active < asset::existential_deposit::<T>() {
 				// This account must have called `unbond()` with some value that caused the active
 				// portion to fall below existential deposit + will have no more unlocking chunks
 				// left. We can now safely remove all staking-related information.
@@ -149,7 +195,10 @@ impl<T: Config> Pallet<T> {
 		if new_total < old_total {
 			// Already checked that this won't overflow by entry condition.
 			let value = old_total.defensive_saturating_sub(new_total);
-			Self::deposit_event(Event::<T>::Withdrawn { stash, amount: value });
+			Self::deposit_event(Event::<T>::Withdrawn { stash: stash.clone(), amount: value });
+
+			// notify listeners.
+			T::EventListeners::on_withdraw(&stash, value);
 		}
 
 		Ok(used_weight)
@@ -503,7 +552,7 @@ impl<T: Config> Pallet<T> {
 			let era_duration = (now_as_millis_u64.defensive_saturating_sub(active_era_start))
 				.saturated_into::<u64>();
 			let staked = Self::eras_total_stake(&active_era.index);
-			let issuance = T::Currency::total_issuance();
+			let issuance = asset::total_issuance::<T>();
 			let (validator_payout, remainder) =
 				T::EraPayout::era_payout(staked, issuance, era_duration);
 
@@ -537,6 +586,17 @@ impl<T: Config> Pallet<T> {
 			MaxWinnersOf<T>,
 		>,
 	) -> BoundedVec<T::AccountId, MaxWinnersOf<T>> {
+		let new_planned_era = Self::plan_new_era(start_session_index);
+
+		// Set staking information for the new era.
+		Self::store_stakers_info(exposures, new_planned_era)
+	}
+
+	/// Bump the current era, store the start session index of the new planned era and clean old
+	/// era information.
+	///
+	/// Returns the new planned era.
+	fn plan_new_era(start_session_index: SessionIndex) -> EraIndex {
 		// Increment or set current era.
 		let new_planned_era = CurrentEra::<T>::mutate(|s| {
 			*s = Some(s.map(|s| s + 1).unwrap_or(0));
@@ -549,13 +609,13 @@ impl<T: Config> Pallet<T> {
 			Self::clear_era_information(old_era);
 		}
 
-		// Set staking information for the new era.
-		Self::store_stakers_info(exposures, new_planned_era)
+		new_planned_era
 	}
 
 	/// Potentially plan a new era.
 	///
-	/// Get election result from `T::ElectionProvider`.
+	/// Get election result from `T::ElectionProvider`. The pages of the result that were not
+	/// fetched ahead of time by [`Self::maybe_fetch_election_page`] are fetched now.
 	/// In case election result has more than [`MinimumValidatorCount`] validator trigger a new era.
 	///
 	/// In case a new era is planned, the new validator set is returned.
@@ -563,55 +623,262 @@ impl<T: Config> Pallet<T> {
 		start_session_index: SessionIndex,
 		is_genesis: bool,
 	) -> Option<BoundedVec<T::AccountId, MaxWinnersOf<T>>> {
-		let election_result: BoundedVec<_, MaxWinnersOf<T>> = if is_genesis {
-			let result = <T::GenesisElectionProvider>::elect().map_err(|e| {
-				log!(warn, "genesis election provider failed due to {:?}", e);
+		if is_genesis {
+			let result = Self::elect_all_pages::<T::GenesisElectionProvider>().ok_or_else(|| {
+				log!(warn, "genesis election provider failed");
 				Self::deposit_event(Event::StakingElectionFailed);
 			});
 
-			result
+			let election_result: BoundedVec<_, MaxWinnersOf<T>> = result
 				.ok()?
 				.into_inner()
 				.try_into()
 				// both bounds checked in integrity test to be equal
-				.defensive_unwrap_or_default()
+				.defensive_unwrap_or_default();
+
+			let exposures = Self::collect_exposures(election_result);
+			if !Self::has_enough_validators(exposures.len(), start_session_index) {
+				return None
+			}
+
+			Self::deposit_event(Event::StakersElected);
+			return Some(Self::trigger_new_era(start_session_index, exposures))
+		}
+
+		let planning_era = Self::planning_era();
+		let remaining_pages = match ElectionPagesStatus::<T>::take() {
+			PagedElectionStatus::Waiting => Some(T::ElectionProvider::msp()),
+			PagedElectionStatus::Ongoing(page) => Some(page),
+			PagedElectionStatus::Fetched => None,
+		};
+		if let Some(first_page) = remaining_pages {
+			for page in (T::ElectionProvider::lsp()..=first_page).rev() {
+				Self::register_weight(Self::do_elect_paged(page, planning_era));
+			}
+		}
+
+		let elected_stashes = ElectableStashes::<T>::get();
+		if !Self::has_enough_validators(elected_stashes.len(), start_session_index) {
+			Self::clear_paged_election(planning_era);
+			return None
+		}
+		ElectableStashes::<T>::kill();
+
+		Self::deposit_event(Event::StakersElected);
+		let new_planned_era = Self::plan_new_era(start_session_index);
+		debug_assert_eq!(new_planned_era, planning_era);
+
+		if new_planned_era > 0 {
+			log!(
+				info,
+				"new validator set of size {:?} has been processed for era {:?}",
+				elected_stashes.len(),
+				new_planned_era,
+			);
+		}
+
+		Some(
+			elected_stashes
+				.into_iter()
+				.collect::<Vec<_>>()
+				.try_into()
+				.expect("`ElectableStashes` is bounded by `MaxWinnersOf<T>` as well; qed"),
+		)
+	}
+
+	/// Whether `elected` validators are enough to plan a new era.
+	///
+	/// Initializes the first era if there are not enough validators for it.
+	fn has_enough_validators(elected: usize, start_session_index: SessionIndex) -> bool {
+		if (elected as u32) >= Self::minimum_validator_count().max(1) {
+			return true
+		}
+
+		// Session will panic if we ever return an empty validator set, thus max(1) ^^.
+		match CurrentEra::<T>::get() {
+			Some(current_era) if current_era > 0 => log!(
+				warn,
+				"chain does not have enough staking candidates to operate for era {:?} ({} \
+				elected, minimum is {})",
+				CurrentEra::<T>::get().unwrap_or(0),
+				elected,
+				Self::minimum_validator_count(),
+			),
+			None => {
+				// The initial era is allowed to have no exposures.
+				// In this case the SessionManager is expected to choose a sensible validator
+				// set.
+				// TODO: this should be simplified #8911
+				CurrentEra::<T>::put(0);
+				ErasStartSessionIndex::<T>::insert(&0, &start_session_index);
+			},
+			_ => (),
+		}
+
+		Self::deposit_event(Event::StakingElectionFailed);
+		false
+	}
+
+	/// The era that the next election is for.
+	fn planning_era() -> EraIndex {
+		CurrentEra::<T>::get().map(|era| era.saturating_add(1)).unwrap_or_default()
+	}
+
+	/// The number of pages of the election result of `T::ElectionProvider`.
+	fn election_pages() -> PageIndex {
+		<T::ElectionProvider as ElectionProviderBase>::Pages::get()
+	}
+
+	/// Fetch the next page of the election result, if the paged election of the next era is
+	/// ongoing or due to start at `now`.
+	///
+	/// The pages are fetched one per block, from the most significant page at
+	/// [`ElectionDataProvider::next_election_prediction`] down to page `0` in the block right
+	/// before the next era is planned. The exposures of each page are stored in
+	/// [`ErasStakersPaged`] right away. The result of an election provider with a single page is
+	/// only fetched when the next era is planned.
+	///
+	/// Returns the weight consumed, besides the weight registered by the election provider.
+	pub(crate) fn maybe_fetch_election_page(now: BlockNumberFor<T>) -> Weight {
+		let mut weight = T::DbWeight::get().reads(1);
+		let page = match ElectionPagesStatus::<T>::get() {
+			PagedElectionStatus::Ongoing(page) => page,
+			PagedElectionStatus::Waiting if Self::election_pages() > 1 => {
+				// the reads of `next_election_prediction`.
+				weight.saturating_accrue(T::DbWeight::get().reads(5));
+				if <Self as ElectionDataProvider>::next_election_prediction(now) != now {
+					return weight
+				}
+				T::ElectionProvider::msp()
+			},
+			_ => return weight,
+		};
+
+		weight.saturating_accrue(Self::do_elect_paged(page, Self::planning_era()));
+		let status = if page <= T::ElectionProvider::lsp() {
+			PagedElectionStatus::Fetched
 		} else {
-			let result = <T::ElectionProvider>::elect().map_err(|e| {
-				log!(warn, "election provider failed due to {:?}", e);
-				Self::deposit_event(Event::StakingElectionFailed);
-			});
-			result.ok()?
+			PagedElectionStatus::Ongoing(page.saturating_sub(1))
 		};
+		ElectionPagesStatus::<T>::put(status);
 
-		let exposures = Self::collect_exposures(election_result);
-		if (exposures.len() as u32) < Self::minimum_validator_count().max(1) {
-			// Session will panic if we ever return an empty validator set, thus max(1) ^^.
-			match CurrentEra::<T>::get() {
-				Some(current_era) if current_era > 0 => log!(
-					warn,
-					"chain does not have enough staking candidates to operate for era {:?} ({} \
-					elected, minimum is {})",
-					CurrentEra::<T>::get().unwrap_or(0),
-					exposures.len(),
-					Self::minimum_validator_count(),
-				),
-				None => {
-					// The initial era is allowed to have no exposures.
-					// In this case the SessionManager is expected to choose a sensible validator
-					// set.
-					// TODO: this should be simplified #8911
-					CurrentEra::<T>::put(0);
-					ErasStartSessionIndex::<T>::insert(&0, &start_session_index);
+		weight.saturating_add(T::DbWeight::get().writes(1))
+	}
+
+	/// Fetch `page` of the election result from `T::ElectionProvider`, and store the exposures of
+	/// its winners for `era`.
+	///
+	/// The winners are added to [`ElectableStashes`]. A winner that does not fit in there anymore
+	/// is dropped. If the page cannot be fetched, it is skipped and the new era is planned with the
+	/// validators elected by the other pages, if they are enough.
+	///
+	/// Returns the weight consumed, besides the weight registered by the election provider.
+	pub(crate) fn do_elect_paged(page: PageIndex, era: EraIndex) -> Weight {
+		let supports = match T::ElectionProvider::elect(page) {
+			Ok(supports) => supports,
+			Err(e) => {
+				log!(warn, "election provider failed for page {} due to {:?}", page, e);
+				return Weight::zero()
+			},
+		};
+
+		let page_size = T::MaxExposurePageSize::get().max(1) as u64;
+		let mut electable_stashes = ElectableStashes::<T>::get();
+		let mut page_stake: BalanceOf<T> = Zero::zero();
+		let (mut reads, mut writes) = (2u64, 2u64);
+
+		for (stash, exposure) in Self::collect_exposures(supports) {
+			if !electable_stashes.contains(&stash) {
+				if electable_stashes.try_insert(stash.clone()).is_err() {
+					defensive!("the paged election result has more winners than `MaxWinners`");
+					continue
+				}
+				<ErasValidatorPrefs<T>>::insert(era, &stash, Self::validators(&stash));
+				reads.saturating_inc();
+				writes.saturating_inc();
+			}
+
+			// the overview and the last page are updated, and new pages may be created.
+			reads.saturating_accrue(2);
+			writes.saturating_accrue(2 + exposure.others.len() as u64 / page_size);
+
+			page_stake.saturating_accrue(exposure.total);
+			EraInfo::<T>::upsert_exposure(era, &stash, exposure);
+		}
+
+		<ErasTotalStake<T>>::mutate(era, |total| total.saturating_accrue(page_stake));
+		ElectableStashes::<T>::put(electable_stashes);
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// Remove everything that was stored for `era` by the pages of an election result that did
+	/// not lead to a new era.
+	fn clear_paged_election(era: EraIndex) {
+		ElectableStashes::<T>::kill();
+		ElectionPagesStatus::<T>::kill();
+
+		let mut cursor = <ErasStakersPaged<T>>::clear_prefix((era,), u32::MAX, None);
+		debug_assert!(cursor.maybe_cursor.is_none());
+		cursor = <ErasStakersOverview<T>>::clear_prefix(era, u32::MAX, None);
+		debug_assert!(cursor.maybe_cursor.is_none());
+		cursor = <ErasValidatorPrefs<T>>::clear_prefix(era, u32::MAX, None);
+		debug_assert!(cursor.maybe_cursor.is_none());
+		<ErasTotalStake<T>>::remove(era);
+	}
+
+	/// Request all the pages of the election result from `E` at once, and merge them into a single
+	/// set of supports.
+	///
+	/// Only used for the genesis election, when there are no blocks to fetch the pages over.
+	///
+	/// Pages are requested from the most significant page down to page `0`. The supports of a
+	/// winner that appears in multiple pages are merged together, in the order of the pages.
+	///
+	/// All pages are requested even if some of them fail, so that the election provider can
+	/// conclude the election. Returns `None` if any of the pages failed, or if the merged result
+	/// has more winners than `E::MaxWinners`.
+	pub(crate) fn elect_all_pages<E>() -> Option<BoundedSupportsOf<E>>
+	where
+		E: ElectionProvider<AccountId = T::AccountId>,
+	{
+		let mut failed = false;
+		let mut supports: Vec<(T::AccountId, Support<T::AccountId>)> = Vec::new();
+		let mut index_of: BTreeMap<T::AccountId, usize> = BTreeMap::new();
+
+		for page in (E::lsp()..=E::msp()).rev() {
+			let page_supports = match E::elect(page) {
+				Ok(page_supports) => page_supports,
+				Err(e) => {
+					log!(warn, "election provider failed for page {} due to {:?}", page, e);
+					failed = true;
+					continue
 				},
-				_ => (),
+			};
+
+			for (winner, support) in page_supports.into_iter() {
+				match index_of.get(&winner) {
+					Some(index) => {
+						let existing = &mut supports[*index].1;
+						existing.total = existing.total.saturating_add(support.total);
+						existing.voters.extend(support.voters);
+					},
+					None => {
+						index_of.insert(winner.clone(), supports.len());
+						supports.push((winner, support));
+					},
+				}
 			}
+		}
 
-			Self::deposit_event(Event::StakingElectionFailed);
+		if failed {
 			return None
 		}
 
-		Self::deposit_event(Event::StakersElected);
-		Some(Self::trigger_new_era(start_session_index, exposures))
+		supports
+			.try_into()
+			.map_err(|_| log!(warn, "merged election result has too many winners"))
+			.ok()
 	}
 
 	/// Process the output of the election.
@@ -666,7 +933,7 @@ impl<T: Config> Pallet<T> {
 	fn collect_exposures(
 		supports: BoundedSupportsOf<T::ElectionProvider>,
 	) -> BoundedVec<(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>), MaxWinnersOf<T>> {
-		let total_issuance = T::Currency::total_issuance();
+		let total_issuance = asset::total_issuance::<T>();
 		let to_currency = |e: frame_election_provider_support::ExtendedBalance| {
 			T::CurrencyToVote::to_currency(e, total_issuance)
 		};
@@ -720,6 +987,11 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Whether `who` is a virtual staker whose funds are managed by another pallet.
+	pub(crate) fn is_virtual_staker(who: &T::AccountId) -> bool {
+		VirtualStakers::<T>::contains_key(who)
+	}
+
 	/// Clear all era information for given era.
 	pub(crate) fn clear_era_information(era_index: EraIndex) {
 		// FIXME: We can possibly set a reasonable limit since we do this only once per era and
@@ -818,6 +1090,85 @@ impl<T: Config> Pallet<T> {
 	///
 	/// This function is self-weighing as [`DispatchClass::Mandatory`].
 	pub fn get_npos_voters(bounds: DataProviderBounds) -> Vec<VoterOf<Self>> {
+		let (all_voters, min_active_stake, _) =
+			Self::do_get_npos_voters(bounds, T::VoterList::iter());
+
+		let min_active_stake: T::CurrencyBalance =
+			if all_voters.is_empty() { Zero::zero() } else { min_active_stake.into() };
+
+		MinimumActiveStake::<T>::put(min_active_stake);
+
+		all_voters
+	}
+
+	/// Get the next page of voters for a paged npos election.
+	///
+	/// The page continues from where the previous page stopped, as tracked by
+	/// [`VoterSnapshotStatus`]. Page `0` is the last page of the snapshot, after which the next
+	/// page starts from the first voter again.
+	///
+	/// Sets `MinimumActiveStake` to the minimum active nominator stake across all the pages of
+	/// the snapshot.
+	///
+	/// This function is self-weighing as [`DispatchClass::Mandatory`].
+	pub fn get_npos_voters_paged(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<VoterOf<Self>>> {
+		let status = VoterSnapshotStatus::<T>::get();
+		let sorted_voters = match status {
+			SnapshotStatus::Waiting => Some(T::VoterList::iter()),
+			SnapshotStatus::Ongoing(ref last) =>
+				Some(T::VoterList::iter_from(last).map_err(|_| {
+					VoterSnapshotStatus::<T>::kill();
+					"the last voter of the previous page is no longer a voter"
+				})?),
+			SnapshotStatus::Consumed => None,
+		};
+
+		let (voters, next_status) = match sorted_voters {
+			Some(sorted_voters) => {
+				let (voters, min_active_stake, progress) =
+					Self::do_get_npos_voters(bounds, sorted_voters);
+
+				if status == SnapshotStatus::Waiting {
+					let min_active_stake: T::CurrencyBalance =
+						if voters.is_empty() { Zero::zero() } else { min_active_stake.into() };
+					MinimumActiveStake::<T>::put(min_active_stake);
+				} else if min_active_stake != VoteWeight::MAX {
+					MinimumActiveStake::<T>::mutate(|min| {
+						*min = (*min).min(min_active_stake.into())
+					});
+				}
+
+				let next_status = match progress {
+					VoterProgress::Exhausted => SnapshotStatus::Consumed,
+					VoterProgress::After(last) => SnapshotStatus::Ongoing(last),
+					VoterProgress::Stalled => status,
+				};
+				(voters, next_status)
+			},
+			None => (Vec::new(), SnapshotStatus::Consumed),
+		};
+
+		if page.is_zero() {
+			// last page of the snapshot, the next one starts over.
+			VoterSnapshotStatus::<T>::kill();
+		} else {
+			VoterSnapshotStatus::<T>::put(next_status);
+		}
+
+		Ok(voters)
+	}
+
+	/// Collect the voters for the npos election from `sorted_voters`, within `bounds`.
+	///
+	/// Returns the voters, the minimum active nominator stake among them (`VoteWeight::MAX` if
+	/// none) and how far `sorted_voters` was iterated.
+	fn do_get_npos_voters(
+		bounds: DataProviderBounds,
+		mut sorted_voters: Box<dyn Iterator<Item = T::AccountId>>,
+	) -> (Vec<VoterOf<Self>>, VoteWeight, VoterProgress<T::AccountId>) {
 		let mut voters_size_tracker: StaticTracker<Self> = StaticTracker::default();
 
 		let final_predicted_len = {
@@ -834,8 +1185,8 @@ impl<T: Config> Pallet<T> {
 		let mut validators_taken = 0u32;
 		let mut nominators_taken = 0u32;
 		let mut min_active_stake = u64::MAX;
+		let mut progress = VoterProgress::Stalled;
 
-		let mut sorted_voters = T::VoterList::iter();
 		while all_voters.len() < final_predicted_len as usize &&
 			voters_seen < (NPOS_MAX_ITERATIONS_COEFFICIENT * final_predicted_len as u32)
 		{
@@ -844,13 +1195,17 @@ impl<T: Config> Pallet<T> {
 					voters_seen.saturating_inc();
 					voter
 				},
-				None => break,
+				None => {
+					progress = VoterProgress::Exhausted;
+					break
+				},
 			};
 
 			let voter_weight = weight_of(&voter);
 			// if voter weight is zero, do not consider this voter for the snapshot.
 			if voter_weight.is_zero() {
 				log!(debug, "voter's active balance is 0. skip this voter.");
+				progress = VoterProgress::After(voter);
 				continue
 			}
 
@@ -869,10 +1224,12 @@ impl<T: Config> Pallet<T> {
 						break
 					}
 
+					progress = VoterProgress::After(voter.0.clone());
 					all_voters.push(voter);
 					nominators_taken.saturating_inc();
 				} else {
 					// technically should never happen, but not much we can do about it.
+					progress = VoterProgress::After(voter);
 				}
 				min_active_stake =
 					if voter_weight < min_active_stake { voter_weight } else { min_active_stake };
@@ -895,6 +1252,7 @@ impl<T: Config> Pallet<T> {
 				}
 				all_voters.push(self_vote);
 				validators_taken.saturating_inc();
+				progress = VoterProgress::After(voter);
 			} else {
 				// this can only happen if: 1. there a bug in the bags-list (or whatever is the
 				// sorted list) logic and the state of the two pallets is no longer compatible, or
@@ -905,6 +1263,7 @@ impl<T: Config> Pallet<T> {
 				    "DEFENSIVE: invalid item in `VoterList`: {:?}, this nominator probably has too many nominations now",
                     voter,
                 );
+				progress = VoterProgress::After(voter);
 			}
 		}
 
@@ -913,11 +1272,6 @@ impl<T: Config> Pallet<T> {
 
 		Self::register_weight(T::WeightInfo::get_npos_voters(validators_taken, nominators_taken));
 
-		let min_active_stake: T::CurrencyBalance =
-			if all_voters.is_empty() { Zero::zero() } else { min_active_stake.into() };
-
-		MinimumActiveStake::<T>::put(min_active_stake);
-
 		log!(
 			info,
 			"generated {} npos voters, {} from validators and {} nominators",
@@ -926,7 +1280,7 @@ impl<T: Config> Pallet<T> {
 			nominators_taken
 		);
 
-		all_voters
+		(all_voters, min_active_stake, progress)
 	}
 
 	/// Get the targets for an upcoming npos election.
@@ -1131,6 +1485,20 @@ impl<T: Config> ElectionDataProvider for Pallet<T> {
 		Ok(voters)
 	}
 
+	fn electing_voters_paged(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<VoterOf<Self>>> {
+		let voters = Self::get_npos_voters_paged(bounds, page)?;
+
+		debug_assert!(!bounds.exhausted(
+			SizeBound(voters.encoded_size() as u32).into(),
+			CountBound(voters.len() as u32).into()
+		));
+
+		Ok(voters)
+	}
+
 	fn electable_targets(bounds: DataProviderBounds) -> data_provider::Result<Vec<T::AccountId>> {
 		let targets = Self::get_npos_targets(bounds);
 
@@ -1176,9 +1544,16 @@ impl<T: Config> ElectionDataProvider for Pallet<T> {
 				.into(),
 		};
 
-		now.saturating_add(
+		let planning_block = now.saturating_add(
 			until_this_session_end.saturating_add(sessions_left.saturating_mul(session_length)),
-		)
+		);
+
+		// A paged election result is fetched one page per block, ending right before the new era
+		// is planned.
+		match Self::election_pages() {
+			pages if pages > 1 => planning_block.saturating_sub(pages.into()),
+			_ => planning_block,
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1505,7 +1880,7 @@ impl<T: Config> ScoreProvider<T::AccountId> for Pallet<T> {
 		// also, we play a trick to make sure that a issuance based-`CurrencyToVote` behaves well:
 		// This will make sure that total issuance is zero, thus the currency to vote will be a 1-1
 		// conversion.
-		let imbalance = T::Currency::burn(T::Currency::total_issuance());
+		let imbalance = T::Currency::burn(asset::total_issuance::<T>());
 		// kinda ugly, but gets the job done. The fact that this works here is a HUGE exception.
 		// Don't try this pattern in other places.
 		sp_std::mem::forget(imbalance);
@@ -1693,7 +2068,7 @@ impl<T: Config> StakingInterface for Pallet<T> {
 	}
 
 	fn bond_extra(who: &Self::AccountId, extra: Self::Balance) -> DispatchResult {
-		Self::bond_extra(RawOrigin::Signed(who.clone()).into(), extra)
+		Self::do_bond_extra(who, extra)
 	}
 
 	fn unbond(who: &Self::AccountId, value: Self::Balance) -> DispatchResult {
@@ -1732,6 +2107,23 @@ impl<T: Config> StakingInterface for Pallet<T> {
 		)
 	}
 
+	fn update_payee(stash: &Self::AccountId, reward_acc: &Self::AccountId) -> DispatchResult {
+		// Since virtual stakers are not allowed to compound their rewards as this pallet does not
+		// manage their locks, we do not allow reward account to be set same as stash. For
+		// external pallets that manage the virtual bond, they can claim rewards and re-bond them.
+		ensure!(
+			!Self::is_virtual_staker(stash) || stash != reward_acc,
+			Error::<T>::RewardDestinationRestricted
+		);
+
+		let ledger = Self::ledger(Stash(stash.clone()))?;
+		let _ = ledger
+			.set_payee(RewardDestination::Account(reward_acc.clone()))
+			.defensive_proof("ledger was retrieved from storage, thus its bonded; qed.")?;
+
+		Ok(())
+	}
+
 	fn nominate(who: &Self::AccountId, targets: Vec<Self::AccountId>) -> DispatchResult {
 		let ctrl = Self::bonded(who).ok_or(Error::<T>::NotStash)?;
 		let targets = targets.into_iter().map(T::Lookup::unlookup).collect::<Vec<_>>();
@@ -1787,6 +2179,10 @@ impl<T: Config> StakingInterface for Pallet<T> {
 		}
 	}
 
+	fn is_virtual_staker(who: &Self::AccountId) -> bool {
+		Self::is_virtual_staker(who)
+	}
+
 	sp_staking::runtime_benchmarks_enabled! {
 		fn nominations(who: &Self::AccountId) -> Option<Vec<T::AccountId>> {
 			Nominators::<T>::get(who).map(|n| n.targets.into_inner())
@@ -1815,6 +2211,40 @@ impl<T: Config> StakingInterface for Pallet<T> {
 	}
 }
 
+impl<T: Config> StakingUnchecked for Pallet<T> {
+	fn migrate_to_virtual_staker(who: &Self::AccountId) {
+		let _ = asset::kill_stake::<T>(who).defensive();
+		VirtualStakers::<T>::insert(who, ());
+	}
+
+	fn virtual_bond(
+		keyless_who: &Self::AccountId,
+		value: Self::Balance,
+		payee: &Self::AccountId,
+	) -> DispatchResult {
+		if StakingLedger::<T>::is_bonded(StakingAccount::Stash(keyless_who.clone())) {
+			return Err(Error::<T>::AlreadyBonded.into())
+		}
+
+		// Virtual stakers can not compound their rewards since this pallet does not manage their
+		// funds, hence the rewards must be paid to a different account.
+		ensure!(keyless_who != payee, Error::<T>::RewardDestinationRestricted);
+
+		// mark this pallet as consumer of `who`.
+		frame_system::Pallet::<T>::inc_consumers(keyless_who).map_err(|_| Error::<T>::BadState)?;
+
+		// mark who as a virtual staker.
+		VirtualStakers::<T>::insert(keyless_who, ());
+
+		Self::deposit_event(Event::<T>::Bonded { stash: keyless_who.clone(), amount: value });
+		let ledger = StakingLedger::<T>::new(keyless_who.clone(), value);
+
+		ledger.bond(RewardDestination::Account(payee.clone()))?;
+
+		Ok(())
+	}
+}
+
 #[cfg(any(test, feature = "try-runtime"))]
 impl<T: Config> Pallet<T> {
 	pub(crate) fn do_try_state(_: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
@@ -1825,6 +2255,7 @@ impl<T: Config> Pallet<T> {
 		);
 
 		Self::check_payees()?;
+		Self::check_virtual_stakers()?;
 		Self::check_nominators()?;
 		Self::check_exposures()?;
 		Self::check_paged_exposures()?;
@@ -1849,6 +2280,24 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Invariants:
+	/// * Virtual stakers must be bonded.
+	/// * Virtual stakers must not receive their rewards into their own account.
+	fn check_virtual_stakers() -> Result<(), TryRuntimeError> {
+		for stash in VirtualStakers::<T>::iter_keys() {
+			ensure!(Bonded::<T>::contains_key(&stash), "virtual staker is not bonded");
+			ensure!(
+				!matches!(
+					Payee::<T>::get(&stash),
+					Some(RewardDestination::Staked) | Some(RewardDestination::Stash)
+				),
+				"virtual staker must not be paid into its own account"
+			);
+		}
+
+		Ok(())
+	}
+
 	fn check_count() -> Result<(), TryRuntimeError> {
 		ensure!(
 			<T as Config>::VoterList::count() ==
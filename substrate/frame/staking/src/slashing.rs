@@ -50,7 +50,7 @@
 //! Based on research at <https://research.web3.foundation/en/latest/polkadot/slashing/npos.html>
 
 use crate::{
-	BalanceOf, Config, Error, Exposure, NegativeImbalanceOf, NominatorSlashInEra,
+	asset, BalanceOf, Config, Error, Exposure, NegativeImbalanceOf, NominatorSlashInEra,
 	OffendingValidators, Pallet, Perbill, SessionInterface, SpanSlash, UnappliedSlash,
 	ValidatorSlashInEra,
 };
@@ -608,15 +608,19 @@ pub fn do_slash<T: Config>(
 			Err(_) => return, // nothing to do.
 		};
 
-	let value = ledger.slash(value, T::Currency::minimum_balance(), slash_era);
+	let value = ledger.slash(value, asset::existential_deposit::<T>(), slash_era);
 
 	if !value.is_zero() {
-		let (imbalance, missing) = T::Currency::slash(stash, value);
-		slashed_imbalance.subsume(imbalance);
-
-		if !missing.is_zero() {
-			// deduct overslash from the reward payout
-			*reward_payout = reward_payout.saturating_sub(missing);
+		// Skip slashing the funds of virtual stakers. The pallets managing them are notified of
+		// the slash through `OnStakingUpdate::on_slash` and apply it to their funds.
+		if !Pallet::<T>::is_virtual_staker(stash) {
+			let (imbalance, missing) = asset::slash::<T>(stash, value);
+			slashed_imbalance.subsume(imbalance);
+
+			if !missing.is_zero() {
+				// deduct overslash from the reward payout
+				*reward_payout = reward_payout.saturating_sub(missing);
+			}
 		}
 
 		let _ = ledger
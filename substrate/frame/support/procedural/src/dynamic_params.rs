@@ -0,0 +1,458 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `dynamic_params` and `dynamic_pallet_params` attribute macros.
+
+use frame_support_procedural_tools::generate_access_from_frame_or_crate;
+use inflector::Inflector;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+	parse::{Parse, ParseStream},
+	spanned::Spanned,
+	Attribute, Error, Expr, Ident, Item, ItemMod, ItemStatic, Path, Result, StaticMutability,
+	Token, Type, Visibility,
+};
+
+/// Arguments of the `dynamic_params` attribute: `AggregateName[, ParameterStore]`.
+struct DynamicParamsAttr {
+	aggregate_name: Ident,
+	parameter_store: Option<Path>,
+}
+
+impl Parse for DynamicParamsAttr {
+	fn parse(input: ParseStream) -> Result<Self> {
+		let aggregate_name = input.parse()?;
+		let parameter_store = if input.peek(Token![,]) {
+			input.parse::<Token![,]>()?;
+			Some(input.parse()?)
+		} else {
+			None
+		};
+		if !input.is_empty() {
+			return Err(input.error("Unexpected tokens, expected `AggregateName[, ParameterStore]`"))
+		}
+
+		Ok(Self { aggregate_name, parameter_store })
+	}
+}
+
+/// A single parameter declared as `static` item within a `dynamic_pallet_params` module.
+struct Parameter {
+	attrs: Vec<Attribute>,
+	codec_attrs: Vec<Attribute>,
+	vis: Visibility,
+	name: Ident,
+	ty: Type,
+	default: Expr,
+}
+
+impl TryFrom<ItemStatic> for Parameter {
+	type Error = Error;
+
+	fn try_from(item: ItemStatic) -> Result<Self> {
+		if !matches!(item.mutability, StaticMutability::None) {
+			return Err(Error::new(item.mutability.span(), "Parameters cannot be `mut`"))
+		}
+		let (codec_attrs, attrs) = item.attrs.into_iter().partition(is_codec_attr);
+
+		Ok(Self {
+			attrs,
+			codec_attrs,
+			vis: item.vis,
+			name: item.ident,
+			ty: *item.ty,
+			default: *item.expr,
+		})
+	}
+}
+
+fn is_codec_attr(attr: &Attribute) -> bool {
+	attr.path().is_ident("codec")
+}
+
+fn is_attr(attr: &Attribute, name: &str) -> bool {
+	attr.path().segments.last().map_or(false, |s| s.ident == name)
+}
+
+/// Expand the `dynamic_params` attribute.
+///
+/// Forwards the parameter store to all inner `dynamic_pallet_params` modules and aggregates their
+/// key-value types into one runtime wide enum.
+pub fn dynamic_params(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+	let scrate = generate_access_from_frame_or_crate("frame-support")?;
+	let DynamicParamsAttr { aggregate_name, parameter_store } = syn::parse2(attr)?;
+	let mut item_mod = syn::parse2::<ItemMod>(item)?;
+
+	let Some((_, items)) = item_mod.content.as_mut() else {
+		return Err(Error::new(item_mod.span(), "Expected an inline module"))
+	};
+
+	let mut pallet_params = Vec::new();
+	for item in items.iter_mut() {
+		let Item::Mod(inner) = item else { continue };
+		if !inner.attrs.iter().any(|a| is_attr(a, "dynamic_pallet_params")) {
+			continue
+		}
+
+		let (codec_attrs, attrs): (Vec<_>, Vec<_>) = inner.attrs.drain(..).partition(is_codec_attr);
+		inner.attrs = attrs;
+
+		let pallet_attr = inner
+			.attrs
+			.iter_mut()
+			.find(|a| is_attr(a, "dynamic_pallet_params"))
+			.expect("Checked to be present above; qed");
+		if matches!(pallet_attr.meta, syn::Meta::Path(_)) {
+			let Some(parameter_store) = parameter_store.as_ref() else {
+				return Err(Error::new(
+					pallet_attr.span(),
+					"No parameter store was provided; set it either on `dynamic_params` or here",
+				))
+			};
+			*pallet_attr = syn::parse_quote!(
+				#[#scrate::dynamic_params::dynamic_pallet_params(#parameter_store)]
+			);
+		}
+
+		pallet_params.push((inner.ident.clone(), codec_attrs));
+	}
+
+	let vis = &item_mod.vis;
+	let mod_name = &item_mod.ident;
+	let key_name = format_ident!("{}Key", aggregate_name);
+	let value_name = format_ident!("{}Value", aggregate_name);
+
+	let variants = pallet_params
+		.iter()
+		.map(|(name, _)| Ident::new(&name.to_string().to_pascal_case(), name.span()))
+		.collect::<Vec<_>>();
+	let codec_attrs = pallet_params.iter().map(|(_, attrs)| attrs).collect::<Vec<_>>();
+	let modules = pallet_params.iter().map(|(name, _)| name).collect::<Vec<_>>();
+
+	let benchmarking_default = variants.first().map(|variant| {
+		quote! {
+			#[cfg(feature = "runtime-benchmarks")]
+			impl Default for #aggregate_name {
+				fn default() -> Self {
+					Self::#variant(Default::default())
+				}
+			}
+		}
+	});
+
+	Ok(quote! {
+		#item_mod
+
+		/// The aggregated key-value type of all dynamic parameters in the runtime.
+		#[derive(
+			Clone, PartialEq, Eq,
+			#scrate::__private::codec::Encode,
+			#scrate::__private::codec::Decode,
+			#scrate::__private::codec::MaxEncodedLen,
+			#scrate::__private::scale_info::TypeInfo,
+			#scrate::__private::RuntimeDebug,
+		)]
+		#vis enum #aggregate_name {
+			#(
+				#( #codec_attrs )*
+				#variants(#mod_name::#modules::Parameters),
+			)*
+		}
+
+		/// The aggregated key type of all dynamic parameters in the runtime.
+		#[derive(
+			Clone, PartialEq, Eq,
+			#scrate::__private::codec::Encode,
+			#scrate::__private::codec::Decode,
+			#scrate::__private::codec::MaxEncodedLen,
+			#scrate::__private::scale_info::TypeInfo,
+			#scrate::__private::RuntimeDebug,
+		)]
+		#vis enum #key_name {
+			#(
+				#( #codec_attrs )*
+				#variants(#mod_name::#modules::ParametersKey),
+			)*
+		}
+
+		/// The aggregated value type of all dynamic parameters in the runtime.
+		#[derive(
+			Clone, PartialEq, Eq,
+			#scrate::__private::codec::Encode,
+			#scrate::__private::codec::Decode,
+			#scrate::__private::codec::MaxEncodedLen,
+			#scrate::__private::scale_info::TypeInfo,
+			#scrate::__private::RuntimeDebug,
+		)]
+		#vis enum #value_name {
+			#(
+				#( #codec_attrs )*
+				#variants(#mod_name::#modules::ParametersValue),
+			)*
+		}
+
+		impl #scrate::traits::dynamic_params::AggregatedKeyValue for #aggregate_name {
+			type Key = #key_name;
+			type Value = #value_name;
+
+			fn into_parts(self) -> (Self::Key, Option<Self::Value>) {
+				match self {
+					#(
+						#aggregate_name::#variants(parameter) => {
+							let (key, value) =
+								#scrate::traits::dynamic_params::AggregatedKeyValue::into_parts(parameter);
+							(#key_name::#variants(key), value.map(#value_name::#variants))
+						},
+					)*
+				}
+			}
+		}
+
+		#(
+			impl #scrate::traits::dynamic_params::FromKey<#mod_name::#modules::ParametersKey>
+				for #key_name
+			{
+				fn from_key(key: #mod_name::#modules::ParametersKey) -> Self {
+					#key_name::#variants(key)
+				}
+			}
+
+			impl #scrate::traits::dynamic_params::TryFromKey<#value_name>
+				for #mod_name::#modules::ParametersValue
+			{
+				type Error = ();
+
+				fn try_from_key(value: #value_name) -> Result<Self, Self::Error> {
+					#[allow(unreachable_patterns)]
+					match value {
+						#value_name::#variants(value) => Ok(value),
+						_ => Err(()),
+					}
+				}
+			}
+		)*
+
+		#benchmarking_default
+	})
+}
+
+/// Expand the `dynamic_pallet_params` attribute.
+///
+/// Turns every `static` item of the module into a parameter that implements `Get` by reading
+/// from the parameter store, falling back to its declared default value.
+pub fn dynamic_pallet_params(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+	let scrate = generate_access_from_frame_or_crate("frame-support")?;
+	if attr.is_empty() {
+		return Err(Error::new(
+			Span::call_site(),
+			"Expected the parameter store as argument, like \
+			`#[dynamic_pallet_params(pallet_parameters::Pallet::<Runtime>)]`",
+		))
+	}
+	let parameter_store = syn::parse2::<Path>(attr)?;
+	let mut item_mod = syn::parse2::<ItemMod>(item)?;
+
+	if let Some(attr) = item_mod.attrs.iter().find(|a| is_codec_attr(a)) {
+		return Err(Error::new(
+			attr.span(),
+			"`codec` attributes on parameter modules are only supported within `dynamic_params`",
+		))
+	}
+	let Some((_, items)) = item_mod.content.as_mut() else {
+		return Err(Error::new(item_mod.span(), "Expected an inline module"))
+	};
+
+	let mut params = Vec::new();
+	let mut others = Vec::new();
+	for item in items.drain(..) {
+		match item {
+			Item::Static(item) => params.push(Parameter::try_from(item)?),
+			other => others.push(other),
+		}
+	}
+	if params.is_empty() {
+		return Err(Error::new(item_mod.ident.span(), "Expected at least one parameter"))
+	}
+
+	let attrs = params.iter().map(|p| &p.attrs).collect::<Vec<_>>();
+	let codec_attrs = params.iter().map(|p| &p.codec_attrs).collect::<Vec<_>>();
+	let vis = params.iter().map(|p| &p.vis).collect::<Vec<_>>();
+	let names = params.iter().map(|p| &p.name).collect::<Vec<_>>();
+	let value_names = params.iter().map(|p| format_ident!("{}Value", p.name)).collect::<Vec<_>>();
+	let types = params.iter().map(|p| &p.ty).collect::<Vec<_>>();
+	let defaults = params.iter().map(|p| &p.default).collect::<Vec<_>>();
+	let (first_name, first_default) = (&names[0], &defaults[0]);
+
+	let mod_attrs = &item_mod.attrs;
+	let mod_vis = &item_mod.vis;
+	let mod_name = &item_mod.ident;
+	let dp = quote!(#scrate::traits::dynamic_params);
+
+	Ok(quote! {
+		#( #mod_attrs )*
+		#mod_vis mod #mod_name {
+			#( #others )*
+
+			/// The key-value type of all parameters in this module.
+			#[derive(
+				Clone, PartialEq, Eq,
+				#scrate::__private::codec::Encode,
+				#scrate::__private::codec::Decode,
+				#scrate::__private::codec::MaxEncodedLen,
+				#scrate::__private::scale_info::TypeInfo,
+				#scrate::__private::RuntimeDebug,
+			)]
+			pub enum Parameters {
+				#(
+					#( #codec_attrs )*
+					#names(#names, Option<#types>),
+				)*
+			}
+
+			/// The key type of all parameters in this module.
+			#[derive(
+				Clone, PartialEq, Eq,
+				#scrate::__private::codec::Encode,
+				#scrate::__private::codec::Decode,
+				#scrate::__private::codec::MaxEncodedLen,
+				#scrate::__private::scale_info::TypeInfo,
+				#scrate::__private::RuntimeDebug,
+			)]
+			pub enum ParametersKey {
+				#(
+					#( #codec_attrs )*
+					#names(#names),
+				)*
+			}
+
+			/// The value type of all parameters in this module.
+			#[derive(
+				Clone, PartialEq, Eq,
+				#scrate::__private::codec::Encode,
+				#scrate::__private::codec::Decode,
+				#scrate::__private::codec::MaxEncodedLen,
+				#scrate::__private::scale_info::TypeInfo,
+				#scrate::__private::RuntimeDebug,
+			)]
+			pub enum ParametersValue {
+				#(
+					#( #codec_attrs )*
+					#names(#types),
+				)*
+			}
+
+			impl #dp::AggregatedKeyValue for Parameters {
+				type Key = ParametersKey;
+				type Value = ParametersValue;
+
+				fn into_parts(self) -> (Self::Key, Option<Self::Value>) {
+					match self {
+						#(
+							Parameters::#names(key, value) => {
+								(ParametersKey::#names(key), value.map(ParametersValue::#names))
+							},
+						)*
+					}
+				}
+			}
+
+			#[cfg(feature = "runtime-benchmarks")]
+			impl Default for Parameters {
+				fn default() -> Self {
+					Parameters::#first_name(#first_name, Some(#first_default))
+				}
+			}
+
+			#(
+				#( #attrs )*
+				#[derive(
+					Clone, PartialEq, Eq,
+					#scrate::__private::codec::Encode,
+					#scrate::__private::codec::Decode,
+					#scrate::__private::codec::MaxEncodedLen,
+					#scrate::__private::scale_info::TypeInfo,
+					#scrate::__private::RuntimeDebug,
+				)]
+				#vis struct #names;
+
+				impl #scrate::traits::Get<#types> for #names {
+					fn get() -> #types {
+						<
+							#dp::ParameterStoreAdapter<#parameter_store, Parameters>
+								as #dp::ParameterStore<Parameters>
+						>::get::<#names>(#names).unwrap_or_else(|| #defaults)
+					}
+				}
+
+				impl #dp::Key for #names {
+					type Value = #types;
+					type WrappedValue = #value_names;
+				}
+
+				impl From<#names> for ParametersKey {
+					fn from(key: #names) -> Self {
+						ParametersKey::#names(key)
+					}
+				}
+
+				impl TryFrom<ParametersKey> for #names {
+					type Error = ();
+
+					fn try_from(key: ParametersKey) -> Result<Self, Self::Error> {
+						#[allow(unreachable_patterns)]
+						match key {
+							ParametersKey::#names(key) => Ok(key),
+							_ => Err(()),
+						}
+					}
+				}
+
+				#[doc(hidden)]
+				#[derive(
+					Clone, PartialEq, Eq,
+					#scrate::__private::RuntimeDebug,
+				)]
+				#vis struct #value_names(pub #types);
+
+				impl From<#value_names> for ParametersValue {
+					fn from(value: #value_names) -> Self {
+						ParametersValue::#names(value.0)
+					}
+				}
+
+				impl From<#value_names> for #types {
+					fn from(value: #value_names) -> Self {
+						value.0
+					}
+				}
+
+				impl TryFrom<ParametersValue> for #value_names {
+					type Error = ();
+
+					fn try_from(value: ParametersValue) -> Result<Self, Self::Error> {
+						#[allow(unreachable_patterns)]
+						match value {
+							ParametersValue::#names(value) => Ok(#value_names(value)),
+							_ => Err(()),
+						}
+					}
+				}
+			)*
+		}
+	})
+}
@@ -2758,7 +2758,9 @@ pub mod pallet_macros {
 	/// [`construct_runtime`](frame_support::construct_runtime).
 	///
 	/// Finally, the `RuntimeTask` can then used by a script or off-chain worker to create and
-	/// submit such tasks via an extrinsic defined in `frame_system` called `do_task`.
+	/// submit such tasks via an extrinsic defined in `frame_system` called `do_task`. A
+	/// runtime can have its off-chain worker submit all valid tasks as unsigned transactions
+	/// by calling `frame_system::offchain::submit_valid_tasks`.
 	///
 	/// ## Example
 	#[doc = docify::embed!("src/tests/tasks.rs", tasks_example)]
@@ -2767,6 +2769,59 @@ pub mod pallet_macros {
 	pub use frame_support_procedural::tasks_experimental;
 }
 
+/// Contains macros to declare dynamic parameters that can be changed at runtime.
+///
+/// The parameters are stored by a
+/// [`RuntimeParameterStore`](traits::dynamic_params::RuntimeParameterStore), usually
+/// `pallet-parameters`, and can be read through [`Get`](traits::Get) just like any static
+/// parameter.
+pub mod dynamic_params {
+	/// Declare a module that contains dynamic parameters.
+	///
+	/// The attribute takes the name of the aggregated key-value enum that is generated next to
+	/// the module, commonly `RuntimeParameters`. The optional second argument is the
+	/// parameter store that is forwarded to all inner [`dynamic_pallet_params`] modules.
+	///
+	/// Every inner module is turned into a variant of the aggregated enum, named after the
+	/// module in `PascalCase` and encoded with its `#[codec(index = ..)]` attribute. Next to
+	/// the key-value enum, a `RuntimeParametersKey` and `RuntimeParametersValue` enum are
+	/// generated that are used as storage key and value by the parameter store.
+	///
+	/// ## Example
+	///
+	/// ```ignore
+	/// #[dynamic_params(RuntimeParameters, pallet_parameters::Pallet::<Runtime>)]
+	/// pub mod dynamic_params {
+	/// 	use super::*;
+	///
+	/// 	#[dynamic_pallet_params]
+	/// 	#[codec(index = 0)]
+	/// 	pub mod storage {
+	/// 		use super::*;
+	///
+	/// 		/// Configures the base deposit of storing some data.
+	/// 		#[codec(index = 0)]
+	/// 		pub static BaseDeposit: Balance = 1 * DOLLARS;
+	/// 	}
+	/// }
+	///
+	/// // `BaseDeposit` can now be used anywhere a `Get<Balance>` is expected.
+	/// type Deposit = dynamic_params::storage::BaseDeposit;
+	/// ```
+	pub use frame_support_procedural::dynamic_params;
+
+	/// Declare a module of dynamic parameters.
+	///
+	/// Each `static` item in the module is converted into a unit struct of the same name that
+	/// implements [`Get`](crate::traits::Get) by reading it from the parameter store and
+	/// falling back to the declared default. The module additionally contains the
+	/// `Parameters`, `ParametersKey` and `ParametersValue` enums of its parameters.
+	///
+	/// Takes the parameter store as argument, unless it is used within a [`dynamic_params`]
+	/// module that already provides one.
+	pub use frame_support_procedural::dynamic_pallet_params;
+}
+
 #[deprecated(note = "Will be removed after July 2023; Use `sp_runtime::traits` directly instead.")]
 pub mod error {
 	#[doc(hidden)]
@@ -16,13 +16,28 @@
 // limitations under the License.
 
 use crate::{
-	traits::{GetStorageVersion, NoStorageVersionSet, PalletInfoAccess, StorageVersion},
-	weights::{RuntimeDbWeight, Weight},
+	storage::transactional::with_transaction,
+	traits::{
+		fungible,
+		tokens::{Fortitude, Preservation},
+		GetStorageVersion, LockIdentifier, LockableCurrency, NamedReservableCurrency,
+		NoStorageVersionSet, PalletInfoAccess, ReservableCurrency, StorageVersion,
+	},
+	weights::{RuntimeDbWeight, Weight, WeightMeter},
 };
+use codec::{Decode, Encode, FullCodec, MaxEncodedLen};
 use impl_trait_for_tuples::impl_for_tuples;
+use scale_info::TypeInfo;
 use sp_core::Get;
 use sp_io::{hashing::twox_128, storage::clear_prefix, KillStorageResult};
-use sp_std::marker::PhantomData;
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	DispatchError, DispatchResult, TransactionOutcome,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
 
 /// Handles storage migration pallet versioning.
 ///
@@ -344,3 +359,473 @@ impl<P: Get<&'static str>, DbWeight: Get<RuntimeDbWeight>> frame_support::traits
 		Ok(())
 	}
 }
+
+/// The outcome of moving the funds of an account from a lock or reserve to a freeze or hold.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CurrencyMigrationOutcome<Balance> {
+	/// The amount which is now frozen or on hold.
+	pub migrated: Balance,
+	/// The amount which could not be put on hold and was left free instead, e.g. because the
+	/// account would otherwise have dropped below the existential deposit.
+	pub dust: Balance,
+}
+
+/// Replace the lock `id` on the funds of `who` by a freeze of `amount` for `reason`.
+///
+/// Like locks, freezes of different reasons overlap, so the whole `amount` is always frozen. If
+/// a freeze for `reason` already exists, it is extended to `amount`. If the freeze cannot be
+/// created, e.g. because `who` already has the maximum number of freezes, the lock is kept and
+/// the error is returned.
+///
+/// The amount of a lock cannot be read through [`LockableCurrency`], so it has to be provided by
+/// the caller, which usually tracks it in its own storage.
+pub fn migrate_lock_to_freeze<AccountId, Old, New>(
+	id: LockIdentifier,
+	reason: &New::Id,
+	who: &AccountId,
+	amount: New::Balance,
+) -> DispatchResult
+where
+	Old: LockableCurrency<AccountId>,
+	New: fungible::MutateFreeze<AccountId>,
+{
+	with_transaction(|| {
+		Old::remove_lock(id, who);
+		match New::extend_freeze(reason, who, amount) {
+			Ok(()) => TransactionOutcome::Commit(Ok(())),
+			Err(err) => TransactionOutcome::Rollback(Err(err)),
+		}
+	})
+}
+
+/// Replace the lock `id` on the funds of `who` by a hold of `amount` for `reason`.
+///
+/// As opposed to locked funds, held funds are no longer free, and an account must keep the
+/// existential deposit free to stay alive. Whatever cannot be put on hold is left free and
+/// reported as [`CurrencyMigrationOutcome::dust`]. If the hold cannot be created, the lock is
+/// kept and the error is returned.
+pub fn migrate_lock_to_hold<AccountId, Old, New>(
+	id: LockIdentifier,
+	reason: &New::Reason,
+	who: &AccountId,
+	amount: New::Balance,
+) -> Result<CurrencyMigrationOutcome<New::Balance>, DispatchError>
+where
+	Old: LockableCurrency<AccountId>,
+	New: fungible::MutateHold<AccountId>,
+{
+	with_transaction(|| {
+		Old::remove_lock(id, who);
+		hold_up_to::<AccountId, New>(reason, who, amount)
+	})
+}
+
+/// Move `amount` of the reserved funds of `who` on hold for `reason`.
+///
+/// If less than `amount` is reserved, only the reserved funds are moved. Whatever cannot be put on
+/// hold is left free and reported as [`CurrencyMigrationOutcome::dust`]. If the hold cannot be
+/// created, the funds stay reserved and the error is returned.
+pub fn migrate_reserve_to_hold<AccountId, Old, New>(
+	reason: &New::Reason,
+	who: &AccountId,
+	amount: New::Balance,
+) -> Result<CurrencyMigrationOutcome<New::Balance>, DispatchError>
+where
+	Old: ReservableCurrency<AccountId, Balance = New::Balance>,
+	New: fungible::MutateHold<AccountId>,
+{
+	with_transaction(|| {
+		let missing = Old::unreserve(who, amount);
+		hold_up_to::<AccountId, New>(reason, who, amount.saturating_sub(missing))
+	})
+}
+
+/// Move all funds of `who` reserved under the name `id` on hold for `reason`.
+///
+/// Behaves like [`migrate_reserve_to_hold`], with the amount being the whole named reserve.
+pub fn migrate_named_reserve_to_hold<AccountId, Old, New>(
+	id: &Old::ReserveIdentifier,
+	reason: &New::Reason,
+	who: &AccountId,
+) -> Result<CurrencyMigrationOutcome<New::Balance>, DispatchError>
+where
+	Old: NamedReservableCurrency<AccountId, Balance = New::Balance>,
+	New: fungible::MutateHold<AccountId>,
+{
+	with_transaction(|| {
+		let amount = Old::reserved_balance_named(id, who);
+		let missing = Old::unreserve_named(id, who, amount);
+		hold_up_to::<AccountId, New>(reason, who, amount.saturating_sub(missing))
+	})
+}
+
+/// Put as much as possible of `amount` of the free funds of `who` on hold for `reason`, rolling
+/// back on failure.
+fn hold_up_to<AccountId, New: fungible::MutateHold<AccountId>>(
+	reason: &New::Reason,
+	who: &AccountId,
+	amount: New::Balance,
+) -> TransactionOutcome<Result<CurrencyMigrationOutcome<New::Balance>, DispatchError>> {
+	let holdable = amount.min(New::reducible_balance(who, Preservation::Protect, Fortitude::Force));
+	if !holdable.is_zero() {
+		if let Err(err) = New::hold(reason, who, holdable) {
+			return TransactionOutcome::Rollback(Err(err))
+		}
+	}
+	TransactionOutcome::Commit(Ok(CurrencyMigrationOutcome {
+		migrated: holdable,
+		dust: amount.saturating_sub(holdable),
+	}))
+}
+
+/// A migration that can proceed in multiple steps.
+///
+/// Each step of the migration is limited by a [`WeightMeter`] and returns a cursor that is passed
+/// back to the next step. Returning `Ok(None)` signals that the migration is complete. This allows
+/// to run migrations that do not fit into a single block, as opposed to
+/// [`OnRuntimeUpgrade`](crate::traits::OnRuntimeUpgrade) migrations.
+pub trait SteppedMigration {
+	/// The cursor type that stores the progress (aka. state) of this migration.
+	type Cursor: FullCodec + MaxEncodedLen;
+
+	/// The unique identifier type of this migration.
+	type Identifier: FullCodec + MaxEncodedLen;
+
+	/// The unique identifier of this migration.
+	///
+	/// If two migrations have the same identifier, then they are assumed to be identical.
+	fn id() -> Self::Identifier;
+
+	/// The maximum number of steps that this migration can take.
+	///
+	/// This can be used to enforce progress and prevent migrations becoming stuck forever. A
+	/// migration that exceeds its max steps is treated as failed. `None` means that there is no
+	/// limit.
+	fn max_steps() -> Option<u32> {
+		None
+	}
+
+	/// Try to migrate as much as possible with the given weight.
+	///
+	/// **ANY STORAGE CHANGES MUST BE ROLLED-BACK BY THE CALLER UPON ERROR.** This is necessary
+	/// since the caller cannot return a cursor in the error case. [`Self::transactional_step`] is
+	/// provided as convenience for a caller. A cursor of `None` implies that the migration is at
+	/// its end.
+	fn step(
+		cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError>;
+
+	/// Same as [`Self::step`], but rolls back pending changes in the error case.
+	fn transactional_step(
+		cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+		with_transaction(|| match Self::step(cursor, meter) {
+			Ok(cursor) => TransactionOutcome::Commit(Ok(Ok(cursor))),
+			Err(err) => TransactionOutcome::Rollback(Ok(Err(err))),
+		})
+		.map_err(|_: sp_runtime::DispatchError| SteppedMigrationError::Failed)?
+	}
+
+	/// Hook for testing that is run before the migration is started.
+	///
+	/// Returns some bytes which are passed into `post_upgrade` after the migration is completed.
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok(Vec::new())
+	}
+
+	/// Hook for testing that is run after the migration is completed.
+	///
+	/// Should be used to verify the state of the chain after the migration. The `state` parameter
+	/// is the return value from `pre_upgrade`.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		Ok(())
+	}
+}
+
+/// Error that can occur during a [`SteppedMigration`].
+#[derive(Debug, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, Clone, Copy)]
+pub enum SteppedMigrationError {
+	// Transient errors:
+	/// The remaining weight is not enough to do anything.
+	///
+	/// Can be resolved by calling with at least `required` weight. Note that calling it with
+	/// exactly `required` weight could cause it to not make any progress.
+	InsufficientWeight {
+		/// Amount of weight required to make progress.
+		required: Weight,
+	},
+	// Permanent errors:
+	/// The migration cannot decode its cursor and therefore not proceed.
+	///
+	/// This should not happen unless (1) the migration itself returned an invalid cursor in a
+	/// previous iteration, (2) the storage got corrupted or (3) there is a bug in the caller's
+	/// code.
+	InvalidCursor,
+	/// The migration encountered a permanent error and cannot continue.
+	Failed,
+}
+
+/// A queue of [`SteppedMigration`]s that are executed one after another.
+///
+/// Implemented for tuples of [`SteppedMigration`]s. The cursors and identifiers are passed around
+/// in their encoded form, such that the queue can be driven without knowing the concrete types.
+pub trait SteppedMigrations {
+	/// The number of migrations that `Self` aggregates.
+	fn len() -> u32;
+
+	/// The `n`th [`SteppedMigration::id`].
+	///
+	/// Is guaranteed to return `Some` if `n < Self::len()`.
+	fn nth_id(n: u32) -> Option<Vec<u8>>;
+
+	/// The [`SteppedMigration::max_steps`] of the `n`th migration.
+	///
+	/// Is guaranteed to return `Some` if `n < Self::len()`.
+	fn nth_max_steps(n: u32) -> Option<Option<u32>>;
+
+	/// Do a [`SteppedMigration::step`] on the `n`th migration.
+	///
+	/// Is guaranteed to return `Some` if `n < Self::len()`.
+	fn nth_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>>;
+
+	/// Do a [`SteppedMigration::transactional_step`] on the `n`th migration.
+	///
+	/// Is guaranteed to return `Some` if `n < Self::len()`.
+	fn nth_transactional_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>>;
+
+	/// Call the pre-upgrade hooks of the `n`th migration.
+	///
+	/// Is guaranteed to return `Some` if `n < Self::len()`.
+	#[cfg(feature = "try-runtime")]
+	fn nth_pre_upgrade(n: u32) -> Option<Result<Vec<u8>, TryRuntimeError>>;
+
+	/// Call the post-upgrade hooks of the `n`th migration.
+	///
+	/// Is guaranteed to return `Some` if `n < Self::len()`.
+	#[cfg(feature = "try-runtime")]
+	fn nth_post_upgrade(n: u32, state: Vec<u8>) -> Option<Result<(), TryRuntimeError>>;
+
+	/// The maximal encoded length across all cursors.
+	fn cursor_max_encoded_len() -> usize;
+
+	/// The maximal encoded length across all identifiers.
+	fn identifier_max_encoded_len() -> usize;
+}
+
+/// A step function of a [`SteppedMigration`], either `step` or `transactional_step`.
+type StepFn<M> = fn(
+	Option<<M as SteppedMigration>::Cursor>,
+	&mut WeightMeter,
+) -> Result<Option<<M as SteppedMigration>::Cursor>, SteppedMigrationError>;
+
+/// Decodes the cursor, runs `step` and re-encodes the resulting cursor.
+fn encoded_step<M: SteppedMigration>(
+	cursor: Option<Vec<u8>>,
+	meter: &mut WeightMeter,
+	step: StepFn<M>,
+) -> Result<Option<Vec<u8>>, SteppedMigrationError> {
+	let cursor = cursor
+		.map(|cursor| M::Cursor::decode(&mut &cursor[..]))
+		.transpose()
+		.map_err(|_| SteppedMigrationError::InvalidCursor)?;
+
+	step(cursor, meter).map(|cursor| cursor.map(|cursor| cursor.encode()))
+}
+
+#[impl_for_tuples(30)]
+#[tuple_types_custom_trait_bound(SteppedMigration)]
+impl SteppedMigrations for Tuple {
+	#[allow(unused_mut)]
+	fn len() -> u32 {
+		let mut len = 0u32;
+		for_tuples!( #( let _: fn() -> Option<u32> = Tuple::max_steps; len += 1; )* );
+		len
+	}
+
+	#[allow(unused_assignments, unused_mut, unused_variables)]
+	fn nth_id(n: u32) -> Option<Vec<u8>> {
+		let mut i = 0;
+		for_tuples!( #(
+			if i == n {
+				return Some(Tuple::id().encode())
+			}
+			i += 1;
+		)* );
+		None
+	}
+
+	#[allow(unused_assignments, unused_mut, unused_variables)]
+	fn nth_max_steps(n: u32) -> Option<Option<u32>> {
+		let mut i = 0;
+		for_tuples!( #(
+			if i == n {
+				return Some(Tuple::max_steps())
+			}
+			i += 1;
+		)* );
+		None
+	}
+
+	#[allow(unused_assignments, unused_mut, unused_variables)]
+	fn nth_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>> {
+		let mut i = 0;
+		for_tuples!( #(
+			if i == n {
+				return Some(encoded_step::<Tuple>(cursor, meter, Tuple::step))
+			}
+			i += 1;
+		)* );
+		None
+	}
+
+	#[allow(unused_assignments, unused_mut, unused_variables)]
+	fn nth_transactional_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>> {
+		let mut i = 0;
+		for_tuples!( #(
+			if i == n {
+				return Some(encoded_step::<Tuple>(cursor, meter, Tuple::transactional_step))
+			}
+			i += 1;
+		)* );
+		None
+	}
+
+	#[cfg(feature = "try-runtime")]
+	#[allow(unused_assignments, unused_mut, unused_variables)]
+	fn nth_pre_upgrade(n: u32) -> Option<Result<Vec<u8>, TryRuntimeError>> {
+		let mut i = 0;
+		for_tuples!( #(
+			if i == n {
+				return Some(Tuple::pre_upgrade())
+			}
+			i += 1;
+		)* );
+		None
+	}
+
+	#[cfg(feature = "try-runtime")]
+	#[allow(unused_assignments, unused_mut, unused_variables)]
+	fn nth_post_upgrade(n: u32, state: Vec<u8>) -> Option<Result<(), TryRuntimeError>> {
+		let mut i = 0;
+		for_tuples!( #(
+			if i == n {
+				return Some(Tuple::post_upgrade(state))
+			}
+			i += 1;
+		)* );
+		None
+	}
+
+	fn cursor_max_encoded_len() -> usize {
+		let mut max_len = 0;
+		for_tuples!( #( max_len = max_len.max(Tuple::Cursor::max_encoded_len()); )* );
+		max_len
+	}
+
+	fn identifier_max_encoded_len() -> usize {
+		let mut max_len = 0;
+		for_tuples!( #( max_len = max_len.max(Tuple::Identifier::max_encoded_len()); )* );
+		max_len
+	}
+}
+
+/// Executes multi-step migrations.
+///
+/// Used by `frame_executive` to step the migrations at the beginning of a block and to block
+/// extrinsics while the migrations are ongoing.
+pub trait MultiStepMigrator {
+	/// Hint for whether [`Self::step`] should be called.
+	fn ongoing() -> bool;
+
+	/// Do the next step in the MBM process.
+	///
+	/// Must gracefully handle the case that it is currently not upgrading.
+	fn step() -> Weight;
+}
+
+impl MultiStepMigrator for () {
+	fn ongoing() -> bool {
+		false
+	}
+
+	fn step() -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Notification handler for status updates regarding multi-step migrations.
+#[impl_for_tuples(8)]
+pub trait MigrationStatusHandler {
+	/// Notifies of the start of a runtime migration.
+	fn started() {}
+
+	/// Notifies of the completion of a runtime migration.
+	fn completed() {}
+}
+
+/// How to proceed after a runtime upgrade failed.
+///
+/// There is NO SANE DEFAULT HERE. All options are very dangerous and should be used with care.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailedMigrationHandling {
+	/// Resume extrinsic processing of the chain. This will not resume the upgrade.
+	///
+	/// This should be supplemented with additional measures to ensure that the broken chain
+	/// state does not get further messed up by user extrinsics.
+	ForceUnstuck,
+	/// Set the cursor to `Stuck`.
+	///
+	/// No further migrations are executed and no new upgrade can be started until governance
+	/// resets the cursor. Extrinsics are processed, such that governance is able to do so.
+	KeepStuck,
+	/// Don't do anything with the cursor and let the handler decide.
+	///
+	/// This can be useful in cases where the other two options would overwrite any changes that
+	/// were done by the handler to the cursor.
+	Ignore,
+}
+
+/// Handles a failed runtime upgrade.
+///
+/// Note that this is only called on a *hard* failure of a migration, i.e. not when a migration
+/// merely needs more weight or blocks to make progress.
+pub trait FailedMigrationHandler {
+	/// Infallibly handle a failed runtime migration.
+	///
+	/// Gets passed in the optional index of the migration in the batch that caused the failure.
+	/// Returning `None` means that no automatic handling should take place and the callee decides
+	/// in the implementation what to do.
+	fn failed(migration: Option<u32>) -> FailedMigrationHandling;
+}
+
+/// Do not do anything on a failed migration.
+///
+/// This is only useful for testing, since it leaves the migrations stuck until governance
+/// intervenes.
+pub struct FreezeChainOnFailedMigration;
+
+impl FailedMigrationHandler for FreezeChainOnFailedMigration {
+	fn failed(_migration: Option<u32>) -> FailedMigrationHandling {
+		FailedMigrationHandling::KeepStuck
+	}
+}
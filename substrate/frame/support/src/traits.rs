@@ -127,6 +127,8 @@ pub use tx_pause::{TransactionPause, TransactionPauseError};
 pub mod tasks;
 pub use tasks::Task;
 
+pub mod dynamic_params;
+
 #[cfg(feature = "try-runtime")]
 mod try_runtime;
 #[cfg(feature = "try-runtime")]
@@ -0,0 +1,169 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the currency migration helpers, such as [`migrate_lock_to_hold`].
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	assert_noop, assert_ok, construct_runtime, derive_impl,
+	migrations::{
+		migrate_lock_to_freeze, migrate_lock_to_hold, migrate_named_reserve_to_hold,
+		migrate_reserve_to_hold, CurrencyMigrationOutcome,
+	},
+	parameter_types,
+	traits::{
+		fungible, ConstU32, Currency, LockIdentifier, LockableCurrency, NamedReservableCurrency,
+		ReservableCurrency, VariantCount, WithdrawReasons,
+	},
+};
+use scale_info::TypeInfo;
+use sp_runtime::{BuildStorage, RuntimeDebug};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+const ID_1: LockIdentifier = *b"1       ";
+const ID_2: LockIdentifier = *b"2       ";
+
+#[derive(
+	Encode,
+	Decode,
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	MaxEncodedLen,
+	TypeInfo,
+	RuntimeDebug,
+)]
+pub enum TestId {
+	Foo,
+	Bar,
+	Baz,
+}
+
+impl VariantCount for TestId {
+	const VARIANT_COUNT: u32 = 3;
+}
+
+construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+parameter_types! {
+	pub static ExistentialDeposit: u64 = 1;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig as pallet_balances::DefaultConfig)]
+impl pallet_balances::Config for Test {
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type ReserveIdentifier = TestId;
+	type RuntimeHoldReason = TestId;
+	type RuntimeFreezeReason = TestId;
+	type FreezeIdentifier = TestId;
+	type MaxFreezes = ConstU32<2>;
+}
+
+fn new_test_ext(existential_deposit: u64) -> sp_io::TestExternalities {
+	ExistentialDeposit::set(existential_deposit);
+	let storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+#[test]
+fn migrate_lock_to_freeze_works() {
+	new_test_ext(1).execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 100);
+		Balances::set_lock(ID_1, &1, 60, WithdrawReasons::all());
+
+		assert_ok!(migrate_lock_to_freeze::<_, Balances, Balances>(ID_1, &TestId::Foo, &1, 60));
+		assert!(Balances::locks(&1).is_empty());
+		assert_eq!(<Balances as fungible::InspectFreeze<_>>::balance_frozen(&TestId::Foo, &1), 60);
+		assert_eq!(Balances::free_balance(1), 100);
+
+		// The lock is kept if the freeze cannot be created.
+		Balances::set_lock(ID_2, &1, 40, WithdrawReasons::all());
+		assert_ok!(<Balances as fungible::MutateFreeze<_>>::set_freeze(&TestId::Bar, &1, 10));
+		assert_noop!(
+			migrate_lock_to_freeze::<_, Balances, Balances>(ID_2, &TestId::Baz, &1, 40),
+			pallet_balances::Error::<Test>::TooManyFreezes
+		);
+		assert_eq!(Balances::locks(&1).len(), 1);
+	});
+}
+
+#[test]
+fn migrate_lock_to_hold_leaves_dust_free() {
+	new_test_ext(10).execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 100);
+		Balances::set_lock(ID_1, &1, 100, WithdrawReasons::all());
+
+		let outcome =
+			migrate_lock_to_hold::<_, Balances, Balances>(ID_1, &TestId::Foo, &1, 100).unwrap();
+		assert_eq!(outcome, CurrencyMigrationOutcome { migrated: 90, dust: 10 });
+		assert!(Balances::locks(&1).is_empty());
+		assert_eq!(<Balances as fungible::InspectHold<_>>::balance_on_hold(&TestId::Foo, &1), 90);
+		assert_eq!(Balances::free_balance(1), 10);
+	});
+}
+
+#[test]
+fn migrate_reserve_to_hold_works() {
+	new_test_ext(1).execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 100);
+		assert_ok!(Balances::reserve(&1, 50));
+
+		// Only the reserved funds are moved.
+		let outcome =
+			migrate_reserve_to_hold::<_, Balances, Balances>(&TestId::Foo, &1, 60).unwrap();
+		assert_eq!(outcome, CurrencyMigrationOutcome { migrated: 50, dust: 0 });
+		assert_eq!(<Balances as fungible::InspectHold<_>>::balance_on_hold(&TestId::Foo, &1), 50);
+		assert_eq!(Balances::free_balance(1), 50);
+	});
+}
+
+#[test]
+fn migrate_named_reserve_to_hold_works() {
+	new_test_ext(1).execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 100);
+		assert_ok!(Balances::reserve_named(&TestId::Bar, &1, 30));
+		assert_ok!(Balances::reserve_named(&TestId::Baz, &1, 20));
+
+		let outcome =
+			migrate_named_reserve_to_hold::<_, Balances, Balances>(&TestId::Bar, &TestId::Foo, &1)
+				.unwrap();
+		assert_eq!(outcome, CurrencyMigrationOutcome { migrated: 30, dust: 0 });
+		assert_eq!(Balances::reserved_balance_named(&TestId::Bar, &1), 0);
+		assert_eq!(Balances::reserved_balance_named(&TestId::Baz, &1), 20);
+		assert_eq!(<Balances as fungible::InspectHold<_>>::balance_on_hold(&TestId::Foo, &1), 30);
+		assert_eq!(Balances::free_balance(1), 50);
+	});
+}
@@ -691,11 +691,17 @@ pub mod pallet {
 			Ok(().into())
 		}
 
+		/// Run a valid `task`.
+		///
+		/// Can be dispatched signed, or unsigned as submitted by offchain workers through
+		/// [`offchain::submit_valid_tasks`].
 		#[cfg(feature = "experimental")]
 		#[pallet::call_index(8)]
 		#[pallet::weight(task.weight())]
 		pub fn do_task(origin: OriginFor<T>, task: T::RuntimeTask) -> DispatchResultWithPostInfo {
-			ensure_signed(origin)?;
+			if ensure_none(origin.clone()).is_err() {
+				ensure_signed(origin)?;
+			}
 
 			if !task.is_valid() {
 				return Err(Error::<T>::InvalidTask.into())
@@ -966,7 +972,24 @@ pub mod pallet {
 	#[pallet::validate_unsigned]
 	impl<T: Config> sp_runtime::traits::ValidateUnsigned for Pallet<T> {
 		type Call = Call<T>;
-		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		#[cfg_attr(not(feature = "experimental"), allow(unused_variables))]
+		fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			#[cfg(feature = "experimental")]
+			if let Call::do_task { ref task } = call {
+				// Nobody pays for tasks, so they are only accepted from the local node and never
+				// gossiped, otherwise any peer could fill up the pool for free.
+				let is_local =
+					matches!(source, TransactionSource::Local | TransactionSource::InBlock);
+				if is_local && task.is_valid() {
+					// Tasks get the lowest priority and only fill up what is left of a block.
+					// Providing the task itself deduplicates submissions.
+					return ValidTransaction::with_tag_prefix("DoTask")
+						.and_provides(task)
+						.propagate(false)
+						.build()
+				}
+				return Err(InvalidTransaction::Call.into())
+			}
 			if let Call::apply_authorized_upgrade { ref code } = call {
 				if let Ok(hash) = Self::validate_authorized_upgrade(&code[..]) {
 					return Ok(ValidTransaction {
@@ -100,6 +100,26 @@ where
 	}
 }
 
+/// Submit an unsigned [`do_task`](crate::Call::do_task) transaction for every valid task of the
+/// runtime.
+///
+/// Meant to be called from the runtime's offchain worker. Tasks that are already in the
+/// transaction pool are deduplicated by the `ValidateUnsigned` implementation of this pallet.
+#[cfg(feature = "experimental")]
+pub fn submit_valid_tasks<T>()
+where
+	T: crate::Config + SendTransactionTypes<crate::Call<T>>,
+{
+	use frame_support::traits::Task;
+
+	for task in T::RuntimeTask::iter().filter(|task| task.is_valid()) {
+		let call = crate::Call::<T>::do_task { task }.into();
+		if SubmitTransaction::<T, crate::Call<T>>::submit_unsigned_transaction(call).is_err() {
+			log::debug!(target: crate::LOG_TARGET, "Failed to submit task transaction");
+		}
+	}
+}
+
 /// Provides an implementation for signing transaction payloads.
 ///
 /// Keys used for signing are defined when instantiating the signer object.
@@ -25,7 +25,8 @@
 //! [`OnChargeAssetTransaction`] implementation analogous to [`pallet-transaction-payment`]. The
 //! included [`AssetConversionAdapter`] (implementing [`OnChargeAssetTransaction`]) determines the
 //! fee amount by converting the fee calculated by [`pallet-transaction-payment`] in the native
-//! asset into the amount required of the specified asset.
+//! asset into the amount required of the specified asset. It accepts any asset with a pool
+//! against the native asset and refunds overpaid fees in the asset used for the payment.
 //!
 //! ## Pallet API
 //!
@@ -34,6 +35,9 @@
 //! your `construct_runtime` macro, but only include this pallet's [`SignedExtension`]
 //! ([`ChargeAssetTxPayment`]).
 //!
+//! The [`AssetConversionTxPaymentApi`] runtime API allows to quote the fee of an extrinsic in a
+//! given asset.
+//!
 //! ## Terminology
 //!
 //! - Native Asset or Native Currency: The asset that a chain considers native, as in its default
@@ -44,9 +48,9 @@
 
 use sp_std::prelude::*;
 
-use codec::{Decode, Encode};
+use codec::{Codec, Decode, Encode};
 use frame_support::{
-	dispatch::{DispatchInfo, DispatchResult, PostDispatchInfo},
+	dispatch::{DispatchInfo, DispatchResult, GetDispatchInfo, PostDispatchInfo},
 	traits::{
 		fungibles::{Balanced, Inspect},
 		IsType,
@@ -144,6 +148,29 @@ pub mod pallet {
 		/// A swap of the refund in native currency back to asset failed.
 		AssetRefundFailed { native_amount_kept: BalanceOf<T> },
 	}
+
+	impl<T: Config> Pallet<T>
+	where
+		T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+		BalanceOf<T>: Into<ChargeAssetBalanceOf<T>>,
+	{
+		/// Quote the fee of `unchecked_extrinsic` with the encoded length `len` in `asset_id`.
+		///
+		/// Any tip is not included. Returns `None` if the fee can not be paid in `asset_id`, e.g.
+		/// because there is no pool against the native asset or it lacks the liquidity.
+		pub fn query_fee_in_asset<Extrinsic: sp_runtime::traits::Extrinsic + GetDispatchInfo>(
+			unchecked_extrinsic: Extrinsic,
+			len: u32,
+			asset_id: ChargeAssetIdOf<T>,
+		) -> Option<AssetBalanceOf<T>> {
+			let fee = pallet_transaction_payment::Pallet::<T>::query_info(unchecked_extrinsic, len)
+				.partial_fee;
+			if fee.is_zero() {
+				return Some(Zero::zero())
+			}
+			T::OnChargeAssetTransaction::quote_fee(asset_id, fee.into())
+		}
+	}
 }
 
 /// Require payment for transaction inclusion and optionally include a tip to gain additional
@@ -346,3 +373,18 @@ where
 		Ok(())
 	}
 }
+
+sp_api::decl_runtime_apis! {
+	/// This runtime api allows to quote transaction fees in assets.
+	pub trait AssetConversionTxPaymentApi<AssetId, AssetBalance>
+	where
+		AssetId: Codec,
+		AssetBalance: Codec,
+	{
+		/// Quote the fee of `uxt` with the encoded length `len` in `asset_id`, without any tip.
+		///
+		/// Returns `None` if the fee can not be paid in `asset_id`. Note that the price may have
+		/// changed by the time the extrinsic is executed.
+		fn query_fee_in_asset(uxt: Block::Extrinsic, len: u32, asset_id: AssetId) -> Option<AssetBalance>;
+	}
+}
@@ -199,6 +199,7 @@ impl pallet_assets::Config for Runtime {
 	type ApprovalDeposit = ConstU64<0>;
 	type StringLimit = ConstU32<20>;
 	type Freezer = ();
+	type Holder = ();
 	type Extra = ();
 	type CallbackHandle = ();
 	type WeightInfo = ();
@@ -224,6 +225,7 @@ impl pallet_assets::Config<Instance2> for Runtime {
 	type ApprovalDeposit = ConstU64<0>;
 	type StringLimit = ConstU32<50>;
 	type Freezer = ();
+	type Holder = ();
 	type Extra = ();
 	type WeightInfo = ();
 	type CallbackHandle = ();
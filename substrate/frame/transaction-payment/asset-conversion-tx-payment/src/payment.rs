@@ -22,7 +22,7 @@ use frame_support::{
 	traits::{fungible::Inspect, tokens::Balance},
 	unsigned::TransactionValidityError,
 };
-use pallet_asset_conversion::Swap;
+use pallet_asset_conversion::{QuotePrice, Swap};
 use sp_runtime::{
 	traits::{DispatchInfoOf, Get, PostDispatchInfoOf, Zero},
 	transaction_validity::InvalidTransaction,
@@ -71,20 +71,30 @@ pub trait OnChargeAssetTransaction<T: Config> {
 		asset_id: Self::AssetId,
 		initial_asset_consumed: AssetBalanceOf<T>,
 	) -> Result<AssetBalanceOf<T>, TransactionValidityError>;
+
+	/// Quote the amount of `asset_id` that paying a `fee` in native currency would cost.
+	///
+	/// Returns `None` if the fee can not be paid in `asset_id`.
+	fn quote_fee(asset_id: Self::AssetId, fee: Self::Balance) -> Option<AssetBalanceOf<T>>;
 }
 
 /// Implements the asset transaction for a balance to asset converter (implementing [`Swap`]).
 ///
 /// The converter is given the complete fee in terms of the asset used for the transaction.
+/// Any asset with a pool against the native asset `N` can be used. The fee is quoted before
+/// swapping, so that a missing pool or insufficient liquidity makes the transaction invalid
+/// instead of charging more than quoted. Refunds are swapped back into the asset used for the
+/// payment; if that swap is not possible, the refund is kept in native currency.
 pub struct AssetConversionAdapter<C, CON, N>(PhantomData<(C, CON, N)>);
 
 /// Default implementation for a runtime instantiating this pallet, an asset to native swapper.
 impl<T, C, CON, N> OnChargeAssetTransaction<T> for AssetConversionAdapter<C, CON, N>
 where
-	N: Get<CON::AssetKind>,
+	N: Get<T::AssetKind>,
 	T: Config,
 	C: Inspect<<T as frame_system::Config>::AccountId>,
-	CON: Swap<T::AccountId, Balance = BalanceOf<T>, AssetKind = T::AssetKind>,
+	CON: Swap<T::AccountId, Balance = BalanceOf<T>, AssetKind = T::AssetKind>
+		+ QuotePrice<Balance = BalanceOf<T>, AssetKind = T::AssetKind>,
 	BalanceOf<T>: Into<AssetBalanceOf<T>>,
 	T::AssetKind: From<AssetIdOf<T>>,
 	BalanceOf<T>: IsType<<C as Inspect<<T as frame_system::Config>::AccountId>>::Balance>,
@@ -115,11 +125,20 @@ where
 		let native_asset_required =
 			if C::balance(&who) >= ed.saturating_add(fee.into()) { fee } else { fee + ed.into() };
 
+		// quote the swap first, the swap itself will never take more than quoted
+		let asset_quoted = CON::quote_price_tokens_for_exact_tokens(
+			asset_id.clone().into(),
+			N::get(),
+			native_asset_required,
+			true,
+		)
+		.ok_or(InvalidTransaction::Payment)?;
+
 		let asset_consumed = CON::swap_tokens_for_exact_tokens(
 			who.clone(),
 			vec![asset_id.into(), N::get()],
 			native_asset_required,
-			None,
+			Some(asset_quoted),
 			who.clone(),
 			true,
 		)
@@ -166,19 +185,28 @@ where
 			// If this fails, the account might have dropped below the existential balance or there
 			// is not enough liquidity left in the pool. In that case we don't throw an error and
 			// the account will keep the native currency.
-			match CON::swap_exact_tokens_for_tokens(
-				who.clone(), // we already deposited the native to `who`
-				vec![
-					N::get(),        // we provide the native
-					asset_id.into(), // we want asset_id back
-				],
-				swap_back,   // amount of the native asset to convert to `asset_id`
-				None,        // no minimum amount back
-				who.clone(), // we will refund to `who`
-				false,       // no need to keep alive
+			let swapped_back = CON::quote_price_exact_tokens_for_tokens(
+				N::get(),
+				asset_id.clone().into(),
+				swap_back,
+				true,
 			)
-			.ok()
-			{
+			.filter(|quoted| !quoted.is_zero())
+			.and_then(|quoted| {
+				CON::swap_exact_tokens_for_tokens(
+					who.clone(), // we already deposited the native to `who`
+					vec![
+						N::get(),        // we provide the native
+						asset_id.into(), // we want asset_id back
+					],
+					swap_back,    // amount of the native asset to convert to `asset_id`
+					Some(quoted), // at least the quoted amount back
+					who.clone(),  // we will refund to `who`
+					false,        // no need to keep alive
+				)
+				.ok()
+			});
+			match swapped_back {
 				Some(acquired) => {
 					asset_refund = acquired
 						.try_into()
@@ -195,4 +223,13 @@ where
 		let actual_paid = initial_asset_consumed.saturating_sub(asset_refund);
 		Ok(actual_paid)
 	}
+
+	/// Quote the amount of `asset_id` to be swapped for `fee` in native currency.
+	///
+	/// Note: This does not include the existential deposit that is additionally acquired for
+	/// accounts that do not hold enough native currency.
+	fn quote_fee(asset_id: Self::AssetId, fee: BalanceOf<T>) -> Option<AssetBalanceOf<T>> {
+		CON::quote_price_tokens_for_exact_tokens(asset_id.into(), N::get(), fee, true)
+			.map(Into::into)
+	}
 }
@@ -27,8 +27,9 @@ use frame_support::{
 };
 use frame_system as system;
 use mock::{ExtrinsicBaseWeight, *};
+use pallet_asset_conversion::PoolLocator;
 use pallet_balances::Call as BalancesCall;
-use sp_runtime::{traits::StaticLookup, BuildStorage};
+use sp_runtime::{testing::TestXt, traits::StaticLookup, BuildStorage};
 
 const CALL: &<Runtime as frame_system::Config>::RuntimeCall =
 	&RuntimeCall::Balances(BalancesCall::transfer_allow_death { dest: 2, value: 69 });
@@ -718,3 +719,127 @@ fn post_dispatch_fee_is_zero_if_unsigned_pre_dispatch_fee_is_zero() {
 			assert_eq!(Assets::balance(asset_id, caller), balance);
 		});
 }
+
+#[test]
+fn asset_refund_is_kept_in_native_if_swap_back_fails() {
+	let base_weight = 5;
+	let balance_factor = 100;
+	ExtBuilder::default()
+		.balance_factor(balance_factor)
+		.base_weight(Weight::from_parts(base_weight, 0))
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			// create the asset
+			let asset_id = 1;
+			let min_balance = 2;
+			assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				asset_id.into(),
+				42,   /* owner */
+				true, /* is_sufficient */
+				min_balance,
+			));
+
+			setup_lp(asset_id, balance_factor);
+
+			// mint into the caller account
+			let caller = 2;
+			let beneficiary = <Runtime as system::Config>::Lookup::unlookup(caller);
+			let balance = 10000;
+
+			assert_ok!(Assets::mint_into(asset_id.into(), &beneficiary, balance));
+
+			let weight = 100;
+			let len = 10;
+			let fee_in_native = base_weight + weight + len as u64;
+			let fee_in_asset = AssetConversion::quote_price_tokens_for_exact_tokens(
+				NativeOrWithId::WithId(asset_id),
+				NativeOrWithId::Native,
+				fee_in_native,
+				true,
+			)
+			.unwrap();
+			let pre = ChargeAssetTxPayment::<Runtime>::from(0, Some(asset_id))
+				.pre_dispatch(&caller, CALL, &info_from_weight(WEIGHT_100), len)
+				.unwrap();
+			assert_eq!(Assets::balance(asset_id, caller), balance - fee_in_asset);
+
+			// the pool can not pay out the asset anymore
+			let pool_account =
+				<Runtime as pallet_asset_conversion::Config>::PoolLocator::pool_address(
+					&NativeOrWithId::Native,
+					&NativeOrWithId::WithId(asset_id),
+				)
+				.unwrap();
+			assert_ok!(Assets::freeze(RuntimeOrigin::signed(42), asset_id.into(), pool_account));
+
+			assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+				Some(pre),
+				&info_from_weight(WEIGHT_100),
+				&post_info_from_weight(WEIGHT_50),
+				len,
+				&Ok(())
+			));
+
+			// the refund is kept in native currency
+			let native_refund = weight - 50;
+			assert_eq!(FeeUnbalancedAmount::get(), fee_in_native - native_refund);
+			assert_eq!(Assets::balance(asset_id, caller), balance - fee_in_asset);
+			assert_eq!(Balances::free_balance(caller), 20 * balance_factor + native_refund);
+			System::assert_has_event(RuntimeEvent::AssetTxPayment(Event::AssetRefundFailed {
+				native_amount_kept: native_refund,
+			}));
+			System::assert_last_event(RuntimeEvent::AssetTxPayment(Event::AssetTxFeePaid {
+				who: caller,
+				actual_fee: fee_in_asset,
+				tip: 0,
+				asset_id,
+			}));
+		});
+}
+
+#[test]
+fn query_fee_in_asset_works() {
+	let base_weight = 5;
+	let balance_factor = 100;
+	ExtBuilder::default()
+		.balance_factor(balance_factor)
+		.base_weight(Weight::from_parts(base_weight, 0))
+		.build()
+		.execute_with(|| {
+			// create the asset
+			let asset_id = 1;
+			let min_balance = 2;
+			assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				asset_id.into(),
+				42,   /* owner */
+				true, /* is_sufficient */
+				min_balance,
+			));
+
+			let xt = TestXt::new(CALL.clone(), Some((1, ())));
+			let len = xt.encode().len() as u32;
+
+			// there is no pool for the asset yet
+			assert_eq!(AssetTxPayment::query_fee_in_asset(xt.clone(), len, asset_id), None);
+
+			setup_lp(asset_id, balance_factor);
+
+			let fee_in_native = TransactionPayment::query_info(xt.clone(), len).partial_fee;
+			let fee_in_asset = AssetConversion::quote_price_tokens_for_exact_tokens(
+				NativeOrWithId::WithId(asset_id),
+				NativeOrWithId::Native,
+				fee_in_native,
+				true,
+			);
+			assert!(fee_in_asset.is_some());
+			assert_eq!(AssetTxPayment::query_fee_in_asset(xt, len, asset_id), fee_in_asset);
+
+			// unsigned extrinsics do not pay fees
+			let unsigned_xt = TestXt::<_, ()>::new(CALL.clone(), None);
+			assert_eq!(AssetTxPayment::query_fee_in_asset(unsigned_xt, len, asset_id), Some(0));
+		});
+}
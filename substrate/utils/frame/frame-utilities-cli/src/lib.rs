@@ -18,5 +18,7 @@
 //! frame-system CLI utilities
 
 mod pallet_id;
+mod verified_storage;
 
 pub use pallet_id::PalletIdCmd;
+pub use verified_storage::VerifiedStorageCmd;
@@ -0,0 +1,87 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `verified-storage` subcommand
+
+use clap::Parser;
+use sc_cli::Error;
+use sc_light_state::{BlockNumberOps, LightStateClient};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor};
+use std::{fmt::Debug, str::FromStr};
+
+/// The `verified-storage` command
+#[derive(Debug, Parser)]
+#[command(
+	name = "verified-storage",
+	about = "Read a storage entry from an untrusted node and verify it against the finalized chain"
+)]
+pub struct VerifiedStorageCmd {
+	/// Name of the pallet of the storage entry
+	pallet: String,
+
+	/// Name of the storage entry
+	entry: String,
+
+	/// Hex encoded SCALE keys of a storage map, one per hasher of the entry
+	#[arg(long = "key", value_name = "HEX")]
+	keys: Vec<String>,
+
+	/// The RPC url of the node to read from
+	#[arg(long, default_value = "ws://127.0.0.1:9944")]
+	uri: String,
+
+	/// Hash of a trusted block to start following finality from
+	#[arg(long, value_name = "HASH")]
+	trusted_block: String,
+}
+
+impl VerifiedStorageCmd {
+	/// runs the command
+	pub async fn run<Block>(&self) -> Result<(), Error>
+	where
+		Block: BlockT,
+		NumberFor<Block>: BlockNumberOps,
+		<Block::Hash as FromStr>::Err: Debug,
+	{
+		let trusted_block = self.trusted_block.strip_prefix("0x").unwrap_or(&self.trusted_block);
+		let trusted_block = Block::Hash::from_str(trusted_block)
+			.map_err(|e| format!("Failed to parse block hash: {:?}", e))?;
+		let keys = self
+			.keys
+			.iter()
+			.map(|key| array_bytes::hex2bytes(key))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let mut client = LightStateClient::<Block>::from_trusted_block(&self.uri, trusted_block)
+			.await
+			.map_err(|e| e.to_string())?;
+		let finalized = client.sync().await.map_err(|e| e.to_string())?;
+		println!("Verified finalized block: #{} ({})", finalized.number(), finalized.hash());
+
+		let entry =
+			client.metadata().entry(&self.pallet, &self.entry).map_err(|e| e.to_string())?;
+		let key = entry.key(&keys).map_err(|e| e.to_string())?;
+		let value = client.storage(&[key]).await.map_err(|e| e.to_string())?.pop().flatten();
+
+		match value.as_deref().or(entry.default_value()) {
+			Some(value) => println!("{}", array_bytes::bytes2hex("0x", value)),
+			None => println!("None"),
+		}
+
+		Ok(())
+	}
+}